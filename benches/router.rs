@@ -0,0 +1,130 @@
+
+// ============================================================
+//  DAEGONICA SOFTWARE — benches/router.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: Router Benchmark
+//!
+//! **Purpose:**
+//! Measures route lookup time against route-table size and path length.
+//!
+//! **Context:**
+//! - Run with `cargo bench --bench router`; uses only the standard library.
+//!
+//! **Responsibilities:**
+//! - Builds routers with 10 to 10,000 routes mixing literal, `:param`, and `*catch-all` paths.
+//! - Times lookups for the last-registered route, which a linear scan would reach last.
+//! - Times lookups for increasingly deep paths on a fixed table.
+//! - Does NOT assert on timings; results are printed for comparison.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+use std::{hint::black_box, time::Instant};
+
+use server::{request::Request, response::Response, router::Router};
+
+/// # ITERATIONS
+///
+/// **Summary:**
+/// Lookups timed per measurement.
+const ITERATIONS: u32 = 20_000;
+
+/// # main
+///
+/// **Purpose:**
+/// Runs both benchmark series and prints nanoseconds per lookup.
+///
+/// **Parameters:**
+/// None.
+///
+/// **Returns:**
+/// None.
+///
+/// **Errors / Failures:**
+/// - Panics if a benchmark request fails to parse or a lookup misses.
+///
+/// **Examples:**
+/// ```rust
+/// // cargo bench --bench router
+/// main();
+/// ```
+fn main() {
+    println!("route table size (lookup of the last route):");
+    for routes in [10, 100, 1_000, 10_000] {
+        let router = table(routes);
+        let target = format!("/svc{}/items/42/detail", routes - 1);
+        println!("  {routes:>6} routes: {:>8.1} ns/lookup", time(&router, &target));
+    }
+
+    println!("path length (1,000 routes):");
+    let router = table(1_000).get("/deep/*rest", |_| Response::new(200, "OK"));
+    for depth in [1, 4, 16, 64] {
+        let target = format!("/deep{}", "/segment".repeat(depth));
+        println!("  {depth:>6} segments: {:>8.1} ns/lookup", time(&router, &target));
+    }
+}
+
+/// # table
+///
+/// **Purpose:**
+/// Builds a router with `count` groups of literal, parameter, and catch-all routes.
+///
+/// **Parameters:**
+/// - `count`: Number of route groups.
+///
+/// **Returns:**
+/// - The populated `Router`.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust
+/// let router = table(100);
+/// ```
+fn table(count: usize) -> Router {
+    (0..count).fold(Router::new(), |router, n| {
+        router
+            .get(&format!("/svc{n}"), |_| Response::new(200, "OK"))
+            .get(&format!("/svc{n}/items/:id/detail"), |_| Response::new(200, "OK"))
+            .get(&format!("/svc{n}/files/*path"), |_| Response::new(200, "OK"))
+    })
+}
+
+/// # time
+///
+/// **Purpose:**
+/// Measures the average time `router` takes to answer a `GET` for `target`.
+///
+/// **Parameters:**
+/// - `router`: Router under test.
+/// - `target`: Request target to look up.
+///
+/// **Returns:**
+/// - Mean nanoseconds per lookup.
+///
+/// **Errors / Failures:**
+/// - Panics if the request fails to parse or no route matches.
+///
+/// **Examples:**
+/// ```rust
+/// let ns = time(&router, "/svc9/items/1/detail");
+/// ```
+fn time(router: &Router, target: &str) -> f64 {
+    let raw = format!("GET {target} HTTP/1.1\r\nHost: bench\r\n\r\n");
+    let request = Request::read_from(&mut raw.as_bytes()).unwrap();
+    assert!(router.handle(&request).is_some(), "no route for {target}");
+
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        black_box(router.handle(black_box(&request)));
+    }
+    start.elapsed().as_nanos() as f64 / f64::from(ITERATIONS)
+}
@@ -0,0 +1,130 @@
+
+// ============================================================
+//  DAEGONICA SOFTWARE — benches/sendfile.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: Sendfile Benchmark
+//!
+//! **Purpose:**
+//! Compares the ways `StaticFiles` can send a large file: read into memory, memory-mapped, and
+//! copied inside the kernel with `sendfile(2)`.
+//!
+//! **Context:**
+//! - Run with `cargo bench --bench sendfile`; uses only the standard library and loopback TCP.
+//!
+//! **Responsibilities:**
+//! - Writes a 16 MiB file to a temporary directory and serves it repeatedly over one
+//!   connection per strategy, timing until the receiver has drained every byte.
+//! - Does NOT assert on timings; results are printed for comparison. On platforms without
+//!   `sendfile(2)` the last strategy falls back to chunked reads.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+use std::{
+    fs,
+    io,
+    net::{Shutdown, TcpListener, TcpStream},
+    thread,
+    time::Instant,
+};
+
+use server::{
+    static_files::StaticFiles,
+    timeouts::{ClientStream, Timeouts},
+};
+
+/// # FILE_BYTES
+///
+/// **Summary:**
+/// Size of the served file.
+const FILE_BYTES: usize = 16 * 1024 * 1024;
+
+/// # ROUNDS
+///
+/// **Summary:**
+/// Responses sent per strategy.
+const ROUNDS: u32 = 32;
+
+/// # main
+///
+/// **Purpose:**
+/// Times each strategy and prints throughput in MiB per second.
+///
+/// **Parameters:**
+/// None.
+///
+/// **Returns:**
+/// None.
+///
+/// **Errors / Failures:**
+/// - Panics if the temporary file cannot be written or the loopback transfer fails.
+///
+/// **Examples:**
+/// ```rust
+/// // cargo bench --bench sendfile
+/// main();
+/// ```
+fn main() {
+    let root = std::env::temp_dir().join(format!("server-bench-sendfile-{}", std::process::id()));
+    fs::create_dir_all(&root).unwrap();
+    let contents: Vec<u8> = (0..FILE_BYTES).map(|n| (n % 251) as u8).collect();
+    fs::write(root.join("large.bin"), contents).unwrap();
+
+    println!("{} MiB file, {ROUNDS} responses per strategy:", FILE_BYTES >> 20);
+    let strategies = [
+        ("read", StaticFiles::new(&root).large_file_threshold(None)),
+        ("mmap", StaticFiles::new(&root).sendfile(false)),
+        ("sendfile", StaticFiles::new(&root).sendfile(true)),
+    ];
+    for (name, files) in &strategies {
+        println!("  {name:>8}: {:>8.1} MiB/s", throughput(files));
+    }
+    fs::remove_dir_all(&root).unwrap();
+}
+
+/// # throughput
+///
+/// **Purpose:**
+/// Serves the large file `ROUNDS` times over a loopback connection.
+///
+/// **Parameters:**
+/// - `files`: Handler configured with the strategy under test.
+///
+/// **Returns:**
+/// - Body bytes delivered per second, in MiB.
+///
+/// **Errors / Failures:**
+/// - Panics if the connection fails or the receiver sees too few bytes.
+///
+/// **Examples:**
+/// ```rust
+/// let mib_per_second = throughput(&StaticFiles::new(&root));
+/// ```
+fn throughput(files: &StaticFiles) -> f64 {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    let receiver = thread::spawn(move || -> io::Result<u64> {
+        let (mut stream, _) = listener.accept()?;
+        io::copy(&mut stream, &mut io::sink())
+    });
+    let stream = TcpStream::connect(address).unwrap();
+
+    let start = Instant::now();
+    let mut client = ClientStream::new(&stream, Timeouts::new());
+    for _ in 0..ROUNDS {
+        files.serve("/large.bin").unwrap().send(&mut client).unwrap();
+    }
+    stream.shutdown(Shutdown::Write).unwrap();
+    let received = receiver.join().unwrap().unwrap();
+    let elapsed = start.elapsed().as_secs_f64();
+
+    assert!(received >= u64::from(ROUNDS) * FILE_BYTES as u64, "receiver got {received} bytes");
+    f64::from(ROUNDS) * FILE_BYTES as f64 / elapsed / f64::from(1 << 20)
+}
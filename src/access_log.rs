@@ -0,0 +1,248 @@
+
+// ============================================================
+//  DAEGONICA SOFTWARE — access_log.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: AccessLog
+//!
+//! **Purpose:**
+//! Appends one Common Log Format line per handled request to a log file or syslog, optionally
+//! followed by chosen request headers.
+//!
+//! **Context:**
+//! - Owned by each virtual host so every site can keep its own access log.
+//!
+//! **Responsibilities:**
+//! - Opens (or creates) the log file in append mode, rotating it as configured (see `LogFile`).
+//! - Serializes concurrent writes from worker threads.
+//! - Redacts logged headers on the `redact` list, so credentials never reach the file.
+//! - Does NOT buffer lines; each is written as soon as the request is logged.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+use std::{
+    io,
+    net::IpAddr,
+    path::Path,
+    sync::{Mutex, PoisonError},
+    time::SystemTime,
+};
+
+use crate::{
+    date::format_clf,
+    log_file::{LogFile, Rotation},
+    redact,
+    request::Request,
+    syslog::{Severity, Syslog},
+};
+
+
+/// # AccessLog
+///
+/// **Summary:**
+/// Thread-safe append-only access log.
+///
+/// **Fields:**
+/// - `sink`: Where lines are written.
+/// - `headers`: Request headers appended to each line as quoted fields, in order.
+///
+/// **Usage Example:**
+/// ```rust,no_run
+/// # use server::{access_log::AccessLog, request::Request};
+/// # let request = Request::read_from(&mut &b"GET / HTTP/1.1\r\n\r\n"[..])?;
+/// let log = AccessLog::open("logs/access.log")?.header("Referer").header("User-Agent");
+/// log.record(request.client, &request, Some(200), Some(512));
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub struct AccessLog {
+    sink: Sink,
+    headers: Vec<String>,
+}
+
+
+/// # Sink
+///
+/// **Summary:**
+/// Destination of access log lines.
+///
+/// **Variants:**
+/// - `File`: Log file opened for appending, with its rotation policy.
+/// - `Syslog`: Syslog daemon, one message per line at informational severity.
+enum Sink {
+    File(Mutex<LogFile>),
+    Syslog(Syslog),
+}
+
+
+impl AccessLog {
+
+    /// # open
+    ///
+    /// **Purpose:**
+    /// Opens `path` for appending, creating it if needed.
+    ///
+    /// **Parameters:**
+    /// - `path`: Log file location.
+    ///
+    /// **Returns:**
+    /// - A new `AccessLog` instance.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns an I/O error if the file cannot be opened.
+    ///
+    /// **Examples:**
+    /// ```rust,no_run
+    /// # use server::access_log::AccessLog;
+    /// let log = AccessLog::open("access.log")?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn open(path: impl AsRef<Path>) -> io::Result<AccessLog> {
+        Ok(AccessLog {
+            sink: Sink::File(Mutex::new(LogFile::open(path)?)),
+            headers: Vec::new(),
+        })
+    }
+
+    /// # syslog
+    ///
+    /// **Purpose:**
+    /// Sends access log lines to syslog instead of a file.
+    ///
+    /// **Parameters:**
+    /// - `syslog`: Syslog destination, typically with `msg_id("access")`.
+    ///
+    /// **Returns:**
+    /// - A new `AccessLog` instance.
+    ///
+    /// **Errors / Failures:**
+    /// None. Nothing is connected until the first line.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{access_log::AccessLog, syslog::Syslog};
+    /// let log = AccessLog::syslog(Syslog::unix("/dev/log").msg_id("access"));
+    /// ```
+    pub fn syslog(syslog: Syslog) -> AccessLog {
+        AccessLog {
+            sink: Sink::Syslog(syslog),
+            headers: Vec::new(),
+        }
+    }
+
+    /// # rotate
+    ///
+    /// **Purpose:**
+    /// Rotates the log by size or schedule, optionally compressing and pruning old files. Has no
+    /// effect on a syslog access log.
+    ///
+    /// **Parameters:**
+    /// - `rotation`: When to rotate and what to keep.
+    ///
+    /// **Returns:**
+    /// - The updated `AccessLog`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,no_run
+    /// # use server::{access_log::AccessLog, log_file::Rotation};
+    /// # use std::time::Duration;
+    /// let log = AccessLog::open("access.log")?.rotate(Rotation::new().every(Duration::from_secs(86_400)).compress(true));
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn rotate(mut self, rotation: Rotation) -> AccessLog {
+        if let Sink::File(file) = self.sink {
+            let file = file.into_inner().unwrap_or_else(PoisonError::into_inner);
+            self.sink = Sink::File(Mutex::new(file.rotate(rotation)));
+        }
+        self
+    }
+
+    /// # header
+    ///
+    /// **Purpose:**
+    /// Appends a request header to every line as a quoted field (`-` when absent), as the
+    /// Combined Log Format does for `Referer` and `User-Agent`. Values of sensitive headers (see
+    /// `redact`) are written as `[redacted]`.
+    ///
+    /// **Parameters:**
+    /// - `name`: Header name.
+    ///
+    /// **Returns:**
+    /// - The updated `AccessLog`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,no_run
+    /// # use server::access_log::AccessLog;
+    /// let log = AccessLog::open("access.log")?.header("User-Agent");
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn header(mut self, name: &str) -> AccessLog {
+        self.headers.push(name.to_string());
+        self
+    }
+
+    /// # record
+    ///
+    /// **Purpose:**
+    /// Writes one Common Log Format line for a request, followed by any configured headers.
+    ///
+    /// **Parameters:**
+    /// - `client`: Client address, if known.
+    /// - `request`: Handled request.
+    /// - `status`: Response status, or `None` if the response was streamed through unparsed.
+    /// - `bytes`: Response body size, or `None` if unknown.
+    ///
+    /// **Returns:**
+    /// None.
+    ///
+    /// **Errors / Failures:**
+    /// None. Write failures are ignored so logging never fails a request.
+    ///
+    /// **Examples:**
+    /// ```rust,no_run
+    /// # use server::{access_log::AccessLog, request::Request};
+    /// # let log = AccessLog::open("access.log")?;
+    /// # let request = Request::read_from(&mut &b"GET /missing HTTP/1.1\r\n\r\n"[..])?;
+    /// log.record(request.client, &request, Some(404), Some(0));
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn record(&self, client: Option<IpAddr>, request: &Request, status: Option<u16>, bytes: Option<usize>) {
+        let client = client.map(|ip| ip.to_string()).unwrap_or_else(|| "-".to_string());
+        let status = status.map(|s| s.to_string()).unwrap_or_else(|| "-".to_string());
+        let bytes = bytes.map(|b| b.to_string()).unwrap_or_else(|| "-".to_string());
+        let mut line = format!(
+            "{client} - - [{}] \"{}\" {status} {bytes}",
+            format_clf(SystemTime::now()),
+            request.request_line()
+        );
+        for name in &self.headers {
+            match request.headers.get(name) {
+                Some(value) => line.push_str(&format!(" \"{}\"", redact::value(name, value).replace('"', "\\\""))),
+                None => line.push_str(" \"-\""),
+            }
+        }
+        match &self.sink {
+            Sink::File(file) => {
+                line.push('\n');
+                if let Ok(mut file) = file.lock() {
+                    let _ = file.write(line.as_bytes());
+                }
+            }
+            Sink::Syslog(syslog) => {
+                let _ = syslog.send(Severity::Informational, &line);
+            }
+        }
+    }
+}
@@ -0,0 +1,425 @@
+
+// ============================================================
+//  DAEGONICA SOFTWARE — acl.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: ACL
+//!
+//! **Purpose:**
+//! Allows or denies requests by client address before any handler runs.
+//!
+//! **Context:**
+//! - Installed as router middleware with `Router::middleware(acl.middleware())`.
+//!
+//! **Responsibilities:**
+//! - Matches the client address against allow and deny ranges in a configurable precedence.
+//! - Uses the forwarded client address when the peer is a trusted proxy.
+//! - Applies per-route rule sets selected by path pattern.
+//! - Rejects refused clients with `403 Forbidden`.
+//! - Does NOT guard static files or unmatched paths, which never reach router middleware.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+use std::{io, net::IpAddr};
+
+use crate::{
+    glob::Glob,
+    ip::{self, Cidr},
+    request::Request,
+    response::Response,
+    router::Next,
+};
+
+
+/// # Precedence
+///
+/// **Summary:**
+/// Which list wins when an address is in both an allow and a deny range.
+///
+/// **Variants:**
+/// - `DenyFirst`: A matching deny range refuses the address even if an allow range matches.
+/// - `AllowFirst`: A matching allow range admits the address even if a deny range matches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Precedence {
+    DenyFirst,
+    AllowFirst,
+}
+
+
+/// # IpAcl
+///
+/// **Summary:**
+/// Address-based access rules, with optional per-route rule sets. An address that matches no
+/// range is refused if any allow range exists, and admitted otherwise.
+///
+/// **Fields:**
+/// - `allow`: Ranges that are admitted.
+/// - `deny`: Ranges that are refused.
+/// - `precedence`: Which list wins when both match.
+/// - `trusted`: Proxy ranges whose `X-Forwarded-For` entries are believed.
+/// - `overrides`: Route patterns with the rules to use instead, checked in order.
+///
+/// **Usage Example:**
+/// ```rust
+/// # use server::{acl::IpAcl, request::Request, response::Response, router::Router};
+/// # fn home(_: &Request) -> Response { Response::new(200, "OK") }
+/// let acl = IpAcl::new()
+///     .deny("203.0.113.0/24")?
+///     .route("/admin/**", IpAcl::new().allow("10.0.0.0/8")?);
+/// let router = Router::new().middleware(acl.middleware()).get("/", home);
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct IpAcl {
+    allow: Vec<Cidr>,
+    deny: Vec<Cidr>,
+    precedence: Precedence,
+    trusted: Vec<Cidr>,
+    overrides: Vec<(Glob, IpAcl)>,
+}
+
+
+impl Default for IpAcl {
+
+    /// # default
+    ///
+    /// **Purpose:**
+    /// Creates an ACL with no ranges, which admits everyone.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - An `IpAcl` with deny-first precedence and no trusted proxies.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::acl::IpAcl;
+    /// let acl = IpAcl::default();
+    /// ```
+    fn default() -> IpAcl {
+        IpAcl {
+            allow: Vec::new(),
+            deny: Vec::new(),
+            precedence: Precedence::DenyFirst,
+            trusted: Vec::new(),
+            overrides: Vec::new(),
+        }
+    }
+}
+
+
+impl IpAcl {
+
+    /// # new
+    ///
+    /// **Purpose:**
+    /// Creates an ACL with no ranges, which admits everyone.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - A new `IpAcl`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::acl::IpAcl;
+    /// let acl = IpAcl::new();
+    /// ```
+    pub fn new() -> IpAcl {
+        IpAcl::default()
+    }
+
+    /// # allow
+    ///
+    /// **Purpose:**
+    /// Admits an address range. Once any range is allowed, unmatched addresses are refused.
+    ///
+    /// **Parameters:**
+    /// - `range`: CIDR range or single address.
+    ///
+    /// **Returns:**
+    /// - The updated `IpAcl`.
+    ///
+    /// **Errors / Failures:**
+    /// - `InvalidInput` if the range is malformed.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::acl::IpAcl;
+    /// let acl = IpAcl::new().allow("192.168.0.0/16")?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn allow(mut self, range: &str) -> io::Result<IpAcl> {
+        self.allow.push(Cidr::parse(range)?);
+        Ok(self)
+    }
+
+    /// # deny
+    ///
+    /// **Purpose:**
+    /// Refuses an address range.
+    ///
+    /// **Parameters:**
+    /// - `range`: CIDR range or single address.
+    ///
+    /// **Returns:**
+    /// - The updated `IpAcl`.
+    ///
+    /// **Errors / Failures:**
+    /// - `InvalidInput` if the range is malformed.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::acl::IpAcl;
+    /// let acl = IpAcl::new().deny("198.51.100.7")?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn deny(mut self, range: &str) -> io::Result<IpAcl> {
+        self.deny.push(Cidr::parse(range)?);
+        Ok(self)
+    }
+
+    /// # precedence
+    ///
+    /// **Purpose:**
+    /// Sets which list wins when an address matches both.
+    ///
+    /// **Parameters:**
+    /// - `precedence`: `DenyFirst` (the default) or `AllowFirst`.
+    ///
+    /// **Returns:**
+    /// - The updated `IpAcl`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::acl::{IpAcl, Precedence};
+    /// let acl = IpAcl::new().deny("10.0.0.0/8")?.allow("10.1.0.0/16")?.precedence(Precedence::AllowFirst);
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn precedence(mut self, precedence: Precedence) -> IpAcl {
+        self.precedence = precedence;
+        self
+    }
+
+    /// # trust_proxy
+    ///
+    /// **Purpose:**
+    /// Believes `X-Forwarded-For` from peers in a range, so rules apply to the forwarded client.
+    ///
+    /// **Parameters:**
+    /// - `range`: CIDR range of the load balancers or reverse proxies in front of the server.
+    ///
+    /// **Returns:**
+    /// - The updated `IpAcl`.
+    ///
+    /// **Errors / Failures:**
+    /// - `InvalidInput` if the range is malformed.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::acl::IpAcl;
+    /// let acl = IpAcl::new().trust_proxy("10.0.0.0/8")?.allow("203.0.113.0/24")?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn trust_proxy(mut self, range: &str) -> io::Result<IpAcl> {
+        self.trusted.push(Cidr::parse(range)?);
+        Ok(self)
+    }
+
+    /// # route
+    ///
+    /// **Purpose:**
+    /// Uses different rules for request paths matching a pattern. The first matching override
+    /// wins; unmatched paths use these rules. Trusted proxies always come from the outer ACL.
+    ///
+    /// **Parameters:**
+    /// - `pattern`: Path glob (e.g. `/admin/**`).
+    /// - `acl`: Rules for those paths.
+    ///
+    /// **Returns:**
+    /// - The updated `IpAcl`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::acl::IpAcl;
+    /// let acl = IpAcl::new().route("/metrics", IpAcl::new().allow("127.0.0.1")?);
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn route(mut self, pattern: &str, acl: IpAcl) -> IpAcl {
+        self.overrides.push((Glob::new(pattern), acl));
+        self
+    }
+
+    /// # middleware
+    ///
+    /// **Purpose:**
+    /// Turns the ACL into router middleware.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - A closure accepted by `Router::middleware`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{acl::IpAcl, router::Router};
+    /// # let acl = IpAcl::new();
+    /// let router = Router::new().middleware(acl.middleware());
+    /// ```
+    pub fn middleware(self) -> impl Fn(&Request, Next<'_>) -> Response + Send + Sync + 'static {
+        move |request, next| self.handle(request, next)
+    }
+
+    /// # handle
+    ///
+    /// **Purpose:**
+    /// Checks the client address against the rules for the request path and either runs the
+    /// rest of the chain or refuses the request.
+    ///
+    /// **Parameters:**
+    /// - `request`: Request being handled.
+    /// - `next`: The rest of the middleware chain.
+    ///
+    /// **Returns:**
+    /// - The downstream response, or `403 Forbidden`.
+    ///
+    /// **Errors / Failures:**
+    /// - Requests without a known peer address are treated as matching no range.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{acl::IpAcl, request::Request, response::Response, router::Next};
+    /// # let acl = IpAcl::new();
+    /// # let request = &Request::read_from(&mut &b"GET / HTTP/1.1\r\n\r\n"[..])?;
+    /// # let next: Next<'_> = &|_| Response::new(200, "OK");
+    /// let response = acl.handle(request, next);
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn handle(&self, request: &Request, next: Next<'_>) -> Response {
+        let client = ip::client_ip(request, &self.trusted);
+        if !self.select(request.path()).permits(client) {
+            return Response::new(403, "Forbidden")
+                .with_header("Content-Type", "text/plain; charset=utf-8")
+                .with_body("access denied");
+        }
+        next(request)
+    }
+
+    /// # permits
+    ///
+    /// **Purpose:**
+    /// Applies these rules to one address.
+    ///
+    /// **Parameters:**
+    /// - `client`: Client address, if known.
+    ///
+    /// **Returns:**
+    /// - `true` if the address is admitted.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let admitted = self.permits(Some("10.0.0.1".parse()?));
+    /// ```
+    fn permits(&self, client: Option<IpAddr>) -> bool {
+        let matches = |ranges: &[Cidr]| client.is_some_and(|address| ranges.iter().any(|range| range.contains(address)));
+        let (allowed, denied) = (matches(&self.allow), matches(&self.deny));
+        match self.precedence {
+            Precedence::DenyFirst if denied => false,
+            Precedence::AllowFirst if allowed => true,
+            _ if allowed => true,
+            _ if denied => false,
+            _ => self.allow.is_empty(),
+        }
+    }
+
+    /// # select
+    ///
+    /// **Purpose:**
+    /// Picks the rules for a path.
+    ///
+    /// **Parameters:**
+    /// - `path`: Request path.
+    ///
+    /// **Returns:**
+    /// - The first matching override, or these rules.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let rules = self.select("/admin/users");
+    /// ```
+    fn select(&self, path: &str) -> &IpAcl {
+        self.overrides
+            .iter()
+            .find(|(pattern, _)| pattern.matches(path))
+            .map(|(_, acl)| acl.select(path))
+            .unwrap_or(self)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn admits(acl: &IpAcl, address: &str) -> bool {
+        acl.permits(Some(address.parse().unwrap()))
+    }
+
+    #[test]
+    fn allow_lists_admit_only_their_ranges() {
+        let acl = IpAcl::new().allow("10.0.0.0/8").unwrap().allow("::1").unwrap();
+        assert!(admits(&acl, "10.1.2.3") && admits(&acl, "::1"));
+        assert!(!admits(&acl, "192.0.2.1"));
+        assert!(!acl.permits(None));
+        assert!(IpAcl::new().permits(None));
+    }
+
+    #[test]
+    fn precedence_settles_addresses_in_both_lists() {
+        let acl = || IpAcl::new().allow("10.0.0.1").unwrap().deny("10.0.0.0/8").unwrap();
+        assert!(!admits(&acl(), "10.0.0.1"));
+        assert!(admits(&acl().precedence(Precedence::AllowFirst), "10.0.0.1"));
+        assert!(!admits(&acl().precedence(Precedence::AllowFirst), "10.0.0.2"));
+        assert!(admits(&IpAcl::new().deny("10.0.0.0/8").unwrap(), "192.0.2.1"));
+    }
+
+    #[test]
+    fn overrides_are_chosen_by_path_and_nest() {
+        let inner = IpAcl::new().route("/admin/keys", IpAcl::new().allow("10.0.0.1").unwrap());
+        let acl = IpAcl::new().route("/admin/**", inner.allow("10.0.0.0/8").unwrap());
+        assert!(admits(acl.select("/admin/users"), "10.0.0.9"));
+        assert!(!admits(acl.select("/admin/keys"), "10.0.0.9"));
+        assert!(admits(acl.select("/public"), "192.0.2.1"));
+    }
+}
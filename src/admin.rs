@@ -0,0 +1,701 @@
+
+// ============================================================
+//  DAEGONICA SOFTWARE — admin.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: Admin
+//!
+//! **Purpose:**
+//! Serves an operator-only JSON API on its own listener, apart from public traffic.
+//!
+//! **Context:**
+//! - Set with `Server::admin`; bound by `Server::run` with the public listener, before
+//!   privileges are dropped, and served once they are.
+//!
+//! **Responsibilities:**
+//! - Binds a TCP address or, on Unix, a `unix:`-prefixed socket path.
+//! - Reports the server's configuration, worker pool and traffic stats, cache and concurrency
+//!   stats, and open connections.
+//! - Accepts `drain` (stop keeping connections alive and fail readiness) and `shutdown` (stop
+//!   accepting, let workers finish, and return from `Server::run`) commands.
+//! - Changes the log level, registered rate limits, and reverse proxy upstream weights while
+//!   serving; each change takes effect atomically for the next request.
+//! - Reads each request within tight size caps and a fixed deadline, so one slow or oversized
+//!   request cannot stall the single listener thread.
+//! - Does NOT authenticate callers; bind it to loopback or a socket only operators can open.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+use std::{
+    io::{self, BufRead, BufReader, Write},
+    net::{SocketAddr, TcpListener},
+    sync::Arc,
+    thread,
+    time::Duration,
+};
+
+use crate::{
+    json_value::JsonValue,
+    limits::HeadLimits,
+    log::{self, LogLevel},
+    rate_limit::RateLimit,
+    request::Request,
+    response::Response,
+    server::Server,
+    timeouts::DeadlineReader,
+    validate::json_string,
+};
+
+
+/// # ADMIN_TIMEOUT
+///
+/// **Summary:**
+/// Time allowed to receive each request on the admin listener, and for each write of its reply.
+const ADMIN_TIMEOUT: Duration = Duration::from_secs(5);
+
+
+/// # ADMIN_MAX_BODY
+///
+/// **Summary:**
+/// Largest request body accepted on the admin listener (64 KiB); settings bodies are tiny.
+const ADMIN_MAX_BODY: usize = 64 * 1024;
+
+
+/// # Admin
+///
+/// **Summary:**
+/// Where the admin API listens.
+///
+/// **Fields:**
+/// - `addr`: TCP address to bind, or `unix:<path>` for a Unix domain socket.
+/// - `rate_limits`: Rate limiters adjustable at runtime, by name.
+///
+/// **Usage Example:**
+/// ```rust,no_run
+/// # use server::{admin::Admin, rate_limit::RateLimit, router::Router, server::Server};
+/// let api_limit = RateLimit::new(5.0, 20);
+/// let router = Router::new().middleware(api_limit.clone().middleware());
+/// Server::new("0.0.0.0:80").admin(Admin::new("127.0.0.1:9000").rate_limit("api", api_limit)).run()?;
+/// // curl -X POST http://127.0.0.1:9000/drain
+/// // curl -X PUT -d '{"rate":10,"burst":40}' http://127.0.0.1:9000/settings/rate-limits/api
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct Admin {
+    addr: String,
+    rate_limits: Vec<(String, RateLimit)>,
+}
+
+
+impl Admin {
+
+    /// # new
+    ///
+    /// **Purpose:**
+    /// Creates an admin API for a bind address.
+    ///
+    /// **Parameters:**
+    /// - `addr`: TCP address (e.g. `127.0.0.1:9000`) or `unix:/run/server/admin.sock`.
+    ///
+    /// **Returns:**
+    /// - A new `Admin`.
+    ///
+    /// **Errors / Failures:**
+    /// None. Binding happens in `Server::run`.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::admin::Admin;
+    /// let admin = Admin::new("unix:/run/server/admin.sock");
+    /// ```
+    pub fn new(addr: impl Into<String>) -> Admin {
+        Admin {
+            addr: addr.into(),
+            rate_limits: Vec::new(),
+        }
+    }
+
+    /// # rate_limit
+    ///
+    /// **Purpose:**
+    /// Registers a rate limiter so its rate and burst can be read and changed through
+    /// `/settings/rate-limits/<name>`. Pass a clone of the limiter installed as middleware;
+    /// clones share their settings.
+    ///
+    /// **Parameters:**
+    /// - `name`: Name used in the admin API.
+    /// - `limit`: The limiter.
+    ///
+    /// **Returns:**
+    /// - The updated `Admin`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{admin::Admin, rate_limit::RateLimit};
+    /// # let login_limit = RateLimit::new(1.0, 5);
+    /// let admin = Admin::new("127.0.0.1:9000").rate_limit("login", login_limit.clone());
+    /// ```
+    pub fn rate_limit(mut self, name: &str, limit: RateLimit) -> Admin {
+        self.rate_limits.push((name.to_string(), limit));
+        self
+    }
+
+    /// # bind
+    ///
+    /// **Purpose:**
+    /// Binds the admin listener without serving it yet, so the server can bind every socket
+    /// before dropping privileges and only then start threads that answer requests.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - The bound `AdminListener`.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns an I/O error if the address cannot be bound, or if a `unix:` path exists and
+    ///   is not a socket.
+    /// - Returns `Unsupported` for a `unix:` address on non-Unix targets.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let listener = admin.bind()?;
+    /// ```
+    pub(crate) fn bind(&self) -> io::Result<AdminListener> {
+        let listener = match self.addr.strip_prefix("unix:") {
+            Some(path) => bind_unix(path)?,
+            None => Listener::Tcp(TcpListener::bind(&self.addr)?),
+        };
+        Ok(AdminListener {
+            admin: self.clone(),
+            listener,
+        })
+    }
+}
+
+
+/// # AdminListener
+///
+/// **Summary:**
+/// Bound admin socket waiting to be served.
+///
+/// **Fields:**
+/// - `admin`: Admin configuration, moved to the listener thread.
+/// - `listener`: Bound socket.
+///
+/// **Usage Example:**
+/// ```rust,ignore
+/// let listener = admin.bind()?;
+/// privileges.apply()?;
+/// listener.serve(Arc::clone(&server), public)?;
+/// ```
+#[derive(Debug)]
+pub(crate) struct AdminListener {
+    admin: Admin,
+    listener: Listener,
+}
+
+
+/// # Listener
+///
+/// **Summary:**
+/// Socket the admin API is bound to.
+///
+/// **Variants:**
+/// - `Tcp`: TCP listener.
+/// - `Unix`: Unix domain socket listener (Unix only).
+#[derive(Debug)]
+enum Listener {
+    Tcp(TcpListener),
+    #[cfg(unix)]
+    Unix(std::os::unix::net::UnixListener),
+}
+
+
+impl AdminListener {
+
+    /// # serve
+    ///
+    /// **Purpose:**
+    /// Answers admin requests one at a time on a background thread.
+    ///
+    /// **Parameters:**
+    /// - `server`: Running server to report on and control.
+    /// - `public`: Address of the public listener, connected to on `shutdown` to wake its
+    ///   accept loop.
+    ///
+    /// **Returns:**
+    /// - `Ok(())` once the thread is started.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns an I/O error if the thread cannot be spawned.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// listener.serve(Arc::clone(&server), listener_addr)?;
+    /// ```
+    pub(crate) fn serve(self, server: Arc<Server>, public: SocketAddr) -> io::Result<()> {
+        let AdminListener { admin, listener } = self;
+        thread::Builder::new().name("admin".to_string()).spawn(move || match listener {
+            Listener::Tcp(listener) => {
+                for stream in listener.incoming().flatten() {
+                    let _ = stream.set_write_timeout(Some(ADMIN_TIMEOUT));
+                    if exchange(&admin, &server, BufReader::new(DeadlineReader::new(&stream, ADMIN_TIMEOUT)), &stream) {
+                        drop(stream);
+                        server.stop(public);
+                    }
+                }
+            }
+            #[cfg(unix)]
+            Listener::Unix(listener) => {
+                for stream in listener.incoming().flatten() {
+                    let _ = stream.set_write_timeout(Some(ADMIN_TIMEOUT));
+                    if exchange(&admin, &server, BufReader::new(DeadlineReader::new(&stream, ADMIN_TIMEOUT)), &stream) {
+                        drop(stream);
+                        server.stop(public);
+                    }
+                }
+            }
+        })?;
+        Ok(())
+    }
+}
+
+
+/// # bind_unix
+///
+/// **Purpose:**
+/// Binds the admin API to a Unix domain socket, replacing a stale socket file left by an
+/// earlier run.
+///
+/// **Parameters:**
+/// - `path`: Socket path.
+///
+/// **Returns:**
+/// - The bound listener.
+///
+/// **Errors / Failures:**
+/// - Returns an I/O error if the path exists and is not a socket, or cannot be bound.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let listener = bind_unix("/run/server/admin.sock")?;
+/// ```
+#[cfg(unix)]
+fn bind_unix(path: &str) -> io::Result<Listener> {
+    use std::{fs, os::unix::fs::FileTypeExt, os::unix::net::UnixListener};
+
+    if fs::symlink_metadata(path).is_ok_and(|metadata| metadata.file_type().is_socket()) {
+        fs::remove_file(path)?;
+    }
+    Ok(Listener::Unix(UnixListener::bind(path)?))
+}
+
+
+/// # bind_unix
+///
+/// **Purpose:**
+/// Stands in on targets without Unix domain sockets.
+///
+/// **Parameters:**
+/// - `_path`: Socket path.
+///
+/// **Returns:**
+/// None.
+///
+/// **Errors / Failures:**
+/// - Always returns `Unsupported`.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let listener = bind_unix("admin.sock")?;
+/// ```
+#[cfg(not(unix))]
+fn bind_unix(_path: &str) -> io::Result<Listener> {
+    Err(io::Error::new(io::ErrorKind::Unsupported, "unix admin sockets need a Unix target"))
+}
+
+
+/// # exchange
+///
+/// **Purpose:**
+/// Reads one admin request and writes its answer. A shutdown is left to the caller, so it
+/// happens only once the reply is out and the connection closed.
+///
+/// **Parameters:**
+/// - `admin`: Admin configuration.
+/// - `server`: Running server.
+/// - `reader`: Buffered admin connection.
+/// - `writer`: The same connection, for the reply.
+///
+/// **Returns:**
+/// - `true` if the request asked the server to shut down.
+///
+/// **Errors / Failures:**
+/// None. Unreadable requests and failed writes just end the exchange.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// if exchange(&admin, &server, BufReader::new(&stream), &stream) { server.stop(public); }
+/// ```
+fn exchange(admin: &Admin, server: &Server, mut reader: impl BufRead, mut writer: impl Write) -> bool {
+    let Ok(request) = Request::read_within(&mut reader, &HeadLimits::OPERATOR, ADMIN_MAX_BODY) else {
+        return false;
+    };
+    let (response, shutdown) = answer(admin, server, &request);
+    let _ = response.with_header("Connection", "close").write_to(&mut writer);
+    shutdown
+}
+
+
+/// # answer
+///
+/// **Purpose:**
+/// Routes an admin request.
+///
+/// **Parameters:**
+/// - `admin`: Admin configuration.
+/// - `server`: Running server.
+/// - `request`: Admin request.
+///
+/// **Returns:**
+/// - The reply, and whether the server should then shut down.
+///
+/// **Errors / Failures:**
+/// None. Unknown paths get `404` and wrong methods `405`.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let (response, shutdown) = answer(admin, server, &request);
+/// ```
+fn answer(admin: &Admin, server: &Server, request: &Request) -> (Response, bool) {
+    let read = request.method == "GET" || request.method == "HEAD";
+    let command = request.method == "POST";
+    if let Some(setting) = request.path().strip_prefix("/settings/") {
+        let response = match request.method.as_str() {
+            "PUT" => update(admin, server, setting, &request.body),
+            _ => not_allowed("PUT"),
+        };
+        return (response, false);
+    }
+    match request.path() {
+        "/settings" if read => (json(200, "OK", settings(admin, server)), false),
+        "/config" if read => (json(200, "OK", server.describe()), false),
+        "/stats" if read => (json(200, "OK", stats(server)), false),
+        "/connections" if read => (json(200, "OK", connections(server)), false),
+        "/drain" if command => {
+            server.drain();
+            (json(200, "OK", r#"{"draining":true}"#.to_string()), false)
+        }
+        "/shutdown" if command => (json(202, "Accepted", r#"{"stopping":true}"#.to_string()), true),
+        "/config" | "/stats" | "/connections" | "/settings" => (not_allowed("GET, HEAD"), false),
+        "/drain" | "/shutdown" => (not_allowed("POST"), false),
+        _ => (plain(404, "Not Found", "unknown admin endpoint"), false),
+    }
+}
+
+
+/// # settings
+///
+/// **Purpose:**
+/// Renders the settings that can be changed at runtime.
+///
+/// **Parameters:**
+/// - `admin`: Admin configuration holding the registered rate limits.
+/// - `server`: Running server.
+///
+/// **Returns:**
+/// - A JSON object with `log_level`, `rate_limits` by name, and reverse proxy `upstreams`.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let body = settings(admin, server);
+/// ```
+fn settings(admin: &Admin, server: &Server) -> String {
+    let rate_limits: Vec<String> = admin
+        .rate_limits
+        .iter()
+        .map(|(name, limit)| {
+            let (rate, burst) = limit.rate();
+            format!(r#"{}:{{"rate":{rate},"burst":{burst}}}"#, json_string(name))
+        })
+        .collect();
+    let upstreams: Vec<String> = server
+        .reverse_proxy()
+        .map(|proxy| proxy.weights())
+        .unwrap_or_default()
+        .iter()
+        .map(|(address, weight)| format!(r#"{{"address":{},"weight":{weight}}}"#, json_string(address)))
+        .collect();
+    format!(
+        r#"{{"log_level":{},"rate_limits":{{{}}},"upstreams":[{}]}}"#,
+        json_string(log::level().as_str()),
+        rate_limits.join(","),
+        upstreams.join(","),
+    )
+}
+
+
+/// # update
+///
+/// **Purpose:**
+/// Applies a `PUT /settings/...` change: `log-level` takes `{"level": "warn"}`,
+/// `rate-limits/<name>` takes `{"rate": 10, "burst": 40}`, and `upstreams/<host:port>` takes
+/// `{"weight": 2}`.
+///
+/// **Parameters:**
+/// - `admin`: Admin configuration holding the registered rate limits.
+/// - `server`: Running server.
+/// - `setting`: Path after `/settings/`.
+/// - `body`: JSON request body.
+///
+/// **Returns:**
+/// - `200 OK` with the updated settings.
+///
+/// **Errors / Failures:**
+/// - `400 Bad Request` for a body that is not valid JSON or lacks a valid value.
+/// - `404 Not Found` for an unknown setting, rate limit name, or upstream.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let response = update(admin, server, "log-level", br#"{"level":"info"}"#);
+/// ```
+fn update(admin: &Admin, server: &Server, setting: &str, body: &[u8]) -> Response {
+    let Some(body) = std::str::from_utf8(body).ok().and_then(JsonValue::parse) else {
+        return plain(400, "Bad Request", "body must be a JSON object");
+    };
+    let number = |key: &str| body.get(key).and_then(JsonValue::as_f64);
+    let count = |key: &str| number(key).filter(|value| value.fract() == 0.0 && (0.0..=f64::from(u32::MAX)).contains(value));
+
+    if setting == "log-level" {
+        let Some(level) = body.get("level").and_then(JsonValue::as_str).and_then(LogLevel::parse) else {
+            return plain(400, "Bad Request", "level must be error, warn, info, or debug");
+        };
+        log::set_level(level);
+    } else if let Some(name) = setting.strip_prefix("rate-limits/") {
+        let Some((_, limit)) = admin.rate_limits.iter().find(|(registered, _)| registered == name) else {
+            return plain(404, "Not Found", "unknown rate limit");
+        };
+        let (Some(rate), Some(burst)) = (number("rate").filter(|rate| *rate > 0.0), count("burst")) else {
+            return plain(400, "Bad Request", "rate must be a positive number and burst a whole number");
+        };
+        limit.set_rate(rate, burst as u32);
+    } else if let Some(address) = setting.strip_prefix("upstreams/") {
+        let Some(weight) = count("weight") else {
+            return plain(400, "Bad Request", "weight must be a whole number");
+        };
+        if !server.reverse_proxy().is_some_and(|proxy| proxy.set_weight(address, weight as u32)) {
+            return plain(404, "Not Found", "unknown upstream");
+        }
+    } else {
+        return plain(404, "Not Found", "unknown setting");
+    }
+    json(200, "OK", settings(admin, server))
+}
+
+
+/// # stats
+///
+/// **Purpose:**
+/// Renders the pool, traffic, concurrency, and cache statistics.
+///
+/// **Parameters:**
+/// - `server`: Running server.
+///
+/// **Returns:**
+/// - A JSON object.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let body = stats(server);
+/// ```
+fn stats(server: &Server) -> String {
+    let traffic = server.traffic().stats();
+    let concurrency = server.concurrency().stats();
+    let cache = server.cache().stats();
+    format!(
+        concat!(
+            r#"{{"pool":{{"workers":{},"queued":{},"in_flight":{}}},"#,
+            r#""connections":{{"open":{},"total":{},"concurrent":{},"rejected":{},"peer_rejected":{}}},"#,
+            r#""traffic":{{"received_bytes":{},"sent_bytes":{}}},"#,
+            r#""cache":{{"hits":{},"misses":{},"coalesced":{},"entries":{},"bytes":{}}},"#,
+            r#""draining":{}}}"#,
+        ),
+        server.worker_count(),
+        traffic.queued,
+        traffic.in_flight,
+        traffic.connections_open,
+        traffic.connections_total,
+        concurrency.current,
+        concurrency.rejected,
+        server.peers().rejected(),
+        traffic.received_bytes,
+        traffic.sent_bytes,
+        cache.hits,
+        cache.misses,
+        cache.coalesced,
+        cache.entries,
+        cache.bytes,
+        server.is_draining(),
+    )
+}
+
+
+/// # connections
+///
+/// **Purpose:**
+/// Renders the open client connections.
+///
+/// **Parameters:**
+/// - `server`: Running server.
+///
+/// **Returns:**
+/// - A JSON array of `{"id", "remote", "local", "age_ms"}` objects, oldest id first.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let body = connections(server);
+/// ```
+fn connections(server: &Server) -> String {
+    let address = |address: Option<SocketAddr>| address.map_or("null".to_string(), |address| json_string(&address.to_string()));
+    let entries: Vec<String> = server
+        .open_connections()
+        .iter()
+        .map(|connection| {
+            format!(
+                r#"{{"id":{},"remote":{},"local":{},"age_ms":{}}}"#,
+                connection.id,
+                address(connection.remote),
+                address(connection.local),
+                connection.accepted.elapsed().as_millis(),
+            )
+        })
+        .collect();
+    format!("[{}]", entries.join(","))
+}
+
+
+/// # json
+///
+/// **Purpose:**
+/// Wraps a rendered JSON body in a reply.
+///
+/// **Parameters:**
+/// - `status`: Status code.
+/// - `reason`: Reason phrase.
+/// - `body`: JSON text.
+///
+/// **Returns:**
+/// - The response, marked uncacheable.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let response = json(200, "OK", stats(server));
+/// ```
+fn json(status: u16, reason: &str, body: String) -> Response {
+    Response::new(status, reason)
+        .with_header("Content-Type", "application/json")
+        .with_header("Cache-Control", "no-store")
+        .with_body(body)
+}
+
+
+/// # not_allowed
+///
+/// **Purpose:**
+/// Builds the reply for a known admin endpoint asked with the wrong method.
+///
+/// **Parameters:**
+/// - `allow`: Methods the endpoint accepts.
+///
+/// **Returns:**
+/// - A `405 Method Not Allowed` response.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let response = not_allowed("POST");
+/// ```
+fn not_allowed(allow: &str) -> Response {
+    Response::new(405, "Method Not Allowed").with_header("Allow", allow)
+}
+
+
+/// # plain
+///
+/// **Purpose:**
+/// Builds a plain-text error reply.
+///
+/// **Parameters:**
+/// - `status`: Status code.
+/// - `reason`: Reason phrase.
+/// - `message`: Body text.
+///
+/// **Returns:**
+/// - The response.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let response = plain(404, "Not Found", "unknown setting");
+/// ```
+fn plain(status: u16, reason: &str, message: &str) -> Response {
+    Response::new(status, reason)
+        .with_header("Content-Type", "text/plain; charset=utf-8")
+        .with_body(message.to_string())
+}
+
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::{io::Read, os::unix::net::UnixStream};
+
+    #[test]
+    fn binds_before_serving_and_answers_once_served() {
+        let path = std::env::temp_dir().join(format!("admin-test-{}.sock", std::process::id()));
+        let path = path.to_str().unwrap().to_string();
+        let admin = Admin::new(format!("unix:{path}"));
+        let listener = admin.bind().unwrap();
+        assert!(std::fs::symlink_metadata(&path).is_ok());
+
+        let handle = Server::new("127.0.0.1:0").start().unwrap();
+        listener.serve(Arc::new(Server::new("127.0.0.1:0")), handle.local_addr()).unwrap();
+        let mut stream = UnixStream::connect(&path).unwrap();
+        stream.write_all(b"GET /stats HTTP/1.1\r\nHost: admin\r\n\r\n").unwrap();
+        let mut reply = String::new();
+        stream.read_to_string(&mut reply).unwrap();
+        assert!(reply.starts_with("HTTP/1.1 200"), "{reply}");
+
+        assert!(admin.bind().is_ok(), "a stale socket file is replaced");
+        handle.shutdown();
+        handle.await_stopped().unwrap();
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,851 @@
+
+// ============================================================
+//  DAEGONICA SOFTWARE — auth.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: Auth
+//!
+//! **Purpose:**
+//! Restricts routes to authenticated users, starting with HTTP Basic authentication, and defines
+//! the `AuthProvider` extension point for custom credential checks.
+//!
+//! **Context:**
+//! - Installed as router middleware; authenticated requests carry an `AuthenticatedUser`
+//!   in their extensions for handlers to read.
+//!
+//! **Responsibilities:**
+//! - Verifies Basic credentials against a static user map or an htpasswd-style file.
+//! - Checks bcrypt (`bcrypt` feature) and Argon2 (`argon2` feature) password hashes.
+//! - Delegates credentials it does not know to `AuthProvider`s (LDAP, databases, custom logic).
+//! - Answers failed attempts with `401 Unauthorized` and a `WWW-Authenticate` challenge.
+//! - Checks `Proxy-Authorization` for the forward proxy, challenging with `407` and
+//!   `Proxy-Authenticate`.
+//! - Does NOT protect credentials in transit; serve Basic-protected routes over HTTPS.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+use std::{collections::HashMap, fmt, fs, io, path::Path, sync::Arc};
+
+use crate::{
+    base64,
+    extract::FromRequest,
+    random,
+    request::{Request, invalid},
+    response::Response,
+    router::Next,
+};
+
+
+/// # AuthenticatedUser
+///
+/// **Summary:**
+/// Name of the user the request was authenticated as.
+///
+/// **Fields:**
+/// - `0`: User name.
+///
+/// **Usage Example:**
+/// ```rust
+/// # use server::{auth::AuthenticatedUser, response::Response};
+/// fn whoami(AuthenticatedUser(name): AuthenticatedUser) -> Response {
+///     Response::new(200, "OK").with_body(name)
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthenticatedUser(pub String);
+
+
+/// # Credentials
+///
+/// **Summary:**
+/// Credentials presented by a client, as handed to an `AuthProvider`.
+///
+/// **Variants:**
+/// - `Basic`: User name and password from `Authorization: Basic`.
+/// - `Bearer`: Token from `Authorization: Bearer`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Credentials<'a> {
+    Basic { user: &'a str, password: &'a str },
+    Bearer(&'a str),
+}
+
+
+/// # AuthProvider
+///
+/// **Summary:**
+/// Verifies client credentials and names the principal they belong to. `BasicAuth` and
+/// `JwtAuth` consult their providers for credentials they cannot verify themselves.
+/// Closures of the same shape implement it too.
+///
+/// **Usage Example:**
+/// ```rust
+/// # use server::{auth::{AuthProvider, AuthenticatedUser, Credentials}, jwt::JwtAuth};
+/// # struct Database;
+/// # impl Database { fn owner_of_key(&self, _key: &str) -> Option<String> { None } }
+/// # let db = Database;
+/// struct ApiKeys(Database);
+///
+/// impl AuthProvider for ApiKeys {
+///     fn authenticate(&self, credentials: &Credentials<'_>) -> Option<AuthenticatedUser> {
+///         match credentials {
+///             Credentials::Bearer(token) => self.0.owner_of_key(token).map(AuthenticatedUser),
+///             _ => None,
+///         }
+///     }
+/// }
+///
+/// let jwt = JwtAuth::new().provider(ApiKeys(db));
+/// ```
+pub trait AuthProvider: Send + Sync + 'static {
+    /// # authenticate
+    ///
+    /// **Purpose:**
+    /// Verifies credentials.
+    ///
+    /// **Parameters:**
+    /// - `credentials`: Credentials sent by the client.
+    ///
+    /// **Returns:**
+    /// - `Some(principal)` if they are valid, or `None` to reject (or defer to the next provider).
+    ///
+    /// **Errors / Failures:**
+    /// None. Back-end failures should be treated as rejection.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::auth::{AuthProvider, AuthenticatedUser, Credentials};
+    /// # let provider = |credentials: &Credentials<'_>| match credentials {
+    /// #     Credentials::Basic { user, password: "s3cret" } => Some(AuthenticatedUser(user.to_string())),
+    /// #     _ => None,
+    /// # };
+    /// let user = provider.authenticate(&Credentials::Basic { user: "ana", password: "s3cret" });
+    /// # assert_eq!(user.map(|user| user.0).as_deref(), Some("ana"));
+    /// ```
+    fn authenticate(&self, credentials: &Credentials<'_>) -> Option<AuthenticatedUser>;
+}
+
+
+/// # Providers
+///
+/// **Summary:**
+/// Ordered list of shared providers; the first to accept the credentials wins.
+///
+/// **Fields:**
+/// - `0`: Providers in consultation order.
+#[derive(Clone, Default)]
+pub(crate) struct Providers(Vec<Arc<dyn AuthProvider>>);
+
+
+/// # Credential
+///
+/// **Summary:**
+/// Stored password of one user.
+///
+/// **Variants:**
+/// - `Plain`: Clear-text password, compared in constant time.
+/// - `Bcrypt`: bcrypt hash (`$2a$`, `$2b$`, `$2y$`).
+/// - `Argon2`: Argon2 PHC string (`$argon2id$...`).
+#[derive(Debug, Clone)]
+enum Credential {
+    Plain(String),
+    Bcrypt(String),
+    Argon2(String),
+}
+
+
+/// # BasicAuth
+///
+/// **Summary:**
+/// HTTP Basic authentication policy.
+///
+/// **Fields:**
+/// - `realm`: Protection space named in the challenge.
+/// - `users`: Stored credentials by user name.
+/// - `providers`: Providers consulted for users not in `users`.
+///
+/// **Usage Example:**
+/// ```rust,no_run
+/// # use server::{auth::BasicAuth, request::Request, response::Response, router::Router};
+/// # fn dashboard(_: &Request) -> Response { Response::new(200, "OK") }
+/// let auth = BasicAuth::new("Admin area").htpasswd("/etc/daegonica/htpasswd")?;
+/// let router = Router::new().scope("/admin", |admin| admin.middleware(auth.middleware()).get("/", dashboard));
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct BasicAuth {
+    realm: String,
+    users: HashMap<String, Credential>,
+    providers: Providers,
+}
+
+
+impl FromRequest for AuthenticatedUser {
+
+    /// # from_request
+    ///
+    /// **Purpose:**
+    /// Fetches the user attached by an authentication middleware.
+    ///
+    /// **Parameters:**
+    /// - `request`: Request being handled.
+    ///
+    /// **Returns:**
+    /// - The authenticated user.
+    ///
+    /// **Errors / Failures:**
+    /// - Rejects with `500 Internal Server Error` if no authentication middleware ran.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{auth::AuthenticatedUser, extract::FromRequest, request::Request, response::Response};
+    /// # let mut request = Request::read_from(&mut &b"GET / HTTP/1.1\r\n\r\n"[..]).unwrap();
+    /// # request.extensions.insert(AuthenticatedUser("ana".to_string()));
+    /// let user = AuthenticatedUser::from_request(&request)?;
+    /// # assert_eq!(user.0, "ana");
+    /// # Ok::<(), Response>(())
+    /// ```
+    fn from_request(request: &Request) -> Result<AuthenticatedUser, Response> {
+        request
+            .extensions
+            .get::<AuthenticatedUser>()
+            .cloned()
+            .ok_or_else(|| Response::new(500, "Internal Server Error"))
+    }
+}
+
+
+impl<F> AuthProvider for F
+where
+    F: Fn(&Credentials<'_>) -> Option<AuthenticatedUser> + Send + Sync + 'static,
+{
+    /// # authenticate
+    ///
+    /// **Purpose:**
+    /// Calls the closure.
+    ///
+    /// **Parameters:**
+    /// - `credentials`: Credentials sent by the client.
+    ///
+    /// **Returns:**
+    /// - The closure's verdict.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::auth::{AuthenticatedUser, BasicAuth, Credentials};
+    /// # struct Ldap;
+    /// # impl Ldap { fn bind(&self, _credentials: &Credentials<'_>) -> Option<AuthenticatedUser> { None } }
+    /// # let ldap = Ldap;
+    /// let auth = BasicAuth::new("Ops").provider(move |credentials: &Credentials<'_>| ldap.bind(credentials));
+    /// ```
+    fn authenticate(&self, credentials: &Credentials<'_>) -> Option<AuthenticatedUser> {
+        self(credentials)
+    }
+}
+
+
+impl Providers {
+
+    /// # push
+    ///
+    /// **Purpose:**
+    /// Appends a provider.
+    ///
+    /// **Parameters:**
+    /// - `provider`: Provider to consult after the existing ones.
+    ///
+    /// **Returns:**
+    /// None.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// self.providers.push(provider);
+    /// ```
+    pub(crate) fn push(&mut self, provider: impl AuthProvider) {
+        self.0.push(Arc::new(provider));
+    }
+
+    /// # authenticate
+    ///
+    /// **Purpose:**
+    /// Asks each provider in turn.
+    ///
+    /// **Parameters:**
+    /// - `credentials`: Credentials sent by the client.
+    ///
+    /// **Returns:**
+    /// - The first accepting provider's principal, or `None`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let user = self.providers.authenticate(&Credentials::Bearer(token));
+    /// ```
+    pub(crate) fn authenticate(&self, credentials: &Credentials<'_>) -> Option<AuthenticatedUser> {
+        self.0.iter().find_map(|provider| provider.authenticate(credentials))
+    }
+}
+
+
+impl fmt::Debug for Providers {
+
+    /// # fmt
+    ///
+    /// **Purpose:**
+    /// Formats the list by size only, since providers need not implement `Debug`.
+    ///
+    /// **Parameters:**
+    /// - `f`: Formatter.
+    ///
+    /// **Returns:**
+    /// - The formatter result.
+    ///
+    /// **Errors / Failures:**
+    /// - Propagates formatter errors.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// println!("{:?}", providers);
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Providers").field(&self.0.len()).finish()
+    }
+}
+
+impl Credential {
+
+    /// # parse
+    ///
+    /// **Purpose:**
+    /// Recognises the hash format of an htpasswd entry.
+    ///
+    /// **Parameters:**
+    /// - `hash`: Stored hash text.
+    ///
+    /// **Returns:**
+    /// - The typed credential.
+    ///
+    /// **Errors / Failures:**
+    /// - `InvalidData` for unsupported formats or formats whose feature is disabled.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let credential = Credential::parse("$2y$10$...")?;
+    /// ```
+    fn parse(hash: &str) -> io::Result<Credential> {
+        if ["$2a$", "$2b$", "$2y$"].iter().any(|prefix| hash.starts_with(prefix)) {
+            if cfg!(feature = "bcrypt") {
+                return Ok(Credential::Bcrypt(hash.to_string()));
+            }
+            return Err(invalid("bcrypt hashes require the `bcrypt` feature"));
+        }
+        if hash.starts_with("$argon2") {
+            if cfg!(feature = "argon2") {
+                return Ok(Credential::Argon2(hash.to_string()));
+            }
+            return Err(invalid("Argon2 hashes require the `argon2` feature"));
+        }
+        Err(invalid("unsupported htpasswd hash (expected bcrypt or Argon2)"))
+    }
+
+    /// # verify
+    ///
+    /// **Purpose:**
+    /// Checks a password against the stored credential.
+    ///
+    /// **Parameters:**
+    /// - `password`: Password sent by the client.
+    ///
+    /// **Returns:**
+    /// - `true` if it matches.
+    ///
+    /// **Errors / Failures:**
+    /// None. Malformed hashes never match.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// if credential.verify(password) { ... }
+    /// ```
+    fn verify(&self, password: &str) -> bool {
+        match self {
+            Credential::Plain(expected) => random::constant_time_eq(expected.as_bytes(), password.as_bytes()),
+            Credential::Bcrypt(hash) => verify_bcrypt(hash, password),
+            Credential::Argon2(hash) => verify_argon2(hash, password),
+        }
+    }
+}
+
+
+impl BasicAuth {
+
+    /// # new
+    ///
+    /// **Purpose:**
+    /// Creates a policy with no users.
+    ///
+    /// **Parameters:**
+    /// - `realm`: Protection space shown by browsers in the login prompt.
+    ///
+    /// **Returns:**
+    /// - A new `BasicAuth`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::auth::BasicAuth;
+    /// let auth = BasicAuth::new("Staging");
+    /// ```
+    pub fn new(realm: impl Into<String>) -> BasicAuth {
+        BasicAuth {
+            realm: realm.into(),
+            users: HashMap::new(),
+            providers: Providers::default(),
+        }
+    }
+
+    /// # user
+    ///
+    /// **Purpose:**
+    /// Adds a user with a clear-text password.
+    ///
+    /// **Parameters:**
+    /// - `name`: User name.
+    /// - `password`: Password.
+    ///
+    /// **Returns:**
+    /// - The updated `BasicAuth`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,no_run
+    /// # use server::auth::BasicAuth;
+    /// # use std::env;
+    /// let auth = BasicAuth::new("Staging").user("qa", &env::var("QA_PASSWORD")?);
+    /// # Ok::<(), env::VarError>(())
+    /// ```
+    pub fn user(mut self, name: impl Into<String>, password: impl Into<String>) -> BasicAuth {
+        self.users.insert(name.into(), Credential::Plain(password.into()));
+        self
+    }
+
+    /// # htpasswd
+    ///
+    /// **Purpose:**
+    /// Adds the users of an htpasswd-style file (`name:hash` per line, `#` comments).
+    ///
+    /// **Parameters:**
+    /// - `path`: File to read.
+    ///
+    /// **Returns:**
+    /// - The updated `BasicAuth`.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns an I/O error if the file cannot be read.
+    /// - Returns `InvalidData` for malformed lines or unsupported hash formats.
+    ///
+    /// **Examples:**
+    /// ```rust,no_run
+    /// # use server::auth::BasicAuth;
+    /// let auth = BasicAuth::new("Admin").htpasswd("htpasswd")?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn htpasswd(mut self, path: impl AsRef<Path>) -> io::Result<BasicAuth> {
+        for line in fs::read_to_string(path)?.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (name, hash) = line
+                .split_once(':')
+                .ok_or_else(|| invalid("htpasswd line is not name:hash"))?;
+            self.users.insert(name.to_string(), Credential::parse(hash)?);
+        }
+        Ok(self)
+    }
+
+    /// # provider
+    ///
+    /// **Purpose:**
+    /// Adds a provider consulted for users that are not configured locally.
+    ///
+    /// **Parameters:**
+    /// - `provider`: Credential verifier (e.g. an LDAP bind or a database lookup).
+    ///
+    /// **Returns:**
+    /// - The updated `BasicAuth`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::auth::{AuthProvider, AuthenticatedUser, BasicAuth, Credentials};
+    /// # struct LdapProvider;
+    /// # impl LdapProvider { fn new(_url: &str) -> LdapProvider { LdapProvider } }
+    /// # impl AuthProvider for LdapProvider {
+    /// #     fn authenticate(&self, _credentials: &Credentials<'_>) -> Option<AuthenticatedUser> { None }
+    /// # }
+    /// let auth = BasicAuth::new("Staff").provider(LdapProvider::new("ldap://directory:389"));
+    /// ```
+    pub fn provider(mut self, provider: impl AuthProvider) -> BasicAuth {
+        self.providers.push(provider);
+        self
+    }
+
+    /// # middleware
+    ///
+    /// **Purpose:**
+    /// Turns the policy into router middleware.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - A closure accepted by `Router::middleware`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{auth::BasicAuth, router::Router};
+    /// let router = Router::new().middleware(BasicAuth::new("Admin").user("root", "secret").middleware());
+    /// ```
+    pub fn middleware(self) -> impl Fn(&Request, Next<'_>) -> Response + Send + Sync + 'static {
+        move |request, next| self.handle(request, next)
+    }
+
+    /// # handle
+    ///
+    /// **Purpose:**
+    /// Runs the rest of the chain for requests with valid credentials, attaching the user name.
+    ///
+    /// **Parameters:**
+    /// - `request`: Request being handled.
+    /// - `next`: The rest of the middleware chain.
+    ///
+    /// **Returns:**
+    /// - The downstream response, or `401 Unauthorized` with a Basic challenge.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{auth::BasicAuth, request::Request, response::Response, router::Next};
+    /// # let auth = BasicAuth::new("Admin").user("root", "secret");
+    /// # let request = &Request::read_from(&mut &b"GET / HTTP/1.1\r\n\r\n"[..])?;
+    /// # let next: Next<'_> = &|_| Response::new(200, "OK");
+    /// let response = auth.handle(request, next);
+    /// # assert_eq!(response.status, 401);
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn handle(&self, request: &Request, next: Next<'_>) -> Response {
+        match self.authenticate(request) {
+            Some(user) => {
+                let mut request = request.clone();
+                request.extensions.insert(AuthenticatedUser(user));
+                next(&request)
+            }
+            None => unauthorized(format!(r#"Basic realm="{}", charset="UTF-8""#, quote(&self.realm))),
+        }
+    }
+
+    /// # authenticate
+    ///
+    /// **Purpose:**
+    /// Decodes the request's Basic credentials and verifies them locally or through the providers.
+    ///
+    /// **Parameters:**
+    /// - `request`: Request being checked.
+    ///
+    /// **Returns:**
+    /// - `Some(user)` for valid credentials, otherwise `None`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let user = self.authenticate(request);
+    /// ```
+    fn authenticate(&self, request: &Request) -> Option<String> {
+        self.verify(authorization(request, "Basic")?)
+    }
+
+    /// # authenticate_proxy
+    ///
+    /// **Purpose:**
+    /// Verifies the Basic credentials a client sends to a proxy in `Proxy-Authorization`.
+    ///
+    /// **Parameters:**
+    /// - `request`: Request addressed to the proxy.
+    ///
+    /// **Returns:**
+    /// - `Some(user)` for valid credentials, otherwise `None`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// if auth.authenticate_proxy(request).is_none() { return auth.proxy_challenge(); }
+    /// ```
+    pub(crate) fn authenticate_proxy(&self, request: &Request) -> Option<String> {
+        let (given, encoded) = request.headers.get("Proxy-Authorization")?.trim().split_once(' ')?;
+        self.verify(given.eq_ignore_ascii_case("Basic").then(|| encoded.trim())?)
+    }
+
+    /// # proxy_challenge
+    ///
+    /// **Purpose:**
+    /// Builds the `407 Proxy Authentication Required` answer for clients without valid
+    /// proxy credentials.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - The rejection `Response`, carrying a Basic `Proxy-Authenticate` challenge.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// return auth.proxy_challenge().write_to(&mut out);
+    /// ```
+    pub(crate) fn proxy_challenge(&self) -> Response {
+        Response::new(407, "Proxy Authentication Required")
+            .with_header("Proxy-Authenticate", format!(r#"Basic realm="{}", charset="UTF-8""#, quote(&self.realm)))
+            .with_header("Content-Type", "text/plain; charset=utf-8")
+            .with_body("proxy authentication required")
+    }
+
+    /// # verify
+    ///
+    /// **Purpose:**
+    /// Decodes Basic credentials and checks them locally or through the providers.
+    ///
+    /// **Parameters:**
+    /// - `encoded`: Base64 `user:password` text following the `Basic` scheme.
+    ///
+    /// **Returns:**
+    /// - `Some(user)` for valid credentials, otherwise `None`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let user = self.verify(authorization(request, "Basic")?);
+    /// ```
+    fn verify(&self, encoded: &str) -> Option<String> {
+        let decoded = String::from_utf8(base64::decode(encoded)?).ok()?;
+        let (user, password) = decoded.split_once(':')?;
+        match self.users.get(user) {
+            Some(credential) => credential.verify(password).then(|| user.to_string()),
+            None => self
+                .providers
+                .authenticate(&Credentials::Basic { user, password })
+                .map(|AuthenticatedUser(name)| name),
+        }
+    }
+}
+
+
+/// # authorization
+///
+/// **Purpose:**
+/// Returns the credentials of an `Authorization` header using the given scheme.
+///
+/// **Parameters:**
+/// - `request`: Request being checked.
+/// - `scheme`: Expected scheme (e.g. `Basic`), compared case-insensitively.
+///
+/// **Returns:**
+/// - `Some(credentials)` without the scheme, otherwise `None`.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let token = authorization(request, "Bearer");
+/// ```
+pub(crate) fn authorization<'a>(request: &'a Request, scheme: &str) -> Option<&'a str> {
+    let (given, credentials) = request.headers.get("Authorization")?.trim().split_once(' ')?;
+    given.eq_ignore_ascii_case(scheme).then(|| credentials.trim())
+}
+
+
+/// # unauthorized
+///
+/// **Purpose:**
+/// Builds a `401 Unauthorized` response carrying a challenge.
+///
+/// **Parameters:**
+/// - `challenge`: `WWW-Authenticate` value.
+///
+/// **Returns:**
+/// - The rejection `Response`.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// return unauthorized(r#"Basic realm="Admin""#.to_string());
+/// ```
+pub(crate) fn unauthorized(challenge: String) -> Response {
+    Response::new(401, "Unauthorized")
+        .with_header("WWW-Authenticate", challenge)
+        .with_header("Content-Type", "text/plain; charset=utf-8")
+        .with_body("authentication required")
+}
+
+
+/// # quote
+///
+/// **Purpose:**
+/// Escapes a value for use inside a quoted-string.
+///
+/// **Parameters:**
+/// - `value`: Raw value.
+///
+/// **Returns:**
+/// - The value with `"` and `\` backslash-escaped.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let header = format!(r#"realm="{}""#, quote(realm));
+/// ```
+pub(crate) fn quote(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+
+/// # verify_bcrypt
+///
+/// **Purpose:**
+/// Checks a password against a bcrypt hash.
+///
+/// **Parameters:**
+/// - `hash`: Stored bcrypt hash.
+/// - `password`: Candidate password.
+///
+/// **Returns:**
+/// - `true` if it matches; always `false` without the `bcrypt` feature.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let ok = verify_bcrypt(hash, password);
+/// ```
+fn verify_bcrypt(hash: &str, password: &str) -> bool {
+    #[cfg(feature = "bcrypt")]
+    return bcrypt::verify(password, hash).unwrap_or(false);
+    #[cfg(not(feature = "bcrypt"))]
+    {
+        let _ = (hash, password);
+        false
+    }
+}
+
+
+/// # verify_argon2
+///
+/// **Purpose:**
+/// Checks a password against an Argon2 PHC string.
+///
+/// **Parameters:**
+/// - `hash`: Stored Argon2 hash.
+/// - `password`: Candidate password.
+///
+/// **Returns:**
+/// - `true` if it matches; always `false` without the `argon2` feature.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let ok = verify_argon2(hash, password);
+/// ```
+fn verify_argon2(hash: &str, password: &str) -> bool {
+    #[cfg(feature = "argon2")]
+    {
+        use argon2::{Argon2, PasswordHash, PasswordVerifier};
+        PasswordHash::new(hash)
+            .is_ok_and(|parsed| Argon2::default().verify_password(password.as_bytes(), &parsed).is_ok())
+    }
+    #[cfg(not(feature = "argon2"))]
+    {
+        let _ = (hash, password);
+        false
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(authorization: &str) -> Request {
+        Request::read_from(&mut format!("GET / HTTP/1.1\r\nHost: example.com\r\n{authorization}\r\n").as_bytes()).unwrap()
+    }
+
+    #[test]
+    fn providers_answer_for_users_not_configured_locally() {
+        let auth = BasicAuth::new("Staff").user("ana", "s3cret").provider(|credentials: &Credentials<'_>| match credentials {
+            Credentials::Basic { user: "bob", password: "hunter2" } => Some(AuthenticatedUser("bob".to_string())),
+            _ => None,
+        });
+        assert_eq!(auth.authenticate(&request("Authorization: Basic YW5hOnMzY3JldA==\r\n")).as_deref(), Some("ana"));
+        assert_eq!(auth.authenticate(&request("Authorization: Basic Ym9iOmh1bnRlcjI=\r\n")).as_deref(), Some("bob"));
+        assert_eq!(auth.authenticate(&request("Authorization: Basic Ym9iOnMzY3JldA==\r\n")), None);
+        assert_eq!(auth.authenticate(&request("")), None);
+    }
+
+    #[test]
+    fn htpasswd_files_refuse_malformed_lines_and_unknown_hashes() {
+        let path = std::env::temp_dir().join(format!("auth-test-{}.htpasswd", std::process::id()));
+        for contents in ["ana\n", "ana:plain-text\n", "# users\n\nana:{SHA}W6ph5Mm5Pz8GgiULbPgzG37mj9g=\n"] {
+            fs::write(&path, contents).unwrap();
+            assert_eq!(BasicAuth::new("Staff").htpasswd(&path).unwrap_err().kind(), io::ErrorKind::InvalidData, "{contents:?}");
+        }
+        fs::write(&path, "# no users yet\n").unwrap();
+        assert!(BasicAuth::new("Staff").htpasswd(&path).is_ok());
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn challenges_quote_the_realm() {
+        let response = unauthorized(format!(r#"Basic realm="{}""#, quote(r#"The "inner" \ area"#)));
+        assert_eq!(response.status, 401);
+        assert_eq!(response.headers.get("WWW-Authenticate"), Some(r#"Basic realm="The \"inner\" \\ area""#));
+    }
+}
@@ -0,0 +1,200 @@
+
+// ============================================================
+//  DAEGONICA SOFTWARE — base64.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: Base64
+//!
+//! **Purpose:**
+//! Encodes and decodes Base64 text found in HTTP credentials and tokens.
+//!
+//! **Context:**
+//! - Internal helper for the authentication layers.
+//!
+//! **Responsibilities:**
+//! - Encodes with the standard alphabet (padded) or the URL-safe alphabet (unpadded).
+//! - Decodes the standard and URL-safe alphabets, with or without `=` padding.
+//! - Does NOT accept embedded whitespace.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+
+/// # STANDARD
+///
+/// **Summary:**
+/// Standard alphabet (RFC 4648 §4).
+const STANDARD: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+
+/// # URL_SAFE
+///
+/// **Summary:**
+/// URL- and filename-safe alphabet (RFC 4648 §5).
+const URL_SAFE: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+
+/// # encode
+///
+/// **Purpose:**
+/// Encodes bytes with the standard alphabet and `=` padding.
+///
+/// **Parameters:**
+/// - `bytes`: Data to encode.
+///
+/// **Returns:**
+/// - The encoded text.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// assert_eq!(base64::encode(b"user:pass"), "dXNlcjpwYXNz");
+/// ```
+pub(crate) fn encode(bytes: &[u8]) -> String {
+    let mut text = encode_with(bytes, STANDARD);
+    let padding = (4 - text.len() % 4) % 4;
+    text.push_str(&"=".repeat(padding));
+    text
+}
+
+
+/// # encode_url
+///
+/// **Purpose:**
+/// Encodes bytes with the URL-safe alphabet and no padding (as used by JWTs and PKCE).
+///
+/// **Parameters:**
+/// - `bytes`: Data to encode.
+///
+/// **Returns:**
+/// - The encoded text.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let challenge = base64::encode_url(&hash::sha256(verifier.as_bytes()));
+/// ```
+pub(crate) fn encode_url(bytes: &[u8]) -> String {
+    encode_with(bytes, URL_SAFE)
+}
+
+
+/// # decode
+///
+/// **Purpose:**
+/// Decodes Base64 text in either alphabet (`+/` or `-_`).
+///
+/// **Parameters:**
+/// - `text`: Encoded text; trailing `=` padding is optional.
+///
+/// **Returns:**
+/// - `Some(bytes)` on success, otherwise `None`.
+///
+/// **Errors / Failures:**
+/// - Returns `None` for characters outside the alphabets or an impossible length.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// assert_eq!(base64::decode("dXNlcjpwYXNz").unwrap(), b"user:pass");
+/// ```
+pub(crate) fn decode(text: &str) -> Option<Vec<u8>> {
+    let text = text.trim_end_matches('=');
+    if text.len() % 4 == 1 {
+        return None;
+    }
+
+    let mut out = Vec::with_capacity(text.len() * 3 / 4);
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for byte in text.bytes() {
+        let value = match byte {
+            b'A'..=b'Z' => byte - b'A',
+            b'a'..=b'z' => byte - b'a' + 26,
+            b'0'..=b'9' => byte - b'0' + 52,
+            b'+' | b'-' => 62,
+            b'/' | b'_' => 63,
+            _ => return None,
+        };
+        buffer = (buffer << 6) | u32::from(value);
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+    Some(out)
+}
+
+
+/// # encode_with
+///
+/// **Purpose:**
+/// Encodes bytes with the given alphabet, without padding.
+///
+/// **Parameters:**
+/// - `bytes`: Data to encode.
+/// - `alphabet`: 64 output characters.
+///
+/// **Returns:**
+/// - The encoded text.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let text = encode_with(bytes, URL_SAFE);
+/// ```
+fn encode_with(bytes: &[u8], alphabet: &[u8; 64]) -> String {
+    let mut text = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let group = chunk.iter().enumerate().fold(0u32, |group, (index, byte)| group | u32::from(*byte) << (16 - 8 * index));
+        for index in 0..=chunk.len() {
+            text.push(char::from(alphabet[(group >> (18 - 6 * index) & 0x3f) as usize]));
+        }
+    }
+    text
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encodes_rfc_4648_vectors() {
+        let vectors = [("", ""), ("f", "Zg=="), ("fo", "Zm8="), ("foo", "Zm9v"), ("foob", "Zm9vYg=="), ("fooba", "Zm9vYmE="), ("foobar", "Zm9vYmFy")];
+        for (plain, encoded) in vectors {
+            assert_eq!(encode(plain.as_bytes()), encoded);
+            assert_eq!(encode_url(plain.as_bytes()), encoded.trim_end_matches('='));
+            assert_eq!(decode(encoded).unwrap(), plain.as_bytes());
+        }
+    }
+
+    #[test]
+    fn url_alphabet_round_trips() {
+        let bytes = [0xfb, 0xff, 0xbf];
+        assert_eq!(encode(&bytes), "+/+/");
+        assert_eq!(encode_url(&bytes), "-_-_");
+        assert_eq!(decode("-_-_").unwrap(), bytes);
+        assert_eq!(decode("+/+/").unwrap(), bytes);
+    }
+
+    #[test]
+    fn rejects_foreign_characters_and_impossible_lengths() {
+        assert_eq!(decode("Zm9v!"), None);
+        assert_eq!(decode("Zm9 v"), None);
+        assert_eq!(decode("Zm9vY"), None);
+    }
+}
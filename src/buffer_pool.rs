@@ -0,0 +1,343 @@
+
+// ============================================================
+//  DAEGONICA SOFTWARE — buffer_pool.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: BufferPool
+//!
+//! **Purpose:**
+//! Recycles the scratch buffers connections use for request lines and response heads, so a
+//! busy server stops allocating and freeing them on every request.
+//!
+//! **Context:**
+//! - Owned by the `Server` (replaceable with `Server::buffer_pool`); each connection checks
+//!   out one buffer when it opens and returns it when it closes.
+//!
+//! **Responsibilities:**
+//! - Hands out cleared buffers, reusing idle ones before allocating.
+//! - Takes buffers back when their guard is dropped, keeping at most a fixed number idle and
+//!   discarding any that grew far past the configured size.
+//! - Counts allocations and reuses.
+//! - Does NOT back the connection's `BufReader`, whose buffer the standard library allocates.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+use std::{
+    ops::{Deref, DerefMut},
+    sync::{
+        Arc, Mutex, PoisonError,
+        atomic::{AtomicU64, Ordering},
+    },
+};
+
+
+/// # DEFAULT_BUFFER_SIZE
+///
+/// **Summary:**
+/// Initial capacity of each buffer unless `BufferPool::new` says otherwise.
+const DEFAULT_BUFFER_SIZE: usize = 4 * 1024;
+
+
+/// # DEFAULT_MAX_IDLE
+///
+/// **Summary:**
+/// Most buffers kept idle unless `BufferPool::new` says otherwise.
+const DEFAULT_MAX_IDLE: usize = 256;
+
+
+/// # GROWTH_LIMIT
+///
+/// **Summary:**
+/// Returned buffers whose capacity exceeds this multiple of the buffer size are freed instead
+/// of pooled, so one huge request head does not pin memory for good.
+const GROWTH_LIMIT: usize = 4;
+
+
+/// # BufferPool
+///
+/// **Summary:**
+/// Thread-safe pool of reusable byte buffers.
+///
+/// **Fields:**
+/// - `buffer_size`: Capacity given to newly allocated buffers.
+/// - `max_idle`: Most buffers kept for reuse.
+/// - `idle`: Buffers waiting to be checked out.
+/// - `allocated`: Buffers allocated because none was idle.
+/// - `reused`: Checkouts answered from the idle list.
+///
+/// **Usage Example:**
+/// ```rust,no_run
+/// # use server::{buffer_pool::BufferPool, server::Server};
+/// # use std::sync::Arc;
+/// let pool = Arc::new(BufferPool::new(8 * 1024, 1024));
+/// Server::new("127.0.0.1:7878").buffer_pool(Arc::clone(&pool)).run()?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct BufferPool {
+    buffer_size: usize,
+    max_idle: usize,
+    idle: Mutex<Vec<Vec<u8>>>,
+    allocated: AtomicU64,
+    reused: AtomicU64,
+}
+
+
+/// # BufferPoolStats
+///
+/// **Summary:**
+/// Point-in-time snapshot of buffer pool counters.
+///
+/// **Fields:**
+/// - `idle`: Buffers currently waiting for reuse.
+/// - `allocated`: Buffers allocated because none was idle.
+/// - `reused`: Checkouts answered from the idle list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BufferPoolStats {
+    pub idle: usize,
+    pub allocated: u64,
+    pub reused: u64,
+}
+
+
+/// # PooledBuffer
+///
+/// **Summary:**
+/// Buffer checked out of a `BufferPool`; dereferences to `Vec<u8>` and goes back to the pool
+/// when dropped.
+///
+/// **Fields:**
+/// - `buffer`: The buffer.
+/// - `pool`: Pool it returns to.
+#[derive(Debug)]
+pub struct PooledBuffer {
+    buffer: Vec<u8>,
+    pool: Arc<BufferPool>,
+}
+
+
+impl Default for BufferPool {
+
+    /// # default
+    ///
+    /// **Purpose:**
+    /// Creates a pool of 4 KiB buffers keeping up to 256 idle.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - A new, empty `BufferPool`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::buffer_pool::BufferPool;
+    /// let pool = BufferPool::default();
+    /// ```
+    fn default() -> Self {
+        BufferPool::new(DEFAULT_BUFFER_SIZE, DEFAULT_MAX_IDLE)
+    }
+}
+
+
+impl BufferPool {
+
+    /// # new
+    ///
+    /// **Purpose:**
+    /// Creates an empty pool.
+    ///
+    /// **Parameters:**
+    /// - `buffer_size`: Initial capacity of each buffer; size it to a typical request head.
+    /// - `max_idle`: Most buffers kept for reuse, usually about the number of workers.
+    ///
+    /// **Returns:**
+    /// - A new `BufferPool`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::buffer_pool::BufferPool;
+    /// let pool = BufferPool::new(8 * 1024, 1024);
+    /// ```
+    pub fn new(buffer_size: usize, max_idle: usize) -> BufferPool {
+        BufferPool {
+            buffer_size,
+            max_idle,
+            idle: Mutex::new(Vec::new()),
+            allocated: AtomicU64::new(0),
+            reused: AtomicU64::new(0),
+        }
+    }
+
+    /// # checkout
+    ///
+    /// **Purpose:**
+    /// Takes an empty buffer from the pool, allocating one if none is idle.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - The buffer, returned to this pool when dropped.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::buffer_pool::BufferPool;
+    /// # use std::sync::Arc;
+    /// # let pool = Arc::new(BufferPool::new(8 * 1024, 1024));
+    /// let mut scratch = pool.checkout();
+    /// scratch.extend_from_slice(b"HTTP/1.1 200 OK\r\n");
+    /// ```
+    pub fn checkout(self: &Arc<BufferPool>) -> PooledBuffer {
+        let idle = self.idle.lock().unwrap_or_else(PoisonError::into_inner).pop();
+        let buffer = match idle {
+            Some(buffer) => {
+                self.reused.fetch_add(1, Ordering::Relaxed);
+                buffer
+            }
+            None => {
+                self.allocated.fetch_add(1, Ordering::Relaxed);
+                Vec::with_capacity(self.buffer_size)
+            }
+        };
+        PooledBuffer { buffer, pool: Arc::clone(self) }
+    }
+
+    /// # stats
+    ///
+    /// **Purpose:**
+    /// Returns current pool counters.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - A `BufferPoolStats` snapshot.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # let pool = server::buffer_pool::BufferPool::new(8 * 1024, 1024);
+    /// println!("buffers reused: {}", pool.stats().reused);
+    /// ```
+    pub fn stats(&self) -> BufferPoolStats {
+        BufferPoolStats {
+            idle: self.idle.lock().unwrap_or_else(PoisonError::into_inner).len(),
+            allocated: self.allocated.load(Ordering::Relaxed),
+            reused: self.reused.load(Ordering::Relaxed),
+        }
+    }
+}
+
+
+impl Deref for PooledBuffer {
+    type Target = Vec<u8>;
+
+    /// # deref
+    ///
+    /// **Purpose:**
+    /// Borrows the buffer.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - The underlying `Vec<u8>`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # let pool = std::sync::Arc::new(server::buffer_pool::BufferPool::new(8 * 1024, 1024));
+    /// # let scratch = pool.checkout();
+    /// let used = scratch.len();
+    /// ```
+    fn deref(&self) -> &Vec<u8> {
+        &self.buffer
+    }
+}
+
+
+impl DerefMut for PooledBuffer {
+
+    /// # deref_mut
+    ///
+    /// **Purpose:**
+    /// Borrows the buffer mutably.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - The underlying `Vec<u8>`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # let pool = std::sync::Arc::new(server::buffer_pool::BufferPool::new(8 * 1024, 1024));
+    /// # let mut scratch = pool.checkout();
+    /// scratch.clear();
+    /// ```
+    fn deref_mut(&mut self) -> &mut Vec<u8> {
+        &mut self.buffer
+    }
+}
+
+
+impl Drop for PooledBuffer {
+
+    /// # drop
+    ///
+    /// **Purpose:**
+    /// Clears the buffer and returns it to the pool, unless the pool is full or the buffer grew
+    /// too large to keep.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// None.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # let pool = std::sync::Arc::new(server::buffer_pool::BufferPool::new(8 * 1024, 1024));
+    /// # let scratch = pool.checkout();
+    /// drop(scratch);
+    /// # assert_eq!(pool.stats().idle, 1);
+    /// ```
+    fn drop(&mut self) {
+        if self.buffer.capacity() > self.pool.buffer_size.saturating_mul(GROWTH_LIMIT) {
+            return;
+        }
+        let mut buffer = std::mem::take(&mut self.buffer);
+        buffer.clear();
+        let mut idle = self.pool.idle.lock().unwrap_or_else(PoisonError::into_inner);
+        if idle.len() < self.pool.max_idle {
+            idle.push(buffer);
+        }
+    }
+}
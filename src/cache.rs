@@ -0,0 +1,1182 @@
+
+// ============================================================
+//  DAEGONICA SOFTWARE — cache.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: ResponseCache
+//!
+//! **Purpose:**
+//! Provides a shared in-memory HTTP cache for proxied and dynamically generated responses.
+//!
+//! **Context:**
+//! - Consulted by the connection handler before a `GET` request is proxied or handled locally,
+//!   for paths that opted in with `ResponseCache::route`. A hit is answered before router
+//!   middleware (IP rules, rate limits, authentication) runs, so only routes whose responses may
+//!   go to every client should opt in; nothing is cached by default.
+//!
+//! **Responsibilities:**
+//! - Stores responses that `Cache-Control` marks as cacheable, keyed by method, URL, and the
+//!   request's values for the headers named in `Vary`, so a compressed body is never served to a
+//!   client that did not ask for one. List headers such as `Accept-Encoding` and
+//!   `Accept-Language` are normalized first, so cosmetic differences share one variant.
+//! - Keeps at most `MAX_VARIANTS` variants per URL, dropping that URL's least recently used one.
+//! - Evicts least-recently-used entries once the configured byte budget is exceeded.
+//! - Serves stale entries inside their `stale-while-revalidate` window and asks one caller to refresh them.
+//! - Coalesces concurrent misses for the same key into a single origin fetch, for URLs whose
+//!   responses are already known to be cacheable.
+//! - Tracks hit/miss counters.
+//! - Does NOT revalidate stale entries with conditional requests.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    io,
+    sync::{
+        Arc, Condvar, Mutex, PoisonError,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+use crate::{glob::Glob, request::Request, response::Response};
+
+
+/// # CACHEABLE_STATUSES
+///
+/// **Summary:**
+/// Status codes this cache will store when the response carries an explicit freshness lifetime.
+const CACHEABLE_STATUSES: [u16; 8] = [200, 203, 204, 300, 301, 308, 404, 410];
+
+
+/// # MAX_VARIANTS
+///
+/// **Summary:**
+/// Most variants stored for one method+URL, so clients sending unusual `Vary`-ed headers cannot
+/// crowd every other URL out of the cache.
+const MAX_VARIANTS: usize = 8;
+
+
+/// # ResponseCache
+///
+/// **Summary:**
+/// Thread-safe, size-bounded HTTP response cache.
+///
+/// **Fields:**
+/// - `max_bytes`: Upper bound on the total size of stored responses.
+/// - `routes`: Path patterns whose `GET` requests use the cache; none by default.
+/// - `state`: Entries, `Vary` rules, and recency order behind one lock.
+/// - `inflight`: Origin fetches currently running, by variant key.
+/// - `hits`: Number of lookups answered from the cache.
+/// - `misses`: Number of lookups that had to go to the origin.
+/// - `coalesced`: Number of misses answered by another caller's in-flight fetch.
+///
+/// **Usage Example:**
+/// ```rust
+/// # use server::{cache::ResponseCache, request::Request, response::Response};
+/// # let request = Request::read_from(&mut &b"GET /assets/app.css HTTP/1.1\r\n\r\n"[..])?;
+/// # let response = Response::new(200, "OK").with_header("Cache-Control", "max-age=60").with_body("body {}");
+/// let cache = ResponseCache::new(16 * 1024 * 1024).route("/assets/**");
+/// if let Some(hit) = cache.lookup(&request) { /* serve hit */ }
+/// cache.store(&request, &response);
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub struct ResponseCache {
+    max_bytes: usize,
+    routes: Vec<Glob>,
+    state: Mutex<CacheState>,
+    inflight: Mutex<HashMap<String, Arc<InFlight>>>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    coalesced: AtomicU64,
+}
+
+
+/// # CacheHit
+///
+/// **Summary:**
+/// A response answered from the cache.
+///
+/// **Fields:**
+/// - `response`: Cached response, with an `Age` header.
+/// - `revalidate`: `true` if the entry is stale and this caller should refresh it in the background.
+///   Only the first stale hit for an entry is asked to refresh.
+#[derive(Debug, Clone)]
+pub struct CacheHit {
+    pub response: Response,
+    pub revalidate: bool,
+}
+
+
+/// # CacheStats
+///
+/// **Summary:**
+/// Point-in-time snapshot of cache counters.
+///
+/// **Fields:**
+/// - `hits`: Lookups answered from the cache.
+/// - `misses`: Lookups that were not answered from the cache.
+/// - `coalesced`: Misses answered by another caller's in-flight fetch.
+/// - `entries`: Number of stored responses.
+/// - `bytes`: Approximate size of all stored responses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub coalesced: u64,
+    pub entries: usize,
+    pub bytes: usize,
+}
+
+
+/// # CacheState
+///
+/// **Summary:**
+/// Mutable cache contents guarded by the `ResponseCache` lock.
+///
+/// **Fields:**
+/// - `entries`: Stored responses by variant key.
+/// - `vary`: Header names listed in `Vary` for each method+URL.
+/// - `variants`: Variant keys stored for each method+URL.
+/// - `recency`: Variant keys ordered by last use (oldest first).
+/// - `tick`: Monotonic use counter feeding `recency`.
+/// - `bytes`: Total size of stored entries.
+#[derive(Default)]
+struct CacheState {
+    entries: HashMap<String, CacheEntry>,
+    vary: HashMap<String, Vec<String>>,
+    variants: HashMap<String, Vec<String>>,
+    recency: BTreeMap<u64, String>,
+    tick: u64,
+    bytes: usize,
+}
+
+
+/// # CacheEntry
+///
+/// **Summary:**
+/// A stored response and its freshness bookkeeping.
+///
+/// **Fields:**
+/// - `response`: The cached response.
+/// - `stored_at`: When the response was stored.
+/// - `ttl`: Freshness lifetime from `s-maxage` / `max-age`.
+/// - `stale_window`: Extra time the entry may be served stale from `stale-while-revalidate`.
+/// - `refreshing`: Whether a caller has already been asked to refresh the stale entry.
+/// - `size`: Approximate size used for eviction accounting.
+/// - `last_used`: Recency tick of the last store or hit.
+struct CacheEntry {
+    response: Response,
+    stored_at: Instant,
+    ttl: Duration,
+    stale_window: Duration,
+    refreshing: bool,
+    size: usize,
+    last_used: u64,
+}
+
+
+/// # InFlight
+///
+/// **Summary:**
+/// Rendezvous point for callers waiting on one origin fetch.
+///
+/// **Fields:**
+/// - `stored`: `None` while the fetch runs; then `Some(true)` if its response was stored, so
+///   waiters can look their own variant up, or `Some(false)` if each must fetch for itself.
+/// - `done`: Signalled once `stored` is set.
+#[derive(Default)]
+struct InFlight {
+    stored: Mutex<Option<bool>>,
+    done: Condvar,
+}
+
+
+/// # Leader
+///
+/// **Summary:**
+/// Held by the caller performing an in-flight fetch. Dropping it (even while unwinding from a
+/// panic) releases the key and wakes every waiter.
+///
+/// **Fields:**
+/// - `inflight`: The cache's in-flight map.
+/// - `key`: Variant key being fetched.
+/// - `flight`: Shared rendezvous for waiters.
+/// - `stored`: Whether the fetched response was stored.
+struct Leader<'a> {
+    inflight: &'a Mutex<HashMap<String, Arc<InFlight>>>,
+    key: String,
+    flight: Arc<InFlight>,
+    stored: bool,
+}
+
+
+impl Drop for Leader<'_> {
+    /// # drop
+    ///
+    /// **Purpose:**
+    /// Removes the in-flight key and publishes the outcome to waiters.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// None.
+    ///
+    /// **Errors / Failures:**
+    /// None. Poisoned locks are recovered so waiters are never stranded.
+    fn drop(&mut self) {
+        self.inflight
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(&self.key);
+        *self.flight.stored.lock().unwrap_or_else(PoisonError::into_inner) = Some(self.stored);
+        self.flight.done.notify_all();
+    }
+}
+
+
+impl ResponseCache {
+
+    /// # new
+    ///
+    /// **Purpose:**
+    /// Creates an empty cache bounded to `max_bytes` of stored responses. No route uses it
+    /// until one opts in with `route`.
+    ///
+    /// **Parameters:**
+    /// - `max_bytes`: Maximum total size of cached responses.
+    ///
+    /// **Returns:**
+    /// - A new `ResponseCache` instance.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::cache::ResponseCache;
+    /// let cache = ResponseCache::new(16 * 1024 * 1024);
+    /// ```
+    pub fn new(max_bytes: usize) -> ResponseCache {
+        ResponseCache {
+            max_bytes,
+            routes: Vec::new(),
+            state: Mutex::new(CacheState::default()),
+            inflight: Mutex::new(HashMap::new()),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+            coalesced: AtomicU64::new(0),
+        }
+    }
+
+    /// # route
+    ///
+    /// **Purpose:**
+    /// Opts the paths matching `pattern` into the cache. Cached responses are served without
+    /// running router middleware, so only opt in routes that answer every client alike.
+    ///
+    /// **Parameters:**
+    /// - `pattern`: Path glob (e.g. `/assets/**`).
+    ///
+    /// **Returns:**
+    /// - The updated `ResponseCache`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::cache::ResponseCache;
+    /// let cache = ResponseCache::new(16 * 1024 * 1024).route("/assets/**").route("/feed.xml");
+    /// ```
+    pub fn route(mut self, pattern: &str) -> ResponseCache {
+        self.routes.push(Glob::new(pattern));
+        self
+    }
+
+    /// # resized
+    ///
+    /// **Purpose:**
+    /// Creates an empty cache with a new byte budget and the same opted-in routes.
+    ///
+    /// **Parameters:**
+    /// - `max_bytes`: Maximum total size of cached responses.
+    ///
+    /// **Returns:**
+    /// - The new `ResponseCache`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// self.cache = self.cache.resized(64 * 1024 * 1024);
+    /// ```
+    pub(crate) fn resized(self, max_bytes: usize) -> ResponseCache {
+        ResponseCache { routes: self.routes, ..ResponseCache::new(max_bytes) }
+    }
+
+    /// # covers
+    ///
+    /// **Purpose:**
+    /// Checks whether a request path opted into the cache.
+    ///
+    /// **Parameters:**
+    /// - `path`: Request path, without the query.
+    ///
+    /// **Returns:**
+    /// - `true` if a `route` pattern matches `path`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let cacheable = request.method == "GET" && self.cache.covers(request.path());
+    /// ```
+    pub(crate) fn covers(&self, path: &str) -> bool {
+        self.routes.iter().any(|pattern| pattern.matches(path))
+    }
+
+    /// # lookup
+    ///
+    /// **Purpose:**
+    /// Returns a cached response for `request`, adding an `Age` header. Entries past their
+    /// freshness lifetime are still returned while inside their `stale-while-revalidate` window.
+    /// Requests sending `Cache-Control: no-cache` or `no-store` always miss.
+    ///
+    /// **Parameters:**
+    /// - `request`: Incoming client request.
+    ///
+    /// **Returns:**
+    /// - `Some(hit)` on a hit, otherwise `None`.
+    ///
+    /// **Errors / Failures:**
+    /// - Panics if the cache lock is poisoned.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{cache::ResponseCache, request::Request};
+    /// # let cache = ResponseCache::new(16 * 1024 * 1024).route("/assets/**");
+    /// # let request = Request::read_from(&mut &b"GET /assets/app.css HTTP/1.1\r\n\r\n"[..])?;
+    /// # let mut stream = Vec::new();
+    /// if let Some(hit) = cache.lookup(&request) { hit.response.write_to(&mut stream)?; }
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn lookup(&self, request: &Request) -> Option<CacheHit> {
+        let found = if bypasses_cache(request) {
+            None
+        } else {
+            self.state.lock().unwrap().take_usable(request)
+        };
+
+        match found {
+            Some(_) => self.hits.fetch_add(1, Ordering::Relaxed),
+            None => self.misses.fetch_add(1, Ordering::Relaxed),
+        };
+        found
+    }
+
+    /// # store
+    ///
+    /// **Purpose:**
+    /// Stores `response` for `request` when both allow shared caching, then evicts
+    /// least-recently-used entries until the cache fits its byte budget. A `Vary` list that
+    /// differs from the one stored for the URL replaces its variants, and a URL already holding
+    /// `MAX_VARIANTS` variants loses its least recently used one. `Vary: *` is never stored.
+    ///
+    /// **Parameters:**
+    /// - `request`: Request the response answers.
+    /// - `response`: Response to store.
+    ///
+    /// **Returns:**
+    /// - `true` if the response was stored.
+    ///
+    /// **Errors / Failures:**
+    /// - Panics if the cache lock is poisoned.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{cache::ResponseCache, request::Request, response::Response};
+    /// # let cache = ResponseCache::new(16 * 1024 * 1024).route("/assets/**");
+    /// # let request = Request::read_from(&mut &b"GET /assets/app.css HTTP/1.1\r\n\r\n"[..])?;
+    /// # let response = Response::new(200, "OK").with_header("Cache-Control", "max-age=60").with_body("body {}");
+    /// cache.store(&request, &response);
+    /// # assert!(cache.lookup(&request).is_some());
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn store(&self, request: &Request, response: &Response) -> bool {
+        if request.method != "GET" || bypasses_cache(request) || request.headers.contains("Authorization") {
+            return false;
+        }
+        if !CACHEABLE_STATUSES.contains(&response.status) || response.headers.contains("Set-Cookie") {
+            return false;
+        }
+        let Some(ttl) = freshness_lifetime(response) else {
+            return false;
+        };
+        let stale_window = stale_while_revalidate(response);
+
+        let vary: Vec<String> = response
+            .headers
+            .get_all("Vary")
+            .into_iter()
+            .flat_map(|value| value.split(','))
+            .map(|name| name.trim().to_ascii_lowercase())
+            .filter(|name| !name.is_empty())
+            .collect();
+        if vary.iter().any(|name| name == "*") {
+            return false;
+        }
+
+        let size = response.body_len()
+            + response
+                .headers
+                .iter()
+                .map(|(name, value)| name.len() + value.len() + 4)
+                .sum::<usize>();
+        if size > self.max_bytes {
+            return false;
+        }
+
+        let mut state = self.state.lock().unwrap();
+        let base = base_key(request);
+        let key = variant_key(&base, &vary, request);
+        if state.vary.get(&base).is_some_and(|known| *known != vary) {
+            state.forget(&base);
+        }
+        state.remove(&key);
+        state.vary.insert(base.clone(), vary);
+        while state.variants.get(&base).is_some_and(|keys| keys.len() >= MAX_VARIANTS) {
+            let Some(oldest) = state.least_recent_variant(&base) else {
+                break;
+            };
+            state.remove(&oldest);
+        }
+        state.variants.entry(base).or_default().push(key.clone());
+
+        state.tick += 1;
+        let tick = state.tick;
+        state.recency.insert(tick, key.clone());
+        state.bytes += size;
+        state.entries.insert(
+            key,
+            CacheEntry {
+                response: response.clone(),
+                stored_at: Instant::now(),
+                ttl,
+                stale_window,
+                refreshing: false,
+                size,
+                last_used: tick,
+            },
+        );
+
+        while state.bytes > self.max_bytes {
+            let Some((_, oldest)) = state.recency.pop_first() else {
+                break;
+            };
+            state.remove(&oldest);
+        }
+        true
+    }
+
+    /// # fetch_coalesced
+    ///
+    /// **Purpose:**
+    /// Runs `fetch` for a cache miss and stores the result. Concurrent misses for a URL already
+    /// known to be cacheable (one with a stored entry, even an expired one) share one origin
+    /// fetch: the first caller fetches and stores, the rest wait and then look up their own
+    /// variant under the `Vary` rule just stored, fetching for themselves if it is not there.
+    /// Other misses, and requests that bypass the cache or carry `Authorization`, fetch at once.
+    /// A known URL whose fresh response is no longer cacheable is forgotten, so its later misses
+    /// stop waiting on each other.
+    ///
+    /// **Parameters:**
+    /// - `request`: Request that missed the cache.
+    /// - `fetch`: Closure producing the origin response.
+    ///
+    /// **Returns:**
+    /// - The origin (or shared) `Response`.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns the error produced by `fetch`.
+    /// - Panics if a cache lock is poisoned.
+    ///
+    /// **Examples:**
+    /// ```rust,no_run
+    /// # use server::{cache::ResponseCache, proxy::Proxy, request::Request};
+    /// # let cache = ResponseCache::new(16 * 1024 * 1024).route("/api/**");
+    /// # let proxy = Proxy::new("127.0.0.1:3000");
+    /// # let request = Request::read_from(&mut &b"GET /api/feed HTTP/1.1\r\n\r\n"[..])?;
+    /// let response = cache.fetch_coalesced(&request, || proxy.fetch(&request))?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn fetch_coalesced<F>(&self, request: &Request, fetch: F) -> io::Result<Response>
+    where
+        F: FnOnce() -> io::Result<Response>,
+    {
+        let known = if bypasses_cache(request) || request.headers.contains("Authorization") {
+            None
+        } else {
+            self.state.lock().unwrap().known_key(request)
+        };
+        let Some(key) = known else {
+            let result = fetch();
+            if let Ok(response) = &result {
+                self.store(request, response);
+            }
+            return result;
+        };
+        let (flight, leading) = {
+            let mut inflight = self.inflight.lock().unwrap();
+            match inflight.get(&key) {
+                Some(flight) => (Arc::clone(flight), false),
+                None => {
+                    let flight = Arc::new(InFlight::default());
+                    inflight.insert(key.clone(), Arc::clone(&flight));
+                    (flight, true)
+                }
+            }
+        };
+
+        if !leading {
+            let mut stored = flight.stored.lock().unwrap();
+            while stored.is_none() {
+                stored = flight.done.wait(stored).unwrap();
+            }
+            let shared = stored.unwrap_or(false);
+            drop(stored);
+            let hit = if shared { self.state.lock().unwrap().take_usable(request) } else { None };
+            if let Some(hit) = hit {
+                self.coalesced.fetch_add(1, Ordering::Relaxed);
+                return Ok(hit.response);
+            }
+            return fetch();
+        }
+
+        let mut leader = Leader {
+            inflight: &self.inflight,
+            key,
+            flight,
+            stored: false,
+        };
+        let result = fetch();
+        if let Ok(response) = &result {
+            leader.stored = self.store(request, response);
+            if !leader.stored {
+                self.state.lock().unwrap().forget(&base_key(request));
+            }
+        }
+        drop(leader);
+        result
+    }
+
+    /// # stats
+    ///
+    /// **Purpose:**
+    /// Returns the current hit/miss counters and occupancy.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - A `CacheStats` snapshot.
+    ///
+    /// **Errors / Failures:**
+    /// - Panics if the cache lock is poisoned.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # let cache = server::cache::ResponseCache::new(16 * 1024 * 1024);
+    /// let stats = cache.stats();
+    /// println!("cache hits: {}", stats.hits);
+    /// ```
+    pub fn stats(&self) -> CacheStats {
+        let state = self.state.lock().unwrap();
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            coalesced: self.coalesced.load(Ordering::Relaxed),
+            entries: state.entries.len(),
+            bytes: state.bytes,
+        }
+    }
+}
+
+
+impl CacheState {
+
+    /// # key_for
+    ///
+    /// **Purpose:**
+    /// Computes the variant key `request` would be stored under, using the `Vary` rules seen so far.
+    ///
+    /// **Parameters:**
+    /// - `request`: Incoming client request.
+    ///
+    /// **Returns:**
+    /// - The variant key.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let key = state.key_for(&request);
+    /// ```
+    fn key_for(&self, request: &Request) -> String {
+        let base = base_key(request);
+        let vary = self.vary.get(&base).map(Vec::as_slice).unwrap_or(&[]);
+        variant_key(&base, vary, request)
+    }
+
+    /// # known_key
+    ///
+    /// **Purpose:**
+    /// Computes the variant key of a request for a URL known to be cacheable.
+    ///
+    /// **Parameters:**
+    /// - `request`: Incoming client request.
+    ///
+    /// **Returns:**
+    /// - The variant key under the stored `Vary` rule, or `None` if the URL has no stored entry.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let known = state.known_key(&request);
+    /// ```
+    fn known_key(&self, request: &Request) -> Option<String> {
+        let base = base_key(request);
+        let vary = self.vary.get(&base)?;
+        Some(variant_key(&base, vary, request))
+    }
+
+    /// # take_usable
+    ///
+    /// **Purpose:**
+    /// Finds the variant matching `request`, refreshes its recency, and returns a copy if it is
+    /// fresh or still inside its stale window. Entries past both are left in place, unused, until
+    /// a store replaces them or eviction drops them, so the URL stays known as cacheable.
+    ///
+    /// **Parameters:**
+    /// - `request`: Incoming client request.
+    ///
+    /// **Returns:**
+    /// - `Some(hit)` with an `Age` header if a usable entry exists.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let hit = state.take_usable(&request);
+    /// ```
+    fn take_usable(&mut self, request: &Request) -> Option<CacheHit> {
+        let key = self.key_for(request);
+
+        let entry = self.entries.get(&key)?;
+        let age = entry.stored_at.elapsed();
+        if age >= entry.ttl + entry.stale_window {
+            return None;
+        }
+
+        self.tick += 1;
+        let tick = self.tick;
+        let entry = self.entries.get_mut(&key)?;
+        self.recency.remove(&entry.last_used);
+        self.recency.insert(tick, key);
+        entry.last_used = tick;
+
+        let revalidate = age >= entry.ttl && !entry.refreshing;
+        entry.refreshing |= revalidate;
+
+        let mut response = entry.response.clone();
+        response.headers.set("Age", age.as_secs().to_string());
+        Some(CacheHit {
+            response,
+            revalidate,
+        })
+    }
+
+    /// # least_recent_variant
+    ///
+    /// **Purpose:**
+    /// Finds the stored variant of a method+URL that was used longest ago.
+    ///
+    /// **Parameters:**
+    /// - `base`: Method+URL key.
+    ///
+    /// **Returns:**
+    /// - Its variant key, or `None` if the URL has no stored variants.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// if let Some(oldest) = state.least_recent_variant(&base) { state.remove(&oldest); }
+    /// ```
+    fn least_recent_variant(&self, base: &str) -> Option<String> {
+        self.variants
+            .get(base)?
+            .iter()
+            .min_by_key(|key| self.entries.get(key.as_str()).map_or(0, |entry| entry.last_used))
+            .cloned()
+    }
+
+    /// # forget
+    ///
+    /// **Purpose:**
+    /// Removes every stored variant of a method+URL, and with them its `Vary` rule.
+    ///
+    /// **Parameters:**
+    /// - `base`: Method+URL key.
+    ///
+    /// **Returns:**
+    /// None.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// state.forget(&base_key(&request));
+    /// ```
+    fn forget(&mut self, base: &str) {
+        for stale in self.variants.get(base).cloned().unwrap_or_default() {
+            self.remove(&stale);
+        }
+    }
+
+    /// # remove
+    ///
+    /// **Purpose:**
+    /// Removes a stored variant and its recency record, updating the byte total. The URL's
+    /// `Vary` rule is forgotten along with its last variant.
+    ///
+    /// **Parameters:**
+    /// - `key`: Variant key to remove.
+    ///
+    /// **Returns:**
+    /// None.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// state.remove(&key);
+    /// ```
+    fn remove(&mut self, key: &str) {
+        if let Some(entry) = self.entries.remove(key) {
+            self.recency.remove(&entry.last_used);
+            self.bytes -= entry.size;
+        }
+        let base = key.split('\n').next().unwrap_or(key);
+        if let Some(keys) = self.variants.get_mut(base) {
+            keys.retain(|stored| stored != key);
+            if keys.is_empty() {
+                self.variants.remove(base);
+                self.vary.remove(base);
+            }
+        }
+    }
+}
+
+
+/// # base_key
+///
+/// **Purpose:**
+/// Builds the method+URL part of a cache key.
+///
+/// **Parameters:**
+/// - `request`: Request to key.
+///
+/// **Returns:**
+/// - A key of the form `GET host/path?query`.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let base = base_key(&request);
+/// ```
+fn base_key(request: &Request) -> String {
+    let host = request.headers.get("Host").unwrap_or("");
+    format!("{} {}{}", request.method, host.to_ascii_lowercase(), request.target)
+}
+
+
+/// # variant_key
+///
+/// **Purpose:**
+/// Extends a base key with the request's normalized values for every header named in `Vary`.
+///
+/// **Parameters:**
+/// - `base`: Method+URL key.
+/// - `vary`: Lower-cased header names from the stored response's `Vary`.
+/// - `request`: Request supplying the header values.
+///
+/// **Returns:**
+/// - The full variant key.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let key = variant_key(&base, &["accept-encoding".into()], &request);
+/// ```
+fn variant_key(base: &str, vary: &[String], request: &Request) -> String {
+    let mut key = base.to_string();
+    for name in vary {
+        key.push('\n');
+        key.push_str(name);
+        key.push('=');
+        key.push_str(&normalize(name, &request.headers.get_all(name)));
+    }
+    key
+}
+
+
+/// # normalize
+///
+/// **Purpose:**
+/// Reduces a request's values for one `Vary`-ed header to a canonical form, so requests that
+/// mean the same thing share a variant.
+///
+/// **Parameters:**
+/// - `name`: Lower-cased header name.
+/// - `values`: Every value the request sent for it.
+///
+/// **Returns:**
+/// - For `accept-encoding` and `accept-language`, the list members lower-cased, stripped of
+///   whitespace, de-duplicated, and sorted; for `origin`, the value lower-cased; for any other
+///   header, the trimmed values joined with commas.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// assert_eq!(normalize("accept-encoding", &["br, GZIP"]), "br,gzip");
+/// ```
+fn normalize(name: &str, values: &[&str]) -> String {
+    match name {
+        "accept-encoding" | "accept-language" => {
+            let mut members: Vec<String> = values
+                .iter()
+                .flat_map(|value| value.split(','))
+                .map(|member| member.split_whitespace().collect::<String>().to_ascii_lowercase())
+                .filter(|member| !member.is_empty())
+                .collect();
+            members.sort_unstable();
+            members.dedup();
+            members.join(",")
+        }
+        "origin" => values.join(",").trim().to_ascii_lowercase(),
+        _ => values.iter().map(|value| value.trim()).collect::<Vec<_>>().join(","),
+    }
+}
+
+
+/// # bypasses_cache
+///
+/// **Purpose:**
+/// Checks whether the client asked not to be served from (or stored into) the cache.
+///
+/// **Parameters:**
+/// - `request`: Incoming client request.
+///
+/// **Returns:**
+/// - `true` if the request carries `Cache-Control: no-cache` or `no-store`.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// if bypasses_cache(&request) { /* go to origin */ }
+/// ```
+fn bypasses_cache(request: &Request) -> bool {
+    request.headers.has_token("Cache-Control", "no-cache")
+        || request.headers.has_token("Cache-Control", "no-store")
+}
+
+
+/// # freshness_lifetime
+///
+/// **Purpose:**
+/// Derives how long a response may be served from a shared cache, preferring `s-maxage` over `max-age`.
+///
+/// **Parameters:**
+/// - `response`: Response to inspect.
+///
+/// **Returns:**
+/// - `Some(duration)` for a positive lifetime, or `None` if the response is uncacheable.
+///
+/// **Errors / Failures:**
+/// None. Unparseable directives are treated as absent.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let ttl = freshness_lifetime(&response);
+/// ```
+fn freshness_lifetime(response: &Response) -> Option<Duration> {
+    let directives = cache_directives(response.headers.get_all("Cache-Control"));
+    let has = |name: &str| directives.iter().any(|(key, _)| key == name);
+    if has("no-store") || has("no-cache") || has("private") {
+        return None;
+    }
+
+    let lifetime = directive_seconds(&directives, "s-maxage")
+        .or_else(|| directive_seconds(&directives, "max-age"))?;
+    (lifetime > 0).then(|| Duration::from_secs(lifetime))
+}
+
+
+/// # stale_while_revalidate
+///
+/// **Purpose:**
+/// Reads the `stale-while-revalidate` window of a response.
+///
+/// **Parameters:**
+/// - `response`: Response to inspect.
+///
+/// **Returns:**
+/// - The window, or zero when the directive is absent or invalid.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let window = stale_while_revalidate(&response);
+/// ```
+fn stale_while_revalidate(response: &Response) -> Duration {
+    let directives = cache_directives(response.headers.get_all("Cache-Control"));
+    Duration::from_secs(directive_seconds(&directives, "stale-while-revalidate").unwrap_or(0))
+}
+
+
+/// # directive_seconds
+///
+/// **Purpose:**
+/// Parses the numeric argument of a `Cache-Control` directive.
+///
+/// **Parameters:**
+/// - `directives`: Parsed directives from `cache_directives`.
+/// - `name`: Lower-cased directive name.
+///
+/// **Returns:**
+/// - `Some(seconds)` if the directive is present with a valid number.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let max_age = directive_seconds(&directives, "max-age");
+/// ```
+fn directive_seconds(directives: &[(String, Option<String>)], name: &str) -> Option<u64> {
+    directives
+        .iter()
+        .find(|(key, _)| key == name)
+        .and_then(|(_, value)| value.as_deref()?.parse().ok())
+}
+
+
+/// # cache_directives
+///
+/// **Purpose:**
+/// Splits `Cache-Control` values into lower-cased directive names and optional unquoted arguments.
+///
+/// **Parameters:**
+/// - `values`: Every `Cache-Control` header value.
+///
+/// **Returns:**
+/// - A list of `(directive, argument)` pairs.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let directives = cache_directives(vec!["public, max-age=60"]);
+/// ```
+pub(crate) fn cache_directives(values: Vec<&str>) -> Vec<(String, Option<String>)> {
+    values
+        .into_iter()
+        .flat_map(|value| value.split(','))
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(|item| match item.split_once('=') {
+            Some((name, value)) => (
+                name.trim().to_ascii_lowercase(),
+                Some(value.trim().trim_matches('"').to_string()),
+            ),
+            None => (item.to_ascii_lowercase(), None),
+        })
+        .collect()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{sync::Barrier, thread, time::Duration};
+
+    fn get(path: &str, extra: &str) -> Request {
+        let raw = format!("GET {path} HTTP/1.1\r\nHost: example.com\r\n{extra}\r\n");
+        Request::read_from(&mut raw.as_bytes()).unwrap()
+    }
+
+    fn ok(cache_control: &str, body: &str) -> Response {
+        Response::new(200, "OK").with_header("Cache-Control", cache_control).with_body(body)
+    }
+
+    fn expire(cache: &ResponseCache) {
+        for entry in cache.state.lock().unwrap().entries.values_mut() {
+            entry.stored_at -= entry.ttl + entry.stale_window;
+        }
+    }
+
+    #[test]
+    fn stores_and_serves_fresh_responses() {
+        let cache = ResponseCache::new(1024 * 1024);
+        assert!(cache.lookup(&get("/a", "")).is_none());
+        assert!(cache.store(&get("/a", ""), &ok("max-age=60", "hello")));
+        let hit = cache.lookup(&get("/a", "")).unwrap();
+        assert_eq!(hit.response.body, b"hello");
+        assert!(!hit.revalidate);
+        assert_eq!(cache.stats().hits, 1);
+    }
+
+    #[test]
+    fn refuses_uncacheable_responses_and_requests() {
+        let cache = ResponseCache::new(1024 * 1024);
+        assert!(!cache.store(&get("/a", ""), &ok("no-store", "x")));
+        assert!(!cache.store(&get("/a", ""), &Response::new(200, "OK")));
+        assert!(!cache.store(&get("/a", "Authorization: Bearer t\r\n"), &ok("max-age=60", "x")));
+        assert!(!cache.store(&get("/a", "Cache-Control: no-store\r\n"), &ok("max-age=60", "x")));
+        assert!(!cache.store(&get("/a", ""), &ok("max-age=60", "x").with_header("Set-Cookie", "a=b")));
+        assert!(!cache.store(&get("/a", ""), &ok("max-age=60", "x").with_header("Vary", "*")));
+    }
+
+    #[test]
+    fn keys_variants_by_vary_headers() {
+        let cache = ResponseCache::new(1024 * 1024);
+        let gzip = get("/a", "Accept-Encoding: gzip\r\n");
+        assert!(cache.store(&gzip, &ok("max-age=60", "zipped").with_header("Vary", "Accept-Encoding")));
+        assert!(cache.lookup(&get("/a", "")).is_none());
+        assert_eq!(cache.lookup(&get("/a", "Accept-Encoding:  gzip\r\n")).unwrap().response.body, b"zipped");
+    }
+
+    #[test]
+    fn evicts_least_recently_used_entries() {
+        let cache = ResponseCache::new(300);
+        for path in ["/a", "/b", "/c"] {
+            cache.store(&get(path, ""), &ok("max-age=60", &"x".repeat(100)));
+        }
+        assert!(cache.stats().bytes <= 300);
+        assert!(cache.lookup(&get("/a", "")).is_none());
+        assert!(cache.lookup(&get("/c", "")).is_some());
+    }
+
+    #[test]
+    fn keeps_expired_entries_out_of_lookups() {
+        let cache = ResponseCache::new(1024 * 1024);
+        cache.store(&get("/a", ""), &ok("max-age=60", "x"));
+        expire(&cache);
+        assert!(cache.lookup(&get("/a", "")).is_none());
+    }
+
+    #[test]
+    fn only_covers_opted_in_routes() {
+        let cache = ResponseCache::new(1024).route("/assets/**");
+        assert!(cache.covers("/assets/app.js"));
+        assert!(!cache.covers("/admin"));
+        assert!(!ResponseCache::new(1024).covers("/"));
+    }
+
+    #[test]
+    fn does_not_serialize_uncacheable_misses() {
+        let cache = ResponseCache::new(1024 * 1024);
+        let barrier = Barrier::new(4);
+        let started = Instant::now();
+        thread::scope(|scope| {
+            for _ in 0..4 {
+                scope.spawn(|| {
+                    barrier.wait();
+                    cache
+                        .fetch_coalesced(&get("/slow", ""), || {
+                            thread::sleep(Duration::from_millis(300));
+                            Ok(ok("no-store", "fresh"))
+                        })
+                        .unwrap()
+                });
+            }
+        });
+        assert!(started.elapsed() < Duration::from_millis(550), "{:?}", started.elapsed());
+        assert_eq!(cache.stats().coalesced, 0);
+    }
+
+    #[test]
+    fn coalesces_misses_for_known_cacheable_urls() {
+        let cache = ResponseCache::new(1024 * 1024);
+        cache.store(&get("/page", ""), &ok("max-age=60", "old"));
+        expire(&cache);
+        let fetches = AtomicU64::new(0);
+        let barrier = Barrier::new(4);
+        thread::scope(|scope| {
+            for _ in 0..4 {
+                scope.spawn(|| {
+                    barrier.wait();
+                    let response = cache
+                        .fetch_coalesced(&get("/page", ""), || {
+                            fetches.fetch_add(1, Ordering::Relaxed);
+                            thread::sleep(Duration::from_millis(200));
+                            Ok(ok("max-age=60", "new"))
+                        })
+                        .unwrap();
+                    assert_eq!(response.body, b"new");
+                });
+            }
+        });
+        assert_eq!(fetches.load(Ordering::Relaxed), 1);
+        assert_eq!(cache.stats().coalesced, 3);
+    }
+
+    #[test]
+    fn waiters_get_their_own_variant_when_vary_appears() {
+        let cache = ResponseCache::new(1024 * 1024);
+        cache.store(&get("/page", ""), &ok("max-age=60", "old"));
+        expire(&cache);
+        let origin = |request: &Request| {
+            let zipped = request.headers.get("Accept-Encoding") == Some("gzip");
+            thread::sleep(Duration::from_millis(200));
+            Ok(ok("max-age=60", if zipped { "zipped" } else { "plain" }).with_header("Vary", "Accept-Encoding"))
+        };
+        let gzip = get("/page", "Accept-Encoding: gzip\r\n");
+        thread::scope(|scope| {
+            let leader = scope.spawn(|| cache.fetch_coalesced(&gzip, || origin(&gzip)).unwrap());
+            thread::sleep(Duration::from_millis(50));
+            let waiter = get("/page", "");
+            assert_eq!(cache.fetch_coalesced(&waiter, || origin(&waiter)).unwrap().body, b"plain");
+            assert_eq!(leader.join().unwrap().body, b"zipped");
+        });
+    }
+}
@@ -0,0 +1,305 @@
+
+// ============================================================
+//  DAEGONICA SOFTWARE — cancel.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: Cancellation
+//!
+//! **Purpose:**
+//! Tells a running handler when its client has hung up, so long work can be abandoned.
+//!
+//! **Context:**
+//! - Attached by the server to every request as `Request::cancellation`; the reverse and
+//!   forward proxies use it to drop upstream fetches whose client has gone.
+//!
+//! **Responsibilities:**
+//! - Probes the client socket without blocking or consuming data whenever a handler asks.
+//! - Runs registered callbacks from a watcher thread once the client hangs up.
+//! - Stops watching once the response is finished, so late hang-ups cancel nothing.
+//! - Does NOT tell a hang-up from a client that merely half-closed its side after sending the
+//!   request, and does NOT detect hang-ups on non-Unix targets.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+use std::{
+    net::TcpStream,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    thread,
+    time::Duration,
+};
+
+
+/// # POLL_INTERVAL
+///
+/// **Summary:**
+/// How often a watcher thread probes the client for a hang-up.
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+
+/// # Cancellation
+///
+/// **Summary:**
+/// Cancellation signal for one request. Clones share the same state.
+///
+/// **Fields:**
+/// - `socket`: Duplicate handle of the client socket, or `None` for requests that were not read
+///   from a connection (these are never cancelled).
+/// - `cancelled`: Set once a hang-up has been seen.
+/// - `finished`: Set once the response is finished, ending the watch.
+///
+/// **Usage Example:**
+/// ```rust
+/// # use server::{request::Request, response::Response};
+/// # fn rows() -> Vec<u32> { vec![1, 2, 3] }
+/// fn report(request: &Request) -> Response {
+///     for row in rows() {
+///         if request.cancellation.is_cancelled() { break; }
+///         // ... expensive work per row ...
+///     }
+///     Response::new(200, "OK")
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Cancellation {
+    socket: Option<Arc<TcpStream>>,
+    cancelled: Arc<AtomicBool>,
+    finished: Arc<AtomicBool>,
+}
+
+
+/// # Finish
+///
+/// **Summary:**
+/// Guard that finishes a request's cancellation signal when dropped.
+///
+/// **Fields:**
+/// - `0`: Signal to finish.
+#[derive(Debug)]
+pub(crate) struct Finish(Cancellation);
+
+
+impl Cancellation {
+
+    /// # watch
+    ///
+    /// **Purpose:**
+    /// Creates a signal that watches a client connection.
+    ///
+    /// **Parameters:**
+    /// - `stream`: Client socket.
+    ///
+    /// **Returns:**
+    /// - A live `Cancellation`, or an inert one if the socket cannot be duplicated.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let cancellation = Cancellation::watch(&stream);
+    /// ```
+    pub(crate) fn watch(stream: &TcpStream) -> Cancellation {
+        Cancellation {
+            socket: stream.try_clone().ok().map(Arc::new),
+            ..Cancellation::default()
+        }
+    }
+
+    /// # is_cancelled
+    ///
+    /// **Purpose:**
+    /// Checks whether the client has hung up, probing its socket if no hang-up was seen yet.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - `true` once the client has closed or reset the connection before the response finished.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{request::Request, response::Response};
+    /// # fn handler(request: &Request) -> Response {
+    /// if request.cancellation.is_cancelled() { return Response::new(499, "Client Closed Request"); }
+    /// # Response::new(200, "OK")
+    /// # }
+    /// ```
+    pub fn is_cancelled(&self) -> bool {
+        if self.cancelled.load(Ordering::Acquire) {
+            return true;
+        }
+        if self.finished.load(Ordering::Acquire) {
+            return false;
+        }
+        let hung_up = self.socket.as_deref().is_some_and(hung_up);
+        if hung_up {
+            self.cancelled.store(true, Ordering::Release);
+        }
+        hung_up
+    }
+
+    /// # on_cancel
+    ///
+    /// **Purpose:**
+    /// Runs a callback if the client hangs up before the response is finished. Each call starts
+    /// a watcher thread that probes the client until one or the other happens.
+    ///
+    /// **Parameters:**
+    /// - `callback`: Work to run on hang-up (e.g. shutting down an upstream socket).
+    ///
+    /// **Returns:**
+    /// None.
+    ///
+    /// **Errors / Failures:**
+    /// None. The callback is dropped unrun if the watcher thread cannot be spawned or the
+    /// request finishes first.
+    ///
+    /// **Examples:**
+    /// ```rust,no_run
+    /// # use server::request::Request;
+    /// # use std::{io, net::{Shutdown, TcpStream}};
+    /// # fn relay(request: &Request, upstream: &TcpStream) -> io::Result<()> {
+    /// let upstream = upstream.try_clone()?;
+    /// request.cancellation.on_cancel(move || { let _ = upstream.shutdown(Shutdown::Both); });
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn on_cancel(&self, callback: impl FnOnce() + Send + 'static) {
+        if self.socket.is_none() {
+            return;
+        }
+        let signal = self.clone();
+        let _ = thread::Builder::new().name("cancel-watch".to_string()).spawn(move || {
+            loop {
+                if signal.is_cancelled() {
+                    callback();
+                    return;
+                }
+                if signal.finished.load(Ordering::Acquire) {
+                    return;
+                }
+                thread::sleep(POLL_INTERVAL);
+            }
+        });
+    }
+
+    /// # finish_on_drop
+    ///
+    /// **Purpose:**
+    /// Returns a guard that ends the watch when the request's handling is over.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - A `Finish` guard.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let _finish = cancellation.finish_on_drop();
+    /// ```
+    pub(crate) fn finish_on_drop(&self) -> Finish {
+        Finish(self.clone())
+    }
+}
+
+
+impl Drop for Finish {
+
+    /// # drop
+    ///
+    /// **Purpose:**
+    /// Marks the request finished, stopping its watcher threads.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// None.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// drop(finish);
+    /// ```
+    fn drop(&mut self) {
+        self.0.finished.store(true, Ordering::Release);
+    }
+}
+
+
+/// # hung_up
+///
+/// **Purpose:**
+/// Peeks at the client socket without blocking: end of stream or a reset means the client is gone.
+///
+/// **Parameters:**
+/// - `socket`: Client socket.
+///
+/// **Returns:**
+/// - `true` if the peer closed or reset the connection; `false` if data is waiting or nothing
+///   has arrived yet.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let gone = hung_up(&socket);
+/// ```
+#[cfg(unix)]
+fn hung_up(socket: &TcpStream) -> bool {
+    use std::{io, os::fd::AsRawFd};
+
+    let mut byte = 0u8;
+    // SAFETY: recv(2) writes at most one byte into `byte`, which outlives the call. MSG_PEEK
+    // leaves the data queued and MSG_DONTWAIT keeps the shared socket's blocking mode untouched.
+    let received = unsafe { libc::recv(socket.as_raw_fd(), (&raw mut byte).cast(), 1, libc::MSG_PEEK | libc::MSG_DONTWAIT) };
+    match received {
+        0 => true,
+        1.. => false,
+        _ => !matches!(io::Error::last_os_error().kind(), io::ErrorKind::WouldBlock | io::ErrorKind::Interrupted),
+    }
+}
+
+
+/// # hung_up
+///
+/// **Purpose:**
+/// Stands in on targets without a non-blocking peek; hang-ups are never reported.
+///
+/// **Parameters:**
+/// - `_socket`: Client socket.
+///
+/// **Returns:**
+/// - Always `false`.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let gone = hung_up(&socket);
+/// ```
+#[cfg(not(unix))]
+fn hung_up(_socket: &TcpStream) -> bool {
+    false
+}
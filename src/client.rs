@@ -0,0 +1,163 @@
+
+// ============================================================
+//  DAEGONICA SOFTWARE — client.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: Client
+//!
+//! **Purpose:**
+//! Sends simple outbound HTTP requests on behalf of the server itself.
+//!
+//! **Context:**
+//! - Used to fetch key sets and talk to identity providers.
+//!
+//! **Responsibilities:**
+//! - Sends one request per connection (`Connection: close`) with connect and I/O timeouts.
+//! - Reads the complete, decoded response, up to a size cap.
+//! - Lets callers that send secrets or fetch keys insist on a loopback host.
+//! - Does NOT speak TLS; only `http://` URLs are accepted, so HTTPS endpoints must be reached
+//!   through a local TLS-terminating forwarder.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+use std::{
+    io::{self, BufReader, Write},
+    net::{IpAddr, TcpStream, ToSocketAddrs},
+    time::Duration,
+};
+
+use crate::{
+    proxy::{absolute_target, authority},
+    request::invalid,
+    response::Response,
+};
+
+
+/// # TIMEOUT
+///
+/// **Summary:**
+/// Connect, read, and write timeout applied to every outbound request.
+pub(crate) const TIMEOUT: Duration = Duration::from_secs(10);
+
+
+/// # MAX_RESPONSE
+///
+/// **Summary:**
+/// Largest reply accepted from an outbound request; key sets and token responses are far smaller.
+pub(crate) const MAX_RESPONSE: usize = 1024 * 1024;
+
+
+/// # send
+///
+/// **Purpose:**
+/// Sends a request to an absolute `http://` URL and reads the reply.
+///
+/// **Parameters:**
+/// - `method`: Request method.
+/// - `url`: Absolute URL.
+/// - `headers`: Extra request headers.
+/// - `body`: Request body (a `Content-Length` is sent when non-empty).
+///
+/// **Returns:**
+/// - The complete `Response`, whatever its status.
+///
+/// **Errors / Failures:**
+/// - `InvalidInput` for URLs that are not absolute `http://` URLs.
+/// - Returns an I/O error if the host cannot be reached, times out, or replies malformed or with
+///   more than `MAX_RESPONSE` bytes.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let response = client::send("GET", "http://idp.internal/jwks.json", &[], b"")?;
+/// ```
+pub(crate) fn send(method: &str, url: &str, headers: &[(&str, &str)], body: &[u8]) -> io::Result<Response> {
+    let (host, port, path) = absolute_target(url)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "only absolute http:// URLs are supported"))?;
+    let address = (host.as_str(), port)
+        .to_socket_addrs()?
+        .next()
+        .ok_or_else(|| invalid("host did not resolve"))?;
+
+    let mut stream = TcpStream::connect_timeout(&address, TIMEOUT)?;
+    stream.set_read_timeout(Some(TIMEOUT))?;
+    stream.set_write_timeout(Some(TIMEOUT))?;
+
+    let mut head = Vec::new();
+    write!(
+        head,
+        "{method} {} HTTP/1.1\r\nHost: {}\r\nConnection: close\r\n",
+        path.unwrap_or_else(|| "/".to_string()),
+        authority(&host, port)
+    )?;
+    for (name, value) in headers {
+        write!(head, "{name}: {value}\r\n")?;
+    }
+    if !body.is_empty() {
+        write!(head, "Content-Length: {}\r\n", body.len())?;
+    }
+    head.extend_from_slice(b"\r\n");
+    head.extend_from_slice(body);
+    stream.write_all(&head)?;
+
+    Response::read_from(&mut BufReader::new(&stream), MAX_RESPONSE)
+}
+
+
+/// # require_loopback
+///
+/// **Purpose:**
+/// Refuses URLs whose host is not a loopback address, for requests that carry secrets or
+/// fetch trust anchors and so must not cross the network in plain HTTP.
+///
+/// **Parameters:**
+/// - `url`: Absolute `http://` URL about to be sent to.
+///
+/// **Returns:**
+/// - `Ok(())` if the host is `localhost` or a loopback IP address.
+///
+/// **Errors / Failures:**
+/// - `InvalidInput` for other hosts and for URLs that are not absolute `http://` URLs, naming
+///   the URL and the local-forwarder remedy.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// client::require_loopback(&provider.token_endpoint)?;
+/// ```
+pub(crate) fn require_loopback(url: &str) -> io::Result<()> {
+    let loopback = absolute_target(url).is_some_and(|(host, _, _)| {
+        host.eq_ignore_ascii_case("localhost") || host.parse::<IpAddr>().is_ok_and(|ip| ip.is_loopback())
+    });
+    if loopback {
+        return Ok(());
+    }
+    Err(io::Error::new(
+        io::ErrorKind::InvalidInput,
+        format!(
+            "refusing {url}: only http:// URLs on a loopback host are allowed here; reach HTTPS \
+             endpoints through a local TLS-terminating forwarder"
+        ),
+    ))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_loopback_hosts_pass() {
+        for url in ["http://localhost:8081/jwks", "http://127.0.0.1/token", "http://127.9.9.9:1/", "http://[::1]:8080/x"] {
+            assert!(require_loopback(url).is_ok(), "{url}");
+        }
+        for url in ["http://idp.internal/token", "http://10.0.0.5/token", "https://127.0.0.1/token", "http://localhost.evil.example/"] {
+            assert_eq!(require_loopback(url).unwrap_err().kind(), io::ErrorKind::InvalidInput, "{url}");
+        }
+    }
+}
@@ -0,0 +1,341 @@
+
+// ============================================================
+//  DAEGONICA SOFTWARE — concurrency.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: ConcurrencyLimit
+//!
+//! **Purpose:**
+//! Caps how many connections the server processes at once and sheds the rest.
+//!
+//! **Context:**
+//! - Checked by `Server::run` for each accepted connection, before it is queued for a worker.
+//!
+//! **Responsibilities:**
+//! - Counts connections that are queued or being handled.
+//! - Answers connections beyond the cap with `503 Service Unavailable` and `Retry-After`
+//!   straight from the accept loop, so the worker queue cannot grow without bound.
+//! - Reports current, limit, and rejected counts.
+//! - Does NOT count background cache refreshes.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+use std::{
+    io,
+    net::TcpStream,
+    sync::{
+        Arc,
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+    },
+    time::Duration,
+};
+
+use crate::response::Response;
+
+
+/// # DEFAULT_RETRY_AFTER
+///
+/// **Summary:**
+/// `Retry-After` sent with shed connections unless configured otherwise.
+pub const DEFAULT_RETRY_AFTER: Duration = Duration::from_secs(1);
+
+
+/// # ConcurrencyLimit
+///
+/// **Summary:**
+/// Shared cap on simultaneously processed connections. Clones share the same counters, so a
+/// clone taken before `Server::run` keeps reporting live values.
+///
+/// **Fields:**
+/// - `limit`: Maximum number of connections queued or in progress.
+/// - `retry_after`: Delay suggested to shed clients.
+/// - `active`: Connections currently queued or in progress.
+/// - `rejected`: Connections shed since start-up.
+///
+/// **Usage Example:**
+/// ```rust
+/// # use server::{concurrency::ConcurrencyLimit, server::Server};
+/// let server = Server::new("127.0.0.1:7878").concurrency_limit(ConcurrencyLimit::new(64));
+/// let load = server.concurrency().clone();
+/// println!("{:?}", load.stats());
+/// ```
+#[derive(Debug, Clone)]
+pub struct ConcurrencyLimit {
+    limit: usize,
+    retry_after: Duration,
+    active: Arc<AtomicUsize>,
+    rejected: Arc<AtomicU64>,
+}
+
+
+/// # ConcurrencyStats
+///
+/// **Summary:**
+/// Point-in-time snapshot of concurrency counters.
+///
+/// **Fields:**
+/// - `current`: Connections queued or in progress.
+/// - `limit`: Configured cap (`usize::MAX` when unlimited).
+/// - `rejected`: Connections answered with `503` since start-up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConcurrencyStats {
+    pub current: usize,
+    pub limit: usize,
+    pub rejected: u64,
+}
+
+
+/// # Permit
+///
+/// **Summary:**
+/// One admitted connection; dropping it frees the slot.
+///
+/// **Fields:**
+/// - `active`: Counter to decrement on drop.
+#[derive(Debug)]
+pub struct Permit {
+    active: Arc<AtomicUsize>,
+}
+
+
+impl Default for ConcurrencyLimit {
+
+    /// # default
+    ///
+    /// **Purpose:**
+    /// Creates an unlimited cap that only counts connections.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - A `ConcurrencyLimit` with a limit of `usize::MAX`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::concurrency::ConcurrencyLimit;
+    /// let limit = ConcurrencyLimit::default();
+    /// ```
+    fn default() -> ConcurrencyLimit {
+        ConcurrencyLimit {
+            limit: usize::MAX,
+            retry_after: DEFAULT_RETRY_AFTER,
+            active: Arc::default(),
+            rejected: Arc::default(),
+        }
+    }
+}
+
+
+impl ConcurrencyLimit {
+
+    /// # new
+    ///
+    /// **Purpose:**
+    /// Creates a cap of `limit` simultaneous connections.
+    ///
+    /// **Parameters:**
+    /// - `limit`: Maximum connections queued or in progress (at least 1).
+    ///
+    /// **Returns:**
+    /// - A new `ConcurrencyLimit`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::concurrency::ConcurrencyLimit;
+    /// let limit = ConcurrencyLimit::new(64);
+    /// ```
+    pub fn new(limit: usize) -> ConcurrencyLimit {
+        ConcurrencyLimit {
+            limit: limit.max(1),
+            ..ConcurrencyLimit::default()
+        }
+    }
+
+    /// # retry_after
+    ///
+    /// **Purpose:**
+    /// Sets the delay suggested to shed clients.
+    ///
+    /// **Parameters:**
+    /// - `delay`: `Retry-After` value, rounded down to whole seconds.
+    ///
+    /// **Returns:**
+    /// - The updated `ConcurrencyLimit`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::concurrency::ConcurrencyLimit;
+    /// # use std::time::Duration;
+    /// let limit = ConcurrencyLimit::new(64).retry_after(Duration::from_secs(5));
+    /// ```
+    pub fn retry_after(mut self, delay: Duration) -> ConcurrencyLimit {
+        self.retry_after = delay;
+        self
+    }
+
+    /// # try_acquire
+    ///
+    /// **Purpose:**
+    /// Claims a slot for one connection if the cap allows it.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - `Some(permit)` holding the slot until dropped, or `None` (counted as rejected) when full.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # let limit = server::concurrency::ConcurrencyLimit::new(64);
+    /// if let Some(permit) = limit.try_acquire() { /* serve, then drop permit */ }
+    /// ```
+    pub fn try_acquire(&self) -> Option<Permit> {
+        let admitted = self
+            .active
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |active| (active < self.limit).then_some(active + 1))
+            .is_ok();
+        if !admitted {
+            self.rejected.fetch_add(1, Ordering::Relaxed);
+            return None;
+        }
+        Some(Permit {
+            active: Arc::clone(&self.active),
+        })
+    }
+
+    /// # stats
+    ///
+    /// **Purpose:**
+    /// Returns a snapshot of the counters.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - Current, limit, and rejected counts.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # let limit = server::concurrency::ConcurrencyLimit::new(64);
+    /// let busy = limit.stats().current;
+    /// # assert_eq!(busy, 0);
+    /// ```
+    pub fn stats(&self) -> ConcurrencyStats {
+        ConcurrencyStats {
+            current: self.active.load(Ordering::Acquire),
+            limit: self.limit,
+            rejected: self.rejected.load(Ordering::Relaxed),
+        }
+    }
+
+    /// # reject
+    ///
+    /// **Purpose:**
+    /// Answers a shed connection with `503` without blocking the accept loop.
+    ///
+    /// **Parameters:**
+    /// - `stream`: Connection being shed.
+    ///
+    /// **Returns:**
+    /// - `Ok(())` once the reply has been written.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns an I/O error if the reply cannot be written within 100 ms.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// limit.reject(&stream)?;
+    /// ```
+    pub(crate) fn reject(&self, stream: &TcpStream) -> io::Result<()> {
+        Response::new(503, "Service Unavailable")
+            .with_header("Retry-After", self.retry_after.as_secs().to_string())
+            .with_header("Content-Type", "text/plain; charset=utf-8")
+            .with_body("server is busy")
+            .write_final(stream, Duration::from_millis(100))
+    }
+}
+
+
+impl Drop for Permit {
+
+    /// # drop
+    ///
+    /// **Purpose:**
+    /// Frees the connection's slot.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// None.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # let limit = server::concurrency::ConcurrencyLimit::new(64);
+    /// # let permit = limit.try_acquire().unwrap();
+    /// drop(permit);
+    /// # assert_eq!(limit.stats().current, 0);
+    /// ```
+    fn drop(&mut self) {
+        self.active.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+    use std::net::TcpListener;
+
+    #[test]
+    fn connections_past_the_limit_are_refused_and_counted() {
+        let limit = ConcurrencyLimit::new(2);
+        let first = limit.try_acquire().unwrap();
+        let _second = limit.try_acquire().unwrap();
+        assert!(limit.try_acquire().is_none());
+        assert_eq!(limit.stats().current, 2);
+        assert_eq!(limit.stats().rejected, 1);
+        drop(first);
+        assert!(limit.try_acquire().is_some(), "a dropped permit frees its slot");
+    }
+
+    #[test]
+    fn shed_connections_get_503_with_retry_after() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let mut client = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        let (server, _) = listener.accept().unwrap();
+        ConcurrencyLimit::new(1).retry_after(Duration::from_secs(7)).reject(&server).unwrap();
+        drop(server);
+        let mut reply = String::new();
+        client.read_to_string(&mut reply).unwrap();
+        assert!(reply.starts_with("HTTP/1.1 503"));
+        assert!(reply.contains("Retry-After: 7\r\n"));
+    }
+}
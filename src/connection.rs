@@ -0,0 +1,291 @@
+
+// ============================================================
+//  DAEGONICA SOFTWARE — connection.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: ConnectionInfo
+//!
+//! **Purpose:**
+//! Describes the TCP connection a request arrived on.
+//!
+//! **Context:**
+//! - Built by the server once per accepted connection and shared by every request on it
+//!   through `Request::connection`.
+//!
+//! **Responsibilities:**
+//! - Numbers connections so handlers and logs can tell which requests shared one.
+//! - Records the socket's remote and local addresses and when it was accepted.
+//! - Keeps a registry of the connections currently open, for the admin API, with a handle to
+//!   each socket so a drain deadline can close whatever is still open.
+//! - Does NOT carry TLS details: TLS is terminated in front of the server, and the forwarded
+//!   scheme is on `Request::secure`.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+use std::{
+    collections::BTreeMap,
+    net::{Shutdown, SocketAddr, TcpStream},
+    sync::{
+        Arc,
+        Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::Instant,
+};
+
+
+/// # NEXT_CONNECTION_ID
+///
+/// **Summary:**
+/// Identifier handed to the next accepted connection; ids start at 1, leaving 0 for requests
+/// that were not read from a connection.
+static NEXT_CONNECTION_ID: AtomicU64 = AtomicU64::new(1);
+
+
+/// # OpenConnection
+///
+/// **Summary:**
+/// Metadata of a registered connection, with a duplicate handle of its socket.
+type OpenConnection = (Arc<ConnectionInfo>, Option<TcpStream>);
+
+
+/// # ConnectionInfo
+///
+/// **Summary:**
+/// Metadata of one client connection.
+///
+/// **Fields:**
+/// - `id`: Process-wide connection number, unique until the counter wraps.
+/// - `remote`: Socket address of the other end (the load balancer's when behind one; the
+///   client it reports is `Request::peer`).
+/// - `local`: Socket address the connection was accepted on.
+/// - `accepted`: When the connection was accepted.
+///
+/// **Usage Example:**
+/// ```rust
+/// # use server::{request::Request, response::Response};
+/// fn debug(request: &Request) -> Response {
+///     let connection = &request.connection;
+///     Response::new(200, "OK").with_body(format!("connection {} on {:?}", connection.id, connection.local))
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ConnectionInfo {
+    pub id: u64,
+    pub remote: Option<SocketAddr>,
+    pub local: Option<SocketAddr>,
+    pub accepted: Instant,
+}
+
+
+/// # ConnectionRegistry
+///
+/// **Summary:**
+/// Connections currently being served, by id.
+///
+/// **Fields:**
+/// - `open`: Metadata of each open connection, with a duplicate handle of its socket when one
+///   could be made.
+#[derive(Debug, Default)]
+pub(crate) struct ConnectionRegistry {
+    open: Mutex<BTreeMap<u64, OpenConnection>>,
+}
+
+
+/// # Registration
+///
+/// **Summary:**
+/// Guard keeping one connection in its registry; dropping it removes the connection.
+///
+/// **Fields:**
+/// - `registry`: Registry the connection was added to.
+/// - `id`: Connection id to remove on drop.
+#[derive(Debug)]
+pub(crate) struct Registration {
+    registry: Arc<ConnectionRegistry>,
+    id: u64,
+}
+
+
+impl Default for ConnectionInfo {
+
+    /// # default
+    ///
+    /// **Purpose:**
+    /// Creates placeholder metadata for a request that did not come from a connection.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - `ConnectionInfo` with id `0`, no addresses, and the current time.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::connection::ConnectionInfo;
+    /// let connection = ConnectionInfo::default();
+    /// ```
+    fn default() -> ConnectionInfo {
+        ConnectionInfo {
+            id: 0,
+            remote: None,
+            local: None,
+            accepted: Instant::now(),
+        }
+    }
+}
+
+
+impl ConnectionInfo {
+
+    /// # new
+    ///
+    /// **Purpose:**
+    /// Describes a freshly accepted connection, giving it the next id.
+    ///
+    /// **Parameters:**
+    /// - `stream`: Accepted client socket.
+    ///
+    /// **Returns:**
+    /// - A new `ConnectionInfo`; addresses the socket cannot report are `None`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::connection::ConnectionInfo;
+    /// # use std::{net::{TcpListener, TcpStream}, sync::Arc};
+    /// # let listener = TcpListener::bind("127.0.0.1:0")?;
+    /// # let stream = TcpStream::connect(listener.local_addr()?)?;
+    /// let connection = Arc::new(ConnectionInfo::new(&stream));
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn new(stream: &TcpStream) -> ConnectionInfo {
+        ConnectionInfo {
+            id: NEXT_CONNECTION_ID.fetch_add(1, Ordering::Relaxed),
+            remote: stream.peer_addr().ok(),
+            local: stream.local_addr().ok(),
+            accepted: Instant::now(),
+        }
+    }
+}
+
+
+impl ConnectionRegistry {
+
+    /// # register
+    ///
+    /// **Purpose:**
+    /// Lists a connection as open until the returned guard is dropped.
+    ///
+    /// **Parameters:**
+    /// - `connection`: Metadata of the accepted connection.
+    /// - `stream`: Its socket, duplicated so `close_all` can shut it down.
+    ///
+    /// **Returns:**
+    /// - A `Registration` guard for the connection.
+    ///
+    /// **Errors / Failures:**
+    /// None. A socket that cannot be duplicated is listed but cannot be closed early.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let _registered = ConnectionRegistry::register(&self.connections, &connection, &stream);
+    /// ```
+    pub(crate) fn register(registry: &Arc<ConnectionRegistry>, connection: &Arc<ConnectionInfo>, stream: &TcpStream) -> Registration {
+        let mut open = registry.open.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        open.insert(connection.id, (Arc::clone(connection), stream.try_clone().ok()));
+        Registration {
+            registry: Arc::clone(registry),
+            id: connection.id,
+        }
+    }
+
+    /// # snapshot
+    ///
+    /// **Purpose:**
+    /// Lists the open connections.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - Metadata of every open connection, oldest id first.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let open = registry.snapshot().len();
+    /// ```
+    pub(crate) fn snapshot(&self) -> Vec<Arc<ConnectionInfo>> {
+        let open = self.open.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        open.values().map(|(connection, _)| Arc::clone(connection)).collect()
+    }
+
+    /// # close_all
+    ///
+    /// **Purpose:**
+    /// Shuts down both directions of every open connection, so the workers serving them see
+    /// the connection end and release it.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - How many connections were open.
+    ///
+    /// **Errors / Failures:**
+    /// None. Sockets that cannot be shut down are left to end on their own.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let closed = registry.close_all();
+    /// ```
+    pub(crate) fn close_all(&self) -> usize {
+        let open = self.open.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        for socket in open.values().filter_map(|(_, socket)| socket.as_ref()) {
+            let _ = socket.shutdown(Shutdown::Both);
+        }
+        open.len()
+    }
+}
+
+
+impl Drop for Registration {
+
+    /// # drop
+    ///
+    /// **Purpose:**
+    /// Removes the connection from the registry.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// None.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// drop(registration);
+    /// ```
+    fn drop(&mut self) {
+        let mut open = self.registry.open.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        open.remove(&self.id);
+    }
+}
@@ -0,0 +1,363 @@
+
+// ============================================================
+//  DAEGONICA SOFTWARE — cookie.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: Cookie
+//!
+//! **Purpose:**
+//! Reads request cookies and builds `Set-Cookie` header values.
+//!
+//! **Context:**
+//! - Used by `Request::cookie`, the session layer, and CSRF protection.
+//!
+//! **Responsibilities:**
+//! - Parses `Cookie` headers into name/value pairs.
+//! - Renders `Set-Cookie` values with `Path`, `Max-Age`, `HttpOnly`, `Secure`, and `SameSite`.
+//! - Does NOT sign or encrypt cookie values.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+use std::fmt;
+
+use crate::headers::Headers;
+
+
+/// # SameSite
+///
+/// **Summary:**
+/// `SameSite` attribute of a cookie.
+///
+/// **Variants:**
+/// - `Strict`: Sent only on same-site requests.
+/// - `Lax`: Also sent on top-level cross-site navigations.
+/// - `None`: Sent on every request; browsers require `Secure`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SameSite {
+    Strict,
+    Lax,
+    None,
+}
+
+
+/// # Cookie
+///
+/// **Summary:**
+/// A cookie to set on the client.
+///
+/// **Fields:**
+/// - `name`: Cookie name.
+/// - `value`: Cookie value.
+/// - `path`: `Path` attribute (default `/`).
+/// - `max_age`: `Max-Age` in seconds; `None` makes a session cookie.
+/// - `http_only`: Hide the cookie from scripts (default on).
+/// - `secure`: Send only over HTTPS.
+/// - `same_site`: `SameSite` attribute (default `Lax`).
+///
+/// **Usage Example:**
+/// ```rust
+/// # use server::{cookie::Cookie, response::Response};
+/// let cookie = Cookie::new("theme", "dark").max_age(86_400).http_only(false);
+/// let response = Response::new(200, "OK").with_header("Set-Cookie", cookie.to_string());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cookie {
+    name: String,
+    value: String,
+    path: String,
+    max_age: Option<u64>,
+    http_only: bool,
+    secure: bool,
+    same_site: SameSite,
+}
+
+
+impl Cookie {
+
+    /// # new
+    ///
+    /// **Purpose:**
+    /// Creates an `HttpOnly`, `SameSite=Lax` session cookie for path `/`.
+    ///
+    /// **Parameters:**
+    /// - `name`: Cookie name.
+    /// - `value`: Cookie value (must not contain `;`, `,`, or whitespace).
+    ///
+    /// **Returns:**
+    /// - A new `Cookie`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::cookie::Cookie;
+    /// # let id = "3f2a9c".to_string();
+    /// let cookie = Cookie::new("sid", &id);
+    /// ```
+    pub fn new(name: impl Into<String>, value: impl Into<String>) -> Cookie {
+        Cookie {
+            name: name.into(),
+            value: value.into(),
+            path: "/".to_string(),
+            max_age: None,
+            http_only: true,
+            secure: false,
+            same_site: SameSite::Lax,
+        }
+    }
+
+    /// # removal
+    ///
+    /// **Purpose:**
+    /// Creates a cookie that tells the client to delete `name`.
+    ///
+    /// **Parameters:**
+    /// - `name`: Cookie name.
+    ///
+    /// **Returns:**
+    /// - An empty cookie with `Max-Age=0`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::cookie::Cookie;
+    /// let cookie = Cookie::removal("sid");
+    /// ```
+    pub fn removal(name: impl Into<String>) -> Cookie {
+        Cookie::new(name, "").max_age(0)
+    }
+
+    /// # path
+    ///
+    /// **Purpose:**
+    /// Sets the `Path` attribute.
+    ///
+    /// **Parameters:**
+    /// - `path`: Path prefix the cookie applies to.
+    ///
+    /// **Returns:**
+    /// - The updated `Cookie`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::cookie::Cookie;
+    /// let cookie = Cookie::new("admin", "1").path("/admin");
+    /// ```
+    pub fn path(mut self, path: impl Into<String>) -> Cookie {
+        self.path = path.into();
+        self
+    }
+
+    /// # max_age
+    ///
+    /// **Purpose:**
+    /// Sets the `Max-Age` attribute.
+    ///
+    /// **Parameters:**
+    /// - `seconds`: Lifetime in seconds; `0` deletes the cookie.
+    ///
+    /// **Returns:**
+    /// - The updated `Cookie`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::cookie::Cookie;
+    /// let cookie = Cookie::new("remember", "1").max_age(30 * 86_400);
+    /// ```
+    pub fn max_age(mut self, seconds: u64) -> Cookie {
+        self.max_age = Some(seconds);
+        self
+    }
+
+    /// # http_only
+    ///
+    /// **Purpose:**
+    /// Sets or clears the `HttpOnly` attribute.
+    ///
+    /// **Parameters:**
+    /// - `http_only`: Whether scripts are denied access.
+    ///
+    /// **Returns:**
+    /// - The updated `Cookie`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::cookie::Cookie;
+    /// # let token = "b7e1d0".to_string();
+    /// let cookie = Cookie::new("csrf_token", &token).http_only(false);
+    /// ```
+    pub fn http_only(mut self, http_only: bool) -> Cookie {
+        self.http_only = http_only;
+        self
+    }
+
+    /// # secure
+    ///
+    /// **Purpose:**
+    /// Sets or clears the `Secure` attribute.
+    ///
+    /// **Parameters:**
+    /// - `secure`: Whether the cookie is sent only over HTTPS.
+    ///
+    /// **Returns:**
+    /// - The updated `Cookie`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::cookie::Cookie;
+    /// # let id = "3f2a9c".to_string();
+    /// let cookie = Cookie::new("sid", &id).secure(true);
+    /// ```
+    pub fn secure(mut self, secure: bool) -> Cookie {
+        self.secure = secure;
+        self
+    }
+
+    /// # same_site
+    ///
+    /// **Purpose:**
+    /// Sets the `SameSite` attribute.
+    ///
+    /// **Parameters:**
+    /// - `same_site`: Cross-site policy.
+    ///
+    /// **Returns:**
+    /// - The updated `Cookie`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::cookie::{Cookie, SameSite};
+    /// # let id = "3f2a9c".to_string();
+    /// let cookie = Cookie::new("sid", &id).same_site(SameSite::Strict);
+    /// ```
+    pub fn same_site(mut self, same_site: SameSite) -> Cookie {
+        self.same_site = same_site;
+        self
+    }
+}
+
+
+impl fmt::Display for Cookie {
+
+    /// # fmt
+    ///
+    /// **Purpose:**
+    /// Renders the cookie as a `Set-Cookie` header value.
+    ///
+    /// **Parameters:**
+    /// - `f`: Formatter.
+    ///
+    /// **Returns:**
+    /// - The formatter result.
+    ///
+    /// **Errors / Failures:**
+    /// - Propagates formatter errors.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::cookie::Cookie;
+    /// assert_eq!(Cookie::new("a", "1").to_string(), "a=1; Path=/; HttpOnly; SameSite=Lax");
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}={}; Path={}", self.name, self.value, self.path)?;
+        if let Some(max_age) = self.max_age {
+            write!(f, "; Max-Age={max_age}")?;
+        }
+        if self.http_only {
+            f.write_str("; HttpOnly")?;
+        }
+        if self.secure {
+            f.write_str("; Secure")?;
+        }
+        let same_site = match self.same_site {
+            SameSite::Strict => "Strict",
+            SameSite::Lax => "Lax",
+            SameSite::None => "None",
+        };
+        write!(f, "; SameSite={same_site}")
+    }
+}
+
+
+/// # get
+///
+/// **Purpose:**
+/// Finds a cookie value in the request's `Cookie` headers.
+///
+/// **Parameters:**
+/// - `headers`: Request headers.
+/// - `name`: Cookie name (case-sensitive).
+///
+/// **Returns:**
+/// - `Some(value)` for the first cookie named `name`, otherwise `None`.
+///
+/// **Errors / Failures:**
+/// None. Malformed pairs are skipped.
+///
+/// **Examples:**
+/// ```rust
+/// # use server::{cookie, request::Request};
+/// # let request = Request::read_from(&mut &b"GET / HTTP/1.1\r\nCookie: theme=dark; sid=3f2a9c\r\n\r\n"[..])?;
+/// let sid = cookie::get(&request.headers, "sid");
+/// # assert_eq!(sid, Some("3f2a9c"));
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub fn get<'a>(headers: &'a Headers, name: &str) -> Option<&'a str> {
+    headers
+        .get_all("Cookie")
+        .into_iter()
+        .flat_map(|header| header.split(';'))
+        .filter_map(|pair| pair.trim().split_once('='))
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| value.trim_matches('"'))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cookies_render_their_attributes() {
+        assert_eq!(Cookie::new("a", "1").to_string(), "a=1; Path=/; HttpOnly; SameSite=Lax");
+        let cookie = Cookie::new("theme", "dark").path("/app").max_age(60).http_only(false).secure(true).same_site(SameSite::Strict);
+        assert_eq!(cookie.to_string(), "theme=dark; Path=/app; Max-Age=60; Secure; SameSite=Strict");
+        assert_eq!(Cookie::removal("sid").to_string(), "sid=; Path=/; Max-Age=0; HttpOnly; SameSite=Lax");
+    }
+
+    #[test]
+    fn values_are_found_across_cookie_headers() {
+        let mut headers = Headers::new();
+        headers.append("Cookie", "theme=dark; broken; sid=\"abc\"");
+        headers.append("Cookie", "lang=en; sid=later");
+        assert_eq!(get(&headers, "sid"), Some("abc"));
+        assert_eq!(get(&headers, "lang"), Some("en"));
+        assert_eq!(get(&headers, "SID"), None);
+        assert_eq!(get(&headers, "broken"), None);
+    }
+}
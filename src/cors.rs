@@ -0,0 +1,531 @@
+
+// ============================================================
+//  DAEGONICA SOFTWARE — cors.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: Cors
+//!
+//! **Purpose:**
+//! Lets browsers on other origins call routed endpoints, following the CORS protocol.
+//!
+//! **Context:**
+//! - Installed as router middleware with `Router::middleware(cors.middleware())`; router-level
+//!   middleware also sees the router's automatic `OPTIONS` answers, so preflights are covered.
+//!
+//! **Responsibilities:**
+//! - Matches the `Origin` header against exact origins, wildcard patterns, or a predicate.
+//! - Answers preflight requests with the allowed methods, headers, credentials flag, and max-age.
+//! - Adds `Access-Control-Allow-Origin` (and friends) to actual responses.
+//! - Emits `Vary: Origin` whenever the answer depends on the requesting origin.
+//! - Does NOT block requests itself; browsers enforce the policy from the headers.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+use std::sync::Arc;
+
+use crate::{glob::Glob, request::Request, response::Response, router::Next};
+
+
+/// # DEFAULT_METHODS
+///
+/// **Summary:**
+/// Methods allowed in preflight answers unless configured otherwise.
+const DEFAULT_METHODS: [&str; 6] = ["GET", "HEAD", "POST", "PUT", "PATCH", "DELETE"];
+
+
+/// # OriginRule
+///
+/// **Summary:**
+/// One way of accepting an origin.
+///
+/// **Variants:**
+/// - `Exact`: Accepts one origin (compared case-insensitively).
+/// - `Pattern`: Accepts origins matching a wildcard pattern such as `https://*.example.com`.
+/// - `Predicate`: Accepts origins for which the function returns `true`.
+#[derive(Clone)]
+enum OriginRule {
+    Exact(String),
+    Pattern(Glob),
+    Predicate(Arc<dyn Fn(&str) -> bool + Send + Sync>),
+}
+
+
+/// # Cors
+///
+/// **Summary:**
+/// CORS policy for a router.
+///
+/// **Fields:**
+/// - `any_origin`: Accept every origin.
+/// - `origins`: Accepted origins when `any_origin` is unset.
+/// - `methods`: Methods allowed in preflight answers; `DEFAULT_METHODS` when `None`.
+/// - `headers`: Request headers allowed in preflight answers; the requested ones are echoed when `None`.
+/// - `expose_headers`: Response headers readable by the calling script.
+/// - `credentials`: Allow cookies and HTTP authentication on cross-origin requests.
+/// - `max_age`: Seconds browsers may cache a preflight answer.
+///
+/// **Usage Example:**
+/// ```rust
+/// # use server::{cors::Cors, request::Request, response::Response, router::Router};
+/// # fn items(_: &Request) -> Response { Response::new(200, "OK") }
+/// let cors = Cors::new()
+///     .allow_origin("https://app.example.com")
+///     .allow_origin("https://*.preview.example.com")
+///     .allow_methods(&["GET", "POST"])
+///     .allow_credentials(true)
+///     .max_age(600);
+/// let router = Router::new().middleware(cors.middleware()).get("/api/items", items);
+/// ```
+#[derive(Clone, Default)]
+pub struct Cors {
+    any_origin: bool,
+    origins: Vec<OriginRule>,
+    methods: Option<Vec<String>>,
+    headers: Option<Vec<String>>,
+    expose_headers: Vec<String>,
+    credentials: bool,
+    max_age: Option<u64>,
+}
+
+
+impl Cors {
+
+    /// # new
+    ///
+    /// **Purpose:**
+    /// Creates a policy that accepts no origins until some are allowed.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - A new `Cors`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::cors::Cors;
+    /// let cors = Cors::new().allow_any_origin();
+    /// ```
+    pub fn new() -> Cors {
+        Cors::default()
+    }
+
+    /// # allow_any_origin
+    ///
+    /// **Purpose:**
+    /// Accepts every origin, answering `*`. Cannot be combined with `allow_credentials`: that
+    /// would let any site make credentialed reads, so `middleware` refuses it and list the
+    /// trusted origins with `allow_origin` instead.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - The updated `Cors`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::cors::Cors;
+    /// let cors = Cors::new().allow_any_origin();
+    /// ```
+    pub fn allow_any_origin(mut self) -> Cors {
+        self.any_origin = true;
+        self
+    }
+
+    /// # allow_origin
+    ///
+    /// **Purpose:**
+    /// Accepts an origin. A `*` in the value matches any run of characters except `/`,
+    /// so `https://*.example.com` accepts every subdomain.
+    ///
+    /// **Parameters:**
+    /// - `origin`: Origin (`scheme://host[:port]`) or wildcard pattern.
+    ///
+    /// **Returns:**
+    /// - The updated `Cors`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::cors::Cors;
+    /// let cors = Cors::new().allow_origin("http://localhost:3000");
+    /// ```
+    pub fn allow_origin(mut self, origin: &str) -> Cors {
+        let rule = if origin.contains('*') {
+            OriginRule::Pattern(Glob::new(&origin.to_ascii_lowercase()))
+        } else {
+            OriginRule::Exact(origin.to_string())
+        };
+        self.origins.push(rule);
+        self
+    }
+
+    /// # allow_origin_fn
+    ///
+    /// **Purpose:**
+    /// Accepts origins chosen by a predicate (e.g. a lookup in a tenant table).
+    ///
+    /// **Parameters:**
+    /// - `predicate`: Function receiving the `Origin` header value.
+    ///
+    /// **Returns:**
+    /// - The updated `Cors`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::cors::Cors;
+    /// let cors = Cors::new().allow_origin_fn(|origin| origin.ends_with(".internal"));
+    /// ```
+    pub fn allow_origin_fn<F>(mut self, predicate: F) -> Cors
+    where
+        F: Fn(&str) -> bool + Send + Sync + 'static,
+    {
+        self.origins.push(OriginRule::Predicate(Arc::new(predicate)));
+        self
+    }
+
+    /// # allow_methods
+    ///
+    /// **Purpose:**
+    /// Sets the methods reported in preflight answers.
+    ///
+    /// **Parameters:**
+    /// - `methods`: Allowed methods (default: `GET, HEAD, POST, PUT, PATCH, DELETE`).
+    ///
+    /// **Returns:**
+    /// - The updated `Cors`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::cors::Cors;
+    /// let cors = Cors::new().allow_methods(&["GET", "POST"]);
+    /// ```
+    pub fn allow_methods(mut self, methods: &[&str]) -> Cors {
+        self.methods = Some(methods.iter().map(|method| method.to_ascii_uppercase()).collect());
+        self
+    }
+
+    /// # allow_headers
+    ///
+    /// **Purpose:**
+    /// Sets the request headers reported in preflight answers. By default the headers the
+    /// browser asks for are echoed back.
+    ///
+    /// **Parameters:**
+    /// - `headers`: Allowed request header names.
+    ///
+    /// **Returns:**
+    /// - The updated `Cors`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::cors::Cors;
+    /// let cors = Cors::new().allow_headers(&["Content-Type", "Authorization"]);
+    /// ```
+    pub fn allow_headers(mut self, headers: &[&str]) -> Cors {
+        self.headers = Some(headers.iter().map(|header| header.to_string()).collect());
+        self
+    }
+
+    /// # expose_headers
+    ///
+    /// **Purpose:**
+    /// Sets the response headers that calling scripts may read beyond the safelisted ones.
+    ///
+    /// **Parameters:**
+    /// - `headers`: Exposed response header names.
+    ///
+    /// **Returns:**
+    /// - The updated `Cors`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::cors::Cors;
+    /// let cors = Cors::new().expose_headers(&["X-Request-Id"]);
+    /// ```
+    pub fn expose_headers(mut self, headers: &[&str]) -> Cors {
+        self.expose_headers = headers.iter().map(|header| header.to_string()).collect();
+        self
+    }
+
+    /// # allow_credentials
+    ///
+    /// **Purpose:**
+    /// Allows cookies and HTTP authentication on cross-origin requests from the origins allowed
+    /// by name, pattern, or predicate; never together with `allow_any_origin`.
+    ///
+    /// **Parameters:**
+    /// - `credentials`: Whether to send `Access-Control-Allow-Credentials: true`.
+    ///
+    /// **Returns:**
+    /// - The updated `Cors`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::cors::Cors;
+    /// let cors = Cors::new().allow_origin("https://app.example.com").allow_credentials(true);
+    /// ```
+    pub fn allow_credentials(mut self, credentials: bool) -> Cors {
+        self.credentials = credentials;
+        self
+    }
+
+    /// # max_age
+    ///
+    /// **Purpose:**
+    /// Sets how long browsers may cache a preflight answer.
+    ///
+    /// **Parameters:**
+    /// - `seconds`: Cache lifetime in seconds.
+    ///
+    /// **Returns:**
+    /// - The updated `Cors`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::cors::Cors;
+    /// let cors = Cors::new().allow_any_origin().max_age(86_400);
+    /// ```
+    pub fn max_age(mut self, seconds: u64) -> Cors {
+        self.max_age = Some(seconds);
+        self
+    }
+
+    /// # middleware
+    ///
+    /// **Purpose:**
+    /// Turns the policy into router middleware.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - A closure accepted by `Router::middleware`.
+    ///
+    /// **Errors / Failures:**
+    /// - Panics if `allow_any_origin` is combined with `allow_credentials`.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{cors::Cors, router::Router};
+    /// let router = Router::new().middleware(Cors::new().allow_any_origin().middleware());
+    /// ```
+    pub fn middleware(self) -> impl Fn(&Request, Next<'_>) -> Response + Send + Sync + 'static {
+        assert!(
+            !(self.any_origin && self.credentials),
+            "CORS credentials need an explicit origin allow-list, not allow_any_origin"
+        );
+        move |request, next| self.handle(request, next)
+    }
+
+    /// # handle
+    ///
+    /// **Purpose:**
+    /// Applies the policy to one request: answers accepted preflights directly and decorates
+    /// other responses with the CORS headers.
+    ///
+    /// **Parameters:**
+    /// - `request`: Request being handled.
+    /// - `next`: The rest of the middleware chain.
+    ///
+    /// **Returns:**
+    /// - The preflight answer or the decorated downstream response.
+    ///
+    /// **Errors / Failures:**
+    /// None. Requests from rejected origins pass through without CORS headers, and a policy
+    /// accepting any origin never sends `Access-Control-Allow-Credentials`.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{cors::Cors, request::Request, response::Response, router::Next};
+    /// # let cors = Cors::new().allow_any_origin();
+    /// # let request = &Request::read_from(&mut &b"GET / HTTP/1.1\r\nOrigin: https://app.example.com\r\n\r\n"[..])?;
+    /// # let next: Next<'_> = &|_| Response::new(200, "OK");
+    /// let response = cors.handle(request, next);
+    /// # assert_eq!(response.headers.get("Access-Control-Allow-Origin"), Some("*"));
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn handle(&self, request: &Request, next: Next<'_>) -> Response {
+        let Some(origin) = request.headers.get("Origin") else {
+            return next(request);
+        };
+        let preflight = request.method == "OPTIONS" && request.headers.contains("Access-Control-Request-Method");
+
+        let Some(allow_origin) = self.allow_origin_value(origin) else {
+            return self.vary(next(request));
+        };
+
+        let mut response = if preflight {
+            let methods = match &self.methods {
+                Some(methods) => methods.join(", "),
+                None => DEFAULT_METHODS.join(", "),
+            };
+            let mut response =
+                Response::new(204, "No Content").with_header("Access-Control-Allow-Methods", methods);
+            let headers = match &self.headers {
+                Some(headers) => Some(headers.join(", ")),
+                None => request.headers.get("Access-Control-Request-Headers").map(str::to_string),
+            };
+            if let Some(headers) = headers.filter(|headers| !headers.is_empty()) {
+                response = response.with_header("Access-Control-Allow-Headers", headers);
+            }
+            if let Some(max_age) = self.max_age {
+                response = response.with_header("Access-Control-Max-Age", max_age.to_string());
+            }
+            response
+        } else {
+            let mut response = next(request);
+            if !self.expose_headers.is_empty() {
+                response.headers.set("Access-Control-Expose-Headers", self.expose_headers.join(", "));
+            }
+            response
+        };
+
+        response.headers.set("Access-Control-Allow-Origin", allow_origin);
+        if self.credentials && !self.any_origin {
+            response.headers.set("Access-Control-Allow-Credentials", "true");
+        }
+        self.vary(response)
+    }
+
+    /// # allow_origin_value
+    ///
+    /// **Purpose:**
+    /// Decides the `Access-Control-Allow-Origin` value for a requesting origin.
+    ///
+    /// **Parameters:**
+    /// - `origin`: `Origin` header value.
+    ///
+    /// **Returns:**
+    /// - `Some("*")` when any origin is accepted, `Some(origin)` when it matches a rule,
+    ///   otherwise `None`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let value = self.allow_origin_value("https://app.example.com");
+    /// ```
+    fn allow_origin_value(&self, origin: &str) -> Option<String> {
+        if self.any_origin {
+            return Some("*".to_string());
+        }
+        let lowercase = origin.to_ascii_lowercase();
+        let accepted = self.origins.iter().any(|rule| match rule {
+            OriginRule::Exact(allowed) => allowed.eq_ignore_ascii_case(origin),
+            OriginRule::Pattern(pattern) => pattern.matches(&lowercase),
+            OriginRule::Predicate(predicate) => predicate(origin),
+        });
+        accepted.then(|| origin.to_string())
+    }
+
+    /// # vary
+    ///
+    /// **Purpose:**
+    /// Adds `Vary: Origin` unless every origin gets the same `*` answer.
+    ///
+    /// **Parameters:**
+    /// - `response`: Response to mark.
+    ///
+    /// **Returns:**
+    /// - The marked `Response`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// return self.vary(next(request));
+    /// ```
+    fn vary(&self, mut response: Response) -> Response {
+        if !self.any_origin && !response.headers.has_token("Vary", "Origin") {
+            response.headers.append("Vary", "Origin");
+        }
+        response
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(raw: &str) -> Request {
+        Request::read_from(&mut raw.as_bytes()).unwrap()
+    }
+
+    fn ok(_: &Request) -> Response {
+        Response::new(200, "OK")
+    }
+
+    #[test]
+    #[should_panic(expected = "explicit origin allow-list")]
+    fn any_origin_with_credentials_is_refused() {
+        let _ = Cors::new().allow_any_origin().allow_credentials(true).middleware();
+    }
+
+    #[test]
+    fn any_origin_never_echoes_or_allows_credentials() {
+        let cors = Cors::new().allow_any_origin().allow_credentials(true);
+        let response = cors.handle(&request("GET / HTTP/1.1\r\nOrigin: https://evil.example\r\n\r\n"), &ok);
+        assert_eq!(response.headers.get("Access-Control-Allow-Origin"), Some("*"));
+        assert!(!response.headers.contains("Access-Control-Allow-Credentials"));
+    }
+
+    #[test]
+    fn listed_origin_gets_credentials_and_others_do_not() {
+        let cors = Cors::new().allow_origin("https://app.example.com").allow_credentials(true);
+        let allowed = cors.handle(&request("GET / HTTP/1.1\r\nOrigin: https://app.example.com\r\n\r\n"), &ok);
+        assert_eq!(allowed.headers.get("Access-Control-Allow-Origin"), Some("https://app.example.com"));
+        assert_eq!(allowed.headers.get("Access-Control-Allow-Credentials"), Some("true"));
+        assert!(allowed.headers.has_token("Vary", "Origin"));
+
+        let other = cors.handle(&request("GET / HTTP/1.1\r\nOrigin: https://evil.example\r\n\r\n"), &ok);
+        assert!(!other.headers.contains("Access-Control-Allow-Origin"));
+        assert!(!other.headers.contains("Access-Control-Allow-Credentials"));
+    }
+
+    #[test]
+    fn preflight_is_answered_without_calling_the_handler() {
+        let cors = Cors::new().allow_origin("https://app.example.com").max_age(60);
+        let raw = "OPTIONS /api HTTP/1.1\r\nOrigin: https://app.example.com\r\nAccess-Control-Request-Method: PUT\r\n\r\n";
+        let response = cors.handle(&request(raw), &|_| panic!("preflight reached the handler"));
+        assert_eq!(response.status, 204);
+        assert_eq!(response.headers.get("Access-Control-Max-Age"), Some("60"));
+    }
+}
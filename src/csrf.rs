@@ -0,0 +1,580 @@
+
+// ============================================================
+//  DAEGONICA SOFTWARE — csrf.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: Csrf
+//!
+//! **Purpose:**
+//! Rejects cross-site request forgery on state-changing requests.
+//!
+//! **Context:**
+//! - Installed as router middleware after `Sessions` (synchronizer token kept in the session) or
+//!   on its own (double-submit cookie).
+//! - Handlers read the `CsrfToken` to embed it in forms or pass it to scripts.
+//!
+//! **Responsibilities:**
+//! - Issues a per-session (or per-browser) random token on safe requests, storing it in the
+//!   session only when the session already holds data or the response carries the token, so
+//!   anonymous traffic does not allocate sessions.
+//! - Requires the token in the `X-CSRF-Token` header or the `csrf_token` form field of
+//!   `POST`, `PUT`, `PATCH`, and `DELETE` requests, answering `403 Forbidden` otherwise.
+//! - Injects hidden token fields into rendered HTML forms.
+//! - Does NOT protect `GET` handlers that change state.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+use crate::{
+    cookie::{self, Cookie, SameSite},
+    extract::{FromRequest, QueryParams},
+    random,
+    request::Request,
+    response::Response,
+    router::Next,
+    session::Session,
+};
+
+
+/// # SESSION_KEY
+///
+/// **Summary:**
+/// Session key holding the synchronizer token.
+const SESSION_KEY: &str = "_csrf";
+
+
+/// # FORM_FIELD
+///
+/// **Summary:**
+/// Form field carrying the token in URL-encoded bodies.
+const FORM_FIELD: &str = "csrf_token";
+
+
+/// # CsrfToken
+///
+/// **Summary:**
+/// The token expected on the visitor's next state-changing request.
+///
+/// **Fields:**
+/// - `0`: Token value.
+///
+/// **Usage Example:**
+/// ```rust
+/// # use server::{csrf::CsrfToken, response::Response};
+/// fn form(token: CsrfToken) -> Response {
+///     let html = format!(r#"<form method="post" action="/profile">{}<button>Save</button></form>"#, token.hidden_input());
+///     Response::new(200, "OK").with_body(html)
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CsrfToken(pub String);
+
+
+/// # Csrf
+///
+/// **Summary:**
+/// CSRF protection configuration.
+///
+/// **Fields:**
+/// - `header`: Request header carrying the token.
+/// - `cookie_name`: Cookie used in double-submit mode.
+/// - `secure`: Mark the double-submit cookie `Secure`.
+///
+/// **Usage Example:**
+/// ```rust
+/// # use server::{csrf::{Csrf, CsrfToken}, extract::extract, request::Request, response::Response, router::Router, session::Sessions};
+/// # fn form(_: CsrfToken) -> Response { Response::new(200, "OK") }
+/// # fn save_profile(_: &Request) -> Response { Response::new(303, "See Other") }
+/// let router = Router::new()
+///     .middleware(Sessions::new().middleware())
+///     .middleware(Csrf::new().middleware())
+///     .get("/profile", extract(form))
+///     .route("POST", "/profile", save_profile);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Csrf {
+    header: String,
+    cookie_name: String,
+    secure: bool,
+}
+
+
+impl CsrfToken {
+
+    /// # hidden_input
+    ///
+    /// **Purpose:**
+    /// Renders the hidden form field carrying the token.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - An `<input type="hidden" name="csrf_token" ...>` element.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # let token = server::csrf::CsrfToken("b7e1d0".to_string());
+    /// let field = token.hidden_input();
+    /// # assert!(field.contains("b7e1d0"));
+    /// ```
+    pub fn hidden_input(&self) -> String {
+        format!(r#"<input type="hidden" name="{FORM_FIELD}" value="{}">"#, self.0)
+    }
+
+    /// # inject
+    ///
+    /// **Purpose:**
+    /// Adds the hidden token field right after the opening tag of every form in `html` whose
+    /// method is not `GET`.
+    ///
+    /// **Parameters:**
+    /// - `html`: Rendered page.
+    ///
+    /// **Returns:**
+    /// - The page with token fields inserted.
+    ///
+    /// **Errors / Failures:**
+    /// None. Malformed tags are left untouched.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{csrf::CsrfToken, json_value::JsonValue, template::{Template, TemplateEngine, Templates}};
+    /// # let token = CsrfToken("b7e1d0".to_string());
+    /// # let templates = Templates::new().add("profile", Template::parse(r#"<form method="post"></form>"#)?);
+    /// # let context = JsonValue::Object(Vec::new());
+    /// let page = token.inject(&templates.render("profile", &context)?);
+    /// # assert!(page.contains("b7e1d0"));
+    /// # Ok::<(), Box<dyn std::error::Error + Send + Sync>>(())
+    /// ```
+    pub fn inject(&self, html: &str) -> String {
+        let lowercase = html.to_ascii_lowercase();
+        let input = self.hidden_input();
+        let mut out = String::with_capacity(html.len());
+        let mut copied = 0;
+        let mut search = 0;
+        while let Some(offset) = lowercase[search..].find("<form") {
+            let start = search + offset;
+            let Some(length) = lowercase[start..].find('>') else {
+                break;
+            };
+            let end = start + length + 1;
+            let tag = &lowercase[start..end];
+            let boundary = tag.as_bytes().get(5).is_some_and(|b| b.is_ascii_whitespace() || *b == b'>');
+            if boundary && !is_get_form(tag) {
+                out.push_str(&html[copied..end]);
+                out.push_str(&input);
+                copied = end;
+            }
+            search = end;
+        }
+        out.push_str(&html[copied..]);
+        out
+    }
+}
+
+
+impl FromRequest for CsrfToken {
+
+    /// # from_request
+    ///
+    /// **Purpose:**
+    /// Fetches the token issued by the `Csrf` middleware.
+    ///
+    /// **Parameters:**
+    /// - `request`: Request being handled.
+    ///
+    /// **Returns:**
+    /// - The token to embed in the response.
+    ///
+    /// **Errors / Failures:**
+    /// - Rejects with `500 Internal Server Error` if the middleware is not installed.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{csrf::CsrfToken, extract::FromRequest, request::Request, response::Response};
+    /// # let mut request = Request::read_from(&mut &b"GET /profile HTTP/1.1\r\n\r\n"[..]).unwrap();
+    /// # request.extensions.insert(CsrfToken("b7e1d0".to_string()));
+    /// let token = CsrfToken::from_request(&request)?;
+    /// # Ok::<(), Response>(())
+    /// ```
+    fn from_request(request: &Request) -> Result<CsrfToken, Response> {
+        request
+            .extensions
+            .get::<CsrfToken>()
+            .cloned()
+            .ok_or_else(|| Response::new(500, "Internal Server Error"))
+    }
+}
+
+
+impl Default for Csrf {
+
+    /// # default
+    ///
+    /// **Purpose:**
+    /// Creates the default configuration: header `X-CSRF-Token` and double-submit cookie `csrf_token`.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - A new `Csrf`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::csrf::Csrf;
+    /// let csrf = Csrf::default();
+    /// ```
+    fn default() -> Csrf {
+        Csrf {
+            header: "X-CSRF-Token".to_string(),
+            cookie_name: "csrf_token".to_string(),
+            secure: false,
+        }
+    }
+}
+
+
+impl Csrf {
+
+    /// # new
+    ///
+    /// **Purpose:**
+    /// Creates the default configuration (see `default`).
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - A new `Csrf`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::csrf::Csrf;
+    /// let csrf = Csrf::new();
+    /// ```
+    pub fn new() -> Csrf {
+        Csrf::default()
+    }
+
+    /// # header
+    ///
+    /// **Purpose:**
+    /// Sets the request header scripts use to send the token.
+    ///
+    /// **Parameters:**
+    /// - `name`: Header name.
+    ///
+    /// **Returns:**
+    /// - The updated `Csrf`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::csrf::Csrf;
+    /// let csrf = Csrf::new().header("X-XSRF-TOKEN");
+    /// ```
+    pub fn header(mut self, name: impl Into<String>) -> Csrf {
+        self.header = name.into();
+        self
+    }
+
+    /// # cookie_name
+    ///
+    /// **Purpose:**
+    /// Sets the cookie used in double-submit mode.
+    ///
+    /// **Parameters:**
+    /// - `name`: Cookie name.
+    ///
+    /// **Returns:**
+    /// - The updated `Csrf`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::csrf::Csrf;
+    /// let csrf = Csrf::new().cookie_name("XSRF-TOKEN");
+    /// ```
+    pub fn cookie_name(mut self, name: impl Into<String>) -> Csrf {
+        self.cookie_name = name.into();
+        self
+    }
+
+    /// # secure
+    ///
+    /// **Purpose:**
+    /// Marks the double-submit cookie `Secure` (HTTPS only).
+    ///
+    /// **Parameters:**
+    /// - `secure`: Whether to set the attribute.
+    ///
+    /// **Returns:**
+    /// - The updated `Csrf`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::csrf::Csrf;
+    /// let csrf = Csrf::new().secure(true);
+    /// ```
+    pub fn secure(mut self, secure: bool) -> Csrf {
+        self.secure = secure;
+        self
+    }
+
+    /// # middleware
+    ///
+    /// **Purpose:**
+    /// Turns the configuration into router middleware.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - A closure accepted by `Router::middleware`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{csrf::Csrf, router::Router};
+    /// let router = Router::new().middleware(Csrf::new().middleware());
+    /// ```
+    pub fn middleware(self) -> impl Fn(&Request, Next<'_>) -> Response + Send + Sync + 'static {
+        move |request, next| self.handle(request, next)
+    }
+
+    /// # handle
+    ///
+    /// **Purpose:**
+    /// Verifies the token on state-changing requests and exposes the current token to the
+    /// handler. With a `Session` on the request the token lives in the session, which keeps a
+    /// newly issued one only if it holds other data or the response embeds the token (a
+    /// rendered form); otherwise it is kept in a script-readable cookie that the client must
+    /// echo back.
+    ///
+    /// **Parameters:**
+    /// - `request`: Request being handled.
+    /// - `next`: The rest of the middleware chain.
+    ///
+    /// **Returns:**
+    /// - The downstream response, or `403 Forbidden` if the token is missing, empty, or wrong.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{csrf::Csrf, request::Request, response::Response, router::Next};
+    /// # let csrf = Csrf::new();
+    /// # let request = &Request::read_from(&mut &b"POST /profile HTTP/1.1\r\n\r\n"[..])?;
+    /// # let next: Next<'_> = &|_| Response::new(200, "OK");
+    /// let response = csrf.handle(request, next);
+    /// # assert_eq!(response.status, 403);
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn handle(&self, request: &Request, next: Next<'_>) -> Response {
+        let session = request.extensions.get::<Session>();
+        let expected = match session {
+            Some(session) => session.get(SESSION_KEY),
+            None => cookie::get(&request.headers, &self.cookie_name).map(str::to_string),
+        };
+
+        if matches!(request.method.as_str(), "POST" | "PUT" | "PATCH" | "DELETE") {
+            let submitted = self.submitted(request);
+            let valid = match (&expected, &submitted) {
+                (Some(expected), Some(submitted)) if !expected.is_empty() => {
+                    random::constant_time_eq(expected.as_bytes(), submitted.as_bytes())
+                }
+                _ => false,
+            };
+            if !valid {
+                return Response::new(403, "Forbidden")
+                    .with_header("Content-Type", "text/plain; charset=utf-8")
+                    .with_body("missing or invalid CSRF token");
+            }
+        }
+
+        let issued = expected.as_deref().is_none_or(str::is_empty);
+        let token = expected.filter(|_| !issued).unwrap_or_else(random::token);
+
+        let mut request = request.clone();
+        request.extensions.insert(CsrfToken(token.clone()));
+        let mut response = next(&request);
+
+        if issued && let Some(session) = session {
+            if !session.is_empty() || carries(&response, &token) {
+                session.insert(SESSION_KEY, token.as_str());
+            }
+        } else if issued {
+            let cookie = Cookie::new(self.cookie_name.as_str(), token)
+                .http_only(false)
+                .secure(self.secure)
+                .same_site(SameSite::Strict);
+            response.headers.append("Set-Cookie", cookie.to_string());
+        }
+        response
+    }
+
+    /// # submitted
+    ///
+    /// **Purpose:**
+    /// Reads the token sent with a request, from the header or a URL-encoded form body.
+    ///
+    /// **Parameters:**
+    /// - `request`: Request being checked.
+    ///
+    /// **Returns:**
+    /// - `Some(token)` if present, otherwise `None`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let submitted = self.submitted(request);
+    /// ```
+    fn submitted(&self, request: &Request) -> Option<String> {
+        if let Some(token) = request.headers.get(&self.header) {
+            return Some(token.trim().to_string());
+        }
+        let form = request
+            .headers
+            .get("Content-Type")
+            .is_some_and(|value| value.to_ascii_lowercase().starts_with("application/x-www-form-urlencoded"));
+        if !form {
+            return None;
+        }
+        let body = std::str::from_utf8(&request.body).ok()?;
+        QueryParams::parse(body).get(FORM_FIELD).map(str::to_string)
+    }
+}
+
+
+/// # carries
+///
+/// **Purpose:**
+/// Checks whether a response hands a token to the client, in its body or a header.
+///
+/// **Parameters:**
+/// - `response`: Downstream response.
+/// - `token`: Token issued for the request.
+///
+/// **Returns:**
+/// - `true` if the token appears in the response.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// if carries(&response, &token) { session.insert(SESSION_KEY, token.as_str()); }
+/// ```
+fn carries(response: &Response, token: &str) -> bool {
+    response.body.windows(token.len()).any(|window| window == token.as_bytes())
+        || response.headers.iter().any(|(_, value)| value.contains(token))
+}
+
+
+/// # is_get_form
+///
+/// **Purpose:**
+/// Checks whether a lower-cased `<form ...>` tag submits with `GET` (the default method).
+///
+/// **Parameters:**
+/// - `tag`: Opening tag text.
+///
+/// **Returns:**
+/// - `true` if the tag has no `method` or `method="get"`.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// assert!(!is_get_form(r#"<form method="post">"#));
+/// ```
+fn is_get_form(tag: &str) -> bool {
+    let Some(position) = tag.find("method") else {
+        return true;
+    };
+    tag[position + "method".len()..]
+        .trim_start()
+        .strip_prefix('=')
+        .unwrap_or_default()
+        .trim_start()
+        .trim_start_matches(['"', '\''])
+        .starts_with("get")
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::Sessions;
+
+    fn request(raw: &str) -> Request {
+        Request::read_from(&mut raw.as_bytes()).unwrap()
+    }
+
+    fn with_session(raw: &str, handler: Next<'_>) -> Response {
+        let csrf = Csrf::new();
+        Sessions::new().handle(&request(raw), &|request| csrf.handle(request, handler))
+    }
+
+    #[test]
+    fn anonymous_requests_do_not_create_sessions() {
+        let response = with_session("GET / HTTP/1.1\r\nHost: example.com\r\n\r\n", &|_| Response::new(200, "OK"));
+        assert!(!response.headers.contains("Set-Cookie"));
+    }
+
+    #[test]
+    fn rendered_token_is_kept_in_the_session() {
+        let response = with_session("GET /form HTTP/1.1\r\nHost: example.com\r\n\r\n", &|request| {
+            let token = request.extensions.get::<CsrfToken>().unwrap();
+            Response::new(200, "OK").with_body(format!("<form method=\"post\">{}</form>", token.hidden_input()))
+        });
+        assert!(response.headers.get("Set-Cookie").is_some_and(|cookie| cookie.starts_with("sid=")));
+    }
+
+    #[test]
+    fn empty_tokens_never_match() {
+        let csrf = Csrf::new();
+        let raw = "POST / HTTP/1.1\r\nHost: example.com\r\nCookie: csrf_token=\r\nX-CSRF-Token: \r\nContent-Length: 0\r\n\r\n";
+        let response = csrf.handle(&request(raw), &|_| Response::new(200, "OK"));
+        assert_eq!(response.status, 403);
+    }
+
+    #[test]
+    fn double_submit_token_must_match() {
+        let csrf = Csrf::new();
+        let ok = "POST / HTTP/1.1\r\nHost: example.com\r\nCookie: csrf_token=abc\r\nX-CSRF-Token: abc\r\nContent-Length: 0\r\n\r\n";
+        assert_eq!(csrf.handle(&request(ok), &|_| Response::new(200, "OK")).status, 200);
+        let wrong = ok.replace("X-CSRF-Token: abc", "X-CSRF-Token: abd");
+        assert_eq!(csrf.handle(&request(&wrong), &|_| Response::new(200, "OK")).status, 403);
+    }
+}
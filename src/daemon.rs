@@ -0,0 +1,547 @@
+
+// ============================================================
+//  DAEGONICA SOFTWARE — daemon.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: Daemon
+//!
+//! **Purpose:**
+//! Runs the server in the background without a process supervisor: forks away from the
+//! terminal, sends standard output and error to log files, and records the process id.
+//!
+//! **Context:**
+//! - Called by the executable for `--daemon` before anything else runs, since forking only
+//!   carries the calling thread into the child.
+//!
+//! **Responsibilities:**
+//! - Opens the log files and checks the PID file while the terminal can still show an error.
+//! - Forks, starts a new session, and forks again so the daemon can never regain a controlling
+//!   terminal; the original process exits once the daemon reports it started.
+//! - Points standard input at `/dev/null` and standard output and error at the log files.
+//! - Changes to `/` (or a chosen working directory), so the daemon does not keep the directory
+//!   it was started from in use, and sets a `022` file mode mask.
+//! - Writes the PID file atomically, refusing to start over one that names a live process, and
+//!   removes it when the returned guard is dropped.
+//! - Does NOT reopen the log files for rotation.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+use std::{
+    fs,
+    io,
+    path::PathBuf,
+};
+
+#[cfg(unix)]
+use std::{
+    env,
+    fs::{File, OpenOptions},
+    io::{Read, Write},
+    os::{fd::AsRawFd, unix::fs::OpenOptionsExt},
+    path::{Path, absolute},
+    process,
+};
+
+
+/// # UMASK
+///
+/// **Summary:**
+/// File mode mask the daemon runs with: files it creates are never group- or world-writable.
+#[cfg(unix)]
+const UMASK: libc::mode_t = 0o022;
+
+
+/// # Daemon
+///
+/// **Summary:**
+/// Where a detached server sends its output and records its process id.
+///
+/// **Fields:**
+/// - `pid_file`: File to write the daemon's process id to, if any.
+/// - `working_directory`: Directory the daemon changes to; `/` if unset.
+/// - `stdout`: File standard output is appended to; `/dev/null` if unset.
+/// - `stderr`: File standard error is appended to; `/dev/null` if unset.
+///
+/// **Usage Example:**
+/// ```rust,no_run
+/// # use server::{daemon::Daemon, server::Server};
+/// let _pid_file = Daemon::new().pid_file("/run/server.pid").stdout("server.log").stderr("server.err").detach()?;
+/// Server::new("0.0.0.0:80").run()?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Daemon {
+    pid_file: Option<PathBuf>,
+    working_directory: Option<PathBuf>,
+    stdout: Option<PathBuf>,
+    stderr: Option<PathBuf>,
+}
+
+
+/// # PidFile
+///
+/// **Summary:**
+/// Guard held by the daemon for as long as it runs; removes the PID file when dropped.
+///
+/// **Fields:**
+/// - `path`: PID file written by `Daemon::detach`, if one was configured.
+///
+/// **Usage Example:**
+/// ```rust,no_run
+/// # use server::{daemon::Daemon, server::Server};
+/// # let server = Server::new("0.0.0.0:80");
+/// let pid_file = Daemon::new().pid_file("server.pid").detach()?;
+/// server.run()?;
+/// drop(pid_file);
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct PidFile {
+    path: Option<PathBuf>,
+}
+
+
+impl Daemon {
+
+    /// # new
+    ///
+    /// **Purpose:**
+    /// Creates a daemon setting that discards all output and writes no PID file.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - A default `Daemon`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::daemon::Daemon;
+    /// let daemon = Daemon::new();
+    /// ```
+    pub fn new() -> Daemon {
+        Daemon::default()
+    }
+
+    /// # pid_file
+    ///
+    /// **Purpose:**
+    /// Records the daemon's process id in a file, for init scripts and `kill $(cat …)`.
+    ///
+    /// **Parameters:**
+    /// - `path`: PID file path.
+    ///
+    /// **Returns:**
+    /// - The updated `Daemon`.
+    ///
+    /// **Errors / Failures:**
+    /// None. The file is written by `detach`.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::daemon::Daemon;
+    /// let daemon = Daemon::new().pid_file("/run/server.pid");
+    /// ```
+    pub fn pid_file(mut self, path: impl Into<PathBuf>) -> Daemon {
+        self.pid_file = Some(path.into());
+        self
+    }
+
+    /// # working_directory
+    ///
+    /// **Purpose:**
+    /// Sets the directory the daemon changes to instead of `/`, for servers configured with
+    /// relative paths such as a document root.
+    ///
+    /// **Parameters:**
+    /// - `path`: Working directory of the daemon.
+    ///
+    /// **Returns:**
+    /// - The updated `Daemon`.
+    ///
+    /// **Errors / Failures:**
+    /// None. The directory is entered by `detach`.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::daemon::Daemon;
+    /// let daemon = Daemon::new().working_directory("/srv/www");
+    /// ```
+    pub fn working_directory(mut self, path: impl Into<PathBuf>) -> Daemon {
+        self.working_directory = Some(path.into());
+        self
+    }
+
+    /// # stdout
+    ///
+    /// **Purpose:**
+    /// Appends standard output, where diagnostic messages go unless an error log is set, to a
+    /// file.
+    ///
+    /// **Parameters:**
+    /// - `path`: Log file path; created if missing.
+    ///
+    /// **Returns:**
+    /// - The updated `Daemon`.
+    ///
+    /// **Errors / Failures:**
+    /// None. The file is opened by `detach`.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::daemon::Daemon;
+    /// let daemon = Daemon::new().stdout("server.log");
+    /// ```
+    pub fn stdout(mut self, path: impl Into<PathBuf>) -> Daemon {
+        self.stdout = Some(path.into());
+        self
+    }
+
+    /// # stderr
+    ///
+    /// **Purpose:**
+    /// Appends standard error, where panics are reported, to a file.
+    ///
+    /// **Parameters:**
+    /// - `path`: Log file path; created if missing, and may be the same as `stdout`.
+    ///
+    /// **Returns:**
+    /// - The updated `Daemon`.
+    ///
+    /// **Errors / Failures:**
+    /// None. The file is opened by `detach`.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::daemon::Daemon;
+    /// let daemon = Daemon::new().stdout("server.log").stderr("server.log");
+    /// ```
+    pub fn stderr(mut self, path: impl Into<PathBuf>) -> Daemon {
+        self.stderr = Some(path.into());
+        self
+    }
+
+    /// # detach
+    ///
+    /// **Purpose:**
+    /// Moves the process into the background. The process that calls it exits once the daemon
+    /// has started (with status `1` and the daemon's error on standard error if it could not);
+    /// only the daemon returns.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - In the daemon, the guard that removes the PID file when dropped.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns an I/O error, before forking, if a log file cannot be opened, the PID file
+    ///   cannot be read, or forking fails.
+    /// - The daemon fails to start (and the caller exits with status `1`) if it cannot enter
+    ///   its working directory or write the PID file.
+    /// - `AlreadyExists` if the PID file names a process that is still running.
+    /// - Must be called before any other thread is started; threads do not survive the fork.
+    ///
+    /// **Examples:**
+    /// ```rust,no_run
+    /// # use server::daemon::Daemon;
+    /// let _pid_file = Daemon::new().pid_file("server.pid").detach()?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    #[cfg(unix)]
+    pub fn detach(self) -> io::Result<PidFile> {
+        let stdin = File::open("/dev/null")?;
+        let stdout = append(self.stdout.as_deref())?;
+        let stderr = append(self.stderr.as_deref())?;
+        let pid_file = self.pid_file.as_deref().map(absolute).transpose()?;
+        if let Some(path) = &pid_file {
+            check_stale(path)?;
+        }
+        let (mut status_reader, mut status_writer) = io::pipe()?;
+
+        // SAFETY: the process is single-threaded here, as documented, so the child is a
+        // complete copy.
+        let child = unsafe { libc::fork() };
+        if child < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        if child > 0 {
+            drop(status_writer);
+            let mut status = Vec::new();
+            let _ = status_reader.read_to_end(&mut status);
+            // SAFETY: reaps the intermediate child, which exits right after forking the daemon.
+            unsafe { libc::waitpid(child, std::ptr::null_mut(), 0) };
+            match status.split_first() {
+                Some((0, _)) => process::exit(0),
+                Some((_, message)) => eprintln!("daemon failed to start: {}", String::from_utf8_lossy(message)),
+                None => eprintln!("daemon exited before starting"),
+            }
+            process::exit(1);
+        }
+        drop(status_reader);
+
+        // SAFETY: plain system calls in the single-threaded child; `_exit` skips destructors
+        // that belong to the parent's copy of the process.
+        unsafe {
+            if libc::setsid() < 0 {
+                let _ = status_writer.write_all(format!("\x01{}", io::Error::last_os_error()).as_bytes());
+                libc::_exit(1);
+            }
+            match libc::fork() {
+                0 => {}
+                -1 => {
+                    let _ = status_writer.write_all(format!("\x01{}", io::Error::last_os_error()).as_bytes());
+                    libc::_exit(1);
+                }
+                _ => libc::_exit(0),
+            }
+        }
+
+        let started = (|| {
+            env::set_current_dir(self.working_directory.as_deref().unwrap_or(Path::new("/")))?;
+            // SAFETY: `umask` only replaces the process's file mode mask.
+            unsafe { libc::umask(UMASK) };
+            if let Some(path) = &pid_file {
+                write_pid_file(path)?;
+            }
+            redirect(&stdin, 0)?;
+            redirect(&stdout, 1)?;
+            redirect(&stderr, 2)
+        })();
+        match started {
+            Ok(()) => {
+                let _ = status_writer.write_all(&[0]);
+                Ok(PidFile { path: pid_file })
+            }
+            Err(err) => {
+                let _ = status_writer.write_all(format!("\x01{err}").as_bytes());
+                process::exit(1);
+            }
+        }
+    }
+
+    /// # detach
+    ///
+    /// **Purpose:**
+    /// Stands in for daemonizing on platforms without `fork`.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// Never returns a guard.
+    ///
+    /// **Errors / Failures:**
+    /// - Always `Unsupported`; run the server as a service instead.
+    ///
+    /// **Examples:**
+    /// ```rust,no_run
+    /// # use server::daemon::Daemon;
+    /// let _pid_file = Daemon::new().detach()?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    #[cfg(not(unix))]
+    pub fn detach(self) -> io::Result<PidFile> {
+        Err(io::Error::new(io::ErrorKind::Unsupported, "daemon mode needs a Unix platform"))
+    }
+}
+
+
+impl Drop for PidFile {
+
+    /// # drop
+    ///
+    /// **Purpose:**
+    /// Removes the PID file, unless another process has since written its own id to it.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// None.
+    ///
+    /// **Errors / Failures:**
+    /// None. A file that cannot be read or removed is left alone.
+    ///
+    /// **Examples:**
+    /// ```rust,no_run
+    /// # use server::daemon::Daemon;
+    /// # let pid_file = Daemon::new().pid_file("server.pid").detach()?;
+    /// drop(pid_file);
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    fn drop(&mut self) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        let ours = fs::read_to_string(path).is_ok_and(|contents| contents.trim() == std::process::id().to_string());
+        if ours {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
+
+
+/// # append
+///
+/// **Purpose:**
+/// Opens a log file for the daemon's output, or `/dev/null` when none is set.
+///
+/// **Parameters:**
+/// - `path`: Log file path, if any.
+///
+/// **Returns:**
+/// - The file, opened for appending.
+///
+/// **Errors / Failures:**
+/// - Returns an I/O error if the file cannot be created or opened.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let stdout = append(Some(Path::new("server.log")))?;
+/// ```
+#[cfg(unix)]
+fn append(path: Option<&Path>) -> io::Result<File> {
+    OpenOptions::new().create(true).append(true).open(path.unwrap_or(Path::new("/dev/null")))
+}
+
+
+/// # check_stale
+///
+/// **Purpose:**
+/// Makes sure a PID file left behind does not belong to a daemon that is still running.
+///
+/// **Parameters:**
+/// - `path`: PID file path.
+///
+/// **Returns:**
+/// - `Ok(())` if the file is missing, unreadable as a process id, or names a process that no
+///   longer exists; it is then overwritten.
+///
+/// **Errors / Failures:**
+/// - `AlreadyExists` if the process it names is alive.
+/// - Returns an I/O error if the file exists but cannot be read.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// check_stale(Path::new("server.pid"))?;
+/// ```
+#[cfg(unix)]
+fn check_stale(path: &Path) -> io::Result<()> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(()),
+        Err(err) => return Err(err),
+    };
+    let Ok(pid) = contents.trim().parse::<libc::pid_t>() else {
+        return Ok(());
+    };
+    // SAFETY: signal 0 only checks that the process exists.
+    let alive = pid > 0
+        && (unsafe { libc::kill(pid, 0) } == 0 || io::Error::last_os_error().raw_os_error() == Some(libc::EPERM));
+    if alive {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} names running process {pid}", path.display()),
+        ));
+    }
+    Ok(())
+}
+
+
+/// # write_pid_file
+///
+/// **Purpose:**
+/// Writes the daemon's process id so that a reader never sees a partly written file: the id
+/// goes to a new temporary file beside it, which is then renamed over the PID file.
+///
+/// **Parameters:**
+/// - `path`: PID file path.
+///
+/// **Returns:**
+/// - `Ok(())` once `path` holds the process id.
+///
+/// **Errors / Failures:**
+/// - `AlreadyExists` if the temporary file is already there (it is created exclusively).
+/// - Returns an I/O error if the file cannot be written or renamed; the temporary file is
+///   removed.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// write_pid_file(Path::new("/run/server.pid"))?;
+/// ```
+#[cfg(unix)]
+fn write_pid_file(path: &Path) -> io::Result<()> {
+    let mut temporary = path.as_os_str().to_owned();
+    temporary.push(format!(".{}.tmp", process::id()));
+    let temporary = PathBuf::from(temporary);
+    let mut file = OpenOptions::new().write(true).create_new(true).mode(0o644).open(&temporary)?;
+    let written = file
+        .write_all(format!("{}\n", process::id()).as_bytes())
+        .and_then(|()| file.sync_all())
+        .and_then(|()| fs::rename(&temporary, path));
+    if written.is_err() {
+        let _ = fs::remove_file(&temporary);
+    }
+    written
+}
+
+
+/// # redirect
+///
+/// **Purpose:**
+/// Points one of the standard streams at a file.
+///
+/// **Parameters:**
+/// - `file`: File to duplicate.
+/// - `stream`: Descriptor to replace: `0`, `1`, or `2`.
+///
+/// **Returns:**
+/// - `Ok(())` once `stream` refers to `file`.
+///
+/// **Errors / Failures:**
+/// - Returns the OS error if the descriptor cannot be duplicated.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// redirect(&stdout, 1)?;
+/// ```
+#[cfg(unix)]
+fn redirect(file: &File, stream: libc::c_int) -> io::Result<()> {
+    // SAFETY: both descriptors are open; `dup2` atomically replaces `stream`.
+    if unsafe { libc::dup2(file.as_raw_fd(), stream) } < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_pid_file_is_replaced_through_an_exclusive_temporary_file() {
+        let path = env::temp_dir().join(format!("daemon-test-{}.pid", process::id()));
+        fs::write(&path, "stale\n").unwrap();
+        write_pid_file(&path).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), format!("{}\n", process::id()));
+
+        let temporary = PathBuf::from(format!("{}.{}.tmp", path.display(), process::id()));
+        assert!(!temporary.exists());
+        fs::write(&temporary, "").unwrap();
+        assert_eq!(write_pid_file(&path).unwrap_err().kind(), io::ErrorKind::AlreadyExists);
+        let _ = fs::remove_file(&temporary);
+        let _ = fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,230 @@
+
+// ============================================================
+//  DAEGONICA SOFTWARE — date.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: Date
+//!
+//! **Purpose:**
+//! Formats timestamps for logs and protocol headers without external date crates.
+//!
+//! **Context:**
+//! - Used by the access log to stamp each request, and by responses for their `Date` header.
+//!
+//! **Responsibilities:**
+//! - Converts `SystemTime` into UTC calendar fields.
+//! - Formats Common Log Format timestamps and HTTP dates (IMF-fixdate).
+//! - Caches the current HTTP date, formatting it at most once per second.
+//! - Does NOT handle local time zones.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+use std::{
+    sync::{Mutex, PoisonError},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+
+/// # MONTHS
+///
+/// **Summary:**
+/// English month abbreviations, January first.
+pub(crate) const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+
+/// # WEEKDAYS
+///
+/// **Summary:**
+/// English weekday abbreviations, Sunday first.
+pub(crate) const WEEKDAYS: [&str; 7] = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+
+
+/// # CURRENT_HTTP_DATE
+///
+/// **Summary:**
+/// Epoch second and formatted HTTP date of the last `http_date_now` call.
+static CURRENT_HTTP_DATE: Mutex<(u64, String)> = Mutex::new((0, String::new()));
+
+
+/// # DateTime
+///
+/// **Summary:**
+/// UTC calendar fields of a timestamp.
+///
+/// **Fields:**
+/// - `year`: Calendar year.
+/// - `month`: Month, `1..=12`.
+/// - `day`: Day of month, `1..=31`.
+/// - `hour`: Hour, `0..=23`.
+/// - `minute`: Minute, `0..=59`.
+/// - `second`: Second, `0..=59`.
+/// - `weekday`: Day of week, `0` = Sunday.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct DateTime {
+    pub year: i64,
+    pub month: u32,
+    pub day: u32,
+    pub hour: u32,
+    pub minute: u32,
+    pub second: u32,
+    pub weekday: u32,
+}
+
+
+impl DateTime {
+
+    /// # from_system_time
+    ///
+    /// **Purpose:**
+    /// Breaks a `SystemTime` into UTC calendar fields.
+    ///
+    /// **Parameters:**
+    /// - `time`: Timestamp to convert; times before 1970 are clamped to the epoch.
+    ///
+    /// **Returns:**
+    /// - The `DateTime` for `time`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let now = DateTime::from_system_time(SystemTime::now());
+    /// ```
+    pub fn from_system_time(time: SystemTime) -> DateTime {
+        let secs = time.duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0) as i64;
+        let days = secs.div_euclid(86_400);
+        let rem = secs.rem_euclid(86_400);
+
+        // Civil-from-days (Howard Hinnant), shifted so the era starts on March 1st.
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let doe = z.rem_euclid(146_097);
+        let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+        let month = (if mp < 10 { mp + 3 } else { mp - 9 }) as u32;
+        let year = yoe + era * 400 + i64::from(month <= 2);
+
+        DateTime {
+            year,
+            month,
+            day,
+            hour: (rem / 3_600) as u32,
+            minute: (rem % 3_600 / 60) as u32,
+            second: (rem % 60) as u32,
+            weekday: (days + 4).rem_euclid(7) as u32,
+        }
+    }
+}
+
+
+/// # format_clf
+///
+/// **Purpose:**
+/// Formats a timestamp for Common Log Format (`10/Oct/2000:13:55:36 +0000`).
+///
+/// **Parameters:**
+/// - `time`: Timestamp to format.
+///
+/// **Returns:**
+/// - The formatted UTC timestamp, without surrounding brackets.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust
+/// # use server::date::format_clf;
+/// # use std::time::SystemTime;
+/// let stamp = format_clf(SystemTime::now());
+/// ```
+pub fn format_clf(time: SystemTime) -> String {
+    let t = DateTime::from_system_time(time);
+    format!(
+        "{:02}/{}/{}:{:02}:{:02}:{:02} +0000",
+        t.day,
+        MONTHS[t.month as usize - 1],
+        t.year,
+        t.hour,
+        t.minute,
+        t.second
+    )
+}
+
+
+/// # format_http_date
+///
+/// **Purpose:**
+/// Formats a timestamp as an HTTP date (`Sun, 06 Nov 1994 08:49:37 GMT`).
+///
+/// **Parameters:**
+/// - `time`: Timestamp to format.
+///
+/// **Returns:**
+/// - The IMF-fixdate string.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust
+/// # use server::date::format_http_date;
+/// # use std::time::SystemTime;
+/// let stamp = format_http_date(SystemTime::now());
+/// ```
+pub fn format_http_date(time: SystemTime) -> String {
+    let t = DateTime::from_system_time(time);
+    format!(
+        "{}, {:02} {} {} {:02}:{:02}:{:02} GMT",
+        WEEKDAYS[t.weekday as usize],
+        t.day,
+        MONTHS[t.month as usize - 1],
+        t.year,
+        t.hour,
+        t.minute,
+        t.second
+    )
+}
+
+
+/// # http_date_now
+///
+/// **Purpose:**
+/// Returns the current time as an HTTP date, reusing the string formatted earlier in the
+/// same second so busy servers format it about once per second.
+///
+/// **Parameters:**
+/// None.
+///
+/// **Returns:**
+/// - The current IMF-fixdate string.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust
+/// # use server::{date::http_date_now, response::Response};
+/// # let mut response = Response::new(200, "OK");
+/// response.headers.set("Date", http_date_now());
+/// ```
+pub fn http_date_now() -> String {
+    let now = SystemTime::now();
+    let second = now.duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+    let mut current = CURRENT_HTTP_DATE.lock().unwrap_or_else(PoisonError::into_inner);
+    if current.0 != second || current.1.is_empty() {
+        *current = (second, format_http_date(now));
+    }
+    current.1.clone()
+}
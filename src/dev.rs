@@ -0,0 +1,505 @@
+
+// ============================================================
+//  DAEGONICA SOFTWARE — dev.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: Dev
+//!
+//! **Purpose:**
+//! Reloads open browser tabs when watched files change, for use as a front-end dev server.
+//!
+//! **Context:**
+//! - Enabled with `Server::dev_reload` (the binary's `--dev` flag); a `Watcher` reports
+//!   changes through `DevReload::watch`.
+//! - Consulted by the connection handler before routing, and when writing every response.
+//!   Open event streams are handed to one notifier thread, started with the server's other
+//!   services, so tabs do not hold worker threads.
+//!
+//! **Responsibilities:**
+//! - Injects a small script into HTML responses that subscribes to a Server-Sent Events stream.
+//! - Holds those streams open, sending a `reload` event after each batch of changes and a
+//!   comment every few seconds so dead connections are noticed; past `MAX_CLIENTS` open
+//!   streams, new ones are refused with `503`.
+//! - Sends the server's boot id on connect, so tabs also reload after the server restarts.
+//! - Does NOT hot-swap stylesheets or modules; every change reloads the whole page.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+use std::{
+    io::{self, Write},
+    net::TcpStream,
+    sync::{
+        Arc, Condvar, Mutex, PoisonError,
+        atomic::{AtomicBool, Ordering},
+    },
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::{request::Request, response::Response, watch::Watcher};
+
+
+/// # DEFAULT_PATH
+///
+/// **Summary:**
+/// Path of the event stream unless `path` says otherwise.
+const DEFAULT_PATH: &str = "/__dev/reload";
+
+
+/// # PING_INTERVAL
+///
+/// **Summary:**
+/// Longest silence on an event stream before a keep-alive comment is sent.
+const PING_INTERVAL: Duration = Duration::from_secs(10);
+
+
+/// # MAX_CLIENTS
+///
+/// **Summary:**
+/// Most event streams held open at once; a dev server rarely has more than a few tabs.
+pub const MAX_CLIENTS: usize = 64;
+
+
+/// # CLIENT_WRITE_TIMEOUT
+///
+/// **Summary:**
+/// Longest an event may wait to be written to one stream, so a stalled tab cannot hold up the
+/// others.
+const CLIENT_WRITE_TIMEOUT: Duration = Duration::from_secs(1);
+
+
+/// # DevReload
+///
+/// **Summary:**
+/// Live-reload channel shared by the watcher and every open event stream. Clones share state.
+///
+/// **Fields:**
+/// - `path`: Path of the event stream.
+/// - `boot`: Identifier of this server process.
+/// - `changes`: Number of change batches seen, with a condition variable woken on each (and
+///   when the notifier should stop).
+/// - `clients`: Open event streams, written to by the notifier thread.
+/// - `stopped`: Set once the server stops, ending the notifier thread.
+///
+/// **Usage Example:**
+/// ```rust,no_run
+/// # use server::{dev::DevReload, server::Server, watch::Watcher};
+/// let reload = DevReload::new();
+/// reload.watch(Watcher::new("html")).spawn()?;
+/// Server::new("127.0.0.1:7878").dev_reload(reload).run()?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Clone)]
+pub struct DevReload {
+    path: String,
+    boot: u128,
+    changes: Arc<(Mutex<u64>, Condvar)>,
+    clients: Arc<Mutex<Vec<TcpStream>>>,
+    stopped: Arc<AtomicBool>,
+}
+
+
+impl Default for DevReload {
+
+    /// # default
+    ///
+    /// **Purpose:**
+    /// Creates a channel serving its event stream at `/__dev/reload`.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - A new `DevReload`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::dev::DevReload;
+    /// let reload = DevReload::default();
+    /// ```
+    fn default() -> Self {
+        DevReload {
+            path: DEFAULT_PATH.to_string(),
+            boot: SystemTime::now().duration_since(UNIX_EPOCH).map(|elapsed| elapsed.as_nanos()).unwrap_or(0),
+            changes: Arc::default(),
+            clients: Arc::default(),
+            stopped: Arc::default(),
+        }
+    }
+}
+
+
+impl DevReload {
+
+    /// # new
+    ///
+    /// **Purpose:**
+    /// Creates a channel serving its event stream at `/__dev/reload`.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - A new `DevReload`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::dev::DevReload;
+    /// let reload = DevReload::new();
+    /// ```
+    pub fn new() -> DevReload {
+        DevReload::default()
+    }
+
+    /// # path
+    ///
+    /// **Purpose:**
+    /// Moves the event stream to another path, for sites that use `/__dev/` themselves.
+    ///
+    /// **Parameters:**
+    /// - `path`: Absolute request path.
+    ///
+    /// **Returns:**
+    /// - The updated `DevReload`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::dev::DevReload;
+    /// let reload = DevReload::new().path("/_reload");
+    /// ```
+    pub fn path(mut self, path: impl Into<String>) -> DevReload {
+        self.path = path.into();
+        self
+    }
+
+    /// # watch
+    ///
+    /// **Purpose:**
+    /// Makes a watcher trigger reloads; spawn the returned watcher to start it.
+    ///
+    /// **Parameters:**
+    /// - `watcher`: Watcher over the files the pages depend on.
+    ///
+    /// **Returns:**
+    /// - The watcher, with a listener that notifies this channel.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,no_run
+    /// # use server::{dev::DevReload, watch::Watcher};
+    /// # let reload = DevReload::new();
+    /// reload.watch(Watcher::new("html").watch("templates")).spawn()?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn watch(&self, watcher: Watcher) -> Watcher {
+        let reload = self.clone();
+        watcher.on_change(move |_| reload.notify())
+    }
+
+    /// # notify
+    ///
+    /// **Purpose:**
+    /// Tells every connected browser to reload.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// None.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # let reload = server::dev::DevReload::new();
+    /// reload.notify();
+    /// ```
+    pub fn notify(&self) {
+        let (count, changed) = &*self.changes;
+        *count.lock().unwrap_or_else(PoisonError::into_inner) += 1;
+        changed.notify_all();
+    }
+
+    /// # handles
+    ///
+    /// **Purpose:**
+    /// Checks whether a request is for the event stream.
+    ///
+    /// **Parameters:**
+    /// - `request`: Parsed client request.
+    ///
+    /// **Returns:**
+    /// - `true` for a `GET` of the stream path.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// if reload.handles(&request) { reload.subscribe(&stream)?; }
+    /// ```
+    pub(crate) fn handles(&self, request: &Request) -> bool {
+        request.method == "GET" && request.path() == self.path
+    }
+
+    /// # subscribe
+    ///
+    /// **Purpose:**
+    /// Answers the event stream request and hands the connection to the notifier thread, so
+    /// the worker that read the request is free again.
+    ///
+    /// **Parameters:**
+    /// - `stream`: Client socket; the notifier keeps its own handle open.
+    ///
+    /// **Returns:**
+    /// - `Ok(())` once the stream is open, or refused with `503` when `MAX_CLIENTS` are open.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns an I/O error if the response head cannot be written or the socket cannot be
+    ///   shared.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// return reload.subscribe(stream).map(|()| false);
+    /// ```
+    pub(crate) fn subscribe(&self, mut stream: &TcpStream) -> io::Result<()> {
+        let mut clients = self.clients.lock().unwrap_or_else(PoisonError::into_inner);
+        if clients.len() >= MAX_CLIENTS {
+            return Response::new(503, "Service Unavailable")
+                .with_header("Content-Type", "text/plain; charset=utf-8")
+                .with_body("too many live-reload streams")
+                .write_final(stream, CLIENT_WRITE_TIMEOUT);
+        }
+        stream.set_write_timeout(Some(CLIENT_WRITE_TIMEOUT))?;
+        write!(
+            stream,
+            "HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nCache-Control: no-store\r\nConnection: close\r\n\r\nretry: 1000\nevent: hello\ndata: {}\n\n",
+            self.boot
+        )?;
+        clients.push(stream.try_clone()?);
+        Ok(())
+    }
+
+    /// # start
+    ///
+    /// **Purpose:**
+    /// Starts the notifier thread, which sends a `reload` event to every open stream after
+    /// each batch of changes and a keep-alive comment after each quiet interval, dropping the
+    /// streams whose client has gone away.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - `Ok(())` once the thread is running.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns an I/O error if the thread cannot be spawned.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// reload.start()?;
+    /// ```
+    pub(crate) fn start(&self) -> io::Result<()> {
+        let reload = self.clone();
+        let seen = *self.changes.0.lock().unwrap_or_else(PoisonError::into_inner);
+        thread::Builder::new().name("dev-reload".to_string()).spawn(move || reload.notify_clients(seen))?;
+        Ok(())
+    }
+
+    /// # stop
+    ///
+    /// **Purpose:**
+    /// Ends the notifier thread, which closes the open streams on its way out.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// None.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// reload.stop();
+    /// ```
+    pub(crate) fn stop(&self) {
+        let (count, changed) = &*self.changes;
+        let _guard = count.lock().unwrap_or_else(PoisonError::into_inner);
+        self.stopped.store(true, Ordering::Release);
+        changed.notify_all();
+    }
+
+    /// # notify_clients
+    ///
+    /// **Purpose:**
+    /// Body of the notifier thread: waits for changes and writes the matching event to every
+    /// open stream until `stop` is called.
+    ///
+    /// **Parameters:**
+    /// - `seen`: Change count when the thread was started; later changes are sent.
+    ///
+    /// **Returns:**
+    /// None.
+    ///
+    /// **Errors / Failures:**
+    /// None. Streams that cannot be written are dropped.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// thread::spawn(move || reload.notify_clients(0));
+    /// ```
+    fn notify_clients(&self, mut seen: u64) {
+        let (count, changed) = &*self.changes;
+        loop {
+            let current = count.lock().unwrap_or_else(PoisonError::into_inner);
+            let (current, _) = changed
+                .wait_timeout_while(current, PING_INTERVAL, |current| *current == seen && !self.stopped.load(Ordering::Acquire))
+                .unwrap_or_else(PoisonError::into_inner);
+            let current = *current;
+            if self.stopped.load(Ordering::Acquire) {
+                break;
+            }
+            let event = if current == seen {
+                ": ping\n\n".to_string()
+            } else {
+                seen = current;
+                format!("event: reload\ndata: {seen}\n\n")
+            };
+            let mut clients = self.clients.lock().unwrap_or_else(PoisonError::into_inner);
+            clients.retain(|mut client| client.write_all(event.as_bytes()).is_ok());
+        }
+        self.clients.lock().unwrap_or_else(PoisonError::into_inner).clear();
+    }
+
+    /// # inject
+    ///
+    /// **Purpose:**
+    /// Adds the live-reload script to an HTML response, before `</body>` when there is one.
+    ///
+    /// **Parameters:**
+    /// - `response`: Response about to be written.
+    ///
+    /// **Returns:**
+    /// None. Non-HTML responses are left alone.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// reload.inject(&mut response);
+    /// ```
+    pub(crate) fn inject(&self, response: &mut Response) {
+        let html = response
+            .headers
+            .get("Content-Type")
+            .is_some_and(|value| value.trim_start().to_ascii_lowercase().starts_with("text/html"));
+        if !html || response.body_len() == 0 {
+            return;
+        }
+        let script = format!(
+            "<script>(function(){{var boot,source=new EventSource({path:?});\
+             source.addEventListener(\"hello\",function(e){{if(boot&&boot!==e.data)location.reload();boot=e.data;}});\
+             source.addEventListener(\"reload\",function(){{location.reload();}});}})();</script>\n",
+            path = self.path
+        );
+        let body = response.body_mut();
+        let lower = body.to_ascii_lowercase();
+        let at = lower.windows(7).rposition(|window| window == b"</body>").unwrap_or(body.len());
+        body.splice(at..at, script.into_bytes());
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader};
+    use std::net::TcpListener;
+
+    fn pair() -> (TcpStream, TcpStream) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let client = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        client.set_read_timeout(Some(Duration::from_secs(10))).unwrap();
+        (client, listener.accept().unwrap().0)
+    }
+
+    fn next_event(reader: &mut impl BufRead) -> String {
+        let mut event = String::new();
+        while !event.ends_with("\n\n") {
+            assert!(reader.read_line(&mut event).unwrap() > 0, "stream closed");
+        }
+        event
+    }
+
+    #[test]
+    fn the_script_goes_before_the_closing_body_tag() {
+        let reload = DevReload::new().path("/_reload");
+        let mut page = Response::new(200, "OK").with_header("Content-Type", "text/html").with_body("<body>hi</BODY>");
+        reload.inject(&mut page);
+        let body = String::from_utf8(page.body).unwrap();
+        assert!(body.starts_with("<body>hi<script>") && body.ends_with("</script>\n</BODY>"), "{body}");
+        assert!(body.contains("new EventSource(\"/_reload\")"));
+
+        let mut text = Response::new(200, "OK").with_header("Content-Type", "text/plain").with_body("hi");
+        reload.inject(&mut text);
+        assert_eq!(text.body, b"hi");
+    }
+
+    #[test]
+    fn subscribers_receive_reload_events_from_the_notifier() {
+        let reload = DevReload::new();
+        let (client, server) = pair();
+        reload.subscribe(&server).unwrap();
+        drop(server);
+        reload.start().unwrap();
+
+        let mut reader = BufReader::new(client);
+        let hello = next_event(&mut reader);
+        assert!(hello.starts_with("HTTP/1.1 200 OK\r\n") && hello.contains("text/event-stream"), "{hello}");
+        assert!(hello.ends_with(&format!("event: hello\ndata: {}\n\n", reload.boot)), "{hello}");
+        reload.notify();
+        assert_eq!(next_event(&mut reader), "event: reload\ndata: 1\n\n");
+
+        reload.stop();
+        let mut rest = String::new();
+        assert_eq!(reader.read_line(&mut rest).unwrap(), 0, "stopping closes the streams");
+    }
+
+    #[test]
+    fn streams_past_the_cap_are_refused() {
+        let reload = DevReload::new();
+        let held: Vec<_> = (0..MAX_CLIENTS).map(|_| pair()).collect();
+        for (_, server) in &held {
+            reload.subscribe(server).unwrap();
+        }
+        let (mut client, server) = pair();
+        reload.subscribe(&server).unwrap();
+        drop(server);
+        let mut reply = String::new();
+        io::Read::read_to_string(&mut client, &mut reply).unwrap();
+        assert!(reply.starts_with("HTTP/1.1 503"), "{reply}");
+    }
+}
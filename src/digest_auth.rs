@@ -0,0 +1,623 @@
+
+// ============================================================
+//  DAEGONICA SOFTWARE — digest_auth.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: Digest Auth
+//!
+//! **Purpose:**
+//! Implements HTTP Digest authentication (RFC 7616), so passwords never cross the wire.
+//!
+//! **Context:**
+//! - An alternative to `auth::BasicAuth` for deployments served over plain HTTP.
+//! - Authenticated requests carry an `AuthenticatedUser` in their extensions, exactly as with Basic.
+//!
+//! **Responsibilities:**
+//! - Issues random server nonces and expires them after a configurable lifetime (`stale=true`).
+//! - Rejects replayed requests by tracking the nonce counts already used with each nonce.
+//! - Verifies `qop=auth` responses for SHA-256 and MD5, including the `-sess` variants.
+//! - Does NOT support `auth-int` or hashed user names (`userhash=true`).
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use crate::{
+    auth::{AuthenticatedUser, authorization, quote, unauthorized},
+    hash, random,
+    request::Request,
+    response::Response,
+    router::Next,
+};
+
+
+/// # MAX_NONCES
+///
+/// **Summary:**
+/// Maximum number of outstanding nonces; the oldest is forgotten when a new one would exceed it.
+pub const MAX_NONCES: usize = 65_536;
+
+
+/// # Algorithm
+///
+/// **Summary:**
+/// Hash algorithm offered in the challenge.
+///
+/// **Variants:**
+/// - `Sha256`: `SHA-256`, preferred by RFC 7616.
+/// - `Md5`: `MD5`, for clients that predate RFC 7616.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Algorithm {
+    Sha256,
+    Md5,
+}
+
+
+/// # Nonce
+///
+/// **Summary:**
+/// Server-side record of an issued nonce.
+///
+/// **Fields:**
+/// - `issued`: When the nonce was handed out.
+/// - `highest`: Highest nonce count accepted so far.
+/// - `seen`: Bit `i` is set if count `highest - i` was accepted, so reordered requests still pass.
+#[derive(Debug, Clone, Copy)]
+struct Nonce {
+    issued: Instant,
+    highest: u64,
+    seen: u64,
+}
+
+
+/// # Nonces
+///
+/// **Summary:**
+/// Outstanding nonces with a queue in issue order, so expiry and the size cap drop the oldest
+/// from the front without scanning.
+///
+/// **Fields:**
+/// - `entries`: Nonce records by nonce.
+/// - `issued`: `(issued, nonce)` of every nonce handed out, oldest first; a nonce already
+///   removed from `entries` simply finds nothing to remove when it reaches the front.
+#[derive(Debug, Default)]
+struct Nonces {
+    entries: HashMap<String, Nonce>,
+    issued: VecDeque<(Instant, String)>,
+}
+
+
+/// # DigestAuth
+///
+/// **Summary:**
+/// HTTP Digest authentication policy with its nonce store.
+///
+/// **Fields:**
+/// - `realm`: Protection space named in the challenge (part of every hash).
+/// - `users`: Passwords by user name.
+/// - `algorithms`: Algorithms offered, one challenge each, in preference order.
+/// - `nonce_lifetime`: How long an issued nonce stays valid.
+/// - `nonces`: Outstanding nonces, shared by every clone of the policy.
+///
+/// **Usage Example:**
+/// ```rust
+/// # use server::{digest_auth::DigestAuth, request::Request, response::Response, router::Router};
+/// # fn dashboard(_: &Request) -> Response { Response::new(200, "OK") }
+/// let auth = DigestAuth::new("Admin area").user("ana", "s3cret");
+/// let router = Router::new().middleware(auth.middleware()).get("/", dashboard);
+/// ```
+#[derive(Debug, Clone)]
+pub struct DigestAuth {
+    realm: String,
+    users: HashMap<String, String>,
+    algorithms: Vec<Algorithm>,
+    nonce_lifetime: Duration,
+    nonces: Arc<Mutex<Nonces>>,
+}
+
+
+impl Algorithm {
+
+    /// # name
+    ///
+    /// **Purpose:**
+    /// Returns the token used in the `algorithm` parameter.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - `SHA-256` or `MD5`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::digest_auth::Algorithm;
+    /// assert_eq!(Algorithm::Sha256.name(), "SHA-256");
+    /// ```
+    pub fn name(self) -> &'static str {
+        match self {
+            Algorithm::Sha256 => "SHA-256",
+            Algorithm::Md5 => "MD5",
+        }
+    }
+
+    /// # digest
+    ///
+    /// **Purpose:**
+    /// Hashes text and formats the digest as lower-case hex, as every Digest value is.
+    ///
+    /// **Parameters:**
+    /// - `text`: Text to hash.
+    ///
+    /// **Returns:**
+    /// - The hex digest.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let ha2 = algorithm.digest(&format!("{method}:{uri}"));
+    /// ```
+    fn digest(self, text: &str) -> String {
+        match self {
+            Algorithm::Sha256 => hash::hex(&hash::sha256(text.as_bytes())),
+            Algorithm::Md5 => hash::hex(&hash::md5(text.as_bytes())),
+        }
+    }
+}
+
+
+impl Nonce {
+
+    /// # accept
+    ///
+    /// **Purpose:**
+    /// Records a nonce count, refusing counts that were already used or fell out of the window.
+    ///
+    /// **Parameters:**
+    /// - `count`: The client's `nc` value.
+    ///
+    /// **Returns:**
+    /// - `true` if the count is new.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// if !nonce.accept(count) { return Err(false); }
+    /// ```
+    fn accept(&mut self, count: u64) -> bool {
+        if count > self.highest {
+            let shift = count - self.highest;
+            self.seen = if shift >= 64 { 1 } else { (self.seen << shift) | 1 };
+            self.highest = count;
+            return true;
+        }
+        let offset = self.highest - count;
+        if offset >= 64 || self.seen & (1 << offset) != 0 {
+            return false;
+        }
+        self.seen |= 1 << offset;
+        true
+    }
+}
+
+
+impl DigestAuth {
+
+    /// # new
+    ///
+    /// **Purpose:**
+    /// Creates a policy with no users that offers SHA-256 and MD5 with five-minute nonces.
+    ///
+    /// **Parameters:**
+    /// - `realm`: Protection space shown by browsers in the login prompt.
+    ///
+    /// **Returns:**
+    /// - A new `DigestAuth`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::digest_auth::DigestAuth;
+    /// let auth = DigestAuth::new("Staging");
+    /// ```
+    pub fn new(realm: impl Into<String>) -> DigestAuth {
+        DigestAuth {
+            realm: realm.into(),
+            users: HashMap::new(),
+            algorithms: vec![Algorithm::Sha256, Algorithm::Md5],
+            nonce_lifetime: Duration::from_secs(300),
+            nonces: Arc::default(),
+        }
+    }
+
+    /// # user
+    ///
+    /// **Purpose:**
+    /// Adds a user. Digest verification needs the clear-text password.
+    ///
+    /// **Parameters:**
+    /// - `name`: User name.
+    /// - `password`: Password.
+    ///
+    /// **Returns:**
+    /// - The updated `DigestAuth`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,no_run
+    /// # use server::digest_auth::DigestAuth;
+    /// # use std::env;
+    /// let auth = DigestAuth::new("Staging").user("qa", &env::var("QA_PASSWORD")?);
+    /// # Ok::<(), env::VarError>(())
+    /// ```
+    pub fn user(mut self, name: impl Into<String>, password: impl Into<String>) -> DigestAuth {
+        self.users.insert(name.into(), password.into());
+        self
+    }
+
+    /// # algorithm
+    ///
+    /// **Purpose:**
+    /// Offers only the given algorithm instead of both.
+    ///
+    /// **Parameters:**
+    /// - `algorithm`: Sole accepted algorithm.
+    ///
+    /// **Returns:**
+    /// - The updated `DigestAuth`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::digest_auth::{Algorithm, DigestAuth};
+    /// let auth = DigestAuth::new("Staging").algorithm(Algorithm::Sha256);
+    /// ```
+    pub fn algorithm(mut self, algorithm: Algorithm) -> DigestAuth {
+        self.algorithms = vec![algorithm];
+        self
+    }
+
+    /// # nonce_lifetime
+    ///
+    /// **Purpose:**
+    /// Sets how long an issued nonce is accepted before clients must fetch a fresh one.
+    ///
+    /// **Parameters:**
+    /// - `lifetime`: Nonce validity period.
+    ///
+    /// **Returns:**
+    /// - The updated `DigestAuth`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::digest_auth::DigestAuth;
+    /// # use std::time::Duration;
+    /// let auth = DigestAuth::new("Staging").nonce_lifetime(Duration::from_secs(60));
+    /// ```
+    pub fn nonce_lifetime(mut self, lifetime: Duration) -> DigestAuth {
+        self.nonce_lifetime = lifetime;
+        self
+    }
+
+    /// # middleware
+    ///
+    /// **Purpose:**
+    /// Turns the policy into router middleware.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - A closure accepted by `Router::middleware`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{digest_auth::DigestAuth, router::Router};
+    /// let router = Router::new().middleware(DigestAuth::new("Admin").user("root", "secret").middleware());
+    /// ```
+    pub fn middleware(self) -> impl Fn(&Request, Next<'_>) -> Response + Send + Sync + 'static {
+        move |request, next| self.handle(request, next)
+    }
+
+    /// # handle
+    ///
+    /// **Purpose:**
+    /// Runs the rest of the chain for requests with a valid, unreplayed Digest response,
+    /// attaching the user name and an `Authentication-Info` header.
+    ///
+    /// **Parameters:**
+    /// - `request`: Request being handled.
+    /// - `next`: The rest of the middleware chain.
+    ///
+    /// **Returns:**
+    /// - The downstream response, or `401 Unauthorized` with one challenge per algorithm.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{digest_auth::DigestAuth, request::Request, response::Response, router::Next};
+    /// # let auth = DigestAuth::new("Admin").user("root", "secret");
+    /// # let request = &Request::read_from(&mut &b"GET / HTTP/1.1\r\n\r\n"[..])?;
+    /// # let next: Next<'_> = &|_| Response::new(200, "OK");
+    /// let response = auth.handle(request, next);
+    /// # assert_eq!(response.status, 401);
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn handle(&self, request: &Request, next: Next<'_>) -> Response {
+        match self.authenticate(request) {
+            Ok((user, info)) => {
+                let mut request = request.clone();
+                request.extensions.insert(AuthenticatedUser(user));
+                let mut response = next(&request);
+                response.headers.set("Authentication-Info", info);
+                response
+            }
+            Err(stale) => self.challenge(stale),
+        }
+    }
+
+    /// # authenticate
+    ///
+    /// **Purpose:**
+    /// Verifies the request's Digest credentials and consumes its nonce count.
+    ///
+    /// **Parameters:**
+    /// - `request`: Request being checked.
+    ///
+    /// **Returns:**
+    /// - The user name and the `Authentication-Info` value.
+    ///
+    /// **Errors / Failures:**
+    /// - `Err(true)` when the response was correct but the nonce is expired or unknown.
+    /// - `Err(false)` for missing, malformed, wrong, or replayed credentials.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let (user, info) = self.authenticate(request)?;
+    /// ```
+    fn authenticate(&self, request: &Request) -> Result<(String, String), bool> {
+        let params = parse_params(authorization(request, "Digest").ok_or(false)?);
+        let field = |name: &str| params.get(name).map(String::as_str).ok_or(false);
+
+        let requested = params.get("algorithm").map_or("MD5".to_string(), |name| name.to_ascii_uppercase());
+        let (algorithm, session) = match requested.as_str() {
+            "SHA-256" => (Algorithm::Sha256, false),
+            "SHA-256-SESS" => (Algorithm::Sha256, true),
+            "MD5" => (Algorithm::Md5, false),
+            "MD5-SESS" => (Algorithm::Md5, true),
+            _ => return Err(false),
+        };
+        let (user, uri, nonce, nc, cnonce) =
+            (field("username")?, field("uri")?, field("nonce")?, field("nc")?, field("cnonce")?);
+        if !self.algorithms.contains(&algorithm)
+            || field("realm")? != self.realm
+            || field("qop")? != "auth"
+            || uri != request.target
+            || params.get("userhash").is_some_and(|flag| flag.eq_ignore_ascii_case("true"))
+        {
+            return Err(false);
+        }
+        let count = u64::from_str_radix(nc, 16).map_err(|_| false)?;
+        let password = self.users.get(user).ok_or(false)?;
+
+        let mut ha1 = algorithm.digest(&format!("{user}:{}:{password}", self.realm));
+        if session {
+            ha1 = algorithm.digest(&format!("{ha1}:{nonce}:{cnonce}"));
+        }
+        let ha2 = algorithm.digest(&format!("{}:{uri}", request.method));
+        let expected = algorithm.digest(&format!("{ha1}:{nonce}:{nc}:{cnonce}:auth:{ha2}"));
+        if !random::constant_time_eq(expected.as_bytes(), field("response")?.as_bytes()) {
+            return Err(false);
+        }
+
+        {
+            let mut nonces = self.nonces.lock().unwrap();
+            let entry = nonces.entries.get_mut(nonce).ok_or(true)?;
+            if entry.issued.elapsed() >= self.nonce_lifetime {
+                nonces.entries.remove(nonce);
+                return Err(true);
+            }
+            if !entry.accept(count) {
+                return Err(false);
+            }
+        }
+
+        let rspauth = algorithm.digest(&format!(
+            "{ha1}:{nonce}:{nc}:{cnonce}:auth:{}",
+            algorithm.digest(&format!(":{uri}"))
+        ));
+        Ok((
+            user.to_string(),
+            format!(r#"qop=auth, rspauth="{rspauth}", cnonce="{}", nc={nc}"#, quote(cnonce)),
+        ))
+    }
+
+    /// # challenge
+    ///
+    /// **Purpose:**
+    /// Issues a fresh nonce and builds the `401 Unauthorized` answer.
+    ///
+    /// **Parameters:**
+    /// - `stale`: Whether to tell the client its previous nonce merely expired.
+    ///
+    /// **Returns:**
+    /// - The rejection `Response`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// return self.challenge(false);
+    /// ```
+    fn challenge(&self, stale: bool) -> Response {
+        let nonce = self.issue_nonce();
+        let mut challenges = self.algorithms.iter().map(|algorithm| {
+            format!(
+                r#"Digest realm="{}", qop="auth", algorithm={}, nonce="{nonce}", charset=UTF-8{}"#,
+                quote(&self.realm),
+                algorithm.name(),
+                if stale { ", stale=true" } else { "" },
+            )
+        });
+        let mut response = unauthorized(challenges.next().unwrap_or_default());
+        for challenge in challenges {
+            response.headers.append("WWW-Authenticate", challenge);
+        }
+        response
+    }
+
+    /// # issue_nonce
+    ///
+    /// **Purpose:**
+    /// Generates and records a new nonce, dropping expired ones and capping the store size.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - The nonce.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let nonce = self.issue_nonce();
+    /// ```
+    fn issue_nonce(&self) -> String {
+        let nonce = random::token();
+        let issued = Instant::now();
+        let mut nonces = self.nonces.lock().unwrap();
+        while let Some((at, _)) = nonces.issued.front() {
+            if issued.duration_since(*at) < self.nonce_lifetime && nonces.issued.len() < MAX_NONCES {
+                break;
+            }
+            if let Some((_, oldest)) = nonces.issued.pop_front() {
+                nonces.entries.remove(&oldest);
+            }
+        }
+        nonces.issued.push_back((issued, nonce.clone()));
+        nonces.entries.insert(
+            nonce.clone(),
+            Nonce {
+                issued,
+                highest: 0,
+                seen: 1,
+            },
+        );
+        nonce
+    }
+}
+
+
+/// # parse_params
+///
+/// **Purpose:**
+/// Splits an auth-param list (`name=token, name="quoted \"string\""`) into a map.
+///
+/// **Parameters:**
+/// - `text`: Credentials after the scheme name.
+///
+/// **Returns:**
+/// - Parameters keyed by lower-cased name; later duplicates are ignored.
+///
+/// **Errors / Failures:**
+/// None. Malformed trailing input is skipped.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let params = parse_params(r#"username="ana", nc=00000001"#);
+/// ```
+fn parse_params(text: &str) -> HashMap<String, String> {
+    let mut params = HashMap::new();
+    let mut rest = text;
+    while let Some((name, after)) = rest.split_once('=') {
+        let name = name.trim_matches(|c: char| c == ',' || c.is_whitespace()).to_ascii_lowercase();
+        let after = after.trim_start();
+        let mut value = String::new();
+        if let Some(quoted) = after.strip_prefix('"') {
+            let mut chars = quoted.char_indices();
+            rest = "";
+            while let Some((index, c)) = chars.next() {
+                match c {
+                    '\\' => value.extend(chars.next().map(|(_, escaped)| escaped)),
+                    '"' => {
+                        rest = &quoted[index + 1..];
+                        break;
+                    }
+                    _ => value.push(c),
+                }
+            }
+        } else {
+            let end = after.find(',').unwrap_or(after.len());
+            value.push_str(after[..end].trim());
+            rest = &after[end..];
+        }
+        params.entry(name).or_insert(value);
+    }
+    params
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expired_nonces_are_dropped_from_the_front() {
+        let auth = DigestAuth::new("test").nonce_lifetime(Duration::ZERO);
+        let first = auth.issue_nonce();
+        let second = auth.issue_nonce();
+        let nonces = auth.nonces.lock().unwrap();
+        assert!(!nonces.entries.contains_key(&first));
+        assert!(nonces.entries.contains_key(&second));
+        assert_eq!(nonces.issued.len(), 1);
+    }
+
+    #[test]
+    fn the_store_is_capped_at_max_nonces() {
+        let auth = DigestAuth::new("test");
+        let first = auth.issue_nonce();
+        for _ in 0..MAX_NONCES {
+            auth.issue_nonce();
+        }
+        let nonces = auth.nonces.lock().unwrap();
+        assert_eq!(nonces.entries.len(), MAX_NONCES);
+        assert_eq!(nonces.issued.len(), MAX_NONCES);
+        assert!(!nonces.entries.contains_key(&first));
+    }
+}
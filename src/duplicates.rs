@@ -0,0 +1,256 @@
+
+// ============================================================
+//  DAEGONICA SOFTWARE — duplicates.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: DuplicatePolicy
+//!
+//! **Purpose:**
+//! Decides what happens to header fields a client sends more than once.
+//!
+//! **Context:**
+//! - Applied by the server to every request head, before host checks, routing, or proxying.
+//!
+//! **Responsibilities:**
+//! - Leaves, joins, trims to the first or last value, or refuses repeated fields, per header name.
+//! - Refuses repeated `Host`, `Content-Length`, and `Authorization` fields by default, because
+//!   backends disagree on which copy counts.
+//! - Does NOT touch response headers or fields that appear once.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+use crate::headers::Headers;
+
+
+/// # Duplicate
+///
+/// **Summary:**
+/// Treatment of a header field that appears more than once.
+///
+/// **Variants:**
+/// - `Allow`: Keeps every field as received.
+/// - `Join`: Merges the values into one field, separated by `, ` (`; ` for `Cookie`).
+/// - `KeepFirst`: Keeps only the first value.
+/// - `KeepLast`: Keeps only the last value.
+/// - `Reject`: Refuses the request with `400 Bad Request`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Duplicate {
+    Allow,
+    Join,
+    KeepFirst,
+    KeepLast,
+    Reject,
+}
+
+
+/// # DuplicatePolicy
+///
+/// **Summary:**
+/// Per-header rules for repeated request fields, with a fallback for unlisted names.
+///
+/// **Fields:**
+/// - `fallback`: Rule for headers without their own rule.
+/// - `rules`: Header names with their rules; later entries win.
+///
+/// **Usage Example:**
+/// ```rust
+/// # use server::{duplicates::{Duplicate, DuplicatePolicy}, server::Server};
+/// let policy = DuplicatePolicy::new().fallback(Duplicate::Join).header("X-Forwarded-For", Duplicate::Allow);
+/// let server = Server::new("0.0.0.0:80").duplicate_headers(policy);
+/// ```
+#[derive(Debug, Clone)]
+pub struct DuplicatePolicy {
+    fallback: Duplicate,
+    rules: Vec<(String, Duplicate)>,
+}
+
+
+impl Default for DuplicatePolicy {
+
+    /// # default
+    ///
+    /// **Purpose:**
+    /// Creates the standard policy: repeated `Host`, `Content-Length`, and `Authorization`
+    /// fields are refused and everything else is left as received.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - A `DuplicatePolicy` with an `Allow` fallback.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::duplicates::DuplicatePolicy;
+    /// let policy = DuplicatePolicy::default();
+    /// ```
+    fn default() -> DuplicatePolicy {
+        DuplicatePolicy {
+            fallback: Duplicate::Allow,
+            rules: ["Host", "Content-Length", "Authorization"]
+                .into_iter()
+                .map(|name| (name.to_string(), Duplicate::Reject))
+                .collect(),
+        }
+    }
+}
+
+
+impl DuplicatePolicy {
+
+    /// # new
+    ///
+    /// **Purpose:**
+    /// Creates the standard policy (see `default`).
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - A new `DuplicatePolicy`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::duplicates::DuplicatePolicy;
+    /// let policy = DuplicatePolicy::new();
+    /// ```
+    pub fn new() -> DuplicatePolicy {
+        DuplicatePolicy::default()
+    }
+
+    /// # fallback
+    ///
+    /// **Purpose:**
+    /// Sets the rule for repeated headers that have no rule of their own.
+    ///
+    /// **Parameters:**
+    /// - `rule`: Treatment of unlisted repeated headers.
+    ///
+    /// **Returns:**
+    /// - The updated `DuplicatePolicy`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::duplicates::{Duplicate, DuplicatePolicy};
+    /// let policy = DuplicatePolicy::new().fallback(Duplicate::KeepFirst);
+    /// ```
+    pub fn fallback(mut self, rule: Duplicate) -> DuplicatePolicy {
+        self.fallback = rule;
+        self
+    }
+
+    /// # header
+    ///
+    /// **Purpose:**
+    /// Sets the rule for one header name, replacing any earlier rule for it (including the
+    /// standard ones).
+    ///
+    /// **Parameters:**
+    /// - `name`: Header name, matched case-insensitively.
+    /// - `rule`: Treatment of that header when repeated.
+    ///
+    /// **Returns:**
+    /// - The updated `DuplicatePolicy`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::duplicates::{Duplicate, DuplicatePolicy};
+    /// let policy = DuplicatePolicy::new().header("Accept", Duplicate::Join).header("User-Agent", Duplicate::KeepLast);
+    /// ```
+    pub fn header(mut self, name: &str, rule: Duplicate) -> DuplicatePolicy {
+        self.rules.push((name.to_string(), rule));
+        self
+    }
+
+    /// # apply
+    ///
+    /// **Purpose:**
+    /// Rewrites a request's repeated header fields according to their rules. A merged or
+    /// trimmed field moves to the end of the header block.
+    ///
+    /// **Parameters:**
+    /// - `headers`: Request header block.
+    ///
+    /// **Returns:**
+    /// - `Ok(())` once every repeated field is handled.
+    ///
+    /// **Errors / Failures:**
+    /// - `Err(name)` with the first repeated header whose rule is `Reject`; the headers may
+    ///   already be partly rewritten.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{duplicates::DuplicatePolicy, request::Request};
+    /// # let policy = DuplicatePolicy::new();
+    /// # let mut request = Request::read_from(&mut &b"GET / HTTP/1.1\r\nHost: a\r\nHost: b\r\n\r\n"[..])?;
+    /// if let Err(name) = policy.apply(&mut request.headers) { /* answer 400 */ }
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn apply(&self, headers: &mut Headers) -> Result<(), String> {
+        let mut repeated: Vec<String> = Vec::new();
+        for (name, _) in headers.iter() {
+            if headers.get_all(name).len() > 1 && !repeated.iter().any(|seen| seen.eq_ignore_ascii_case(name)) {
+                repeated.push(name.to_string());
+            }
+        }
+
+        for name in repeated {
+            let values = headers.get_all(&name);
+            let kept = match self.rule(&name) {
+                Duplicate::Allow => continue,
+                Duplicate::Reject => return Err(name),
+                Duplicate::KeepFirst => values.first().copied().unwrap_or_default().to_string(),
+                Duplicate::KeepLast => values.last().copied().unwrap_or_default().to_string(),
+                Duplicate::Join if name.eq_ignore_ascii_case("Cookie") => values.join("; "),
+                Duplicate::Join => values.join(", "),
+            };
+            headers.set(name, kept);
+        }
+        Ok(())
+    }
+
+    /// # rule
+    ///
+    /// **Purpose:**
+    /// Looks up the rule for a header name.
+    ///
+    /// **Parameters:**
+    /// - `name`: Header name.
+    ///
+    /// **Returns:**
+    /// - The most recently added rule for the name, or the fallback.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let rule = self.rule("Host");
+    /// ```
+    fn rule(&self, name: &str) -> Duplicate {
+        self.rules
+            .iter()
+            .rev()
+            .find(|(header, _)| header.eq_ignore_ascii_case(name))
+            .map_or(self.fallback, |(_, rule)| *rule)
+    }
+}
@@ -0,0 +1,171 @@
+
+// ============================================================
+//  DAEGONICA SOFTWARE — early_hints.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: EarlyHints
+//!
+//! **Purpose:**
+//! Tells browsers which critical assets a page needs before the page itself is ready, with a
+//! `103 Early Hints` interim response carrying `Link` headers.
+//!
+//! **Context:**
+//! - Set with `Server::early_hints`; the connection handler sends the hints for a request's
+//!   path just before producing the final response, for routed handlers and static files alike.
+//!
+//! **Responsibilities:**
+//! - Holds `Link` header values declared for path patterns, such as stylesheet and script
+//!   preloads or origin preconnects.
+//! - Serializes the `103 Early Hints` response for a path from every matching declaration.
+//! - Does NOT add the links to the final response, which should repeat any it wants kept, and
+//!   does NOT send hints to HTTP/1.0 clients, which cannot parse interim responses.
+//! - Does NOT push the assets themselves. Server push needs HTTP/2, which this server does not
+//!   speak; hints are the HTTP/1.1 way to get critical assets fetched early, and the one
+//!   browsers still act on.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+use crate::glob::Glob;
+
+
+/// # EarlyHints
+///
+/// **Summary:**
+/// `Link` headers to announce in a `103 Early Hints` response, by request path.
+///
+/// **Fields:**
+/// - `links`: Path patterns with the `Link` header value each adds, in declaration order.
+///
+/// **Usage Example:**
+/// ```rust
+/// # use server::{early_hints::EarlyHints, server::Server};
+/// let hints = EarlyHints::new()
+///     .preload("/", "/assets/app.css", "style")
+///     .preload("/docs/**", "/assets/docs.js", "script");
+/// let server = Server::new("127.0.0.1:7878").early_hints(hints);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct EarlyHints {
+    links: Vec<(Glob, String)>,
+}
+
+
+impl EarlyHints {
+
+    /// # new
+    ///
+    /// **Purpose:**
+    /// Creates an empty set of hints.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - An `EarlyHints` that sends nothing.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::early_hints::EarlyHints;
+    /// let hints = EarlyHints::new();
+    /// ```
+    pub fn new() -> EarlyHints {
+        EarlyHints::default()
+    }
+
+    /// # preload
+    ///
+    /// **Purpose:**
+    /// Announces an asset to preload for request paths matching a pattern.
+    ///
+    /// **Parameters:**
+    /// - `pattern`: Path glob (`*` within a segment, `**` across segments).
+    /// - `href`: URL of the asset.
+    /// - `kind`: Its `as` destination (e.g. `style`, `script`, `font`, `image`).
+    ///
+    /// **Returns:**
+    /// - The updated `EarlyHints`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::early_hints::EarlyHints;
+    /// let hints = EarlyHints::new().preload("/**", "/fonts/body.woff2", "font");
+    /// ```
+    pub fn preload(self, pattern: &str, href: &str, kind: &str) -> EarlyHints {
+        let crossorigin = if kind == "font" { "; crossorigin" } else { "" };
+        self.link(pattern, format!("<{href}>; rel=preload; as={kind}{crossorigin}"))
+    }
+
+    /// # link
+    ///
+    /// **Purpose:**
+    /// Announces a raw `Link` header value for request paths matching a pattern, for hints
+    /// other than preloads.
+    ///
+    /// **Parameters:**
+    /// - `pattern`: Path glob.
+    /// - `value`: Header value (e.g. `<https://cdn.example.com>; rel=preconnect`).
+    ///
+    /// **Returns:**
+    /// - The updated `EarlyHints`.
+    ///
+    /// **Errors / Failures:**
+    /// None. Line breaks in `value` are dropped so it cannot split the header.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::early_hints::EarlyHints;
+    /// let hints = EarlyHints::new().link("/**", "<https://cdn.example.com>; rel=preconnect");
+    /// ```
+    pub fn link(mut self, pattern: &str, value: impl Into<String>) -> EarlyHints {
+        let value: String = value.into().chars().filter(|c| *c != '\r' && *c != '\n').collect();
+        self.links.push((Glob::new(pattern), value));
+        self
+    }
+
+    /// # interim
+    ///
+    /// **Purpose:**
+    /// Serializes the `103 Early Hints` response for a request path.
+    ///
+    /// **Parameters:**
+    /// - `path`: Request path.
+    ///
+    /// **Returns:**
+    /// - The interim response with one `Link` header per matching declaration, or `None` if
+    ///   none matches.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// if let Some(interim) = hints.interim(request.path()) { client.write_all(&interim)?; }
+    /// ```
+    pub(crate) fn interim(&self, path: &str) -> Option<Vec<u8>> {
+        let mut interim = Vec::new();
+        for (_, value) in self.links.iter().filter(|(pattern, _)| pattern.matches(path)) {
+            if interim.is_empty() {
+                interim.extend_from_slice(b"HTTP/1.1 103 Early Hints\r\n");
+            }
+            interim.extend_from_slice(format!("Link: {value}\r\n").as_bytes());
+        }
+        if interim.is_empty() {
+            return None;
+        }
+        interim.extend_from_slice(b"\r\n");
+        Some(interim)
+    }
+}
@@ -0,0 +1,272 @@
+
+// ============================================================
+//  DAEGONICA SOFTWARE — embed.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: Embed
+//!
+//! **Purpose:**
+//! Compiles static assets into the binary so single-binary deployments need no document root.
+//!
+//! **Context:**
+//! - A build script calls `write_manifest` to list a directory as `include_bytes!` entries, or
+//!   `embed_assets!` names the files inline; the result is handed to `StaticFiles::embedded`.
+//!
+//! **Responsibilities:**
+//! - Generates a manifest of every file beneath a directory and tells Cargo to rebuild when
+//!   any of them changes.
+//! - Indexes embedded files by path and derives each one's `ETag` from a hash of its contents.
+//! - Does NOT compress assets or embed anything at run time.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+use std::{
+    collections::HashMap,
+    fmt::Write as _,
+    fs,
+    io,
+    path::Path,
+};
+
+use crate::hash;
+
+
+/// # EmbeddedAssets
+///
+/// **Summary:**
+/// Files compiled into the binary, looked up by their path relative to the embedded directory.
+///
+/// **Fields:**
+/// - `files`: Contents and `ETag` of each file, keyed by `/`-separated relative path.
+///
+/// **Usage Example:**
+/// ```rust,ignore
+/// static ASSETS: &[(&str, &[u8])] = include!(concat!(env!("OUT_DIR"), "/assets.rs"));
+/// let files = StaticFiles::embedded(EmbeddedAssets::new(ASSETS));
+/// ```
+#[derive(Debug, Clone)]
+pub struct EmbeddedAssets {
+    files: HashMap<&'static str, EmbeddedFile>,
+}
+
+
+/// # EmbeddedFile
+///
+/// **Summary:**
+/// One embedded file.
+///
+/// **Fields:**
+/// - `contents`: File bytes, stored in the binary.
+/// - `etag`: Strong `ETag`, quoted, from the SHA-256 of the contents.
+#[derive(Debug, Clone)]
+pub(crate) struct EmbeddedFile {
+    pub contents: &'static [u8],
+    pub etag: String,
+}
+
+
+/// # embed_assets
+///
+/// **Purpose:**
+/// Embeds the listed files from a directory relative to the crate root, producing the
+/// `&[(&str, &[u8])]` table `EmbeddedAssets::new` takes.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let assets = EmbeddedAssets::new(server::embed_assets!("html", "index.html", "css/site.css"));
+/// ```
+#[macro_export]
+macro_rules! embed_assets {
+    ($dir:literal, $($file:literal),+ $(,)?) => {
+        &[$(($file, include_bytes!(concat!(env!("CARGO_MANIFEST_DIR"), "/", $dir, "/", $file)) as &[u8])),+]
+    };
+}
+
+
+impl EmbeddedAssets {
+
+    /// # new
+    ///
+    /// **Purpose:**
+    /// Indexes a table of embedded files, hashing each for its `ETag`.
+    ///
+    /// **Parameters:**
+    /// - `files`: `(relative path, contents)` pairs, as generated by `write_manifest` or
+    ///   `embed_assets!`.
+    ///
+    /// **Returns:**
+    /// - The indexed assets.
+    ///
+    /// **Errors / Failures:**
+    /// None. Later entries replace earlier ones with the same path.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::embed::EmbeddedAssets;
+    /// let assets = EmbeddedAssets::new(&[("index.html", b"<h1>Hi</h1>")]);
+    /// ```
+    pub fn new(files: &'static [(&'static str, &'static [u8])]) -> EmbeddedAssets {
+        let files = files
+            .iter()
+            .map(|&(name, contents)| {
+                let etag = format!("\"{}\"", hash::hex(&hash::sha256(contents)[..16]));
+                (name.trim_start_matches('/'), EmbeddedFile { contents, etag })
+            })
+            .collect();
+        EmbeddedAssets { files }
+    }
+
+    /// # len
+    ///
+    /// **Purpose:**
+    /// Counts the embedded files.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - The number of files.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # let assets = server::embed::EmbeddedAssets::new(&[("index.html", b"<h1>Hi</h1>")]);
+    /// println!("serving {} embedded assets", assets.len());
+    /// ```
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    /// # is_empty
+    ///
+    /// **Purpose:**
+    /// Checks whether no files are embedded.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - `true` if the table was empty.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # let assets = server::embed::EmbeddedAssets::new(&[("index.html", b"<h1>Hi</h1>")]);
+    /// assert!(!assets.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// # get
+    ///
+    /// **Purpose:**
+    /// Looks up an embedded file.
+    ///
+    /// **Parameters:**
+    /// - `name`: Relative path, such as `css/site.css`.
+    ///
+    /// **Returns:**
+    /// - The file, or `None`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let file = assets.get("index.html");
+    /// ```
+    pub(crate) fn get(&self, name: &str) -> Option<&EmbeddedFile> {
+        self.files.get(name)
+    }
+}
+
+
+/// # write_manifest
+///
+/// **Purpose:**
+/// Writes a Rust expression embedding every file beneath `dir`, for `include!` from a build
+/// script's output directory, and prints the `cargo:rerun-if-changed` lines that keep it fresh.
+///
+/// **Parameters:**
+/// - `dir`: Asset directory, usually relative to the crate root.
+/// - `out`: File to write, usually `$OUT_DIR/assets.rs`.
+///
+/// **Returns:**
+/// - `Ok(())` once the manifest is written.
+///
+/// **Errors / Failures:**
+/// - Returns an I/O error if the directory cannot be walked or the manifest cannot be written.
+///
+/// **Examples:**
+/// ```rust,no_run
+/// // in build.rs's main
+/// let out = std::path::Path::new(&std::env::var("OUT_DIR").unwrap()).join("assets.rs");
+/// server::embed::write_manifest("html", out).unwrap();
+/// ```
+pub fn write_manifest(dir: impl AsRef<Path>, out: impl AsRef<Path>) -> io::Result<()> {
+    let root = fs::canonicalize(dir.as_ref())?;
+    let mut files = Vec::new();
+    let mut pending = vec![root.clone()];
+    while let Some(directory) = pending.pop() {
+        println!("cargo:rerun-if-changed={}", directory.display());
+        for entry in fs::read_dir(&directory)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                pending.push(path);
+            } else {
+                files.push(path);
+            }
+        }
+    }
+    files.sort();
+
+    let mut manifest = String::from("&[\n");
+    for file in &files {
+        println!("cargo:rerun-if-changed={}", file.display());
+        let name = relative_name(&root, file);
+        let _ = writeln!(manifest, "    ({name:?}, include_bytes!({:?}) as &[u8]),", file.display().to_string());
+    }
+    manifest.push_str("]\n");
+    fs::write(out, manifest)
+}
+
+
+/// # relative_name
+///
+/// **Purpose:**
+/// Names a file by its path beneath the asset root, with `/` separators on every platform.
+///
+/// **Parameters:**
+/// - `root`: Asset directory.
+/// - `file`: File beneath it.
+///
+/// **Returns:**
+/// - The relative name, such as `css/site.css`.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// assert_eq!(relative_name(Path::new("/a"), Path::new("/a/b/c.css")), "b/c.css");
+/// ```
+fn relative_name(root: &Path, file: &Path) -> String {
+    file.strip_prefix(root)
+        .unwrap_or(file)
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
@@ -0,0 +1,566 @@
+
+// ============================================================
+//  DAEGONICA SOFTWARE — event_loop.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: EventLoop
+//!
+//! **Purpose:**
+//! Watches idle persistent connections for their next request with readiness polling
+//! (`epoll` on Linux, `kqueue` on macOS and FreeBSD), so a connection waiting between requests
+//! holds no worker thread.
+//!
+//! **Context:**
+//! - Enabled with `Server::event_loop`; one thread runs the loop for the whole server.
+//! - Workers park a connection here after a response instead of blocking on its next read,
+//!   and the loop hands it back to the thread pool once the next request begins to arrive.
+//!
+//! **Responsibilities:**
+//! - Registers parked sockets for one-shot read readiness and returns each, with its state, as
+//!   soon as it becomes readable (including when the peer closes).
+//! - Closes parked connections whose keep-alive idle timeout runs out.
+//! - Closes every parked connection, and refuses further ones, once the server drains.
+//! - Does NOT read or write sockets itself: a woken connection is served by a worker with
+//!   blocking I/O, so a client that stalls mid-request still holds its worker until a timeout.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+use std::{
+    collections::HashMap,
+    io,
+    net::TcpStream,
+    os::fd::{AsRawFd, FromRawFd, OwnedFd, RawFd},
+    sync::{Mutex, PoisonError},
+    time::{Duration, Instant},
+};
+
+use crate::log::{self, LogLevel};
+
+
+/// # TICK
+///
+/// **Summary:**
+/// Longest wait for readiness before expired idle connections and draining are checked, which
+/// is also how late an idle timeout may close its connection.
+const TICK: Duration = Duration::from_millis(250);
+
+
+/// # EVENTS
+///
+/// **Summary:**
+/// Most readiness events taken from the kernel per wait.
+const EVENTS: usize = 256;
+
+
+/// # EventLoop
+///
+/// **Summary:**
+/// Readiness poller over parked connections, each carrying caller-defined state.
+///
+/// **Fields:**
+/// - `selector`: Kernel readiness queue.
+/// - `parked`: Connections waiting for their next request.
+///
+/// **Usage Example:**
+/// ```rust,ignore
+/// let idle = Arc::new(EventLoop::new()?);
+/// let poller = Arc::clone(&idle);
+/// thread::spawn(move || poller.run(|stream, state| pool.execute(move || serve(stream, state)), || false));
+/// idle.park(stream, Duration::from_secs(5), state)?;
+/// ```
+#[derive(Debug)]
+pub(crate) struct EventLoop<T> {
+    selector: Selector,
+    parked: Mutex<Parked<T>>,
+}
+
+
+/// # Parked
+///
+/// **Summary:**
+/// Connections registered with the selector, by token.
+///
+/// **Fields:**
+/// - `next_token`: Token for the next registration.
+/// - `connections`: Waiting connections, by token.
+/// - `closed`: Set once the loop has stopped; connections parked afterwards are closed.
+#[derive(Debug)]
+struct Parked<T> {
+    next_token: u64,
+    connections: HashMap<u64, Idle<T>>,
+    closed: bool,
+}
+
+
+/// # Idle
+///
+/// **Summary:**
+/// One parked connection.
+///
+/// **Fields:**
+/// - `stream`: Client socket.
+/// - `deadline`: When the connection is closed if no request has begun.
+/// - `state`: Caller state handed back with the socket.
+#[derive(Debug)]
+struct Idle<T> {
+    stream: TcpStream,
+    deadline: Instant,
+    state: T,
+}
+
+
+/// # Selector
+///
+/// **Summary:**
+/// An `epoll` instance or `kqueue`.
+///
+/// **Fields:**
+/// - `fd`: Its descriptor.
+#[derive(Debug)]
+struct Selector {
+    fd: OwnedFd,
+}
+
+
+impl<T> EventLoop<T> {
+
+    /// # new
+    ///
+    /// **Purpose:**
+    /// Creates an event loop with nothing parked.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - A new `EventLoop`.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns the OS error if the readiness queue cannot be created.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let idle = EventLoop::new()?;
+    /// ```
+    pub(crate) fn new() -> io::Result<EventLoop<T>> {
+        Ok(EventLoop {
+            selector: Selector::new()?,
+            parked: Mutex::new(Parked { next_token: 0, connections: HashMap::new(), closed: false }),
+        })
+    }
+
+    /// # park
+    ///
+    /// **Purpose:**
+    /// Hands over a connection until its next request begins to arrive or `idle` runs out.
+    /// After the loop has stopped the connection is closed at once.
+    ///
+    /// **Parameters:**
+    /// - `stream`: Client socket with nothing buffered for it.
+    /// - `idle`: Keep-alive idle timeout.
+    /// - `state`: State handed back with the socket.
+    ///
+    /// **Returns:**
+    /// - `Ok(())` once the connection is parked or closed.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns the OS error if the socket cannot be registered; the connection is closed.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// idle.park(stream, keep_alive.idle_timeout(), state)?;
+    /// ```
+    pub(crate) fn park(&self, stream: TcpStream, idle: Duration, state: T) -> io::Result<()> {
+        let mut parked = self.parked.lock().unwrap_or_else(PoisonError::into_inner);
+        if parked.closed {
+            return Ok(());
+        }
+        let token = parked.next_token;
+        parked.next_token += 1;
+        self.selector.register(stream.as_raw_fd(), token)?;
+        parked.connections.insert(token, Idle { stream, deadline: Instant::now() + idle, state });
+        Ok(())
+    }
+
+    /// # run
+    ///
+    /// **Purpose:**
+    /// Polls parked connections until `draining` returns `true`, passing each readable one to
+    /// `ready` and closing those left idle too long; then closes the rest and stops.
+    ///
+    /// **Parameters:**
+    /// - `ready`: Called on the loop thread with each connection whose next request has begun
+    ///   (or whose peer closed); should queue it for a worker and return promptly.
+    /// - `draining`: Checked on every wake-up; `true` stops the loop.
+    ///
+    /// **Returns:**
+    /// None.
+    ///
+    /// **Errors / Failures:**
+    /// None. A failed wait is logged and retried after a tick.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// idle.run(|stream, state| spawner.execute(move || serve(stream, state)), || server.is_draining());
+    /// ```
+    pub(crate) fn run(&self, mut ready: impl FnMut(TcpStream, T), draining: impl Fn() -> bool) {
+        let mut tokens = Vec::with_capacity(EVENTS);
+        let mut next_sweep = Instant::now() + TICK;
+        while !draining() {
+            if let Err(error) = self.selector.wait(&mut tokens, TICK) {
+                log::emit(LogLevel::Warn, format_args!("Event loop wait failed: {error}"));
+                std::thread::sleep(TICK);
+            }
+            let mut woken = Vec::with_capacity(tokens.len());
+            {
+                let mut parked = self.parked.lock().unwrap_or_else(PoisonError::into_inner);
+                for token in tokens.drain(..) {
+                    if let Some(idle) = parked.connections.remove(&token) {
+                        let _ = self.selector.deregister(idle.stream.as_raw_fd());
+                        woken.push(idle);
+                    }
+                }
+                let now = Instant::now();
+                if now >= next_sweep {
+                    parked.connections.retain(|_, idle| idle.deadline > now);
+                    next_sweep = now + TICK;
+                }
+            }
+            for idle in woken {
+                ready(idle.stream, idle.state);
+            }
+        }
+        let mut parked = self.parked.lock().unwrap_or_else(PoisonError::into_inner);
+        parked.closed = true;
+        parked.connections.clear();
+    }
+}
+
+
+impl Selector {
+
+    /// # new
+    ///
+    /// **Purpose:**
+    /// Creates an `epoll` instance.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - The selector.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns the OS error if the instance cannot be created.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let selector = Selector::new()?;
+    /// ```
+    #[cfg(target_os = "linux")]
+    fn new() -> io::Result<Selector> {
+        // SAFETY: a plain system call with no pointer arguments.
+        let fd = unsafe { libc::epoll_create1(libc::EPOLL_CLOEXEC) };
+        if fd < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        // SAFETY: the kernel returned a fresh descriptor nothing else owns.
+        Ok(Selector { fd: unsafe { OwnedFd::from_raw_fd(fd) } })
+    }
+
+    /// # new
+    ///
+    /// **Purpose:**
+    /// Creates a `kqueue`.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - The selector.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns the OS error if the queue cannot be created.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let selector = Selector::new()?;
+    /// ```
+    #[cfg(any(target_os = "macos", target_os = "freebsd"))]
+    fn new() -> io::Result<Selector> {
+        // SAFETY: a plain system call with no arguments.
+        let fd = unsafe { libc::kqueue() };
+        if fd < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        // SAFETY: the kernel returned a fresh descriptor nothing else owns.
+        Ok(Selector { fd: unsafe { OwnedFd::from_raw_fd(fd) } })
+    }
+
+    /// # register
+    ///
+    /// **Purpose:**
+    /// Watches a socket for one read-readiness or hang-up event.
+    ///
+    /// **Parameters:**
+    /// - `fd`: Socket to watch; must not be registered already.
+    /// - `token`: Value reported when it becomes ready.
+    ///
+    /// **Returns:**
+    /// - `Ok(())` once registered.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns the OS error if `epoll_ctl` fails.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// self.selector.register(stream.as_raw_fd(), token)?;
+    /// ```
+    #[cfg(target_os = "linux")]
+    fn register(&self, fd: RawFd, token: u64) -> io::Result<()> {
+        let mut event = libc::epoll_event { events: (libc::EPOLLIN | libc::EPOLLRDHUP | libc::EPOLLONESHOT) as u32, u64: token };
+        // SAFETY: `event` is a valid event description for the duration of the call.
+        if unsafe { libc::epoll_ctl(self.fd.as_raw_fd(), libc::EPOLL_CTL_ADD, fd, &raw mut event) } < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(())
+    }
+
+    /// # register
+    ///
+    /// **Purpose:**
+    /// Watches a socket for one read-readiness or end-of-file event.
+    ///
+    /// **Parameters:**
+    /// - `fd`: Socket to watch; must not be registered already.
+    /// - `token`: Value reported when it becomes ready.
+    ///
+    /// **Returns:**
+    /// - `Ok(())` once registered.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns the OS error if `kevent` fails.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// self.selector.register(stream.as_raw_fd(), token)?;
+    /// ```
+    #[cfg(any(target_os = "macos", target_os = "freebsd"))]
+    fn register(&self, fd: RawFd, token: u64) -> io::Result<()> {
+        // SAFETY: `kevent` is plain data for which all zeroes is a valid, empty change.
+        let mut change: libc::kevent = unsafe { std::mem::zeroed() };
+        change.ident = fd as libc::uintptr_t;
+        change.filter = libc::EVFILT_READ;
+        change.flags = libc::EV_ADD | libc::EV_ONESHOT;
+        change.udata = std::ptr::without_provenance_mut(token as usize);
+        // SAFETY: one valid change is passed and no events are requested.
+        let result = unsafe { libc::kevent(self.fd.as_raw_fd(), &raw const change, 1, std::ptr::null_mut(), 0, std::ptr::null()) };
+        if result < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(())
+    }
+
+    /// # deregister
+    ///
+    /// **Purpose:**
+    /// Stops watching a socket that fired, so it can be registered again when next parked.
+    ///
+    /// **Parameters:**
+    /// - `fd`: Registered socket.
+    ///
+    /// **Returns:**
+    /// - `Ok(())` once removed.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns the OS error if `epoll_ctl` fails.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let _ = self.selector.deregister(idle.stream.as_raw_fd());
+    /// ```
+    #[cfg(target_os = "linux")]
+    fn deregister(&self, fd: RawFd) -> io::Result<()> {
+        // SAFETY: deleting needs no event description; null is accepted since Linux 2.6.9.
+        if unsafe { libc::epoll_ctl(self.fd.as_raw_fd(), libc::EPOLL_CTL_DEL, fd, std::ptr::null_mut()) } < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(())
+    }
+
+    /// # deregister
+    ///
+    /// **Purpose:**
+    /// Stands in for removing a registration; one-shot `kqueue` events remove themselves when
+    /// they fire.
+    ///
+    /// **Parameters:**
+    /// - `fd`: Registered socket.
+    ///
+    /// **Returns:**
+    /// - `Ok(())`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let _ = self.selector.deregister(idle.stream.as_raw_fd());
+    /// ```
+    #[cfg(any(target_os = "macos", target_os = "freebsd"))]
+    fn deregister(&self, _fd: RawFd) -> io::Result<()> {
+        Ok(())
+    }
+
+    /// # wait
+    ///
+    /// **Purpose:**
+    /// Waits for registered sockets to become ready.
+    ///
+    /// **Parameters:**
+    /// - `tokens`: Receives the token of each ready socket.
+    /// - `timeout`: Longest wait.
+    ///
+    /// **Returns:**
+    /// - `Ok(())` when sockets are ready, the wait timed out, or a signal cut it short.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns the OS error if `epoll_wait` fails.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// self.selector.wait(&mut tokens, TICK)?;
+    /// ```
+    #[cfg(target_os = "linux")]
+    fn wait(&self, tokens: &mut Vec<u64>, timeout: Duration) -> io::Result<()> {
+        let mut events = [libc::epoll_event { events: 0, u64: 0 }; EVENTS];
+        let timeout = libc::c_int::try_from(timeout.as_millis()).unwrap_or(libc::c_int::MAX);
+        // SAFETY: `events` has room for the `EVENTS` entries the kernel may write.
+        let ready = unsafe { libc::epoll_wait(self.fd.as_raw_fd(), events.as_mut_ptr(), EVENTS as libc::c_int, timeout) };
+        if ready < 0 {
+            let error = io::Error::last_os_error();
+            return if error.kind() == io::ErrorKind::Interrupted { Ok(()) } else { Err(error) };
+        }
+        tokens.extend(events[..ready as usize].iter().map(|event| event.u64));
+        Ok(())
+    }
+
+    /// # wait
+    ///
+    /// **Purpose:**
+    /// Waits for registered sockets to become ready.
+    ///
+    /// **Parameters:**
+    /// - `tokens`: Receives the token of each ready socket.
+    /// - `timeout`: Longest wait.
+    ///
+    /// **Returns:**
+    /// - `Ok(())` when sockets are ready, the wait timed out, or a signal cut it short.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns the OS error if `kevent` fails.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// self.selector.wait(&mut tokens, TICK)?;
+    /// ```
+    #[cfg(any(target_os = "macos", target_os = "freebsd"))]
+    fn wait(&self, tokens: &mut Vec<u64>, timeout: Duration) -> io::Result<()> {
+        // SAFETY: `kevent` is plain data for which all zeroes is a valid, empty event.
+        let mut events: [libc::kevent; EVENTS] = unsafe { std::mem::zeroed() };
+        let timeout = libc::timespec {
+            tv_sec: libc::time_t::try_from(timeout.as_secs()).unwrap_or(libc::time_t::MAX),
+            tv_nsec: timeout.subsec_nanos() as libc::c_long,
+        };
+        // SAFETY: no changes are passed, `events` has room for `EVENTS` entries, and `timeout`
+        // is valid for the call.
+        let ready = unsafe {
+            libc::kevent(self.fd.as_raw_fd(), std::ptr::null(), 0, events.as_mut_ptr(), EVENTS as libc::c_int, &raw const timeout)
+        };
+        if ready < 0 {
+            let error = io::Error::last_os_error();
+            return if error.kind() == io::ErrorKind::Interrupted { Ok(()) } else { Err(error) };
+        }
+        tokens.extend(events[..ready as usize].iter().map(|event| event.udata.addr() as u64));
+        Ok(())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        io::{Read, Write},
+        net::TcpListener,
+        sync::{
+            Arc,
+            atomic::{AtomicBool, Ordering},
+            mpsc,
+        },
+        thread,
+    };
+
+    fn pair() -> (TcpStream, TcpStream) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let client = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        client.set_read_timeout(Some(Duration::from_secs(10))).unwrap();
+        (client, listener.accept().unwrap().0)
+    }
+
+    type Woken = mpsc::Receiver<(TcpStream, &'static str)>;
+
+    fn spawn_loop() -> (Arc<EventLoop<&'static str>>, Woken, Arc<AtomicBool>) {
+        let idle = Arc::new(EventLoop::new().unwrap());
+        let draining = Arc::new(AtomicBool::new(false));
+        let (sender, woken) = mpsc::channel();
+        let (poller, stop) = (Arc::clone(&idle), Arc::clone(&draining));
+        thread::spawn(move || poller.run(|stream, state| sender.send((stream, state)).unwrap(), || stop.load(Ordering::Acquire)));
+        (idle, woken, draining)
+    }
+
+    #[test]
+    fn readable_connections_are_handed_back_with_their_state() {
+        let (idle, woken, _) = spawn_loop();
+        let (_quiet, quiet_server) = pair();
+        let (mut client, server) = pair();
+        idle.park(quiet_server, Duration::from_secs(60), "quiet").unwrap();
+        idle.park(server, Duration::from_secs(60), "busy").unwrap();
+        assert!(woken.recv_timeout(Duration::from_millis(300)).is_err(), "nothing is readable yet");
+
+        client.write_all(b"GET").unwrap();
+        let (mut stream, state) = woken.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert_eq!(state, "busy");
+        let mut buf = [0; 3];
+        stream.read_exact(&mut buf).unwrap();
+        assert_eq!(&buf, b"GET");
+    }
+
+    #[test]
+    fn idle_connections_are_closed_after_their_timeout() {
+        let (idle, _woken, _) = spawn_loop();
+        let (mut client, server) = pair();
+        idle.park(server, Duration::from_millis(100), "idle").unwrap();
+        assert_eq!(client.read(&mut [0; 1]).unwrap(), 0);
+    }
+
+    #[test]
+    fn draining_closes_parked_connections_and_refuses_new_ones() {
+        let (idle, _woken, draining) = spawn_loop();
+        let (mut client, server) = pair();
+        idle.park(server, Duration::from_secs(60), "parked").unwrap();
+        draining.store(true, Ordering::Release);
+        assert_eq!(client.read(&mut [0; 1]).unwrap(), 0);
+
+        let (mut late, late_server) = pair();
+        idle.park(late_server, Duration::from_secs(60), "late").unwrap();
+        assert_eq!(late.read(&mut [0; 1]).unwrap(), 0);
+    }
+}
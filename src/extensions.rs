@@ -0,0 +1,313 @@
+
+// ============================================================
+//  DAEGONICA SOFTWARE — extensions.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: Extensions
+//!
+//! **Purpose:**
+//! Carries typed per-request data from middleware to the handlers behind it.
+//!
+//! **Context:**
+//! - Middleware attaches values (authenticated user, request ID, tenant) to a request before
+//!   calling `next`; handlers read them with `Request::extensions` or the `Extension<T>` extractor.
+//!
+//! **Responsibilities:**
+//! - Stores one value per type for a single request.
+//! - Keeps cloning a request cheap by sharing the stored values.
+//! - Does NOT outlive the request; use `Server::with_state` for application-wide data.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+use std::{
+    any::{Any, TypeId},
+    collections::HashMap,
+    fmt,
+    sync::Arc,
+};
+
+use crate::{extract::FromRequest, request::Request, response::Response};
+
+
+/// # Extensions
+///
+/// **Summary:**
+/// Type-keyed map of values attached to one request.
+///
+/// **Fields:**
+/// - `values`: Attached values keyed by their type.
+///
+/// **Usage Example:**
+/// ```rust
+/// # use server::{request::Request, response::Response, router::{Next, Router}};
+/// # #[derive(Clone)]
+/// # struct RequestId(u64);
+/// # impl RequestId { fn generate() -> RequestId { RequestId(7) } }
+/// # impl std::fmt::Display for RequestId {
+/// #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { write!(f, "{:016x}", self.0) }
+/// # }
+/// let router = Router::new()
+///     .middleware(|request: &Request, next: Next<'_>| {
+///         let mut request = request.clone();
+///         request.extensions.insert(RequestId::generate());
+///         next(&request)
+///     })
+///     .get("/", |request: &Request| {
+///         let id = request.extensions.get::<RequestId>().unwrap();
+///         Response::new(200, "OK").with_body(id.to_string())
+///     });
+/// ```
+#[derive(Clone, Default)]
+pub struct Extensions {
+    values: HashMap<TypeId, Arc<dyn Any + Send + Sync>>,
+}
+
+
+/// # Extension
+///
+/// **Summary:**
+/// Extractor for a value attached to the request by middleware.
+///
+/// **Usage Example:**
+/// ```rust
+/// # use server::{extensions::Extension, response::Response};
+/// # #[derive(Clone)]
+/// # struct User { name: String }
+/// fn profile(Extension(user): Extension<User>) -> Response {
+///     Response::new(200, "OK").with_body(user.name)
+/// }
+/// ```
+pub struct Extension<T>(pub T);
+
+
+impl Extensions {
+
+    /// # insert
+    ///
+    /// **Purpose:**
+    /// Attaches a value, replacing any earlier value of the same type.
+    ///
+    /// **Parameters:**
+    /// - `value`: Value to attach.
+    ///
+    /// **Returns:**
+    /// None.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # struct Tenant(String);
+    /// # let mut request = server::request::Request::read_from(&mut &b"GET / HTTP/1.1\r\n\r\n"[..])?;
+    /// request.extensions.insert(Tenant("acme".into()));
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn insert<T: Send + Sync + 'static>(&mut self, value: T) {
+        self.values.insert(TypeId::of::<T>(), Arc::new(value));
+    }
+
+    /// # get
+    ///
+    /// **Purpose:**
+    /// Returns the attached value of type `T`.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - `Some(value)` if a `T` was attached, otherwise `None`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # struct Tenant(String);
+    /// # let mut request = server::request::Request::read_from(&mut &b"GET / HTTP/1.1\r\n\r\n"[..])?;
+    /// # request.extensions.insert(Tenant("acme".into()));
+    /// let tenant = request.extensions.get::<Tenant>();
+    /// # assert_eq!(tenant.map(|tenant| tenant.0.as_str()), Some("acme"));
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn get<T: Send + Sync + 'static>(&self) -> Option<&T> {
+        self.values.get(&TypeId::of::<T>())?.downcast_ref()
+    }
+
+    /// # contains
+    ///
+    /// **Purpose:**
+    /// Checks whether a value of type `T` is attached.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - `true` if a `T` was attached.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{request::Request, response::Response};
+    /// # struct User;
+    /// # fn unauthorized() -> Response { Response::new(401, "Unauthorized") }
+    /// # fn handler(request: &Request) -> Response {
+    /// if !request.extensions.contains::<User>() { return unauthorized(); }
+    /// # Response::new(200, "OK")
+    /// # }
+    /// ```
+    pub fn contains<T: Send + Sync + 'static>(&self) -> bool {
+        self.values.contains_key(&TypeId::of::<T>())
+    }
+
+    /// # remove
+    ///
+    /// **Purpose:**
+    /// Detaches the value of type `T`.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - `true` if a `T` was attached.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # struct User;
+    /// # let mut request = server::request::Request::read_from(&mut &b"GET / HTTP/1.1\r\n\r\n"[..])?;
+    /// # request.extensions.insert(User);
+    /// request.extensions.remove::<User>();
+    /// # assert!(request.extensions.is_empty());
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn remove<T: Send + Sync + 'static>(&mut self) -> bool {
+        self.values.remove(&TypeId::of::<T>()).is_some()
+    }
+
+    /// # len
+    ///
+    /// **Purpose:**
+    /// Returns the number of attached values.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - The value count.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # let request = server::request::Request::read_from(&mut &b"GET / HTTP/1.1\r\n\r\n"[..])?;
+    /// let n = request.extensions.len();
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// # is_empty
+    ///
+    /// **Purpose:**
+    /// Checks whether no values are attached.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - `true` if the map is empty.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # let request = server::request::Request::read_from(&mut &b"GET / HTTP/1.1\r\n\r\n"[..])?;
+    /// assert!(request.extensions.is_empty());
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+
+impl fmt::Debug for Extensions {
+
+    /// # fmt
+    ///
+    /// **Purpose:**
+    /// Formats the map by size only, since values need not implement `Debug`.
+    ///
+    /// **Parameters:**
+    /// - `f`: Formatter.
+    ///
+    /// **Returns:**
+    /// - The formatter result.
+    ///
+    /// **Errors / Failures:**
+    /// - Propagates formatter errors.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # let request = server::request::Request::read_from(&mut &b"GET / HTTP/1.1\r\n\r\n"[..])?;
+    /// println!("{:?}", request.extensions);
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Extensions").field("values", &self.values.len()).finish()
+    }
+}
+
+
+impl<T: Clone + Send + Sync + 'static> FromRequest for Extension<T> {
+
+    /// # from_request
+    ///
+    /// **Purpose:**
+    /// Clones the `T` attached to the request by middleware.
+    ///
+    /// **Parameters:**
+    /// - `request`: Request being handled.
+    ///
+    /// **Returns:**
+    /// - The attached value.
+    ///
+    /// **Errors / Failures:**
+    /// - Rejects with `500 Internal Server Error` if no `T` is attached, since that means the
+    ///   middleware providing it is not installed in front of the handler.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{extensions::Extension, extract::FromRequest, request::Request, response::Response};
+    /// # #[derive(Clone)]
+    /// # struct User { name: String }
+    /// # let mut request = Request::read_from(&mut &b"GET / HTTP/1.1\r\n\r\n"[..]).unwrap();
+    /// # request.extensions.insert(User { name: "ana".to_string() });
+    /// let Extension(user) = Extension::<User>::from_request(&request)?;
+    /// # assert_eq!(user.name, "ana");
+    /// # Ok::<(), Response>(())
+    /// ```
+    fn from_request(request: &Request) -> Result<Extension<T>, Response> {
+        request
+            .extensions
+            .get::<T>()
+            .cloned()
+            .map(Extension)
+            .ok_or_else(|| Response::new(500, "Internal Server Error"))
+    }
+}
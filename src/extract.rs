@@ -0,0 +1,785 @@
+
+// ============================================================
+//  DAEGONICA SOFTWARE — extract.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: Extract
+//!
+//! **Purpose:**
+//! Lets handlers declare typed arguments (`Path<u32>`, `Query<SearchParams>`) that are parsed
+//! from the request before the handler runs.
+//!
+//! **Context:**
+//! - `extract(handler)` turns such a function into the `Fn(&Request) -> Response` closure that
+//!   `Router` routes accept.
+//!
+//! **Responsibilities:**
+//! - Defines the `FromRequest` trait and the `Path` and `Query` extractors.
+//! - Parses path parameters and percent-decoded query strings into typed values.
+//! - Converts any extraction failure into a `400 Bad Request` response naming the problem.
+//! - Does NOT parse request bodies.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+use std::{collections::HashMap, str::FromStr};
+
+use crate::{request::Request, response::Response, static_files::percent_decode};
+
+
+/// # FromRequest
+///
+/// **Summary:**
+/// A value that can be built from a request, or else rejects it with a response.
+///
+/// **Usage Example:**
+/// ```rust
+/// # use server::{extract::{FromRequest, bad_request}, request::Request, response::Response};
+/// struct UserAgent(String);
+///
+/// impl FromRequest for UserAgent {
+///     fn from_request(request: &Request) -> Result<UserAgent, Response> {
+///         let agent = request.headers.get("User-Agent").ok_or_else(|| bad_request("missing User-Agent"))?;
+///         Ok(UserAgent(agent.to_string()))
+///     }
+/// }
+/// ```
+pub trait FromRequest: Sized {
+    /// # from_request
+    ///
+    /// **Purpose:**
+    /// Extracts the value from a request.
+    ///
+    /// **Parameters:**
+    /// - `request`: Request being handled.
+    ///
+    /// **Returns:**
+    /// - The extracted value.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns the response to send instead of running the handler (usually `400 Bad Request`).
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{extract::{FromRequest, Path}, request::Request, response::Response};
+    /// # let mut request = Request::read_from(&mut &b"GET /users/42 HTTP/1.1\r\n\r\n"[..]).unwrap();
+    /// # request.params.push(("id".to_string(), "42".to_string()));
+    /// let Path(id) = Path::<u32>::from_request(&request)?;
+    /// # assert_eq!(id, 42);
+    /// # Ok::<(), Response>(())
+    /// ```
+    fn from_request(request: &Request) -> Result<Self, Response>;
+}
+
+
+/// # Path
+///
+/// **Summary:**
+/// The route's captured path parameters, parsed as `T`: a single value (`Path<u32>`) or a
+/// tuple matched to the route's parameters in path order (`Path<(String, u32)>`).
+///
+/// **Usage Example:**
+/// ```rust
+/// # use server::{extract::{Path, extract}, response::Response, router::Router};
+/// fn show_user(Path(id): Path<u32>) -> Response {
+///     Response::new(200, "OK").with_body(format!("user {id}"))
+/// }
+/// let router = Router::new().get(r"/users/:id", extract(show_user));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<T>(pub T);
+
+
+/// # Query
+///
+/// **Summary:**
+/// The request's query string, parsed as `T`.
+///
+/// **Usage Example:**
+/// ```rust
+/// # use server::{extract::{FromQuery, Query, QueryParams}, response::Response};
+/// struct SearchParams { q: String, page: u32 }
+///
+/// impl FromQuery for SearchParams {
+///     fn from_query(query: &QueryParams) -> Result<SearchParams, String> {
+///         Ok(SearchParams { q: query.require("q")?, page: query.parse_value("page")?.unwrap_or(1) })
+///     }
+/// }
+/// fn search(Query(params): Query<SearchParams>) -> Response {
+///     Response::new(200, "OK").with_body(format!("{} (page {})", params.q, params.page))
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Query<T>(pub T);
+
+
+/// # QueryParams
+///
+/// **Summary:**
+/// Decoded `name=value` pairs of a query string, in order.
+///
+/// **Fields:**
+/// - `pairs`: Decoded pairs; names may repeat.
+///
+/// **Usage Example:**
+/// ```rust
+/// # use server::extract::QueryParams;
+/// let query = QueryParams::parse("q=rust+http&page=2");
+/// assert_eq!(query.get("q"), Some("rust http"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct QueryParams {
+    pairs: Vec<(String, String)>,
+}
+
+
+/// # FromQuery
+///
+/// **Summary:**
+/// A value that can be built from decoded query parameters.
+pub trait FromQuery: Sized {
+    /// # from_query
+    ///
+    /// **Purpose:**
+    /// Builds the value from query parameters.
+    ///
+    /// **Parameters:**
+    /// - `query`: Decoded query parameters.
+    ///
+    /// **Returns:**
+    /// - The parsed value.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns a message describing the missing or invalid parameter.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::extract::{FromQuery, QueryParams};
+    /// # struct SearchParams { q: String }
+    /// # impl FromQuery for SearchParams {
+    /// #     fn from_query(query: &QueryParams) -> Result<SearchParams, String> { Ok(SearchParams { q: query.require("q")? }) }
+    /// # }
+    /// let params = SearchParams::from_query(&QueryParams::parse("q=x"))?;
+    /// # assert_eq!(params.q, "x");
+    /// # Ok::<(), String>(())
+    /// ```
+    fn from_query(query: &QueryParams) -> Result<Self, String>;
+}
+
+
+/// # FromPath
+///
+/// **Summary:**
+/// A value that can be built from the trailing path parameters of a request.
+pub trait FromPath: Sized {
+    /// # from_path
+    ///
+    /// **Purpose:**
+    /// Builds the value from the captured parameters.
+    ///
+    /// **Parameters:**
+    /// - `params`: All captured parameters, host captures first, path captures last.
+    ///
+    /// **Returns:**
+    /// - The parsed value.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns a message describing the missing or invalid parameter.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{extract::FromPath, request::Request};
+    /// # let mut request = Request::read_from(&mut &b"GET /users/42 HTTP/1.1\r\n\r\n"[..]).unwrap();
+    /// # request.params.push(("id".to_string(), "42".to_string()));
+    /// let id = u32::from_path(&request.params)?;
+    /// # assert_eq!(id, 42);
+    /// # Ok::<(), String>(())
+    /// ```
+    fn from_path(params: &[(String, String)]) -> Result<Self, String>;
+}
+
+
+/// # PathValue
+///
+/// **Summary:**
+/// A single path parameter type parsed with `FromStr` after percent-decoding.
+pub trait PathValue: FromStr {}
+
+
+/// # ExtractHandler
+///
+/// **Summary:**
+/// A function whose arguments are all extractors; implemented for functions of one to four arguments.
+pub trait ExtractHandler<Args> {
+    /// # call
+    ///
+    /// **Purpose:**
+    /// Extracts every argument from the request, then runs the function.
+    ///
+    /// **Parameters:**
+    /// - `request`: Request being handled.
+    ///
+    /// **Returns:**
+    /// - The function's response, or the rejection of the first argument that failed.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{extract::{ExtractHandler, Path}, request::Request, response::Response};
+    /// # fn show_user(Path(id): Path<u32>) -> Response { Response::new(200, "OK").with_body(format!("user {id}")) }
+    /// # let mut request = Request::read_from(&mut &b"GET /users/42 HTTP/1.1\r\n\r\n"[..])?;
+    /// # request.params.push(("id".to_string(), "42".to_string()));
+    /// let response = show_user.call(&request);
+    /// # assert_eq!(response.body, b"user 42");
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    fn call(&self, request: &Request) -> Response;
+}
+
+
+impl QueryParams {
+
+    /// # parse
+    ///
+    /// **Purpose:**
+    /// Splits and decodes a query string. `+` decodes to a space; pairs with invalid escapes are dropped.
+    ///
+    /// **Parameters:**
+    /// - `query`: Raw query string, without the leading `?`.
+    ///
+    /// **Returns:**
+    /// - The decoded pairs; a name without `=` gets an empty value.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{extract::QueryParams, request::Request};
+    /// # let request = Request::read_from(&mut &b"GET /search?q=rust HTTP/1.1\r\n\r\n"[..])?;
+    /// let query = QueryParams::parse(request.query().unwrap_or(""));
+    /// # assert_eq!(query.get("q"), Some("rust"));
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn parse(query: &str) -> QueryParams {
+        let pairs = query
+            .split('&')
+            .filter(|pair| !pair.is_empty())
+            .filter_map(|pair| {
+                let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+                Some((decode_component(name)?, decode_component(value)?))
+            })
+            .collect();
+        QueryParams { pairs }
+    }
+
+    /// # get
+    ///
+    /// **Purpose:**
+    /// Returns the first value of a parameter.
+    ///
+    /// **Parameters:**
+    /// - `name`: Parameter name.
+    ///
+    /// **Returns:**
+    /// - `Some(value)` if present, otherwise `None`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # let query = server::extract::QueryParams::parse("q=rust");
+    /// let q = query.get("q");
+    /// # assert_eq!(q, Some("rust"));
+    /// ```
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.pairs
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// # get_all
+    ///
+    /// **Purpose:**
+    /// Returns every value of a repeated parameter, in order.
+    ///
+    /// **Parameters:**
+    /// - `name`: Parameter name.
+    ///
+    /// **Returns:**
+    /// - The values, possibly empty.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # let query = server::extract::QueryParams::parse("tag=a&tag=b");
+    /// let tags = query.get_all("tag");
+    /// # assert_eq!(tags, ["a", "b"]);
+    /// ```
+    pub fn get_all(&self, name: &str) -> Vec<&str> {
+        self.pairs
+            .iter()
+            .filter(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+            .collect()
+    }
+
+    /// # parse_value
+    ///
+    /// **Purpose:**
+    /// Parses an optional parameter.
+    ///
+    /// **Parameters:**
+    /// - `name`: Parameter name.
+    ///
+    /// **Returns:**
+    /// - `Ok(None)` if absent, `Ok(Some(value))` if it parses.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns a message if the parameter is present but does not parse as `T`.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # let query = server::extract::QueryParams::parse("page=2");
+    /// let page: u32 = query.parse_value("page")?.unwrap_or(1);
+    /// # assert_eq!(page, 2);
+    /// # Ok::<(), String>(())
+    /// ```
+    pub fn parse_value<T: FromStr>(&self, name: &str) -> Result<Option<T>, String> {
+        self.get(name)
+            .map(|value| value.parse().map_err(|_| format!("invalid query parameter '{name}'")))
+            .transpose()
+    }
+
+    /// # require
+    ///
+    /// **Purpose:**
+    /// Parses a mandatory parameter.
+    ///
+    /// **Parameters:**
+    /// - `name`: Parameter name.
+    ///
+    /// **Returns:**
+    /// - The parsed value.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns a message if the parameter is missing or does not parse as `T`.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # let query = server::extract::QueryParams::parse("q=rust");
+    /// let q: String = query.require("q")?;
+    /// # Ok::<(), String>(())
+    /// ```
+    pub fn require<T: FromStr>(&self, name: &str) -> Result<T, String> {
+        self.parse_value(name)?
+            .ok_or_else(|| format!("missing query parameter '{name}'"))
+    }
+
+    /// # iter
+    ///
+    /// **Purpose:**
+    /// Iterates over all decoded pairs in order.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - An iterator of `(name, value)` pairs.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # let query = server::extract::QueryParams::parse("q=rust&page=2");
+    /// for (name, value) in query.iter() { println!("{name} = {value}"); }
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.pairs.iter().map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+}
+
+
+impl FromQuery for QueryParams {
+
+    /// # from_query
+    ///
+    /// **Purpose:**
+    /// Returns the parameters unchanged.
+    ///
+    /// **Parameters:**
+    /// - `query`: Decoded query parameters.
+    ///
+    /// **Returns:**
+    /// - A copy of `query`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{extract::{Query, QueryParams}, response::Response};
+    /// fn list(Query(query): Query<QueryParams>) -> Response {
+    ///     Response::new(200, "OK").with_body(query.get("sort").unwrap_or("name").to_string())
+    /// }
+    /// ```
+    fn from_query(query: &QueryParams) -> Result<QueryParams, String> {
+        Ok(query.clone())
+    }
+}
+
+
+impl FromQuery for HashMap<String, String> {
+
+    /// # from_query
+    ///
+    /// **Purpose:**
+    /// Collects the parameters into a map; for repeated names the first value wins.
+    ///
+    /// **Parameters:**
+    /// - `query`: Decoded query parameters.
+    ///
+    /// **Returns:**
+    /// - The parameter map.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{extract::Query, response::Response};
+    /// # use std::collections::HashMap;
+    /// fn list(Query(query): Query<HashMap<String, String>>) -> Response {
+    ///     Response::new(200, "OK").with_body(query.get("page").cloned().unwrap_or_default())
+    /// }
+    /// ```
+    fn from_query(query: &QueryParams) -> Result<HashMap<String, String>, String> {
+        let mut map = HashMap::new();
+        for (name, value) in query.iter() {
+            map.entry(name.to_string()).or_insert_with(|| value.to_string());
+        }
+        Ok(map)
+    }
+}
+
+
+impl<T: FromQuery> FromRequest for Query<T> {
+
+    /// # from_request
+    ///
+    /// **Purpose:**
+    /// Parses the request's query string as `T`.
+    ///
+    /// **Parameters:**
+    /// - `request`: Request being handled.
+    ///
+    /// **Returns:**
+    /// - The parsed query.
+    ///
+    /// **Errors / Failures:**
+    /// - Rejects with `400 Bad Request` if `T` cannot be built.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{extract::{FromQuery, FromRequest, Query, QueryParams}, request::Request, response::Response};
+    /// # struct SearchParams { q: String }
+    /// # impl FromQuery for SearchParams {
+    /// #     fn from_query(query: &QueryParams) -> Result<SearchParams, String> { Ok(SearchParams { q: query.require("q")? }) }
+    /// # }
+    /// # let request = Request::read_from(&mut &b"GET /search?q=rust HTTP/1.1\r\n\r\n"[..]).unwrap();
+    /// let Query(params) = Query::<SearchParams>::from_request(&request)?;
+    /// # assert_eq!(params.q, "rust");
+    /// # Ok::<(), Response>(())
+    /// ```
+    fn from_request(request: &Request) -> Result<Query<T>, Response> {
+        let query = QueryParams::parse(request.query().unwrap_or(""));
+        T::from_query(&query).map(Query).map_err(|message| bad_request(&message))
+    }
+}
+
+
+impl<T: FromPath> FromRequest for Path<T> {
+
+    /// # from_request
+    ///
+    /// **Purpose:**
+    /// Parses the route's captured parameters as `T`.
+    ///
+    /// **Parameters:**
+    /// - `request`: Request being handled.
+    ///
+    /// **Returns:**
+    /// - The parsed path parameters.
+    ///
+    /// **Errors / Failures:**
+    /// - Rejects with `400 Bad Request` if a parameter is missing or does not parse.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{extract::{FromRequest, Path}, request::Request, response::Response};
+    /// # let mut request = Request::read_from(&mut &b"GET /users/42 HTTP/1.1\r\n\r\n"[..]).unwrap();
+    /// # request.params.push(("id".to_string(), "42".to_string()));
+    /// let Path(id) = Path::<u32>::from_request(&request)?;
+    /// # assert_eq!(id, 42);
+    /// # Ok::<(), Response>(())
+    /// ```
+    fn from_request(request: &Request) -> Result<Path<T>, Response> {
+        T::from_path(&request.params).map(Path).map_err(|message| bad_request(&message))
+    }
+}
+
+
+impl<T: PathValue> FromPath for T {
+
+    /// # from_path
+    ///
+    /// **Purpose:**
+    /// Parses the last captured parameter.
+    ///
+    /// **Parameters:**
+    /// - `params`: Captured parameters.
+    ///
+    /// **Returns:**
+    /// - The parsed value.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns a message if there is no parameter or it does not parse.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{extract::FromPath, request::Request};
+    /// # let mut request = Request::read_from(&mut &b"GET /users/42 HTTP/1.1\r\n\r\n"[..]).unwrap();
+    /// # request.params.push(("id".to_string(), "42".to_string()));
+    /// let id = u32::from_path(&request.params)?;
+    /// # assert_eq!(id, 42);
+    /// # Ok::<(), String>(())
+    /// ```
+    fn from_path(params: &[(String, String)]) -> Result<T, String> {
+        let (name, value) = params.last().ok_or("route has no path parameters")?;
+        path_value(name, value)
+    }
+}
+
+
+/// # path_value
+///
+/// **Purpose:**
+/// Percent-decodes and parses one path parameter.
+///
+/// **Parameters:**
+/// - `name`: Parameter name, for error messages.
+/// - `value`: Raw captured text.
+///
+/// **Returns:**
+/// - The parsed value.
+///
+/// **Errors / Failures:**
+/// - Returns a message if the value has invalid escapes or does not parse as `T`.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let id: u32 = path_value("id", "42")?;
+/// ```
+fn path_value<T: FromStr>(name: &str, value: &str) -> Result<T, String> {
+    percent_decode(value)
+        .and_then(|value| value.parse().ok())
+        .ok_or_else(|| format!("invalid path parameter '{name}'"))
+}
+
+
+/// # path_values
+///
+/// **Purpose:**
+/// Implements `PathValue` for standard scalar types.
+macro_rules! path_values {
+    ($($ty:ty),*) => {
+        $(impl PathValue for $ty {})*
+    };
+}
+
+path_values!(String, bool, char, u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64);
+
+
+/// # path_tuples
+///
+/// **Purpose:**
+/// Implements `FromPath` for tuples, matching elements to the last captured parameters in order.
+macro_rules! path_tuples {
+    ($count:literal => $($ty:ident),+) => {
+        impl<$($ty: PathValue),+> FromPath for ($($ty,)+) {
+            fn from_path(params: &[(String, String)]) -> Result<Self, String> {
+                let start = params
+                    .len()
+                    .checked_sub($count)
+                    .ok_or_else(|| format!("route has fewer than {} path parameters", $count))?;
+                let mut params = params[start..].iter();
+                Ok(($({
+                    let (name, value) = params.next().expect("tuple arity checked above");
+                    path_value::<$ty>(name, value)?
+                },)+))
+            }
+        }
+    };
+}
+
+path_tuples!(1 => A);
+path_tuples!(2 => A, B);
+path_tuples!(3 => A, B, C);
+path_tuples!(4 => A, B, C, D);
+
+
+/// # extract_handlers
+///
+/// **Purpose:**
+/// Implements `ExtractHandler` for functions taking one or more extractor arguments.
+macro_rules! extract_handlers {
+    ($($arg:ident),+) => {
+        impl<F, $($arg),+> ExtractHandler<($($arg,)+)> for F
+        where
+            F: Fn($($arg),+) -> Response,
+            $($arg: FromRequest),+
+        {
+            #[allow(non_snake_case)]
+            fn call(&self, request: &Request) -> Response {
+                $(
+                    let $arg = match $arg::from_request(request) {
+                        Ok(value) => value,
+                        Err(rejection) => return rejection,
+                    };
+                )+
+                self($($arg),+)
+            }
+        }
+    };
+}
+
+extract_handlers!(A);
+extract_handlers!(A, B);
+extract_handlers!(A, B, C);
+extract_handlers!(A, B, C, D);
+
+
+/// # extract
+///
+/// **Purpose:**
+/// Adapts a function with extractor arguments into a route handler.
+///
+/// **Parameters:**
+/// - `handler`: Function such as `fn(Path<u32>, Query<SearchParams>) -> Response`.
+///
+/// **Returns:**
+/// - A closure usable with `Router::route`, `get`, and friends.
+///
+/// **Errors / Failures:**
+/// None. Extraction failures are answered with the extractor's rejection (`400 Bad Request`).
+///
+/// **Examples:**
+/// ```rust
+/// # use server::{extract::{Path, extract}, response::Response, router::Router};
+/// # fn show_user(Path(id): Path<u32>) -> Response { Response::new(200, "OK").with_body(format!("user {id}")) }
+/// let router = Router::new().get("/users/:id", extract(show_user));
+/// ```
+pub fn extract<Args, H>(handler: H) -> impl Fn(&Request) -> Response + Send + Sync + 'static
+where
+    H: ExtractHandler<Args> + Send + Sync + 'static,
+{
+    move |request| handler.call(request)
+}
+
+
+/// # bad_request
+///
+/// **Purpose:**
+/// Builds the `400 Bad Request` rejection used by the built-in extractors.
+///
+/// **Parameters:**
+/// - `message`: Explanation sent as a plain-text body.
+///
+/// **Returns:**
+/// - The rejection `Response`.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust
+/// # use server::{extract::bad_request, request::Request, response::Response};
+/// # fn user_agent(request: &Request) -> Result<String, Response> {
+/// # if request.headers.get("User-Agent").is_none() {
+/// return Err(bad_request("missing User-Agent"));
+/// # }
+/// # Ok(request.headers.get("User-Agent").unwrap_or_default().to_string())
+/// # }
+/// ```
+pub fn bad_request(message: &str) -> Response {
+    Response::new(400, "Bad Request")
+        .with_header("Content-Type", "text/plain; charset=utf-8")
+        .with_body(message.to_string())
+}
+
+
+/// # decode_component
+///
+/// **Purpose:**
+/// Decodes one `application/x-www-form-urlencoded` component.
+///
+/// **Parameters:**
+/// - `component`: Raw name or value.
+///
+/// **Returns:**
+/// - `Some(decoded)`, or `None` for invalid escapes.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// assert_eq!(decode_component("a+b%21").as_deref(), Some("a b!"));
+/// ```
+fn decode_component(component: &str) -> Option<String> {
+    percent_decode(&component.replace('+', " "))
+}
+
+
+/// # encode_component
+///
+/// **Purpose:**
+/// Encodes one `application/x-www-form-urlencoded` component, the inverse of `decode_component`.
+///
+/// **Parameters:**
+/// - `component`: Raw name or value.
+///
+/// **Returns:**
+/// - The component with every byte outside `A-Z a-z 0-9 - . _ ~` percent-encoded.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// assert_eq!(encode_component("a b/c"), "a%20b%2Fc");
+/// ```
+pub(crate) fn encode_component(component: &str) -> String {
+    let mut encoded = String::with_capacity(component.len());
+    for byte in component.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~') {
+            encoded.push(char::from(byte));
+        } else {
+            encoded.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    encoded
+}
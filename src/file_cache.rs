@@ -0,0 +1,404 @@
+
+// ============================================================
+//  DAEGONICA SOFTWARE — file_cache.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: FileCache
+//!
+//! **Purpose:**
+//! Keeps the contents of small, frequently served static files in memory.
+//!
+//! **Context:**
+//! - Consulted by `StaticFiles` in place of reading the file whenever a cache is attached, so a
+//!   hot landing page costs one `stat` per request instead of an open, read, and close.
+//!
+//! **Responsibilities:**
+//! - Stores files up to a per-file size limit, keyed by path.
+//! - Invalidates an entry when the file's modification time or length changes.
+//! - Evicts least-recently-used entries once the configured byte budget is exceeded.
+//! - Tracks hit/miss counters.
+//! - Can be told about changes by a `Watcher`; otherwise changes are noticed on the next
+//!   request for the file.
+//! - Does NOT watch the file system itself.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs,
+    io,
+    path::{Path, PathBuf},
+    sync::{
+        Mutex, PoisonError,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::SystemTime,
+};
+
+
+/// # DEFAULT_MAX_FILE_BYTES
+///
+/// **Summary:**
+/// Largest file cached unless `max_file_size` says otherwise.
+const DEFAULT_MAX_FILE_BYTES: usize = 256 * 1024;
+
+
+/// # FileCache
+///
+/// **Summary:**
+/// Thread-safe, size-bounded cache of file contents.
+///
+/// **Fields:**
+/// - `max_bytes`: Upper bound on the total size of cached files.
+/// - `max_file_bytes`: Files larger than this are always read from disk.
+/// - `state`: Entries and recency order behind one lock.
+/// - `hits`: Reads answered from memory.
+/// - `misses`: Reads that went to disk.
+///
+/// **Usage Example:**
+/// ```rust
+/// # use server::{file_cache::FileCache, static_files::StaticFiles};
+/// # use std::sync::Arc;
+/// let cache = Arc::new(FileCache::new(8 * 1024 * 1024).max_file_size(64 * 1024));
+/// let files = StaticFiles::new("html").file_cache(Arc::clone(&cache));
+/// ```
+pub struct FileCache {
+    max_bytes: usize,
+    max_file_bytes: usize,
+    state: Mutex<FileCacheState>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+
+/// # FileCacheStats
+///
+/// **Summary:**
+/// Point-in-time snapshot of file cache counters.
+///
+/// **Fields:**
+/// - `hits`: Reads answered from memory.
+/// - `misses`: Reads that went to disk.
+/// - `entries`: Number of cached files.
+/// - `bytes`: Total size of cached files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FileCacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub entries: usize,
+    pub bytes: usize,
+}
+
+
+/// # FileCacheState
+///
+/// **Summary:**
+/// Mutable cache contents guarded by the `FileCache` lock.
+///
+/// **Fields:**
+/// - `entries`: Cached files by path.
+/// - `recency`: Paths ordered by last use (oldest first).
+/// - `tick`: Monotonic use counter feeding `recency`.
+/// - `bytes`: Total size of cached files.
+#[derive(Default)]
+struct FileCacheState {
+    entries: HashMap<PathBuf, CachedFile>,
+    recency: BTreeMap<u64, PathBuf>,
+    tick: u64,
+    bytes: usize,
+}
+
+
+/// # CachedFile
+///
+/// **Summary:**
+/// One cached file and the metadata it was read with.
+///
+/// **Fields:**
+/// - `contents`: File bytes.
+/// - `modified`: Modification time when read.
+/// - `len`: Length when read.
+/// - `last_used`: Recency tick of the last hit.
+struct CachedFile {
+    contents: Vec<u8>,
+    modified: Option<SystemTime>,
+    len: u64,
+    last_used: u64,
+}
+
+
+impl FileCache {
+
+    /// # new
+    ///
+    /// **Purpose:**
+    /// Creates an empty cache holding at most `max_bytes` of file contents, with files up to
+    /// 256 KiB eligible.
+    ///
+    /// **Parameters:**
+    /// - `max_bytes`: Maximum total size of cached files.
+    ///
+    /// **Returns:**
+    /// - A new `FileCache`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::file_cache::FileCache;
+    /// let cache = FileCache::new(8 * 1024 * 1024);
+    /// ```
+    pub fn new(max_bytes: usize) -> FileCache {
+        FileCache {
+            max_bytes,
+            max_file_bytes: DEFAULT_MAX_FILE_BYTES,
+            state: Mutex::new(FileCacheState::default()),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// # max_file_size
+    ///
+    /// **Purpose:**
+    /// Sets the largest file the cache will hold.
+    ///
+    /// **Parameters:**
+    /// - `bytes`: Per-file size limit.
+    ///
+    /// **Returns:**
+    /// - The updated `FileCache`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::file_cache::FileCache;
+    /// let cache = FileCache::new(8 * 1024 * 1024).max_file_size(64 * 1024);
+    /// ```
+    pub fn max_file_size(mut self, bytes: usize) -> FileCache {
+        self.max_file_bytes = bytes;
+        self
+    }
+
+    /// # read
+    ///
+    /// **Purpose:**
+    /// Returns a file's contents, from memory when the cached copy still matches the file's
+    /// modification time and length, otherwise from disk (caching the result if it fits).
+    ///
+    /// **Parameters:**
+    /// - `path`: File to read.
+    ///
+    /// **Returns:**
+    /// - The file's contents.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns an I/O error if the file cannot be inspected or read.
+    ///
+    /// **Examples:**
+    /// ```rust,no_run
+    /// # use server::file_cache::FileCache;
+    /// # use std::path::Path;
+    /// # let cache = FileCache::new(8 * 1024 * 1024);
+    /// let contents = cache.read(Path::new("html/index.html"))?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        let metadata = fs::metadata(path)?;
+        let modified = metadata.modified().ok();
+        let len = metadata.len();
+
+        {
+            let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+            state.tick += 1;
+            let tick = state.tick;
+            let FileCacheState { entries, recency, .. } = &mut *state;
+            if let Some(entry) = entries.get_mut(path)
+                && entry.modified == modified
+                && entry.len == len
+            {
+                recency.remove(&entry.last_used);
+                recency.insert(tick, path.to_path_buf());
+                entry.last_used = tick;
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                return Ok(entry.contents.clone());
+            }
+            state.remove(path);
+        }
+
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        let contents = fs::read(path)?;
+        if contents.len() <= self.max_file_bytes && contents.len() <= self.max_bytes {
+            self.store(path, &contents, modified);
+        }
+        Ok(contents)
+    }
+
+    /// # invalidate
+    ///
+    /// **Purpose:**
+    /// Drops the cached copy of one file, or of every file beneath a directory.
+    ///
+    /// **Parameters:**
+    /// - `path`: Changed file or directory, spelled as `StaticFiles` resolves it (document root
+    ///   joined with the request path).
+    ///
+    /// **Returns:**
+    /// None.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use std::path::Path;
+    /// # let cache = server::file_cache::FileCache::new(8 * 1024 * 1024);
+    /// cache.invalidate(Path::new("html/index.html"));
+    /// ```
+    pub fn invalidate(&self, path: &Path) {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        let stale: Vec<PathBuf> = state.entries.keys().filter(|cached| cached.starts_with(path)).cloned().collect();
+        for cached in stale {
+            state.remove(&cached);
+        }
+    }
+
+    /// # clear
+    ///
+    /// **Purpose:**
+    /// Drops every cached file.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// None.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # let cache = server::file_cache::FileCache::new(8 * 1024 * 1024);
+    /// cache.clear();
+    /// ```
+    pub fn clear(&self) {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        state.entries.clear();
+        state.recency.clear();
+        state.bytes = 0;
+    }
+
+    /// # stats
+    ///
+    /// **Purpose:**
+    /// Returns current cache counters.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - A `FileCacheStats` snapshot.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # let cache = server::file_cache::FileCache::new(8 * 1024 * 1024);
+    /// println!("file cache hits: {}", cache.stats().hits);
+    /// ```
+    pub fn stats(&self) -> FileCacheStats {
+        let state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        FileCacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            entries: state.entries.len(),
+            bytes: state.bytes,
+        }
+    }
+
+    /// # store
+    ///
+    /// **Purpose:**
+    /// Caches freshly read contents, evicting least-recently-used files to stay within budget.
+    ///
+    /// **Parameters:**
+    /// - `path`: File the contents came from.
+    /// - `contents`: File bytes.
+    /// - `modified`: Modification time seen before reading.
+    ///
+    /// **Returns:**
+    /// None.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// self.store(path, &contents, modified);
+    /// ```
+    fn store(&self, path: &Path, contents: &[u8], modified: Option<SystemTime>) {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        state.remove(path);
+        while state.bytes + contents.len() > self.max_bytes {
+            let Some((_, oldest)) = state.recency.pop_first() else {
+                break;
+            };
+            state.remove(&oldest);
+        }
+
+        state.tick += 1;
+        let tick = state.tick;
+        state.recency.insert(tick, path.to_path_buf());
+        state.bytes += contents.len();
+        state.entries.insert(
+            path.to_path_buf(),
+            CachedFile {
+                contents: contents.to_vec(),
+                modified,
+                len: contents.len() as u64,
+                last_used: tick,
+            },
+        );
+    }
+}
+
+
+impl FileCacheState {
+
+    /// # remove
+    ///
+    /// **Purpose:**
+    /// Removes a cached file and its recency record, updating the byte total.
+    ///
+    /// **Parameters:**
+    /// - `path`: File to forget.
+    ///
+    /// **Returns:**
+    /// None.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// state.remove(path);
+    /// ```
+    fn remove(&mut self, path: &Path) {
+        if let Some(entry) = self.entries.remove(path) {
+            self.recency.remove(&entry.last_used);
+            self.bytes -= entry.contents.len();
+        }
+    }
+}
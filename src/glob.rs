@@ -0,0 +1,237 @@
+
+// ============================================================
+//  DAEGONICA SOFTWARE — glob.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: Glob
+//!
+//! **Purpose:**
+//! Matches URL paths against simple wildcard patterns such as `/assets/**` or `/blog/*.html`.
+//!
+//! **Context:**
+//! - Used wherever configuration selects requests by path pattern (e.g. static cache policies).
+//!
+//! **Responsibilities:**
+//! - Parses patterns into literal and wildcard tokens.
+//! - Matches paths and returns the text captured by each wildcard.
+//! - Does NOT support character classes, alternation, or escaping.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+
+/// # Glob
+///
+/// **Summary:**
+/// A compiled path pattern. `*` matches within one path segment, `**` matches across segments.
+///
+/// **Fields:**
+/// - `pattern`: Source pattern, kept for display.
+/// - `tokens`: Parsed literal and wildcard tokens.
+///
+/// **Usage Example:**
+/// ```rust
+/// # use server::glob::Glob;
+/// let glob = Glob::new("/assets/**");
+/// assert!(glob.matches("/assets/img/logo.png"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Glob {
+    pattern: String,
+    tokens: Vec<Token>,
+}
+
+
+/// # Token
+///
+/// **Summary:**
+/// One element of a compiled glob.
+///
+/// **Variants:**
+/// - `Literal`: Text that must match exactly.
+/// - `Star`: Any run of characters other than `/`.
+/// - `DoubleStar`: Any run of characters, including `/`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Literal(String),
+    Star,
+    DoubleStar,
+}
+
+
+impl Glob {
+
+    /// # new
+    ///
+    /// **Purpose:**
+    /// Compiles a glob pattern.
+    ///
+    /// **Parameters:**
+    /// - `pattern`: Pattern text using `*` and `**` wildcards.
+    ///
+    /// **Returns:**
+    /// - A compiled `Glob`.
+    ///
+    /// **Errors / Failures:**
+    /// None. Every string is a valid pattern.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::glob::Glob;
+    /// let glob = Glob::new("/blog/*.html");
+    /// ```
+    pub fn new(pattern: &str) -> Glob {
+        let mut tokens = Vec::new();
+        let mut literal = String::new();
+        let mut chars = pattern.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            if c != '*' {
+                literal.push(c);
+                continue;
+            }
+            if !literal.is_empty() {
+                tokens.push(Token::Literal(std::mem::take(&mut literal)));
+            }
+            if chars.peek() == Some(&'*') {
+                chars.next();
+                tokens.push(Token::DoubleStar);
+            } else {
+                tokens.push(Token::Star);
+            }
+        }
+        if !literal.is_empty() {
+            tokens.push(Token::Literal(literal));
+        }
+
+        Glob {
+            pattern: pattern.to_string(),
+            tokens,
+        }
+    }
+
+    /// # as_str
+    ///
+    /// **Purpose:**
+    /// Returns the source pattern.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - The pattern text passed to `new`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # let glob = server::glob::Glob::new("/blog/*.html");
+    /// println!("{}", glob.as_str());
+    /// ```
+    pub fn as_str(&self) -> &str {
+        &self.pattern
+    }
+
+    /// # matches
+    ///
+    /// **Purpose:**
+    /// Checks whether `path` matches the whole pattern.
+    ///
+    /// **Parameters:**
+    /// - `path`: Path to test.
+    ///
+    /// **Returns:**
+    /// - `true` on a match.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::glob::Glob;
+    /// assert!(Glob::new("/*.css").matches("/site.css"));
+    /// ```
+    pub fn matches(&self, path: &str) -> bool {
+        self.captures(path).is_some()
+    }
+
+    /// # captures
+    ///
+    /// **Purpose:**
+    /// Matches `path` and returns the text consumed by each wildcard, in pattern order.
+    /// Wildcards are greedy, backtracking as needed for the rest of the pattern to match.
+    ///
+    /// **Parameters:**
+    /// - `path`: Path to test.
+    ///
+    /// **Returns:**
+    /// - `Some(captures)` on a match, otherwise `None`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::glob::Glob;
+    /// let caps = Glob::new("/old-blog/**").captures("/old-blog/2020/post");
+    /// assert_eq!(caps, Some(vec!["2020/post".to_string()]));
+    /// ```
+    pub fn captures(&self, path: &str) -> Option<Vec<String>> {
+        let mut captures = Vec::new();
+        match_tokens(&self.tokens, path, &mut captures).then_some(captures)
+    }
+}
+
+
+/// # match_tokens
+///
+/// **Purpose:**
+/// Recursive backtracking matcher behind `Glob::captures`.
+///
+/// **Parameters:**
+/// - `tokens`: Remaining pattern tokens.
+/// - `path`: Remaining input.
+/// - `captures`: Wildcard captures collected so far.
+///
+/// **Returns:**
+/// - `true` if the remaining tokens consume the remaining input exactly.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let matched = match_tokens(&tokens, "/a/b", &mut Vec::new());
+/// ```
+fn match_tokens(tokens: &[Token], path: &str, captures: &mut Vec<String>) -> bool {
+    let Some((token, rest)) = tokens.split_first() else {
+        return path.is_empty();
+    };
+
+    match token {
+        Token::Literal(literal) => path
+            .strip_prefix(literal.as_str())
+            .is_some_and(|remaining| match_tokens(rest, remaining, captures)),
+        Token::Star | Token::DoubleStar => {
+            let limit = match token {
+                Token::Star => path.find('/').unwrap_or(path.len()),
+                _ => path.len(),
+            };
+            for end in (0..=limit).rev().filter(|&end| path.is_char_boundary(end)) {
+                captures.push(path[..end].to_string());
+                if match_tokens(rest, &path[end..], captures) {
+                    return true;
+                }
+                captures.pop();
+            }
+            false
+        }
+    }
+}
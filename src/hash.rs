@@ -0,0 +1,487 @@
+
+// ============================================================
+//  DAEGONICA SOFTWARE — hash.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: Hash
+//!
+//! **Purpose:**
+//! Computes the message digests required by the authentication protocols.
+//!
+//! **Context:**
+//! - Internal helper for Digest authentication, token verification, and response digests.
+//!
+//! **Responsibilities:**
+//! - Implements MD5 (RFC 1321), SHA-256 (FIPS 180-4), and HMAC-SHA256 (RFC 2104) over byte slices.
+//! - Computes SHA-256 incrementally over data that arrives in pieces.
+//! - Formats digests as lower-case hex.
+//! - Does NOT hash passwords for storage; MD5 is only here because Digest auth mandates it.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+use std::fmt::Write as _;
+
+
+/// # MD5_SHIFTS
+///
+/// **Summary:**
+/// Per-round left-rotation amounts of MD5.
+const MD5_SHIFTS: [u32; 64] = [
+    7, 12, 17, 22, 7, 12, 17, 22, 7, 12, 17, 22, 7, 12, 17, 22,
+    5, 9, 14, 20, 5, 9, 14, 20, 5, 9, 14, 20, 5, 9, 14, 20,
+    4, 11, 16, 23, 4, 11, 16, 23, 4, 11, 16, 23, 4, 11, 16, 23,
+    6, 10, 15, 21, 6, 10, 15, 21, 6, 10, 15, 21, 6, 10, 15, 21,
+];
+
+
+/// # SHA256_ROUND_CONSTANTS
+///
+/// **Summary:**
+/// First 32 bits of the fractional parts of the cube roots of the first 64 primes.
+const SHA256_ROUND_CONSTANTS: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+
+/// # md5
+///
+/// **Purpose:**
+/// Computes the MD5 digest of a message.
+///
+/// **Parameters:**
+/// - `message`: Bytes to hash.
+///
+/// **Returns:**
+/// - The 16-byte digest.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// assert_eq!(hash::hex(&hash::md5(b"")), "d41d8cd98f00b204e9800998ecf8427e");
+/// ```
+pub(crate) fn md5(message: &[u8]) -> [u8; 16] {
+    let mut state: [u32; 4] = [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476];
+    for block in pad(message, false).chunks_exact(64) {
+        let words: Vec<u32> = block
+            .chunks_exact(4)
+            .map(|word| u32::from_le_bytes([word[0], word[1], word[2], word[3]]))
+            .collect();
+        let [mut a, mut b, mut c, mut d] = state;
+        for (round, shift) in MD5_SHIFTS.iter().enumerate() {
+            let (mixed, index) = match round / 16 {
+                0 => ((b & c) | (!b & d), round),
+                1 => ((d & b) | (!d & c), (5 * round + 1) % 16),
+                2 => (b ^ c ^ d, (3 * round + 5) % 16),
+                _ => (c ^ (b | !d), (7 * round) % 16),
+            };
+            // The sine-derived constant: floor(|sin(round + 1)| * 2^32).
+            let constant = ((round as f64 + 1.0).sin().abs() * 4_294_967_296.0) as u32;
+            let rotated = a
+                .wrapping_add(mixed)
+                .wrapping_add(constant)
+                .wrapping_add(words[index])
+                .rotate_left(*shift);
+            (a, b, c, d) = (d, b.wrapping_add(rotated), b, c);
+        }
+        for (word, value) in state.iter_mut().zip([a, b, c, d]) {
+            *word = word.wrapping_add(value);
+        }
+    }
+
+    let mut digest = [0u8; 16];
+    for (chunk, word) in digest.chunks_exact_mut(4).zip(state) {
+        chunk.copy_from_slice(&word.to_le_bytes());
+    }
+    digest
+}
+
+
+/// # Sha256
+///
+/// **Summary:**
+/// SHA-256 digest computed incrementally, for data that arrives in pieces.
+///
+/// **Fields:**
+/// - `state`: Hash state after the blocks processed so far.
+/// - `pending`: Bytes not yet filling a whole 64-byte block.
+/// - `len`: Total bytes hashed.
+///
+/// **Usage Example:**
+/// ```rust,ignore
+/// let mut hasher = Sha256::new();
+/// hasher.update(b"ab");
+/// hasher.update(b"c");
+/// assert_eq!(hasher.finish(), hash::sha256(b"abc"));
+/// ```
+#[derive(Debug, Clone)]
+pub(crate) struct Sha256 {
+    state: [u32; 8],
+    pending: Vec<u8>,
+    len: u64,
+}
+
+
+impl Sha256 {
+
+    /// # new
+    ///
+    /// **Purpose:**
+    /// Starts a digest of no data.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - A fresh `Sha256`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let mut hasher = Sha256::new();
+    /// ```
+    pub(crate) fn new() -> Sha256 {
+        Sha256 {
+            state: [0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19],
+            pending: Vec::with_capacity(64),
+            len: 0,
+        }
+    }
+
+    /// # update
+    ///
+    /// **Purpose:**
+    /// Hashes the next piece of data.
+    ///
+    /// **Parameters:**
+    /// - `bytes`: Data following everything hashed so far.
+    ///
+    /// **Returns:**
+    /// None.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// hasher.update(chunk);
+    /// ```
+    pub(crate) fn update(&mut self, mut bytes: &[u8]) {
+        self.len = self.len.wrapping_add(bytes.len() as u64);
+        if !self.pending.is_empty() {
+            let take = (64 - self.pending.len()).min(bytes.len());
+            self.pending.extend_from_slice(&bytes[..take]);
+            bytes = &bytes[take..];
+            if self.pending.len() < 64 {
+                return;
+            }
+            let block = std::mem::take(&mut self.pending);
+            sha256_block(&mut self.state, &block);
+            self.pending = block;
+            self.pending.clear();
+        }
+        let blocks = bytes.chunks_exact(64);
+        self.pending.extend_from_slice(blocks.remainder());
+        for block in blocks {
+            sha256_block(&mut self.state, block);
+        }
+    }
+
+    /// # finish
+    ///
+    /// **Purpose:**
+    /// Pads the data hashed so far and produces its digest.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - The 32-byte digest.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let digest = hasher.finish();
+    /// ```
+    pub(crate) fn finish(mut self) -> [u8; 32] {
+        let bits = self.len.wrapping_mul(8);
+        let mut tail = std::mem::take(&mut self.pending);
+        tail.push(0x80);
+        while tail.len() % 64 != 56 {
+            tail.push(0);
+        }
+        tail.extend_from_slice(&bits.to_be_bytes());
+        for block in tail.chunks_exact(64) {
+            sha256_block(&mut self.state, block);
+        }
+
+        let mut digest = [0u8; 32];
+        for (chunk, word) in digest.chunks_exact_mut(4).zip(self.state) {
+            chunk.copy_from_slice(&word.to_be_bytes());
+        }
+        digest
+    }
+}
+
+
+/// # sha256
+///
+/// **Purpose:**
+/// Computes the SHA-256 digest of a message.
+///
+/// **Parameters:**
+/// - `message`: Bytes to hash.
+///
+/// **Returns:**
+/// - The 32-byte digest.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let digest = hash::sha256(b"abc");
+/// ```
+pub(crate) fn sha256(message: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(message);
+    hasher.finish()
+}
+
+
+/// # sha256_block
+///
+/// **Purpose:**
+/// Runs the SHA-256 compression function over one block.
+///
+/// **Parameters:**
+/// - `state`: Hash state to advance.
+/// - `block`: Exactly 64 bytes of padded message.
+///
+/// **Returns:**
+/// None.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// sha256_block(&mut self.state, block);
+/// ```
+fn sha256_block(state: &mut [u32; 8], block: &[u8]) {
+    let mut schedule = [0u32; 64];
+    for (word, bytes) in schedule.iter_mut().zip(block.chunks_exact(4)) {
+        *word = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+    }
+    for i in 16..64 {
+        let s0 = schedule[i - 15].rotate_right(7) ^ schedule[i - 15].rotate_right(18) ^ (schedule[i - 15] >> 3);
+        let s1 = schedule[i - 2].rotate_right(17) ^ schedule[i - 2].rotate_right(19) ^ (schedule[i - 2] >> 10);
+        schedule[i] = schedule[i - 16]
+            .wrapping_add(s0)
+            .wrapping_add(schedule[i - 7])
+            .wrapping_add(s1);
+    }
+
+    let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = *state;
+    for (constant, word) in SHA256_ROUND_CONSTANTS.iter().zip(schedule) {
+        let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+        let choice = (e & f) ^ (!e & g);
+        let t1 = h
+            .wrapping_add(s1)
+            .wrapping_add(choice)
+            .wrapping_add(*constant)
+            .wrapping_add(word);
+        let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+        let majority = (a & b) ^ (a & c) ^ (b & c);
+        let t2 = s0.wrapping_add(majority);
+        (h, g, f, e, d, c, b, a) = (g, f, e, d.wrapping_add(t1), c, b, a, t1.wrapping_add(t2));
+    }
+    for (word, value) in state.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+        *word = word.wrapping_add(value);
+    }
+}
+
+
+/// # hmac_sha256
+///
+/// **Purpose:**
+/// Computes an HMAC-SHA256 message authentication code.
+///
+/// **Parameters:**
+/// - `key`: Secret key; keys longer than the 64-byte block are hashed first.
+/// - `message`: Bytes to authenticate.
+///
+/// **Returns:**
+/// - The 32-byte MAC.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let mac = hash::hmac_sha256(secret, signing_input.as_bytes());
+/// ```
+pub(crate) fn hmac_sha256(key: &[u8], message: &[u8]) -> [u8; 32] {
+    let mut block = [0u8; 64];
+    if key.len() > 64 {
+        block[..32].copy_from_slice(&sha256(key));
+    } else {
+        block[..key.len()].copy_from_slice(key);
+    }
+
+    let mut inner: Vec<u8> = block.iter().map(|byte| byte ^ 0x36).collect();
+    inner.extend_from_slice(message);
+    let mut outer: Vec<u8> = block.iter().map(|byte| byte ^ 0x5c).collect();
+    outer.extend_from_slice(&sha256(&inner));
+    sha256(&outer)
+}
+
+/// # hex
+///
+/// **Purpose:**
+/// Formats bytes as lower-case hex.
+///
+/// **Parameters:**
+/// - `bytes`: Bytes to format.
+///
+/// **Returns:**
+/// - Two hex digits per byte.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// assert_eq!(hash::hex(&[0xde, 0xad]), "dead");
+/// ```
+pub(crate) fn hex(bytes: &[u8]) -> String {
+    bytes.iter().fold(String::with_capacity(bytes.len() * 2), |mut hex, byte| {
+        let _ = write!(hex, "{byte:02x}");
+        hex
+    })
+}
+
+
+/// # pad
+///
+/// **Purpose:**
+/// Applies Merkle–Damgård padding: a `0x80` byte, zeros, then the bit length.
+///
+/// **Parameters:**
+/// - `message`: Message to pad.
+/// - `big_endian`: Byte order of the length (SHA-256 is big-endian, MD5 little-endian).
+///
+/// **Returns:**
+/// - The padded message, a multiple of 64 bytes long.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let blocks = pad(b"abc", true);
+/// ```
+fn pad(message: &[u8], big_endian: bool) -> Vec<u8> {
+    let bit_length = (message.len() as u64).wrapping_mul(8);
+    let mut padded = message.to_vec();
+    padded.push(0x80);
+    while padded.len() % 64 != 56 {
+        padded.push(0);
+    }
+    padded.extend(if big_endian { bit_length.to_be_bytes() } else { bit_length.to_le_bytes() });
+    padded
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn md5_matches_rfc_1321_vectors() {
+        let vectors = [
+            ("", "d41d8cd98f00b204e9800998ecf8427e"),
+            ("a", "0cc175b9c0f1b6a831c399e269772661"),
+            ("abc", "900150983cd24fb0d6963f7d28e17f72"),
+            ("message digest", "f96b697d7cb7938d525a2f31aaf161d0"),
+            ("abcdefghijklmnopqrstuvwxyz", "c3fcd3d76192e4007dfb496cca67e13b"),
+            (
+                "12345678901234567890123456789012345678901234567890123456789012345678901234567890",
+                "57edf4a22be3c955ac49da2e2107b67a",
+            ),
+        ];
+        for (message, digest) in vectors {
+            assert_eq!(hex(&md5(message.as_bytes())), digest, "{message}");
+        }
+    }
+
+    #[test]
+    fn sha256_matches_fips_180_vectors() {
+        let vectors = [
+            ("", "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"),
+            ("abc", "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"),
+            (
+                "abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq",
+                "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1",
+            ),
+        ];
+        for (message, digest) in vectors {
+            assert_eq!(hex(&sha256(message.as_bytes())), digest, "{message}");
+        }
+        assert_eq!(
+            hex(&sha256(&vec![b'a'; 1_000_000])),
+            "cdc76e5c9914fb9281a1c7e284d73e67f1809a48a497200e046d39ccc7112cd0"
+        );
+    }
+
+    #[test]
+    fn padding_is_right_at_block_boundaries() {
+        let vectors = [
+            (55, "9f4390f8d30c2dd92ec9f095b65e2b9ae9b0a925a5258e241c9f1e910f734318", "ef1772b6dff9a122358552954ad0df65"),
+            (56, "b35439a4ac6f0948b6d6f9e3c6af0f5f590ce20f1bde7090ef7970686ec6738a", "3b0c8ac703f828b04c6c197006d17218"),
+            (63, "7d3e74a05d7db15bce4ad9ec0658ea98e3f06eeecf16b4c6fff2da457ddc2f34", "b06521f39153d618550606be297466d5"),
+            (64, "ffe054fe7ae0cb6dc65c3af9b61d5209f439851db43d0ba5997337df154668eb", "014842d480b571495a4a0363793f7367"),
+            (119, "31eba51c313a5c08226adf18d4a359cfdfd8d2e816b13f4af952f7ea6584dcfb", "8a7bd0732ed6a28ce75f6dabc90e1613"),
+        ];
+        for (length, sha256_digest, md5_digest) in vectors {
+            let message = vec![b'a'; length];
+            assert_eq!(hex(&sha256(&message)), sha256_digest, "{length}");
+            assert_eq!(hex(&md5(&message)), md5_digest, "{length}");
+        }
+    }
+
+    #[test]
+    fn hmac_sha256_matches_rfc_4231_vectors() {
+        assert_eq!(
+            hex(&hmac_sha256(&[0x0b; 20], b"Hi There")),
+            "b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7"
+        );
+        assert_eq!(
+            hex(&hmac_sha256(b"Jefe", b"what do ya want for nothing?")),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+        assert_eq!(
+            hex(&hmac_sha256(&[0xaa; 131], b"Test Using Larger Than Block-Size Key - Hash Key First")),
+            "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54"
+        );
+    }
+}
@@ -0,0 +1,351 @@
+
+// ============================================================
+//  DAEGONICA SOFTWARE — headers.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: Headers
+//!
+//! **Purpose:**
+//! Provides an ordered, case-insensitive HTTP header collection shared by requests and responses.
+//!
+//! **Context:**
+//! - Used by the request parser and the proxy when reading and re-emitting header blocks.
+//!
+//! **Responsibilities:**
+//! - Stores header name/value pairs in the order they were received.
+//! - Performs case-insensitive lookups and comma-separated token checks.
+//! - Masks sensitive values in its `Debug` output, so dumped requests and responses never show them.
+//! - Does NOT validate header semantics or enforce size limits.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+use std::{
+    fmt,
+    io::{self, Write},
+};
+
+use crate::redact;
+
+
+/// # Headers
+///
+/// **Summary:**
+/// Ordered list of HTTP header fields with case-insensitive access.
+///
+/// **Fields:**
+/// - `entries`: Header name/value pairs in wire order.
+///
+/// **Usage Example:**
+/// ```rust
+/// # use server::headers::Headers;
+/// let mut headers = Headers::new();
+/// headers.append("Content-Type", "text/html");
+/// assert_eq!(headers.get("content-type"), Some("text/html"));
+/// ```
+#[derive(Clone, Default)]
+pub struct Headers {
+    entries: Vec<(String, String)>,
+}
+
+
+impl Headers {
+
+    /// # new
+    ///
+    /// **Purpose:**
+    /// Creates an empty header collection.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - An empty `Headers` instance.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::headers::Headers;
+    /// let headers = Headers::new();
+    /// ```
+    pub fn new() -> Headers {
+        Headers::default()
+    }
+
+    /// # get
+    ///
+    /// **Purpose:**
+    /// Returns the value of the first header matching `name`, ignoring case.
+    ///
+    /// **Parameters:**
+    /// - `name`: Header name to look up.
+    ///
+    /// **Returns:**
+    /// - `Some(value)` if the header is present, otherwise `None`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # let mut headers = server::headers::Headers::new();
+    /// # headers.append("Host", "example.com");
+    /// let host = headers.get("Host");
+    /// # assert_eq!(host, Some("example.com"));
+    /// ```
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// # get_all
+    ///
+    /// **Purpose:**
+    /// Returns every value recorded for `name`, in wire order.
+    ///
+    /// **Parameters:**
+    /// - `name`: Header name to look up.
+    ///
+    /// **Returns:**
+    /// - A vector of matching values (empty if the header is absent).
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # let mut headers = server::headers::Headers::new();
+    /// # headers.append("Cookie", "a=1");
+    /// # headers.append("Cookie", "b=2");
+    /// let cookies = headers.get_all("Cookie");
+    /// # assert_eq!(cookies, ["a=1", "b=2"]);
+    /// ```
+    pub fn get_all(&self, name: &str) -> Vec<&str> {
+        self.entries
+            .iter()
+            .filter(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+            .collect()
+    }
+
+    /// # contains
+    ///
+    /// **Purpose:**
+    /// Checks whether at least one header named `name` is present.
+    ///
+    /// **Parameters:**
+    /// - `name`: Header name to look up.
+    ///
+    /// **Returns:**
+    /// - `true` if the header is present.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # let headers = server::headers::Headers::new();
+    /// if headers.contains("Content-Length") { /* ... */ }
+    /// ```
+    pub fn contains(&self, name: &str) -> bool {
+        self.get(name).is_some()
+    }
+
+    /// # has_token
+    ///
+    /// **Purpose:**
+    /// Checks whether any value of a comma-separated header (e.g. `Connection`) contains `token`, ignoring case.
+    ///
+    /// **Parameters:**
+    /// - `name`: Header name to inspect.
+    /// - `token`: Token to search for.
+    ///
+    /// **Returns:**
+    /// - `true` if the token appears in any value of the header.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # let mut headers = server::headers::Headers::new();
+    /// # headers.append("Connection", "keep-alive, Upgrade");
+    /// let upgrading = headers.has_token("Connection", "upgrade");
+    /// # assert!(upgrading);
+    /// ```
+    pub fn has_token(&self, name: &str, token: &str) -> bool {
+        self.get_all(name)
+            .iter()
+            .flat_map(|value| value.split(','))
+            .any(|item| item.trim().eq_ignore_ascii_case(token))
+    }
+
+    /// # append
+    ///
+    /// **Purpose:**
+    /// Adds a header field, keeping any existing fields with the same name.
+    ///
+    /// **Parameters:**
+    /// - `name`: Header name.
+    /// - `value`: Header value.
+    ///
+    /// **Returns:**
+    /// None.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # let mut headers = server::headers::Headers::new();
+    /// headers.append("Set-Cookie", "a=1");
+    /// ```
+    pub fn append(&mut self, name: impl Into<String>, value: impl Into<String>) {
+        self.entries.push((name.into(), value.into()));
+    }
+
+    /// # set
+    ///
+    /// **Purpose:**
+    /// Replaces all fields named `name` with a single field holding `value`.
+    ///
+    /// **Parameters:**
+    /// - `name`: Header name.
+    /// - `value`: Header value.
+    ///
+    /// **Returns:**
+    /// None.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # let mut headers = server::headers::Headers::new();
+    /// headers.set("Connection", "close");
+    /// ```
+    pub fn set(&mut self, name: impl Into<String>, value: impl Into<String>) {
+        let name = name.into();
+        self.remove(&name);
+        self.entries.push((name, value.into()));
+    }
+
+    /// # remove
+    ///
+    /// **Purpose:**
+    /// Removes every field named `name`, ignoring case.
+    ///
+    /// **Parameters:**
+    /// - `name`: Header name to remove.
+    ///
+    /// **Returns:**
+    /// None.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # let mut headers = server::headers::Headers::new();
+    /// # headers.append("Keep-Alive", "timeout=5");
+    /// headers.remove("Keep-Alive");
+    /// # assert!(!headers.contains("Keep-Alive"));
+    /// ```
+    pub fn remove(&mut self, name: &str) {
+        self.entries.retain(|(key, _)| !key.eq_ignore_ascii_case(name));
+    }
+
+    /// # iter
+    ///
+    /// **Purpose:**
+    /// Iterates over all header fields in wire order.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - An iterator of `(name, value)` pairs.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # let mut headers = server::headers::Headers::new();
+    /// # headers.append("Host", "example.com");
+    /// for (name, value) in headers.iter() { println!("{name}: {value}"); }
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.entries
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+
+    /// # write_to
+    ///
+    /// **Purpose:**
+    /// Serializes every header field as `Name: value\r\n` into `writer`.
+    ///
+    /// **Parameters:**
+    /// - `writer`: Destination for the serialized header block.
+    ///
+    /// **Returns:**
+    /// - `Ok(())` once all fields are written.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns any I/O error raised by `writer`.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # let mut headers = server::headers::Headers::new();
+    /// # headers.append("Host", "example.com");
+    /// # let mut stream = Vec::new();
+    /// headers.write_to(&mut stream)?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        for (name, value) in &self.entries {
+            write!(writer, "{name}: {value}\r\n")?;
+        }
+        Ok(())
+    }
+}
+
+
+impl fmt::Debug for Headers {
+
+    /// # fmt
+    ///
+    /// **Purpose:**
+    /// Formats the fields like the derived output, with the values of sensitive headers redacted.
+    ///
+    /// **Parameters:**
+    /// - `f`: Formatter.
+    ///
+    /// **Returns:**
+    /// - The formatter result.
+    ///
+    /// **Errors / Failures:**
+    /// - Propagates formatter errors.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # let request = server::request::Request::read_from(&mut &b"GET / HTTP/1.1\r\nHost: a\r\n\r\n"[..])?;
+    /// println!("{:?}", request.headers);
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let entries: Vec<(&str, &str)> = self.iter().map(|(name, value)| (name, redact::value(name, value))).collect();
+        f.debug_struct("Headers").field("entries", &entries).finish()
+    }
+}
@@ -0,0 +1,406 @@
+
+// ============================================================
+//  DAEGONICA SOFTWARE — health.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: Health
+//!
+//! **Purpose:**
+//! Answers liveness and readiness probes such as those sent by Kubernetes.
+//!
+//! **Context:**
+//! - Set with `Server::health`; probes are answered on the main listener before virtual hosts
+//!   and the proxy see the request.
+//!
+//! **Responsibilities:**
+//! - Answers the liveness path with `200` whenever the server can serve a request at all.
+//! - Answers the readiness path with `200` only while the server is not draining and every
+//!   registered check passes, and with `503` naming what failed otherwise.
+//! - Does NOT cache check results or run checks in the background; each readiness probe runs
+//!   them all, so they should be quick.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+use std::{
+    fmt::Write as _,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+};
+
+use crate::{request::Request, response::Response};
+
+
+/// # Check
+///
+/// **Summary:**
+/// A readiness check: `Ok(())` when the dependency is usable, else why it is not.
+pub type Check = Arc<dyn Fn() -> Result<(), String> + Send + Sync>;
+
+
+/// # Health
+///
+/// **Summary:**
+/// Probe endpoints and readiness state. Clones share the draining flag, so a clone taken before
+/// `Server::run` can take the server out of rotation.
+///
+/// **Fields:**
+/// - `liveness`: Path of the liveness probe.
+/// - `readiness`: Path of the readiness probe.
+/// - `checks`: Named readiness checks, run in registration order.
+/// - `draining`: Set once the server should stop receiving new traffic.
+///
+/// **Usage Example:**
+/// ```rust
+/// # use server::{health::Health, server::Server};
+/// # use std::net::TcpStream;
+/// let health = Health::new().check("upstream", || TcpStream::connect("10.0.0.5:8080").map(drop).map_err(|e| e.to_string()));
+/// let server = Server::new("0.0.0.0:80").health(health.clone());
+/// // later, before stopping: health.drain();
+/// ```
+#[derive(Clone)]
+pub struct Health {
+    liveness: String,
+    readiness: String,
+    checks: Vec<(String, Check)>,
+    draining: Arc<AtomicBool>,
+}
+
+
+impl Default for Health {
+
+    /// # default
+    ///
+    /// **Purpose:**
+    /// Creates probes at `/healthz` and `/readyz` with no readiness checks.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - A `Health` that is ready.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::health::Health;
+    /// let health = Health::default();
+    /// ```
+    fn default() -> Health {
+        Health {
+            liveness: "/healthz".to_string(),
+            readiness: "/readyz".to_string(),
+            checks: Vec::new(),
+            draining: Arc::default(),
+        }
+    }
+}
+
+
+impl Health {
+
+    /// # new
+    ///
+    /// **Purpose:**
+    /// Creates probes at the conventional `/healthz` and `/readyz` paths.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - A new `Health`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::health::Health;
+    /// let health = Health::new();
+    /// ```
+    pub fn new() -> Health {
+        Health::default()
+    }
+
+    /// # liveness
+    ///
+    /// **Purpose:**
+    /// Moves the liveness probe to another path.
+    ///
+    /// **Parameters:**
+    /// - `path`: Request path such as `/livez`.
+    ///
+    /// **Returns:**
+    /// - The updated `Health`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::health::Health;
+    /// let health = Health::new().liveness("/livez");
+    /// ```
+    pub fn liveness(mut self, path: &str) -> Health {
+        self.liveness = path.to_string();
+        self
+    }
+
+    /// # readiness
+    ///
+    /// **Purpose:**
+    /// Moves the readiness probe to another path.
+    ///
+    /// **Parameters:**
+    /// - `path`: Request path such as `/ready`.
+    ///
+    /// **Returns:**
+    /// - The updated `Health`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::health::Health;
+    /// let health = Health::new().readiness("/ready");
+    /// ```
+    pub fn readiness(mut self, path: &str) -> Health {
+        self.readiness = path.to_string();
+        self
+    }
+
+    /// # check
+    ///
+    /// **Purpose:**
+    /// Registers a check that must pass for the server to report ready.
+    ///
+    /// **Parameters:**
+    /// - `name`: Label shown in the readiness body.
+    /// - `check`: Function returning `Ok(())` when healthy, or a reason when not.
+    ///
+    /// **Returns:**
+    /// - The updated `Health`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::health::Health;
+    /// # fn disk_free() -> u64 { 2 << 30 }
+    /// let health = Health::new().check("disk", || if disk_free() > 1 << 30 { Ok(()) } else { Err("disk nearly full".into()) });
+    /// # assert!(health.is_ready());
+    /// ```
+    pub fn check(mut self, name: &str, check: impl Fn() -> Result<(), String> + Send + Sync + 'static) -> Health {
+        self.checks.push((name.to_string(), Arc::new(check)));
+        self
+    }
+
+    /// # drain
+    ///
+    /// **Purpose:**
+    /// Marks the server as draining, so readiness fails and load balancers stop sending traffic
+    /// while in-flight requests finish.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// None.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # let health = server::health::Health::new();
+    /// health.drain();
+    /// # assert!(health.is_draining());
+    /// ```
+    pub fn drain(&self) {
+        self.draining.store(true, Ordering::Release);
+    }
+
+    /// # is_draining
+    ///
+    /// **Purpose:**
+    /// Checks whether `drain` has been called.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - `true` once the server is draining.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # let health = server::health::Health::new();
+    /// if health.is_draining() { println!("draining"); }
+    /// ```
+    pub fn is_draining(&self) -> bool {
+        self.draining.load(Ordering::Acquire)
+    }
+
+    /// # is_ready
+    ///
+    /// **Purpose:**
+    /// Checks readiness without a request: not draining and every check passing.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - `true` if the readiness probe would answer `200`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # let health = server::health::Health::new();
+    /// if !health.is_ready() { eprintln!("not ready"); }
+    /// ```
+    pub fn is_ready(&self) -> bool {
+        !self.is_draining() && self.checks.iter().all(|(_, check)| check().is_ok())
+    }
+
+    /// # respond
+    ///
+    /// **Purpose:**
+    /// Answers a request if it is a `GET` or `HEAD` of a probe path.
+    ///
+    /// **Parameters:**
+    /// - `request`: Request being served.
+    ///
+    /// **Returns:**
+    /// - `Some(response)` for a probe: liveness always `200 OK`; readiness `200 OK`, or
+    ///   `503 Service Unavailable` listing the draining state and each check's result.
+    /// - `None` for any other request.
+    ///
+    /// **Errors / Failures:**
+    /// None. A failing check only fails the probe.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// if let Some(response) = health.respond(&request) { return response; }
+    /// ```
+    pub(crate) fn respond(&self, request: &Request) -> Option<Response> {
+        if request.method != "GET" && request.method != "HEAD" {
+            return None;
+        }
+        if request.path() == self.liveness {
+            return Some(probe(200, "OK", "ok\n".to_string()));
+        }
+        if request.path() != self.readiness {
+            return None;
+        }
+
+        let mut ready = !self.is_draining();
+        let mut body = String::new();
+        if !ready {
+            body.push_str("draining\n");
+        }
+        for (name, check) in &self.checks {
+            match check() {
+                Ok(()) => {
+                    let _ = writeln!(body, "{name}: ok");
+                }
+                Err(reason) => {
+                    ready = false;
+                    let _ = writeln!(body, "{name}: {reason}");
+                }
+            }
+        }
+        Some(if ready {
+            body.push_str("ready\n");
+            probe(200, "OK", body)
+        } else {
+            probe(503, "Service Unavailable", body)
+        })
+    }
+}
+
+
+/// # probe
+///
+/// **Purpose:**
+/// Builds a probe answer.
+///
+/// **Parameters:**
+/// - `status`: Status code.
+/// - `reason`: Reason phrase.
+/// - `body`: Plain-text body.
+///
+/// **Returns:**
+/// - The response, marked uncacheable.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let response = probe(200, "OK", "ok\n".to_string());
+/// ```
+fn probe(status: u16, reason: &str, body: String) -> Response {
+    Response::new(status, reason)
+        .with_header("Content-Type", "text/plain; charset=utf-8")
+        .with_header("Cache-Control", "no-store")
+        .with_body(body)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get(health: &Health, path: &str) -> Option<Response> {
+        health.respond(&Request::read_from(&mut format!("GET {path} HTTP/1.1\r\nHost: a\r\n\r\n").as_bytes()).unwrap())
+    }
+
+    #[test]
+    fn liveness_answers_even_while_draining() {
+        let health = Health::new();
+        health.drain();
+        assert_eq!(get(&health, "/healthz").unwrap().status, 200);
+        assert_eq!(get(&health, "/readyz").unwrap().status, 503);
+        assert!(get(&health, "/other").is_none());
+    }
+
+    #[test]
+    fn failing_checks_make_the_server_unready() {
+        let health = Health::new().check("db", || Ok(())).check("upstream", || Err("refused".to_string()));
+        let probe = get(&health, "/readyz").unwrap();
+        assert_eq!(probe.status, 503);
+        assert_eq!(String::from_utf8_lossy(&probe.body), "db: ok\nupstream: refused\n");
+        assert!(!health.is_ready());
+    }
+
+    #[test]
+    fn probe_paths_can_be_moved() {
+        let health = Health::new().liveness("/live").readiness("/ready");
+        assert_eq!(get(&health, "/ready").unwrap().status, 200);
+        assert_eq!(get(&health, "/live").unwrap().status, 200);
+        assert!(get(&health, "/healthz").is_none());
+    }
+
+    #[test]
+    fn clones_share_the_draining_flag() {
+        let health = Health::new();
+        health.clone().drain();
+        assert!(health.is_draining());
+    }
+}
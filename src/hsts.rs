@@ -0,0 +1,173 @@
+
+// ============================================================
+//  DAEGONICA SOFTWARE — hsts.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: Hsts
+//!
+//! **Purpose:**
+//! Configures the `Strict-Transport-Security` header that tells browsers to use HTTPS only.
+//!
+//! **Context:**
+//! - Set on the server with `Server::hsts`; the server adds the header only to responses for
+//!   requests that arrived over HTTPS.
+//! - The server itself does not terminate TLS, so HTTPS is recognised from the
+//!   `Forwarded: proto=https` or `X-Forwarded-Proto: https` header of a TLS-terminating proxy
+//!   listed with `Server::trust_proxy`.
+//!
+//! **Responsibilities:**
+//! - Holds the `max-age`, `includeSubDomains`, and `preload` settings.
+//! - Renders the header value.
+//! - Does NOT send the header over plain HTTP, where browsers must ignore it and a stray
+//!   value only hides misconfiguration.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+
+/// # PRELOAD_MIN_AGE
+///
+/// **Summary:**
+/// Smallest `max-age` (one year) accepted by browser preload lists.
+pub const PRELOAD_MIN_AGE: u64 = 31_536_000;
+
+
+/// # Hsts
+///
+/// **Summary:**
+/// `Strict-Transport-Security` policy.
+///
+/// **Fields:**
+/// - `max_age`: Seconds browsers remember to use HTTPS.
+/// - `include_subdomains`: Apply the policy to every subdomain too.
+/// - `preload`: Ask for inclusion in browser preload lists.
+///
+/// **Usage Example:**
+/// ```rust
+/// # use server::{hsts::{Hsts, PRELOAD_MIN_AGE}, server::Server};
+/// let server = Server::new("0.0.0.0:8080").hsts(Hsts::new(PRELOAD_MIN_AGE).include_subdomains());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Hsts {
+    max_age: u64,
+    include_subdomains: bool,
+    preload: bool,
+}
+
+
+impl Hsts {
+
+    /// # new
+    ///
+    /// **Purpose:**
+    /// Creates a policy covering only the responding host.
+    ///
+    /// **Parameters:**
+    /// - `max_age`: Seconds browsers remember to use HTTPS; `0` tells them to forget the policy.
+    ///
+    /// **Returns:**
+    /// - A new `Hsts`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::hsts::Hsts;
+    /// let hsts = Hsts::new(86_400);
+    /// ```
+    pub fn new(max_age: u64) -> Hsts {
+        Hsts {
+            max_age,
+            include_subdomains: false,
+            preload: false,
+        }
+    }
+
+    /// # include_subdomains
+    ///
+    /// **Purpose:**
+    /// Extends the policy to every subdomain of the responding host.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - The updated `Hsts`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::hsts::{Hsts, PRELOAD_MIN_AGE};
+    /// let hsts = Hsts::new(PRELOAD_MIN_AGE).include_subdomains();
+    /// ```
+    pub fn include_subdomains(mut self) -> Hsts {
+        self.include_subdomains = true;
+        self
+    }
+
+    /// # preload
+    ///
+    /// **Purpose:**
+    /// Adds the `preload` flag. Preload lists also require `includeSubDomains` and a `max-age` of
+    /// at least `PRELOAD_MIN_AGE`, so both are raised to those minimums.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - The updated `Hsts`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::hsts::{Hsts, PRELOAD_MIN_AGE};
+    /// let hsts = Hsts::new(PRELOAD_MIN_AGE * 2).preload();
+    /// ```
+    pub fn preload(mut self) -> Hsts {
+        self.preload = true;
+        self.include_subdomains = true;
+        self.max_age = self.max_age.max(PRELOAD_MIN_AGE);
+        self
+    }
+
+    /// # header_value
+    ///
+    /// **Purpose:**
+    /// Renders the `Strict-Transport-Security` value.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - The value, e.g. `max-age=31536000; includeSubDomains; preload`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::hsts::Hsts;
+    /// assert_eq!(Hsts::new(60).header_value(), "max-age=60");
+    /// ```
+    pub fn header_value(&self) -> String {
+        let mut value = format!("max-age={}", self.max_age);
+        if self.include_subdomains {
+            value.push_str("; includeSubDomains");
+        }
+        if self.preload {
+            value.push_str("; preload");
+        }
+        value
+    }
+}
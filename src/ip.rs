@@ -0,0 +1,513 @@
+
+// ============================================================
+//  DAEGONICA SOFTWARE — ip.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: IP
+//!
+//! **Purpose:**
+//! Matches client addresses against CIDR ranges and works out which address a request came from.
+//!
+//! **Context:**
+//! - Used by the server to set each request's effective client and scheme, and by
+//!   address-based middleware such as `IpAcl`.
+//!
+//! **Responsibilities:**
+//! - Parses IPv4 and IPv6 ranges (`10.0.0.0/8`, `2001:db8::/32`, or a bare address).
+//! - Treats IPv4-mapped IPv6 addresses (`::ffff:10.1.2.3`) as their IPv4 form.
+//! - Resolves the client address and protocol from RFC 7239 `Forwarded`, or from
+//!   `X-Forwarded-For` and `X-Forwarded-Proto` when it is absent, trusting only listed proxies.
+//! - Does NOT believe forwarding headers from peers outside the trusted ranges.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+use std::{
+    io,
+    net::{IpAddr, SocketAddr},
+};
+
+use crate::request::Request;
+
+
+/// # Cidr
+///
+/// **Summary:**
+/// Address range given as a network address and prefix length.
+///
+/// **Fields:**
+/// - `network`: Network address, with host bits cleared.
+/// - `prefix`: Number of leading bits that must match.
+///
+/// **Usage Example:**
+/// ```rust
+/// # use server::ip::Cidr;
+/// let office = Cidr::parse("192.168.0.0/16")?;
+/// assert!(office.contains("192.168.4.20".parse()?));
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cidr {
+    network: IpAddr,
+    prefix: u8,
+}
+
+
+impl Cidr {
+
+    /// # parse
+    ///
+    /// **Purpose:**
+    /// Parses a range in `address/prefix` form; a bare address is a single-address range.
+    ///
+    /// **Parameters:**
+    /// - `text`: Range such as `10.0.0.0/8`, `::1`, or `fd00::/8`.
+    ///
+    /// **Returns:**
+    /// - The parsed `Cidr`; host bits after the prefix are ignored.
+    ///
+    /// **Errors / Failures:**
+    /// - `InvalidInput` if the address is malformed or the prefix is too long for it.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::ip::Cidr;
+    /// let loopback = Cidr::parse("127.0.0.0/8")?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn parse(text: &str) -> io::Result<Cidr> {
+        let malformed = || io::Error::new(io::ErrorKind::InvalidInput, format!("invalid address range: {text}"));
+        let (address, prefix) = match text.trim().split_once('/') {
+            Some((address, prefix)) => (address, Some(prefix)),
+            None => (text.trim(), None),
+        };
+        let address = canonical(address.parse().map_err(|_| malformed())?);
+        let width = bit_width(address);
+        let prefix = match prefix {
+            Some(prefix) => prefix.parse::<u8>().ok().filter(|prefix| *prefix <= width).ok_or_else(malformed)?,
+            None => width,
+        };
+        Ok(Cidr { network: mask(address, prefix), prefix })
+    }
+
+    /// # contains
+    ///
+    /// **Purpose:**
+    /// Checks whether an address falls inside the range.
+    ///
+    /// **Parameters:**
+    /// - `address`: Address to test; IPv4-mapped IPv6 addresses are compared as IPv4.
+    ///
+    /// **Returns:**
+    /// - `true` if the address is in the range; IPv4 and IPv6 never match each other.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::ip::Cidr;
+    /// let inside = Cidr::parse("10.0.0.0/8")?.contains("10.9.8.7".parse()?);
+    /// # assert!(inside);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn contains(&self, address: IpAddr) -> bool {
+        let address = canonical(address);
+        bit_width(address) == bit_width(self.network) && mask(address, self.prefix) == self.network
+    }
+}
+
+
+/// # Hop
+///
+/// **Summary:**
+/// One proxy hop recorded in the forwarding headers.
+///
+/// **Fields:**
+/// - `address`: Address the proxy received the request from, or `None` if unknown or obfuscated.
+/// - `proto`: Protocol the proxy received the request over, if recorded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Hop {
+    address: Option<IpAddr>,
+    proto: Option<String>,
+}
+
+
+/// # client_ip
+///
+/// **Purpose:**
+/// Works out the address of the client behind a request. When the connected peer is a trusted
+/// proxy, the forwarding hops (`Forwarded`, else `X-Forwarded-For`) are walked from the right,
+/// skipping further trusted proxies, and the first untrusted hop is the client. With no ranges
+/// of its own, the address the server already resolved (`request.client`) is used.
+///
+/// **Parameters:**
+/// - `request`: Request whose origin is wanted.
+/// - `trusted`: Ranges of proxies whose forwarding entries are believed.
+///
+/// **Returns:**
+/// - The client address, or `None` if the request has no peer address.
+///
+/// **Errors / Failures:**
+/// - An unparseable or unknown forwarded entry stops the walk at the last proxy that was trusted.
+///
+/// **Examples:**
+/// ```rust
+/// # use server::{ip::{self, Cidr}, request::Request};
+/// # let request = &Request::read_from(&mut &b"GET / HTTP/1.1\r\nX-Forwarded-For: 203.0.113.9\r\n\r\n"[..])?;
+/// let ip = ip::client_ip(request, &[Cidr::parse("10.0.0.0/8")?]);
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub fn client_ip(request: &Request, trusted: &[Cidr]) -> Option<IpAddr> {
+    if trusted.is_empty()
+        && let Some(client) = request.client
+    {
+        return Some(client);
+    }
+    walk(request, trusted).map(|(client, _)| client)
+}
+
+
+/// # client_proto
+///
+/// **Purpose:**
+/// Works out the protocol the client used to reach the first trusted proxy: the `proto` of the
+/// last `Forwarded` element a trusted proxy added, else the first `X-Forwarded-Proto` value.
+///
+/// **Parameters:**
+/// - `request`: Request whose origin is wanted.
+/// - `trusted`: Ranges of proxies whose forwarding entries are believed.
+///
+/// **Returns:**
+/// - The protocol in lowercase (e.g. `https`), or `None` if the peer is not trusted or no
+///   protocol was forwarded.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust
+/// # use server::{ip::{self, Cidr}, request::Request};
+/// # let request = &Request::read_from(&mut &b"GET / HTTP/1.1\r\nX-Forwarded-Proto: https\r\n\r\n"[..])?;
+/// # let trusted = vec![Cidr::parse("10.0.0.0/8")?];
+/// let secure = ip::client_proto(request, &trusted).as_deref() == Some("https");
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub fn client_proto(request: &Request, trusted: &[Cidr]) -> Option<String> {
+    let peer = canonical(request.peer?.ip());
+    if !trusted.iter().any(|range| range.contains(peer)) {
+        return None;
+    }
+    let (_, hop) = walk(request, trusted)?;
+    hop.and_then(|hop| hop.proto)
+        .or_else(|| {
+            request
+                .headers
+                .get("X-Forwarded-Proto")
+                .and_then(|value| value.split(',').next())
+                .map(|proto| proto.trim().to_string())
+        })
+        .filter(|proto| !proto.is_empty())
+        .map(|proto| proto.to_ascii_lowercase())
+}
+
+
+/// # walk
+///
+/// **Purpose:**
+/// Walks the forwarding hops from the right while each address reached is a trusted proxy.
+///
+/// **Parameters:**
+/// - `request`: Request whose origin is wanted.
+/// - `trusted`: Ranges of trusted proxies.
+///
+/// **Returns:**
+/// - The client address and the last hop a trusted proxy reported (`None` when the peer itself
+///   is the client), or `None` if the request has no peer address.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let (client, hop) = walk(request, trusted)?;
+/// ```
+fn walk(request: &Request, trusted: &[Cidr]) -> Option<(IpAddr, Option<Hop>)> {
+    let mut client = canonical(request.peer?.ip());
+    let mut reported = None;
+    for hop in hops(request).into_iter().rev() {
+        if !trusted.iter().any(|range| range.contains(client)) {
+            break;
+        }
+        let address = hop.address;
+        reported = Some(hop);
+        match address {
+            Some(address) => client = address,
+            None => break,
+        }
+    }
+    Some((client, reported))
+}
+
+
+/// # hops
+///
+/// **Purpose:**
+/// Lists the forwarding hops of a request, oldest first. `Forwarded` takes precedence over
+/// `X-Forwarded-For`, so proxies that send both are not counted twice.
+///
+/// **Parameters:**
+/// - `request`: Request with forwarding headers.
+///
+/// **Returns:**
+/// - One `Hop` per `Forwarded` element or `X-Forwarded-For` entry.
+///
+/// **Errors / Failures:**
+/// None. Malformed entries become hops without an address.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let nearest = hops(request).pop();
+/// ```
+fn hops(request: &Request) -> Vec<Hop> {
+    let forwarded = request.headers.get_all("Forwarded");
+    if !forwarded.is_empty() {
+        return forwarded
+            .iter()
+            .flat_map(|value| value.split(','))
+            .map(parse_element)
+            .collect();
+    }
+    request
+        .headers
+        .get_all("X-Forwarded-For")
+        .iter()
+        .flat_map(|value| value.split(','))
+        .map(|hop| Hop {
+            address: parse_hop(hop),
+            proto: None,
+        })
+        .collect()
+}
+
+
+/// # parse_element
+///
+/// **Purpose:**
+/// Parses one `Forwarded` element, such as `for=192.0.2.60;proto=https;by=203.0.113.43`.
+///
+/// **Parameters:**
+/// - `element`: Semicolon-separated `name=value` pairs; values may be quoted.
+///
+/// **Returns:**
+/// - The hop's `for` address and `proto`, where present.
+///
+/// **Errors / Failures:**
+/// None. `unknown`, obfuscated (`_hidden`), and malformed `for` values give no address.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let hop = parse_element(r#"for="[2001:db8::1]:4711";proto=https"#);
+/// ```
+fn parse_element(element: &str) -> Hop {
+    let mut hop = Hop {
+        address: None,
+        proto: None,
+    };
+    for pair in element.split(';') {
+        let Some((name, value)) = pair.split_once('=') else {
+            continue;
+        };
+        let value = value.trim().trim_matches('"');
+        match name.trim().to_ascii_lowercase().as_str() {
+            "for" => hop.address = parse_hop(value),
+            "proto" => hop.proto = Some(value.to_string()),
+            _ => {}
+        }
+    }
+    hop
+}
+
+
+/// # parse_hop
+///
+/// **Purpose:**
+/// Parses one forwarded address, which some proxies write with a port or in brackets.
+///
+/// **Parameters:**
+/// - `hop`: Entry such as `203.0.113.9`, `203.0.113.9:5120`, `[2001:db8::1]`, or `[2001:db8::1]:443`.
+///
+/// **Returns:**
+/// - The address, or `None` if the entry is not an address.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let address = parse_hop(" 203.0.113.9:5120");
+/// ```
+fn parse_hop(hop: &str) -> Option<IpAddr> {
+    let hop = hop.trim();
+    let bare = hop.strip_prefix('[').and_then(|hop| hop.strip_suffix(']')).unwrap_or(hop);
+    bare.parse::<IpAddr>()
+        .or_else(|_| hop.parse::<SocketAddr>().map(|address| address.ip()))
+        .ok()
+        .map(canonical)
+}
+
+
+/// # canonical
+///
+/// **Purpose:**
+/// Converts IPv4-mapped IPv6 addresses to plain IPv4.
+///
+/// **Parameters:**
+/// - `address`: Address to normalise.
+///
+/// **Returns:**
+/// - The IPv4 address for `::ffff:a.b.c.d`, otherwise `address` unchanged.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let address = canonical("::ffff:10.0.0.1".parse()?);
+/// ```
+fn canonical(address: IpAddr) -> IpAddr {
+    address.to_canonical()
+}
+
+
+/// # bit_width
+///
+/// **Purpose:**
+/// Gives the number of bits in an address of this family.
+///
+/// **Parameters:**
+/// - `address`: Any address.
+///
+/// **Returns:**
+/// - `32` for IPv4, `128` for IPv6.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let width = bit_width(address);
+/// ```
+fn bit_width(address: IpAddr) -> u8 {
+    match address {
+        IpAddr::V4(_) => 32,
+        IpAddr::V6(_) => 128,
+    }
+}
+
+
+/// # mask
+///
+/// **Purpose:**
+/// Clears every bit after the prefix.
+///
+/// **Parameters:**
+/// - `address`: Address to mask.
+/// - `prefix`: Number of leading bits to keep.
+///
+/// **Returns:**
+/// - The network address of `address` at that prefix length.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let network = mask("10.1.2.3".parse()?, 8);
+/// ```
+fn mask(address: IpAddr, prefix: u8) -> IpAddr {
+    match address {
+        IpAddr::V4(v4) => {
+            let bits = u32::from(v4) & u32::MAX.checked_shl(32 - u32::from(prefix)).unwrap_or(0);
+            IpAddr::V4(bits.into())
+        }
+        IpAddr::V6(v6) => {
+            let bits = u128::from(v6) & u128::MAX.checked_shl(128 - u32::from(prefix)).unwrap_or(0);
+            IpAddr::V6(bits.into())
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn from(peer: &str, headers: &str) -> Request {
+        let raw = format!("GET / HTTP/1.1\r\nHost: example.com\r\n{headers}\r\n");
+        let mut request = Request::read_from(&mut raw.as_bytes()).unwrap();
+        request.peer = Some(peer.parse().unwrap());
+        request
+    }
+
+    fn ip(text: &str) -> IpAddr {
+        text.parse().unwrap()
+    }
+
+    #[test]
+    fn ranges_match_addresses_under_their_prefix() {
+        let range = Cidr::parse("192.168.8.0/21").unwrap();
+        assert!(range.contains(ip("192.168.8.0")) && range.contains(ip("192.168.15.255")));
+        assert!(!range.contains(ip("192.168.16.0")) && !range.contains(ip("192.168.7.255")));
+        let range = Cidr::parse("2001:db8::/32").unwrap();
+        assert!(range.contains(ip("2001:db8:ffff::1")) && !range.contains(ip("2001:db9::1")));
+    }
+
+    #[test]
+    fn host_bits_are_masked_and_bare_addresses_are_single_hosts() {
+        assert_eq!(Cidr::parse("10.1.2.3/8").unwrap(), Cidr::parse("10.0.0.0/8").unwrap());
+        let host = Cidr::parse(" 10.1.2.3 ").unwrap();
+        assert!(host.contains(ip("10.1.2.3")) && !host.contains(ip("10.1.2.4")));
+        assert!(Cidr::parse("0.0.0.0/0").unwrap().contains(ip("203.0.113.9")));
+        assert!(Cidr::parse("::/0").unwrap().contains(ip("2001:db8::1")));
+    }
+
+    #[test]
+    fn families_do_not_mix_except_for_mapped_addresses() {
+        assert!(!Cidr::parse("0.0.0.0/0").unwrap().contains(ip("2001:db8::1")));
+        assert!(!Cidr::parse("::/0").unwrap().contains(ip("10.0.0.1")));
+        assert!(Cidr::parse("10.0.0.0/8").unwrap().contains(ip("::ffff:10.0.0.1")));
+        assert!(Cidr::parse("::ffff:10.0.0.0/8").unwrap().contains(ip("10.9.9.9")));
+    }
+
+    #[test]
+    fn malformed_ranges_are_refused() {
+        for text in ["10.0.0.0/33", "2001:db8::/129", "10.0.0/8", "10.0.0.0/", "10.0.0.0/-1", "example.com/8", ""] {
+            assert_eq!(Cidr::parse(text).unwrap_err().kind(), io::ErrorKind::InvalidInput, "{text}");
+        }
+    }
+
+    #[test]
+    fn forwarded_proto_is_ignored_without_trusted_proxies() {
+        let request = from("203.0.113.9:4000", "X-Forwarded-Proto: https\r\n");
+        assert_eq!(client_proto(&request, &[]), None);
+    }
+
+    #[test]
+    fn forwarded_proto_is_believed_only_from_a_trusted_peer() {
+        let trusted = [Cidr::parse("10.0.0.0/8").unwrap()];
+        let proxied = from("10.0.0.2:4000", "X-Forwarded-For: 198.51.100.7\r\nX-Forwarded-Proto: https\r\n");
+        assert_eq!(client_proto(&proxied, &trusted).as_deref(), Some("https"));
+        assert_eq!(client_ip(&proxied, &trusted), Some("198.51.100.7".parse().unwrap()));
+
+        let direct = from("203.0.113.9:4000", "X-Forwarded-For: 198.51.100.7\r\nX-Forwarded-Proto: https\r\n");
+        assert_eq!(client_proto(&direct, &trusted), None);
+        assert_eq!(client_ip(&direct, &trusted), Some("203.0.113.9".parse().unwrap()));
+    }
+}
@@ -0,0 +1,225 @@
+
+// ============================================================
+//  DAEGONICA SOFTWARE — json.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: Json
+//!
+//! **Purpose:**
+//! Reads and writes JSON bodies with serde, so API handlers do not hand-roll parsing and headers.
+//!
+//! **Context:**
+//! - Compiled only with the `json` feature.
+//! - Adds `Request::json`, `Response::json`, and the `Json<T>` extractor.
+//!
+//! **Responsibilities:**
+//! - Checks the request `Content-Type` and body size before deserializing.
+//! - Maps malformed bodies to `400`, oversized ones to `413`, wrong media types to `415`,
+//!   and serialization failures to `500`.
+//! - Sets `Content-Type: application/json` on JSON responses.
+//! - Does NOT stream bodies; requests are fully read before parsing.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+use serde::{Serialize, de::DeserializeOwned};
+
+use crate::{
+    extract::{FromRequest, bad_request},
+    request::Request,
+    response::Response,
+};
+
+
+/// # DEFAULT_BODY_LIMIT
+///
+/// **Summary:**
+/// Largest JSON request body accepted by `Request::json` and `Json<T>` (1 MiB).
+pub const DEFAULT_BODY_LIMIT: usize = 1024 * 1024;
+
+
+/// # Json
+///
+/// **Summary:**
+/// Extractor for a JSON request body of type `T`.
+///
+/// **Usage Example:**
+/// ```rust
+/// # use server::{extract::extract, json::Json, response::Response, router::Router};
+/// # type NewUser = serde_json::Value;
+/// # struct Users;
+/// # impl Users { fn insert(&self, user: NewUser) -> NewUser { user } }
+/// # #[allow(non_upper_case_globals)]
+/// # static db: Users = Users;
+/// fn create(Json(user): Json<NewUser>) -> Response {
+///     Response::json(&db.insert(user))
+/// }
+/// let router = Router::new().route("POST", "/users", extract(create));
+/// ```
+pub struct Json<T>(pub T);
+
+
+impl Request {
+
+    /// # json
+    ///
+    /// **Purpose:**
+    /// Deserializes the request body as JSON, accepting up to `DEFAULT_BODY_LIMIT` bytes.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - The deserialized value.
+    ///
+    /// **Errors / Failures:**
+    /// - Same rejections as `json_with_limit`.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{request::Request, response::Response};
+    /// # type Order = serde_json::Value;
+    /// # let mut request = Request::read_from(&mut &b"POST /orders HTTP/1.1\r\nContent-Type: application/json\r\n\r\n"[..]).unwrap();
+    /// # request.body = br#"{"item":"tea","quantity":2}"#.to_vec();
+    /// let order: Order = request.json()?;
+    /// # assert_eq!(order["quantity"], 2);
+    /// # Ok::<(), Response>(())
+    /// ```
+    pub fn json<T: DeserializeOwned>(&self) -> Result<T, Response> {
+        self.json_with_limit(DEFAULT_BODY_LIMIT)
+    }
+
+    /// # json_with_limit
+    ///
+    /// **Purpose:**
+    /// Deserializes the request body as JSON, accepting up to `limit` bytes.
+    ///
+    /// **Parameters:**
+    /// - `limit`: Largest accepted body size in bytes.
+    ///
+    /// **Returns:**
+    /// - The deserialized value.
+    ///
+    /// **Errors / Failures:**
+    /// - `415 Unsupported Media Type` if `Content-Type` is not `application/json` or `application/*+json`.
+    /// - `413 Payload Too Large` if the body exceeds `limit`.
+    /// - `400 Bad Request` if the body is not valid JSON for `T`.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{request::Request, response::Response};
+    /// # type Manifest = serde_json::Value;
+    /// # let mut request = Request::read_from(&mut &b"POST /upload HTTP/1.1\r\nContent-Type: application/json\r\n\r\n"[..]).unwrap();
+    /// # request.body = br#"{"files":[]}"#.to_vec();
+    /// let upload: Manifest = request.json_with_limit(16 * 1024 * 1024)?;
+    /// # Ok::<(), Response>(())
+    /// ```
+    pub fn json_with_limit<T: DeserializeOwned>(&self, limit: usize) -> Result<T, Response> {
+        if !self.headers.get("Content-Type").is_some_and(is_json) {
+            return Err(Response::new(415, "Unsupported Media Type")
+                .with_header("Content-Type", "text/plain; charset=utf-8")
+                .with_body("expected Content-Type: application/json"));
+        }
+        if self.body.len() > limit {
+            return Err(Response::new(413, "Payload Too Large")
+                .with_header("Content-Type", "text/plain; charset=utf-8")
+                .with_body(format!("JSON body exceeds {limit} bytes")));
+        }
+        serde_json::from_slice(&self.body).map_err(|err| bad_request(&format!("invalid JSON body: {err}")))
+    }
+}
+
+
+impl Response {
+
+    /// # json
+    ///
+    /// **Purpose:**
+    /// Builds a `200 OK` response with `value` serialized as JSON.
+    ///
+    /// **Parameters:**
+    /// - `value`: Value to serialize.
+    ///
+    /// **Returns:**
+    /// - The JSON `Response`.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns `500 Internal Server Error` if `value` cannot be serialized.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::response::Response;
+    /// # let users = vec!["ana", "ben"];
+    /// let response = Response::json(&users);
+    /// # assert_eq!(response.body, br#"["ana","ben"]"#);
+    /// ```
+    pub fn json<T: Serialize + ?Sized>(value: &T) -> Response {
+        match serde_json::to_vec(value) {
+            Ok(body) => Response::new(200, "OK")
+                .with_header("Content-Type", "application/json")
+                .with_body(body),
+            Err(_) => Response::new(500, "Internal Server Error"),
+        }
+    }
+}
+
+
+impl<T: DeserializeOwned> FromRequest for Json<T> {
+
+    /// # from_request
+    ///
+    /// **Purpose:**
+    /// Deserializes the request body into `T`.
+    ///
+    /// **Parameters:**
+    /// - `request`: Request being handled.
+    ///
+    /// **Returns:**
+    /// - The deserialized body.
+    ///
+    /// **Errors / Failures:**
+    /// - Rejects with `400`, `413`, or `415` as described on `Request::json_with_limit`.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{extract::FromRequest, json::Json, request::Request, response::Response};
+    /// # type Order = serde_json::Value;
+    /// # let mut request = Request::read_from(&mut &b"POST /orders HTTP/1.1\r\nContent-Type: application/json\r\n\r\n"[..]).unwrap();
+    /// # request.body = br#"{"item":"tea"}"#.to_vec();
+    /// let Json(order) = Json::<Order>::from_request(&request)?;
+    /// # Ok::<(), Response>(())
+    /// ```
+    fn from_request(request: &Request) -> Result<Json<T>, Response> {
+        request.json().map(Json)
+    }
+}
+
+
+/// # is_json
+///
+/// **Purpose:**
+/// Checks whether a `Content-Type` value names a JSON media type.
+///
+/// **Parameters:**
+/// - `content_type`: Header value, possibly with parameters (e.g. `application/json; charset=utf-8`).
+///
+/// **Returns:**
+/// - `true` for `application/json` and structured `+json` types.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// assert!(is_json("application/problem+json"));
+/// ```
+fn is_json(content_type: &str) -> bool {
+    let media_type = content_type.split(';').next().unwrap_or_default().trim().to_ascii_lowercase();
+    media_type == "application/json" || (media_type.starts_with("application/") && media_type.ends_with("+json"))
+}
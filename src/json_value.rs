@@ -0,0 +1,708 @@
+
+// ============================================================
+//  DAEGONICA SOFTWARE — json_value.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: JSON Value
+//!
+//! **Purpose:**
+//! Parses JSON documents into a dynamically typed tree without optional dependencies.
+//!
+//! **Context:**
+//! - Used for token claims, key sets, and identity-provider metadata, which must work even
+//!   when the serde-based `json` feature is disabled.
+//!
+//! **Responsibilities:**
+//! - Parses RFC 8259 JSON, including `\u` escapes and surrogate pairs.
+//! - Offers typed accessors for objects, arrays, strings, numbers, and booleans.
+//! - Converts common Rust values into `JsonValue` for building contexts by hand.
+//! - Does NOT serialize values or map them onto Rust types; use the `json` feature for that.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+use std::{iter::Peekable, str::Chars};
+
+
+/// # MAX_DEPTH
+///
+/// **Summary:**
+/// Maximum nesting of arrays and objects accepted by the parser.
+pub const MAX_DEPTH: usize = 128;
+
+
+/// # JsonValue
+///
+/// **Summary:**
+/// One parsed JSON value.
+///
+/// **Variants:**
+/// - `Null`: `null`.
+/// - `Bool`: `true` or `false`.
+/// - `Number`: Any number, as `f64`.
+/// - `String`: A string with escapes decoded.
+/// - `Array`: Values in document order.
+/// - `Object`: Members in document order.
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonValue {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<JsonValue>),
+    Object(Vec<(String, JsonValue)>),
+}
+
+
+impl JsonValue {
+
+    /// # parse
+    ///
+    /// **Purpose:**
+    /// Parses a complete JSON document.
+    ///
+    /// **Parameters:**
+    /// - `text`: Document text.
+    ///
+    /// **Returns:**
+    /// - `Some(value)`, or `None` if the text is not valid JSON.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns `None` for syntax errors, trailing data, or nesting beyond [`MAX_DEPTH`].
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::json_value::JsonValue;
+    /// let value = JsonValue::parse(r#"{"sub":"ana"}"#).unwrap();
+    /// ```
+    pub fn parse(text: &str) -> Option<JsonValue> {
+        let mut chars = text.chars().peekable();
+        let value = parse_value(&mut chars, 0)?;
+        skip_whitespace(&mut chars);
+        chars.next().is_none().then_some(value)
+    }
+
+    /// # get
+    ///
+    /// **Purpose:**
+    /// Looks up an object member.
+    ///
+    /// **Parameters:**
+    /// - `key`: Member name.
+    ///
+    /// **Returns:**
+    /// - The first member with that name, or `None` if absent or not an object.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::json_value::JsonValue;
+    /// # let document = JsonValue::parse(r#"{"keys":[]}"#).unwrap();
+    /// let keys = document.get("keys");
+    /// # assert!(keys.is_some());
+    /// ```
+    pub fn get(&self, key: &str) -> Option<&JsonValue> {
+        match self {
+            JsonValue::Object(members) => members.iter().find(|(name, _)| name == key).map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    /// # as_str
+    ///
+    /// **Purpose:**
+    /// Borrows the value as a string.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - `Some(text)` for strings, otherwise `None`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::json_value::JsonValue;
+    /// # let key = JsonValue::parse(r#"{"kid":"k1"}"#).unwrap();
+    /// let kid = key.get("kid").and_then(JsonValue::as_str);
+    /// # assert_eq!(kid, Some("k1"));
+    /// ```
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            JsonValue::String(text) => Some(text),
+            _ => None,
+        }
+    }
+
+    /// # as_f64
+    ///
+    /// **Purpose:**
+    /// Reads the value as a number.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - `Some(number)` for numbers, otherwise `None`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::json_value::JsonValue;
+    /// # let claims = JsonValue::parse(r#"{"exp":1700000000}"#).unwrap();
+    /// let expires = claims.get("exp").and_then(JsonValue::as_f64);
+    /// # assert_eq!(expires, Some(1_700_000_000.0));
+    /// ```
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            JsonValue::Number(number) => Some(*number),
+            _ => None,
+        }
+    }
+
+    /// # as_bool
+    ///
+    /// **Purpose:**
+    /// Reads the value as a boolean.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - `Some(flag)` for booleans, otherwise `None`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::json_value::JsonValue;
+    /// # let claims = JsonValue::parse(r#"{"email_verified":true}"#).unwrap();
+    /// let verified = claims.get("email_verified").and_then(JsonValue::as_bool);
+    /// # assert_eq!(verified, Some(true));
+    /// ```
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            JsonValue::Bool(flag) => Some(*flag),
+            _ => None,
+        }
+    }
+
+    /// # as_array
+    ///
+    /// **Purpose:**
+    /// Borrows the value as an array.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - `Some(items)` for arrays, otherwise `None`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::json_value::JsonValue;
+    /// # let document = JsonValue::parse(r#"{"keys":[{"kid":"k1"}]}"#).unwrap();
+    /// let keys = document.get("keys").and_then(JsonValue::as_array);
+    /// # assert_eq!(keys.map(<[JsonValue]>::len), Some(1));
+    /// ```
+    pub fn as_array(&self) -> Option<&[JsonValue]> {
+        match self {
+            JsonValue::Array(items) => Some(items),
+            _ => None,
+        }
+    }
+}
+
+
+impl From<&str> for JsonValue {
+
+    /// # from
+    ///
+    /// **Purpose:**
+    /// Wraps a string, e.g. when building a template context.
+    ///
+    /// **Parameters:**
+    /// - `text`: Value to wrap.
+    ///
+    /// **Returns:**
+    /// - The `JsonValue`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::json_value::JsonValue;
+    /// let value = JsonValue::from("Ana");
+    /// ```
+    fn from(text: &str) -> JsonValue {
+        JsonValue::String(text.to_string())
+    }
+}
+
+
+impl From<String> for JsonValue {
+
+    /// # from
+    ///
+    /// **Purpose:**
+    /// Wraps a string, e.g. when building a template context.
+    ///
+    /// **Parameters:**
+    /// - `text`: Value to wrap.
+    ///
+    /// **Returns:**
+    /// - The `JsonValue`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::json_value::JsonValue;
+    /// let value = JsonValue::from(String::from("Ana"));
+    /// ```
+    fn from(text: String) -> JsonValue {
+        JsonValue::String(text)
+    }
+}
+
+
+impl From<bool> for JsonValue {
+
+    /// # from
+    ///
+    /// **Purpose:**
+    /// Wraps a boolean, e.g. when building a template context.
+    ///
+    /// **Parameters:**
+    /// - `flag`: Value to wrap.
+    ///
+    /// **Returns:**
+    /// - The `JsonValue`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::json_value::JsonValue;
+    /// let value = JsonValue::from(true);
+    /// ```
+    fn from(flag: bool) -> JsonValue {
+        JsonValue::Bool(flag)
+    }
+}
+
+
+impl From<f64> for JsonValue {
+
+    /// # from
+    ///
+    /// **Purpose:**
+    /// Wraps a number, e.g. when building a template context.
+    ///
+    /// **Parameters:**
+    /// - `number`: Value to wrap.
+    ///
+    /// **Returns:**
+    /// - The `JsonValue`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::json_value::JsonValue;
+    /// let value = JsonValue::from(4.5);
+    /// ```
+    fn from(number: f64) -> JsonValue {
+        JsonValue::Number(number)
+    }
+}
+
+
+impl From<i64> for JsonValue {
+
+    /// # from
+    ///
+    /// **Purpose:**
+    /// Wraps an integer as a number (exact up to 2^53), e.g. when building a template context.
+    ///
+    /// **Parameters:**
+    /// - `number`: Value to wrap.
+    ///
+    /// **Returns:**
+    /// - The `JsonValue`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::json_value::JsonValue;
+    /// let value = JsonValue::from(42i64);
+    /// ```
+    fn from(number: i64) -> JsonValue {
+        JsonValue::Number(number as f64)
+    }
+}
+
+
+impl From<Vec<JsonValue>> for JsonValue {
+
+    /// # from
+    ///
+    /// **Purpose:**
+    /// Wraps an array, e.g. when building a template context.
+    ///
+    /// **Parameters:**
+    /// - `items`: Value to wrap.
+    ///
+    /// **Returns:**
+    /// - The `JsonValue`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::json_value::JsonValue;
+    /// let value = JsonValue::from(vec![JsonValue::Null]);
+    /// ```
+    fn from(items: Vec<JsonValue>) -> JsonValue {
+        JsonValue::Array(items)
+    }
+}
+
+
+/// # parse_value
+///
+/// **Purpose:**
+/// Parses one value starting at the next non-whitespace character.
+///
+/// **Parameters:**
+/// - `chars`: Remaining input.
+/// - `depth`: Current nesting depth.
+///
+/// **Returns:**
+/// - `Some(value)`, or `None` on a syntax error.
+///
+/// **Errors / Failures:**
+/// - Returns `None` for malformed input or excessive nesting.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let value = parse_value(&mut chars, 0)?;
+/// ```
+fn parse_value(chars: &mut Peekable<Chars<'_>>, depth: usize) -> Option<JsonValue> {
+    if depth > MAX_DEPTH {
+        return None;
+    }
+    skip_whitespace(chars);
+    match *chars.peek()? {
+        '{' => {
+            chars.next();
+            let mut members = Vec::new();
+            skip_whitespace(chars);
+            if chars.next_if_eq(&'}').is_some() {
+                return Some(JsonValue::Object(members));
+            }
+            loop {
+                skip_whitespace(chars);
+                if chars.next()? != '"' {
+                    return None;
+                }
+                let name = parse_string(chars)?;
+                skip_whitespace(chars);
+                if chars.next()? != ':' {
+                    return None;
+                }
+                members.push((name, parse_value(chars, depth + 1)?));
+                skip_whitespace(chars);
+                match chars.next()? {
+                    ',' => continue,
+                    '}' => return Some(JsonValue::Object(members)),
+                    _ => return None,
+                }
+            }
+        }
+        '[' => {
+            chars.next();
+            let mut items = Vec::new();
+            skip_whitespace(chars);
+            if chars.next_if_eq(&']').is_some() {
+                return Some(JsonValue::Array(items));
+            }
+            loop {
+                items.push(parse_value(chars, depth + 1)?);
+                skip_whitespace(chars);
+                match chars.next()? {
+                    ',' => continue,
+                    ']' => return Some(JsonValue::Array(items)),
+                    _ => return None,
+                }
+            }
+        }
+        '"' => {
+            chars.next();
+            parse_string(chars).map(JsonValue::String)
+        }
+        't' => expect_word(chars, "true").then_some(JsonValue::Bool(true)),
+        'f' => expect_word(chars, "false").then_some(JsonValue::Bool(false)),
+        'n' => expect_word(chars, "null").then_some(JsonValue::Null),
+        _ => parse_number(chars),
+    }
+}
+
+
+/// # parse_string
+///
+/// **Purpose:**
+/// Parses the rest of a string whose opening quote was already consumed.
+///
+/// **Parameters:**
+/// - `chars`: Remaining input.
+///
+/// **Returns:**
+/// - `Some(text)` with escapes decoded, or `None` on a syntax error.
+///
+/// **Errors / Failures:**
+/// - Returns `None` for unterminated strings, raw control characters, or bad escapes.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let name = parse_string(&mut chars)?;
+/// ```
+fn parse_string(chars: &mut Peekable<Chars<'_>>) -> Option<String> {
+    let mut text = String::new();
+    loop {
+        match chars.next()? {
+            '"' => return Some(text),
+            '\\' => match chars.next()? {
+                '"' => text.push('"'),
+                '\\' => text.push('\\'),
+                '/' => text.push('/'),
+                'b' => text.push('\u{8}'),
+                'f' => text.push('\u{c}'),
+                'n' => text.push('\n'),
+                'r' => text.push('\r'),
+                't' => text.push('\t'),
+                'u' => {
+                    let high = parse_hex4(chars)?;
+                    let code = if (0xd800..0xdc00).contains(&high) {
+                        if chars.next()? != '\\' || chars.next()? != 'u' {
+                            return None;
+                        }
+                        let low = parse_hex4(chars)?;
+                        if !(0xdc00..0xe000).contains(&low) {
+                            return None;
+                        }
+                        0x10000 + ((high - 0xd800) << 10) + (low - 0xdc00)
+                    } else {
+                        high
+                    };
+                    text.push(char::from_u32(code)?);
+                }
+                _ => return None,
+            },
+            c if c < ' ' => return None,
+            c => text.push(c),
+        }
+    }
+}
+
+
+/// # parse_hex4
+///
+/// **Purpose:**
+/// Reads the four hex digits of a `\u` escape.
+///
+/// **Parameters:**
+/// - `chars`: Remaining input.
+///
+/// **Returns:**
+/// - The code unit, or `None` if the digits are missing or invalid.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let unit = parse_hex4(&mut chars)?;
+/// ```
+fn parse_hex4(chars: &mut Peekable<Chars<'_>>) -> Option<u32> {
+    (0..4).try_fold(0, |code, _| Some(code * 16 + chars.next()?.to_digit(16)?))
+}
+
+
+/// # parse_number
+///
+/// **Purpose:**
+/// Parses a number in JSON syntax.
+///
+/// **Parameters:**
+/// - `chars`: Remaining input.
+///
+/// **Returns:**
+/// - `Some(JsonValue::Number)`, or `None` on a syntax error.
+///
+/// **Errors / Failures:**
+/// - Returns `None` for leading zeros, a bare sign, or a missing fraction or exponent digit.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let number = parse_number(&mut chars)?;
+/// ```
+fn parse_number(chars: &mut Peekable<Chars<'_>>) -> Option<JsonValue> {
+    let mut text = String::new();
+    let digits = |chars: &mut Peekable<Chars<'_>>, text: &mut String| {
+        let start = text.len();
+        while let Some(digit) = chars.next_if(char::is_ascii_digit) {
+            text.push(digit);
+        }
+        text.len() > start
+    };
+
+    text.extend(chars.next_if_eq(&'-'));
+    if chars.next_if_eq(&'0').is_some() {
+        text.push('0');
+    } else if !digits(chars, &mut text) {
+        return None;
+    }
+    if let Some(point) = chars.next_if_eq(&'.') {
+        text.push(point);
+        if !digits(chars, &mut text) {
+            return None;
+        }
+    }
+    if let Some(exponent) = chars.next_if(|c| *c == 'e' || *c == 'E') {
+        text.push(exponent);
+        text.extend(chars.next_if(|c| *c == '+' || *c == '-'));
+        if !digits(chars, &mut text) {
+            return None;
+        }
+    }
+    text.parse().ok().map(JsonValue::Number)
+}
+
+
+/// # expect_word
+///
+/// **Purpose:**
+/// Consumes a literal keyword.
+///
+/// **Parameters:**
+/// - `chars`: Remaining input.
+/// - `word`: Expected literal (`true`, `false`, or `null`).
+///
+/// **Returns:**
+/// - `true` if the input continued with exactly that word.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// if expect_word(&mut chars, "null") { ... }
+/// ```
+fn expect_word(chars: &mut Peekable<Chars<'_>>, word: &str) -> bool {
+    word.chars().all(|expected| chars.next() == Some(expected))
+}
+
+
+/// # skip_whitespace
+///
+/// **Purpose:**
+/// Skips JSON insignificant whitespace.
+///
+/// **Parameters:**
+/// - `chars`: Remaining input.
+///
+/// **Returns:**
+/// None.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// skip_whitespace(&mut chars);
+/// ```
+fn skip_whitespace(chars: &mut Peekable<Chars<'_>>) {
+    while chars.next_if(|c| matches!(c, ' ' | '\t' | '\n' | '\r')).is_some() {}
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validate::json_string;
+
+    #[test]
+    fn documents_are_parsed() {
+        let value = JsonValue::parse(r#" {"name": "ana", "age": 41.5, "admin": false, "tags": ["a", null], "nested": {}} "#).unwrap();
+        assert_eq!(value.get("name").and_then(JsonValue::as_str), Some("ana"));
+        assert_eq!(value.get("age").and_then(JsonValue::as_f64), Some(41.5));
+        assert_eq!(value.get("admin").and_then(JsonValue::as_bool), Some(false));
+        assert_eq!(value.get("tags").and_then(JsonValue::as_array), Some(&[JsonValue::from("a"), JsonValue::Null][..]));
+        assert_eq!(value.get("nested"), Some(&JsonValue::Object(Vec::new())));
+        assert_eq!(value.get("missing"), None);
+    }
+
+    #[test]
+    fn numbers_follow_the_json_grammar() {
+        for (text, number) in [("0", 0.0), ("-0", -0.0), ("12", 12.0), ("-1.25", -1.25), ("1e3", 1000.0), ("2.5E-1", 0.25)] {
+            assert_eq!(JsonValue::parse(text), Some(JsonValue::Number(number)), "{text}");
+        }
+        for text in ["01", "+1", "1.", ".5", "1e", "-", "0x10", "NaN", "Infinity"] {
+            assert_eq!(JsonValue::parse(text), None, "{text}");
+        }
+    }
+
+    #[test]
+    fn strings_decode_escapes_and_surrogate_pairs() {
+        let value = JsonValue::parse(r#""a\"b\\c\/d\n\té😀""#).unwrap();
+        assert_eq!(value.as_str(), Some("a\"b\\c/d\n\té😀"));
+        for text in [r#""\ud83d""#, r#""\ud83dx""#, r#""\ude00""#, r#""\x""#, "\"a\nb\"", r#""open"#] {
+            assert_eq!(JsonValue::parse(text), None, "{text}");
+        }
+    }
+
+    #[test]
+    fn malformed_documents_are_refused() {
+        for text in ["", "{", "[1,]", "[1 2]", r#"{"a" 1}"#, r#"{"a":1,}"#, "{a:1}", "tru", "nul", "[] []", "'a'"] {
+            assert_eq!(JsonValue::parse(text), None, "{text}");
+        }
+    }
+
+    #[test]
+    fn nesting_is_capped_at_max_depth() {
+        let nested = |depth: usize| format!("{}{}", "[".repeat(depth), "]".repeat(depth));
+        assert!(JsonValue::parse(&nested(MAX_DEPTH + 1)).is_some());
+        assert_eq!(JsonValue::parse(&nested(MAX_DEPTH + 2)), None);
+    }
+
+    #[test]
+    fn encoded_strings_parse_back_unchanged() {
+        let text = "quote \" backslash \\ newline \n tab \t bell \u{7} é";
+        assert_eq!(JsonValue::parse(&json_string(text)).unwrap().as_str(), Some(text));
+        assert_eq!(json_string("\u{1}"), r#""\u0001""#);
+    }
+}
@@ -0,0 +1,299 @@
+
+// ============================================================
+//  DAEGONICA SOFTWARE — keep_alive.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: KeepAlive
+//!
+//! **Purpose:**
+//! Configures persistent client connections.
+//!
+//! **Context:**
+//! - Set with `Server::keep_alive`; the connection handler serves requests in a loop until
+//!   these limits, the client, or a response ends the connection.
+//!
+//! **Responsibilities:**
+//! - Holds how long an idle connection waits for its next request and how many requests one
+//!   connection may serve.
+//! - Formats the `Keep-Alive` response header advertising both.
+//! - Does NOT free the worker while a connection is idle; each open connection holds one.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+use std::time::Duration;
+
+
+/// # DEFAULT_IDLE_TIMEOUT
+///
+/// **Summary:**
+/// Time an idle connection waits for its next request unless configured otherwise.
+pub const DEFAULT_IDLE_TIMEOUT: Duration = Duration::from_secs(5);
+
+
+/// # DEFAULT_MAX_REQUESTS
+///
+/// **Summary:**
+/// Requests one connection may serve unless configured otherwise.
+pub const DEFAULT_MAX_REQUESTS: usize = 100;
+
+
+/// # KeepAlive
+///
+/// **Summary:**
+/// Persistent connection limits.
+///
+/// **Fields:**
+/// - `idle`: Longest wait for the first byte of the next request.
+/// - `max_requests`: Requests served before the connection is closed (`1` disables keep-alive).
+///
+/// **Usage Example:**
+/// ```rust,no_run
+/// # use server::{keep_alive::KeepAlive, server::Server};
+/// # use std::time::Duration;
+/// let keep_alive = KeepAlive::new().idle(Duration::from_secs(2)).max_requests(20);
+/// Server::new("127.0.0.1:7878").keep_alive(keep_alive).run()?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeepAlive {
+    idle: Duration,
+    max_requests: usize,
+}
+
+
+impl Default for KeepAlive {
+
+    /// # default
+    ///
+    /// **Purpose:**
+    /// Creates the standard limits.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - `KeepAlive` with `DEFAULT_IDLE_TIMEOUT` and `DEFAULT_MAX_REQUESTS`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::keep_alive::KeepAlive;
+    /// let keep_alive = KeepAlive::default();
+    /// ```
+    fn default() -> KeepAlive {
+        KeepAlive {
+            idle: DEFAULT_IDLE_TIMEOUT,
+            max_requests: DEFAULT_MAX_REQUESTS,
+        }
+    }
+}
+
+
+impl KeepAlive {
+
+    /// # new
+    ///
+    /// **Purpose:**
+    /// Creates the standard limits (see `default`).
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - A new `KeepAlive`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::keep_alive::KeepAlive;
+    /// let keep_alive = KeepAlive::new();
+    /// ```
+    pub fn new() -> KeepAlive {
+        KeepAlive::default()
+    }
+
+    /// # disabled
+    ///
+    /// **Purpose:**
+    /// Serves one request per connection, closing it after every response.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - A `KeepAlive` allowing a single request.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{keep_alive::KeepAlive, server::Server};
+    /// let server = Server::new("127.0.0.1:7878").keep_alive(KeepAlive::disabled());
+    /// ```
+    pub fn disabled() -> KeepAlive {
+        KeepAlive::default().max_requests(1)
+    }
+
+    /// # idle
+    ///
+    /// **Purpose:**
+    /// Sets how long a connection may sit idle between requests before it is closed.
+    ///
+    /// **Parameters:**
+    /// - `timeout`: Idle timeout, advertised rounded down to whole seconds.
+    ///
+    /// **Returns:**
+    /// - The updated `KeepAlive`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::keep_alive::KeepAlive;
+    /// # use std::time::Duration;
+    /// let keep_alive = KeepAlive::new().idle(Duration::from_secs(15));
+    /// ```
+    pub fn idle(mut self, timeout: Duration) -> KeepAlive {
+        self.idle = timeout;
+        self
+    }
+
+    /// # max_requests
+    ///
+    /// **Purpose:**
+    /// Sets how many requests one connection may serve.
+    ///
+    /// **Parameters:**
+    /// - `max`: Request count (at least 1).
+    ///
+    /// **Returns:**
+    /// - The updated `KeepAlive`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::keep_alive::KeepAlive;
+    /// let keep_alive = KeepAlive::new().max_requests(1_000);
+    /// ```
+    pub fn max_requests(mut self, max: usize) -> KeepAlive {
+        self.max_requests = max.max(1);
+        self
+    }
+
+    /// # idle_timeout
+    ///
+    /// **Purpose:**
+    /// Returns the idle timeout.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - The configured idle timeout.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{keep_alive::KeepAlive, timeouts::{ClientStream, Timeouts}};
+    /// # use std::net::{TcpListener, TcpStream};
+    /// # let listener = TcpListener::bind("127.0.0.1:0")?;
+    /// # let _peer = TcpStream::connect(listener.local_addr()?)?;
+    /// # let (stream, _) = listener.accept()?;
+    /// # let mut client = ClientStream::new(&stream, Timeouts::new());
+    /// # let keep_alive = KeepAlive::new();
+    /// client.next_request(keep_alive.idle_timeout());
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn idle_timeout(&self) -> Duration {
+        self.idle
+    }
+
+    /// # request_limit
+    ///
+    /// **Purpose:**
+    /// Returns how many requests one connection may serve.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - The configured request limit.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::keep_alive::KeepAlive;
+    /// # let keep_alive = KeepAlive::new().max_requests(20);
+    /// # let served = 20;
+    /// let last = served == keep_alive.request_limit();
+    /// # assert!(last);
+    /// ```
+    pub fn request_limit(&self) -> usize {
+        self.max_requests
+    }
+
+    /// # header_value
+    ///
+    /// **Purpose:**
+    /// Formats the `Keep-Alive` header for a response that leaves the connection open.
+    ///
+    /// **Parameters:**
+    /// - `remaining`: Requests the connection may still serve after this one.
+    ///
+    /// **Returns:**
+    /// - A value such as `timeout=5, max=99`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{keep_alive::KeepAlive, response::Response};
+    /// # use std::time::Duration;
+    /// # let keep_alive = KeepAlive::new().idle(Duration::from_secs(5));
+    /// # let mut response = Response::new(200, "OK");
+    /// response.headers.set("Keep-Alive", keep_alive.header_value(99));
+    /// # assert_eq!(response.headers.get("Keep-Alive"), Some("timeout=5, max=99"));
+    /// ```
+    pub fn header_value(&self, remaining: usize) -> String {
+        format!("timeout={}, max={remaining}", self.idle.as_secs())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_header_advertises_the_idle_timeout_and_remaining_requests() {
+        let keep_alive = KeepAlive::new().idle(Duration::from_millis(15_900)).max_requests(10);
+        assert_eq!(keep_alive.header_value(9), "timeout=15, max=9");
+        assert_eq!(keep_alive.request_limit(), 10);
+    }
+
+    #[test]
+    fn connections_serve_at_least_one_request() {
+        assert_eq!(KeepAlive::new().max_requests(0).request_limit(), 1);
+        assert_eq!(KeepAlive::disabled().request_limit(), 1);
+    }
+}
@@ -26,10 +26,104 @@
 //! ---------------------------------------------------------------
 
 use std::{
-    sync::{Arc, Mutex, mpsc},
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        Arc,
+        Mutex,
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
     thread,
 };
 
+use crate::log::LogLevel;
+
+pub mod access_log;
+pub mod acl;
+pub mod admin;
+pub mod auth;
+pub(crate) mod base64;
+pub mod buffer_pool;
+pub mod cache;
+pub mod cancel;
+pub(crate) mod client;
+pub mod concurrency;
+pub mod connection;
+pub mod cookie;
+pub mod cors;
+pub mod csrf;
+pub mod daemon;
+pub mod date;
+pub mod dev;
+pub mod digest_auth;
+pub mod duplicates;
+pub mod early_hints;
+pub mod embed;
+#[cfg(any(target_os = "linux", target_os = "macos", target_os = "freebsd"))]
+pub(crate) mod event_loop;
+pub mod extensions;
+pub mod extract;
+pub mod file_cache;
+pub mod glob;
+pub(crate) mod gzip;
+pub(crate) mod hash;
+pub mod headers;
+pub mod health;
+pub mod hsts;
+pub mod ip;
+#[cfg(feature = "json")]
+pub mod json;
+pub mod json_value;
+pub mod jwt;
+pub mod keep_alive;
+pub mod limits;
+pub mod log;
+pub mod log_file;
+pub mod markdown;
+pub mod metrics;
+pub mod mime;
+pub(crate) mod mmap;
+pub mod oidc;
+pub mod openapi;
+pub mod peer_limit;
+pub mod privileges;
+pub mod proxy;
+pub mod proxy_protocol;
+pub(crate) mod random;
+pub(crate) mod range;
+pub mod rate_limit;
+pub mod redact;
+pub mod redirect;
+pub mod regex;
+pub mod request;
+pub mod response;
+pub mod rewrite;
+pub(crate) mod rsa;
+pub mod router;
+pub(crate) mod sandbox;
+pub mod security_headers;
+pub(crate) mod sendfile;
+pub mod server;
+pub mod service;
+pub mod session;
+pub(crate) mod signal;
+pub mod socket;
+pub mod state;
+pub mod static_files;
+pub mod syslog;
+pub mod tasks;
+pub mod template;
+pub mod throttle;
+pub mod timeouts;
+#[cfg(feature = "otel")]
+pub mod trace;
+pub(crate) mod trie;
+#[cfg(all(feature = "io_uring", target_os = "linux"))]
+pub(crate) mod uring;
+pub mod validate;
+pub mod vhost;
+pub mod watch;
+
 
 /// # ThreadPool
 ///
@@ -39,15 +133,18 @@ use std::{
 /// **Fields:**
 /// - `workers`: Vector of worker threads.
 /// - `sender`: Channel sender for job dispatching.
+/// - `queued`: Number of jobs sent but not yet picked up by a worker.
 ///
 /// **Usage Example:**
 /// ```rust
+/// # use server::ThreadPool;
 /// let pool = ThreadPool::new(4);
 /// pool.execute(|| println!("Hello from a thread!"));
 /// ```
 pub struct ThreadPool {
     workers: Vec<Worker>,
     sender: Option<mpsc::Sender<Job>>,
+    queued: Arc<AtomicUsize>,
 }
 
 
@@ -69,9 +166,35 @@ impl ThreadPool {
     ///
     /// **Examples:**
     /// ```rust
+    /// # use server::ThreadPool;
     /// let pool = ThreadPool::new(4);
     /// ```
     pub fn new(size: usize) -> ThreadPool {
+        ThreadPool::with_queue_gauge(size, Arc::default())
+    }
+
+    /// # with_queue_gauge
+    ///
+    /// **Purpose:**
+    /// Creates a thread pool that keeps an external counter of its queue depth up to date.
+    ///
+    /// **Parameters:**
+    /// - `size`: Number of worker threads to spawn.
+    /// - `queued`: Counter of jobs waiting for a worker (e.g. from `Metrics`).
+    ///
+    /// **Returns:**
+    /// - A new `ThreadPool` instance.
+    ///
+    /// **Errors / Failures:**
+    /// - Panics if `size` is zero.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::ThreadPool;
+    /// # use std::sync::{Arc, atomic::AtomicUsize};
+    /// let pool = ThreadPool::with_queue_gauge(4, Arc::new(AtomicUsize::new(0)));
+    /// ```
+    pub fn with_queue_gauge(size: usize, queued: Arc<AtomicUsize>) -> ThreadPool {
         assert!(size > 0);
 
         let (sender, receiver) = mpsc::channel();
@@ -80,12 +203,13 @@ impl ThreadPool {
         let mut workers = Vec::with_capacity(size);
 
         for id in 0..size {
-            workers.push(Worker::new(id, Arc::clone(&receiver)));
+            workers.push(Worker::new(id, Arc::clone(&receiver), Arc::clone(&queued)));
         }
 
         ThreadPool {
             workers,
             sender: Some(sender),
+            queued,
         }
     }
 
@@ -105,6 +229,7 @@ impl ThreadPool {
     ///
     /// **Examples:**
     /// ```rust
+    /// # let pool = server::ThreadPool::new(4);
     /// pool.execute(|| println!("Hello from a thread!"));
     /// ```
     pub fn execute<F>(&self, f: F)
@@ -112,8 +237,165 @@ impl ThreadPool {
         F: FnOnce() + Send + 'static,
     {
         let job = Box::new(f);
+        self.queued.fetch_add(1, Ordering::Relaxed);
         self.sender.as_ref().unwrap().send(job).unwrap();
     }
+
+    /// # queue_depth
+    ///
+    /// **Purpose:**
+    /// Returns how many jobs are waiting for a worker.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - The number of queued jobs not yet picked up.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # let pool = server::ThreadPool::new(4);
+    /// println!("{} jobs waiting", pool.queue_depth());
+    /// ```
+    pub fn queue_depth(&self) -> usize {
+        self.queued.load(Ordering::Relaxed)
+    }
+
+    /// # spawner
+    ///
+    /// **Purpose:**
+    /// Returns a cloneable handle that can queue jobs from inside other jobs (e.g. background cache refreshes).
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - A `Spawner` feeding this pool's job queue.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # let pool = server::ThreadPool::new(4);
+    /// let spawner = pool.spawner();
+    /// pool.execute(move || spawner.execute(|| println!("queued from a job")));
+    /// ```
+    pub fn spawner(&self) -> Spawner {
+        Spawner {
+            target: Target::Pool {
+                sender: self.sender.as_ref().unwrap().clone(),
+                queued: Arc::clone(&self.queued),
+            },
+        }
+    }
+}
+
+
+/// # Spawner
+///
+/// **Summary:**
+/// Cloneable handle for queueing jobs onto a `ThreadPool`, or onto a tokio runtime's blocking
+/// threads when the server runs under `Server::serve_async`.
+/// Workers keep running while any `Spawner` is alive, so handles should travel with jobs
+/// rather than be stored somewhere that outlives the pool.
+///
+/// **Fields:**
+/// - `target`: Where jobs run.
+///
+/// **Usage Example:**
+/// ```rust
+/// # let pool = server::ThreadPool::new(4);
+/// let spawner = pool.spawner();
+/// spawner.execute(|| println!("Hello from a thread!"));
+/// ```
+#[derive(Clone)]
+pub struct Spawner {
+    target: Target,
+}
+
+
+/// # Target
+///
+/// **Summary:**
+/// Executor behind a `Spawner`.
+///
+/// **Variants:**
+/// - `Pool`: A `ThreadPool`'s job channel sender and queue depth counter.
+/// - `Runtime`: A tokio runtime whose blocking threads run the jobs.
+#[derive(Clone)]
+enum Target {
+    Pool {
+        sender: mpsc::Sender<Job>,
+        queued: Arc<AtomicUsize>,
+    },
+    #[cfg(feature = "async")]
+    Runtime(tokio::runtime::Handle),
+}
+
+
+impl Spawner {
+    /// # blocking
+    ///
+    /// **Purpose:**
+    /// Creates a handle that runs jobs on a tokio runtime's blocking threads.
+    ///
+    /// **Parameters:**
+    /// - `runtime`: Handle of the runtime to use.
+    ///
+    /// **Returns:**
+    /// - A `Spawner` calling `spawn_blocking` on the runtime.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let spawner = Spawner::blocking(tokio::runtime::Handle::current());
+    /// ```
+    #[cfg(feature = "async")]
+    pub(crate) fn blocking(runtime: tokio::runtime::Handle) -> Spawner {
+        Spawner { target: Target::Runtime(runtime) }
+    }
+
+    /// # execute
+    ///
+    /// **Purpose:**
+    /// Sends a job (closure) to the pool for execution by a worker thread.
+    ///
+    /// **Parameters:**
+    /// - `f`: Closure to execute. Must be `FnOnce() + Send + 'static`.
+    ///
+    /// **Returns:**
+    /// None.
+    ///
+    /// **Errors / Failures:**
+    /// - Panics if every worker has already shut down.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # let pool = server::ThreadPool::new(4);
+    /// # let spawner = pool.spawner();
+    /// spawner.execute(|| println!("Hello from a thread!"));
+    /// ```
+    pub fn execute<F>(&self, f: F)
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        match &self.target {
+            Target::Pool { sender, queued } => {
+                queued.fetch_add(1, Ordering::Relaxed);
+                sender.send(Box::new(f)).unwrap();
+            }
+            #[cfg(feature = "async")]
+            Target::Runtime(runtime) => {
+                runtime.spawn_blocking(f);
+            }
+        }
+    }
 }
 
 
@@ -135,7 +417,7 @@ impl Drop for ThreadPool {
         drop(self.sender.take());
 
         for worker in &mut self.workers {
-            println!("Shutting down worker {}", worker.id);
+            log::emit(LogLevel::Info, format_args!("Shutting down worker {}", worker.id));
 
             if let Some(thread) = worker.thread.take() {
                 thread.join().unwrap();
@@ -170,11 +452,13 @@ impl Worker {
     /// # new
     ///
     /// **Purpose:**
-    /// Spawns a new worker thread that waits for and executes jobs from the job queue.
+    /// Spawns a new worker thread that waits for and executes jobs from the job queue. A job
+    /// that panics is reported and the worker moves on to the next one.
     ///
     /// **Parameters:**
     /// - `id`: Worker thread identifier.
     /// - `receiver`: Shared receiver for job queue.
+    /// - `queued`: Queue depth counter, decremented as jobs are picked up.
     ///
     /// **Returns:**
     /// - A new `Worker` instance with a running thread.
@@ -183,19 +467,22 @@ impl Worker {
     /// - Panics if thread spawning fails.
     ///
     /// **Examples:**
-    /// ```rust
-    /// let worker = Worker::new(0, receiver);
+    /// ```rust,ignore
+    /// let worker = Worker::new(0, receiver, queued);
     /// ```
-    fn new(id: usize, receiver: Arc<Mutex<mpsc::Receiver<Job>>>) -> Worker {
+    fn new(id: usize, receiver: Arc<Mutex<mpsc::Receiver<Job>>>, queued: Arc<AtomicUsize>) -> Worker {
         let thread = thread::spawn(move || loop {
             let message = receiver.lock().unwrap().recv();
             match message {
                 Ok(job) => {
-                    println!("Worker {id} got a job; executing.");
-                    job();
+                    queued.fetch_sub(1, Ordering::Relaxed);
+                    log::emit(LogLevel::Debug, format_args!("Worker {id} got a job; executing."));
+                    if panic::catch_unwind(AssertUnwindSafe(job)).is_err() {
+                        log::emit(LogLevel::Error, format_args!("Worker {id} recovered from a panicking job."));
+                    }
                 }
                 Err(_) => {
-                    println!("Worker {id} disconnected; shutting down.");
+                    log::emit(LogLevel::Info, format_args!("Worker {id} disconnected; shutting down."));
                     break;
                 }
             }
@@ -0,0 +1,532 @@
+
+// ============================================================
+//  DAEGONICA SOFTWARE — limits.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: Limits
+//!
+//! **Purpose:**
+//! Caps how much data a client may make the server buffer for one request.
+//!
+//! **Context:**
+//! - Configured with `Server::limits` and checked while each request is read.
+//!
+//! **Responsibilities:**
+//! - Caps request bodies, with per-route overrides selected by path pattern.
+//! - Caps the request target length, the number of header fields, and the size of the request head.
+//! - Refuses a declared `Content-Length` over the cap before reading any of the body, and stops
+//!   chunked bodies as soon as they pass it.
+//! - Describes each exceeded limit as an error the server answers with the matching status.
+//! - Does NOT limit response sizes.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+use std::{error::Error, fmt, io};
+
+use crate::{glob::Glob, response::Response};
+
+
+/// # DEFAULT_MAX_BODY
+///
+/// **Summary:**
+/// Largest request body accepted unless configured otherwise (10 MiB).
+pub const DEFAULT_MAX_BODY: usize = 10 * 1024 * 1024;
+
+
+/// # DEFAULT_MAX_TARGET
+///
+/// **Summary:**
+/// Longest request target accepted unless configured otherwise (8 KiB).
+pub const DEFAULT_MAX_TARGET: usize = 8 * 1024;
+
+
+/// # REQUEST_LINE_SLACK
+///
+/// **Summary:**
+/// Room allowed on the request line for the method, version, and separators on top of the target.
+const REQUEST_LINE_SLACK: usize = 64;
+
+
+/// # DEFAULT_MAX_HEADERS
+///
+/// **Summary:**
+/// Most header fields accepted in a request unless configured otherwise.
+pub const DEFAULT_MAX_HEADERS: usize = 100;
+
+
+/// # DEFAULT_MAX_HEADER_BYTES
+///
+/// **Summary:**
+/// Largest request head (request line and header fields) accepted unless configured otherwise (32 KiB).
+pub const DEFAULT_MAX_HEADER_BYTES: usize = 32 * 1024;
+
+
+/// # Limits
+///
+/// **Summary:**
+/// Request size limits.
+///
+/// **Fields:**
+/// - `max_body`: Largest body accepted on paths without an override.
+/// - `body_overrides`: Route patterns with their own body cap, checked in order.
+/// - `max_target`: Longest request target accepted.
+/// - `max_headers`: Most header fields accepted.
+/// - `max_header_bytes`: Largest request head accepted, line terminators included.
+///
+/// **Usage Example:**
+/// ```rust,no_run
+/// # use server::{limits::Limits, server::Server};
+/// let limits = Limits::new().max_body(64 * 1024).route_body("/upload/**", 512 * 1024 * 1024);
+/// Server::new("127.0.0.1:7878").limits(limits).run()?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct Limits {
+    max_body: usize,
+    body_overrides: Vec<(Glob, usize)>,
+    max_target: usize,
+    max_headers: usize,
+    max_header_bytes: usize,
+}
+
+
+/// # HeadLimits
+///
+/// **Summary:**
+/// The caps that apply while a request head is parsed.
+///
+/// **Fields:**
+/// - `max_target`: Longest request target accepted.
+/// - `max_headers`: Most header fields accepted.
+/// - `max_bytes`: Largest head accepted, line terminators included.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct HeadLimits {
+    pub(crate) max_target: usize,
+    pub(crate) max_headers: usize,
+    pub(crate) max_bytes: usize,
+}
+
+
+/// # LimitExceeded
+///
+/// **Summary:**
+/// Which limit a request broke; carried inside the `io::Error` returned while reading it.
+///
+/// **Variants:**
+/// - `Body`: The body is larger than its cap (`413`).
+/// - `Target`: The request target is longer than its cap (`414`).
+/// - `Headers`: The head has too many fields or too many bytes (`431`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum LimitExceeded {
+    Body,
+    Target,
+    Headers,
+}
+
+
+impl Default for Limits {
+
+    /// # default
+    ///
+    /// **Purpose:**
+    /// Creates the default limits.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - `Limits` with the `DEFAULT_*` caps and no body overrides.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::limits::Limits;
+    /// let limits = Limits::default();
+    /// ```
+    fn default() -> Limits {
+        Limits {
+            max_body: DEFAULT_MAX_BODY,
+            body_overrides: Vec::new(),
+            max_target: DEFAULT_MAX_TARGET,
+            max_headers: DEFAULT_MAX_HEADERS,
+            max_header_bytes: DEFAULT_MAX_HEADER_BYTES,
+        }
+    }
+}
+
+
+impl Limits {
+
+    /// # new
+    ///
+    /// **Purpose:**
+    /// Creates the default limits.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - A new `Limits`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::limits::Limits;
+    /// let limits = Limits::new();
+    /// ```
+    pub fn new() -> Limits {
+        Limits::default()
+    }
+
+    /// # max_body
+    ///
+    /// **Purpose:**
+    /// Sets the body cap for paths without an override.
+    ///
+    /// **Parameters:**
+    /// - `bytes`: Largest decoded body accepted.
+    ///
+    /// **Returns:**
+    /// - The updated `Limits`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::limits::Limits;
+    /// let limits = Limits::new().max_body(1024 * 1024);
+    /// ```
+    pub fn max_body(mut self, bytes: usize) -> Limits {
+        self.max_body = bytes;
+        self
+    }
+
+    /// # route_body
+    ///
+    /// **Purpose:**
+    /// Uses a different body cap, larger or smaller, for request paths matching a pattern.
+    /// The first matching override wins.
+    ///
+    /// **Parameters:**
+    /// - `pattern`: Path glob (e.g. `/upload/**`), matched against the path as sent.
+    /// - `bytes`: Largest decoded body accepted on those paths.
+    ///
+    /// **Returns:**
+    /// - The updated `Limits`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::limits::Limits;
+    /// let limits = Limits::new().route_body("/api/**", 64 * 1024);
+    /// ```
+    pub fn route_body(mut self, pattern: &str, bytes: usize) -> Limits {
+        self.body_overrides.push((Glob::new(pattern), bytes));
+        self
+    }
+
+    /// # max_target
+    ///
+    /// **Purpose:**
+    /// Sets the longest request target (path and query) accepted.
+    ///
+    /// **Parameters:**
+    /// - `bytes`: Target length limit.
+    ///
+    /// **Returns:**
+    /// - The updated `Limits`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::limits::Limits;
+    /// let limits = Limits::new().max_target(2048);
+    /// ```
+    pub fn max_target(mut self, bytes: usize) -> Limits {
+        self.max_target = bytes;
+        self
+    }
+
+    /// # max_headers
+    ///
+    /// **Purpose:**
+    /// Sets the most header fields a request may carry.
+    ///
+    /// **Parameters:**
+    /// - `count`: Header field limit.
+    ///
+    /// **Returns:**
+    /// - The updated `Limits`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::limits::Limits;
+    /// let limits = Limits::new().max_headers(50);
+    /// ```
+    pub fn max_headers(mut self, count: usize) -> Limits {
+        self.max_headers = count;
+        self
+    }
+
+    /// # max_header_bytes
+    ///
+    /// **Purpose:**
+    /// Sets the largest request head (request line plus header fields) accepted.
+    ///
+    /// **Parameters:**
+    /// - `bytes`: Head size limit, line terminators included.
+    ///
+    /// **Returns:**
+    /// - The updated `Limits`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::limits::Limits;
+    /// let limits = Limits::new().max_header_bytes(16 * 1024);
+    /// ```
+    pub fn max_header_bytes(mut self, bytes: usize) -> Limits {
+        self.max_header_bytes = bytes;
+        self
+    }
+
+    /// # head
+    ///
+    /// **Purpose:**
+    /// Returns the caps that apply while parsing a request head.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - The target, field count, and head size caps.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let request = Request::read_head(&mut reader, &limits.head())?;
+    /// ```
+    pub(crate) fn head(&self) -> HeadLimits {
+        HeadLimits {
+            max_target: self.max_target,
+            max_headers: self.max_headers,
+            max_bytes: self.max_header_bytes,
+        }
+    }
+
+    /// # body_limit
+    ///
+    /// **Purpose:**
+    /// Picks the body cap for a request path.
+    ///
+    /// **Parameters:**
+    /// - `path`: Request path.
+    ///
+    /// **Returns:**
+    /// - The first matching override's cap, or the default cap.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let cap = limits.body_limit(request.path());
+    /// ```
+    pub(crate) fn body_limit(&self, path: &str) -> usize {
+        self.body_overrides
+            .iter()
+            .find(|(pattern, _)| pattern.matches(path))
+            .map_or(self.max_body, |(_, bytes)| *bytes)
+    }
+}
+
+
+impl HeadLimits {
+
+    /// # OPERATOR
+    ///
+    /// **Summary:**
+    /// Tight caps for the admin and metrics listeners, which only ever see short requests from
+    /// operators and scrapers.
+    pub(crate) const OPERATOR: HeadLimits = HeadLimits {
+        max_target: 1024,
+        max_headers: 32,
+        max_bytes: 8 * 1024,
+    };
+
+    /// # request_line
+    ///
+    /// **Purpose:**
+    /// Works out how many bytes the request line may use.
+    ///
+    /// **Parameters:**
+    /// - `budget`: Bytes left for the whole head.
+    ///
+    /// **Returns:**
+    /// - The target cap plus room for the method and version, never more than `budget`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let line_budget = limits.request_line(budget);
+    /// ```
+    pub(crate) fn request_line(&self, budget: usize) -> usize {
+        self.max_target.saturating_add(REQUEST_LINE_SLACK).min(budget)
+    }
+}
+
+
+impl LimitExceeded {
+
+    /// # error
+    ///
+    /// **Purpose:**
+    /// Wraps the exceeded limit in an I/O error so it can travel through the readers.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - An `InvalidData` error carrying this value.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// return Err(LimitExceeded::Body.error());
+    /// ```
+    pub(crate) fn error(self) -> io::Error {
+        io::Error::new(io::ErrorKind::InvalidData, self)
+    }
+
+    /// # of
+    ///
+    /// **Purpose:**
+    /// Recovers the exceeded limit from an error built by `error`.
+    ///
+    /// **Parameters:**
+    /// - `error`: Error returned while reading a request.
+    ///
+    /// **Returns:**
+    /// - `Some(limit)` if the error reports an exceeded limit, otherwise `None`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// if let Some(limit) = LimitExceeded::of(&error) { /* answer limit.response() */ }
+    /// ```
+    pub(crate) fn of(error: &io::Error) -> Option<LimitExceeded> {
+        error.get_ref()?.downcast_ref::<LimitExceeded>().copied()
+    }
+
+    /// # response
+    ///
+    /// **Purpose:**
+    /// Builds the response owed to a client that broke this limit.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - The matching `4xx` response.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let response = LimitExceeded::Body.response();
+    /// ```
+    pub(crate) fn response(self) -> Response {
+        let (status, reason) = match self {
+            LimitExceeded::Body => (413, "Payload Too Large"),
+            LimitExceeded::Target => (414, "URI Too Long"),
+            LimitExceeded::Headers => (431, "Request Header Fields Too Large"),
+        };
+        Response::new(status, reason)
+            .with_header("Content-Type", "text/plain; charset=utf-8")
+            .with_body(self.to_string())
+    }
+}
+
+
+impl fmt::Display for LimitExceeded {
+
+    /// # fmt
+    ///
+    /// **Purpose:**
+    /// Describes the exceeded limit.
+    ///
+    /// **Parameters:**
+    /// - `f`: Formatter to write to.
+    ///
+    /// **Returns:**
+    /// - The formatter result.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let message = LimitExceeded::Body.to_string();
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LimitExceeded::Body => f.write_str("request body too large"),
+            LimitExceeded::Target => f.write_str("request target too long"),
+            LimitExceeded::Headers => f.write_str("request header fields too large"),
+        }
+    }
+}
+
+
+impl Error for LimitExceeded {}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn route_overrides_pick_the_body_cap() {
+        let limits = Limits::new().max_body(10).route_body("/upload/**", 100);
+        assert_eq!(limits.body_limit("/upload/photo"), 100);
+        assert_eq!(limits.body_limit("/other"), 10);
+    }
+
+    #[test]
+    fn exceeded_limits_survive_the_trip_through_io_errors() {
+        let error = LimitExceeded::Headers.error();
+        assert_eq!(LimitExceeded::of(&error), Some(LimitExceeded::Headers));
+        assert_eq!(LimitExceeded::of(&io::Error::other("other")), None);
+        assert_eq!(LimitExceeded::Body.response().status, 413);
+    }
+}
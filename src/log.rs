@@ -0,0 +1,316 @@
+
+// ============================================================
+//  DAEGONICA SOFTWARE — log.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: Log
+//!
+//! **Purpose:**
+//! Filters the server's diagnostic messages by a process-wide level.
+//!
+//! **Context:**
+//! - Used by the worker pool and connection handling in place of bare `println!`; the level
+//!   can be changed while serving through the admin API.
+//!
+//! **Responsibilities:**
+//! - Holds the current level in one atomic, so a change applies to every thread at once.
+//! - Prints messages at or above the level to standard output, or sends them to a rotating
+//!   error log file or syslog once one is set.
+//! - Does NOT format access logs, which `AccessLog` writes regardless of the level.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+use std::{
+    fmt,
+    sync::{
+        Mutex,
+        PoisonError,
+        atomic::{AtomicU8, Ordering},
+    },
+    time::SystemTime,
+};
+
+use crate::{
+    date::format_clf,
+    log_file::LogFile,
+    syslog::{Severity, Syslog},
+};
+
+
+/// # LEVEL
+///
+/// **Summary:**
+/// Most verbose level currently printed; everything is printed until it is lowered.
+static LEVEL: AtomicU8 = AtomicU8::new(LogLevel::Debug as u8);
+
+
+/// # SINK
+///
+/// **Summary:**
+/// Where messages are sent, or `None` to print them to standard output.
+static SINK: Mutex<Option<Sink>> = Mutex::new(None);
+
+
+/// # LogLevel
+///
+/// **Summary:**
+/// Severity of a diagnostic message, from most to least severe.
+///
+/// **Variants:**
+/// - `Error`: A job or connection failed in a way worth investigating.
+/// - `Warn`: A connection was dropped early.
+/// - `Info`: Lifecycle events such as worker shutdown.
+/// - `Debug`: Per-job chatter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogLevel {
+    Error,
+    Warn,
+    Info,
+    Debug,
+}
+
+
+/// # Sink
+///
+/// **Summary:**
+/// Destination of diagnostic messages other than standard output.
+///
+/// **Variants:**
+/// - `File`: Error log file, with its rotation policy.
+/// - `Syslog`: Syslog daemon, at the severity matching each message's level.
+enum Sink {
+    File(LogFile),
+    Syslog(Syslog),
+}
+
+
+impl LogLevel {
+
+    /// # parse
+    ///
+    /// **Purpose:**
+    /// Reads a level name, ignoring case.
+    ///
+    /// **Parameters:**
+    /// - `name`: `error`, `warn`, `info`, or `debug`.
+    ///
+    /// **Returns:**
+    /// - The level, or `None` for an unknown name.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::log::LogLevel;
+    /// assert_eq!(LogLevel::parse("WARN"), Some(LogLevel::Warn));
+    /// ```
+    pub fn parse(name: &str) -> Option<LogLevel> {
+        [LogLevel::Error, LogLevel::Warn, LogLevel::Info, LogLevel::Debug]
+            .into_iter()
+            .find(|level| level.as_str().eq_ignore_ascii_case(name.trim()))
+    }
+
+    /// # as_str
+    ///
+    /// **Purpose:**
+    /// Names the level.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - The lowercase level name.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::log::LogLevel;
+    /// assert_eq!(LogLevel::Info.as_str(), "info");
+    /// ```
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LogLevel::Error => "error",
+            LogLevel::Warn => "warn",
+            LogLevel::Info => "info",
+            LogLevel::Debug => "debug",
+        }
+    }
+
+    /// # severity
+    ///
+    /// **Purpose:**
+    /// Maps the level to a syslog severity.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - The matching severity.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let severity = LogLevel::Warn.severity();
+    /// ```
+    fn severity(self) -> Severity {
+        match self {
+            LogLevel::Error => Severity::Error,
+            LogLevel::Warn => Severity::Warning,
+            LogLevel::Info => Severity::Informational,
+            LogLevel::Debug => Severity::Debug,
+        }
+    }
+}
+
+
+/// # set_level
+///
+/// **Purpose:**
+/// Changes which messages are printed from now on, for every thread.
+///
+/// **Parameters:**
+/// - `level`: Most verbose level to print.
+///
+/// **Returns:**
+/// None.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust
+/// # use server::log::{self, LogLevel};
+/// log::set_level(LogLevel::Warn);
+/// ```
+pub fn set_level(level: LogLevel) {
+    LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
+
+/// # level
+///
+/// **Purpose:**
+/// Returns the current level.
+///
+/// **Parameters:**
+/// None.
+///
+/// **Returns:**
+/// - The most verbose level being printed.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust
+/// # use server::log;
+/// println!("logging at {}", log::level().as_str());
+/// ```
+pub fn level() -> LogLevel {
+    match LEVEL.load(Ordering::Relaxed) {
+        0 => LogLevel::Error,
+        1 => LogLevel::Warn,
+        2 => LogLevel::Info,
+        _ => LogLevel::Debug,
+    }
+}
+
+
+/// # set_file
+///
+/// **Purpose:**
+/// Appends messages to a log file instead of printing them, for every thread.
+///
+/// **Parameters:**
+/// - `file`: Log file, with its rotation policy.
+///
+/// **Returns:**
+/// None.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,no_run
+/// # use server::{log, log_file::{LogFile, Rotation}};
+/// log::set_file(LogFile::open("error.log")?.rotate(Rotation::new().max_size(10 << 20).compress(true)));
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub fn set_file(file: LogFile) {
+    *SINK.lock().unwrap_or_else(PoisonError::into_inner) = Some(Sink::File(file));
+}
+
+
+/// # set_syslog
+///
+/// **Purpose:**
+/// Sends messages to syslog instead of standard output, for every thread.
+///
+/// **Parameters:**
+/// - `syslog`: Syslog destination.
+///
+/// **Returns:**
+/// None.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,no_run
+/// # use server::{log, syslog::Syslog};
+/// log::set_syslog(Syslog::unix("/dev/log").msg_id("error"));
+/// ```
+pub fn set_syslog(syslog: Syslog) {
+    *SINK.lock().unwrap_or_else(PoisonError::into_inner) = Some(Sink::Syslog(syslog));
+}
+
+
+/// # emit
+///
+/// **Purpose:**
+/// Prints a diagnostic message if its level is enabled, or sends it to the configured file (with
+/// a timestamp and level) or syslog.
+///
+/// **Parameters:**
+/// - `level`: Severity of the message.
+/// - `message`: Formatted message, built only if printed.
+///
+/// **Returns:**
+/// None.
+///
+/// **Errors / Failures:**
+/// None. Failed writes are ignored so logging never fails a request.
+///
+/// **Examples:**
+/// ```rust
+/// # use server::log::{self, LogLevel};
+/// # let error = std::io::Error::from(std::io::ErrorKind::ConnectionReset);
+/// log::emit(LogLevel::Warn, format_args!("Connection dropped: {error}"));
+/// ```
+pub fn emit(level: LogLevel, message: fmt::Arguments<'_>) {
+    if level > self::level() {
+        return;
+    }
+    match &mut *SINK.lock().unwrap_or_else(PoisonError::into_inner) {
+        Some(Sink::File(file)) => {
+            let line = format!("[{}] {} {message}\n", format_clf(SystemTime::now()), level.as_str());
+            let _ = file.write(line.as_bytes());
+        }
+        Some(Sink::Syslog(syslog)) => {
+            let _ = syslog.send(level.severity(), &message.to_string());
+        }
+        None => println!("{message}"),
+    }
+}
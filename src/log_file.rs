@@ -0,0 +1,551 @@
+
+// ============================================================
+//  DAEGONICA SOFTWARE — log_file.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: LogFile
+//!
+//! **Purpose:**
+//! Appends to a log file and rotates it by size or on a schedule.
+//!
+//! **Context:**
+//! - Backs `AccessLog`, and the diagnostic log once `log::set_file` is called.
+//!
+//! **Responsibilities:**
+//! - Moves the live file aside as `<name>.<YYYYMMDD-HHMMSS>` once it would exceed a size or a
+//!   UTC interval boundary passes, then reopens a fresh file under the original name.
+//! - Optionally gzips rotated files and keeps only the newest few, on a background thread so
+//!   the request that triggered rotation is not held up.
+//! - Does NOT coordinate with other processes writing the same file, or notice the file being
+//!   moved by an external tool such as `logrotate`.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+use std::{
+    ffi::OsString,
+    fs::{self, File, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::{date::DateTime, gzip};
+
+
+/// # DEFAULT_KEEP
+///
+/// **Summary:**
+/// Rotated files kept unless `Rotation::keep` says otherwise.
+const DEFAULT_KEEP: usize = 7;
+
+
+/// # Rotation
+///
+/// **Summary:**
+/// When to rotate a log file and what to do with rotated files. With neither a size nor an
+/// interval set, the file is never rotated.
+///
+/// **Fields:**
+/// - `max_size`: Rotate before a write would take the file past this many bytes.
+/// - `interval`: Rotate whenever a multiple of this interval since the Unix epoch passes (a day
+///   rotates at midnight UTC).
+/// - `keep`: Rotated files kept; older ones are deleted.
+/// - `compress`: Whether rotated files are gzipped.
+///
+/// **Usage Example:**
+/// ```rust,no_run
+/// # use server::{access_log::AccessLog, log_file::Rotation};
+/// # use std::time::Duration;
+/// let rotation = Rotation::new().max_size(100 << 20).every(Duration::from_secs(86_400)).keep(14).compress(true);
+/// let log = AccessLog::open("access.log")?.rotate(rotation);
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Rotation {
+    max_size: Option<u64>,
+    interval: Option<Duration>,
+    keep: usize,
+    compress: bool,
+}
+
+
+/// # LogFile
+///
+/// **Summary:**
+/// An append-only log file with its rotation policy. Not synchronised; owners wrap it in a mutex.
+///
+/// **Fields:**
+/// - `path`: Path of the live file.
+/// - `file`: The live file, opened for appending.
+/// - `size`: Bytes in the live file.
+/// - `due`: Unix time of the next scheduled rotation, if rotating on a schedule.
+/// - `rotation`: The rotation policy.
+///
+/// **Usage Example:**
+/// ```rust,no_run
+/// # use server::{log, log_file::{LogFile, Rotation}};
+/// log::set_file(LogFile::open("error.log")?.rotate(Rotation::new().max_size(10 << 20)));
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct LogFile {
+    path: PathBuf,
+    file: File,
+    size: u64,
+    due: Option<u64>,
+    rotation: Rotation,
+}
+
+
+impl Default for Rotation {
+
+    /// # default
+    ///
+    /// **Purpose:**
+    /// Creates a policy that never rotates and keeps seven uncompressed files once it does.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - A `Rotation` with no trigger set.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::log_file::Rotation;
+    /// let rotation = Rotation::default();
+    /// ```
+    fn default() -> Rotation {
+        Rotation {
+            max_size: None,
+            interval: None,
+            keep: DEFAULT_KEEP,
+            compress: false,
+        }
+    }
+}
+
+
+impl Rotation {
+
+    /// # new
+    ///
+    /// **Purpose:**
+    /// Creates a policy to be configured with the builder methods.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - A new `Rotation`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::log_file::Rotation;
+    /// let rotation = Rotation::new().max_size(50 << 20);
+    /// ```
+    pub fn new() -> Rotation {
+        Rotation::default()
+    }
+
+    /// # max_size
+    ///
+    /// **Purpose:**
+    /// Rotates the file before a write would take it past a size.
+    ///
+    /// **Parameters:**
+    /// - `bytes`: Largest file size.
+    ///
+    /// **Returns:**
+    /// - The updated `Rotation`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::log_file::Rotation;
+    /// let rotation = Rotation::new().max_size(100 * 1024 * 1024);
+    /// ```
+    pub fn max_size(mut self, bytes: u64) -> Rotation {
+        self.max_size = Some(bytes);
+        self
+    }
+
+    /// # every
+    ///
+    /// **Purpose:**
+    /// Rotates the file on a fixed UTC schedule, e.g. hourly or daily.
+    ///
+    /// **Parameters:**
+    /// - `interval`: Time between rotations, at least one second.
+    ///
+    /// **Returns:**
+    /// - The updated `Rotation`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::log_file::Rotation;
+    /// # use std::time::Duration;
+    /// let rotation = Rotation::new().every(Duration::from_secs(3_600));
+    /// ```
+    pub fn every(mut self, interval: Duration) -> Rotation {
+        self.interval = Some(interval.max(Duration::from_secs(1)));
+        self
+    }
+
+    /// # keep
+    ///
+    /// **Purpose:**
+    /// Sets how many rotated files are retained.
+    ///
+    /// **Parameters:**
+    /// - `count`: Rotated files kept; `0` deletes each as soon as it is rotated.
+    ///
+    /// **Returns:**
+    /// - The updated `Rotation`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::log_file::Rotation;
+    /// let rotation = Rotation::new().max_size(10 << 20).keep(30);
+    /// ```
+    pub fn keep(mut self, count: usize) -> Rotation {
+        self.keep = count;
+        self
+    }
+
+    /// # compress
+    ///
+    /// **Purpose:**
+    /// Gzips rotated files, adding `.gz` to their names.
+    ///
+    /// **Parameters:**
+    /// - `compress`: Whether to compress.
+    ///
+    /// **Returns:**
+    /// - The updated `Rotation`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::log_file::Rotation;
+    /// # use std::time::Duration;
+    /// let rotation = Rotation::new().every(Duration::from_secs(86_400)).compress(true);
+    /// ```
+    pub fn compress(mut self, compress: bool) -> Rotation {
+        self.compress = compress;
+        self
+    }
+
+    /// # next_due
+    ///
+    /// **Purpose:**
+    /// Finds the next interval boundary after a time.
+    ///
+    /// **Parameters:**
+    /// - `now`: Current time.
+    ///
+    /// **Returns:**
+    /// - Unix time of the next scheduled rotation, or `None` without an interval.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let due = rotation.next_due(SystemTime::now());
+    /// ```
+    fn next_due(&self, now: SystemTime) -> Option<u64> {
+        let interval = self.interval?.as_secs().max(1);
+        Some((unix_seconds(now) / interval + 1) * interval)
+    }
+}
+
+
+impl LogFile {
+
+    /// # open
+    ///
+    /// **Purpose:**
+    /// Opens `path` for appending, creating it if needed, without rotation.
+    ///
+    /// **Parameters:**
+    /// - `path`: Log file location.
+    ///
+    /// **Returns:**
+    /// - A new `LogFile`.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns an I/O error if the file cannot be opened.
+    ///
+    /// **Examples:**
+    /// ```rust,no_run
+    /// # use server::log_file::LogFile;
+    /// let file = LogFile::open("logs/error.log")?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn open(path: impl AsRef<Path>) -> io::Result<LogFile> {
+        let path = path.as_ref().to_path_buf();
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let size = file.metadata()?.len();
+        Ok(LogFile {
+            path,
+            file,
+            size,
+            due: None,
+            rotation: Rotation::default(),
+        })
+    }
+
+    /// # rotate
+    ///
+    /// **Purpose:**
+    /// Sets the rotation policy.
+    ///
+    /// **Parameters:**
+    /// - `rotation`: When to rotate and what to keep.
+    ///
+    /// **Returns:**
+    /// - The updated `LogFile`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,no_run
+    /// # use server::log_file::{LogFile, Rotation};
+    /// let file = LogFile::open("error.log")?.rotate(Rotation::new().max_size(1 << 20));
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn rotate(mut self, rotation: Rotation) -> LogFile {
+        self.due = rotation.next_due(SystemTime::now());
+        self.rotation = rotation;
+        self
+    }
+
+    /// # write
+    ///
+    /// **Purpose:**
+    /// Appends bytes, rotating first if the policy says the file is due.
+    ///
+    /// **Parameters:**
+    /// - `bytes`: Complete log lines.
+    ///
+    /// **Returns:**
+    /// - `Ok(())` once written.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns an I/O error if the write fails. A failed rotation is not an error; writing
+    ///   continues to the current file and is retried on the next write.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// file.write(b"line\n")?;
+    /// ```
+    pub(crate) fn write(&mut self, bytes: &[u8]) -> io::Result<()> {
+        let now = SystemTime::now();
+        let oversized = self.rotation.max_size.is_some_and(|max| self.size > 0 && self.size + bytes.len() as u64 > max);
+        let scheduled = self.due.is_some_and(|due| unix_seconds(now) >= due);
+        if (oversized || scheduled) && self.rotate_now(now).is_ok() {
+            self.due = self.rotation.next_due(now);
+        }
+        self.file.write_all(bytes)?;
+        self.size += bytes.len() as u64;
+        Ok(())
+    }
+
+    /// # rotate_now
+    ///
+    /// **Purpose:**
+    /// Moves the live file aside, reopens a fresh one, and hands the rotated file to a
+    /// background thread for compression and pruning.
+    ///
+    /// **Parameters:**
+    /// - `now`: Time used in the rotated file's name.
+    ///
+    /// **Returns:**
+    /// - `Ok(())` once a fresh file is open.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns an I/O error if the file cannot be renamed or reopened; the old file stays live.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// self.rotate_now(SystemTime::now())?;
+    /// ```
+    fn rotate_now(&mut self, now: SystemTime) -> io::Result<()> {
+        let archive = self.archive_path(now);
+        fs::rename(&self.path, &archive)?;
+        self.file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        self.size = 0;
+
+        let (path, rotation) = (self.path.clone(), self.rotation);
+        thread::Builder::new().name("log-rotate".to_string()).spawn(move || {
+            if rotation.compress {
+                let _ = compress_file(&archive);
+            }
+            let _ = prune(&path, rotation.keep);
+        })?;
+        Ok(())
+    }
+
+    /// # archive_path
+    ///
+    /// **Purpose:**
+    /// Names the rotated file, adding a counter if a file rotated in the same second exists.
+    ///
+    /// **Parameters:**
+    /// - `now`: Rotation time.
+    ///
+    /// **Returns:**
+    /// - `<path>.<YYYYMMDD-HHMMSS>`, or `<path>.<YYYYMMDD-HHMMSS>-<n>`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let archive = self.archive_path(SystemTime::now());
+    /// ```
+    fn archive_path(&self, now: SystemTime) -> PathBuf {
+        let time = DateTime::from_system_time(now);
+        let stamp = format!(
+            "{:04}{:02}{:02}-{:02}{:02}{:02}",
+            time.year, time.month, time.day, time.hour, time.minute, time.second
+        );
+        let mut counter = 0;
+        loop {
+            let mut name = OsString::from(self.path.as_os_str());
+            name.push(".");
+            name.push(&stamp);
+            if counter > 0 {
+                name.push(format!("-{counter}"));
+            }
+            let candidate = PathBuf::from(name);
+            let mut compressed = candidate.clone().into_os_string();
+            compressed.push(".gz");
+            if !candidate.exists() && !Path::new(&compressed).exists() {
+                return candidate;
+            }
+            counter += 1;
+        }
+    }
+}
+
+
+/// # compress_file
+///
+/// **Purpose:**
+/// Replaces a rotated file with a gzipped copy named `<file>.gz`.
+///
+/// **Parameters:**
+/// - `path`: Rotated file.
+///
+/// **Returns:**
+/// - `Ok(())` once the original is removed.
+///
+/// **Errors / Failures:**
+/// - Returns an I/O error if the file cannot be read, the copy written, or the original
+///   removed; the uncompressed file is then left in place.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// compress_file(Path::new("access.log.20261014-000000"))?;
+/// ```
+fn compress_file(path: &Path) -> io::Result<()> {
+    let mut compressed = path.as_os_str().to_os_string();
+    compressed.push(".gz");
+    fs::write(&compressed, gzip::compress(&fs::read(path)?))?;
+    fs::remove_file(path)
+}
+
+
+/// # prune
+///
+/// **Purpose:**
+/// Deletes the oldest rotated files of a log beyond the retention count.
+///
+/// **Parameters:**
+/// - `path`: Path of the live file, whose rotated files share its name as a prefix.
+/// - `keep`: Rotated files to keep.
+///
+/// **Returns:**
+/// - `Ok(())` once pruned.
+///
+/// **Errors / Failures:**
+/// - Returns an I/O error if the directory cannot be listed. Files that cannot be deleted are skipped.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// prune(Path::new("logs/access.log"), 7)?;
+/// ```
+fn prune(path: &Path, keep: usize) -> io::Result<()> {
+    let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+        return Ok(());
+    };
+    let prefix = format!("{name}.");
+    let directory = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    let mut rotated: Vec<(String, PathBuf)> = fs::read_dir(directory)?
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let file_name = entry.file_name().into_string().ok()?;
+            let stamp = file_name.strip_prefix(&prefix)?;
+            stamp.starts_with(|c: char| c.is_ascii_digit()).then(|| (stamp.to_string(), entry.path()))
+        })
+        .collect();
+    rotated.sort();
+    let excess = rotated.len().saturating_sub(keep);
+    for (_, old) in rotated.into_iter().take(excess) {
+        let _ = fs::remove_file(old);
+    }
+    Ok(())
+}
+
+
+/// # unix_seconds
+///
+/// **Purpose:**
+/// Converts a time to whole seconds since the Unix epoch.
+///
+/// **Parameters:**
+/// - `time`: Time to convert.
+///
+/// **Returns:**
+/// - The seconds, or `0` before the epoch.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let now = unix_seconds(SystemTime::now());
+/// ```
+fn unix_seconds(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH).map_or(0, |elapsed| elapsed.as_secs())
+}
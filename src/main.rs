@@ -7,39 +7,83 @@
 //! # Daegonica Module: Server Main
 //!
 //! **Purpose:**
-//! Entry point for the Daegonica experimental server. Sets up TCP listener and thread pool, and handles incoming HTTP requests.
+//! Entry point for the Daegonica experimental server. Configures a `Server` from command-line flags and runs it.
 //!
 //! **Context:**
 //! - Used as the main executable for the server project.
 //!
 //! **Responsibilities:**
-//! - Accepts incoming TCP connections.
-//! - Dispatches requests to worker threads.
-//! - Handles basic HTTP GET requests and serves HTML files.
-//! - Does NOT handle advanced routing, security, or persistent state.
+//! - Defines the default site: the `/sleep` route, files from `html/`, and the 404 page.
+//! - Adds named virtual hosts from `--vhost <name>=<root>` flags.
+//! - Writes one line per request to `--access-log <path>`.
+//! - Applies `--cache-control '<pattern> => <policy>'` rules to served files.
+//! - Renders `.md` files with `--markdown`, lists index-less directories with `--listing`, and tags files as
+//!   `--etag weak|strong|off` says.
+//! - Follows symbolic links as `--symlinks follow|within-root|refuse` says, and confines every open beneath
+//!   `html/` with `--sandbox`.
+//! - Types files from `--mime-types <file>`, falling back to `--default-type <type>` and labelling text with
+//!   `--charset <label|none>`.
+//! - Redirects matching paths from `--redirect '<pattern> -> <target> [status]'` flags and remaps them
+//!   from `--rewrite '<pattern> <replacement> [last|redirect|permanent]'` flags.
+//! - Forwards every request to an upstream server when started with `--proxy <host:port>`.
+//! - Acts as a forward proxy (absolute-form targets and `CONNECT`) when started with `--forward-proxy`,
+//!   tunnelling only to `--connect-ports <p1,p2,...>` and reaching loopback and private addresses only for
+//!   `--allow-destination <range>` ranges. The listener stays on loopback; clients must log in with a user
+//!   from `--proxy-htpasswd <file>` when one is given.
+//! - Sheds connections beyond `--max-concurrent <n>` with `503 Service Unavailable`.
+//! - Announces assets in `103 Early Hints` from `--preload '<pattern> <href> <as>'` flags.
+//! - Parks idle keep-alive connections in an event loop with `--event-loop`.
+//! - Serves connections through `io_uring` with `--io-uring` (built with the `io_uring` feature).
+//! - Switches to an unprivileged account after binding with `--user <name>` and `--group <name>`.
+//! - Detaches into the background with `--daemon`, logging to `--log-file <path>` and recording its id in
+//!   `--pid-file <path>`.
+//! - Runs as the Windows service named by `--service <name>`.
+//! - Reloads open pages whenever `html/` changes with `--dev`.
+//! - Drains on `SIGTERM`, closing connections still open after `--drain-deadline <seconds>` (default 30).
+//! - Does NOT read a configuration file; every setting comes from the flags above.
 //!
 //! **Author:** Daegonica Software
 //! **Version:** 0.1.0
-//! **Last Updated:** 2025-12-04
+//! **Last Updated:** 2026-10-14
 //!
 //! ---------------------------------------------------------------
 //! This file is part of the Daegonica Software codebase.
 //! ---------------------------------------------------------------
 
 use std::{
+    env,
     fs,
-    io::{prelude::*, BufReader},
-    net::{TcpListener, TcpStream},
     thread,
     time::Duration,
 };
 
-use server::ThreadPool;
+use server::{
+    access_log::AccessLog,
+    auth::BasicAuth,
+    concurrency::ConcurrencyLimit,
+    daemon::{Daemon, PidFile},
+    dev::DevReload,
+    early_hints::EarlyHints,
+    mime::MimeTypes,
+    privileges::Privileges,
+    proxy::{ForwardProxy, Proxy},
+    redirect::Redirect,
+    request::Request,
+    response::Response,
+    rewrite::{RewriteFlag, RewriteRule},
+    router::Router,
+    server::Server,
+    service::Service,
+    static_files::{EtagStrategy, StaticFiles, SymlinkPolicy},
+    tasks::{Restart, Tasks},
+    vhost::VirtualHost,
+    watch::Watcher,
+};
 
 /// # main
 ///
 /// **Purpose:**
-/// Starts the TCP server, initializes the thread pool, and dispatches incoming connections to worker threads.
+/// Builds the server from command-line flags and serves connections until accepting fails.
 ///
 /// **Parameters:**
 /// None.
@@ -48,55 +92,585 @@ use server::ThreadPool;
 /// None.
 ///
 /// **Errors / Failures:**
-/// - Panics if the TCP listener cannot be bound or a stream cannot be unwrapped.
+/// - Panics if the TCP listener cannot be bound, a log file cannot be opened, the `--dev` watcher cannot start,
+///   privileges cannot be dropped, `--daemon` cannot detach, `--service` lacks its name or cannot run,
+///   `--drain-deadline` is not a number of seconds, or accepting fails.
 ///
 /// **Examples:**
 /// ```rust
 /// // Run with `cargo run` to start the server.
+/// // Run with `cargo run -- --proxy 127.0.0.1:3000` to proxy to an upstream.
+/// // Run with `cargo run -- --forward-proxy --connect-ports 443,8443` to act as a forward proxy.
+/// // Run with `cargo run -- --cache-control '/** => no-cache'` to set a file caching policy.
+/// // Run with `cargo run -- --vhost example.com=/srv/example --access-log access.log` for more sites.
+/// // Run with `cargo run -- --rewrite '^/old/(.*)$ /new/$1 permanent'` to remap legacy URLs.
+/// // Run with `cargo run -- --redirect '/old-blog/** -> https://blog.example.com/$1 308'` to redirect.
+/// // Run with `cargo run -- --max-concurrent 64` to answer 503 instead of queueing under load.
+/// // Run with `cargo run -- --preload '/ /style.css style'` to send 103 Early Hints for the home page.
+/// // Run with `cargo run -- --dev --markdown` to reload open pages whenever html/ changes.
+/// // Run with `cargo run -- --event-loop` so idle keep-alive connections hold no worker.
+/// // Run with `cargo run --features io_uring -- --io-uring` to serve through io_uring on Linux.
+/// // Run with `sudo cargo run -- --user www-data` to stop running as root once the port is bound.
+/// // Run with `cargo run -- --daemon --pid-file server.pid --log-file server.log` to serve in the background.
+/// // Register `server.exe --service daegonica` with `sc.exe create daegonica` to run as a Windows service.
+/// // Run with `cargo run -- --drain-deadline 10` to give connections ten seconds to finish after SIGTERM.
 /// main();
 /// ```
 fn main() {
-    let listener = TcpListener::bind("127.0.0.1:7878").unwrap();
-    let pool = ThreadPool::new(4);
-    for stream in listener.incoming().take(2) {
-        let stream = stream.unwrap();
-        pool.execute(|| {
-            handle_connection(stream);
-        });
+    let _pid_file = daemon_from_args();
+    let mut site = VirtualHost::new()
+        .router(Router::new().get("/sleep", sleep))
+        .files(files_from_args())
+        .not_found(not_found);
+    for redirect in redirects_from_args() {
+        site = site.redirect(redirect);
+    }
+    for rule in rewrites_from_args() {
+        site = site.rewrite(rule);
+    }
+    if let Some(log) = access_log_from_args() {
+        site = site.access_log(log);
+    }
+
+    let mut server = Server::new("127.0.0.1:7878").workers(4).default_host(site);
+    for host in vhosts_from_args() {
+        server = server.vhost(host);
+    }
+    if let Some(proxy) = proxy_from_args() {
+        server = server.proxy(proxy);
+    }
+    if let Some(forward_proxy) = forward_proxy_from_args() {
+        server = server.forward_proxy(forward_proxy);
+    }
+    if let Some(limit) = concurrency_limit_from_args() {
+        server = server.concurrency_limit(limit);
+    }
+    server = server.early_hints(early_hints_from_args());
+    if env::args().any(|arg| arg == "--dev") {
+        let reload = DevReload::new();
+        let watcher = reload.watch(Watcher::new("html"));
+        let tasks = Tasks::new().spawn("fs-watch", Restart::OnPanic, move |ctx| watcher.run(ctx));
+        server = server.dev_reload(reload).tasks(tasks);
+    }
+    if env::args().any(|arg| arg == "--event-loop") {
+        server = server.event_loop(true);
+    }
+    #[cfg(feature = "io_uring")]
+    if env::args().any(|arg| arg == "--io-uring") {
+        server = server.io_uring(true);
+    }
+    if let Some(privileges) = privileges_from_args() {
+        server = server.privileges(privileges);
+    }
+    server = server.drain_on_sigterm(drain_deadline_from_args());
+    let mut args = env::args().skip_while(|arg| arg != "--service");
+    if args.next().is_some() {
+        let name = args.next().expect("--service requires a service name");
+        Service::new(name).run(server).unwrap();
+        return;
+    }
+    server.run().unwrap();
+}
+
+/// # files_from_args
+///
+/// **Purpose:**
+/// Builds the static file handler for `html/`, adding every `--cache-control '<pattern> => <policy>'` rule in order
+/// rendering `.md` files as HTML when `--markdown` is given, listing index-less directories when `--listing` is,
+/// tagging files as `--etag weak|strong|off` says, adding the mappings of a `--mime-types <file>`, and sending
+/// `--default-type <type>` for unknown extensions, labelling text files with `--charset <label|none>`, and
+/// following symbolic links as `--symlinks follow|within-root|refuse` says, and confining every open beneath the
+/// root when `--sandbox` is given.
+///
+/// **Parameters:**
+/// None.
+///
+/// **Returns:**
+/// - The configured `StaticFiles` handler.
+///
+/// **Errors / Failures:**
+/// - Panics if a `--cache-control` value is missing or lacks the `=>` separator, or if `--etag` is not followed by
+///   `weak`, `strong`, or `off`, or `--symlinks` by `follow`, `within-root`, or `refuse`.
+/// - Panics if `--mime-types`, `--default-type`, or `--charset` lacks its value, or the `mime.types` file cannot be read.
+/// - Panics if `--sandbox` is given and `html/` cannot be opened as a directory.
+///
+/// **Examples:**
+/// ```rust
+/// let files = files_from_args();
+/// ```
+fn files_from_args() -> StaticFiles {
+    let mut files = StaticFiles::new("html").index("hello.html");
+    let mut types = MimeTypes::new();
+    let mut args = env::args();
+    while let Some(arg) = args.next() {
+        if arg == "--cache-control" {
+            let rule = args.next().expect("--cache-control requires '<pattern> => <policy>'");
+            let (pattern, policy) = rule
+                .split_once("=>")
+                .expect("--cache-control rules look like '<pattern> => <policy>'");
+            files = files.cache_control(pattern.trim(), policy.trim());
+        } else if arg == "--markdown" {
+            files = files.markdown();
+        } else if arg == "--listing" {
+            files = files.directory_listing();
+        } else if arg == "--etag" {
+            files = files.etag(match args.next().as_deref() {
+                Some("weak") => EtagStrategy::Weak,
+                Some("strong") => EtagStrategy::Strong,
+                Some("off") => EtagStrategy::Disabled,
+                _ => panic!("--etag requires weak, strong, or off"),
+            });
+        } else if arg == "--symlinks" {
+            files = files.symlinks(match args.next().as_deref() {
+                Some("follow") => SymlinkPolicy::Follow,
+                Some("within-root") => SymlinkPolicy::WithinRoot,
+                Some("refuse") => SymlinkPolicy::Refuse,
+                _ => panic!("--symlinks requires follow, within-root, or refuse"),
+            });
+        } else if arg == "--sandbox" {
+            files = files.sandbox().expect("--sandbox requires html/ to be a readable directory");
+        } else if arg == "--mime-types" {
+            let path = args.next().expect("--mime-types requires a mime.types file");
+            types = types.load(&path).unwrap_or_else(|err| panic!("cannot read {path}: {err}"));
+        } else if arg == "--default-type" {
+            types = types.fallback(args.next().expect("--default-type requires a media type"));
+        } else if arg == "--charset" {
+            let charset = args.next().expect("--charset requires a charset label or none");
+            types = types.charset(Some(charset.as_str()).filter(|label| *label != "none"));
+        }
     }
+    files.mime_types(types)
 }
 
-/// # handle_connection
+/// # proxy_from_args
 ///
 /// **Purpose:**
-/// Processes a single TCP stream, parses the HTTP request, and sends an appropriate HTML response.
+/// Reads the optional `--proxy <host:port>` command-line flag.
 ///
 /// **Parameters:**
-/// - `stream`: TCP stream representing the client connection.
+/// None.
 ///
 /// **Returns:**
+/// - `Some(Proxy)` when the flag is present, otherwise `None`.
+///
+/// **Errors / Failures:**
+/// - Panics if `--proxy` is given without an address.
+///
+/// **Examples:**
+/// ```rust
+/// let proxy = proxy_from_args();
+/// ```
+fn proxy_from_args() -> Option<Proxy> {
+    let mut args = env::args().skip_while(|arg| arg != "--proxy");
+    args.next()?;
+    let upstream = args.next().expect("--proxy requires an upstream address");
+    Some(Proxy::new(upstream))
+}
+
+/// # forward_proxy_from_args
+///
+/// **Purpose:**
+/// Reads the `--forward-proxy` flag, its optional `--connect-ports <p1,p2,...>` allowlist, every
+/// `--allow-destination <range>` exemption from the private-address block, and the optional
+/// `--proxy-htpasswd <file>` of users allowed to use the proxy.
+///
+/// **Parameters:**
 /// None.
 ///
+/// **Returns:**
+/// - `Some(ForwardProxy)` when `--forward-proxy` is present, otherwise `None`.
+///
 /// **Errors / Failures:**
-/// - Panics if reading from the stream or writing the response fails.
+/// - Panics if `--connect-ports` is missing its value or lists an invalid port, if an
+///   `--allow-destination` range is missing or malformed, or if `--proxy-htpasswd` lacks its
+///   file or the file cannot be read.
 ///
 /// **Examples:**
 /// ```rust
-/// handle_connection(stream);
+/// let forward_proxy = forward_proxy_from_args();
 /// ```
-fn handle_connection(mut stream: TcpStream) {
-    let buf_reader = BufReader::new(&stream);
-    let request_line = buf_reader.lines().next().unwrap().unwrap();
-    let (status_line, filename) = match &request_line[..] {
-        "GET / HTTP/1.1" => ("HTTP/1.1 200 OK", "html/hello.html"),
-        "GET /sleep HTTP/1.1" => {
-            thread::sleep(Duration::from_secs(5));
-            ("HTTP/1.1 200 OK", "html/hello.html")
-        },
-        _ => ("HTTP/1.1 404 WHAT THE HELL ARE YOU DOING HERE?!?!", "html/404.html"),
-    };
-    let contents = fs::read_to_string(filename).unwrap();
-    let length = contents.len();
-    let response = format!("{status_line}\r\nContent-Length: {length}\r\n\r\n{contents}");
-    stream.write_all(response.as_bytes()).unwrap();
-}
\ No newline at end of file
+fn forward_proxy_from_args() -> Option<ForwardProxy> {
+    if !env::args().any(|arg| arg == "--forward-proxy") {
+        return None;
+    }
+
+    let mut proxy = ForwardProxy::new();
+    let mut args = env::args();
+    while let Some(arg) = args.next() {
+        if arg == "--allow-destination" {
+            let range = args.next().expect("--allow-destination requires an address range");
+            proxy = proxy.allow_destination(&range).expect("--allow-destination takes a CIDR range or address");
+        } else if arg == "--proxy-htpasswd" {
+            let path = args.next().expect("--proxy-htpasswd requires an htpasswd file");
+            let users = BasicAuth::new("Proxy").htpasswd(&path).unwrap_or_else(|err| panic!("cannot read {path}: {err}"));
+            proxy = proxy.authenticate(users);
+        }
+    }
+
+    let mut args = env::args().skip_while(|arg| arg != "--connect-ports");
+    if args.next().is_none() {
+        return Some(proxy);
+    }
+    let ports = args
+        .next()
+        .expect("--connect-ports requires a comma-separated port list")
+        .split(',')
+        .map(|port| port.trim().parse().expect("--connect-ports lists an invalid port"))
+        .collect();
+    Some(proxy.allow_ports(ports))
+}
+
+/// # vhosts_from_args
+///
+/// **Purpose:**
+/// Builds one virtual host per `--vhost <name>=<root>` flag, serving files from `<root>`.
+///
+/// **Parameters:**
+/// None.
+///
+/// **Returns:**
+/// - The configured virtual hosts, in flag order.
+///
+/// **Errors / Failures:**
+/// - Panics if a `--vhost` value is missing or lacks the `=` separator.
+///
+/// **Examples:**
+/// ```rust
+/// let hosts = vhosts_from_args();
+/// ```
+fn vhosts_from_args() -> Vec<VirtualHost> {
+    let mut hosts = Vec::new();
+    let mut args = env::args();
+    while let Some(arg) = args.next() {
+        if arg == "--vhost" {
+            let spec = args.next().expect("--vhost requires '<name>=<root>'");
+            let (name, root) = spec.split_once('=').expect("--vhost values look like '<name>=<root>'");
+            hosts.push(VirtualHost::new().name(name).files(StaticFiles::new(root)));
+        }
+    }
+    hosts
+}
+
+/// # redirects_from_args
+///
+/// **Purpose:**
+/// Builds one redirect per `--redirect '<pattern> -> <target> [status]'` flag; the status defaults to `301`.
+///
+/// **Parameters:**
+/// None.
+///
+/// **Returns:**
+/// - The configured redirects, in flag order.
+///
+/// **Errors / Failures:**
+/// - Panics if a `--redirect` value is missing, lacks the `->` separator, or has an invalid status.
+///
+/// **Examples:**
+/// ```rust
+/// let redirects = redirects_from_args();
+/// ```
+fn redirects_from_args() -> Vec<Redirect> {
+    let mut redirects = Vec::new();
+    let mut args = env::args();
+    while let Some(arg) = args.next() {
+        if arg == "--redirect" {
+            let spec = args.next().expect("--redirect requires '<pattern> -> <target> [status]'");
+            let (pattern, rest) = spec
+                .split_once("->")
+                .expect("--redirect values look like '<pattern> -> <target> [status]'");
+            let mut rest = rest.split_whitespace();
+            let target = rest.next().expect("--redirect requires a target");
+            let status = match rest.next() {
+                Some(status) => match status.parse() {
+                    Ok(status @ (301 | 302 | 303 | 307 | 308)) => status,
+                    _ => panic!("--redirect status must be 301, 302, 303, 307, or 308"),
+                },
+                None => 301,
+            };
+            redirects.push(Redirect::permanent(pattern.trim(), target).status(status));
+        }
+    }
+    redirects
+}
+
+/// # rewrites_from_args
+///
+/// **Purpose:**
+/// Builds one rewrite rule per `--rewrite '<pattern> <replacement> [last|redirect|permanent]'` flag.
+/// Without a flag word the rewrite is internal and later rules still run.
+///
+/// **Parameters:**
+/// None.
+///
+/// **Returns:**
+/// - The configured rules, in flag order.
+///
+/// **Errors / Failures:**
+/// - Panics if a `--rewrite` value is missing, malformed, or has an invalid pattern.
+///
+/// **Examples:**
+/// ```rust
+/// let rules = rewrites_from_args();
+/// ```
+fn rewrites_from_args() -> Vec<RewriteRule> {
+    let mut rules = Vec::new();
+    let mut args = env::args();
+    while let Some(arg) = args.next() {
+        if arg == "--rewrite" {
+            let spec = args.next().expect("--rewrite requires '<pattern> <replacement> [flag]'");
+            let parts: Vec<&str> = spec.split_whitespace().collect();
+            let flag = match parts.get(2).copied() {
+                None => RewriteFlag::Internal,
+                Some("last") => RewriteFlag::Last,
+                Some("redirect") => RewriteFlag::Redirect(302),
+                Some("permanent") => RewriteFlag::Redirect(301),
+                Some(other) => panic!("unknown --rewrite flag '{other}'"),
+            };
+            let [pattern, replacement, ..] = parts[..] else {
+                panic!("--rewrite values look like '<pattern> <replacement> [flag]'");
+            };
+            rules.push(RewriteRule::new(pattern, replacement, flag).expect("--rewrite has an invalid pattern"));
+        }
+    }
+    rules
+}
+
+/// # access_log_from_args
+///
+/// **Purpose:**
+/// Opens the default site's access log from the optional `--access-log <path>` flag.
+///
+/// **Parameters:**
+/// None.
+///
+/// **Returns:**
+/// - `Some(AccessLog)` when the flag is present, otherwise `None`.
+///
+/// **Errors / Failures:**
+/// - Panics if the flag has no path or the file cannot be opened.
+///
+/// **Examples:**
+/// ```rust
+/// let log = access_log_from_args();
+/// ```
+fn access_log_from_args() -> Option<AccessLog> {
+    let mut args = env::args().skip_while(|arg| arg != "--access-log");
+    args.next()?;
+    let path = args.next().expect("--access-log requires a file path");
+    Some(AccessLog::open(path).expect("cannot open access log"))
+}
+
+/// # concurrency_limit_from_args
+///
+/// **Purpose:**
+/// Reads the `--max-concurrent <n>` flag.
+///
+/// **Parameters:**
+/// None.
+///
+/// **Returns:**
+/// - `Some(ConcurrencyLimit)` when the flag is present, otherwise `None`.
+///
+/// **Errors / Failures:**
+/// - Panics if the flag has no value or the value is not a number.
+///
+/// **Examples:**
+/// ```rust
+/// let limit = concurrency_limit_from_args();
+/// ```
+fn concurrency_limit_from_args() -> Option<ConcurrencyLimit> {
+    let mut args = env::args().skip_while(|arg| arg != "--max-concurrent");
+    args.next()?;
+    let limit = args
+        .next()
+        .expect("--max-concurrent requires a connection count")
+        .parse()
+        .expect("--max-concurrent must be a number");
+    Some(ConcurrencyLimit::new(limit))
+}
+
+/// # early_hints_from_args
+///
+/// **Purpose:**
+/// Collects `--preload '<pattern> <href> <as>'` flags into early hints.
+///
+/// **Parameters:**
+/// None.
+///
+/// **Returns:**
+/// - The declared hints, empty when no flag is given.
+///
+/// **Errors / Failures:**
+/// - Panics if a `--preload` value is missing or does not have three parts.
+///
+/// **Examples:**
+/// ```rust
+/// let hints = early_hints_from_args();
+/// ```
+fn early_hints_from_args() -> EarlyHints {
+    let mut hints = EarlyHints::new();
+    let mut args = env::args();
+    while let Some(arg) = args.next() {
+        if arg == "--preload" {
+            let spec = args.next().expect("--preload requires '<pattern> <href> <as>'");
+            let [pattern, href, kind] = spec.split_whitespace().collect::<Vec<_>>()[..] else {
+                panic!("--preload values look like '<pattern> <href> <as>'");
+            };
+            hints = hints.preload(pattern, href, kind);
+        }
+    }
+    hints
+}
+
+/// # daemon_from_args
+///
+/// **Purpose:**
+/// Detaches into the background when `--daemon` is given, with output appended to `--log-file <path>` (default
+/// `server.log`) and the process id written to `--pid-file <path>` (default `server.pid`). The daemon stays in the
+/// directory it was started from, since the document roots given on the command line are relative to it. Runs
+/// before any thread is started, since only the calling thread survives the fork.
+///
+/// **Parameters:**
+/// None.
+///
+/// **Returns:**
+/// - In the daemon, the guard that removes the PID file once `main` returns; `None` in the foreground.
+///
+/// **Errors / Failures:**
+/// - Panics if `--log-file` or `--pid-file` lacks its value, or the daemon cannot be started (for instance because
+///   the PID file names a running server).
+///
+/// **Examples:**
+/// ```rust
+/// let _pid_file = daemon_from_args();
+/// ```
+fn daemon_from_args() -> Option<PidFile> {
+    if !env::args().any(|arg| arg == "--daemon") {
+        return None;
+    }
+    let (mut log_file, mut pid_file) = ("server.log".to_string(), "server.pid".to_string());
+    let mut args = env::args();
+    while let Some(arg) = args.next() {
+        if arg == "--log-file" {
+            log_file = args.next().expect("--log-file requires a file path");
+        } else if arg == "--pid-file" {
+            pid_file = args.next().expect("--pid-file requires a file path");
+        }
+    }
+    let here = env::current_dir().expect("cannot read the working directory");
+    let daemon = Daemon::new().pid_file(pid_file).working_directory(here).stdout(&log_file).stderr(&log_file);
+    Some(daemon.detach().unwrap_or_else(|err| panic!("cannot start daemon: {err}")))
+}
+
+/// # drain_deadline_from_args
+///
+/// **Purpose:**
+/// Reads the `--drain-deadline <seconds>` flag.
+///
+/// **Parameters:**
+/// None.
+///
+/// **Returns:**
+/// - How long to drain after `SIGTERM`; thirty seconds when the flag is absent.
+///
+/// **Errors / Failures:**
+/// - Panics if the flag has no value or the value is not a whole number of seconds.
+///
+/// **Examples:**
+/// ```rust
+/// let server = server.drain_on_sigterm(drain_deadline_from_args());
+/// ```
+fn drain_deadline_from_args() -> Duration {
+    let mut args = env::args().skip_while(|arg| arg != "--drain-deadline");
+    if args.next().is_none() {
+        return Duration::from_secs(30);
+    }
+    let seconds = args
+        .next()
+        .expect("--drain-deadline requires a number of seconds")
+        .parse()
+        .expect("--drain-deadline must be a whole number of seconds");
+    Duration::from_secs(seconds)
+}
+
+/// # privileges_from_args
+///
+/// **Purpose:**
+/// Reads the `--user <name>` and `--group <name>` flags.
+///
+/// **Parameters:**
+/// None.
+///
+/// **Returns:**
+/// - `Some(Privileges)` when either flag is present, otherwise `None`.
+///
+/// **Errors / Failures:**
+/// - Panics if a flag has no value.
+///
+/// **Examples:**
+/// ```rust
+/// let privileges = privileges_from_args();
+/// ```
+fn privileges_from_args() -> Option<Privileges> {
+    let mut privileges = None;
+    let mut args = env::args();
+    while let Some(arg) = args.next() {
+        if arg == "--user" {
+            let user = args.next().expect("--user requires a user name");
+            privileges = Some(privileges.unwrap_or_else(Privileges::new).user(user));
+        } else if arg == "--group" {
+            let group = args.next().expect("--group requires a group name");
+            privileges = Some(privileges.unwrap_or_else(Privileges::new).group(group));
+        }
+    }
+    privileges
+}
+
+/// # sleep
+///
+/// **Purpose:**
+/// Slow route used to demonstrate concurrent workers: waits five seconds, then serves the hello page.
+///
+/// **Parameters:**
+/// - `_request`: Parsed client request (unused).
+///
+/// **Returns:**
+/// - A `200 OK` response with `html/hello.html`.
+///
+/// **Errors / Failures:**
+/// None. A missing page yields an empty body.
+///
+/// **Examples:**
+/// ```rust
+/// let router = Router::new().get("/sleep", sleep);
+/// ```
+fn sleep(_request: &Request) -> Response {
+    thread::sleep(Duration::from_secs(5));
+    Response::new(200, "OK")
+        .with_header("Content-Type", "text/html; charset=utf-8")
+        .with_body(fs::read("html/hello.html").unwrap_or_default())
+}
+
+/// # not_found
+///
+/// **Purpose:**
+/// Serves the `html/404.html` page for requests nothing else answered.
+///
+/// **Parameters:**
+/// - `_request`: Parsed client request (unused).
+///
+/// **Returns:**
+/// - A `404` response with the 404 page.
+///
+/// **Errors / Failures:**
+/// None. A missing page yields an empty body.
+///
+/// **Examples:**
+/// ```rust
+/// let site = VirtualHost::new().not_found(not_found);
+/// ```
+fn not_found(_request: &Request) -> Response {
+    Response::new(404, "WHAT THE HELL ARE YOU DOING HERE?!?!")
+        .with_header("Content-Type", "text/html; charset=utf-8")
+        .with_body(fs::read("html/404.html").unwrap_or_default())
+}
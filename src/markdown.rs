@@ -0,0 +1,1120 @@
+
+// ============================================================
+//  DAEGONICA SOFTWARE — markdown.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: Markdown
+//!
+//! **Purpose:**
+//! Converts Markdown documents to HTML.
+//!
+//! **Context:**
+//! - Used by `StaticFiles` when Markdown rendering is enabled, so `.md` files under a mount are
+//!   served as HTML pages.
+//!
+//! **Responsibilities:**
+//! - Renders ATX and setext headings (with slug `id`s), paragraphs, block quotes, nested ordered
+//!   and unordered lists, fenced code blocks, and horizontal rules.
+//! - Renders inline code, emphasis, strong emphasis, links, images, autolinks, backslash escapes,
+//!   and hard line breaks.
+//! - Escapes everything else, raw HTML included, and replaces link and image targets whose
+//!   scheme could run script, so documents cannot inject markup.
+//! - Does NOT support tables, footnotes, reference-style links, or indented code blocks.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+use crate::template::escape_html;
+
+
+/// # ListMarker
+///
+/// **Summary:**
+/// The marker opening a list item.
+///
+/// **Fields:**
+/// - `ordered`: Whether the marker is a number (`1.`) rather than a bullet (`-`).
+/// - `start`: Number of an ordered marker.
+/// - `delimiter`: The bullet character, or `.`/`)` after the number.
+/// - `width`: Columns from the start of the line to the item's content.
+#[derive(Debug, Clone, Copy)]
+struct ListMarker {
+    ordered: bool,
+    start: u64,
+    delimiter: char,
+    width: usize,
+}
+
+
+/// # to_html
+///
+/// **Purpose:**
+/// Converts a Markdown document to an HTML fragment.
+///
+/// **Parameters:**
+/// - `source`: Markdown text.
+///
+/// **Returns:**
+/// - The HTML, without a surrounding page.
+///
+/// **Errors / Failures:**
+/// None. Markup that is not understood is rendered as escaped text.
+///
+/// **Examples:**
+/// ```rust
+/// # use server::markdown;
+/// assert_eq!(markdown::to_html("# Hi\n\n*there*"), "<h1 id=\"hi\">Hi</h1>\n<p><em>there</em></p>\n");
+/// ```
+pub fn to_html(source: &str) -> String {
+    let lines: Vec<String> = source.lines().map(|line| line.replace('\t', "    ")).collect();
+    let lines: Vec<&str> = lines.iter().map(String::as_str).collect();
+    let mut html = String::new();
+    render_blocks(&lines, false, &mut html);
+    html
+}
+
+
+/// # title
+///
+/// **Purpose:**
+/// Finds a document's title: the text of its first level-one heading.
+///
+/// **Parameters:**
+/// - `source`: Markdown text.
+///
+/// **Returns:**
+/// - The heading as plain text, or `None` if the document has no `# ` heading outside code.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust
+/// # use server::markdown;
+/// assert_eq!(markdown::title("# Guide\n\nText").as_deref(), Some("Guide"));
+/// ```
+pub fn title(source: &str) -> Option<String> {
+    let mut fence: Option<String> = None;
+    for line in source.lines() {
+        let trimmed = line.trim_start();
+        if let Some(open) = &fence {
+            if trimmed.starts_with(open.as_str()) {
+                fence = None;
+            }
+            continue;
+        }
+        if let Some((marker, _)) = fence_open(line) {
+            fence = Some(marker);
+            continue;
+        }
+        if let Some((1, text)) = atx_heading(line) {
+            return Some(plain(text));
+        }
+    }
+    None
+}
+
+
+/// # render_blocks
+///
+/// **Purpose:**
+/// Renders a run of lines as block elements.
+///
+/// **Parameters:**
+/// - `lines`: Lines of the block container, with tabs expanded.
+/// - `tight`: Whether paragraphs are emitted without `<p>` tags, as in a tight list item.
+/// - `html`: Output buffer.
+///
+/// **Returns:**
+/// None.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// render_blocks(&["> quoted"], false, &mut html);
+/// ```
+fn render_blocks(lines: &[&str], tight: bool, html: &mut String) {
+    let mut i = 0;
+    while i < lines.len() {
+        let line = lines[i];
+        if line.trim().is_empty() {
+            i += 1;
+        } else if let Some((marker, language)) = fence_open(line) {
+            i += 1;
+            let indent = line.len() - line.trim_start().len();
+            let mut code = String::new();
+            while i < lines.len() && !is_fence_close(lines[i], &marker) {
+                code.push_str(strip_indent(lines[i], indent));
+                code.push('\n');
+                i += 1;
+            }
+            i += 1;
+            match language {
+                Some(language) => html.push_str(&format!("<pre><code class=\"language-{}\">", escape_html(&language))),
+                None => html.push_str("<pre><code>"),
+            }
+            html.push_str(&escape_html(&code));
+            html.push_str("</code></pre>\n");
+        } else if let Some((level, text)) = atx_heading(line) {
+            push_heading(level, text, html);
+            i += 1;
+        } else if is_rule(line) {
+            html.push_str("<hr />\n");
+            i += 1;
+        } else if let Some(first) = quote_line(line) {
+            let mut quoted = vec![first];
+            i += 1;
+            while i < lines.len() {
+                match quote_line(lines[i]) {
+                    Some(rest) => quoted.push(rest),
+                    None if !lines[i].trim().is_empty() && !starts_block(lines[i]) => quoted.push(lines[i]),
+                    None => break,
+                }
+                i += 1;
+            }
+            html.push_str("<blockquote>\n");
+            render_blocks(&quoted, false, html);
+            html.push_str("</blockquote>\n");
+        } else if let Some(marker) = list_marker(line) {
+            i = render_list(lines, i, marker, html);
+        } else {
+            let mut paragraph = vec![line.trim_start()];
+            i += 1;
+            let mut level = None;
+            while i < lines.len() && !lines[i].trim().is_empty() {
+                let next = lines[i].trim_start();
+                if next.trim_end().chars().all(|c| c == '=') {
+                    level = Some(1);
+                } else if next.trim_end().len() >= 2 && next.trim_end().chars().all(|c| c == '-') {
+                    level = Some(2);
+                } else if starts_block(lines[i]) {
+                    break;
+                } else {
+                    paragraph.push(next);
+                    i += 1;
+                    continue;
+                }
+                i += 1;
+                break;
+            }
+            let joined = paragraph.join("\n");
+            let text = joined.trim_end();
+            match level {
+                Some(level) => push_heading(level, text, html),
+                None if tight => {
+                    html.push_str(&inline(text));
+                    html.push('\n');
+                }
+                None => {
+                    html.push_str("<p>");
+                    html.push_str(&inline(text));
+                    html.push_str("</p>\n");
+                }
+            }
+        }
+    }
+}
+
+
+/// # render_list
+///
+/// **Purpose:**
+/// Renders the list starting at line `start`, nesting whatever is indented under each item.
+///
+/// **Parameters:**
+/// - `lines`: Lines of the enclosing container.
+/// - `start`: Index of the list's first line.
+/// - `first`: Marker of the first item; later items must use the same kind of marker.
+/// - `html`: Output buffer.
+///
+/// **Returns:**
+/// - Index of the first line after the list.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let next = render_list(&lines, i, marker, &mut html);
+/// ```
+fn render_list(lines: &[&str], start: usize, first: ListMarker, html: &mut String) -> usize {
+    let mut items: Vec<Vec<&str>> = Vec::new();
+    let mut loose = false;
+    let mut i = start;
+    let same_list = |line: &str| {
+        list_marker(line).filter(|marker| marker.ordered == first.ordered && marker.delimiter == first.delimiter)
+    };
+    while i < lines.len() {
+        let Some(marker) = same_list(lines[i]) else {
+            break;
+        };
+        let mut item = vec![lines[i].get(marker.width..).unwrap_or("")];
+        i += 1;
+        while i < lines.len() {
+            let line = lines[i];
+            let indent = line.len() - line.trim_start().len();
+            if line.trim().is_empty() {
+                item.push("");
+            } else if indent >= marker.width {
+                item.push(&line[marker.width..]);
+            } else if item.last().is_some_and(|last| !last.is_empty()) && !starts_block(line) {
+                item.push(line.trim());
+            } else {
+                break;
+            }
+            i += 1;
+        }
+
+        while item.last() == Some(&"") {
+            item.pop();
+            if i < lines.len() && same_list(lines[i]).is_some() {
+                loose = true;
+            }
+        }
+        if item.contains(&"") && has_blank_between_blocks(&item) {
+            loose = true;
+        }
+        items.push(item);
+    }
+
+    let (open, close) = match (first.ordered, first.start) {
+        (false, _) => ("<ul>".to_string(), "</ul>"),
+        (true, 1) => ("<ol>".to_string(), "</ol>"),
+        (true, number) => (format!("<ol start=\"{number}\">"), "</ol>"),
+    };
+    html.push_str(&open);
+    html.push('\n');
+    for item in items {
+        html.push_str("<li>");
+        let mut body = String::new();
+        render_blocks(&item, !loose, &mut body);
+        if loose {
+            html.push('\n');
+            html.push_str(&body);
+        } else {
+            html.push_str(body.trim_end());
+        }
+        html.push_str("</li>\n");
+    }
+    html.push_str(close);
+    html.push('\n');
+    i
+}
+
+
+/// # has_blank_between_blocks
+///
+/// **Purpose:**
+/// Checks whether a list item's own content is split by a blank line, which makes the list loose.
+///
+/// **Parameters:**
+/// - `item`: The item's lines, with trailing blanks removed.
+///
+/// **Returns:**
+/// - `true` if a blank line separates two of the item's top-level blocks.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// assert!(has_blank_between_blocks(&["one", "", "two"]));
+/// ```
+fn has_blank_between_blocks(item: &[&str]) -> bool {
+    item.windows(2).any(|pair| pair[0].is_empty() && !pair[1].is_empty() && !pair[1].starts_with(' '))
+}
+
+
+/// # push_heading
+///
+/// **Purpose:**
+/// Writes a heading element with an `id` derived from its text.
+///
+/// **Parameters:**
+/// - `level`: Heading level, `1` to `6`.
+/// - `text`: Heading text, still in Markdown.
+/// - `html`: Output buffer.
+///
+/// **Returns:**
+/// None.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// push_heading(2, "Getting started", &mut html);
+/// ```
+fn push_heading(level: usize, text: &str, html: &mut String) {
+    html.push_str(&format!("<h{level} id=\"{}\">{}</h{level}>\n", slug(text), inline(text)));
+}
+
+
+/// # slug
+///
+/// **Purpose:**
+/// Turns heading text into an anchor name: lowercase letters and digits joined by `-`.
+///
+/// **Parameters:**
+/// - `text`: Heading text.
+///
+/// **Returns:**
+/// - The slug.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// assert_eq!(slug("Getting Started!"), "getting-started");
+/// ```
+fn slug(text: &str) -> String {
+    let mut slug = String::with_capacity(text.len());
+    for c in text.chars() {
+        if c.is_alphanumeric() {
+            slug.extend(c.to_lowercase());
+        } else if (c.is_whitespace() || c == '-') && !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    slug.trim_end_matches('-').to_string()
+}
+
+
+/// # starts_block
+///
+/// **Purpose:**
+/// Checks whether a line opens a block that interrupts a paragraph.
+///
+/// **Parameters:**
+/// - `line`: Line to inspect.
+///
+/// **Returns:**
+/// - `true` for headings, fences, rules, quotes, and list items.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// assert!(starts_block("## Next"));
+/// ```
+fn starts_block(line: &str) -> bool {
+    atx_heading(line).is_some()
+        || fence_open(line).is_some()
+        || is_rule(line)
+        || quote_line(line).is_some()
+        || list_marker(line).is_some()
+}
+
+
+/// # atx_heading
+///
+/// **Purpose:**
+/// Parses a `#`-style heading line.
+///
+/// **Parameters:**
+/// - `line`: Line to inspect.
+///
+/// **Returns:**
+/// - The level and text, with any closing `#`s removed, or `None`.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// assert_eq!(atx_heading("## Usage ##"), Some((2, "Usage")));
+/// ```
+fn atx_heading(line: &str) -> Option<(usize, &str)> {
+    if line.len() - line.trim_start().len() > 3 {
+        return None;
+    }
+    let line = line.trim();
+    let level = line.chars().take_while(|&c| c == '#').count();
+    let rest = &line[level..];
+    if !(1..=6).contains(&level) || !(rest.is_empty() || rest.starts_with(' ')) {
+        return None;
+    }
+    let text = rest.trim();
+    let closed = text.trim_end_matches('#');
+    let text = if closed.is_empty() || closed.ends_with(' ') { closed.trim_end() } else { text };
+    Some((level, text))
+}
+
+
+/// # fence_open
+///
+/// **Purpose:**
+/// Parses the opening line of a fenced code block.
+///
+/// **Parameters:**
+/// - `line`: Line to inspect.
+///
+/// **Returns:**
+/// - The fence (three or more backticks or tildes) and the language named after it, or `None`.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// assert_eq!(fence_open("```rust"), Some(("```".to_string(), Some("rust".to_string()))));
+/// ```
+fn fence_open(line: &str) -> Option<(String, Option<String>)> {
+    let trimmed = line.trim_start();
+    if line.len() - trimmed.len() > 3 {
+        return None;
+    }
+    let fence_char = trimmed.chars().next().filter(|&c| c == '`' || c == '~')?;
+    let length = trimmed.chars().take_while(|&c| c == fence_char).count();
+    if length < 3 {
+        return None;
+    }
+    let info = trimmed[length..].trim();
+    if fence_char == '`' && info.contains('`') {
+        return None;
+    }
+    let language = info.split_whitespace().next().map(str::to_string);
+    Some((trimmed[..length].to_string(), language))
+}
+
+
+/// # is_fence_close
+///
+/// **Purpose:**
+/// Checks whether a line closes the fenced code block opened by `fence`.
+///
+/// **Parameters:**
+/// - `line`: Line to inspect.
+/// - `fence`: The opening fence.
+///
+/// **Returns:**
+/// - `true` if the line is a run of the same character at least as long, and nothing else.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// assert!(is_fence_close("````", "```"));
+/// ```
+fn is_fence_close(line: &str, fence: &str) -> bool {
+    let trimmed = line.trim();
+    let fence_char = fence.chars().next().unwrap_or('`');
+    trimmed.len() >= fence.len() && trimmed.chars().all(|c| c == fence_char)
+}
+
+
+/// # strip_indent
+///
+/// **Purpose:**
+/// Removes up to `indent` leading spaces from a line of fenced code.
+///
+/// **Parameters:**
+/// - `line`: Code line.
+/// - `indent`: Indentation of the opening fence.
+///
+/// **Returns:**
+/// - The line without that indentation.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// assert_eq!(strip_indent("   code", 2), " code");
+/// ```
+fn strip_indent(line: &str, indent: usize) -> &str {
+    let spaces = line.len() - line.trim_start_matches(' ').len();
+    &line[spaces.min(indent)..]
+}
+
+
+/// # is_rule
+///
+/// **Purpose:**
+/// Checks whether a line is a horizontal rule: three or more `-`, `*`, or `_`, optionally spaced.
+///
+/// **Parameters:**
+/// - `line`: Line to inspect.
+///
+/// **Returns:**
+/// - `true` for a rule.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// assert!(is_rule("* * *"));
+/// ```
+fn is_rule(line: &str) -> bool {
+    let compact: String = line.chars().filter(|c| !c.is_whitespace()).collect();
+    let Some(first) = compact.chars().next() else {
+        return false;
+    };
+    matches!(first, '-' | '*' | '_') && compact.len() >= 3 && compact.chars().all(|c| c == first)
+}
+
+
+/// # quote_line
+///
+/// **Purpose:**
+/// Strips the `>` marker from a block quote line.
+///
+/// **Parameters:**
+/// - `line`: Line to inspect.
+///
+/// **Returns:**
+/// - The quoted content, or `None` if the line is not part of a quote.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// assert_eq!(quote_line("> note"), Some("note"));
+/// ```
+fn quote_line(line: &str) -> Option<&str> {
+    let trimmed = line.trim_start();
+    if line.len() - trimmed.len() > 3 {
+        return None;
+    }
+    let rest = trimmed.strip_prefix('>')?;
+    Some(rest.strip_prefix(' ').unwrap_or(rest))
+}
+
+
+/// # list_marker
+///
+/// **Purpose:**
+/// Parses the marker of a list item line.
+///
+/// **Parameters:**
+/// - `line`: Line to inspect.
+///
+/// **Returns:**
+/// - The marker, or `None` if the line does not open a list item.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// assert!(list_marker("2. second").is_some_and(|marker| marker.ordered));
+/// ```
+fn list_marker(line: &str) -> Option<ListMarker> {
+    if is_rule(line) {
+        return None;
+    }
+    let trimmed = line.trim_start();
+    let indent = line.len() - trimmed.len();
+    if indent > 3 {
+        return None;
+    }
+    let (ordered, start, delimiter, marker_len) = match trimmed.chars().next()? {
+        bullet @ ('-' | '*' | '+') => (false, 0, bullet, 1),
+        _ => {
+            let digits = trimmed.chars().take_while(char::is_ascii_digit).count();
+            let delimiter = trimmed[digits..].chars().next()?;
+            if digits == 0 || digits > 9 || !matches!(delimiter, '.' | ')') {
+                return None;
+            }
+            (true, trimmed[..digits].parse().ok()?, delimiter, digits + 1)
+        }
+    };
+    let after = &trimmed[marker_len..];
+    if !after.is_empty() && !after.starts_with(' ') {
+        return None;
+    }
+    let spaces = after.len() - after.trim_start_matches(' ').len();
+    let spaces = if after.trim().is_empty() || spaces > 4 { 1 } else { spaces };
+    Some(ListMarker { ordered, start, delimiter, width: indent + marker_len + spaces })
+}
+
+
+/// # inline
+///
+/// **Purpose:**
+/// Renders the inline markup of a paragraph or heading.
+///
+/// **Parameters:**
+/// - `text`: Inline Markdown, possibly spanning several lines.
+///
+/// **Returns:**
+/// - The HTML.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// assert_eq!(inline("see `x` and **y**"), "see <code>x</code> and <strong>y</strong>");
+/// ```
+fn inline(text: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut html = String::with_capacity(text.len());
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            '\\' if chars.get(i + 1).is_some_and(char::is_ascii_punctuation) => {
+                html.push_str(&escape_html(&chars[i + 1].to_string()));
+                i += 2;
+            }
+            '\\' if chars.get(i + 1) == Some(&'\n') => {
+                html.push_str("<br />\n");
+                i += 2;
+            }
+            '`' => {
+                let run = count_run(&chars, i, '`');
+                match find_code_close(&chars, i + run, run) {
+                    Some(close) => {
+                        let code: String = chars[i + run..close].iter().collect();
+                        let code = code.replace('\n', " ");
+                        let code = if code.len() > 2 && code.starts_with(' ') && code.ends_with(' ') && !code.trim().is_empty() {
+                            &code[1..code.len() - 1]
+                        } else {
+                            &code
+                        };
+                        html.push_str(&format!("<code>{}</code>", escape_html(code)));
+                        i = close + run;
+                    }
+                    None => {
+                        html.push_str(&"`".repeat(run));
+                        i += run;
+                    }
+                }
+            }
+            '!' if chars.get(i + 1) == Some(&'[') => match parse_link(&chars, i + 1) {
+                Some((label, url, title, end)) => {
+                    html.push_str(&format!("<img src=\"{}\" alt=\"{}\"", escape_html(safe_url(&url)), escape_html(&plain(&label))));
+                    if let Some(title) = title {
+                        html.push_str(&format!(" title=\"{}\"", escape_html(&title)));
+                    }
+                    html.push_str(" />");
+                    i = end;
+                }
+                None => {
+                    html.push('!');
+                    i += 1;
+                }
+            },
+            '[' => match parse_link(&chars, i) {
+                Some((label, url, title, end)) => {
+                    html.push_str(&format!("<a href=\"{}\"", escape_html(safe_url(&url))));
+                    if let Some(title) = title {
+                        html.push_str(&format!(" title=\"{}\"", escape_html(&title)));
+                    }
+                    html.push('>');
+                    html.push_str(&inline(&label));
+                    html.push_str("</a>");
+                    i = end;
+                }
+                None => {
+                    html.push('[');
+                    i += 1;
+                }
+            },
+            '<' => match autolink(&chars, i) {
+                Some((url, end)) => {
+                    let href = if url.contains(':') { url.clone() } else { format!("mailto:{url}") };
+                    html.push_str(&format!("<a href=\"{}\">{}</a>", escape_html(&href), escape_html(&url)));
+                    i = end;
+                }
+                None => {
+                    html.push_str("&lt;");
+                    i += 1;
+                }
+            },
+            '*' | '_' => {
+                let full = count_run(&chars, i, c);
+                let intraword = c == '_' && i > 0 && chars[i - 1].is_alphanumeric();
+                let emphasis = (1..=full.min(3))
+                    .rev()
+                    .filter(|_| !intraword)
+                    .find_map(|run| find_emphasis_close(&chars, i + run, c, run).map(|close| (run, close)));
+                match emphasis {
+                    Some((run, close)) => {
+                        let inner = inline(&chars[i + run..close].iter().collect::<String>());
+                        match run {
+                            3 => html.push_str(&format!("<em><strong>{inner}</strong></em>")),
+                            2 => html.push_str(&format!("<strong>{inner}</strong>")),
+                            _ => html.push_str(&format!("<em>{inner}</em>")),
+                        }
+                        i = close + run;
+                    }
+                    None => {
+                        html.extend(std::iter::repeat_n(c, full));
+                        i += full;
+                    }
+                }
+            }
+            '\n' => {
+                let trailing = html.len() - html.trim_end_matches(' ').len();
+                html.truncate(html.len() - trailing);
+                html.push_str(if trailing >= 2 { "<br />\n" } else { "\n" });
+                i += 1;
+            }
+            c => {
+                html.push_str(&escape_html(&c.to_string()));
+                i += 1;
+            }
+        }
+    }
+    html
+}
+
+
+/// # count_run
+///
+/// **Purpose:**
+/// Counts how many times `c` repeats starting at `start`.
+///
+/// **Parameters:**
+/// - `chars`: Inline text.
+/// - `start`: Position to count from.
+/// - `c`: Character to count.
+///
+/// **Returns:**
+/// - The length of the run.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// assert_eq!(count_run(&['*', '*', 'a'], 0, '*'), 2);
+/// ```
+fn count_run(chars: &[char], start: usize, c: char) -> usize {
+    chars[start..].iter().take_while(|&&next| next == c).count()
+}
+
+
+/// # find_code_close
+///
+/// **Purpose:**
+/// Finds the backtick run closing a code span.
+///
+/// **Parameters:**
+/// - `chars`: Inline text.
+/// - `from`: Position just after the opening run.
+/// - `run`: Length of the opening run.
+///
+/// **Returns:**
+/// - Position of a backtick run of exactly the same length, or `None`.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let close = find_code_close(&chars, 1, 1);
+/// ```
+fn find_code_close(chars: &[char], from: usize, run: usize) -> Option<usize> {
+    let mut i = from;
+    while i < chars.len() {
+        if chars[i] == '`' {
+            let length = count_run(chars, i, '`');
+            if length == run {
+                return Some(i);
+            }
+            i += length;
+        } else {
+            i += 1;
+        }
+    }
+    None
+}
+
+
+/// # find_emphasis_close
+///
+/// **Purpose:**
+/// Finds the delimiter run closing emphasis opened at `from`.
+///
+/// **Parameters:**
+/// - `chars`: Inline text.
+/// - `from`: Position just after the opening delimiters.
+/// - `c`: Delimiter character, `*` or `_`.
+/// - `run`: Number of opening delimiters, `1` to `3`.
+///
+/// **Returns:**
+/// - Position of the closing delimiters, or `None` if the emphasis is never closed or would be
+///   empty or padded with whitespace.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let close = find_emphasis_close(&chars, 2, '*', 2);
+/// ```
+fn find_emphasis_close(chars: &[char], from: usize, c: char, run: usize) -> Option<usize> {
+    if chars.get(from).is_none_or(|next| next.is_whitespace()) {
+        return None;
+    }
+    let mut i = from;
+    while i < chars.len() {
+        match chars[i] {
+            '\\' => i += 2,
+            '`' => {
+                let length = count_run(chars, i, '`');
+                i = find_code_close(chars, i + length, length).map_or(i + length, |close| close + length);
+            }
+            next if next == c => {
+                let length = count_run(chars, i, c);
+                let after = chars.get(i + length);
+                let closes = i > from
+                    && !chars[i - 1].is_whitespace()
+                    && (c == '*' || after.is_none_or(|after| !after.is_alphanumeric()));
+                if closes && (length == run || (run == 1 && length >= 3) || (run == 2 && length >= 2)) {
+                    return Some(if run == 1 && length >= 3 { i + length - 1 } else { i });
+                }
+                if run == 1 && length == 2 {
+                    i = find_emphasis_close(chars, i + 2, c, 2).map_or(i + 2, |close| close + 2);
+                    continue;
+                }
+                i += length;
+            }
+            _ => i += 1,
+        }
+    }
+    None
+}
+
+
+/// # parse_link
+///
+/// **Purpose:**
+/// Parses an inline link `[label](url "title")` starting at `[`.
+///
+/// **Parameters:**
+/// - `chars`: Inline text.
+/// - `open`: Position of the `[`.
+///
+/// **Returns:**
+/// - The label, destination, optional title, and the position after `)`, or `None`.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let link = parse_link(&chars, 0);
+/// ```
+fn parse_link(chars: &[char], open: usize) -> Option<(String, String, Option<String>, usize)> {
+    let mut depth = 0;
+    let mut close = None;
+    let mut i = open;
+    while i < chars.len() {
+        match chars[i] {
+            '\\' => i += 1,
+            '[' => depth += 1,
+            ']' => {
+                depth -= 1;
+                if depth == 0 {
+                    close = Some(i);
+                    break;
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    let close = close?;
+    if chars.get(close + 1) != Some(&'(') {
+        return None;
+    }
+
+    let mut i = close + 2;
+    while chars.get(i).is_some_and(|c| c.is_whitespace()) {
+        i += 1;
+    }
+    let mut url = String::new();
+    if chars.get(i) == Some(&'<') {
+        i += 1;
+        while *chars.get(i)? != '>' {
+            url.push(chars[i]);
+            i += 1;
+        }
+        i += 1;
+    } else {
+        let mut parens = 0;
+        while let Some(&c) = chars.get(i) {
+            if c.is_whitespace() || (c == ')' && parens == 0) {
+                break;
+            }
+            match c {
+                '(' => parens += 1,
+                ')' => parens -= 1,
+                _ => {}
+            }
+            url.push(c);
+            i += 1;
+        }
+    }
+    while chars.get(i).is_some_and(|c| c.is_whitespace()) {
+        i += 1;
+    }
+    let mut title = None;
+    if let Some(&quote @ ('"' | '\'')) = chars.get(i) {
+        let mut text = String::new();
+        i += 1;
+        while *chars.get(i)? != quote {
+            text.push(chars[i]);
+            i += 1;
+        }
+        i += 1;
+        title = Some(text);
+        while chars.get(i).is_some_and(|c| c.is_whitespace()) {
+            i += 1;
+        }
+    }
+    if chars.get(i) != Some(&')') {
+        return None;
+    }
+    let label = chars[open + 1..close].iter().collect();
+    Some((label, url, title, i + 1))
+}
+
+
+/// # autolink
+///
+/// **Purpose:**
+/// Parses an autolink such as `<https://example.com>` or `<admin@example.com>` starting at `<`.
+///
+/// **Parameters:**
+/// - `chars`: Inline text.
+/// - `open`: Position of the `<`.
+///
+/// **Returns:**
+/// - The address and the position after `>`, or `None`.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let link = autolink(&chars, 0);
+/// ```
+fn autolink(chars: &[char], open: usize) -> Option<(String, usize)> {
+    let close = open + chars[open..].iter().position(|&c| c == '>')?;
+    let address: String = chars[open + 1..close].iter().collect();
+    if address.is_empty() || address.contains(|c: char| c.is_whitespace() || c == '<') {
+        return None;
+    }
+    let scheme = address.split_once(':').map(|(scheme, _)| scheme.to_ascii_lowercase());
+    let is_url = matches!(scheme.as_deref(), Some("http" | "https" | "ftp" | "mailto"));
+    let is_email = !address.contains(':') && address.split_once('@').is_some_and(|(user, host)| !user.is_empty() && host.contains('.'));
+    (is_url || is_email).then_some((address, close + 1))
+}
+
+
+/// # safe_url
+///
+/// **Purpose:**
+/// Keeps link and image targets from running script: relative URLs and the `http`, `https`,
+/// `ftp`, and `mailto` schemes pass, anything else (`javascript:`, `data:`, ...) is replaced.
+///
+/// **Parameters:**
+/// - `url`: Target as written in the document.
+///
+/// **Returns:**
+/// - `url`, or `#` when its scheme is not allowed.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// assert_eq!(safe_url("javascript:alert(1)"), "#");
+/// ```
+fn safe_url(url: &str) -> &str {
+    let visible: String = url.chars().filter(|c| !c.is_ascii_whitespace() && !c.is_control()).collect();
+    let Some((scheme, _)) = visible.split_once(':') else {
+        return url;
+    };
+    if !scheme.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.')) {
+        return url;
+    }
+    match scheme.to_ascii_lowercase().as_str() {
+        "http" | "https" | "ftp" | "mailto" => url,
+        _ => "#",
+    }
+}
+
+
+/// # plain
+///
+/// **Purpose:**
+/// Strips the most common inline markup, for `alt` text and titles.
+///
+/// **Parameters:**
+/// - `text`: Inline Markdown.
+///
+/// **Returns:**
+/// - The text without `*`, `_`, or backticks.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// assert_eq!(plain("*logo*"), "logo");
+/// ```
+fn plain(text: &str) -> String {
+    text.chars().filter(|c| !matches!(c, '*' | '_' | '`')).collect()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn headings_get_slug_ids() {
+        assert_eq!(to_html("# Getting Started!"), "<h1 id=\"getting-started\">Getting Started!</h1>\n");
+        assert_eq!(to_html("Title\n=====\n\nSub\n---"), "<h1 id=\"title\">Title</h1>\n<h2 id=\"sub\">Sub</h2>\n");
+    }
+
+    #[test]
+    fn inline_markup_is_rendered() {
+        assert_eq!(
+            to_html("Some *em*, **strong**, `code`, [a link](/x \"t\") and <https://a.example>."),
+            "<p>Some <em>em</em>, <strong>strong</strong>, <code>code</code>, <a href=\"/x\" title=\"t\">a link</a> and <a href=\"https://a.example\">https://a.example</a>.</p>\n"
+        );
+    }
+
+    #[test]
+    fn lists_nest_and_code_blocks_keep_their_text() {
+        assert_eq!(to_html("- a\n  1. b\n- c"), "<ul>\n<li>a\n<ol>\n<li>b</li>\n</ol></li>\n<li>c</li>\n</ul>\n");
+        assert_eq!(
+            to_html("```rust\nlet x = 1 < 2;\n```"),
+            "<pre><code class=\"language-rust\">let x = 1 &lt; 2;\n</code></pre>\n"
+        );
+    }
+
+    #[test]
+    fn raw_html_and_script_links_are_neutralized() {
+        assert_eq!(to_html("<script>alert(1)</script>"), "<p>&lt;script&gt;alert(1)&lt;/script&gt;</p>\n");
+        assert_eq!(to_html("[x](javascript:alert(1))"), "<p><a href=\"#\">x</a></p>\n");
+        assert_eq!(to_html("![x](JavaScript:alert(1))"), "<p><img src=\"#\" alt=\"x\" /></p>\n");
+        assert_eq!(to_html("[x](docs/a:b.md)"), "<p><a href=\"docs/a:b.md\">x</a></p>\n");
+    }
+
+    #[test]
+    fn the_title_is_the_first_heading() {
+        assert_eq!(title("intro\n\n## Usage\n```\n# not a heading\n```\n# The *Guide*").as_deref(), Some("The Guide"));
+        assert_eq!(title("no headings"), None);
+    }
+}
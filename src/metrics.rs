@@ -0,0 +1,747 @@
+
+// ============================================================
+//  DAEGONICA SOFTWARE — metrics.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: Metrics
+//!
+//! **Purpose:**
+//! Collects server-wide traffic counters and exports them in the Prometheus text format.
+//!
+//! **Context:**
+//! - Always collected by `Server`; exported on the main listener when an endpoint path is
+//!   set, or on a separate admin listener started with `Metrics::serve`.
+//!
+//! **Responsibilities:**
+//! - Counts responses by status class, requests in flight, worker queue depth, open and
+//!   accepted connections, and bytes received from and sent to clients.
+//! - Keeps request duration histograms labelled by route pattern and method, never by raw
+//!   path, so the number of series stays bounded.
+//! - Renders the counters as a Prometheus text exposition (version 0.0.4).
+//! - Does NOT count the statuses or bytes of streamed proxy replies and tunnels, which bypass
+//!   the client stream, or keep any history; rates are left to the scraper.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+use std::{
+    collections::BTreeMap,
+    fmt::Write as _,
+    io::{self, BufReader},
+    net::TcpListener,
+    sync::{
+        Arc,
+        Mutex,
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+    },
+    thread,
+    time::Duration,
+};
+
+use crate::{limits::HeadLimits, request::Request, response::Response, timeouts::DeadlineReader};
+
+
+/// # CONTENT_TYPE
+///
+/// **Summary:**
+/// Media type of the Prometheus text exposition format.
+pub const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+
+/// # ADMIN_TIMEOUT
+///
+/// **Summary:**
+/// Time allowed to receive each scrape request on the admin listener, and to write its reply.
+const ADMIN_TIMEOUT: Duration = Duration::from_secs(5);
+
+
+/// # MAX_SCRAPE_BODY
+///
+/// **Summary:**
+/// Largest request body accepted on the admin listener; scrapes carry none.
+const MAX_SCRAPE_BODY: usize = 1024;
+
+
+/// # DURATION_BUCKETS
+///
+/// **Summary:**
+/// Upper bounds, in seconds, of the request duration histogram buckets.
+const DURATION_BUCKETS: [f64; 11] = [0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0];
+
+
+/// # METHODS
+///
+/// **Summary:**
+/// Methods kept as histogram labels; any other method is recorded as `OTHER`.
+const METHODS: [&str; 9] = ["GET", "HEAD", "POST", "PUT", "DELETE", "PATCH", "OPTIONS", "CONNECT", "TRACE"];
+
+
+/// # Metrics
+///
+/// **Summary:**
+/// Shared traffic counters. Clones share the same counters, so a clone taken before
+/// `Server::run` keeps reporting live values.
+///
+/// **Fields:**
+/// - `endpoint`: Path exporting the counters on the main listener, if any.
+/// - `counters`: The counters themselves.
+///
+/// **Usage Example:**
+/// ```rust,no_run
+/// # use server::{metrics::Metrics, server::Server};
+/// let metrics = Metrics::new();
+/// metrics.serve("127.0.0.1:9100")?;
+/// Server::new("0.0.0.0:80").metrics(metrics).run()?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Metrics {
+    endpoint: Option<String>,
+    counters: Arc<Counters>,
+}
+
+
+/// # Counters
+///
+/// **Summary:**
+/// Atomic counters behind a `Metrics` handle.
+///
+/// **Fields:**
+/// - `responses`: Responses sent, indexed by status class (`1xx` to `5xx`).
+/// - `in_flight`: Requests read and not yet answered.
+/// - `queued`: Jobs waiting in the worker pool's queue.
+/// - `connections_open`: Client connections currently being served.
+/// - `connections_total`: Client connections served since start-up.
+/// - `received_bytes`: Bytes read from clients.
+/// - `sent_bytes`: Bytes written to clients.
+/// - `durations`: Request duration histograms keyed by route pattern and method.
+#[derive(Debug, Default)]
+struct Counters {
+    responses: [AtomicU64; 5],
+    in_flight: AtomicUsize,
+    queued: Arc<AtomicUsize>,
+    connections_open: AtomicUsize,
+    connections_total: AtomicU64,
+    received_bytes: AtomicU64,
+    sent_bytes: AtomicU64,
+    durations: Mutex<BTreeMap<(String, &'static str), Histogram>>,
+}
+
+
+/// # Histogram
+///
+/// **Summary:**
+/// Request durations observed for one route and method.
+///
+/// **Fields:**
+/// - `buckets`: Observations per bucket of `DURATION_BUCKETS`, not cumulative; longer ones only
+///   count towards `count`.
+/// - `count`: Observations in total.
+/// - `sum`: Total observed seconds.
+#[derive(Debug, Default)]
+struct Histogram {
+    buckets: [u64; DURATION_BUCKETS.len()],
+    count: u64,
+    sum: f64,
+}
+
+
+/// # Gauge
+///
+/// **Summary:**
+/// Guard holding one unit of a gauge (a request in flight or an open connection); dropping it
+/// gives the unit back.
+///
+/// **Fields:**
+/// - `counters`: Counters the gauge belongs to.
+/// - `gauge`: Which gauge to decrement on drop.
+#[derive(Debug)]
+pub(crate) struct Gauge {
+    counters: Arc<Counters>,
+    gauge: fn(&Counters) -> &AtomicUsize,
+}
+
+
+/// # TrafficStats
+///
+/// **Summary:**
+/// Point-in-time snapshot of the traffic gauges and totals.
+///
+/// **Fields:**
+/// - `in_flight`: Requests being handled.
+/// - `queued`: Jobs waiting for a worker thread.
+/// - `connections_open`: Client connections being served.
+/// - `connections_total`: Client connections served since start-up.
+/// - `received_bytes`: Bytes read from clients.
+/// - `sent_bytes`: Bytes written to clients.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TrafficStats {
+    pub in_flight: usize,
+    pub queued: usize,
+    pub connections_open: usize,
+    pub connections_total: u64,
+    pub received_bytes: u64,
+    pub sent_bytes: u64,
+}
+
+
+impl Metrics {
+
+    /// # new
+    ///
+    /// **Purpose:**
+    /// Creates fresh counters with no endpoint on the main listener.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - A new `Metrics`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::metrics::Metrics;
+    /// let metrics = Metrics::new();
+    /// ```
+    pub fn new() -> Metrics {
+        Metrics::default()
+    }
+
+    /// # endpoint
+    ///
+    /// **Purpose:**
+    /// Exports the counters on the main listener at a path, answered before virtual hosts and
+    /// the proxy see the request. Anyone who can reach the server can then read them.
+    ///
+    /// **Parameters:**
+    /// - `path`: Request path such as `/metrics`.
+    ///
+    /// **Returns:**
+    /// - The updated `Metrics`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::metrics::Metrics;
+    /// let metrics = Metrics::new().endpoint("/metrics");
+    /// ```
+    pub fn endpoint(mut self, path: &str) -> Metrics {
+        self.endpoint = Some(path.to_string());
+        self
+    }
+
+    /// # serve
+    ///
+    /// **Purpose:**
+    /// Exports the counters on a separate admin listener, answering every `GET` there with the
+    /// exposition, one scrape at a time on a background thread. Each scrape request must be
+    /// small and arrive within a fixed deadline.
+    ///
+    /// **Parameters:**
+    /// - `addr`: Address to bind (e.g. `127.0.0.1:9100`).
+    ///
+    /// **Returns:**
+    /// - `Ok(())` once the listener is bound and its thread started.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns an I/O error if the address cannot be bound or the thread cannot be spawned.
+    ///
+    /// **Examples:**
+    /// ```rust,no_run
+    /// # use server::metrics::Metrics;
+    /// # let metrics = Metrics::new();
+    /// metrics.serve("127.0.0.1:9100")?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn serve(&self, addr: &str) -> io::Result<()> {
+        let listener = TcpListener::bind(addr)?;
+        let metrics = self.clone();
+        thread::Builder::new().name("metrics".to_string()).spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let mut reader = BufReader::new(DeadlineReader::new(&stream, ADMIN_TIMEOUT));
+                let response = match Request::read_within(&mut reader, &HeadLimits::OPERATOR, MAX_SCRAPE_BODY) {
+                    Ok(request) if request.method == "GET" || request.method == "HEAD" => metrics.response(),
+                    Ok(_) => Response::new(405, "Method Not Allowed").with_header("Allow", "GET, HEAD"),
+                    Err(_) => continue,
+                };
+                let _ = response.write_final(&stream, ADMIN_TIMEOUT);
+            }
+        })?;
+        Ok(())
+    }
+
+    /// # exports
+    ///
+    /// **Purpose:**
+    /// Checks whether a request on the main listener asks for the exposition.
+    ///
+    /// **Parameters:**
+    /// - `request`: Request being served.
+    ///
+    /// **Returns:**
+    /// - `true` for a `GET` or `HEAD` of the configured endpoint path.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// if metrics.exports(&request) { return self.write_response(&request, metrics.response(), stream, remaining); }
+    /// ```
+    pub(crate) fn exports(&self, request: &Request) -> bool {
+        self.endpoint.as_deref() == Some(request.path()) && (request.method == "GET" || request.method == "HEAD")
+    }
+
+    /// # response
+    ///
+    /// **Purpose:**
+    /// Builds the scrape response.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - `200 OK` carrying `render()`, marked uncacheable.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let response = metrics.response();
+    /// ```
+    pub(crate) fn response(&self) -> Response {
+        Response::new(200, "OK")
+            .with_header("Content-Type", CONTENT_TYPE)
+            .with_header("Cache-Control", "no-store")
+            .with_body(self.render())
+    }
+
+    /// # render
+    ///
+    /// **Purpose:**
+    /// Formats the current counters in the Prometheus text format.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - The exposition text, one `# HELP` / `# TYPE` block per metric.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::metrics::Metrics;
+    /// # let metrics = Metrics::new();
+    /// print!("{}", metrics.render());
+    /// ```
+    pub fn render(&self) -> String {
+        let counters = &self.counters;
+        let stats = self.stats();
+        let mut text = String::new();
+        header(&mut text, "http_responses_total", "counter", "Responses sent, by status class.");
+        for (index, count) in counters.responses.iter().enumerate() {
+            let _ = writeln!(text, "http_responses_total{{class=\"{}xx\"}} {}", index + 1, count.load(Ordering::Relaxed));
+        }
+        let gauges = [
+            ("http_requests_in_flight", "gauge", "Requests being handled.", stats.in_flight as u64),
+            ("http_pool_queue_depth", "gauge", "Jobs waiting for a worker thread.", stats.queued as u64),
+            ("http_connections_open", "gauge", "Client connections being served.", stats.connections_open as u64),
+            ("http_connections_total", "counter", "Client connections served.", stats.connections_total),
+            ("http_received_bytes_total", "counter", "Bytes read from clients.", stats.received_bytes),
+            ("http_sent_bytes_total", "counter", "Bytes written to clients.", stats.sent_bytes),
+        ];
+        for (name, kind, help, value) in gauges {
+            header(&mut text, name, kind, help);
+            let _ = writeln!(text, "{name} {value}");
+        }
+
+        let durations = counters.durations.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        header(&mut text, "http_request_duration_seconds", "histogram", "Time to answer requests, by route pattern and method.");
+        for ((route, method), histogram) in durations.iter() {
+            let labels = format!("route=\"{}\",method=\"{method}\"", escape_label(route));
+            let mut cumulative = 0;
+            for (bound, count) in DURATION_BUCKETS.iter().zip(histogram.buckets) {
+                cumulative += count;
+                let _ = writeln!(text, "http_request_duration_seconds_bucket{{{labels},le=\"{bound}\"}} {cumulative}");
+            }
+            let _ = writeln!(text, "http_request_duration_seconds_bucket{{{labels},le=\"+Inf\"}} {}", histogram.count);
+            let _ = writeln!(text, "http_request_duration_seconds_sum{{{labels}}} {}", histogram.sum);
+            let _ = writeln!(text, "http_request_duration_seconds_count{{{labels}}} {}", histogram.count);
+        }
+        text
+    }
+
+    /// # observe
+    ///
+    /// **Purpose:**
+    /// Records how long a request took in its route's duration histogram.
+    ///
+    /// **Parameters:**
+    /// - `route`: Route pattern that answered (e.g. `/users/:id`), or a placeholder such as
+    ///   `(unrouted)`; never the raw path.
+    /// - `method`: Request method; methods outside the standard set are recorded as `OTHER`.
+    /// - `elapsed`: Time from reading the request head to writing the response.
+    ///
+    /// **Returns:**
+    /// None.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// metrics.observe("/users/:id", &request.method, started.elapsed());
+    /// ```
+    pub(crate) fn observe(&self, route: &str, method: &str, elapsed: Duration) {
+        let method = METHODS.into_iter().find(|known| *known == method).unwrap_or("OTHER");
+        let seconds = elapsed.as_secs_f64();
+        let mut durations = self.counters.durations.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let histogram = durations.entry((route.to_string(), method)).or_default();
+        if let Some(bucket) = DURATION_BUCKETS.iter().position(|bound| seconds <= *bound) {
+            histogram.buckets[bucket] += 1;
+        }
+        histogram.count += 1;
+        histogram.sum += seconds;
+    }
+
+    /// # stats
+    ///
+    /// **Purpose:**
+    /// Reads the traffic gauges and totals without rendering them.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - A `TrafficStats` snapshot.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::metrics::Metrics;
+    /// # let metrics = Metrics::new();
+    /// let busy = metrics.stats().in_flight;
+    /// # assert_eq!(busy, 0);
+    /// ```
+    pub fn stats(&self) -> TrafficStats {
+        let counters = &self.counters;
+        TrafficStats {
+            in_flight: counters.in_flight.load(Ordering::Relaxed),
+            queued: counters.queued.load(Ordering::Relaxed),
+            connections_open: counters.connections_open.load(Ordering::Relaxed),
+            connections_total: counters.connections_total.load(Ordering::Relaxed),
+            received_bytes: counters.received_bytes.load(Ordering::Relaxed),
+            sent_bytes: counters.sent_bytes.load(Ordering::Relaxed),
+        }
+    }
+
+    /// # record_response
+    ///
+    /// **Purpose:**
+    /// Counts a response by its status class.
+    ///
+    /// **Parameters:**
+    /// - `status`: Status code sent; codes outside `100..=599` are not counted.
+    ///
+    /// **Returns:**
+    /// None.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// metrics.record_response(response.status);
+    /// ```
+    pub(crate) fn record_response(&self, status: u16) {
+        if let Some(count) = self.counters.responses.get(usize::from(status / 100).wrapping_sub(1)) {
+            count.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// # record_received
+    ///
+    /// **Purpose:**
+    /// Counts bytes read from a client.
+    ///
+    /// **Parameters:**
+    /// - `bytes`: Bytes read.
+    ///
+    /// **Returns:**
+    /// None.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// metrics.record_received(read);
+    /// ```
+    pub(crate) fn record_received(&self, bytes: usize) {
+        self.counters.received_bytes.fetch_add(bytes as u64, Ordering::Relaxed);
+    }
+
+    /// # record_sent
+    ///
+    /// **Purpose:**
+    /// Counts bytes written to a client.
+    ///
+    /// **Parameters:**
+    /// - `bytes`: Bytes written.
+    ///
+    /// **Returns:**
+    /// None.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// metrics.record_sent(written);
+    /// ```
+    pub(crate) fn record_sent(&self, bytes: usize) {
+        self.counters.sent_bytes.fetch_add(bytes as u64, Ordering::Relaxed);
+    }
+
+    /// # connection
+    ///
+    /// **Purpose:**
+    /// Counts a client connection as open until the returned guard is dropped.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - A `Gauge` guard for the connection.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let _open = metrics.connection();
+    /// ```
+    pub(crate) fn connection(&self) -> Gauge {
+        self.counters.connections_total.fetch_add(1, Ordering::Relaxed);
+        Gauge::hold(&self.counters, |counters| &counters.connections_open)
+    }
+
+    /// # request
+    ///
+    /// **Purpose:**
+    /// Counts a request as in flight until the returned guard is dropped.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - A `Gauge` guard for the request.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let _in_flight = metrics.request();
+    /// ```
+    pub(crate) fn request(&self) -> Gauge {
+        Gauge::hold(&self.counters, |counters| &counters.in_flight)
+    }
+
+    /// # queue_gauge
+    ///
+    /// **Purpose:**
+    /// Returns the queue depth counter for the worker pool to maintain.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - The shared queue depth counter.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let pool = ThreadPool::with_queue_gauge(4, metrics.queue_gauge());
+    /// ```
+    pub(crate) fn queue_gauge(&self) -> Arc<AtomicUsize> {
+        Arc::clone(&self.counters.queued)
+    }
+}
+
+
+impl Gauge {
+
+    /// # hold
+    ///
+    /// **Purpose:**
+    /// Increments a gauge and returns the guard that will decrement it.
+    ///
+    /// **Parameters:**
+    /// - `counters`: Counters the gauge belongs to.
+    /// - `gauge`: Selects the gauge.
+    ///
+    /// **Returns:**
+    /// - A new `Gauge`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let guard = Gauge::hold(&self.counters, |counters| &counters.in_flight);
+    /// ```
+    fn hold(counters: &Arc<Counters>, gauge: fn(&Counters) -> &AtomicUsize) -> Gauge {
+        gauge(counters).fetch_add(1, Ordering::Relaxed);
+        Gauge {
+            counters: Arc::clone(counters),
+            gauge,
+        }
+    }
+}
+
+
+impl Drop for Gauge {
+
+    /// # drop
+    ///
+    /// **Purpose:**
+    /// Gives the gauge's unit back.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// None.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// drop(in_flight);
+    /// ```
+    fn drop(&mut self) {
+        (self.gauge)(&self.counters).fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+
+/// # header
+///
+/// **Purpose:**
+/// Writes the `# HELP` and `# TYPE` lines that introduce a metric.
+///
+/// **Parameters:**
+/// - `text`: Exposition being built.
+/// - `name`: Metric name.
+/// - `kind`: Metric type (`counter`, `gauge`, or `histogram`).
+/// - `help`: One-line description.
+///
+/// **Returns:**
+/// None.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// header(&mut text, "http_connections_open", "gauge", "Client connections being served.");
+/// ```
+fn header(text: &mut String, name: &str, kind: &str, help: &str) {
+    let _ = writeln!(text, "# HELP {name} {help}");
+    let _ = writeln!(text, "# TYPE {name} {kind}");
+}
+
+
+/// # escape_label
+///
+/// **Purpose:**
+/// Escapes a label value for the exposition format.
+///
+/// **Parameters:**
+/// - `value`: Raw label value.
+///
+/// **Returns:**
+/// - The value with backslashes, double quotes, and newlines escaped.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let label = escape_label("/files/*path");
+/// ```
+fn escape_label(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn responses_are_counted_by_status_class() {
+        let metrics = Metrics::new();
+        metrics.record_response(200);
+        metrics.record_response(204);
+        metrics.record_response(503);
+        let text = metrics.render();
+        assert!(text.contains("http_responses_total{class=\"2xx\"} 2\n"));
+        assert!(text.contains("http_responses_total{class=\"5xx\"} 1\n"));
+        assert!(text.contains("# TYPE http_responses_total counter\n"));
+    }
+
+    #[test]
+    fn gauges_fall_back_when_their_guards_drop() {
+        let metrics = Metrics::new();
+        let connection = metrics.connection();
+        let request = metrics.request();
+        assert_eq!((metrics.stats().connections_open, metrics.stats().in_flight), (1, 1));
+        drop((connection, request));
+        let stats = metrics.stats();
+        assert_eq!((stats.connections_open, stats.in_flight, stats.connections_total), (0, 0, 1));
+    }
+
+    #[test]
+    fn durations_are_cumulative_histograms() {
+        let metrics = Metrics::new();
+        metrics.observe("/users/:id", "GET", Duration::from_millis(3));
+        metrics.observe("/users/:id", "GET", Duration::from_millis(40));
+        metrics.observe("/users/:id", "BREW", Duration::from_secs(60));
+        let text = metrics.render();
+        let labels = "route=\"/users/:id\",method=\"GET\"";
+        assert!(text.contains(&format!("http_request_duration_seconds_bucket{{{labels},le=\"0.005\"}} 1\n")));
+        assert!(text.contains(&format!("http_request_duration_seconds_bucket{{{labels},le=\"0.05\"}} 2\n")));
+        assert!(text.contains(&format!("http_request_duration_seconds_count{{{labels}}} 2\n")));
+        assert!(text.contains("method=\"OTHER\",le=\"+Inf\"} 1\n"));
+    }
+
+    #[test]
+    fn only_reads_of_the_endpoint_are_exported() {
+        let metrics = Metrics::new().endpoint("/metrics");
+        let read = |raw: &str| Request::read_from(&mut raw.as_bytes()).unwrap();
+        assert!(metrics.exports(&read("GET /metrics HTTP/1.1\r\nHost: a\r\n\r\n")));
+        assert!(!metrics.exports(&read("POST /metrics HTTP/1.1\r\nHost: a\r\nContent-Length: 0\r\n\r\n")));
+        assert!(!metrics.exports(&read("GET /other HTTP/1.1\r\nHost: a\r\n\r\n")));
+    }
+}
@@ -0,0 +1,536 @@
+
+// ============================================================
+//  DAEGONICA SOFTWARE — mime.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: MimeTypes
+//!
+//! **Purpose:**
+//! Maps file extensions onto the media types sent in `Content-Type`, from a built-in table
+//! that applications can extend or override.
+//!
+//! **Context:**
+//! - Consulted by `StaticFiles` for every file it serves (replaceable with
+//!   `StaticFiles::mime_types`) and by `Response::send_file`, which uses the built-in table.
+//!
+//! **Responsibilities:**
+//! - Ships a table of the web's common extensions: documents, scripts and modules, styles,
+//!   images (including `.avif` and `.webp`), fonts, audio, video, archives, and `.wasm`.
+//! - Lets callers add or override mappings in code or from an Apache/nginx `mime.types` file.
+//! - Falls back to a configurable type, `application/octet-stream` by default, for unknown
+//!   extensions.
+//! - Adds a configurable charset, `utf-8` by default, to `text/*` types that do not name one,
+//!   checking a sample of the file first: a byte order mark names the charset itself, and a
+//!   file that is not valid UTF-8 is never labelled as such.
+//! - Does NOT sniff the media type from file contents; the extension alone decides it.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+use std::{collections::HashMap, fs, io, path::Path};
+
+
+/// # DEFAULT_TYPE
+///
+/// **Summary:**
+/// Type sent for extensions no mapping covers, unless `MimeTypes::fallback` says otherwise.
+const DEFAULT_TYPE: &str = "application/octet-stream";
+
+
+/// # DEFAULT_CHARSET
+///
+/// **Summary:**
+/// Charset added to text types unless `MimeTypes::charset` says otherwise.
+const DEFAULT_CHARSET: &str = "utf-8";
+
+
+/// # BUILTIN
+///
+/// **Summary:**
+/// Lower-case extensions and their media types, without parameters.
+const BUILTIN: &[(&str, &str)] = &[
+    ("7z", "application/x-7z-compressed"),
+    ("aac", "audio/aac"),
+    ("apng", "image/apng"),
+    ("atom", "application/atom+xml"),
+    ("avif", "image/avif"),
+    ("bmp", "image/bmp"),
+    ("bz2", "application/x-bzip2"),
+    ("cjs", "text/javascript"),
+    ("css", "text/css"),
+    ("csv", "text/csv"),
+    ("doc", "application/msword"),
+    ("docx", "application/vnd.openxmlformats-officedocument.wordprocessingml.document"),
+    ("eot", "application/vnd.ms-fontobject"),
+    ("epub", "application/epub+zip"),
+    ("flac", "audio/flac"),
+    ("gif", "image/gif"),
+    ("gz", "application/gzip"),
+    ("heic", "image/heic"),
+    ("htm", "text/html"),
+    ("html", "text/html"),
+    ("ico", "image/x-icon"),
+    ("ics", "text/calendar"),
+    ("jpeg", "image/jpeg"),
+    ("jpg", "image/jpeg"),
+    ("js", "text/javascript"),
+    ("json", "application/json"),
+    ("jsonld", "application/ld+json"),
+    ("jxl", "image/jxl"),
+    ("m4a", "audio/mp4"),
+    ("m4v", "video/mp4"),
+    ("manifest", "text/cache-manifest"),
+    ("map", "application/json"),
+    ("markdown", "text/markdown"),
+    ("md", "text/markdown"),
+    ("mid", "audio/midi"),
+    ("midi", "audio/midi"),
+    ("mjs", "text/javascript"),
+    ("mkv", "video/x-matroska"),
+    ("mov", "video/quicktime"),
+    ("mp3", "audio/mpeg"),
+    ("mp4", "video/mp4"),
+    ("mpeg", "video/mpeg"),
+    ("odp", "application/vnd.oasis.opendocument.presentation"),
+    ("ods", "application/vnd.oasis.opendocument.spreadsheet"),
+    ("odt", "application/vnd.oasis.opendocument.text"),
+    ("oga", "audio/ogg"),
+    ("ogg", "audio/ogg"),
+    ("ogv", "video/ogg"),
+    ("opus", "audio/opus"),
+    ("otf", "font/otf"),
+    ("pdf", "application/pdf"),
+    ("png", "image/png"),
+    ("ppt", "application/vnd.ms-powerpoint"),
+    ("pptx", "application/vnd.openxmlformats-officedocument.presentationml.presentation"),
+    ("rar", "application/vnd.rar"),
+    ("rss", "application/rss+xml"),
+    ("rtf", "application/rtf"),
+    ("svg", "image/svg+xml"),
+    ("svgz", "image/svg+xml"),
+    ("tar", "application/x-tar"),
+    ("tif", "image/tiff"),
+    ("tiff", "image/tiff"),
+    ("toml", "application/toml"),
+    ("ts", "video/mp2t"),
+    ("ttf", "font/ttf"),
+    ("txt", "text/plain"),
+    ("vtt", "text/vtt"),
+    ("wasm", "application/wasm"),
+    ("wav", "audio/wav"),
+    ("weba", "audio/webm"),
+    ("webm", "video/webm"),
+    ("webmanifest", "application/manifest+json"),
+    ("webp", "image/webp"),
+    ("woff", "font/woff"),
+    ("woff2", "font/woff2"),
+    ("xhtml", "application/xhtml+xml"),
+    ("xls", "application/vnd.ms-excel"),
+    ("xlsx", "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"),
+    ("xml", "application/xml"),
+    ("yaml", "application/yaml"),
+    ("yml", "application/yaml"),
+    ("zip", "application/zip"),
+    ("zst", "application/zstd"),
+];
+
+
+/// # MimeTypes
+///
+/// **Summary:**
+/// Extension-to-media-type registry: the built-in table plus caller overrides.
+///
+/// **Fields:**
+/// - `overrides`: Lower-case extensions (without the dot) mapped by the caller; these win
+///   over the built-in table.
+/// - `fallback`: Type for extensions neither covers.
+/// - `charset`: Charset added to `text/*` types without one, or `None` to add none.
+///
+/// **Usage Example:**
+/// ```rust
+/// # use server::{mime::MimeTypes, static_files::StaticFiles};
+/// let types = MimeTypes::new()
+///     .insert(".gltf", "model/gltf+json")
+///     .insert("glb", "model/gltf-binary")
+///     .fallback("text/plain");
+/// let files = StaticFiles::new("html").mime_types(types);
+/// ```
+#[derive(Debug, Clone)]
+pub struct MimeTypes {
+    overrides: HashMap<String, String>,
+    fallback: String,
+    charset: Option<String>,
+}
+
+
+impl Default for MimeTypes {
+
+    /// # default
+    ///
+    /// **Purpose:**
+    /// Creates a registry holding only the built-in table.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - A `MimeTypes` falling back to `application/octet-stream`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::mime::MimeTypes;
+    /// let types = MimeTypes::default();
+    /// ```
+    fn default() -> Self {
+        MimeTypes {
+            overrides: HashMap::new(),
+            fallback: DEFAULT_TYPE.to_string(),
+            charset: Some(DEFAULT_CHARSET.to_string()),
+        }
+    }
+}
+
+
+impl MimeTypes {
+
+    /// # new
+    ///
+    /// **Purpose:**
+    /// Creates a registry holding only the built-in table.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - A `MimeTypes` falling back to `application/octet-stream`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::mime::MimeTypes;
+    /// let types = MimeTypes::new();
+    /// ```
+    pub fn new() -> MimeTypes {
+        MimeTypes::default()
+    }
+
+    /// # insert
+    ///
+    /// **Purpose:**
+    /// Maps an extension onto a media type, replacing any built-in or earlier mapping.
+    ///
+    /// **Parameters:**
+    /// - `extension`: Extension with or without its leading dot; case does not matter.
+    /// - `media_type`: Type to send, optionally with parameters such as `charset`.
+    ///
+    /// **Returns:**
+    /// - The updated `MimeTypes`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::mime::MimeTypes;
+    /// let types = MimeTypes::new().insert(".log", "text/plain");
+    /// ```
+    pub fn insert(mut self, extension: &str, media_type: impl Into<String>) -> MimeTypes {
+        let extension = extension.trim().trim_start_matches('.').to_ascii_lowercase();
+        self.overrides.insert(extension, media_type.into());
+        self
+    }
+
+    /// # fallback
+    ///
+    /// **Purpose:**
+    /// Sets the type sent for extensions no mapping covers.
+    ///
+    /// **Parameters:**
+    /// - `media_type`: Fallback type.
+    ///
+    /// **Returns:**
+    /// - The updated `MimeTypes`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::mime::MimeTypes;
+    /// let types = MimeTypes::new().fallback("text/plain");
+    /// ```
+    pub fn fallback(mut self, media_type: impl Into<String>) -> MimeTypes {
+        self.fallback = media_type.into();
+        self
+    }
+
+    /// # charset
+    ///
+    /// **Purpose:**
+    /// Sets the charset added to `text/*` types that do not name one (`utf-8` by default), for
+    /// sites whose files are stored in a legacy encoding.
+    ///
+    /// **Parameters:**
+    /// - `charset`: Charset label (e.g. `iso-8859-1`), or `None` to leave clients to detect it.
+    ///
+    /// **Returns:**
+    /// - The updated `MimeTypes`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::mime::MimeTypes;
+    /// let types = MimeTypes::new().charset(Some("windows-1252"));
+    /// ```
+    pub fn charset(mut self, charset: Option<&str>) -> MimeTypes {
+        self.charset = charset.map(str::to_string);
+        self
+    }
+
+    /// # load
+    ///
+    /// **Purpose:**
+    /// Adds every mapping from a `mime.types` file, the format Apache and nginx ship: one type
+    /// per line followed by its extensions, with `#` comments. nginx's `types { ... }` wrapper
+    /// and trailing semicolons are accepted.
+    ///
+    /// **Parameters:**
+    /// - `path`: File to read.
+    ///
+    /// **Returns:**
+    /// - The updated `MimeTypes`.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns an I/O error if the file cannot be read.
+    ///
+    /// **Examples:**
+    /// ```rust,no_run
+    /// # use server::mime::MimeTypes;
+    /// let types = MimeTypes::new().load("/etc/mime.types")?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn load(mut self, path: impl AsRef<Path>) -> io::Result<MimeTypes> {
+        for line in fs::read_to_string(path)?.lines() {
+            let line = line.split('#').next().unwrap_or("").replace(['{', '}', ';'], " ");
+            let mut words = line.split_whitespace();
+            let Some(media_type) = words.next().filter(|word| word.contains('/')) else {
+                continue;
+            };
+            for extension in words {
+                self = self.insert(extension, media_type);
+            }
+        }
+        Ok(self)
+    }
+
+    /// # lookup
+    ///
+    /// **Purpose:**
+    /// Finds the media type for a file from its extension, as mapped, without adding a charset.
+    ///
+    /// **Parameters:**
+    /// - `file`: Path of the file being served.
+    ///
+    /// **Returns:**
+    /// - The override, else the built-in type, else the fallback.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::mime::MimeTypes;
+    /// # use std::path::Path;
+    /// assert_eq!(MimeTypes::new().lookup(Path::new("app.wasm")), "application/wasm");
+    /// ```
+    pub fn lookup(&self, file: &Path) -> &str {
+        let extension = extension(file);
+        self.overrides
+            .get(&extension)
+            .map(String::as_str)
+            .or_else(|| builtin(&extension))
+            .unwrap_or(&self.fallback)
+    }
+
+    /// # content_type
+    ///
+    /// **Purpose:**
+    /// Builds the `Content-Type` value for a file whose contents are not at hand, trusting the
+    /// configured charset.
+    ///
+    /// **Parameters:**
+    /// - `file`: Path of the file being served.
+    ///
+    /// **Returns:**
+    /// - The looked-up type, with the configured charset added to `text/*` types that name none.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::mime::MimeTypes;
+    /// # use std::path::Path;
+    /// assert_eq!(MimeTypes::new().content_type(Path::new("a.html")), "text/html; charset=utf-8");
+    /// ```
+    pub fn content_type(&self, file: &Path) -> String {
+        with_charset(self.lookup(file), self.charset.as_deref())
+    }
+
+    /// # content_type_for
+    ///
+    /// **Purpose:**
+    /// Builds the `Content-Type` value for a file, choosing the charset of a text type from the
+    /// file's leading bytes rather than assuming they are UTF-8.
+    ///
+    /// **Parameters:**
+    /// - `file`: Path of the file being served.
+    /// - `sample`: The file's contents, or its first few kilobytes.
+    ///
+    /// **Returns:**
+    /// - The looked-up type. A `text/*` type naming no charset gets `utf-8` or `utf-16` if the
+    ///   sample starts with that byte order mark; otherwise the configured charset, unless that
+    ///   is `utf-8` and the sample is not valid UTF-8 (a sequence cut off at its end is allowed),
+    ///   in which case no charset is added.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::mime::MimeTypes;
+    /// # use std::path::Path;
+    /// assert_eq!(MimeTypes::new().content_type_for(Path::new("old.txt"), b"caf\xe9 au lait"), "text/plain");
+    /// ```
+    pub fn content_type_for(&self, file: &Path, sample: &[u8]) -> String {
+        let configured = self.charset.as_deref();
+        let charset = if sample.starts_with(&[0xef, 0xbb, 0xbf]) {
+            Some("utf-8")
+        } else if sample.starts_with(&[0xfe, 0xff]) || sample.starts_with(&[0xff, 0xfe]) {
+            Some("utf-16")
+        } else {
+            match std::str::from_utf8(sample) {
+                Err(err) if err.error_len().is_some() => configured.filter(|charset| !charset.eq_ignore_ascii_case("utf-8")),
+                _ => configured,
+            }
+        };
+        with_charset(self.lookup(file), charset)
+    }
+}
+
+
+/// # content_type
+///
+/// **Purpose:**
+/// Builds the `Content-Type` value for a file from the built-in table alone.
+///
+/// **Parameters:**
+/// - `file`: Path of the file being served.
+///
+/// **Returns:**
+/// - A media type, defaulting to `application/octet-stream`, with a charset for `text/*`.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// assert_eq!(mime::content_type(Path::new("app.mjs")), "text/javascript; charset=utf-8");
+/// ```
+pub(crate) fn content_type(file: &Path) -> String {
+    with_charset(builtin(&extension(file)).unwrap_or(DEFAULT_TYPE), Some(DEFAULT_CHARSET))
+}
+
+
+/// # builtin
+///
+/// **Purpose:**
+/// Looks an extension up in the built-in table.
+///
+/// **Parameters:**
+/// - `extension`: Lower-case extension without the dot.
+///
+/// **Returns:**
+/// - Its media type, or `None`.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// assert_eq!(builtin("avif"), Some("image/avif"));
+/// ```
+fn builtin(extension: &str) -> Option<&'static str> {
+    BUILTIN
+        .binary_search_by_key(&extension, |(known, _)| known)
+        .ok()
+        .map(|index| BUILTIN[index].1)
+}
+
+
+/// # extension
+///
+/// **Purpose:**
+/// Extracts a file's extension for lookup.
+///
+/// **Parameters:**
+/// - `file`: Path of the file.
+///
+/// **Returns:**
+/// - The lower-cased extension, or an empty string if it has none.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// assert_eq!(extension(Path::new("Photo.AVIF")), "avif");
+/// ```
+fn extension(file: &Path) -> String {
+    file.extension()
+        .and_then(|ext| ext.to_str())
+        .unwrap_or("")
+        .to_ascii_lowercase()
+}
+
+
+/// # with_charset
+///
+/// **Purpose:**
+/// Adds the charset parameter text types are sent with.
+///
+/// **Parameters:**
+/// - `media_type`: Looked-up type, possibly with parameters.
+/// - `charset`: Charset to add, if any.
+///
+/// **Returns:**
+/// - `media_type; charset=<charset>` for a `text/*` type without a `charset` parameter,
+///   otherwise `media_type` unchanged.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// assert_eq!(with_charset("text/css", Some("utf-8")), "text/css; charset=utf-8");
+/// ```
+fn with_charset(media_type: &str, charset: Option<&str>) -> String {
+    let textual = media_type.get(..5).is_some_and(|prefix| prefix.eq_ignore_ascii_case("text/"));
+    match charset {
+        Some(charset) if textual && !media_type.to_ascii_lowercase().contains("charset=") => format!("{media_type}; charset={charset}"),
+        _ => media_type.to_string(),
+    }
+}
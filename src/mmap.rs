@@ -0,0 +1,204 @@
+
+// ============================================================
+//  DAEGONICA SOFTWARE — mmap.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: Mmap
+//!
+//! **Purpose:**
+//! Maps files read-only into memory so large bodies are paged in by the kernel as they are
+//! written instead of being copied into a buffer first.
+//!
+//! **Context:**
+//! - Used by `StaticFiles` for files above its large-file threshold when `sendfile` is off;
+//!   the mapping rides along in the `Response` until the body has been written.
+//!
+//! **Responsibilities:**
+//! - Maps a whole file with `mmap(2)` and unmaps it when the last reference is dropped.
+//! - Reports `Unsupported` on non-Unix platforms so callers fall back to reading the file.
+//! - Does NOT guard against the file being truncated while mapped; reading past the new end
+//!   raises `SIGBUS`, so only map files that are replaced rather than rewritten in place.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+use std::{fs::File, io};
+
+
+/// # MappedFile
+///
+/// **Summary:**
+/// Read-only view of a file's contents, unmapped on drop.
+///
+/// **Fields:**
+/// - `address`: Start of the mapping.
+/// - `len`: Length of the mapping in bytes.
+///
+/// **Usage Example:**
+/// ```rust,ignore
+/// let map = MappedFile::map(&File::open("html/video.mp4")?)?;
+/// stream.write_all(map.as_slice())?;
+/// ```
+#[derive(Debug)]
+pub(crate) struct MappedFile {
+    address: *const u8,
+    len: usize,
+}
+
+
+// SAFETY: the mapping is read-only and owned by this value alone, so it may be read from, and
+// dropped on, any thread.
+unsafe impl Send for MappedFile {}
+unsafe impl Sync for MappedFile {}
+
+
+impl MappedFile {
+
+    /// # map
+    ///
+    /// **Purpose:**
+    /// Maps the whole of an open file read-only.
+    ///
+    /// **Parameters:**
+    /// - `file`: File to map, open for reading.
+    ///
+    /// **Returns:**
+    /// - The mapping, which stays valid after `file` is closed.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns an I/O error if the file cannot be mapped.
+    /// - `InvalidInput` for empty files, which cannot be mapped.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let map = MappedFile::map(&sandbox.open_beneath(Path::new("video.mp4"))?)?;
+    /// ```
+    #[cfg(unix)]
+    pub(crate) fn map(file: &File) -> io::Result<MappedFile> {
+        use std::os::fd::AsRawFd;
+
+        let len = usize::try_from(file.metadata()?.len())
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "file too large to map"))?;
+        if len == 0 {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "cannot map an empty file"));
+        }
+        // SAFETY: a fresh read-only private mapping of an open descriptor; the kernel keeps
+        // it valid after the descriptor is closed, until `munmap` in `drop`.
+        let address = unsafe {
+            libc::mmap(std::ptr::null_mut(), len, libc::PROT_READ, libc::MAP_PRIVATE, file.as_raw_fd(), 0)
+        };
+        if address == libc::MAP_FAILED {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(MappedFile { address: address.cast(), len })
+    }
+
+    /// # map
+    ///
+    /// **Purpose:**
+    /// Stands in for mapping on platforms without `mmap(2)`.
+    ///
+    /// **Parameters:**
+    /// - `file`: File that would have been mapped.
+    ///
+    /// **Returns:**
+    /// Never returns a mapping.
+    ///
+    /// **Errors / Failures:**
+    /// - Always `Unsupported`.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let contents = MappedFile::map(&file).map(|map| map.as_slice().to_vec()).or_else(|_| fs::read(path))?;
+    /// ```
+    #[cfg(not(unix))]
+    pub(crate) fn map(_file: &File) -> io::Result<MappedFile> {
+        Err(io::Error::new(io::ErrorKind::Unsupported, "memory mapping needs a Unix platform"))
+    }
+
+    /// # as_slice
+    ///
+    /// **Purpose:**
+    /// Borrows the mapped contents.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - The file's bytes.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// stream.write_all(map.as_slice())?;
+    /// ```
+    pub(crate) fn as_slice(&self) -> &[u8] {
+        // SAFETY: `address` points at `len` readable bytes until `self` is dropped.
+        unsafe { std::slice::from_raw_parts(self.address, self.len) }
+    }
+}
+
+
+impl Drop for MappedFile {
+
+    /// # drop
+    ///
+    /// **Purpose:**
+    /// Unmaps the file.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// None.
+    ///
+    /// **Errors / Failures:**
+    /// None. A failed `munmap` leaks the mapping.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// drop(map);
+    /// ```
+    fn drop(&mut self) {
+        #[cfg(unix)]
+        // SAFETY: the mapping was created by `open` with this address and length and is not
+        // used again.
+        unsafe {
+            libc::munmap(self.address.cast_mut().cast(), self.len);
+        }
+    }
+}
+
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::{env, fs, process};
+
+    #[test]
+    fn maps_binary_files_byte_for_byte() {
+        let path = env::temp_dir().join(format!("mmap-test-{}.bin", process::id()));
+        let contents: Vec<u8> = (0..=255u8).cycle().take(10_000).collect();
+        fs::write(&path, &contents).unwrap();
+        let map = MappedFile::map(&File::open(&path).unwrap()).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(map.as_slice(), contents.as_slice(), "the mapping outlives the file's name");
+    }
+
+    #[test]
+    fn empty_files_cannot_be_mapped() {
+        let path = env::temp_dir().join(format!("mmap-test-{}.empty", process::id()));
+        fs::write(&path, b"").unwrap();
+        let error = MappedFile::map(&File::open(&path).unwrap()).unwrap_err();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+    }
+}
@@ -0,0 +1,614 @@
+
+// ============================================================
+//  DAEGONICA SOFTWARE — oidc.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: OIDC
+//!
+//! **Purpose:**
+//! Signs browser users in through an OpenID Connect identity provider (authorization code flow).
+//!
+//! **Context:**
+//! - Installed on a router after `Sessions` (see `Oidc::install`), e.g. to protect an internal
+//!   static site served through a catch-all route, without an authenticating proxy in front.
+//! - Signed-in requests carry the ID token's `Claims` and an `AuthenticatedUser` (`sub`).
+//!
+//! **Responsibilities:**
+//! - Redirects unauthenticated browser navigations to the provider with `state`, `nonce`, and PKCE.
+//! - Handles the callback: checks `state`, redeems the code, and verifies the ID token's
+//!   signature, issuer, audience, expiry, and nonce.
+//! - Keeps the verified claims in the session and returns the user to the page they asked for.
+//! - Does NOT refresh tokens or call the userinfo endpoint, and does NOT speak TLS: the
+//!   discovery, token, and JWKS endpoints must be `http://` URLs on a loopback host, such as a
+//!   local TLS-terminating forwarder to the provider (see `client`).
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+use std::{io, time::Duration};
+
+use crate::{
+    auth::AuthenticatedUser,
+    base64, client,
+    extract::{QueryParams, encode_component},
+    hash,
+    json_value::JsonValue,
+    jwt::{Claims, JwtAuth},
+    random,
+    request::{Request, invalid},
+    response::Response,
+    router::{Next, Router},
+    session::Session,
+};
+
+
+/// # CLAIMS_KEY
+///
+/// **Summary:**
+/// Session key holding the signed-in user's verified ID-token claims.
+pub const CLAIMS_KEY: &str = "oidc.claims";
+
+
+/// # PENDING_KEYS
+///
+/// **Summary:**
+/// Session keys of a login in progress: state, nonce, PKCE verifier, and return target.
+const PENDING_KEYS: [&str; 4] = ["oidc.state", "oidc.nonce", "oidc.verifier", "oidc.return"];
+
+
+/// # Provider
+///
+/// **Summary:**
+/// Endpoints of an OpenID Connect provider.
+///
+/// **Fields:**
+/// - `issuer`: Issuer identifier, matched against the ID token's `iss`.
+/// - `authorization_endpoint`: Where browsers are sent to sign in (may be HTTPS).
+/// - `token_endpoint`: Where codes are redeemed (loopback `http://`, fetched by the server).
+/// - `jwks_uri`: Provider signing keys (loopback `http://`, fetched by the server).
+///
+/// **Usage Example:**
+/// ```rust,no_run
+/// # use server::oidc::Provider;
+/// let provider = Provider::discover("http://127.0.0.1:8081/.well-known/openid-configuration")?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct Provider {
+    pub issuer: String,
+    pub authorization_endpoint: String,
+    pub token_endpoint: String,
+    pub jwks_uri: String,
+}
+
+
+/// # Oidc
+///
+/// **Summary:**
+/// OpenID Connect relying-party configuration.
+///
+/// **Fields:**
+/// - `provider`: Provider endpoints.
+/// - `client_id`: Client identifier registered with the provider.
+/// - `client_secret`: Client secret, sent with HTTP Basic to the token endpoint.
+/// - `redirect_uri`: Absolute callback URL registered with the provider.
+/// - `callback_path`: Path part of `redirect_uri`, routed to the callback handler.
+/// - `scopes`: Requested scopes; always includes `openid`.
+/// - `verifier`: ID-token verifier bound to the provider's keys, issuer, and this client.
+///
+/// **Usage Example:**
+/// ```rust,no_run
+/// # use server::{oidc::{Oidc, Provider}, response::Response, router::Router, session::Sessions, static_files::StaticFiles};
+/// # use std::env;
+/// # let provider = Provider::discover("http://127.0.0.1:8081/.well-known/openid-configuration")?;
+/// # let files = StaticFiles::new("html");
+/// let oidc = Oidc::new(provider, "intranet", env::var("OIDC_SECRET")?, "https://intranet.example.com/oidc/callback");
+/// let router = oidc.install(Router::new().middleware(Sessions::new().middleware()))
+///     .get("/*path", move |request| files.serve(request.path()).unwrap_or_else(|| Response::new(404, "Not Found")));
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct Oidc {
+    provider: Provider,
+    client_id: String,
+    client_secret: String,
+    redirect_uri: String,
+    callback_path: String,
+    scopes: Vec<String>,
+    verifier: JwtAuth,
+}
+
+
+impl Provider {
+
+    /// # discover
+    ///
+    /// **Purpose:**
+    /// Reads provider endpoints from an OpenID discovery document.
+    ///
+    /// **Parameters:**
+    /// - `url`: Loopback `http://` URL of `/.well-known/openid-configuration`.
+    ///
+    /// **Returns:**
+    /// - The provider endpoints.
+    ///
+    /// **Errors / Failures:**
+    /// - `InvalidInput` if `url`, the token endpoint, or the JWKS URI is not a loopback
+    ///   `http://` URL: the client secret and signing keys would cross the network unprotected.
+    /// - Returns an I/O error if the document cannot be fetched.
+    /// - `InvalidData` if it is not JSON or lacks a required endpoint.
+    ///
+    /// **Examples:**
+    /// ```rust,no_run
+    /// # use server::oidc::Provider;
+    /// let provider = Provider::discover("http://127.0.0.1:8081/.well-known/openid-configuration")?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn discover(url: &str) -> io::Result<Provider> {
+        client::require_loopback(url)?;
+        let response = client::send("GET", url, &[("Accept", "application/json")], b"")?;
+        if response.status != 200 {
+            return Err(invalid("discovery document request did not answer 200"));
+        }
+        let document = JsonValue::parse(&String::from_utf8_lossy(&response.body))
+            .ok_or_else(|| invalid("discovery document is not valid JSON"))?;
+        let field = |name: &str| {
+            document
+                .get(name)
+                .and_then(JsonValue::as_str)
+                .map(str::to_string)
+                .ok_or_else(|| invalid("discovery document is missing a required endpoint"))
+        };
+        let provider = Provider {
+            issuer: field("issuer")?,
+            authorization_endpoint: field("authorization_endpoint")?,
+            token_endpoint: field("token_endpoint")?,
+            jwks_uri: field("jwks_uri")?,
+        };
+        client::require_loopback(&provider.token_endpoint)?;
+        client::require_loopback(&provider.jwks_uri)?;
+        Ok(provider)
+    }
+}
+
+
+impl Oidc {
+
+    /// # new
+    ///
+    /// **Purpose:**
+    /// Creates a relying party requesting the `openid` scope.
+    ///
+    /// **Parameters:**
+    /// - `provider`: Provider endpoints.
+    /// - `client_id`: Registered client identifier.
+    /// - `client_secret`: Registered client secret.
+    /// - `redirect_uri`: Absolute callback URL registered with the provider.
+    ///
+    /// **Returns:**
+    /// - A new `Oidc`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,no_run
+    /// # use server::oidc::{Oidc, Provider};
+    /// # let provider = Provider::discover("http://127.0.0.1:8081/.well-known/openid-configuration")?;
+    /// # let secret = "s3cret";
+    /// let oidc = Oidc::new(provider, "intranet", secret, "http://localhost:7878/oidc/callback");
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn new(
+        provider: Provider,
+        client_id: impl Into<String>,
+        client_secret: impl Into<String>,
+        redirect_uri: impl Into<String>,
+    ) -> Oidc {
+        let client_id = client_id.into();
+        let redirect_uri = redirect_uri.into();
+        let callback_path = redirect_uri
+            .split_once("://")
+            .and_then(|(_, rest)| rest.find('/').map(|slash| rest[slash..].to_string()))
+            .map(|path| path.split('?').next().unwrap_or_default().to_string())
+            .unwrap_or_else(|| "/".to_string());
+        let verifier = JwtAuth::new()
+            .jwks_url(provider.jwks_uri.as_str(), Duration::from_secs(3600))
+            .issuer(provider.issuer.as_str())
+            .audience(client_id.as_str());
+
+        Oidc {
+            provider,
+            client_id,
+            client_secret: client_secret.into(),
+            redirect_uri,
+            callback_path,
+            scopes: vec!["openid".to_string()],
+            verifier,
+        }
+    }
+
+    /// # scopes
+    ///
+    /// **Purpose:**
+    /// Requests additional scopes (e.g. `email`, `profile`).
+    ///
+    /// **Parameters:**
+    /// - `scopes`: Extra scope names.
+    ///
+    /// **Returns:**
+    /// - The updated `Oidc`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,no_run
+    /// # use server::oidc::{Oidc, Provider};
+    /// # let provider = Provider::discover("http://127.0.0.1:8081/.well-known/openid-configuration")?;
+    /// # let oidc = Oidc::new(provider, "intranet", "s3cret", "http://localhost:7878/oidc/callback");
+    /// let oidc = oidc.scopes(&["email", "profile"]);
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn scopes(mut self, scopes: &[&str]) -> Oidc {
+        for scope in scopes {
+            if !self.scopes.iter().any(|existing| existing == scope) {
+                self.scopes.push(scope.to_string());
+            }
+        }
+        self
+    }
+
+    /// # install
+    ///
+    /// **Purpose:**
+    /// Adds the sign-in middleware to a router and routes the callback path to the handler
+    /// that completes logins.
+    ///
+    /// **Parameters:**
+    /// - `router`: Router whose routes need a signed-in user; its `Sessions` middleware must
+    ///   already be registered.
+    ///
+    /// **Returns:**
+    /// - The updated `Router`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,no_run
+    /// # use server::{oidc::{Oidc, Provider}, request::Request, response::Response, router::Router, session::Sessions};
+    /// # fn home(_: &Request) -> Response { Response::new(200, "OK") }
+    /// # let provider = Provider::discover("http://127.0.0.1:8081/.well-known/openid-configuration")?;
+    /// # let oidc = Oidc::new(provider, "intranet", "s3cret", "http://localhost:7878/oidc/callback");
+    /// let router = oidc.install(Router::new().middleware(Sessions::new().middleware())).get("/", home);
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn install(self, router: Router) -> Router {
+        let callback = self.clone();
+        let path = self.callback_path.clone();
+        router
+            .middleware(self.middleware())
+            .get(&path, move |request| callback.callback(request))
+    }
+
+    /// # middleware
+    ///
+    /// **Purpose:**
+    /// Turns the configuration into router middleware.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - A closure accepted by `Router::middleware`; prefer `install`, which also routes the callback.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,no_run
+    /// # use server::{oidc::{Oidc, Provider}, router::Router, session::Sessions};
+    /// # let provider = Provider::discover("http://127.0.0.1:8081/.well-known/openid-configuration")?;
+    /// # let oidc = Oidc::new(provider, "intranet", "s3cret", "http://localhost:7878/oidc/callback");
+    /// let router = Router::new().middleware(Sessions::new().middleware()).middleware(oidc.clone().middleware());
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn middleware(self) -> impl Fn(&Request, Next<'_>) -> Response + Send + Sync + 'static {
+        move |request, next| self.handle(request, next)
+    }
+
+    /// # handle
+    ///
+    /// **Purpose:**
+    /// Runs the chain for signed-in users and for the callback path, and starts a login for
+    /// everyone else.
+    ///
+    /// **Parameters:**
+    /// - `request`: Request being handled.
+    /// - `next`: The rest of the middleware chain.
+    ///
+    /// **Returns:**
+    /// - The downstream response for signed-in users.
+    /// - A `302 Found` to the provider for unauthenticated `GET`/`HEAD` requests accepting HTML.
+    /// - `401 Unauthorized` for other unauthenticated requests (e.g. API calls).
+    ///
+    /// **Errors / Failures:**
+    /// - Returns `500 Internal Server Error` if no `Sessions` middleware ran first.
+    ///
+    /// **Examples:**
+    /// ```rust,no_run
+    /// # use server::{oidc::{Oidc, Provider}, request::Request, response::Response, router::Next};
+    /// # let provider = Provider::discover("http://127.0.0.1:8081/.well-known/openid-configuration")?;
+    /// # let oidc = Oidc::new(provider, "intranet", "s3cret", "http://localhost:7878/oidc/callback");
+    /// # let request = &Request::read_from(&mut &b"GET / HTTP/1.1\r\n\r\n"[..])?;
+    /// # let next: Next<'_> = &|_| Response::new(200, "OK");
+    /// let response = oidc.handle(request, next);
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn handle(&self, request: &Request, next: Next<'_>) -> Response {
+        if request.path() == self.callback_path {
+            return next(request);
+        }
+        let Some(session) = request.extensions.get::<Session>().cloned() else {
+            return Response::new(500, "Internal Server Error");
+        };
+
+        if let Some(claims) = session.get(CLAIMS_KEY).and_then(Claims::from_payload) {
+            let mut request = request.clone();
+            if let Some(subject) = claims.subject() {
+                request.extensions.insert(AuthenticatedUser(subject.to_string()));
+            }
+            request.extensions.insert(claims);
+            return next(&request);
+        }
+
+        let browser = matches!(request.method.as_str(), "GET" | "HEAD")
+            && request.headers.get("Accept").is_some_and(|accept| accept.contains("text/html"));
+        if !browser {
+            return failure(401, "Unauthorized", "sign-in required");
+        }
+        self.login(request, &session)
+    }
+
+    /// # login
+    ///
+    /// **Purpose:**
+    /// Records a pending login in the session and redirects to the authorization endpoint.
+    ///
+    /// **Parameters:**
+    /// - `request`: Request that needs a signed-in user; its target is returned to afterwards.
+    /// - `session`: Visitor's session.
+    ///
+    /// **Returns:**
+    /// - The `302 Found` redirect.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// return self.login(request, &session);
+    /// ```
+    fn login(&self, request: &Request, session: &Session) -> Response {
+        let (state, nonce, verifier) = (random::token(), random::token(), random::token());
+        let challenge = base64::encode_url(&hash::sha256(verifier.as_bytes()));
+        for (key, value) in PENDING_KEYS.iter().zip([&state, &nonce, &verifier, &request.target]) {
+            session.insert(*key, value.as_str());
+        }
+
+        let query = [
+            ("response_type", "code"),
+            ("client_id", self.client_id.as_str()),
+            ("redirect_uri", self.redirect_uri.as_str()),
+            ("scope", self.scopes.join(" ").as_str()),
+            ("state", state.as_str()),
+            ("nonce", nonce.as_str()),
+            ("code_challenge", challenge.as_str()),
+            ("code_challenge_method", "S256"),
+        ]
+        .iter()
+        .map(|(name, value)| format!("{name}={}", encode_component(value)))
+        .collect::<Vec<_>>()
+        .join("&");
+        let separator = if self.provider.authorization_endpoint.contains('?') { '&' } else { '?' };
+        Response::redirect(302, format!("{}{separator}{query}", self.provider.authorization_endpoint))
+    }
+
+    /// # callback
+    ///
+    /// **Purpose:**
+    /// Completes a pending login: checks `state`, redeems the code, verifies the ID token, and
+    /// moves the signed-in session to a fresh ID.
+    ///
+    /// **Parameters:**
+    /// - `request`: Callback request from the provider redirect.
+    ///
+    /// **Returns:**
+    /// - A `302 Found` back to the originally requested page once signed in.
+    ///
+    /// **Errors / Failures:**
+    /// - `400 Bad Request` for missing parameters or a `state` mismatch.
+    /// - `403 Forbidden` if the provider reported an error (e.g. the user declined).
+    /// - `502 Bad Gateway` if the token endpoint fails or returns no ID token.
+    /// - `401 Unauthorized` if the ID token fails verification or its nonce does not match.
+    /// - `500 Internal Server Error` if no `Sessions` middleware ran first.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let response = self.callback(request);
+    /// ```
+    fn callback(&self, request: &Request) -> Response {
+        let Some(session) = request.extensions.get::<Session>() else {
+            return Response::new(500, "Internal Server Error");
+        };
+        let params = QueryParams::parse(request.query().unwrap_or(""));
+        let [state, nonce, verifier, target] = PENDING_KEYS.map(|key| session.get(key));
+        for key in PENDING_KEYS {
+            session.remove(key);
+        }
+        if let Some(error) = params.get("error") {
+            return failure(403, "Forbidden", &format!("sign-in failed: {error}"));
+        }
+        let (Some(code), Some(returned), Some(state), Some(nonce), Some(verifier)) =
+            (params.get("code"), params.get("state"), state, nonce, verifier)
+        else {
+            return failure(400, "Bad Request", "no sign-in in progress");
+        };
+        if !random::constant_time_eq(returned.as_bytes(), state.as_bytes()) {
+            return failure(400, "Bad Request", "sign-in state mismatch");
+        }
+
+        let Some(id_token) = self.redeem(code, &verifier) else {
+            return failure(502, "Bad Gateway", "token endpoint did not return an ID token");
+        };
+        let claims = match self.verifier.verify(&id_token) {
+            Ok(claims) => claims,
+            Err(reason) => return failure(401, "Unauthorized", reason),
+        };
+        if claims.get("nonce").and_then(JsonValue::as_str) != Some(nonce.as_str()) {
+            return failure(401, "Unauthorized", "ID token nonce mismatch");
+        }
+
+        session.insert(CLAIMS_KEY, claims.payload());
+        session.renew();
+        Response::redirect(302, target.unwrap_or_else(|| "/".to_string()))
+    }
+
+    /// # redeem
+    ///
+    /// **Purpose:**
+    /// Exchanges an authorization code for an ID token at the token endpoint.
+    ///
+    /// **Parameters:**
+    /// - `code`: Authorization code from the callback.
+    /// - `verifier`: PKCE code verifier of the pending login.
+    ///
+    /// **Returns:**
+    /// - `Some(id_token)`, or `None` if the token endpoint is not on a loopback host, the
+    ///   request fails, or the reply lacks one.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let id_token = self.redeem(code, &verifier)?;
+    /// ```
+    fn redeem(&self, code: &str, verifier: &str) -> Option<String> {
+        let body = [
+            ("grant_type", "authorization_code"),
+            ("code", code),
+            ("redirect_uri", self.redirect_uri.as_str()),
+            ("code_verifier", verifier),
+        ]
+        .iter()
+        .map(|(name, value)| format!("{name}={}", encode_component(value)))
+        .collect::<Vec<_>>()
+        .join("&");
+        let credentials = format!(
+            "{}:{}",
+            encode_component(&self.client_id),
+            encode_component(&self.client_secret)
+        );
+        let authorization = format!("Basic {}", base64::encode(credentials.as_bytes()));
+        client::require_loopback(&self.provider.token_endpoint).ok()?;
+
+        let response = client::send(
+            "POST",
+            &self.provider.token_endpoint,
+            &[
+                ("Content-Type", "application/x-www-form-urlencoded"),
+                ("Accept", "application/json"),
+                ("Authorization", authorization.as_str()),
+            ],
+            body.as_bytes(),
+        )
+        .ok()
+        .filter(|response| response.status == 200)?;
+        JsonValue::parse(&String::from_utf8_lossy(&response.body))?
+            .get("id_token")
+            .and_then(JsonValue::as_str)
+            .map(str::to_string)
+    }
+}
+
+
+/// # failure
+///
+/// **Purpose:**
+/// Builds a plain-text error page for a failed sign-in.
+///
+/// **Parameters:**
+/// - `status`: Status code.
+/// - `reason`: Reason phrase.
+/// - `message`: Body text.
+///
+/// **Returns:**
+/// - The error `Response`.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// return failure(400, "Bad Request", "sign-in state mismatch");
+/// ```
+fn failure(status: u16, reason: &str, message: &str) -> Response {
+    Response::new(status, reason)
+        .with_header("Content-Type", "text/plain; charset=utf-8")
+        .with_body(message.to_string())
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        io::{Read, Write},
+        net::TcpListener,
+        thread,
+    };
+
+    fn serve_once(body: &'static str) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = [0; 1024];
+            let _ = stream.read(&mut request);
+            let reply = format!("HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n{body}", body.len());
+            stream.write_all(reply.as_bytes()).unwrap();
+        });
+        format!("http://{address}/.well-known/openid-configuration")
+    }
+
+    #[test]
+    fn discovery_refuses_remote_plain_http() {
+        let error = Provider::discover("http://idp.example/.well-known/openid-configuration").unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn discovery_refuses_remote_endpoints_in_the_document() {
+        let url = serve_once(
+            r#"{"issuer":"https://idp.example","authorization_endpoint":"https://idp.example/auth","token_endpoint":"http://idp.example/token","jwks_uri":"http://127.0.0.1:9/jwks"}"#,
+        );
+        assert_eq!(Provider::discover(&url).unwrap_err().kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn discovery_accepts_loopback_endpoints() {
+        let url = serve_once(
+            r#"{"issuer":"https://idp.example","authorization_endpoint":"https://idp.example/auth","token_endpoint":"http://127.0.0.1:9/token","jwks_uri":"http://localhost:9/jwks"}"#,
+        );
+        let provider = Provider::discover(&url).unwrap();
+        assert_eq!(provider.authorization_endpoint, "https://idp.example/auth");
+    }
+}
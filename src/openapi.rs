@@ -0,0 +1,698 @@
+
+// ============================================================
+//  DAEGONICA SOFTWARE — openapi.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: OpenApi
+//!
+//! **Purpose:**
+//! Describes routed endpoints as an OpenAPI 3 document so API clients get documentation for free.
+//!
+//! **Context:**
+//! - Routes are annotated with `Router::with_doc(Operation)`; `Router::openapi` renders the
+//!   document and `Router::serve_openapi` publishes it at a chosen path.
+//!
+//! **Responsibilities:**
+//! - Collects per-route metadata: summary, description, tags, parameters, request and response types.
+//! - Describes Rust types as JSON Schema through the `ApiSchema` trait.
+//! - Converts router paths (`/users/:id(\d+)`) into OpenAPI templates (`/users/{id}`) and
+//!   documents undeclared path parameters as strings.
+//! - Does NOT derive schemas automatically; `ApiSchema` is implemented by hand for custom types.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+use crate::validate::json_string;
+
+
+/// # ApiSchema
+///
+/// **Summary:**
+/// A type with a JSON Schema description, used for parameters, request bodies, and responses.
+///
+/// **Usage Example:**
+/// ```rust
+/// # use server::openapi::ApiSchema;
+/// # struct User;
+/// impl ApiSchema for User {
+///     fn schema() -> String {
+///         format!(r#"{{"type":"object","properties":{{"id":{},"name":{}}}}}"#, u64::schema(), String::schema())
+///     }
+/// }
+/// ```
+pub trait ApiSchema {
+    /// # schema
+    ///
+    /// **Purpose:**
+    /// Describes the type as a JSON Schema object.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - The schema as JSON text.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::openapi::ApiSchema;
+    /// assert_eq!(bool::schema(), r#"{"type":"boolean"}"#);
+    /// ```
+    fn schema() -> String;
+}
+
+
+/// # Operation
+///
+/// **Summary:**
+/// Documentation for one route.
+///
+/// **Fields:**
+/// - `summary`: One-line summary.
+/// - `description`: Longer description.
+/// - `tags`: Tags grouping the operation in documentation UIs.
+/// - `parameters`: Declared path, query, and header parameters.
+/// - `request_body`: Schema of the JSON request body, if any.
+/// - `responses`: Status code, description, and optional JSON schema per documented response.
+///
+/// **Usage Example:**
+/// ```rust
+/// # use server::{openapi::{ApiSchema, Operation}, request::Request, response::Response, router::Router};
+/// # struct User;
+/// # impl ApiSchema for User { fn schema() -> String { r#"{"type":"object"}"#.to_string() } }
+/// # fn show_user(_: &Request) -> Response { Response::new(200, "OK") }
+/// let router = Router::new()
+///     .get("/users/:id(\\d+)", show_user)
+///     .with_doc(
+///         Operation::new()
+///             .summary("Fetch a user")
+///             .path_param::<u64>("id", "User ID")
+///             .returns::<User>(200, "The user")
+///             .response(404, "No such user"),
+///     )
+///     .serve_openapi("/openapi.json", "Users API", "1.0.0");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Operation {
+    summary: Option<String>,
+    description: Option<String>,
+    tags: Vec<String>,
+    parameters: Vec<Parameter>,
+    request_body: Option<String>,
+    responses: Vec<(u16, String, Option<String>)>,
+}
+
+
+/// # Parameter
+///
+/// **Summary:**
+/// One documented parameter.
+///
+/// **Fields:**
+/// - `name`: Parameter name.
+/// - `location`: `path`, `query`, or `header`.
+/// - `required`: Whether clients must send it.
+/// - `description`: Explanation.
+/// - `schema`: JSON Schema of the value.
+#[derive(Debug, Clone)]
+struct Parameter {
+    name: String,
+    location: &'static str,
+    required: bool,
+    description: String,
+    schema: String,
+}
+
+
+impl Operation {
+
+    /// # new
+    ///
+    /// **Purpose:**
+    /// Creates empty route documentation.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - A new `Operation`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::openapi::Operation;
+    /// let doc = Operation::new().summary("List users");
+    /// ```
+    pub fn new() -> Operation {
+        Operation::default()
+    }
+
+    /// # summary
+    ///
+    /// **Purpose:**
+    /// Sets the one-line summary.
+    ///
+    /// **Parameters:**
+    /// - `summary`: Summary text.
+    ///
+    /// **Returns:**
+    /// - The updated `Operation`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::openapi::Operation;
+    /// let doc = Operation::new().summary("Create an order");
+    /// ```
+    pub fn summary(mut self, summary: impl Into<String>) -> Operation {
+        self.summary = Some(summary.into());
+        self
+    }
+
+    /// # description
+    ///
+    /// **Purpose:**
+    /// Sets the longer description.
+    ///
+    /// **Parameters:**
+    /// - `description`: Description text (CommonMark is allowed by OpenAPI).
+    ///
+    /// **Returns:**
+    /// - The updated `Operation`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::openapi::Operation;
+    /// let doc = Operation::new().description("Orders are charged when they ship.");
+    /// ```
+    pub fn description(mut self, description: impl Into<String>) -> Operation {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// # tag
+    ///
+    /// **Purpose:**
+    /// Adds a grouping tag.
+    ///
+    /// **Parameters:**
+    /// - `tag`: Tag name.
+    ///
+    /// **Returns:**
+    /// - The updated `Operation`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::openapi::Operation;
+    /// let doc = Operation::new().tag("orders");
+    /// ```
+    pub fn tag(mut self, tag: impl Into<String>) -> Operation {
+        self.tags.push(tag.into());
+        self
+    }
+
+    /// # path_param
+    ///
+    /// **Purpose:**
+    /// Documents a path parameter with type `T`, replacing the default string description.
+    ///
+    /// **Parameters:**
+    /// - `name`: Parameter name as written in the route (without `:` or `*`).
+    /// - `description`: Explanation.
+    ///
+    /// **Returns:**
+    /// - The updated `Operation`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::openapi::Operation;
+    /// let doc = Operation::new().path_param::<u64>("id", "Order ID");
+    /// ```
+    pub fn path_param<T: ApiSchema>(self, name: &str, description: &str) -> Operation {
+        self.param::<T>(name, "path", true, description)
+    }
+
+    /// # query_param
+    ///
+    /// **Purpose:**
+    /// Documents a query-string parameter with type `T`.
+    ///
+    /// **Parameters:**
+    /// - `name`: Parameter name.
+    /// - `required`: Whether clients must send it.
+    /// - `description`: Explanation.
+    ///
+    /// **Returns:**
+    /// - The updated `Operation`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::openapi::Operation;
+    /// let doc = Operation::new().query_param::<u32>("page", false, "Page number, from 1");
+    /// ```
+    pub fn query_param<T: ApiSchema>(self, name: &str, required: bool, description: &str) -> Operation {
+        self.param::<T>(name, "query", required, description)
+    }
+
+    /// # header_param
+    ///
+    /// **Purpose:**
+    /// Documents a request header with type `T`.
+    ///
+    /// **Parameters:**
+    /// - `name`: Header name.
+    /// - `required`: Whether clients must send it.
+    /// - `description`: Explanation.
+    ///
+    /// **Returns:**
+    /// - The updated `Operation`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::openapi::Operation;
+    /// let doc = Operation::new().header_param::<String>("X-Request-Id", false, "Correlation ID");
+    /// ```
+    pub fn header_param<T: ApiSchema>(self, name: &str, required: bool, description: &str) -> Operation {
+        self.param::<T>(name, "header", required, description)
+    }
+
+    /// # request
+    ///
+    /// **Purpose:**
+    /// Documents a required JSON request body of type `T`.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - The updated `Operation`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::openapi::{ApiSchema, Operation};
+    /// # struct NewOrder;
+    /// # impl ApiSchema for NewOrder { fn schema() -> String { r#"{"type":"object"}"#.to_string() } }
+    /// let doc = Operation::new().request::<NewOrder>();
+    /// ```
+    pub fn request<T: ApiSchema>(mut self) -> Operation {
+        self.request_body = Some(T::schema());
+        self
+    }
+
+    /// # response
+    ///
+    /// **Purpose:**
+    /// Documents a response without a body schema.
+    ///
+    /// **Parameters:**
+    /// - `status`: Status code.
+    /// - `description`: When the response is sent.
+    ///
+    /// **Returns:**
+    /// - The updated `Operation`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::openapi::Operation;
+    /// let doc = Operation::new().response(204, "Deleted");
+    /// ```
+    pub fn response(mut self, status: u16, description: &str) -> Operation {
+        self.responses.push((status, description.to_string(), None));
+        self
+    }
+
+    /// # returns
+    ///
+    /// **Purpose:**
+    /// Documents a response with a JSON body of type `T`.
+    ///
+    /// **Parameters:**
+    /// - `status`: Status code.
+    /// - `description`: When the response is sent.
+    ///
+    /// **Returns:**
+    /// - The updated `Operation`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::openapi::{ApiSchema, Operation};
+    /// # struct Order;
+    /// # impl ApiSchema for Order { fn schema() -> String { r#"{"type":"object"}"#.to_string() } }
+    /// let doc = Operation::new().returns::<Vec<Order>>(200, "Matching orders");
+    /// ```
+    pub fn returns<T: ApiSchema>(mut self, status: u16, description: &str) -> Operation {
+        self.responses.push((status, description.to_string(), Some(T::schema())));
+        self
+    }
+
+    /// # param
+    ///
+    /// **Purpose:**
+    /// Records a parameter of any location.
+    ///
+    /// **Parameters:**
+    /// - `name`: Parameter name.
+    /// - `location`: `path`, `query`, or `header`.
+    /// - `required`: Whether clients must send it.
+    /// - `description`: Explanation.
+    ///
+    /// **Returns:**
+    /// - The updated `Operation`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// self.param::<u64>("id", "path", true, "Order ID")
+    /// ```
+    fn param<T: ApiSchema>(mut self, name: &str, location: &'static str, required: bool, description: &str) -> Operation {
+        self.parameters.push(Parameter {
+            name: name.to_string(),
+            location,
+            required,
+            description: description.to_string(),
+            schema: T::schema(),
+        });
+        self
+    }
+
+    /// # to_json
+    ///
+    /// **Purpose:**
+    /// Renders the OpenAPI operation object for a route path.
+    ///
+    /// **Parameters:**
+    /// - `captures`: Path parameters of the route with their regex constraints, in path order.
+    ///
+    /// **Returns:**
+    /// - The operation object as JSON text.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let json = operation.to_json(&[("id".into(), Some("\\d+".into()))]);
+    /// ```
+    fn to_json(&self, captures: &[(String, Option<String>)]) -> String {
+        let mut fields = Vec::new();
+        if let Some(summary) = &self.summary {
+            fields.push(format!(r#""summary":{}"#, json_string(summary)));
+        }
+        if let Some(description) = &self.description {
+            fields.push(format!(r#""description":{}"#, json_string(description)));
+        }
+        if !self.tags.is_empty() {
+            let tags: Vec<String> = self.tags.iter().map(|tag| json_string(tag)).collect();
+            fields.push(format!(r#""tags":[{}]"#, tags.join(",")));
+        }
+
+        let mut parameters: Vec<String> = captures
+            .iter()
+            .filter(|(name, _)| {
+                !self.parameters.iter().any(|param| param.location == "path" && &param.name == name)
+            })
+            .map(|(name, pattern)| {
+                let schema = match pattern {
+                    Some(pattern) => format!(r#"{{"type":"string","pattern":{}}}"#, json_string(&format!("^(?:{pattern})$"))),
+                    None => r#"{"type":"string"}"#.to_string(),
+                };
+                format!(r#"{{"name":{},"in":"path","required":true,"schema":{schema}}}"#, json_string(name))
+            })
+            .collect();
+        parameters.extend(self.parameters.iter().map(|param| {
+            format!(
+                r#"{{"name":{},"in":"{}","required":{},"description":{},"schema":{}}}"#,
+                json_string(&param.name),
+                param.location,
+                param.required,
+                json_string(&param.description),
+                param.schema
+            )
+        }));
+        if !parameters.is_empty() {
+            fields.push(format!(r#""parameters":[{}]"#, parameters.join(",")));
+        }
+
+        if let Some(schema) = &self.request_body {
+            fields.push(format!(
+                r#""requestBody":{{"required":true,"content":{{"application/json":{{"schema":{schema}}}}}}}"#
+            ));
+        }
+
+        let responses: Vec<String> = if self.responses.is_empty() {
+            vec![r#""200":{"description":"OK"}"#.to_string()]
+        } else {
+            self.responses
+                .iter()
+                .map(|(status, description, schema)| match schema {
+                    Some(schema) => format!(
+                        r#""{status}":{{"description":{},"content":{{"application/json":{{"schema":{schema}}}}}}}"#,
+                        json_string(description)
+                    ),
+                    None => format!(r#""{status}":{{"description":{}}}"#, json_string(description)),
+                })
+                .collect()
+        };
+        fields.push(format!(r#""responses":{{{}}}"#, responses.join(",")));
+
+        format!("{{{}}}", fields.join(","))
+    }
+}
+
+
+/// # document
+///
+/// **Purpose:**
+/// Renders an OpenAPI 3 document for a list of routes. Paths keep registration order, and only
+/// the first registration of a method + path pair is documented, matching dispatch.
+///
+/// **Parameters:**
+/// - `title`: API title.
+/// - `version`: API version.
+/// - `routes`: Method, path, and documentation of each route, in registration order.
+///
+/// **Returns:**
+/// - The document as JSON text.
+///
+/// **Errors / Failures:**
+/// None. Methods OpenAPI cannot describe are skipped.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let json = document("Users API", "1.0.0", &[("GET", "/users", Some(&doc))]);
+/// ```
+pub(crate) fn document(title: &str, version: &str, routes: &[(&str, &str, Option<&Operation>)]) -> String {
+    const METHODS: [&str; 8] = ["get", "put", "post", "delete", "options", "head", "patch", "trace"];
+    let undocumented = Operation::default();
+
+    let mut paths: Vec<(String, Vec<(String, String)>)> = Vec::new();
+    for (method, path, doc) in routes {
+        let method = method.to_ascii_lowercase();
+        if !METHODS.contains(&method.as_str()) {
+            continue;
+        }
+        let (template, captures) = template(path);
+        let index = match paths.iter().position(|(existing, _)| *existing == template) {
+            Some(index) => index,
+            None => {
+                paths.push((template, Vec::new()));
+                paths.len() - 1
+            }
+        };
+        let operations = &mut paths[index].1;
+        if operations.iter().any(|(existing, _)| *existing == method) {
+            continue;
+        }
+        operations.push((method, doc.unwrap_or(&undocumented).to_json(&captures)));
+    }
+
+    let paths: Vec<String> = paths
+        .iter()
+        .map(|(template, operations)| {
+            let operations: Vec<String> = operations
+                .iter()
+                .map(|(method, operation)| format!(r#""{method}":{operation}"#))
+                .collect();
+            format!("{}:{{{}}}", json_string(template), operations.join(","))
+        })
+        .collect();
+
+    format!(
+        r#"{{"openapi":"3.0.3","info":{{"title":{},"version":{}}},"paths":{{{}}}}}"#,
+        json_string(title),
+        json_string(version),
+        paths.join(",")
+    )
+}
+
+
+/// # template
+///
+/// **Purpose:**
+/// Converts a router path into an OpenAPI path template.
+///
+/// **Parameters:**
+/// - `path`: Route path, possibly with `:name`, `*name`, and `(regex)` constraints.
+///
+/// **Returns:**
+/// - The template (e.g. `/users/{id}`) and each parameter's name and constraint, in path order.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let (template, captures) = template("/users/:id(\\d+)");
+/// ```
+fn template(path: &str) -> (String, Vec<(String, Option<String>)>) {
+    let mut captures = Vec::new();
+    let segments: Vec<String> = path
+        .split('/')
+        .map(|segment| match segment.chars().next() {
+            Some(':' | '*') if segment.len() > 1 => {
+                let declaration = &segment[1..];
+                let (name, pattern) = match declaration
+                    .strip_suffix(')')
+                    .and_then(|declaration| declaration.split_once('('))
+                {
+                    Some((name, pattern)) => (name, Some(pattern.to_string())),
+                    None => (declaration, None),
+                };
+                captures.push((name.to_string(), pattern));
+                format!("{{{name}}}")
+            }
+            _ => segment.to_string(),
+        })
+        .collect();
+    (segments.join("/"), captures)
+}
+
+
+/// # impl_schema
+///
+/// **Purpose:**
+/// Implements `ApiSchema` for primitive types with a fixed schema.
+macro_rules! impl_schema {
+    ($($ty:ty => $schema:literal),* $(,)?) => {
+        $(
+            impl ApiSchema for $ty {
+                fn schema() -> String {
+                    $schema.to_string()
+                }
+            }
+        )*
+    };
+}
+
+impl_schema! {
+    String => r#"{"type":"string"}"#,
+    str => r#"{"type":"string"}"#,
+    char => r#"{"type":"string","minLength":1,"maxLength":1}"#,
+    bool => r#"{"type":"boolean"}"#,
+    u8 => r#"{"type":"integer","format":"int32","minimum":0}"#,
+    u16 => r#"{"type":"integer","format":"int32","minimum":0}"#,
+    u32 => r#"{"type":"integer","format":"int64","minimum":0}"#,
+    u64 => r#"{"type":"integer","format":"int64","minimum":0}"#,
+    usize => r#"{"type":"integer","format":"int64","minimum":0}"#,
+    i8 => r#"{"type":"integer","format":"int32"}"#,
+    i16 => r#"{"type":"integer","format":"int32"}"#,
+    i32 => r#"{"type":"integer","format":"int32"}"#,
+    i64 => r#"{"type":"integer","format":"int64"}"#,
+    isize => r#"{"type":"integer","format":"int64"}"#,
+    f32 => r#"{"type":"number","format":"float"}"#,
+    f64 => r#"{"type":"number","format":"double"}"#,
+}
+
+
+impl<T: ApiSchema> ApiSchema for Vec<T> {
+
+    /// # schema
+    ///
+    /// **Purpose:**
+    /// Describes a list as a JSON array of `T`.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - The array schema.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::openapi::ApiSchema;
+    /// let schema = Vec::<u64>::schema();
+    /// # assert!(schema.starts_with(r#"{"type":"array","items":"#));
+    /// ```
+    fn schema() -> String {
+        format!(r#"{{"type":"array","items":{}}}"#, T::schema())
+    }
+}
+
+
+impl<T: ApiSchema> ApiSchema for Option<T> {
+
+    /// # schema
+    ///
+    /// **Purpose:**
+    /// Describes an optional value as a nullable `T`.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - The schema of `T` with `nullable` set.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::openapi::ApiSchema;
+    /// let schema = Option::<String>::schema();
+    /// # assert!(schema.starts_with(r#"{"nullable":true"#));
+    /// ```
+    fn schema() -> String {
+        format!(r#"{{"nullable":true,"allOf":[{}]}}"#, T::schema())
+    }
+}
@@ -0,0 +1,404 @@
+
+// ============================================================
+//  DAEGONICA SOFTWARE — peer_limit.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: PeerLimit
+//!
+//! **Purpose:**
+//! Caps how many connections one client IP address may hold open at once.
+//!
+//! **Context:**
+//! - Checked by `Server::run` for each accepted connection, before the server-wide
+//!   concurrency cap and before it is queued for a worker.
+//!
+//! **Responsibilities:**
+//! - Counts open connections per remote IP address.
+//! - Refuses connections beyond the cap, with `429 Too Many Requests` or by closing them
+//!   outright, so one misbehaving client cannot occupy the whole worker pool.
+//! - Does NOT see through proxies or NAT; clients sharing an address share its cap.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+use std::{
+    collections::HashMap,
+    io,
+    net::{IpAddr, TcpStream},
+    sync::{
+        Arc,
+        Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::Duration,
+};
+
+use crate::{concurrency::DEFAULT_RETRY_AFTER, response::Response};
+
+
+/// # PeerLimit
+///
+/// **Summary:**
+/// Shared per-IP cap on open connections. Clones share the same counters, so a clone taken
+/// before `Server::run` keeps reporting live values.
+///
+/// **Fields:**
+/// - `limit`: Maximum open connections per IP address.
+/// - `respond`: Whether refused connections get a `429` reply rather than being closed silently.
+/// - `retry_after`: Delay suggested in the `429` reply.
+/// - `open`: Open connection counts by IP address.
+/// - `rejected`: Connections refused since start-up.
+///
+/// **Usage Example:**
+/// ```rust
+/// # use server::{peer_limit::PeerLimit, server::Server};
+/// let server = Server::new("0.0.0.0:80").peer_limit(PeerLimit::new(8));
+/// ```
+#[derive(Debug, Clone)]
+pub struct PeerLimit {
+    limit: usize,
+    respond: bool,
+    retry_after: Duration,
+    open: Arc<Mutex<HashMap<IpAddr, usize>>>,
+    rejected: Arc<AtomicU64>,
+}
+
+
+/// # PeerPermit
+///
+/// **Summary:**
+/// One admitted connection from an address; dropping it frees the address's slot.
+///
+/// **Fields:**
+/// - `ip`: Client address.
+/// - `open`: Counts to decrement on drop (`None` when the limit is unlimited and untracked).
+#[derive(Debug)]
+pub struct PeerPermit {
+    ip: IpAddr,
+    open: Option<Arc<Mutex<HashMap<IpAddr, usize>>>>,
+}
+
+
+impl Default for PeerLimit {
+
+    /// # default
+    ///
+    /// **Purpose:**
+    /// Creates an unlimited cap that tracks nothing.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - A `PeerLimit` with a limit of `usize::MAX`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::peer_limit::PeerLimit;
+    /// let limit = PeerLimit::default();
+    /// ```
+    fn default() -> PeerLimit {
+        PeerLimit {
+            limit: usize::MAX,
+            respond: true,
+            retry_after: DEFAULT_RETRY_AFTER,
+            open: Arc::default(),
+            rejected: Arc::default(),
+        }
+    }
+}
+
+
+impl PeerLimit {
+
+    /// # new
+    ///
+    /// **Purpose:**
+    /// Creates a cap of `limit` open connections per client IP address.
+    ///
+    /// **Parameters:**
+    /// - `limit`: Maximum open connections per address (at least 1).
+    ///
+    /// **Returns:**
+    /// - A new `PeerLimit` that answers refused connections with `429`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::peer_limit::PeerLimit;
+    /// let limit = PeerLimit::new(8);
+    /// ```
+    pub fn new(limit: usize) -> PeerLimit {
+        PeerLimit {
+            limit: limit.max(1),
+            ..PeerLimit::default()
+        }
+    }
+
+    /// # respond
+    ///
+    /// **Purpose:**
+    /// Chooses between answering refused connections with `429` and closing them without a reply.
+    ///
+    /// **Parameters:**
+    /// - `enabled`: `true` to send `429 Too Many Requests`, `false` to close silently.
+    ///
+    /// **Returns:**
+    /// - The updated `PeerLimit`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::peer_limit::PeerLimit;
+    /// let limit = PeerLimit::new(4).respond(false);
+    /// ```
+    pub fn respond(mut self, enabled: bool) -> PeerLimit {
+        self.respond = enabled;
+        self
+    }
+
+    /// # retry_after
+    ///
+    /// **Purpose:**
+    /// Sets the delay suggested to refused clients.
+    ///
+    /// **Parameters:**
+    /// - `delay`: `Retry-After` value, rounded down to whole seconds.
+    ///
+    /// **Returns:**
+    /// - The updated `PeerLimit`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::peer_limit::PeerLimit;
+    /// # use std::time::Duration;
+    /// let limit = PeerLimit::new(8).retry_after(Duration::from_secs(10));
+    /// ```
+    pub fn retry_after(mut self, delay: Duration) -> PeerLimit {
+        self.retry_after = delay;
+        self
+    }
+
+    /// # try_acquire
+    ///
+    /// **Purpose:**
+    /// Claims a slot for one connection from an address if its cap allows it.
+    ///
+    /// **Parameters:**
+    /// - `ip`: Client address.
+    ///
+    /// **Returns:**
+    /// - `Some(permit)` holding the slot until dropped, or `None` (counted as rejected) when the
+    ///   address is at its cap.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::peer_limit::PeerLimit;
+    /// # use std::net::SocketAddr;
+    /// # let limit = PeerLimit::new(8);
+    /// # let peer: SocketAddr = "127.0.0.1:50000".parse().unwrap();
+    /// let Some(permit) = limit.try_acquire(peer.ip()) else { return };
+    /// # assert_eq!(limit.connections(peer.ip()), 1);
+    /// ```
+    pub fn try_acquire(&self, ip: IpAddr) -> Option<PeerPermit> {
+        if self.limit == usize::MAX {
+            return Some(PeerPermit { ip, open: None });
+        }
+        let mut open = self.open.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let count = open.entry(ip).or_default();
+        if *count >= self.limit {
+            self.rejected.fetch_add(1, Ordering::Relaxed);
+            return None;
+        }
+        *count += 1;
+        Some(PeerPermit {
+            ip,
+            open: Some(Arc::clone(&self.open)),
+        })
+    }
+
+    /// # connections
+    ///
+    /// **Purpose:**
+    /// Returns how many connections an address has open.
+    ///
+    /// **Parameters:**
+    /// - `ip`: Client address.
+    ///
+    /// **Returns:**
+    /// - The open connection count (always `0` when unlimited, since nothing is tracked).
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::peer_limit::PeerLimit;
+    /// # let limit = PeerLimit::new(8);
+    /// let open = limit.connections("127.0.0.1".parse()?);
+    /// # assert_eq!(open, 0);
+    /// # Ok::<(), std::net::AddrParseError>(())
+    /// ```
+    pub fn connections(&self, ip: IpAddr) -> usize {
+        let open = self.open.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        open.get(&ip).copied().unwrap_or(0)
+    }
+
+    /// # rejected
+    ///
+    /// **Purpose:**
+    /// Returns how many connections have been refused.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - Connections refused since start-up.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::peer_limit::PeerLimit;
+    /// # let limit = PeerLimit::new(8);
+    /// println!("refused {}", limit.rejected());
+    /// ```
+    pub fn rejected(&self) -> u64 {
+        self.rejected.load(Ordering::Relaxed)
+    }
+
+    /// # reject
+    ///
+    /// **Purpose:**
+    /// Refuses a connection over its address's cap, with `429` or by closing it, without
+    /// blocking the accept loop.
+    ///
+    /// **Parameters:**
+    /// - `stream`: Connection being refused.
+    ///
+    /// **Returns:**
+    /// - `Ok(())` once the reply has been written, or at once when closing silently.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns an I/O error if the reply cannot be written within 100 ms.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// limit.reject(&stream)?;
+    /// ```
+    pub(crate) fn reject(&self, stream: &TcpStream) -> io::Result<()> {
+        if !self.respond {
+            return Ok(());
+        }
+        Response::new(429, "Too Many Requests")
+            .with_header("Retry-After", self.retry_after.as_secs().to_string())
+            .with_header("Content-Type", "text/plain; charset=utf-8")
+            .with_body("too many connections from this address")
+            .write_final(stream, Duration::from_millis(100))
+    }
+}
+
+
+impl Drop for PeerPermit {
+
+    /// # drop
+    ///
+    /// **Purpose:**
+    /// Frees the connection's slot, forgetting the address once it has none open.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// None.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::peer_limit::PeerLimit;
+    /// # let limit = PeerLimit::new(1);
+    /// # let permit = limit.try_acquire([127, 0, 0, 1].into()).unwrap();
+    /// drop(permit);
+    /// # assert!(limit.try_acquire([127, 0, 0, 1].into()).is_some());
+    /// ```
+    fn drop(&mut self) {
+        let Some(open) = &self.open else {
+            return;
+        };
+        let mut open = open.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(count) = open.get_mut(&self.ip) {
+            *count -= 1;
+            if *count == 0 {
+                open.remove(&self.ip);
+            }
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+    use std::net::{Ipv4Addr, TcpListener};
+
+    const A: IpAddr = IpAddr::V4(Ipv4Addr::new(192, 0, 2, 1));
+    const B: IpAddr = IpAddr::V4(Ipv4Addr::new(192, 0, 2, 2));
+
+    #[test]
+    fn each_address_gets_its_own_cap() {
+        let limit = PeerLimit::new(2);
+        let first = limit.try_acquire(A).unwrap();
+        let _second = limit.try_acquire(A).unwrap();
+        assert!(limit.try_acquire(A).is_none());
+        assert!(limit.try_acquire(B).is_some());
+        assert_eq!(limit.connections(A), 2);
+        assert_eq!(limit.rejected(), 1);
+        drop(first);
+        assert_eq!(limit.connections(A), 1);
+        assert!(limit.try_acquire(A).is_some());
+    }
+
+    #[test]
+    fn the_default_is_unlimited() {
+        let limit = PeerLimit::default();
+        let permits: Vec<_> = (0..100).map(|_| limit.try_acquire(A).unwrap()).collect();
+        assert_eq!(permits.len(), 100);
+    }
+
+    #[test]
+    fn refusals_are_429_only_when_responding() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        for (respond, expected) in [(true, "HTTP/1.1 429"), (false, "")] {
+            let mut client = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+            let (server, _) = listener.accept().unwrap();
+            PeerLimit::new(1).respond(respond).reject(&server).unwrap();
+            drop(server);
+            let mut reply = String::new();
+            client.read_to_string(&mut reply).unwrap();
+            assert!(reply.starts_with(expected) && (respond || reply.is_empty()), "{reply}");
+        }
+    }
+}
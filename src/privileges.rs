@@ -0,0 +1,373 @@
+
+// ============================================================
+//  DAEGONICA SOFTWARE — privileges.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: Privileges
+//!
+//! **Purpose:**
+//! Lets the server start as root to bind ports 80 and 443, then switch to an unprivileged
+//! account before it serves a single request.
+//!
+//! **Context:**
+//! - Set with `Server::privileges`; `run`, `start`, and `serve_async` drop them once the
+//!   public and admin listeners are bound and before any worker, admin, or service thread
+//!   starts.
+//!
+//! **Responsibilities:**
+//! - Resolves user and group names (or numeric ids) through the system account database.
+//! - Replaces the supplementary groups with the target group, then sets the group id and the
+//!   user id, in that order, so no step needs privileges that an earlier one gave up.
+//! - Verifies the drop stuck: the ids changed and root cannot be regained.
+//! - Does NOT keep capabilities, chroot, or reopen files already open; log files and
+//!   listeners opened as root stay usable.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+use std::io;
+
+#[cfg(unix)]
+use std::ffi::CString;
+
+
+/// # Privileges
+///
+/// **Summary:**
+/// Account to switch to after binding.
+///
+/// **Fields:**
+/// - `user`: User name or numeric id; its primary group is used unless `group` is set.
+/// - `group`: Group name or numeric id.
+///
+/// **Usage Example:**
+/// ```rust
+/// # use server::{privileges::Privileges, server::Server};
+/// let server = Server::new("0.0.0.0:80").privileges(Privileges::new().user("www-data").group("www-data"));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Privileges {
+    user: Option<String>,
+    group: Option<String>,
+}
+
+
+impl Privileges {
+
+    /// # new
+    ///
+    /// **Purpose:**
+    /// Creates a setting that changes nothing until a user or group is given.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - An empty `Privileges`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::privileges::Privileges;
+    /// let privileges = Privileges::new();
+    /// ```
+    pub fn new() -> Privileges {
+        Privileges::default()
+    }
+
+    /// # user
+    ///
+    /// **Purpose:**
+    /// Sets the user to run as.
+    ///
+    /// **Parameters:**
+    /// - `user`: User name, or a numeric user id.
+    ///
+    /// **Returns:**
+    /// - The updated `Privileges`.
+    ///
+    /// **Errors / Failures:**
+    /// None. Unknown users fail when the privileges are dropped.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::privileges::Privileges;
+    /// let privileges = Privileges::new().user("www-data");
+    /// ```
+    pub fn user(mut self, user: impl Into<String>) -> Privileges {
+        self.user = Some(user.into());
+        self
+    }
+
+    /// # group
+    ///
+    /// **Purpose:**
+    /// Sets the group to run as, instead of the user's primary group.
+    ///
+    /// **Parameters:**
+    /// - `group`: Group name, or a numeric group id.
+    ///
+    /// **Returns:**
+    /// - The updated `Privileges`.
+    ///
+    /// **Errors / Failures:**
+    /// None. Unknown groups fail when the privileges are dropped.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::privileges::Privileges;
+    /// let privileges = Privileges::new().user("deploy").group("www-data");
+    /// ```
+    pub fn group(mut self, group: impl Into<String>) -> Privileges {
+        self.group = Some(group.into());
+        self
+    }
+
+    /// # apply
+    ///
+    /// **Purpose:**
+    /// Switches the whole process to the configured group and user, for good.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - `Ok(())` once the process runs as the target account.
+    ///
+    /// **Errors / Failures:**
+    /// - `NotFound` if the user or group does not exist.
+    /// - Passes through the OS error of a failed `setgroups`, `setgid`, or `setuid`, usually
+    ///   `PermissionDenied` when the process is not root.
+    /// - `PermissionDenied` if the ids did not change or root could be regained afterwards.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// privileges.apply()?;
+    /// ```
+    #[cfg(unix)]
+    pub(crate) fn apply(&self) -> io::Result<()> {
+        let account = self.user.as_deref().map(lookup_user).transpose()?;
+        let gid = match (&self.group, &account) {
+            (Some(group), _) => Some(lookup_group(group)?),
+            (None, Some((_, gid))) => Some(*gid),
+            (None, None) => None,
+        };
+
+        if let Some(gid) = gid {
+            // SAFETY: `gid` is a single valid group id passed by pointer with a count of one.
+            check(unsafe { libc::setgroups(1, &raw const gid) })?;
+            // SAFETY: plain system call on a value.
+            check(unsafe { libc::setgid(gid) })?;
+        }
+        if let Some((uid, _)) = account {
+            // SAFETY: plain system call on a value.
+            check(unsafe { libc::setuid(uid) })?;
+        }
+
+        // SAFETY: these calls only read the process credentials.
+        let (uid, euid, gid_now, egid) = unsafe { (libc::getuid(), libc::geteuid(), libc::getgid(), libc::getegid()) };
+        let changed = account.is_none_or(|(target, _)| uid == target && euid == target)
+            && gid.is_none_or(|target| gid_now == target && egid == target);
+        // SAFETY: probing whether root can be regained; if it can, the caller refuses to serve.
+        let regained = account.is_some_and(|(target, _)| target != 0 && unsafe { libc::setuid(0) } == 0);
+        if !changed || regained {
+            return Err(io::Error::new(io::ErrorKind::PermissionDenied, "privileges were not dropped for good"));
+        }
+        Ok(())
+    }
+
+    /// # apply
+    ///
+    /// **Purpose:**
+    /// Stands in for dropping privileges on platforms without Unix accounts.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - `Ok(())` if nothing was asked for.
+    ///
+    /// **Errors / Failures:**
+    /// - `Unsupported` if a user or group was given.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// privileges.apply()?;
+    /// ```
+    #[cfg(not(unix))]
+    pub(crate) fn apply(&self) -> io::Result<()> {
+        if self.user.is_none() && self.group.is_none() {
+            return Ok(());
+        }
+        Err(io::Error::new(io::ErrorKind::Unsupported, "switching users needs a Unix platform"))
+    }
+}
+
+
+/// # lookup_user
+///
+/// **Purpose:**
+/// Resolves a user to its id and primary group.
+///
+/// **Parameters:**
+/// - `user`: User name, or a numeric id (whose primary group is then looked up if the id has
+///   an entry, and otherwise taken to be the same number).
+///
+/// **Returns:**
+/// - The user id and primary group id.
+///
+/// **Errors / Failures:**
+/// - `NotFound` if no such user exists.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let (uid, gid) = lookup_user("www-data")?;
+/// ```
+#[cfg(unix)]
+fn lookup_user(user: &str) -> io::Result<(libc::uid_t, libc::gid_t)> {
+    let mut buffer = vec![0 as libc::c_char; 16 * 1024];
+    // SAFETY: an all-zero `passwd` is a valid value for the call to fill in.
+    let mut entry: libc::passwd = unsafe { std::mem::zeroed() };
+    let mut found: *mut libc::passwd = std::ptr::null_mut();
+    let status = match user.parse::<libc::uid_t>() {
+        // SAFETY: every pointer refers to live storage of the size passed.
+        Ok(uid) => unsafe { libc::getpwuid_r(uid, &raw mut entry, buffer.as_mut_ptr(), buffer.len(), &raw mut found) },
+        Err(_) => {
+            let name = c_string(user)?;
+            // SAFETY: every pointer refers to live storage of the size passed.
+            unsafe { libc::getpwnam_r(name.as_ptr(), &raw mut entry, buffer.as_mut_ptr(), buffer.len(), &raw mut found) }
+        }
+    };
+    if found.is_null() {
+        if let Ok(uid) = user.parse::<libc::uid_t>() {
+            return Ok((uid, uid as libc::gid_t));
+        }
+        return Err(not_found("user", user, status));
+    }
+    Ok((entry.pw_uid, entry.pw_gid))
+}
+
+
+/// # lookup_group
+///
+/// **Purpose:**
+/// Resolves a group to its id.
+///
+/// **Parameters:**
+/// - `group`: Group name, or a numeric id, used as is.
+///
+/// **Returns:**
+/// - The group id.
+///
+/// **Errors / Failures:**
+/// - `NotFound` if no such group exists.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let gid = lookup_group("www-data")?;
+/// ```
+#[cfg(unix)]
+fn lookup_group(group: &str) -> io::Result<libc::gid_t> {
+    if let Ok(gid) = group.parse::<libc::gid_t>() {
+        return Ok(gid);
+    }
+    let name = c_string(group)?;
+    let mut buffer = vec![0 as libc::c_char; 64 * 1024];
+    // SAFETY: an all-zero `group` is a valid value for the call to fill in.
+    let mut entry: libc::group = unsafe { std::mem::zeroed() };
+    let mut found: *mut libc::group = std::ptr::null_mut();
+    // SAFETY: every pointer refers to live storage of the size passed.
+    let status = unsafe { libc::getgrnam_r(name.as_ptr(), &raw mut entry, buffer.as_mut_ptr(), buffer.len(), &raw mut found) };
+    if found.is_null() {
+        return Err(not_found("group", group, status));
+    }
+    Ok(entry.gr_gid)
+}
+
+
+/// # c_string
+///
+/// **Purpose:**
+/// Converts an account name for the C library.
+///
+/// **Parameters:**
+/// - `name`: User or group name.
+///
+/// **Returns:**
+/// - The NUL-terminated name.
+///
+/// **Errors / Failures:**
+/// - `InvalidInput` if `name` contains a NUL byte.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let name = c_string("www-data")?;
+/// ```
+#[cfg(unix)]
+fn c_string(name: &str) -> io::Result<CString> {
+    CString::new(name).map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "account names cannot contain NUL"))
+}
+
+
+/// # not_found
+///
+/// **Purpose:**
+/// Builds the error for an account the database does not know, or could not be read.
+///
+/// **Parameters:**
+/// - `kind`: `user` or `group`.
+/// - `name`: Name looked up.
+/// - `status`: Return code of the lookup; non-zero if the database itself failed.
+///
+/// **Returns:**
+/// - The lookup error, or `NotFound` naming the account.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// return Err(not_found("user", user, status));
+/// ```
+#[cfg(unix)]
+fn not_found(kind: &str, name: &str, status: libc::c_int) -> io::Error {
+    if status != 0 {
+        return io::Error::from_raw_os_error(status);
+    }
+    io::Error::new(io::ErrorKind::NotFound, format!("no such {kind}: {name}"))
+}
+
+
+/// # check
+///
+/// **Purpose:**
+/// Turns a C library return code into a result.
+///
+/// **Parameters:**
+/// - `status`: Return code; negative on failure.
+///
+/// **Returns:**
+/// - `Ok(())` on success.
+///
+/// **Errors / Failures:**
+/// - The OS error recorded by the failed call.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// check(unsafe { libc::setgid(gid) })?;
+/// ```
+#[cfg(unix)]
+fn check(status: libc::c_int) -> io::Result<()> {
+    if status < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
@@ -0,0 +1,1470 @@
+
+// ============================================================
+//  DAEGONICA SOFTWARE — proxy.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: Proxy
+//!
+//! **Purpose:**
+//! Forwards client requests to weighted upstream servers (reverse proxy mode), including WebSocket upgrades,
+//! or to the hosts clients name themselves (forward proxy mode), including `CONNECT` tunnels.
+//!
+//! **Context:**
+//! - Used by the server when started with `--proxy <host:port>` or `--forward-proxy`.
+//!
+//! **Responsibilities:**
+//! - Rewrites hop-by-hop headers and relays requests and responses.
+//! - Buffers upstream responses to `GET` requests, up to a size cap, so they can be cached.
+//! - Switches to bidirectional byte tunneling for `Upgrade: websocket` and `CONNECT` requests.
+//! - Restricts forward-proxy destinations to an allowlist of ports, and refuses loopback,
+//!   link-local, and private addresses unless explicitly allowed, so the proxy is not an open
+//!   relay into the network it runs in.
+//! - Bounds how long an upstream may take to accept a connection, and each read and write on it.
+//! - Abandons upstream exchanges whose client hangs up before the reply arrives.
+//! - Closes tunnels that carry no bytes in either direction for longer than an idle timeout.
+//! - Optionally requires Basic `Proxy-Authorization` from forward-proxy clients, answering
+//!   `407` otherwise; without it, anyone who can reach the listener may use the proxy, so keep
+//!   it on a loopback or otherwise private address.
+//! - Spreads reverse-proxied requests over upstreams in proportion to weights that can be
+//!   changed while serving.
+//! - Does NOT health-check, retry, or pool upstream connections.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+use std::{
+    io::{self, BufRead, BufReader, Read, Write},
+    net::{IpAddr, Shutdown, SocketAddr, TcpStream, ToSocketAddrs},
+    sync::{
+        Arc,
+        PoisonError,
+        RwLock,
+        atomic::{AtomicU64, Ordering},
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+#[cfg(feature = "otel")]
+use crate::trace;
+use crate::{auth::BasicAuth, headers::Headers, ip::Cidr, request::Request, response::Response, timeouts::{ClientStream, Timeouts}};
+
+
+/// # DEFAULT_ALLOWED_PORTS
+///
+/// **Summary:**
+/// Destination ports a forward proxy accepts unless configured otherwise.
+const DEFAULT_ALLOWED_PORTS: [u16; 2] = [80, 443];
+
+
+/// # DEFAULT_DENIED_DESTINATIONS
+///
+/// **Summary:**
+/// Address ranges a forward proxy refuses to reach unless configured otherwise: unspecified,
+/// loopback, link-local (cloud metadata endpoints included), private, and shared addresses.
+const DEFAULT_DENIED_DESTINATIONS: [&str; 11] = [
+    "0.0.0.0/8",
+    "10.0.0.0/8",
+    "100.64.0.0/10",
+    "127.0.0.0/8",
+    "169.254.0.0/16",
+    "172.16.0.0/12",
+    "192.168.0.0/16",
+    "::/128",
+    "::1/128",
+    "fc00::/7",
+    "fe80::/10",
+];
+
+
+/// # DEFAULT_CONNECT_TIMEOUT
+///
+/// **Summary:**
+/// Longest either proxy waits for an upstream or destination to accept a connection unless
+/// configured otherwise.
+const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+
+/// # DEFAULT_TUNNEL_IDLE
+///
+/// **Summary:**
+/// Longest a tunnel may carry no bytes in either direction before both proxies close it,
+/// unless configured otherwise.
+const DEFAULT_TUNNEL_IDLE: Duration = Duration::from_secs(300);
+
+
+/// # DEFAULT_MAX_RESPONSE
+///
+/// **Summary:**
+/// Largest upstream response a reverse proxy buffers unless configured otherwise.
+const DEFAULT_MAX_RESPONSE: usize = 10 * 1024 * 1024;
+
+
+/// # HOP_BY_HOP
+///
+/// **Summary:**
+/// Headers that apply to a single connection and must not be forwarded as-is (RFC 9110 §7.6.1).
+const HOP_BY_HOP: [&str; 8] = [
+    "Connection",
+    "Keep-Alive",
+    "Proxy-Connection",
+    "TE",
+    "Trailer",
+    "Transfer-Encoding",
+    "Upgrade",
+    "Content-Length",
+];
+
+
+/// # Proxy
+///
+/// **Summary:**
+/// Reverse proxy that relays every request to one of its upstream addresses, chosen by
+/// weighted round robin. Clones share the upstream list, so weight changes through any clone
+/// apply to all.
+///
+/// **Fields:**
+/// - `upstreams`: Upstream addresses in `host:port` form with their weights.
+/// - `next`: Requests dispatched so far, selecting the next upstream.
+/// - `connect_timeout`: Longest wait for an upstream to accept the connection.
+/// - `timeouts`: Read and write limits on upstream connections.
+/// - `max_response`: Largest upstream response buffered by `fetch`.
+/// - `tunnel_idle`: Longest a WebSocket tunnel may sit idle.
+///
+/// **Usage Example:**
+/// ```rust,no_run
+/// # use server::{proxy::Proxy, request::Request};
+/// # let request = Request::read_from(&mut &b"GET / HTTP/1.1\r\nHost: example.com\r\n\r\n"[..])?;
+/// let proxy = Proxy::new("127.0.0.1:3000").upstream("127.0.0.1:3001", 3);
+/// let response = proxy.fetch(&request)?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct Proxy {
+    upstreams: Arc<RwLock<Vec<(String, u32)>>>,
+    next: Arc<AtomicU64>,
+    connect_timeout: Duration,
+    timeouts: Timeouts,
+    max_response: usize,
+    tunnel_idle: Duration,
+}
+
+
+impl Proxy {
+
+    /// # new
+    ///
+    /// **Purpose:**
+    /// Creates a proxy targeting the given upstream address, with a 10 second connect timeout
+    /// the same 30 second read and write limits as client connections, a 10 MiB cap on buffered
+    /// responses, and a 5 minute idle timeout for WebSocket tunnels.
+    ///
+    /// **Parameters:**
+    /// - `upstream`: Upstream address in `host:port` form.
+    ///
+    /// **Returns:**
+    /// - A new `Proxy` instance.
+    ///
+    /// **Errors / Failures:**
+    /// None. The address is resolved on each forwarded request.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::proxy::Proxy;
+    /// let proxy = Proxy::new("127.0.0.1:3000");
+    /// ```
+    pub fn new(upstream: impl Into<String>) -> Proxy {
+        Proxy {
+            upstreams: Arc::new(RwLock::new(vec![(upstream.into(), 1)])),
+            next: Arc::default(),
+            connect_timeout: DEFAULT_CONNECT_TIMEOUT,
+            timeouts: Timeouts::default(),
+            max_response: DEFAULT_MAX_RESPONSE,
+            tunnel_idle: DEFAULT_TUNNEL_IDLE,
+        }
+    }
+
+    /// # connect_timeout
+    ///
+    /// **Purpose:**
+    /// Sets how long to wait for an upstream to accept a connection.
+    ///
+    /// **Parameters:**
+    /// - `timeout`: Longest wait per upstream address.
+    ///
+    /// **Returns:**
+    /// - The updated `Proxy`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::proxy::Proxy;
+    /// # use std::time::Duration;
+    /// let proxy = Proxy::new("127.0.0.1:3000").connect_timeout(Duration::from_secs(2));
+    /// ```
+    pub fn connect_timeout(mut self, timeout: Duration) -> Proxy {
+        self.connect_timeout = timeout;
+        self
+    }
+
+    /// # timeouts
+    ///
+    /// **Purpose:**
+    /// Sets the read and write limits on upstream connections, so an upstream that stops
+    /// answering releases the worker; the header and exchange deadlines are ignored here.
+    ///
+    /// **Parameters:**
+    /// - `timeouts`: Limits whose `read` and `write` values apply to each upstream socket.
+    ///
+    /// **Returns:**
+    /// - The updated `Proxy`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{proxy::Proxy, timeouts::Timeouts};
+    /// # use std::time::Duration;
+    /// let proxy = Proxy::new("127.0.0.1:3000").timeouts(Timeouts::none().read(Duration::from_secs(60)));
+    /// ```
+    pub fn timeouts(mut self, timeouts: Timeouts) -> Proxy {
+        self.timeouts = timeouts;
+        self
+    }
+
+    /// # max_response
+    ///
+    /// **Purpose:**
+    /// Caps the size of upstream responses buffered by `fetch`, so a misbehaving upstream
+    /// cannot exhaust memory; larger responses are answered with `502 Bad Gateway`.
+    ///
+    /// **Parameters:**
+    /// - `bytes`: Most bytes of status line, headers, and decoded body accepted.
+    ///
+    /// **Returns:**
+    /// - The updated `Proxy`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::proxy::Proxy;
+    /// let proxy = Proxy::new("127.0.0.1:3000").max_response(64 * 1024 * 1024);
+    /// ```
+    pub fn max_response(mut self, bytes: usize) -> Proxy {
+        self.max_response = bytes;
+        self
+    }
+
+    /// # tunnel_idle
+    ///
+    /// **Purpose:**
+    /// Sets how long a WebSocket tunnel may carry no bytes in either direction before it is closed.
+    ///
+    /// **Parameters:**
+    /// - `timeout`: Idle limit; must be non-zero.
+    ///
+    /// **Returns:**
+    /// - The updated `Proxy`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::proxy::Proxy;
+    /// # use std::time::Duration;
+    /// let proxy = Proxy::new("127.0.0.1:3000").tunnel_idle(Duration::from_secs(60));
+    /// ```
+    pub fn tunnel_idle(mut self, timeout: Duration) -> Proxy {
+        self.tunnel_idle = timeout;
+        self
+    }
+
+    /// # upstream
+    ///
+    /// **Purpose:**
+    /// Adds another upstream, or changes the weight of one already listed.
+    ///
+    /// **Parameters:**
+    /// - `upstream`: Upstream address in `host:port` form.
+    /// - `weight`: Share of requests relative to the other upstreams; `0` takes it out of rotation.
+    ///
+    /// **Returns:**
+    /// - The updated `Proxy`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::proxy::Proxy;
+    /// let proxy = Proxy::new("10.0.0.1:8080").upstream("10.0.0.2:8080", 2);
+    /// ```
+    pub fn upstream(self, upstream: impl Into<String>, weight: u32) -> Proxy {
+        let upstream = upstream.into();
+        if !self.set_weight(&upstream, weight) {
+            self.upstreams.write().unwrap_or_else(PoisonError::into_inner).push((upstream, weight));
+        }
+        self
+    }
+
+    /// # set_weight
+    ///
+    /// **Purpose:**
+    /// Changes an upstream's weight while serving; requests already sent are unaffected.
+    ///
+    /// **Parameters:**
+    /// - `upstream`: Address of a listed upstream.
+    /// - `weight`: New weight; `0` takes it out of rotation.
+    ///
+    /// **Returns:**
+    /// - `true` if the upstream is listed, `false` if nothing was changed.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::proxy::Proxy;
+    /// # let proxy = Proxy::new("10.0.0.1:8080").upstream("10.0.0.2:8080", 2);
+    /// proxy.set_weight("10.0.0.2:8080", 0);
+    /// # assert_eq!(proxy.weights()[1].1, 0);
+    /// ```
+    pub fn set_weight(&self, upstream: &str, weight: u32) -> bool {
+        let mut upstreams = self.upstreams.write().unwrap_or_else(PoisonError::into_inner);
+        match upstreams.iter_mut().find(|(address, _)| address == upstream) {
+            Some((_, current)) => {
+                *current = weight;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// # weights
+    ///
+    /// **Purpose:**
+    /// Lists the upstreams and their current weights.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - `(address, weight)` pairs in the order they were added.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::proxy::Proxy;
+    /// # let proxy = Proxy::new("10.0.0.1:8080").upstream("10.0.0.2:8080", 2);
+    /// for (address, weight) in proxy.weights() { println!("{address} {weight}"); }
+    /// ```
+    pub fn weights(&self) -> Vec<(String, u32)> {
+        self.upstreams.read().unwrap_or_else(PoisonError::into_inner).clone()
+    }
+
+    /// # pick
+    ///
+    /// **Purpose:**
+    /// Chooses the upstream for the next request, each one getting its weight's share.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - The chosen upstream address.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns `NotConnected` if every upstream has weight `0`.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let address = self.pick()?;
+    /// ```
+    fn pick(&self) -> io::Result<String> {
+        let upstreams = self.upstreams.read().unwrap_or_else(PoisonError::into_inner);
+        let total: u64 = upstreams.iter().map(|(_, weight)| u64::from(*weight)).sum();
+        let mut slot = self.next.fetch_add(1, Ordering::Relaxed) % total.max(1);
+        upstreams
+            .iter()
+            .find(|(_, weight)| {
+                let weight = u64::from(*weight);
+                slot = match slot.checked_sub(weight) {
+                    Some(rest) => rest,
+                    None => return true,
+                };
+                false
+            })
+            .map(|(address, _)| address.clone())
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotConnected, "no upstream is in rotation"))
+    }
+
+    /// # fetch
+    ///
+    /// **Purpose:**
+    /// Sends a `GET` request to the upstream and buffers the complete reply, with hop-by-hop
+    /// headers removed, so it can be cached and replayed.
+    ///
+    /// **Parameters:**
+    /// - `request`: Request already read from the client.
+    ///
+    /// **Returns:**
+    /// - The upstream `Response`.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns an I/O error if the upstream cannot be reached or replies with a malformed response.
+    /// - Returns `TimedOut` (or `WouldBlock`) if the upstream does not accept, read, or answer in time.
+    /// - Returns an error carrying a `LimitExceeded` if the response is larger than `max_response`.
+    ///
+    /// **Examples:**
+    /// ```rust,no_run
+    /// # use server::{proxy::Proxy, request::Request};
+    /// # let proxy = Proxy::new("127.0.0.1:3000");
+    /// # let request = Request::read_from(&mut &b"GET / HTTP/1.1\r\nHost: example.com\r\n\r\n"[..])?;
+    /// let response = proxy.fetch(&request)?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn fetch(&self, request: &Request) -> io::Result<Response> {
+        let address = self.pick()?;
+        #[cfg(feature = "otel")]
+        let _span = trace::client_span(&request.method, &address);
+        let mut upstream = self.connect(&address)?;
+        abandon_on_cancel(request, &upstream);
+        write_head(&mut upstream, request, false)?;
+        upstream.write_all(&request.body)?;
+
+        let mut response = Response::read_from(&mut BufReader::new(&upstream), self.max_response)?;
+        response.headers = end_to_end(&response.headers);
+        #[cfg(feature = "otel")]
+        trace::set_status(response.status);
+        Ok(response)
+    }
+
+    /// # forward
+    ///
+    /// **Purpose:**
+    /// Relays `request` to the upstream and streams the upstream's reply back to the client.
+    /// WebSocket upgrade requests are handed off to [`tunnel`] once the handshake is sent, so
+    /// frames flow in both directions until either side closes.
+    ///
+    /// **Parameters:**
+    /// - `request`: Request already read from the client.
+    /// - `client`: Buffered reader over the client connection; any bytes it has buffered beyond the request are forwarded too.
+    ///
+    /// **Returns:**
+    /// - `Ok(())` once the exchange (or tunnel) has finished.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns an I/O error if the upstream cannot be reached or either connection fails mid-transfer.
+    /// - Returns `TimedOut` (or `WouldBlock`) if the upstream does not accept, read, or answer in time.
+    ///
+    /// **Examples:**
+    /// ```rust,no_run
+    /// # use server::{proxy::Proxy, request::Request, timeouts::{ClientStream, Timeouts}};
+    /// # let proxy = Proxy::new("127.0.0.1:3000");
+    /// # let request = Request::read_from(&mut &b"GET / HTTP/1.1\r\nHost: example.com\r\n\r\n"[..])?;
+    /// # let listener = std::net::TcpListener::bind("127.0.0.1:0")?;
+    /// # let _peer = std::net::TcpStream::connect(listener.local_addr()?)?;
+    /// # let (stream, _) = listener.accept()?;
+    /// # let mut reader = std::io::BufReader::new(ClientStream::new(&stream, Timeouts::new()));
+    /// proxy.forward(&request, &mut reader)?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn forward(&self, request: &Request, client: &mut BufReader<ClientStream<'_>>) -> io::Result<()> {
+        let upstream = self.connect(&self.pick()?)?;
+        relay(upstream, request, client, self.tunnel_idle)
+    }
+
+    /// # connect
+    ///
+    /// **Purpose:**
+    /// Resolves an upstream address and connects to the first of its addresses that answers
+    /// within the connect timeout, then bounds reads and writes on the new socket.
+    ///
+    /// **Parameters:**
+    /// - `address`: Upstream address in `host:port` form.
+    ///
+    /// **Returns:**
+    /// - The connected upstream socket.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns the resolution error, or the error of the last address tried if none accepts
+    ///   (`TimedOut` once the connect timeout runs out).
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let upstream = self.connect(&self.pick()?)?;
+    /// ```
+    fn connect(&self, address: &str) -> io::Result<TcpStream> {
+        let mut last = io::Error::new(io::ErrorKind::NotFound, format!("{address} did not resolve"));
+        for candidate in address.to_socket_addrs()? {
+            match TcpStream::connect_timeout(&candidate, self.connect_timeout) {
+                Ok(upstream) => {
+                    self.timeouts.apply(&upstream)?;
+                    return Ok(upstream);
+                }
+                Err(error) => last = error,
+            }
+        }
+        Err(last)
+    }
+}
+
+
+/// # ForwardProxy
+///
+/// **Summary:**
+/// Explicit forward proxy: relays absolute-form requests (`GET http://host/path`) and opens
+/// blind TCP tunnels for `CONNECT host:port`, only towards allowlisted ports on permitted
+/// addresses.
+///
+/// **Fields:**
+/// - `allowed_ports`: Destination ports clients may reach.
+/// - `denied`: Destination address ranges refused.
+/// - `allowed`: Ranges exempted from `denied`.
+/// - `connect_timeout`: Longest wait for a destination to accept the connection.
+/// - `tunnel_idle`: Longest a `CONNECT` tunnel may sit idle.
+/// - `auth`: Credentials clients must present in `Proxy-Authorization`, if required.
+///
+/// **Usage Example:**
+/// ```rust,no_run
+/// # use server::{proxy::ForwardProxy, request::Request, timeouts::{ClientStream, Timeouts}};
+/// # let request = Request::read_from(&mut &b"GET / HTTP/1.1\r\nHost: example.com\r\n\r\n"[..])?;
+/// # let listener = std::net::TcpListener::bind("127.0.0.1:0")?;
+/// # let _peer = std::net::TcpStream::connect(listener.local_addr()?)?;
+/// # let (stream, _) = listener.accept()?;
+/// # let mut reader = std::io::BufReader::new(ClientStream::new(&stream, Timeouts::new()));
+/// let proxy = ForwardProxy::new().allow_ports(vec![443]).allow_destination("10.20.0.0/16")?;
+/// if proxy.handles(&request) { proxy.serve(&request, &mut reader)?; }
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub struct ForwardProxy {
+    allowed_ports: Vec<u16>,
+    denied: Vec<Cidr>,
+    allowed: Vec<Cidr>,
+    connect_timeout: Duration,
+    tunnel_idle: Duration,
+    auth: Option<BasicAuth>,
+}
+
+
+impl Default for ForwardProxy {
+    /// # default
+    ///
+    /// **Purpose:**
+    /// Same as `ForwardProxy::new`.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - A `ForwardProxy` allowing ports 80 and 443 on public addresses.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    fn default() -> ForwardProxy {
+        ForwardProxy::new()
+    }
+}
+
+
+impl ForwardProxy {
+
+    /// # new
+    ///
+    /// **Purpose:**
+    /// Creates a forward proxy allowing destination ports 80 and 443 on public addresses to any
+    /// client, with a 10 second connect timeout and a 5 minute tunnel idle timeout.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - A new `ForwardProxy` instance.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::proxy::ForwardProxy;
+    /// let proxy = ForwardProxy::new();
+    /// ```
+    pub fn new() -> ForwardProxy {
+        ForwardProxy {
+            allowed_ports: DEFAULT_ALLOWED_PORTS.to_vec(),
+            denied: DEFAULT_DENIED_DESTINATIONS.iter().filter_map(|range| Cidr::parse(range).ok()).collect(),
+            allowed: Vec::new(),
+            connect_timeout: DEFAULT_CONNECT_TIMEOUT,
+            tunnel_idle: DEFAULT_TUNNEL_IDLE,
+            auth: None,
+        }
+    }
+
+    /// # allow_ports
+    ///
+    /// **Purpose:**
+    /// Replaces the destination port allowlist.
+    ///
+    /// **Parameters:**
+    /// - `ports`: Ports clients may connect or tunnel to.
+    ///
+    /// **Returns:**
+    /// - The updated `ForwardProxy`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::proxy::ForwardProxy;
+    /// let proxy = ForwardProxy::new().allow_ports(vec![443, 8443]);
+    /// ```
+    pub fn allow_ports(mut self, ports: Vec<u16>) -> ForwardProxy {
+        self.allowed_ports = ports;
+        self
+    }
+
+    /// # allow_destination
+    ///
+    /// **Purpose:**
+    /// Lets clients reach an address range that is otherwise denied, such as an internal
+    /// network the proxy is meant to front.
+    ///
+    /// **Parameters:**
+    /// - `range`: CIDR range or single address.
+    ///
+    /// **Returns:**
+    /// - The updated `ForwardProxy`.
+    ///
+    /// **Errors / Failures:**
+    /// - `InvalidInput` if the range is malformed.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::proxy::ForwardProxy;
+    /// let proxy = ForwardProxy::new().allow_destination("10.20.0.0/16")?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn allow_destination(mut self, range: &str) -> io::Result<ForwardProxy> {
+        self.allowed.push(Cidr::parse(range)?);
+        Ok(self)
+    }
+
+    /// # deny_destination
+    ///
+    /// **Purpose:**
+    /// Refuses an address range on top of the default loopback, link-local, and private ones.
+    ///
+    /// **Parameters:**
+    /// - `range`: CIDR range or single address.
+    ///
+    /// **Returns:**
+    /// - The updated `ForwardProxy`.
+    ///
+    /// **Errors / Failures:**
+    /// - `InvalidInput` if the range is malformed.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::proxy::ForwardProxy;
+    /// let proxy = ForwardProxy::new().deny_destination("203.0.113.0/24")?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn deny_destination(mut self, range: &str) -> io::Result<ForwardProxy> {
+        self.denied.push(Cidr::parse(range)?);
+        Ok(self)
+    }
+
+    /// # connect_timeout
+    ///
+    /// **Purpose:**
+    /// Sets how long to wait for a destination to accept the connection.
+    ///
+    /// **Parameters:**
+    /// - `timeout`: Longest wait per destination address.
+    ///
+    /// **Returns:**
+    /// - The updated `ForwardProxy`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::proxy::ForwardProxy;
+    /// # use std::time::Duration;
+    /// let proxy = ForwardProxy::new().connect_timeout(Duration::from_secs(3));
+    /// ```
+    pub fn connect_timeout(mut self, timeout: Duration) -> ForwardProxy {
+        self.connect_timeout = timeout;
+        self
+    }
+
+    /// # tunnel_idle
+    ///
+    /// **Purpose:**
+    /// Sets how long a `CONNECT` tunnel may carry no bytes in either direction before it is closed.
+    ///
+    /// **Parameters:**
+    /// - `timeout`: Idle limit; must be non-zero.
+    ///
+    /// **Returns:**
+    /// - The updated `ForwardProxy`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::proxy::ForwardProxy;
+    /// # use std::time::Duration;
+    /// let proxy = ForwardProxy::new().tunnel_idle(Duration::from_secs(60));
+    /// ```
+    pub fn tunnel_idle(mut self, timeout: Duration) -> ForwardProxy {
+        self.tunnel_idle = timeout;
+        self
+    }
+
+    /// # authenticate
+    ///
+    /// **Purpose:**
+    /// Requires clients to present Basic credentials in `Proxy-Authorization` before anything
+    /// is relayed; others are answered with `407 Proxy Authentication Required`.
+    ///
+    /// **Parameters:**
+    /// - `auth`: Users allowed to use the proxy; its realm names the challenge.
+    ///
+    /// **Returns:**
+    /// - The updated `ForwardProxy`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,no_run
+    /// # use server::{auth::BasicAuth, proxy::ForwardProxy};
+    /// let proxy = ForwardProxy::new().authenticate(BasicAuth::new("Proxy").htpasswd("/etc/daegonica/proxy.htpasswd")?);
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn authenticate(mut self, auth: BasicAuth) -> ForwardProxy {
+        self.auth = Some(auth);
+        self
+    }
+
+    /// # handles
+    ///
+    /// **Purpose:**
+    /// Checks whether a request is addressed to the proxy (`CONNECT` or an absolute-form target)
+    /// rather than to this server. Origin-form targets (`/path`, even with `://` in the query)
+    /// and the asterisk form (`OPTIONS *`) are the server's own.
+    ///
+    /// **Parameters:**
+    /// - `request`: Parsed client request.
+    ///
+    /// **Returns:**
+    /// - `true` if `serve` should handle the request.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{proxy::ForwardProxy, request::Request};
+    /// # let proxy = ForwardProxy::new();
+    /// # let request = Request::read_from(&mut &b"CONNECT example.com:443 HTTP/1.1\r\nHost: example.com:443\r\n\r\n"[..])?;
+    /// if proxy.handles(&request) { /* ... */ }
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn handles(&self, request: &Request) -> bool {
+        request.method == "CONNECT" || (!request.target.starts_with('/') && request.target != "*")
+    }
+
+    /// # serve
+    ///
+    /// **Purpose:**
+    /// Handles a forward-proxy request. `CONNECT` gets `200 Connection Established` followed by a
+    /// blind tunnel; absolute-form requests are rewritten to origin form and relayed.
+    ///
+    /// **Parameters:**
+    /// - `request`: Request for which `handles` returned `true`.
+    /// - `client`: Buffered reader over the client connection.
+    ///
+    /// **Returns:**
+    /// - `Ok(())` once the exchange (or tunnel) has finished.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns an I/O error if either connection fails mid-transfer.
+    /// - Answers `407` when credentials are required and missing or wrong, `400` for unusable
+    ///   targets, `403` for ports outside the allowlist or hosts
+    ///   that resolve only to denied addresses, `502` if the destination cannot be resolved or
+    ///   refuses the connection, and `504` if the connect timeout runs out.
+    ///
+    /// **Examples:**
+    /// ```rust,no_run
+    /// # use server::{proxy::ForwardProxy, request::Request, timeouts::{ClientStream, Timeouts}};
+    /// # let proxy = ForwardProxy::new();
+    /// # let request = Request::read_from(&mut &b"CONNECT example.com:443 HTTP/1.1\r\nHost: example.com:443\r\n\r\n"[..])?;
+    /// # let listener = std::net::TcpListener::bind("127.0.0.1:0")?;
+    /// # let _peer = std::net::TcpStream::connect(listener.local_addr()?)?;
+    /// # let (stream, _) = listener.accept()?;
+    /// # let mut reader = std::io::BufReader::new(ClientStream::new(&stream, Timeouts::new()));
+    /// proxy.serve(&request, &mut reader)?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn serve(&self, request: &Request, client: &mut BufReader<ClientStream<'_>>) -> io::Result<()> {
+        let mut out: &TcpStream = client.get_ref().socket();
+        if let Some(auth) = &self.auth
+            && auth.authenticate_proxy(request).is_none()
+        {
+            return auth.proxy_challenge().write_to(&mut out);
+        }
+
+        let destination = if request.method == "CONNECT" {
+            split_authority(&request.target, None).map(|(host, port)| (host, port, None))
+        } else {
+            absolute_target(&request.target)
+        };
+        let Some((host, port, path)) = destination else {
+            return Response::new(400, "Bad Request").write_to(&mut out);
+        };
+        if !self.allowed_ports.contains(&port) {
+            return Response::new(403, "Forbidden").write_to(&mut out);
+        }
+        let upstream = match self.connect(&host, port) {
+            Ok(upstream) => upstream,
+            Err(refusal) => return refusal.write_to(&mut out),
+        };
+
+        match path {
+            None => {
+                out.write_all(b"HTTP/1.1 200 Connection Established\r\n\r\n")?;
+                forward_buffered(client, &upstream)?;
+                tunnel(client.get_ref().socket().try_clone()?, upstream, self.tunnel_idle)
+            }
+            Some(path) => {
+                let mut rewritten = request.clone();
+                rewritten.target = path;
+                rewritten.headers.set("Host", authority(&host, port));
+                rewritten.headers.remove("Proxy-Authorization");
+                relay(upstream, &rewritten, client, self.tunnel_idle)
+            }
+        }
+    }
+
+    /// # permits
+    ///
+    /// **Purpose:**
+    /// Checks a resolved destination address against the deny and allow ranges.
+    ///
+    /// **Parameters:**
+    /// - `address`: Destination address.
+    ///
+    /// **Returns:**
+    /// - `true` unless the address is denied and not explicitly allowed.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let permitted = proxy.permits("10.0.0.5".parse()?);
+    /// ```
+    fn permits(&self, address: IpAddr) -> bool {
+        let within = |ranges: &[Cidr]| ranges.iter().any(|range| range.contains(address));
+        !within(&self.denied) || within(&self.allowed)
+    }
+
+    /// # connect
+    ///
+    /// **Purpose:**
+    /// Resolves a destination and connects to its first permitted address that answers. Only
+    /// addresses checked here are dialled, so a name cannot resolve to a public address for the
+    /// check and a private one for the connection.
+    ///
+    /// **Parameters:**
+    /// - `host`: Destination host name or address.
+    /// - `port`: Destination port.
+    ///
+    /// **Returns:**
+    /// - The connected upstream socket.
+    ///
+    /// **Errors / Failures:**
+    /// - The response to send instead: `403` if every address is denied, `502` if the name
+    ///   does not resolve or no address accepts, `504` if connecting timed out.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let upstream = self.connect("example.com", 443)?;
+    /// ```
+    fn connect(&self, host: &str, port: u16) -> Result<TcpStream, Response> {
+        let addresses: Vec<SocketAddr> = match (host, port).to_socket_addrs() {
+            Ok(addresses) => addresses.collect(),
+            Err(_) => return Err(Response::new(502, "Bad Gateway")),
+        };
+        let permitted: Vec<&SocketAddr> = addresses.iter().filter(|address| self.permits(address.ip())).collect();
+        if permitted.is_empty() && !addresses.is_empty() {
+            return Err(Response::new(403, "Forbidden"));
+        }
+        let mut timed_out = false;
+        for address in permitted {
+            match TcpStream::connect_timeout(address, self.connect_timeout) {
+                Ok(upstream) => return Ok(upstream),
+                Err(error) => timed_out |= error.kind() == io::ErrorKind::TimedOut,
+            }
+        }
+        if timed_out {
+            return Err(Response::new(504, "Gateway Timeout"));
+        }
+        Err(Response::new(502, "Bad Gateway"))
+    }
+}
+
+
+/// # relay
+///
+/// **Purpose:**
+/// Sends `request` over an open upstream connection and streams the reply back to the client,
+/// switching to [`tunnel`] for WebSocket upgrades once the handshake is sent.
+///
+/// **Parameters:**
+/// - `upstream`: Connected upstream socket.
+/// - `request`: Request to send, already in origin form.
+/// - `client`: Buffered reader over the client connection.
+/// - `idle`: Idle limit for the tunnel, if the request upgrades.
+///
+/// **Returns:**
+/// - `Ok(())` once the exchange (or tunnel) has finished.
+///
+/// **Errors / Failures:**
+/// - Returns an I/O error if either connection fails mid-transfer.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// relay(upstream, &request, &mut reader, DEFAULT_TUNNEL_IDLE)?;
+/// ```
+fn relay(mut upstream: TcpStream, request: &Request, client: &mut BufReader<ClientStream<'_>>, idle: Duration) -> io::Result<()> {
+    let upgrade = request.is_upgrade();
+    #[cfg(feature = "otel")]
+    let _span = trace::client_span(&request.method, &upstream.peer_addr().map_or(String::new(), |addr| addr.to_string()));
+
+    write_head(&mut upstream, request, upgrade)?;
+    upstream.write_all(&request.body)?;
+
+    if upgrade {
+        forward_buffered(client, &upstream)?;
+        let client = client.get_ref().socket().try_clone()?;
+        return tunnel(client, upstream, idle);
+    }
+
+    abandon_on_cancel(request, &upstream);
+    let mut client: &TcpStream = client.get_ref().socket();
+    io::copy(&mut upstream, &mut client)?;
+    Ok(())
+}
+
+
+/// # abandon_on_cancel
+///
+/// **Purpose:**
+/// Shuts down an upstream connection if the client hangs up first, so a blocked upstream read
+/// ends instead of waiting on a reply no one will receive.
+///
+/// **Parameters:**
+/// - `request`: Request whose client is watched.
+/// - `upstream`: Upstream connection serving it.
+///
+/// **Returns:**
+/// None.
+///
+/// **Errors / Failures:**
+/// None. If the socket cannot be duplicated the upstream is simply not watched.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// abandon_on_cancel(request, &upstream);
+/// ```
+fn abandon_on_cancel(request: &Request, upstream: &TcpStream) {
+    if let Ok(upstream) = upstream.try_clone() {
+        request.cancellation.on_cancel(move || {
+            let _ = upstream.shutdown(Shutdown::Both);
+        });
+    }
+}
+
+
+/// # forward_buffered
+///
+/// **Purpose:**
+/// Sends bytes the client reader has already buffered (sent right after the request head) to the upstream.
+///
+/// **Parameters:**
+/// - `client`: Buffered reader over the client connection.
+/// - `upstream`: Upstream connection.
+///
+/// **Returns:**
+/// - `Ok(())` once the buffered bytes are written and consumed.
+///
+/// **Errors / Failures:**
+/// - Returns any I/O error raised while writing.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// forward_buffered(&mut reader, &upstream)?;
+/// ```
+fn forward_buffered(client: &mut BufReader<ClientStream<'_>>, mut upstream: &TcpStream) -> io::Result<()> {
+    let pending = client.buffer().len();
+    upstream.write_all(client.buffer())?;
+    client.consume(pending);
+    Ok(())
+}
+
+
+/// # absolute_target
+///
+/// **Purpose:**
+/// Splits an absolute-form `http://` target into destination host, port, and origin-form path.
+///
+/// **Parameters:**
+/// - `target`: Request target such as `http://example.com:8080/a?b`.
+///
+/// **Returns:**
+/// - `Some((host, port, Some(path)))`, or `None` for other schemes, user info, or a bad authority.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let (host, port, path) = absolute_target("http://example.com/a").unwrap();
+/// ```
+pub(crate) fn absolute_target(target: &str) -> Option<(String, u16, Option<String>)> {
+    let (scheme, rest) = target.split_once("://")?;
+    if !scheme.eq_ignore_ascii_case("http") {
+        return None;
+    }
+
+    let end = rest.find(['/', '?']).unwrap_or(rest.len());
+    let (authority, path) = rest.split_at(end);
+    if authority.contains('@') {
+        return None;
+    }
+
+    let path = match path {
+        "" => "/".to_string(),
+        path if path.starts_with('?') => format!("/{path}"),
+        path => path.to_string(),
+    };
+    let (host, port) = split_authority(authority, Some(80))?;
+    Some((host, port, Some(path)))
+}
+
+
+/// # split_authority
+///
+/// **Purpose:**
+/// Splits `host:port` (including bracketed IPv6 hosts) into its parts.
+///
+/// **Parameters:**
+/// - `authority`: Authority text.
+/// - `default_port`: Port to assume when none is given; `None` makes the port mandatory.
+///
+/// **Returns:**
+/// - `Some((host, port))`, or `None` if the authority is malformed.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// assert_eq!(split_authority("[::1]:8080", None), Some(("::1".into(), 8080)));
+/// ```
+fn split_authority(authority: &str, default_port: Option<u16>) -> Option<(String, u16)> {
+    let (host, port) = match authority.strip_prefix('[') {
+        Some(rest) => {
+            let (host, rest) = rest.split_once(']')?;
+            (host, rest.strip_prefix(':'))
+        }
+        None => match authority.rsplit_once(':') {
+            Some((host, port)) => (host, Some(port)),
+            None => (authority, None),
+        },
+    };
+
+    let port = match port {
+        Some(port) => port.parse().ok()?,
+        None => default_port?,
+    };
+    (!host.is_empty()).then(|| (host.to_string(), port))
+}
+
+
+/// # authority
+///
+/// **Purpose:**
+/// Formats a host and port back into `Host` header form, bracketing IPv6 literals.
+///
+/// **Parameters:**
+/// - `host`: Destination host.
+/// - `port`: Destination port.
+///
+/// **Returns:**
+/// - The authority string, omitting port 80.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// assert_eq!(authority("example.com", 80), "example.com");
+/// ```
+pub(crate) fn authority(host: &str, port: u16) -> String {
+    let host = if host.contains(':') {
+        format!("[{host}]")
+    } else {
+        host.to_string()
+    };
+    match port {
+        80 => host,
+        port => format!("{host}:{port}"),
+    }
+}
+
+
+/// # write_head
+///
+/// **Purpose:**
+/// Writes the request line and end-to-end headers to the upstream, replacing hop-by-hop headers
+/// with `Connection: close` or, for upgrades, with the original `Upgrade` token. With the `otel`
+/// feature, `traceparent` is replaced by the context of the open upstream-call span.
+///
+/// **Parameters:**
+/// - `upstream`: Upstream connection.
+/// - `request`: Request whose head is being forwarded.
+/// - `upgrade`: Whether the request is a protocol upgrade.
+///
+/// **Returns:**
+/// - `Ok(())` once the head is written.
+///
+/// **Errors / Failures:**
+/// - Returns any I/O error raised while writing.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// write_head(&mut upstream, &request, false)?;
+/// ```
+fn write_head(upstream: &mut TcpStream, request: &Request, upgrade: bool) -> io::Result<()> {
+    let mut head = Vec::new();
+    write!(head, "{}\r\n", request.request_line())?;
+
+    let headers = end_to_end(&request.headers);
+    #[cfg(feature = "otel")]
+    let headers = {
+        let mut headers = headers;
+        if let Some(traceparent) = trace::current_traceparent() {
+            headers.set("traceparent", traceparent);
+        }
+        headers
+    };
+    headers.write_to(&mut head)?;
+
+    if upgrade {
+        let protocol = request.headers.get("Upgrade").unwrap_or("websocket");
+        write!(head, "Connection: Upgrade\r\nUpgrade: {protocol}\r\n")?;
+    } else {
+        write!(head, "Connection: close\r\n")?;
+    }
+    if !request.body.is_empty() || request.headers.contains("Content-Length") {
+        write!(head, "Content-Length: {}\r\n", request.body.len())?;
+    }
+    head.extend_from_slice(b"\r\n");
+
+    upstream.write_all(&head)
+}
+
+
+/// # end_to_end
+///
+/// **Purpose:**
+/// Copies a header block without hop-by-hop fields, including any named in `Connection`.
+///
+/// **Parameters:**
+/// - `headers`: Headers received on one connection.
+///
+/// **Returns:**
+/// - The headers that may be forwarded on another connection.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let forwarded = end_to_end(&request.headers);
+/// ```
+fn end_to_end(headers: &Headers) -> Headers {
+    let listed: Vec<&str> = headers
+        .get_all("Connection")
+        .into_iter()
+        .flat_map(|value| value.split(','))
+        .map(str::trim)
+        .collect();
+
+    let mut forwarded = Headers::new();
+    for (name, value) in headers.iter() {
+        let hop_by_hop = HOP_BY_HOP.iter().any(|hop| hop.eq_ignore_ascii_case(name))
+            || listed.iter().any(|token| token.eq_ignore_ascii_case(name));
+        if !hop_by_hop {
+            forwarded.append(name, value);
+        }
+    }
+    forwarded
+}
+
+
+/// # tunnel
+///
+/// **Purpose:**
+/// Copies bytes in both directions between two connections until each side has finished sending,
+/// half-closing the opposite side as each direction reaches EOF. Tunnels are long-lived, so the
+/// usual socket timeouts give way to an idle limit: once neither direction has carried a byte for
+/// `idle`, both connections are shut down.
+///
+/// **Parameters:**
+/// - `client`: Client connection.
+/// - `upstream`: Upstream connection.
+/// - `idle`: Longest both directions may be silent at once; must be non-zero.
+///
+/// **Returns:**
+/// - `Ok(())` once both directions are drained.
+///
+/// **Errors / Failures:**
+/// - Returns `TimedOut` if the tunnel sat idle for `idle`.
+/// - Returns the first other I/O error raised by either direction.
+/// - Panics if the outbound copy thread panics.
+///
+/// **Examples:**
+/// ```rust,no_run
+/// # use server::proxy::tunnel;
+/// # use std::{net::TcpStream, time::Duration};
+/// # let client = TcpStream::connect("127.0.0.1:7878")?;
+/// # let upstream = TcpStream::connect("127.0.0.1:3000")?;
+/// tunnel(client, upstream, Duration::from_secs(300))?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub fn tunnel(client: TcpStream, upstream: TcpStream, idle: Duration) -> io::Result<()> {
+    for socket in [&client, &upstream] {
+        socket.set_read_timeout(Some(idle))?;
+        socket.set_write_timeout(Some(idle))?;
+    }
+    let started = Instant::now();
+    let activity = Arc::new(AtomicU64::new(0));
+    let (client_read, upstream_write) = (client.try_clone()?, upstream.try_clone()?);
+    let outbound_activity = Arc::clone(&activity);
+    let outbound = thread::spawn(move || pump(client_read, upstream_write, started, &outbound_activity, idle));
+
+    let inbound = pump(upstream, client, started, &activity, idle);
+    outbound.join().expect("tunnel thread panicked")?;
+    inbound
+}
+
+
+/// # pump
+///
+/// **Purpose:**
+/// Copies one direction of a tunnel, recording each transfer so the other direction knows the
+/// tunnel is still in use. A read timeout ends the copy only when neither direction has moved
+/// data for `idle`; the tunnel is then shut down so the other direction stops as well.
+///
+/// **Parameters:**
+/// - `from`: Connection read from.
+/// - `to`: Connection written to; its write side is closed once `from` reaches EOF.
+/// - `started`: When the tunnel opened, the origin for `activity`.
+/// - `activity`: Milliseconds after `started` at which either direction last moved data.
+/// - `idle`: Idle limit.
+///
+/// **Returns:**
+/// - `Ok(())` once `from` reaches EOF.
+///
+/// **Errors / Failures:**
+/// - Returns `TimedOut` once the tunnel has been idle for `idle`.
+/// - Returns any other I/O error raised while copying.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// pump(upstream, client, started, &activity, idle)?;
+/// ```
+fn pump(mut from: TcpStream, mut to: TcpStream, started: Instant, activity: &AtomicU64, idle: Duration) -> io::Result<()> {
+    let elapsed = || u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX);
+    let mut buffer = [0; 16 * 1024];
+    loop {
+        match from.read(&mut buffer) {
+            Ok(0) => {
+                let _ = to.shutdown(Shutdown::Write);
+                return Ok(());
+            }
+            Ok(read) => {
+                to.write_all(&buffer[..read])?;
+                activity.store(elapsed(), Ordering::Relaxed);
+            }
+            Err(error) if matches!(error.kind(), io::ErrorKind::TimedOut | io::ErrorKind::WouldBlock) => {
+                let quiet = Duration::from_millis(elapsed().saturating_sub(activity.load(Ordering::Relaxed)));
+                if quiet >= idle {
+                    let _ = from.shutdown(Shutdown::Both);
+                    let _ = to.shutdown(Shutdown::Both);
+                    return Err(io::Error::new(io::ErrorKind::TimedOut, "tunnel idle timeout"));
+                }
+            }
+            Err(error) if error.kind() == io::ErrorKind::Interrupted => {}
+            Err(error) => return Err(error),
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(line: &str) -> Request {
+        Request::read_from(&mut format!("{line}\r\nHost: example.com\r\n\r\n").as_bytes()).unwrap()
+    }
+
+    #[test]
+    fn handles_only_absolute_form_and_connect() {
+        let proxy = ForwardProxy::new();
+        assert!(proxy.handles(&request("GET http://example.com/ HTTP/1.1")));
+        assert!(proxy.handles(&request("CONNECT example.com:443 HTTP/1.1")));
+        assert!(!proxy.handles(&request("GET /go?u=http://x HTTP/1.1")));
+        assert!(!proxy.handles(&request("GET / HTTP/1.1")));
+        assert!(!proxy.handles(&request("OPTIONS * HTTP/1.1")));
+    }
+
+    #[test]
+    fn denies_internal_destinations_by_default() {
+        let proxy = ForwardProxy::new();
+        for address in ["127.0.0.1", "10.1.2.3", "172.16.0.1", "192.168.1.1", "169.254.169.254", "::1", "fe80::1", "fd00::1", "::ffff:127.0.0.1", "0.0.0.0"] {
+            assert!(!proxy.permits(address.parse().unwrap()), "{address}");
+        }
+        for address in ["93.184.216.34", "2606:2800:220:1::1", "172.32.0.1"] {
+            assert!(proxy.permits(address.parse().unwrap()), "{address}");
+        }
+    }
+
+    #[test]
+    fn allows_and_denies_configured_destinations() {
+        let proxy = ForwardProxy::new().allow_destination("10.20.0.0/16").unwrap().deny_destination("203.0.113.0/24").unwrap();
+        assert!(proxy.permits("10.20.1.1".parse().unwrap()));
+        assert!(!proxy.permits("10.21.1.1".parse().unwrap()));
+        assert!(!proxy.permits("203.0.113.9".parse().unwrap()));
+        assert!(ForwardProxy::new().allow_destination("not-a-range").is_err());
+    }
+
+    #[test]
+    fn refuses_connections_to_denied_addresses() {
+        let refusal = ForwardProxy::new().connect("127.0.0.1", 80).unwrap_err();
+        assert_eq!(refusal.status, 403);
+    }
+
+    #[test]
+    fn upstreams_that_stop_answering_time_out() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        let proxy = Proxy::new(address).timeouts(Timeouts::none().read(Duration::from_millis(100)));
+        let silent = thread::spawn(move || {
+            let connection = listener.accept();
+            thread::sleep(Duration::from_millis(500));
+            drop(connection);
+        });
+
+        let error = proxy.fetch(&request("GET / HTTP/1.1")).unwrap_err();
+        assert!(matches!(error.kind(), io::ErrorKind::TimedOut | io::ErrorKind::WouldBlock), "{error}");
+        silent.join().unwrap();
+    }
+
+    #[test]
+    fn unreachable_upstreams_fail_to_connect() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        drop(listener);
+        assert!(Proxy::new(address).connect_timeout(Duration::from_millis(200)).fetch(&request("GET / HTTP/1.1")).is_err());
+    }
+
+    fn pair() -> (TcpStream, TcpStream) {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let near = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        (near, listener.accept().unwrap().0)
+    }
+
+    fn answer(proxy: &ForwardProxy, request: &Request) -> String {
+        let (mut client, served) = pair();
+        proxy.serve(request, &mut BufReader::new(ClientStream::new(&served, Timeouts::none()))).unwrap();
+        drop(served);
+        let mut raw = String::new();
+        client.read_to_string(&mut raw).unwrap();
+        raw
+    }
+
+    #[test]
+    fn tunnels_relay_bytes_until_idle() {
+        let ((mut client, client_side), (upstream_side, mut upstream)) = (pair(), pair());
+        let tunnel = thread::spawn(move || tunnel(client_side, upstream_side, Duration::from_millis(200)));
+
+        client.write_all(b"ping").unwrap();
+        let mut received = [0; 4];
+        upstream.read_exact(&mut received).unwrap();
+        assert_eq!(&received, b"ping");
+        for _ in 0..3 {
+            thread::sleep(Duration::from_millis(100));
+            upstream.write_all(b"tick").unwrap();
+            client.read_exact(&mut received).unwrap();
+        }
+
+        let started = Instant::now();
+        let error = tunnel.join().unwrap().unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::TimedOut);
+        assert!(started.elapsed() < Duration::from_secs(2));
+        assert_eq!(client.read(&mut received).unwrap(), 0);
+    }
+
+    #[test]
+    fn forward_proxy_credentials_are_challenged_with_407() {
+        let proxy = ForwardProxy::new().authenticate(BasicAuth::new("Proxy").user("ana", "s3cret"));
+
+        let refused = answer(&proxy, &request("CONNECT 127.0.0.1:443 HTTP/1.1"));
+        assert!(refused.starts_with("HTTP/1.1 407 "), "{refused}");
+        assert!(refused.contains(r#"Proxy-Authenticate: Basic realm="Proxy""#), "{refused}");
+
+        let wrong = Request::read_from(&mut "CONNECT 127.0.0.1:443 HTTP/1.1\r\nProxy-Authorization: Basic YW5hOndyb25n\r\n\r\n".as_bytes()).unwrap();
+        assert!(answer(&proxy, &wrong).starts_with("HTTP/1.1 407 "));
+
+        let admitted = Request::read_from(&mut "CONNECT 127.0.0.1:443 HTTP/1.1\r\nProxy-Authorization: Basic YW5hOnMzY3JldA==\r\n\r\n".as_bytes()).unwrap();
+        assert!(answer(&proxy, &admitted).starts_with("HTTP/1.1 403 "), "loopback is still denied once admitted");
+    }
+
+    #[test]
+    fn parses_absolute_targets() {
+        assert_eq!(absolute_target("http://example.com"), Some(("example.com".into(), 80, Some("/".into()))));
+        assert_eq!(absolute_target("http://example.com:8080/a?b"), Some(("example.com".into(), 8080, Some("/a?b".into()))));
+        assert_eq!(absolute_target("HTTP://[::1]:81?q"), Some(("::1".into(), 81, Some("/?q".into()))));
+        assert_eq!(absolute_target("https://example.com/"), None);
+        assert_eq!(absolute_target("http://user@example.com/"), None);
+        assert_eq!(absolute_target("http://:80/"), None);
+        assert_eq!(split_authority("example.com", None), None);
+        assert_eq!(split_authority("example.com:99999", None), None);
+        assert_eq!(authority("::1", 80), "[::1]");
+        assert_eq!(authority("example.com", 8080), "example.com:8080");
+    }
+}
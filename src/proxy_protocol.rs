@@ -0,0 +1,380 @@
+
+// ============================================================
+//  DAEGONICA SOFTWARE — proxy_protocol.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: ProxyProtocol
+//!
+//! **Purpose:**
+//! Reads the HAProxy PROXY protocol preamble that load balancers put in front of each connection.
+//!
+//! **Context:**
+//! - Set with `Server::proxy_protocol`; the connection handler reads the preamble before the
+//!   first request and uses the address it carries as the request peer, so access logs, rate
+//!   limits, address ACLs, and handlers all see the real client.
+//!
+//! **Responsibilities:**
+//! - Parses version 1 (text) and version 2 (binary) preambles for TCP over IPv4 and IPv6.
+//! - Requires a preamble from trusted load balancers and refuses connections without one, since
+//!   a missing preamble would let a client's own bytes be taken for it.
+//! - Keeps the socket address for `UNKNOWN`, `LOCAL` (health check), and non-TCP preambles.
+//! - Does NOT interpret version 2 TLV extensions, or apply before `Server::peer_limit`, which
+//!   counts the load balancer's own address.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+use std::{
+    io::{self, BufRead, Read},
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+};
+
+use crate::ip::Cidr;
+
+
+/// # V2_SIGNATURE
+///
+/// **Summary:**
+/// The twelve bytes that open a version 2 preamble.
+const V2_SIGNATURE: [u8; 12] = *b"\r\n\r\n\0\r\nQUIT\n";
+
+
+/// # V1_MAX_LINE
+///
+/// **Summary:**
+/// Longest version 1 preamble line the specification allows, including `CRLF`.
+const V1_MAX_LINE: u64 = 107;
+
+
+/// # ProxyProtocol
+///
+/// **Summary:**
+/// Which peers must open their connections with a PROXY protocol preamble.
+///
+/// **Fields:**
+/// - `trusted`: Load balancer ranges that send a preamble; empty means every peer must.
+///
+/// **Usage Example:**
+/// ```rust
+/// # use server::{ip::Cidr, proxy_protocol::ProxyProtocol, server::Server};
+/// let server = Server::new("0.0.0.0:8080").proxy_protocol(ProxyProtocol::new().trust(Cidr::parse("10.0.0.0/8")?));
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ProxyProtocol {
+    trusted: Vec<Cidr>,
+}
+
+
+impl ProxyProtocol {
+
+    /// # new
+    ///
+    /// **Purpose:**
+    /// Requires a preamble on every connection, for listeners reachable only through the load balancer.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - A new `ProxyProtocol` trusting every peer.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::proxy_protocol::ProxyProtocol;
+    /// let proxy_protocol = ProxyProtocol::new();
+    /// ```
+    pub fn new() -> ProxyProtocol {
+        ProxyProtocol::default()
+    }
+
+    /// # trust
+    ///
+    /// **Purpose:**
+    /// Limits the preamble to peers in a range; once any range is given, other peers are served
+    /// as direct clients and no preamble is read from them.
+    ///
+    /// **Parameters:**
+    /// - `range`: Load balancer address range.
+    ///
+    /// **Returns:**
+    /// - The updated `ProxyProtocol`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{ip::Cidr, proxy_protocol::ProxyProtocol};
+    /// let proxy_protocol = ProxyProtocol::new().trust(Cidr::parse("192.168.1.10")?);
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn trust(mut self, range: Cidr) -> ProxyProtocol {
+        self.trusted.push(range);
+        self
+    }
+
+    /// # expects
+    ///
+    /// **Purpose:**
+    /// Checks whether a peer must send a preamble.
+    ///
+    /// **Parameters:**
+    /// - `peer`: Socket address of the connection.
+    ///
+    /// **Returns:**
+    /// - `true` if no ranges are set or the peer is in one of them.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// if proxy_protocol.expects(peer) { peer = proxy_protocol::read_preamble(&mut reader, peer)?; }
+    /// ```
+    pub(crate) fn expects(&self, peer: SocketAddr) -> bool {
+        self.trusted.is_empty() || self.trusted.iter().any(|range| range.contains(peer.ip()))
+    }
+}
+
+
+/// # read_preamble
+///
+/// **Purpose:**
+/// Reads a PROXY protocol preamble from the start of a connection.
+///
+/// **Parameters:**
+/// - `reader`: Buffered client connection, positioned at its first byte.
+/// - `peer`: Socket address of the connection, kept when the preamble carries no client address.
+///
+/// **Returns:**
+/// - The client address the preamble names, or `peer`.
+///
+/// **Errors / Failures:**
+/// - `InvalidData` if the connection does not open with a well-formed preamble.
+/// - Any I/O error (including timeouts) raised while reading it.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let peer = proxy_protocol::read_preamble(&mut buf_reader, peer)?;
+/// ```
+pub(crate) fn read_preamble<R: BufRead>(reader: &mut R, peer: SocketAddr) -> io::Result<SocketAddr> {
+    let mut start = [0u8; 12];
+    reader.read_exact(&mut start)?;
+    if start == V2_SIGNATURE {
+        read_v2(reader, peer)
+    } else if start.starts_with(b"PROXY ") {
+        read_v1(reader, &start, peer)
+    } else {
+        Err(malformed("connection did not start with a PROXY protocol preamble"))
+    }
+}
+
+
+/// # read_v1
+///
+/// **Purpose:**
+/// Reads the rest of a version 1 line, such as `PROXY TCP4 203.0.113.7 10.0.0.2 51234 443\r\n`.
+///
+/// **Parameters:**
+/// - `reader`: Buffered client connection, just past the first twelve bytes.
+/// - `start`: The twelve bytes already read.
+/// - `peer`: Socket address, kept for `UNKNOWN` lines.
+///
+/// **Returns:**
+/// - The source address and port, or `peer`.
+///
+/// **Errors / Failures:**
+/// - `InvalidData` for an overlong or malformed line.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let peer = read_v1(reader, &start, peer)?;
+/// ```
+fn read_v1<R: BufRead>(reader: &mut R, start: &[u8], peer: SocketAddr) -> io::Result<SocketAddr> {
+    let mut line = start.to_vec();
+    reader.take(V1_MAX_LINE - start.len() as u64).read_until(b'\n', &mut line)?;
+    let line = line
+        .strip_suffix(b"\r\n")
+        .and_then(|line| std::str::from_utf8(line).ok())
+        .ok_or_else(|| malformed("malformed PROXY protocol line"))?;
+
+    let fields = line.split(' ').collect::<Vec<_>>();
+    match fields.as_slice() {
+        ["PROXY", "UNKNOWN", ..] => Ok(peer),
+        ["PROXY", family @ ("TCP4" | "TCP6"), source, _, port, _] => {
+            let address = source.parse::<IpAddr>().map_err(|_| malformed("malformed PROXY protocol address"))?;
+            if address.is_ipv4() != (*family == "TCP4") {
+                return Err(malformed("PROXY protocol address does not match its family"));
+            }
+            let port = port.parse::<u16>().map_err(|_| malformed("malformed PROXY protocol port"))?;
+            Ok(SocketAddr::new(address, port))
+        }
+        _ => Err(malformed("malformed PROXY protocol line")),
+    }
+}
+
+
+/// # read_v2
+///
+/// **Purpose:**
+/// Reads the rest of a version 2 header: command, family, length, addresses, and any TLVs.
+///
+/// **Parameters:**
+/// - `reader`: Buffered client connection, just past the signature.
+/// - `peer`: Socket address, kept for `LOCAL` and non-TCP headers.
+///
+/// **Returns:**
+/// - The source address and port, or `peer`.
+///
+/// **Errors / Failures:**
+/// - `InvalidData` for an unknown version or command, or an address block too short for its family.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let peer = read_v2(reader, peer)?;
+/// ```
+fn read_v2<R: BufRead>(reader: &mut R, peer: SocketAddr) -> io::Result<SocketAddr> {
+    let mut fixed = [0u8; 4];
+    reader.read_exact(&mut fixed)?;
+    let [version_command, family, high, low] = fixed;
+    let mut block = vec![0u8; u16::from_be_bytes([high, low]) as usize];
+    reader.read_exact(&mut block)?;
+
+    match version_command {
+        0x20 => return Ok(peer),
+        0x21 => {}
+        _ => return Err(malformed("unsupported PROXY protocol version or command")),
+    }
+    let short = || malformed("truncated PROXY protocol address block");
+    match family {
+        0x11 => {
+            let block: &[u8; 12] = block.get(..12).and_then(|block| block.try_into().ok()).ok_or_else(short)?;
+            let address = Ipv4Addr::from([block[0], block[1], block[2], block[3]]);
+            Ok(SocketAddr::new(address.into(), u16::from_be_bytes([block[8], block[9]])))
+        }
+        0x21 => {
+            let block: &[u8; 36] = block.get(..36).and_then(|block| block.try_into().ok()).ok_or_else(short)?;
+            let mut octets = [0u8; 16];
+            octets.copy_from_slice(&block[..16]);
+            Ok(SocketAddr::new(Ipv6Addr::from(octets).into(), u16::from_be_bytes([block[32], block[33]])))
+        }
+        _ => Ok(peer),
+    }
+}
+
+
+/// # malformed
+///
+/// **Purpose:**
+/// Builds the error for a bad preamble.
+///
+/// **Parameters:**
+/// - `reason`: What was wrong.
+///
+/// **Returns:**
+/// - An `InvalidData` I/O error.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// return Err(malformed("malformed PROXY protocol line"));
+/// ```
+fn malformed(reason: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, reason.to_string())
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn peer() -> SocketAddr {
+        "10.0.0.1:4000".parse().unwrap()
+    }
+
+    fn preamble(bytes: &[u8]) -> io::Result<SocketAddr> {
+        read_preamble(&mut &bytes[..], peer())
+    }
+
+    fn v2(command: u8, family: u8, block: &[u8]) -> Vec<u8> {
+        let mut bytes = V2_SIGNATURE.to_vec();
+        bytes.extend([command, family]);
+        bytes.extend((block.len() as u16).to_be_bytes());
+        bytes.extend(block);
+        bytes
+    }
+
+    #[test]
+    fn version_1_carries_the_client_address() {
+        let client = preamble(b"PROXY TCP4 192.0.2.7 10.0.0.2 51000 80\r\nGET / HTTP/1.1\r\n").unwrap();
+        assert_eq!(client, "192.0.2.7:51000".parse().unwrap());
+        let client = preamble(b"PROXY TCP6 2001:db8::7 2001:db8::1 51000 443\r\n").unwrap();
+        assert_eq!(client, "[2001:db8::7]:51000".parse().unwrap());
+        assert_eq!(preamble(b"PROXY UNKNOWN\r\n").unwrap(), peer());
+    }
+
+    #[test]
+    fn malformed_version_1_lines_are_refused() {
+        for line in [
+            &b"PROXY TCP4 2001:db8::7 10.0.0.2 51000 80\r\n"[..],
+            b"PROXY TCP4 192.0.2.7 10.0.0.2 99999 80\r\n",
+            b"PROXY TCP4 192.0.2.7 10.0.0.2 51000\r\n",
+            b"PROXY TCP4 192.0.2.7 10.0.0.2 51000 80\n",
+            b"GET / HTTP/1.1\r\nHost: a\r\n\r\n",
+        ] {
+            assert_eq!(preamble(line).unwrap_err().kind(), io::ErrorKind::InvalidData);
+        }
+        let long = format!("PROXY TCP4 192.0.2.7 10.0.0.2 51000 80{}\r\n", " ".repeat(100));
+        assert!(preamble(long.as_bytes()).is_err());
+    }
+
+    #[test]
+    fn version_2_carries_the_client_address() {
+        let mut block = vec![192, 0, 2, 7, 10, 0, 0, 2];
+        block.extend(51000u16.to_be_bytes());
+        block.extend(80u16.to_be_bytes());
+        block.extend([0x03, 0x00, 0x01, 0x00]);
+        assert_eq!(preamble(&v2(0x21, 0x11, &block)).unwrap(), "192.0.2.7:51000".parse().unwrap());
+
+        let mut block = "2001:db8::7".parse::<Ipv6Addr>().unwrap().octets().to_vec();
+        block.extend([0; 16]);
+        block.extend(443u16.to_be_bytes());
+        block.extend(443u16.to_be_bytes());
+        assert_eq!(preamble(&v2(0x21, 0x21, &block)).unwrap(), "[2001:db8::7]:443".parse().unwrap());
+    }
+
+    #[test]
+    fn version_2_local_and_unknown_families_keep_the_peer() {
+        assert_eq!(preamble(&v2(0x20, 0x00, &[])).unwrap(), peer());
+        assert_eq!(preamble(&v2(0x21, 0x31, &[0; 216])).unwrap(), peer());
+    }
+
+    #[test]
+    fn malformed_version_2_preambles_are_refused() {
+        assert_eq!(preamble(&v2(0x22, 0x11, &[0; 12])).unwrap_err().kind(), io::ErrorKind::InvalidData);
+        assert_eq!(preamble(&v2(0x21, 0x11, &[0; 8])).unwrap_err().kind(), io::ErrorKind::InvalidData);
+        let mut truncated = v2(0x21, 0x11, &[0; 12]);
+        truncated.truncate(20);
+        assert_eq!(preamble(&truncated).unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+    }
+
+    #[test]
+    fn only_trusted_peers_must_send_a_preamble() {
+        assert!(ProxyProtocol::new().expects(peer()));
+        let protocol = ProxyProtocol::new().trust(Cidr::parse("10.0.0.0/8").unwrap());
+        assert!(protocol.expects(peer()));
+        assert!(!protocol.expects("192.0.2.1:80".parse().unwrap()));
+    }
+}
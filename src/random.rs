@@ -0,0 +1,155 @@
+
+// ============================================================
+//  DAEGONICA SOFTWARE — random.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: Random
+//!
+//! **Purpose:**
+//! Produces unguessable tokens for session IDs, CSRF tokens, and nonces.
+//!
+//! **Context:**
+//! - Internal helper for the session and security layers.
+//!
+//! **Responsibilities:**
+//! - Reads the operating system's random source (`/dev/urandom`).
+//! - Falls back to SipHash keyed by the standard library's per-process random keys when the
+//!   device is unavailable.
+//! - Does NOT provide general-purpose random numbers.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+use std::{
+    collections::hash_map::RandomState,
+    fs::File,
+    hash::{BuildHasher, Hasher},
+    io::Read,
+    sync::atomic::{AtomicU64, Ordering},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::hash;
+
+
+/// # fill
+///
+/// **Purpose:**
+/// Fills a buffer with random bytes.
+///
+/// **Parameters:**
+/// - `buffer`: Buffer to fill.
+///
+/// **Returns:**
+/// None.
+///
+/// **Errors / Failures:**
+/// None. Falls back to keyed hashing if the OS source cannot be read.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let mut nonce = [0u8; 16];
+/// random::fill(&mut nonce);
+/// ```
+pub(crate) fn fill(buffer: &mut [u8]) {
+    if File::open("/dev/urandom")
+        .and_then(|mut source| source.read_exact(buffer))
+        .is_ok()
+    {
+        return;
+    }
+
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let keys = RandomState::new();
+    for chunk in buffer.chunks_mut(8) {
+        let mut hasher = keys.build_hasher();
+        hasher.write_u64(COUNTER.fetch_add(1, Ordering::Relaxed));
+        hasher.write_u128(SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_nanos());
+        let bytes = hasher.finish().to_le_bytes();
+        chunk.copy_from_slice(&bytes[..chunk.len()]);
+    }
+}
+
+
+/// # token
+///
+/// **Purpose:**
+/// Generates a 256-bit random token as lower-case hex.
+///
+/// **Parameters:**
+/// None.
+///
+/// **Returns:**
+/// - A 64-character hex string.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let session_id = random::token();
+/// ```
+pub(crate) fn token() -> String {
+    let mut bytes = [0u8; 32];
+    fill(&mut bytes);
+    hash::hex(&bytes)
+}
+
+
+/// # constant_time_eq
+///
+/// **Purpose:**
+/// Compares two secrets without exiting early, so timing does not reveal matching prefixes.
+///
+/// **Parameters:**
+/// - `a`: First value.
+/// - `b`: Second value.
+///
+/// **Returns:**
+/// - `true` if both are byte-for-byte equal.
+///
+/// **Errors / Failures:**
+/// None. Length differences are not hidden.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// if !random::constant_time_eq(submitted.as_bytes(), expected.as_bytes()) { return forbidden(); }
+/// ```
+pub(crate) fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokens_are_distinct_hex() {
+        let (first, second) = (token(), token());
+        assert_eq!(first.len(), 64);
+        assert!(first.bytes().all(|byte| byte.is_ascii_hexdigit()));
+        assert_ne!(first, second);
+    }
+
+    #[test]
+    fn fill_covers_odd_lengths() {
+        let mut buffer = [0u8; 37];
+        fill(&mut buffer);
+        assert!(buffer.iter().any(|&byte| byte != 0));
+    }
+
+    #[test]
+    fn constant_time_eq_compares_length_and_content() {
+        assert!(constant_time_eq(b"secret", b"secret"));
+        assert!(constant_time_eq(b"", b""));
+        assert!(!constant_time_eq(b"secret", b"secreT"));
+        assert!(!constant_time_eq(b"secret", b"secret!"));
+    }
+}
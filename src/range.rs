@@ -0,0 +1,425 @@
+
+// ============================================================
+//  DAEGONICA SOFTWARE — range.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: Range
+//!
+//! **Purpose:**
+//! Answers `Range` requests for static files with the requested bytes only, so downloads can
+//! resume and media players can seek.
+//!
+//! **Context:**
+//! - Applied by the virtual host to static file responses after conditional revalidation.
+//!
+//! **Responsibilities:**
+//! - Parses `bytes=` range sets: `first-last`, open-ended `first-`, and suffix `-count`.
+//! - Answers one satisfiable range with `206 Partial Content` and a `Content-Range` header,
+//!   keeping file bodies on their file.
+//! - Answers several with a `multipart/byteranges` body under a random boundary, merging
+//!   overlapping and adjacent ranges first.
+//! - Answers `416 Range Not Satisfiable` with `Content-Range: bytes */length` when no range
+//!   overlaps the file.
+//! - Honors `If-Range`, sending the whole file instead of ranges unless its entity tag or
+//!   date names the current version, so a resumed download is never spliced from two versions.
+//! - Advertises `Accept-Ranges: bytes` on full file responses.
+//! - Does NOT honor ranges on `HEAD`, non-`200`, or routed responses, and serves the whole
+//!   file instead when a request asks for more than `MAX_RANGES` ranges or its parts would
+//!   exceed `MULTIPART_LIMIT`. Malformed `Range` headers are ignored, as the RFC allows.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+use std::fmt::Write as _;
+
+use crate::{random, request::Request, response::Response};
+
+
+/// # MAX_RANGES
+///
+/// **Summary:**
+/// Most ranges honored in one request; more are answered with the whole file, so a request
+/// cannot make the server assemble thousands of tiny parts.
+const MAX_RANGES: usize = 16;
+
+
+/// # MULTIPART_LIMIT
+///
+/// **Summary:**
+/// Largest `multipart/byteranges` body assembled in memory, in bytes.
+const MULTIPART_LIMIT: u64 = 8 * 1024 * 1024;
+
+
+/// # apply
+///
+/// **Purpose:**
+/// Cuts the ranges a `GET` request asks for out of a full file response, unless its
+/// `If-Range` validator shows the client's partial copy is of another version.
+///
+/// **Parameters:**
+/// - `request`: Client request, possibly carrying `Range` and `If-Range`.
+/// - `response`: Static file response.
+///
+/// **Returns:**
+/// - `206 Partial Content` with one range or a multipart body, `416 Range Not Satisfiable`,
+///   or `response` itself (with `Accept-Ranges: bytes` when it is a `200`).
+///
+/// **Errors / Failures:**
+/// None. A streamed file that cannot be read for a multipart body is sent whole.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let response = range::apply(&request, revalidate(&request, files.serve(request.path())?));
+/// ```
+pub(crate) fn apply(request: &Request, mut response: Response) -> Response {
+    if response.status != 200 {
+        return response;
+    }
+    response.headers.set("Accept-Ranges", "bytes");
+    let Some(header) = request.headers.get("Range").filter(|_| request.method == "GET") else {
+        return response;
+    };
+    if let Some(validator) = request.headers.get("If-Range")
+        && !unchanged(validator, &response)
+    {
+        return response;
+    }
+    let len = response.body_len() as u64;
+    let Some(ranges) = parse(header, len) else {
+        return response;
+    };
+    match ranges[..] {
+        [] => Response::new(416, "Range Not Satisfiable").with_header("Content-Range", format!("bytes */{len}")),
+        [(start, count)] => {
+            let mut partial = response.slice(start, count);
+            partial.status = 206;
+            partial.reason = "Partial Content".to_string();
+            partial.headers.set("Content-Range", content_range(start, count, len));
+            partial
+        }
+        _ => multipart(response, &ranges, len),
+    }
+}
+
+
+/// # parse
+///
+/// **Purpose:**
+/// Resolves a `Range` header against a body length.
+///
+/// **Parameters:**
+/// - `header`: `Range` header value.
+/// - `len`: Length of the full body.
+///
+/// **Returns:**
+/// - `Some(ranges)` with each satisfiable range as a start offset and byte count, merged if
+///   any overlap; empty if none is satisfiable.
+/// - `None` if the header is malformed, uses a unit other than `bytes`, or lists more than
+///   `MAX_RANGES` ranges, in which case the whole body is sent.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// assert_eq!(parse("bytes=0-9,-5", 100), Some(vec![(0, 10), (95, 5)]));
+/// ```
+fn parse(header: &str, len: u64) -> Option<Vec<(u64, u64)>> {
+    let (unit, specs) = header.trim().split_once('=')?;
+    if !unit.trim().eq_ignore_ascii_case("bytes") {
+        return None;
+    }
+    let specs: Vec<&str> = specs.split(',').map(str::trim).filter(|spec| !spec.is_empty()).collect();
+    if specs.is_empty() || specs.len() > MAX_RANGES {
+        return None;
+    }
+    let mut ranges = Vec::new();
+    for spec in specs {
+        let (first, last) = spec.split_once('-')?;
+        let (first, last) = (first.trim(), last.trim());
+        if first.is_empty() {
+            let suffix = digits(last)?;
+            if suffix > 0 && len > 0 {
+                ranges.push((len.saturating_sub(suffix), suffix.min(len)));
+            }
+            continue;
+        }
+        let start = digits(first)?;
+        let end = if last.is_empty() { None } else { Some(digits(last)?) };
+        if end.is_some_and(|end| end < start) {
+            return None;
+        }
+        if start < len {
+            let end = end.map_or(len - 1, |end| end.min(len - 1));
+            ranges.push((start, end - start + 1));
+        }
+    }
+    Some(coalesce(ranges))
+}
+
+
+/// # unchanged
+///
+/// **Purpose:**
+/// Evaluates an `If-Range` validator against the response about to be cut.
+///
+/// **Parameters:**
+/// - `validator`: `If-Range` header value: an entity tag or an HTTP-date.
+/// - `response`: Full file response, with its `ETag` and `Last-Modified`.
+///
+/// **Returns:**
+/// - `true` if an entity tag matches the `ETag` by strong comparison (weak tags never match),
+///   or a date exactly matches `Last-Modified`; `false` otherwise, including when the response
+///   has no such validator.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// if !unchanged("\"v2\"", &response) { return response; }
+/// ```
+fn unchanged(validator: &str, response: &Response) -> bool {
+    let validator = validator.trim();
+    if validator.starts_with('"') || validator.starts_with("W/") {
+        let strong = |tag: &str| tag.starts_with('"');
+        return response
+            .headers
+            .get("ETag")
+            .map(str::trim)
+            .is_some_and(|etag| strong(validator) && strong(etag) && etag == validator);
+    }
+    response.headers.get("Last-Modified").is_some_and(|modified| modified.trim() == validator)
+}
+
+
+/// # digits
+///
+/// **Purpose:**
+/// Parses a byte position, which must be plain decimal digits.
+///
+/// **Parameters:**
+/// - `text`: Candidate position.
+///
+/// **Returns:**
+/// - The position, or `None` if `text` is empty, has other characters, or overflows.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// assert_eq!(digits("42"), Some(42));
+/// ```
+fn digits(text: &str) -> Option<u64> {
+    if text.is_empty() || !text.bytes().all(|byte| byte.is_ascii_digit()) {
+        return None;
+    }
+    text.parse().ok()
+}
+
+
+/// # coalesce
+///
+/// **Purpose:**
+/// Merges overlapping and adjacent ranges, so no byte is sent twice.
+///
+/// **Parameters:**
+/// - `ranges`: Satisfiable ranges in request order, as start offsets and byte counts.
+///
+/// **Returns:**
+/// - The ranges unchanged if none touches another; otherwise the merged ranges in ascending
+///   order.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// assert_eq!(coalesce(vec![(10, 10), (0, 15)]), vec![(0, 20)]);
+/// ```
+fn coalesce(ranges: Vec<(u64, u64)>) -> Vec<(u64, u64)> {
+    let mut sorted = ranges.clone();
+    sorted.sort_unstable();
+    if sorted.windows(2).all(|pair| pair[0].0 + pair[0].1 < pair[1].0) {
+        return ranges;
+    }
+    let mut merged: Vec<(u64, u64)> = Vec::with_capacity(sorted.len());
+    for (start, count) in sorted {
+        match merged.last_mut() {
+            Some((first, kept)) if start <= *first + *kept => *kept = (*kept).max(start + count - *first),
+            _ => merged.push((start, count)),
+        }
+    }
+    merged
+}
+
+
+/// # multipart
+///
+/// **Purpose:**
+/// Builds the `multipart/byteranges` answer for several ranges.
+///
+/// **Parameters:**
+/// - `response`: Full file response; its headers are kept except `Content-Type`.
+/// - `ranges`: Satisfiable, non-overlapping ranges, as start offsets and byte counts.
+/// - `len`: Length of the full body.
+///
+/// **Returns:**
+/// - `206 Partial Content` with one part per range, each with its own `Content-Type` and
+///   `Content-Range`; or `response` unchanged if the parts would exceed `MULTIPART_LIMIT` or
+///   cannot be read.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let partial = multipart(response, &[(0, 10), (95, 5)], 100);
+/// ```
+fn multipart(response: Response, ranges: &[(u64, u64)], len: u64) -> Response {
+    if ranges.iter().map(|(_, count)| count).sum::<u64>() > MULTIPART_LIMIT {
+        return response;
+    }
+    let boundary = random::token();
+    let content_type = response.headers.get("Content-Type").map(str::to_string);
+    let mut body = Vec::new();
+    for &(start, count) in ranges {
+        let Ok(part) = response.body_range(start, count) else {
+            return response;
+        };
+        let mut head = format!("--{boundary}\r\n");
+        if let Some(content_type) = &content_type {
+            let _ = write!(head, "Content-Type: {content_type}\r\n");
+        }
+        let _ = write!(head, "Content-Range: {}\r\n\r\n", content_range(start, count, len));
+        body.extend_from_slice(head.as_bytes());
+        body.extend_from_slice(&part);
+        body.extend_from_slice(b"\r\n");
+    }
+    body.extend_from_slice(format!("--{boundary}--\r\n").as_bytes());
+
+    let mut partial = response.with_body(body);
+    partial.status = 206;
+    partial.reason = "Partial Content".to_string();
+    partial.headers.set("Content-Type", format!("multipart/byteranges; boundary={boundary}"));
+    partial
+}
+
+
+/// # content_range
+///
+/// **Purpose:**
+/// Formats a `Content-Range` value for one range.
+///
+/// **Parameters:**
+/// - `start`: Offset of the first byte.
+/// - `count`: Number of bytes (at least one).
+/// - `len`: Length of the full body.
+///
+/// **Returns:**
+/// - `bytes first-last/length`.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// assert_eq!(content_range(0, 10, 100), "bytes 0-9/100");
+/// ```
+fn content_range(start: u64, count: u64, len: u64) -> String {
+    format!("bytes {start}-{}/{len}", start + count - 1)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get(headers: &str) -> Request {
+        let raw = format!("GET /file HTTP/1.1\r\nHost: a\r\n{headers}\r\n");
+        Request::read_from(&mut raw.as_bytes()).unwrap()
+    }
+
+    fn file() -> Response {
+        Response::new(200, "OK")
+            .with_header("Content-Type", "text/plain")
+            .with_header("ETag", "\"v1\"")
+            .with_body("0123456789")
+    }
+
+    #[test]
+    fn ranges_are_parsed_and_clamped() {
+        assert_eq!(parse("bytes=0-3", 10), Some(vec![(0, 4)]));
+        assert_eq!(parse("bytes=5-", 10), Some(vec![(5, 5)]));
+        assert_eq!(parse("bytes=-3", 10), Some(vec![(7, 3)]));
+        assert_eq!(parse("bytes=-30", 10), Some(vec![(0, 10)]));
+        assert_eq!(parse("bytes=8-100", 10), Some(vec![(8, 2)]));
+        assert_eq!(parse(" BYTES = 0-0 , 9-9", 10), Some(vec![(0, 1), (9, 1)]));
+        assert_eq!(parse("bytes=10-", 10), Some(vec![]));
+        assert_eq!(parse("bytes=-0", 10), Some(vec![]));
+    }
+
+    #[test]
+    fn malformed_ranges_are_ignored() {
+        for header in ["items=0-3", "bytes=", "bytes=3-1", "bytes=a-b", "bytes=+1-2", "bytes=0", "bytes=1--2"] {
+            assert_eq!(parse(header, 10), None, "{header}");
+        }
+        let many = format!("bytes={}", vec!["0-0"; MAX_RANGES + 1].join(","));
+        assert_eq!(parse(&many, 10), None);
+    }
+
+    #[test]
+    fn overlapping_ranges_are_coalesced() {
+        assert_eq!(parse("bytes=0-4,3-6,9-9", 10), Some(vec![(0, 7), (9, 1)]));
+        assert_eq!(parse("bytes=5-6,0-1", 10), Some(vec![(5, 2), (0, 2)]));
+        assert_eq!(parse("bytes=0-1,2-3", 10), Some(vec![(0, 4)]));
+    }
+
+    #[test]
+    fn a_single_range_is_served_as_partial_content() {
+        let response = apply(&get("Range: bytes=2-4\r\n"), file());
+        assert_eq!(response.status, 206);
+        assert_eq!(response.headers.get("Content-Range"), Some("bytes 2-4/10"));
+        assert_eq!(response.body, b"234");
+    }
+
+    #[test]
+    fn unsatisfiable_ranges_get_416() {
+        let response = apply(&get("Range: bytes=20-30\r\n"), file());
+        assert_eq!(response.status, 416);
+        assert_eq!(response.headers.get("Content-Range"), Some("bytes */10"));
+    }
+
+    #[test]
+    fn several_ranges_are_served_as_multipart() {
+        let response = apply(&get("Range: bytes=0-0,8-9\r\n"), file());
+        assert_eq!(response.status, 206);
+        let content_type = response.headers.get("Content-Type").unwrap();
+        let boundary = content_type.strip_prefix("multipart/byteranges; boundary=").unwrap();
+        let body = String::from_utf8(response.body.clone()).unwrap();
+        assert!(body.starts_with(&format!("--{boundary}\r\nContent-Type: text/plain\r\nContent-Range: bytes 0-0/10\r\n\r\n0\r\n")));
+        assert!(body.ends_with(&format!("Content-Range: bytes 8-9/10\r\n\r\n89\r\n--{boundary}--\r\n")));
+    }
+
+    #[test]
+    fn if_range_must_match_a_strong_validator() {
+        assert_eq!(apply(&get("Range: bytes=0-0\r\nIf-Range: \"v1\"\r\n"), file()).status, 206);
+        assert_eq!(apply(&get("Range: bytes=0-0\r\nIf-Range: \"v2\"\r\n"), file()).status, 200);
+        assert_eq!(apply(&get("Range: bytes=0-0\r\nIf-Range: W/\"v1\"\r\n"), file()).status, 200);
+    }
+
+    #[test]
+    fn other_responses_are_left_whole() {
+        let response = apply(&get(""), file());
+        assert_eq!((response.status, response.headers.get("Accept-Ranges")), (200, Some("bytes")));
+        let missing = apply(&get("Range: bytes=0-0\r\n"), Response::new(404, "Not Found"));
+        assert_eq!(missing.status, 404);
+    }
+}
@@ -0,0 +1,476 @@
+
+// ============================================================
+//  DAEGONICA SOFTWARE — rate_limit.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: RateLimit
+//!
+//! **Purpose:**
+//! Limits how fast each client may send requests, using one token bucket per client.
+//!
+//! **Context:**
+//! - Installed as router middleware with `Router::middleware(limit.middleware())`.
+//!
+//! **Responsibilities:**
+//! - Keys buckets by client address, or by a request header such as an API key.
+//! - Refills each bucket at a steady rate up to a burst size.
+//! - Answers `429 Too Many Requests` with `Retry-After` when a bucket is empty.
+//! - Bounds memory by evicting the least recently seen client once the bucket limit is reached.
+//! - Lets the rate and burst be changed while serving, e.g. from the admin API.
+//! - Does NOT share buckets between server processes.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    io,
+    sync::{Arc, Mutex, PoisonError},
+    time::Instant,
+};
+
+use crate::{
+    ip::{self, Cidr},
+    request::Request,
+    response::Response,
+    router::Next,
+};
+
+
+/// # DEFAULT_MAX_CLIENTS
+///
+/// **Summary:**
+/// Number of client buckets kept before the least recently seen one is evicted.
+pub const DEFAULT_MAX_CLIENTS: usize = 10_000;
+
+
+/// # RateLimit
+///
+/// **Summary:**
+/// Token-bucket rate limiter shared by every clone of the same limiter.
+///
+/// **Fields:**
+/// - `header`: Header whose value keys the bucket; the client address is used when unset or absent.
+/// - `trusted`: Proxy ranges whose `X-Forwarded-For` entries are believed.
+/// - `max_clients`: Maximum number of buckets kept.
+/// - `buckets`: Limits, buckets, and their recency order behind one lock.
+///
+/// **Usage Example:**
+/// ```rust
+/// # use server::{rate_limit::RateLimit, request::Request, response::Response, router::Router};
+/// # fn home(_: &Request) -> Response { Response::new(200, "OK") }
+/// let limit = RateLimit::new(5.0, 20).key_header("X-Api-Key");
+/// let router = Router::new().middleware(limit.middleware()).get("/", home);
+/// ```
+#[derive(Debug, Clone)]
+pub struct RateLimit {
+    header: Option<String>,
+    trusted: Vec<Cidr>,
+    max_clients: usize,
+    buckets: Arc<Mutex<Buckets>>,
+}
+
+
+/// # Buckets
+///
+/// **Summary:**
+/// Mutable limiter state guarded by the `RateLimit` lock.
+///
+/// **Fields:**
+/// - `rate`: Tokens added to each bucket per second.
+/// - `burst`: Bucket capacity, i.e. how many requests may arrive at once.
+/// - `entries`: Buckets by client key.
+/// - `recency`: Client keys ordered by last request (oldest first).
+/// - `tick`: Monotonic use counter feeding `recency`.
+#[derive(Debug)]
+struct Buckets {
+    rate: f64,
+    burst: f64,
+    entries: HashMap<String, Bucket>,
+    recency: BTreeMap<u64, String>,
+    tick: u64,
+}
+
+
+/// # Bucket
+///
+/// **Summary:**
+/// Token count for one client.
+///
+/// **Fields:**
+/// - `tokens`: Tokens available as of `updated`.
+/// - `updated`: When `tokens` was last brought up to date.
+/// - `last_used`: Recency tick of the last request.
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+    last_used: u64,
+}
+
+
+impl RateLimit {
+
+    /// # new
+    ///
+    /// **Purpose:**
+    /// Creates a limiter keyed by client address.
+    ///
+    /// **Parameters:**
+    /// - `rate`: Sustained requests per second allowed for each client; must be positive.
+    /// - `burst`: Requests a client may send at once after being idle (at least 1).
+    ///
+    /// **Returns:**
+    /// - A new `RateLimit` with `DEFAULT_MAX_CLIENTS` buckets.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::rate_limit::RateLimit;
+    /// let limit = RateLimit::new(0.5, 10);
+    /// ```
+    pub fn new(rate: f64, burst: u32) -> RateLimit {
+        RateLimit {
+            header: None,
+            trusted: Vec::new(),
+            max_clients: DEFAULT_MAX_CLIENTS,
+            buckets: Arc::new(Mutex::new(Buckets {
+                rate,
+                burst: f64::from(burst.max(1)),
+                entries: HashMap::new(),
+                recency: BTreeMap::new(),
+                tick: 0,
+            })),
+        }
+    }
+
+    /// # set_rate
+    ///
+    /// **Purpose:**
+    /// Changes the rate and burst of a limiter already serving, for every clone at once. Existing
+    /// buckets keep their tokens, capped at the new burst on their next request.
+    ///
+    /// **Parameters:**
+    /// - `rate`: Sustained requests per second allowed for each client; must be positive.
+    /// - `burst`: Requests a client may send at once after being idle (at least 1).
+    ///
+    /// **Returns:**
+    /// None.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::rate_limit::RateLimit;
+    /// # let limit = RateLimit::new(5.0, 20);
+    /// limit.set_rate(20.0, 40);
+    /// # assert_eq!(limit.rate(), (20.0, 40));
+    /// ```
+    pub fn set_rate(&self, rate: f64, burst: u32) {
+        let mut buckets = self.buckets.lock().unwrap_or_else(PoisonError::into_inner);
+        buckets.rate = rate;
+        buckets.burst = f64::from(burst.max(1));
+    }
+
+    /// # rate
+    ///
+    /// **Purpose:**
+    /// Returns the limiter's current rate and burst.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - `(rate, burst)`: requests per second and bucket capacity.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::rate_limit::RateLimit;
+    /// # let limit = RateLimit::new(5.0, 20);
+    /// let (rate, burst) = limit.rate();
+    /// # assert_eq!((rate, burst), (5.0, 20));
+    /// ```
+    pub fn rate(&self) -> (f64, u32) {
+        let buckets = self.buckets.lock().unwrap_or_else(PoisonError::into_inner);
+        (buckets.rate, buckets.burst as u32)
+    }
+
+    /// # key_header
+    ///
+    /// **Purpose:**
+    /// Keys buckets by a request header instead of the client address. Requests without the
+    /// header still fall back to their address.
+    ///
+    /// **Parameters:**
+    /// - `name`: Header name, e.g. `X-Api-Key`.
+    ///
+    /// **Returns:**
+    /// - The updated `RateLimit`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::rate_limit::RateLimit;
+    /// let limit = RateLimit::new(10.0, 50).key_header("X-Api-Key");
+    /// ```
+    pub fn key_header(mut self, name: &str) -> RateLimit {
+        self.header = Some(name.to_string());
+        self
+    }
+
+    /// # trust_proxy
+    ///
+    /// **Purpose:**
+    /// Believes `X-Forwarded-For` from peers in a range, so each forwarded client gets its own bucket.
+    ///
+    /// **Parameters:**
+    /// - `range`: CIDR range of the proxies in front of the server.
+    ///
+    /// **Returns:**
+    /// - The updated `RateLimit`.
+    ///
+    /// **Errors / Failures:**
+    /// - `InvalidInput` if the range is malformed.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::rate_limit::RateLimit;
+    /// let limit = RateLimit::new(5.0, 20).trust_proxy("10.0.0.0/8")?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn trust_proxy(mut self, range: &str) -> io::Result<RateLimit> {
+        self.trusted.push(Cidr::parse(range)?);
+        Ok(self)
+    }
+
+    /// # max_clients
+    ///
+    /// **Purpose:**
+    /// Sets how many client buckets are kept before the least recently seen is evicted.
+    ///
+    /// **Parameters:**
+    /// - `max`: Bucket limit (at least 1).
+    ///
+    /// **Returns:**
+    /// - The updated `RateLimit`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::rate_limit::RateLimit;
+    /// let limit = RateLimit::new(5.0, 20).max_clients(1_000);
+    /// ```
+    pub fn max_clients(mut self, max: usize) -> RateLimit {
+        self.max_clients = max.max(1);
+        self
+    }
+
+    /// # middleware
+    ///
+    /// **Purpose:**
+    /// Turns the limiter into router middleware.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - A closure accepted by `Router::middleware`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{rate_limit::RateLimit, router::Router};
+    /// let router = Router::new().middleware(RateLimit::new(5.0, 20).middleware());
+    /// ```
+    pub fn middleware(self) -> impl Fn(&Request, Next<'_>) -> Response + Send + Sync + 'static {
+        move |request, next| self.handle(request, next)
+    }
+
+    /// # handle
+    ///
+    /// **Purpose:**
+    /// Takes a token from the client's bucket and runs the rest of the chain, or refuses the
+    /// request when the bucket is empty.
+    ///
+    /// **Parameters:**
+    /// - `request`: Request being handled.
+    /// - `next`: The rest of the middleware chain.
+    ///
+    /// **Returns:**
+    /// - The downstream response, or `429 Too Many Requests` with `Retry-After`.
+    ///
+    /// **Errors / Failures:**
+    /// - Requests with neither the key header nor a peer address share one bucket.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{rate_limit::RateLimit, request::Request, response::Response, router::Next};
+    /// # let limit = RateLimit::new(5.0, 20);
+    /// # let request = &Request::read_from(&mut &b"GET / HTTP/1.1\r\n\r\n"[..]).unwrap();
+    /// # let next: Next<'_> = &|_| Response::new(200, "OK");
+    /// let response = limit.handle(request, next);
+    /// # assert_eq!(response.status, 200);
+    /// ```
+    pub fn handle(&self, request: &Request, next: Next<'_>) -> Response {
+        match self.take(&self.key(request)) {
+            Ok(()) => next(request),
+            Err(wait) => Response::new(429, "Too Many Requests")
+                .with_header("Retry-After", wait.to_string())
+                .with_header("Content-Type", "text/plain; charset=utf-8")
+                .with_body("rate limit exceeded"),
+        }
+    }
+
+    /// # key
+    ///
+    /// **Purpose:**
+    /// Picks the bucket key for a request.
+    ///
+    /// **Parameters:**
+    /// - `request`: Request being handled.
+    ///
+    /// **Returns:**
+    /// - `header:<value>` when the key header is present, otherwise `ip:<address>`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let key = self.key(request);
+    /// ```
+    fn key(&self, request: &Request) -> String {
+        if let Some(value) = self.header.as_deref().and_then(|name| request.headers.get(name)) {
+            return format!("header:{value}");
+        }
+        match ip::client_ip(request, &self.trusted) {
+            Some(address) => format!("ip:{address}"),
+            None => "ip:".to_string(),
+        }
+    }
+
+    /// # take
+    ///
+    /// **Purpose:**
+    /// Refills a client's bucket for the time since its last request and takes one token,
+    /// creating the bucket (full) and evicting the least recently seen one if needed.
+    ///
+    /// **Parameters:**
+    /// - `key`: Bucket key.
+    ///
+    /// **Returns:**
+    /// - `Ok(())` if a token was taken.
+    ///
+    /// **Errors / Failures:**
+    /// - `Err(seconds)` with the whole seconds until a token is available when the bucket is empty.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// if let Err(wait) = self.take("ip:10.0.0.1") { /* refuse */ }
+    /// ```
+    fn take(&self, key: &str) -> Result<(), u64> {
+        let mut buckets = self.buckets.lock().unwrap_or_else(PoisonError::into_inner);
+        let now = Instant::now();
+        buckets.tick += 1;
+        let tick = buckets.tick;
+
+        if !buckets.entries.contains_key(key) {
+            while buckets.entries.len() >= self.max_clients {
+                let Some((_, oldest)) = buckets.recency.pop_first() else {
+                    break;
+                };
+                buckets.entries.remove(&oldest);
+            }
+        }
+
+        let Buckets { rate, burst, entries, recency, .. } = &mut *buckets;
+        let (rate, burst) = (*rate, *burst);
+        let bucket = entries.entry(key.to_string()).or_insert(Bucket {
+            tokens: burst,
+            updated: now,
+            last_used: tick,
+        });
+        recency.remove(&bucket.last_used);
+        recency.insert(tick, key.to_string());
+        bucket.last_used = tick;
+
+        let elapsed = now.duration_since(bucket.updated).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * rate).min(burst);
+        bucket.updated = now;
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(((1.0 - bucket.tokens) / rate).ceil() as u64)
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(headers: &str) -> Request {
+        Request::read_from(&mut format!("GET / HTTP/1.1\r\nHost: example.com\r\n{headers}\r\n").as_bytes()).unwrap()
+    }
+
+    fn status(limit: &RateLimit, headers: &str) -> Response {
+        limit.handle(&request(headers), &|_| Response::new(200, "OK"))
+    }
+
+    #[test]
+    fn empty_buckets_are_refused_with_retry_after() {
+        let limit = RateLimit::new(0.5, 2);
+        assert_eq!(status(&limit, "").status, 200);
+        assert_eq!(status(&limit, "").status, 200);
+        let refused = status(&limit, "");
+        assert_eq!(refused.status, 429);
+        assert_eq!(refused.headers.get("Retry-After"), Some("2"));
+    }
+
+    #[test]
+    fn key_headers_give_each_client_its_own_bucket() {
+        let limit = RateLimit::new(1.0, 1).key_header("X-Api-Key");
+        assert_eq!(status(&limit, "X-Api-Key: a\r\n").status, 200);
+        assert_eq!(status(&limit, "X-Api-Key: a\r\n").status, 429);
+        assert_eq!(status(&limit, "X-Api-Key: b\r\n").status, 200);
+        assert_eq!(status(&limit, "").status, 200);
+    }
+
+    #[test]
+    fn the_least_recently_seen_bucket_is_evicted_first() {
+        let limit = RateLimit::new(1.0, 1).max_clients(2);
+        assert!(limit.take("a").is_ok() && limit.take("b").is_ok());
+        assert!(limit.take("b").is_err());
+        assert!(limit.take("c").is_ok());
+        assert!(limit.take("a").is_ok(), "a was evicted and starts full again");
+        assert!(limit.take("c").is_err());
+    }
+
+    #[test]
+    fn rates_can_change_while_serving() {
+        let limit = RateLimit::new(1.0, 1);
+        limit.set_rate(2.0, 3);
+        assert_eq!(limit.rate(), (2.0, 3));
+        assert!(limit.take("a").is_ok());
+    }
+}
@@ -0,0 +1,184 @@
+
+// ============================================================
+//  DAEGONICA SOFTWARE — redact.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: Redact
+//!
+//! **Purpose:**
+//! Keeps secrets carried in headers out of logs, traces, and debug output.
+//!
+//! **Context:**
+//! - Consulted by `AccessLog` for logged headers, by the tracer for captured headers, and by the
+//!   `Debug` output of `Headers`, so request and response dumps are masked too.
+//!
+//! **Responsibilities:**
+//! - Holds one process-wide list of sensitive header names, starting with `Authorization`,
+//!   `Proxy-Authorization`, `Cookie`, and `Set-Cookie`.
+//! - Replaces the values of listed headers with a fixed marker, matching names case-insensitively.
+//! - Does NOT scrub secrets from request lines, query strings, or bodies.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+use std::sync::{PoisonError, RwLock};
+
+
+/// # REDACTED
+///
+/// **Summary:**
+/// Written in place of a sensitive header's value.
+pub const REDACTED: &str = "[redacted]";
+
+
+/// # DEFAULT_HEADERS
+///
+/// **Summary:**
+/// Headers redacted until the list is replaced with `set_headers`.
+const DEFAULT_HEADERS: [&str; 4] = ["Authorization", "Proxy-Authorization", "Cookie", "Set-Cookie"];
+
+
+/// # SENSITIVE
+///
+/// **Summary:**
+/// Configured sensitive header names, or `None` while the defaults apply.
+static SENSITIVE: RwLock<Option<Vec<String>>> = RwLock::new(None);
+
+
+/// # set_headers
+///
+/// **Purpose:**
+/// Replaces the list of sensitive headers, including the defaults.
+///
+/// **Parameters:**
+/// - `names`: Header names to redact from now on.
+///
+/// **Returns:**
+/// None.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust
+/// # use server::redact;
+/// redact::set_headers(&["Authorization", "X-Api-Key"]);
+/// ```
+pub fn set_headers(names: &[&str]) {
+    let names = names.iter().map(|name| name.to_string()).collect();
+    *SENSITIVE.write().unwrap_or_else(PoisonError::into_inner) = Some(names);
+}
+
+
+/// # add_header
+///
+/// **Purpose:**
+/// Adds one header to the sensitive list, keeping the ones already listed.
+///
+/// **Parameters:**
+/// - `name`: Header name, such as `X-Api-Key`.
+///
+/// **Returns:**
+/// None.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust
+/// # use server::redact;
+/// redact::add_header("X-Upstream-Token");
+/// ```
+pub fn add_header(name: &str) {
+    let mut sensitive = SENSITIVE.write().unwrap_or_else(PoisonError::into_inner);
+    let names = sensitive.get_or_insert_with(|| DEFAULT_HEADERS.iter().map(|name| name.to_string()).collect());
+    if !names.iter().any(|listed| listed.eq_ignore_ascii_case(name)) {
+        names.push(name.to_string());
+    }
+}
+
+
+/// # headers
+///
+/// **Purpose:**
+/// Returns the sensitive header names.
+///
+/// **Parameters:**
+/// None.
+///
+/// **Returns:**
+/// - The names, in the order they were configured.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust
+/// # use server::redact;
+/// println!("redacting {}", redact::headers().join(", "));
+/// ```
+pub fn headers() -> Vec<String> {
+    match &*SENSITIVE.read().unwrap_or_else(PoisonError::into_inner) {
+        Some(names) => names.clone(),
+        None => DEFAULT_HEADERS.iter().map(|name| name.to_string()).collect(),
+    }
+}
+
+
+/// # is_sensitive
+///
+/// **Purpose:**
+/// Checks whether a header's value must be redacted.
+///
+/// **Parameters:**
+/// - `name`: Header name, in any case.
+///
+/// **Returns:**
+/// - `true` if the name is on the sensitive list.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust
+/// # use server::redact;
+/// assert!(redact::is_sensitive("cookie"));
+/// ```
+pub fn is_sensitive(name: &str) -> bool {
+    match &*SENSITIVE.read().unwrap_or_else(PoisonError::into_inner) {
+        Some(names) => names.iter().any(|listed| listed.eq_ignore_ascii_case(name)),
+        None => DEFAULT_HEADERS.iter().any(|listed| listed.eq_ignore_ascii_case(name)),
+    }
+}
+
+
+/// # value
+///
+/// **Purpose:**
+/// Returns a header value fit for logging.
+///
+/// **Parameters:**
+/// - `name`: Header name.
+/// - `value`: Header value as received or sent.
+///
+/// **Returns:**
+/// - `REDACTED` for sensitive headers, otherwise `value` unchanged.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust
+/// # use server::redact;
+/// let shown = redact::value("Authorization", "Bearer abc");
+/// # assert_ne!(shown, "Bearer abc");
+/// ```
+pub fn value<'a>(name: &str, value: &'a str) -> &'a str {
+    if is_sensitive(name) { REDACTED } else { value }
+}
@@ -0,0 +1,201 @@
+
+// ============================================================
+//  DAEGONICA SOFTWARE — redirect.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: Redirect
+//!
+//! **Purpose:**
+//! Answers configured paths with redirect responses, without writing a handler per redirect.
+//!
+//! **Context:**
+//! - Each virtual host checks its redirects before rewrite rules and routing.
+//!
+//! **Responsibilities:**
+//! - Matches request paths against glob patterns (e.g. `/old-blog/**`).
+//! - Expands `$n` references to wildcard captures in the target.
+//! - Builds `301`, `302`, `307`, or `308` responses with a `Location` header, keeping the query string.
+//! - Does NOT match hosts or methods.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+use crate::{glob::Glob, request::Request, response::Response, rewrite::expand};
+
+
+/// # Redirect
+///
+/// **Summary:**
+/// A path pattern that redirects to a target URL.
+///
+/// **Fields:**
+/// - `pattern`: Glob matched against the request path.
+/// - `target`: Redirect location; `$1`, `$2`, ... refer to wildcard captures and `$0` to the whole path.
+/// - `status`: Redirect status code.
+///
+/// **Usage Example:**
+/// ```rust
+/// # use server::{redirect::Redirect, vhost::VirtualHost};
+/// let old_blog = Redirect::permanent("/old-blog/**", "https://blog.example.com/$1");
+/// let promo = Redirect::temporary("/promo", "/sale").status(307);
+/// let site = VirtualHost::new().redirect(old_blog).redirect(promo);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Redirect {
+    pattern: Glob,
+    target: String,
+    status: u16,
+}
+
+
+impl Redirect {
+
+    /// # permanent
+    ///
+    /// **Purpose:**
+    /// Creates a `301 Moved Permanently` redirect.
+    ///
+    /// **Parameters:**
+    /// - `pattern`: Glob matched against the request path.
+    /// - `target`: Redirect location with optional `$n` references.
+    ///
+    /// **Returns:**
+    /// - A new `Redirect`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::redirect::Redirect;
+    /// let redirect = Redirect::permanent("/docs/*", "/manual/$1");
+    /// ```
+    pub fn permanent(pattern: &str, target: impl Into<String>) -> Redirect {
+        Redirect {
+            pattern: Glob::new(pattern),
+            target: target.into(),
+            status: 301,
+        }
+    }
+
+    /// # temporary
+    ///
+    /// **Purpose:**
+    /// Creates a `302 Found` redirect.
+    ///
+    /// **Parameters:**
+    /// - `pattern`: Glob matched against the request path.
+    /// - `target`: Redirect location with optional `$n` references.
+    ///
+    /// **Returns:**
+    /// - A new `Redirect`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::redirect::Redirect;
+    /// let redirect = Redirect::temporary("/login", "https://auth.example.com/login");
+    /// ```
+    pub fn temporary(pattern: &str, target: impl Into<String>) -> Redirect {
+        Redirect {
+            status: 302,
+            ..Redirect::permanent(pattern, target)
+        }
+    }
+
+    /// # status
+    ///
+    /// **Purpose:**
+    /// Overrides the redirect status, e.g. `307` or `308` to preserve the request method.
+    ///
+    /// **Parameters:**
+    /// - `status`: One of `301`, `302`, `303`, `307`, or `308`.
+    ///
+    /// **Returns:**
+    /// - The updated `Redirect`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::redirect::Redirect;
+    /// let redirect = Redirect::permanent("/api/v1/**", "/api/v2/$1").status(308);
+    /// ```
+    pub fn status(mut self, status: u16) -> Redirect {
+        self.status = status;
+        self
+    }
+
+    /// # respond
+    ///
+    /// **Purpose:**
+    /// Builds the redirect response if the request path matches.
+    ///
+    /// **Parameters:**
+    /// - `request`: Parsed client request.
+    ///
+    /// **Returns:**
+    /// - `Some(response)` on a match, otherwise `None`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{redirect::Redirect, request::Request};
+    /// # let redirect = Redirect::permanent("/docs/*", "/manual/$1");
+    /// # let request = Request::read_from(&mut &b"GET /docs/intro HTTP/1.1\r\n\r\n"[..])?;
+    /// let response = redirect.respond(&request);
+    /// # assert_eq!(response.unwrap().headers.get("Location"), Some("/manual/intro"));
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn respond(&self, request: &Request) -> Option<Response> {
+        let path = request.path();
+        let wildcards = self.pattern.captures(path)?;
+
+        let mut captures = vec![Some(path.to_string())];
+        captures.extend(wildcards.into_iter().map(Some));
+        let mut location = expand(&self.target, &captures);
+        if !location.contains('?')
+            && let Some(query) = request.query()
+        {
+            location = format!("{location}?{query}");
+        }
+        Some(Response::redirect(self.status, location))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(target: &str) -> Request {
+        Request::read_from(&mut format!("GET {target} HTTP/1.1\r\nHost: example.com\r\n\r\n").as_bytes()).unwrap()
+    }
+
+    #[test]
+    fn matching_paths_are_redirected_with_their_captures() {
+        let redirect = Redirect::permanent("/old-blog/**", "https://blog.example.com/$1");
+        let response = redirect.respond(&request("/old-blog/2024/post?ref=rss")).unwrap();
+        assert_eq!(response.status, 301);
+        assert_eq!(response.headers.get("Location"), Some("https://blog.example.com/2024/post?ref=rss"));
+        assert!(redirect.respond(&request("/blog/2024/post")).is_none());
+    }
+
+    #[test]
+    fn statuses_default_by_kind_and_can_be_overridden() {
+        assert_eq!(Redirect::temporary("/promo", "/sale").respond(&request("/promo")).unwrap().status, 302);
+        let response = Redirect::permanent("/api/v1/*", "/api/v2/$1?v=2").status(308).respond(&request("/api/v1/users?page=3")).unwrap();
+        assert_eq!((response.status, response.headers.get("Location")), (308, Some("/api/v2/users?v=2")));
+    }
+}
@@ -0,0 +1,919 @@
+
+// ============================================================
+//  DAEGONICA SOFTWARE — regex.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: Regex
+//!
+//! **Purpose:**
+//! Provides a small regular expression engine for configuration patterns, matched in linear time.
+//!
+//! **Context:**
+//! - Used by rewrite rules and route constraints, where patterns are short and written by the operator.
+//!
+//! **Responsibilities:**
+//! - Parses literals, `.`, classes (`[a-z]`, `\d`, `\w`, `\s`), anchors, groups, alternation, and
+//!   greedy or lazy quantifiers (`*`, `+`, `?`, `{n,m}`).
+//! - Compiles patterns to a program run by a Pike VM, which steps every candidate match through
+//!   the text together: matching takes time proportional to the text length times the pattern
+//!   size and never recurses on the text, so request paths cannot make it backtrack exponentially.
+//! - Searches text and reports numbered capture groups, preferring matches the way a
+//!   backtracking engine would (leftmost, then by greediness and alternation order).
+//! - Does NOT support backreferences, lookaround, or Unicode classes.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+use std::{io, iter::Peekable, str::Chars};
+
+use crate::request::invalid;
+
+
+/// # Regex
+///
+/// **Summary:**
+/// A compiled regular expression.
+///
+/// **Fields:**
+/// - `pattern`: Source pattern, kept for display.
+/// - `program`: Compiled instructions.
+/// - `groups`: Number of capture groups, including group 0 (the whole match).
+///
+/// **Usage Example:**
+/// ```rust
+/// # use server::regex::Regex;
+/// let re = Regex::new(r"^/user/(\d+)$")?;
+/// let caps = re.captures("/user/42").unwrap();
+/// assert_eq!(caps[1].as_deref(), Some("42"));
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct Regex {
+    pattern: String,
+    program: Vec<Inst>,
+    groups: usize,
+}
+
+
+/// # MAX_REPEAT
+///
+/// **Summary:**
+/// Largest count accepted in a `{n,m}` quantifier; counted repetitions are compiled by copying
+/// the repeated expression.
+const MAX_REPEAT: usize = 1000;
+
+
+/// # MAX_PROGRAM
+///
+/// **Summary:**
+/// Most instructions a compiled pattern may have.
+const MAX_PROGRAM: usize = 100_000;
+
+
+/// # Node
+///
+/// **Summary:**
+/// One element of a parsed expression.
+///
+/// **Variants:**
+/// - `Char`: A literal character.
+/// - `Any`: Any character (`.`).
+/// - `Class`: A set of character ranges, optionally negated.
+/// - `Start`: Start of text (`^`).
+/// - `End`: End of text (`$`).
+/// - `Group`: A parenthesized expression; capturing groups carry their index.
+/// - `Concat`: Expressions matched one after another.
+/// - `Alt`: Alternatives tried left to right.
+/// - `Repeat`: An expression repeated between `min` and `max` times.
+#[derive(Debug, Clone)]
+enum Node {
+    Char(char),
+    Any,
+    Class { ranges: Vec<(char, char)>, negated: bool },
+    Start,
+    End,
+    Group(Box<Node>, Option<usize>),
+    Concat(Vec<Node>),
+    Alt(Vec<Node>),
+    Repeat { node: Box<Node>, min: usize, max: Option<usize>, greedy: bool },
+}
+
+
+/// # Inst
+///
+/// **Summary:**
+/// One instruction of a compiled pattern.
+///
+/// **Variants:**
+/// - `Char`, `Any`, `Class`: Consume one character that matches.
+/// - `Start`, `End`: Continue only at the start or end of the text.
+/// - `Save`: Record the current position in a capture slot (two per group).
+/// - `Split`: Continue at both targets, preferring the first.
+/// - `Jump`: Continue at the target.
+/// - `Match`: The whole pattern matched.
+#[derive(Debug, Clone)]
+enum Inst {
+    Char(char),
+    Any,
+    Class { ranges: Vec<(char, char)>, negated: bool },
+    Start,
+    End,
+    Save(usize),
+    Split(usize, usize),
+    Jump(usize),
+    Match,
+}
+
+
+impl Regex {
+
+    /// # new
+    ///
+    /// **Purpose:**
+    /// Compiles a regular expression.
+    ///
+    /// **Parameters:**
+    /// - `pattern`: Expression source.
+    ///
+    /// **Returns:**
+    /// - A compiled `Regex`.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns `InvalidData` for unbalanced parentheses or brackets, dangling quantifiers,
+    ///   malformed repetition counts (or counts over `MAX_REPEAT`), a trailing backslash, or a
+    ///   pattern that compiles to more than `MAX_PROGRAM` instructions.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::regex::Regex;
+    /// let re = Regex::new("^/old/(.*)$")?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn new(pattern: &str) -> io::Result<Regex> {
+        let mut parser = Parser {
+            chars: pattern.chars().peekable(),
+            groups: 1,
+        };
+        let root = parser.parse_alt()?;
+        if parser.chars.next().is_some() {
+            return Err(invalid("unbalanced ')' in regex"));
+        }
+        let mut program = vec![Inst::Save(0)];
+        compile(&root, &mut program)?;
+        program.extend([Inst::Save(1), Inst::Match]);
+        Ok(Regex {
+            pattern: pattern.to_string(),
+            program,
+            groups: parser.groups,
+        })
+    }
+
+    /// # as_str
+    ///
+    /// **Purpose:**
+    /// Returns the source pattern.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - The pattern text passed to `new`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::regex::Regex;
+    /// # let re = Regex::new("^/old/(.*)$")?;
+    /// println!("{}", re.as_str());
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn as_str(&self) -> &str {
+        &self.pattern
+    }
+
+    /// # is_match
+    ///
+    /// **Purpose:**
+    /// Checks whether the expression matches anywhere in `text`.
+    ///
+    /// **Parameters:**
+    /// - `text`: Text to search.
+    ///
+    /// **Returns:**
+    /// - `true` if a match exists.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::regex::Regex;
+    /// assert!(Regex::new(r"\d")?.is_match("v2"));
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn is_match(&self, text: &str) -> bool {
+        self.captures(text).is_some()
+    }
+
+    /// # captures
+    ///
+    /// **Purpose:**
+    /// Finds the leftmost match in `text` and returns its capture groups.
+    ///
+    /// **Parameters:**
+    /// - `text`: Text to search.
+    ///
+    /// **Returns:**
+    /// - `Some(groups)` where index 0 is the whole match and unmatched groups are `None`,
+    ///   otherwise `None`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::regex::Regex;
+    /// let caps = Regex::new("^/(a|b)/")?.captures("/a/x");
+    /// # assert_eq!(caps.unwrap()[1].as_deref(), Some("a"));
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn captures(&self, text: &str) -> Option<Vec<Option<String>>> {
+        let chars: Vec<char> = text.chars().collect();
+        let slots = run(&self.program, &chars, self.groups * 2)?;
+        Some(
+            slots
+                .chunks(2)
+                .map(|span| match span {
+                    [Some(from), Some(to)] => Some(chars[*from..*to].iter().collect()),
+                    _ => None,
+                })
+                .collect(),
+        )
+    }
+}
+
+
+/// # Parser
+///
+/// **Summary:**
+/// Recursive-descent parser over the pattern's characters.
+///
+/// **Fields:**
+/// - `chars`: Remaining pattern characters.
+/// - `groups`: Next capture group index.
+struct Parser<'a> {
+    chars: Peekable<Chars<'a>>,
+    groups: usize,
+}
+
+
+impl Parser<'_> {
+
+    /// # parse_alt
+    ///
+    /// **Purpose:**
+    /// Parses `|`-separated alternatives up to a closing `)` or the end of the pattern.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - The parsed node.
+    ///
+    /// **Errors / Failures:**
+    /// - Propagates errors from nested expressions.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let root = parser.parse_alt()?;
+    /// ```
+    fn parse_alt(&mut self) -> io::Result<Node> {
+        let mut branches = vec![self.parse_concat()?];
+        while self.chars.next_if_eq(&'|').is_some() {
+            branches.push(self.parse_concat()?);
+        }
+        Ok(if branches.len() == 1 {
+            branches.remove(0)
+        } else {
+            Node::Alt(branches)
+        })
+    }
+
+    /// # parse_concat
+    ///
+    /// **Purpose:**
+    /// Parses a run of quantified atoms up to `|`, `)`, or the end of the pattern.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - A `Concat` node.
+    ///
+    /// **Errors / Failures:**
+    /// - Propagates errors from atoms and quantifiers.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let branch = parser.parse_concat()?;
+    /// ```
+    fn parse_concat(&mut self) -> io::Result<Node> {
+        let mut nodes = Vec::new();
+        while let Some(&c) = self.chars.peek() {
+            if c == '|' || c == ')' {
+                break;
+            }
+            let atom = self.parse_atom()?;
+            nodes.push(self.parse_quantifier(atom)?);
+        }
+        Ok(Node::Concat(nodes))
+    }
+
+    /// # parse_atom
+    ///
+    /// **Purpose:**
+    /// Parses one literal, class, anchor, escape, or group.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - The parsed node.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns `InvalidData` for a quantifier with nothing to repeat or an unclosed group.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let atom = parser.parse_atom()?;
+    /// ```
+    fn parse_atom(&mut self) -> io::Result<Node> {
+        let Some(c) = self.chars.next() else {
+            return Err(invalid("unexpected end of regex"));
+        };
+
+        match c {
+            '.' => Ok(Node::Any),
+            '^' => Ok(Node::Start),
+            '$' => Ok(Node::End),
+            '[' => self.parse_class(),
+            '\\' => self.parse_escape(),
+            '*' | '+' | '?' | '{' => Err(invalid("regex quantifier has nothing to repeat")),
+            '(' => {
+                let index = if self.chars.next_if_eq(&'?').is_some() {
+                    if self.chars.next_if_eq(&':').is_none() {
+                        return Err(invalid("unsupported regex group syntax"));
+                    }
+                    None
+                } else {
+                    self.groups += 1;
+                    Some(self.groups - 1)
+                };
+                let inner = self.parse_alt()?;
+                if self.chars.next_if_eq(&')').is_none() {
+                    return Err(invalid("unclosed '(' in regex"));
+                }
+                Ok(Node::Group(Box::new(inner), index))
+            }
+            c => Ok(Node::Char(c)),
+        }
+    }
+
+    /// # parse_escape
+    ///
+    /// **Purpose:**
+    /// Parses the character after a backslash outside a class.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - A class node for `\d`, `\w`, `\s` (and their negations), otherwise the literal character.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns `InvalidData` for a trailing backslash.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let node = parser.parse_escape()?;
+    /// ```
+    fn parse_escape(&mut self) -> io::Result<Node> {
+        let c = self.chars.next().ok_or_else(|| invalid("trailing '\\' in regex"))?;
+        Ok(match shorthand_class(c.to_ascii_lowercase()) {
+            Some(ranges) => Node::Class {
+                ranges,
+                negated: c.is_ascii_uppercase(),
+            },
+            None => Node::Char(escaped_char(c)),
+        })
+    }
+
+    /// # parse_class
+    ///
+    /// **Purpose:**
+    /// Parses a bracketed character class after its opening `[`.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - A `Class` node.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns `InvalidData` for an unclosed class or a negated shorthand (`\D`) inside it.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let class = parser.parse_class()?;
+    /// ```
+    fn parse_class(&mut self) -> io::Result<Node> {
+        let negated = self.chars.next_if_eq(&'^').is_some();
+        let mut ranges = Vec::new();
+        let mut first = true;
+
+        loop {
+            let c = self.chars.next().ok_or_else(|| invalid("unclosed '[' in regex"))?;
+            if c == ']' && !first {
+                break;
+            }
+            first = false;
+
+            let low = if c == '\\' {
+                let escaped = self.chars.next().ok_or_else(|| invalid("trailing '\\' in regex"))?;
+                if let Some(class) = shorthand_class(escaped) {
+                    ranges.extend(class);
+                    continue;
+                }
+                if shorthand_class(escaped.to_ascii_lowercase()).is_some() {
+                    return Err(invalid("negated shorthand inside a regex class"));
+                }
+                escaped_char(escaped)
+            } else {
+                c
+            };
+
+            let mut lookahead = self.chars.clone();
+            if lookahead.next() == Some('-') && lookahead.peek().is_some_and(|&next| next != ']') {
+                self.chars.next();
+                let high = match self.chars.next() {
+                    Some('\\') => escaped_char(self.chars.next().ok_or_else(|| invalid("trailing '\\' in regex"))?),
+                    Some(high) => high,
+                    None => return Err(invalid("unclosed '[' in regex")),
+                };
+                if high < low {
+                    return Err(invalid("reversed range in regex class"));
+                }
+                ranges.push((low, high));
+            } else {
+                ranges.push((low, low));
+            }
+        }
+
+        Ok(Node::Class { ranges, negated })
+    }
+
+    /// # parse_quantifier
+    ///
+    /// **Purpose:**
+    /// Wraps `atom` in a repetition if a quantifier follows it.
+    ///
+    /// **Parameters:**
+    /// - `atom`: The node just parsed.
+    ///
+    /// **Returns:**
+    /// - `atom` unchanged, or a `Repeat` node.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns `InvalidData` for malformed `{n,m}` counts.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let node = parser.parse_quantifier(atom)?;
+    /// ```
+    fn parse_quantifier(&mut self, atom: Node) -> io::Result<Node> {
+        let (min, max) = match self.chars.peek() {
+            Some('*') => (0, None),
+            Some('+') => (1, None),
+            Some('?') => (0, Some(1)),
+            Some('{') => {
+                self.chars.next();
+                let mut spec = String::new();
+                loop {
+                    match self.chars.next() {
+                        Some('}') => break,
+                        Some(c) => spec.push(c),
+                        None => return Err(invalid("unclosed '{' in regex")),
+                    }
+                }
+                let count = |text: &str| text.trim().parse::<usize>().map_err(|_| invalid("invalid regex repetition count"));
+                let (min, max) = match spec.split_once(',') {
+                    Some((min, "")) => (count(min)?, None),
+                    Some((min, max)) => (count(min)?, Some(count(max)?)),
+                    None => (count(&spec)?, Some(count(&spec)?)),
+                };
+                if max.is_some_and(|max| max < min) || max.unwrap_or(min) > MAX_REPEAT {
+                    return Err(invalid("invalid regex repetition count"));
+                }
+                let greedy = self.chars.next_if_eq(&'?').is_none();
+                return Ok(Node::Repeat { node: Box::new(atom), min, max, greedy });
+            }
+            _ => return Ok(atom),
+        };
+        self.chars.next();
+        let greedy = self.chars.next_if_eq(&'?').is_none();
+        Ok(Node::Repeat { node: Box::new(atom), min, max, greedy })
+    }
+}
+
+
+/// # Threads
+///
+/// **Summary:**
+/// Candidate matches waiting at one text position, in priority order.
+///
+/// **Fields:**
+/// - `threads`: Program counter and capture slots of each candidate.
+/// - `seen`: Instructions already reached at this position; a later, lower-priority arrival
+///   at the same instruction could only repeat the earlier one's work.
+struct Threads {
+    threads: Vec<(usize, Vec<Option<usize>>)>,
+    seen: Vec<bool>,
+}
+
+
+impl Threads {
+
+    /// # new
+    ///
+    /// **Purpose:**
+    /// Creates an empty list for a program.
+    ///
+    /// **Parameters:**
+    /// - `size`: Number of instructions in the program.
+    ///
+    /// **Returns:**
+    /// - An empty `Threads`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let mut current = Threads::new(program.len());
+    /// ```
+    fn new(size: usize) -> Threads {
+        Threads {
+            threads: Vec::new(),
+            seen: vec![false; size],
+        }
+    }
+
+    /// # add
+    ///
+    /// **Purpose:**
+    /// Adds a candidate at `pc`, following jumps, splits, saves, and anchors until each path
+    /// reaches an instruction that consumes a character (or `Match`). Paths are followed
+    /// depth first with an explicit stack, so the preferred branch of a split comes first.
+    ///
+    /// **Parameters:**
+    /// - `program`: Compiled instructions.
+    /// - `pc`: Instruction to start at.
+    /// - `pos`: Text position the candidate is at.
+    /// - `len`: Text length, for `End`.
+    /// - `caps`: Capture slots so far.
+    ///
+    /// **Returns:**
+    /// None.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// current.add(program, 0, pos, text.len(), vec![None; slots]);
+    /// ```
+    fn add(&mut self, program: &[Inst], pc: usize, pos: usize, len: usize, caps: Vec<Option<usize>>) {
+        let mut stack = vec![(pc, caps)];
+        while let Some((pc, mut caps)) = stack.pop() {
+            if std::mem::replace(&mut self.seen[pc], true) {
+                continue;
+            }
+            match &program[pc] {
+                Inst::Jump(target) => stack.push((*target, caps)),
+                Inst::Split(first, second) => {
+                    stack.push((*second, caps.clone()));
+                    stack.push((*first, caps));
+                }
+                Inst::Save(slot) => {
+                    caps[*slot] = Some(pos);
+                    stack.push((pc + 1, caps));
+                }
+                Inst::Start if pos == 0 => stack.push((pc + 1, caps)),
+                Inst::End if pos == len => stack.push((pc + 1, caps)),
+                Inst::Start | Inst::End => {}
+                _ => self.threads.push((pc, caps)),
+            }
+        }
+    }
+}
+
+
+/// # compile
+///
+/// **Purpose:**
+/// Appends the instructions for `node` to `program`.
+///
+/// **Parameters:**
+/// - `node`: Parsed expression.
+/// - `program`: Instructions compiled so far.
+///
+/// **Returns:**
+/// - `Ok(())` once `node` is compiled.
+///
+/// **Errors / Failures:**
+/// - Returns `InvalidData` if the program grows past `MAX_PROGRAM` instructions.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// compile(&root, &mut program)?;
+/// ```
+fn compile(node: &Node, program: &mut Vec<Inst>) -> io::Result<()> {
+    if program.len() > MAX_PROGRAM {
+        return Err(invalid("regex is too large"));
+    }
+    let prefer = |greedy: bool, body: usize, exit: usize| if greedy { Inst::Split(body, exit) } else { Inst::Split(exit, body) };
+    match node {
+        Node::Char(c) => program.push(Inst::Char(*c)),
+        Node::Any => program.push(Inst::Any),
+        Node::Class { ranges, negated } => program.push(Inst::Class { ranges: ranges.clone(), negated: *negated }),
+        Node::Start => program.push(Inst::Start),
+        Node::End => program.push(Inst::End),
+        Node::Group(inner, None) => compile(inner, program)?,
+        Node::Group(inner, Some(index)) => {
+            program.push(Inst::Save(index * 2));
+            compile(inner, program)?;
+            program.push(Inst::Save(index * 2 + 1));
+        }
+        Node::Concat(nodes) => {
+            for node in nodes {
+                compile(node, program)?;
+            }
+        }
+        Node::Alt(branches) => {
+            let mut jumps = Vec::new();
+            for (index, branch) in branches.iter().enumerate() {
+                if index + 1 == branches.len() {
+                    compile(branch, program)?;
+                    break;
+                }
+                let split = program.len();
+                program.push(Inst::Split(split + 1, split + 1));
+                compile(branch, program)?;
+                jumps.push(program.len());
+                program.push(Inst::Jump(0));
+                program[split] = Inst::Split(split + 1, program.len());
+            }
+            let end = program.len();
+            for jump in jumps {
+                program[jump] = Inst::Jump(end);
+            }
+        }
+        Node::Repeat { node, min, max, greedy } => {
+            for _ in 0..*min {
+                compile(node, program)?;
+            }
+            match max {
+                None => {
+                    let split = program.len();
+                    program.push(Inst::Jump(0));
+                    compile(node, program)?;
+                    program.push(Inst::Jump(split));
+                    program[split] = prefer(*greedy, split + 1, program.len());
+                }
+                Some(max) => {
+                    let mut splits = Vec::new();
+                    for _ in *min..*max {
+                        splits.push(program.len());
+                        program.push(Inst::Jump(0));
+                        compile(node, program)?;
+                    }
+                    let exit = program.len();
+                    for split in splits {
+                        program[split] = prefer(*greedy, split + 1, exit);
+                    }
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+
+/// # run
+///
+/// **Purpose:**
+/// Runs a compiled program over `text` as a Pike VM: every candidate advances one character
+/// at a time, in priority order, and a new candidate starts at each position until one matches.
+///
+/// **Parameters:**
+/// - `program`: Compiled instructions.
+/// - `text`: Characters to search.
+/// - `slots`: Number of capture slots (two per group).
+///
+/// **Returns:**
+/// - The capture slots of the preferred leftmost match, or `None`.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let slots = run(&self.program, &chars, self.groups * 2)?;
+/// ```
+fn run(program: &[Inst], text: &[char], slots: usize) -> Option<Vec<Option<usize>>> {
+    let mut current = Threads::new(program.len());
+    let mut next = Threads::new(program.len());
+    let mut matched = None;
+
+    for pos in 0..=text.len() {
+        if matched.is_none() {
+            current.add(program, 0, pos, text.len(), vec![None; slots]);
+        } else if current.threads.is_empty() {
+            break;
+        }
+        let here = text.get(pos);
+        for (pc, caps) in current.threads.drain(..) {
+            let advances = match &program[pc] {
+                Inst::Char(expected) => here == Some(expected),
+                Inst::Any => here.is_some(),
+                Inst::Class { ranges, negated } => {
+                    here.is_some_and(|c| ranges.iter().any(|&(low, high)| (low..=high).contains(c)) != *negated)
+                }
+                Inst::Match => {
+                    matched = Some(caps);
+                    break;
+                }
+                _ => false,
+            };
+            if advances {
+                next.add(program, pc + 1, pos + 1, text.len(), caps);
+            }
+        }
+        current.seen.fill(false);
+        std::mem::swap(&mut current, &mut next);
+    }
+    matched
+}
+
+
+/// # shorthand_class
+///
+/// **Purpose:**
+/// Expands the lower-case shorthand classes `d`, `w`, and `s` into character ranges.
+///
+/// **Parameters:**
+/// - `c`: Character following the backslash.
+///
+/// **Returns:**
+/// - `Some(ranges)` for a shorthand class, otherwise `None`.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let digits = shorthand_class('d');
+/// ```
+fn shorthand_class(c: char) -> Option<Vec<(char, char)>> {
+    match c {
+        'd' => Some(vec![('0', '9')]),
+        'w' => Some(vec![('a', 'z'), ('A', 'Z'), ('0', '9'), ('_', '_')]),
+        's' => Some(vec![(' ', ' '), ('\t', '\r')]),
+        _ => None,
+    }
+}
+
+
+/// # escaped_char
+///
+/// **Purpose:**
+/// Resolves an escaped literal (`\n`, `\t`, `\r`, or the character itself).
+///
+/// **Parameters:**
+/// - `c`: Character following the backslash.
+///
+/// **Returns:**
+/// - The literal character.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// assert_eq!(escaped_char('.'), '.');
+/// ```
+fn escaped_char(c: char) -> char {
+    match c {
+        'n' => '\n',
+        't' => '\t',
+        'r' => '\r',
+        c => c,
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, Instant};
+
+    fn caps(pattern: &str, text: &str) -> Option<Vec<Option<String>>> {
+        Regex::new(pattern).unwrap().captures(text)
+    }
+
+    fn group(pattern: &str, text: &str, index: usize) -> Option<String> {
+        caps(pattern, text).and_then(|groups| groups[index].clone())
+    }
+
+    #[test]
+    fn matches_literals_classes_and_anchors() {
+        assert!(Regex::new(r"\d").unwrap().is_match("v2"));
+        assert!(!Regex::new(r"^\d").unwrap().is_match("v2"));
+        assert!(Regex::new(r"^[a-c]+x$").unwrap().is_match("abcx"));
+        assert!(!Regex::new(r"^[^a-c]+$").unwrap().is_match("abd"));
+        assert!(Regex::new(r"^\w+\s\W$").unwrap().is_match("ab_9 !"));
+        assert!(Regex::new(r"^a\.b$").unwrap().is_match("a.b"));
+        assert!(!Regex::new(r"^a\.b$").unwrap().is_match("axb"));
+    }
+
+    #[test]
+    fn reports_leftmost_match_and_groups() {
+        assert_eq!(group(r"^/user/(\d+)$", "/user/42", 1).as_deref(), Some("42"));
+        assert_eq!(group(r"b+", "abbbc", 0).as_deref(), Some("bbb"));
+        assert_eq!(caps("^/(a|b)/(x)?", "/a/").unwrap(), vec![Some("/a/".into()), Some("a".into()), None]);
+        assert_eq!(group("(?:ab)+(c)", "ababc", 1).as_deref(), Some("c"));
+    }
+
+    #[test]
+    fn prefers_matches_like_a_backtracker() {
+        assert_eq!(group("^(a*)(a*)$", "aaa", 1).as_deref(), Some("aaa"));
+        assert_eq!(group("^(a*?)(a*)$", "aaa", 1).as_deref(), Some(""));
+        assert_eq!(group("<(.+)>", "<a><b>", 1).as_deref(), Some("a><b"));
+        assert_eq!(group("<(.+?)>", "<a><b>", 1).as_deref(), Some("a"));
+        assert_eq!(group("(a|ab)(c|bcd)", "abcd", 0).as_deref(), Some("abcd"));
+        assert_eq!(group("(ab|a)(c|bcd)", "abcd", 1).as_deref(), Some("ab"));
+        assert_eq!(group("^(a{2,3})(a*)$", "aaaa", 1).as_deref(), Some("aaa"));
+        assert_eq!(group("^(a{2,3}?)(a*)$", "aaaa", 1).as_deref(), Some("aa"));
+        assert_eq!(group("^x{2}$", "xx", 0).as_deref(), Some("xx"));
+        assert!(caps("^x{2}$", "xxx").is_none());
+    }
+
+    #[test]
+    fn terminates_on_nested_empty_loops() {
+        assert!(Regex::new("^(a*)*$").unwrap().is_match("aaaa"));
+        assert!(!Regex::new("^(a*)*$").unwrap().is_match("aaab"));
+        assert!(Regex::new("^(a?)+b$").unwrap().is_match("b"));
+    }
+
+    #[test]
+    fn matches_pathological_patterns_in_linear_time() {
+        let started = Instant::now();
+        let evil = format!("{}b", "a".repeat(5000));
+        assert!(!Regex::new("^(a+)+$").unwrap().is_match(&evil));
+        assert!(!Regex::new("^(a|a)*$").unwrap().is_match(&evil));
+        assert!(Regex::new("^/(.*)/(.*)/(.*)$").unwrap().is_match(&format!("/{evil}/x/y")));
+        assert!(started.elapsed() < Duration::from_secs(5), "{:?}", started.elapsed());
+    }
+
+    #[test]
+    fn classes_take_ranges_escapes_and_negation() {
+        let class = Regex::new(r"^[a-f0-9\-_.]+$").unwrap();
+        assert!(class.is_match("3fa9-b_c.d") && !class.is_match("3fg"));
+        assert!(Regex::new(r"^[\d\s]+$").unwrap().is_match("1 2\t3"));
+        assert!(Regex::new(r"^[^/]+$").unwrap().is_match("name.txt"));
+        assert!(!Regex::new(r"^[^/]+$").unwrap().is_match("dir/name"));
+        assert!(Regex::new(r"^a\tb\n$").unwrap().is_match("a\tb\n"));
+    }
+
+    #[test]
+    fn works_on_characters_not_bytes() {
+        assert_eq!(group("^(.)(.)$", "é€", 2).as_deref(), Some("€"));
+        assert_eq!(group("é+", "caféé!", 0).as_deref(), Some("éé"));
+        assert!(Regex::new("^[à-ÿ]$").unwrap().is_match("ö"));
+    }
+
+    #[test]
+    fn rejects_malformed_and_oversized_patterns() {
+        for pattern in ["(a", "a)", "[a", "*a", "a{2,1}", "a{x}", "\\", "a{1001}", "(?=a)", "(a{1000}){1000}"] {
+            assert!(Regex::new(pattern).is_err(), "{pattern}");
+        }
+    }
+}
@@ -0,0 +1,1103 @@
+
+// ============================================================
+//  DAEGONICA SOFTWARE — request.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: Request
+//!
+//! **Purpose:**
+//! Parses incoming HTTP/1.x requests (request line, headers, and body) from a buffered stream.
+//!
+//! **Context:**
+//! - Used by the connection handler and the proxy to inspect and forward client requests.
+//!
+//! **Responsibilities:**
+//! - Reads the request line and header block.
+//! - Reads `Content-Length` and `chunked` request bodies.
+//! - Does NOT route requests or enforce size limits.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+use std::{
+    error::Error,
+    fmt,
+    io::{self, BufRead, Read},
+    net::{IpAddr, SocketAddr},
+    sync::Arc,
+};
+
+use crate::{cancel::Cancellation, connection::ConnectionInfo, cookie, extensions::Extensions, headers::Headers, limits::{DEFAULT_MAX_BODY, HeadLimits, LimitExceeded, Limits}, response::Response, state::AppState};
+
+
+/// # MAX_CHUNK_LINE
+///
+/// **Summary:**
+/// Longest chunk-size line (size plus extensions) accepted in a chunked body.
+const MAX_CHUNK_LINE: usize = 4096;
+
+
+/// # RequestError
+///
+/// **Summary:**
+/// Why a well-formed request cannot be served; carried inside the `io::Error` returned while
+/// reading it, next to `LimitExceeded` for requests that break a size limit.
+///
+/// **Variants:**
+/// - `UnsupportedVersion`: The request names an HTTP major version other than 1 (`505`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum RequestError {
+    UnsupportedVersion,
+}
+
+
+/// # Request
+///
+/// **Summary:**
+/// A fully read HTTP request.
+///
+/// **Fields:**
+/// - `method`: Request method (e.g. `GET`).
+/// - `target`: Request target exactly as sent (e.g. `/index.html?x=1`).
+/// - `version`: Protocol version (e.g. `HTTP/1.1`).
+/// - `headers`: Request header fields.
+/// - `body`: Decoded request body (empty when none was sent).
+/// - `params`: Parameters captured while routing, in capture order: host captures (e.g. `tenant`
+///   from `:tenant.example.com`) first, then path captures.
+/// - `peer`: Address of the connected client (set by the server; a proxy's address when behind one).
+/// - `client`: Effective client address: the peer's, or the forwarded one when the peer is a
+///   trusted proxy (set by the server; see `Server::trust_proxy`).
+/// - `secure`: Whether the client reached the server over HTTPS, as forwarded by a trusted proxy.
+/// - `connection`: The connection the request arrived on (set by the server), shared by every
+///   request on it.
+/// - `cancellation`: Signal raised when the client hangs up before the response is finished.
+/// - `state`: Application state shared by the server (see `Server::with_state`).
+/// - `extensions`: Typed values attached by middleware for downstream handlers.
+///
+/// **Usage Example:**
+/// ```rust
+/// # use server::request::Request;
+/// # let mut reader = &b"GET /search?q=rust HTTP/1.1\r\nHost: example.com\r\n\r\n"[..];
+/// let request = Request::read_from(&mut reader)?;
+/// println!("{} {}", request.method, request.target);
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct Request {
+    pub method: String,
+    pub target: String,
+    pub version: String,
+    pub headers: Headers,
+    pub body: Vec<u8>,
+    pub params: Vec<(String, String)>,
+    pub peer: Option<SocketAddr>,
+    pub client: Option<IpAddr>,
+    pub secure: bool,
+    pub connection: Arc<ConnectionInfo>,
+    pub cancellation: Cancellation,
+    pub state: Arc<AppState>,
+    pub extensions: Extensions,
+}
+
+
+impl Request {
+
+    /// # read_from
+    ///
+    /// **Purpose:**
+    /// Reads one complete request (head and body) from a buffered reader, within the default
+    /// `Limits`.
+    ///
+    /// **Parameters:**
+    /// - `reader`: Buffered reader positioned at the start of a request.
+    ///
+    /// **Returns:**
+    /// - The parsed `Request`.
+    ///
+    /// **Errors / Failures:**
+    /// - `UnexpectedEof` if the connection closes before a request line arrives.
+    /// - `InvalidData` if the request line, a header, or the body framing is malformed.
+    /// - `LimitExceeded` if the target, head, or body is larger than the default caps.
+    ///
+    /// **Examples:**
+    /// ```rust,no_run
+    /// # use server::request::Request;
+    /// # use std::{io::BufReader, net::TcpStream};
+    /// # let stream = TcpStream::connect("127.0.0.1:7878")?;
+    /// let request = Request::read_from(&mut BufReader::new(&stream))?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn read_from<R: BufRead>(reader: &mut R) -> io::Result<Request> {
+        Request::read_within(reader, &Limits::default().head(), DEFAULT_MAX_BODY)
+    }
+
+    /// # read_within
+    ///
+    /// **Purpose:**
+    /// Reads one complete request under explicit caps, for listeners that serve outside the
+    /// server's configured `Limits`. A declared body over the cap is refused before anything is
+    /// allocated for it.
+    ///
+    /// **Parameters:**
+    /// - `reader`: Buffered reader positioned at the start of a request.
+    /// - `limits`: Target length, header count, and head size caps.
+    /// - `max_body`: Largest decoded body accepted.
+    ///
+    /// **Returns:**
+    /// - The parsed `Request`.
+    ///
+    /// **Errors / Failures:**
+    /// - The failures of `read_head`, plus `LimitExceeded::Body` for a body over `max_body`.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let request = Request::read_within(&mut reader, &ADMIN_HEAD, ADMIN_MAX_BODY)?;
+    /// ```
+    pub(crate) fn read_within<R: BufRead>(reader: &mut R, limits: &HeadLimits, max_body: usize) -> io::Result<Request> {
+        let mut request = Request::read_head(reader, limits, &mut Vec::new())?;
+        request.body = read_body_within(reader, &request.headers, max_body)?;
+        Ok(request)
+    }
+
+    /// # read_head
+    ///
+    /// **Purpose:**
+    /// Reads the request line and headers, leaving the body unread. Oversized lines are refused
+    /// without being buffered in full.
+    ///
+    /// **Parameters:**
+    /// - `reader`: Buffered reader positioned at the start of a request.
+    /// - `limits`: Target length, header count, and head size caps.
+    /// - `line`: Scratch buffer each line is read into, reused across calls on a connection.
+    ///
+    /// **Returns:**
+    /// - The parsed `Request` with an empty body.
+    ///
+    /// **Errors / Failures:**
+    /// - `UnexpectedEof` if the connection closes before a request line arrives.
+    /// - `InvalidData` if the request line or a header is malformed: the method must be a token,
+    ///   the target free of control characters, and the version `HTTP/<digit>.<digit>`.
+    /// - `LimitExceeded::Target` if the request target (or the line carrying it) is too long.
+    /// - `LimitExceeded::Headers` if the header fields break the count or size cap.
+    /// - `RequestError::UnsupportedVersion` if the version is well formed but its major digit is
+    ///   not `1`.
+    /// - `InvalidData` if the body framing is ambiguous (see `check_framing`).
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let mut request = Request::read_head(&mut reader, &limits.head(), &mut scratch)?;
+    /// request.body = read_body_within(&mut reader, &request.headers, limits.body_limit(&request.path))?;
+    /// ```
+    pub(crate) fn read_head<R: BufRead>(reader: &mut R, limits: &HeadLimits, line: &mut Vec<u8>) -> io::Result<Request> {
+        let mut budget = limits.max_bytes;
+        let allowance = limits.request_line(budget);
+        let mut line_budget = allowance;
+        let request_line = read_line_within(reader, &mut line_budget, line)
+            .map_err(|error| match LimitExceeded::of(&error) {
+                Some(_) if allowance < budget => LimitExceeded::Target.error(),
+                _ => error,
+            })?
+            .ok_or_else(|| io::Error::new(io::ErrorKind::UnexpectedEof, "connection closed"))?;
+        budget -= allowance - line_budget;
+
+        let mut parts = request_line.split(' ');
+        let (method, target, version) = match (parts.next(), parts.next(), parts.next(), parts.next()) {
+            (Some(method), Some(target), Some(version), None)
+                if is_token(method)
+                    && !target.is_empty()
+                    && !target.bytes().any(|byte| byte.is_ascii_control())
+                    && is_version(version) =>
+            {
+                (method.to_string(), target.to_string(), version.to_string())
+            }
+            _ => return Err(invalid("malformed request line")),
+        };
+        if !version.starts_with("HTTP/1.") {
+            return Err(RequestError::UnsupportedVersion.error());
+        }
+        if target.len() > limits.max_target {
+            return Err(LimitExceeded::Target.error());
+        }
+
+        let headers = read_headers_within(reader, limits.max_headers, &mut budget, line)?;
+        check_framing(&version, &headers)?;
+
+        Ok(Request {
+            method,
+            target,
+            version,
+            headers,
+            body: Vec::new(),
+            params: Vec::new(),
+            peer: None,
+            client: None,
+            secure: false,
+            connection: Arc::default(),
+            cancellation: Cancellation::default(),
+            state: Arc::default(),
+            extensions: Extensions::default(),
+        })
+    }
+
+    /// # request_line
+    ///
+    /// **Purpose:**
+    /// Rebuilds the request line (`METHOD target VERSION`) without the trailing CRLF.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - The request line as a `String`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::request::Request;
+    /// # let request = Request::read_from(&mut &b"GET / HTTP/1.1\r\n\r\n"[..])?;
+    /// assert_eq!(request.request_line(), "GET / HTTP/1.1");
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn request_line(&self) -> String {
+        format!("{} {} {}", self.method, self.target, self.version)
+    }
+
+    /// # path
+    ///
+    /// **Purpose:**
+    /// Returns the request target without its query string.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - The path component (e.g. `/search` for `/search?q=rust`).
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::request::Request;
+    /// # let request = Request::read_from(&mut &b"GET /search?q=rust HTTP/1.1\r\n\r\n"[..])?;
+    /// assert_eq!(request.path(), "/search");
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn path(&self) -> &str {
+        self.target.split('?').next().unwrap_or("")
+    }
+
+    /// # query
+    ///
+    /// **Purpose:**
+    /// Returns the raw query string of the request target, if any.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - `Some(query)` without the leading `?`, or `None`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::request::Request;
+    /// # let request = Request::read_from(&mut &b"GET /search?q=rust HTTP/1.1\r\n\r\n"[..])?;
+    /// assert_eq!(request.query(), Some("q=rust"));
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn query(&self) -> Option<&str> {
+        self.target.split_once('?').map(|(_, query)| query)
+    }
+
+    /// # param
+    ///
+    /// **Purpose:**
+    /// Returns a parameter captured while routing the request.
+    ///
+    /// **Parameters:**
+    /// - `name`: Parameter name as written in the pattern (without the leading `:`).
+    ///
+    /// **Returns:**
+    /// - `Some(value)` if the parameter was captured (the latest capture wins), otherwise `None`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::request::Request;
+    /// # let mut request = Request::read_from(&mut &b"GET /acme/orders HTTP/1.1\r\n\r\n"[..])?;
+    /// # request.params.push(("tenant".to_string(), "acme".to_string()));
+    /// let tenant = request.param("tenant");
+    /// # assert_eq!(tenant, Some("acme"));
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .rev()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// # cookie
+    ///
+    /// **Purpose:**
+    /// Returns the value of a cookie sent with the request.
+    ///
+    /// **Parameters:**
+    /// - `name`: Cookie name (case-sensitive).
+    ///
+    /// **Returns:**
+    /// - `Some(value)` if the cookie was sent, otherwise `None`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::request::Request;
+    /// # let request = Request::read_from(&mut &b"GET / HTTP/1.1\r\nCookie: theme=dark\r\n\r\n"[..])?;
+    /// let theme = request.cookie("theme").unwrap_or("light");
+    /// # assert_eq!(theme, "dark");
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn cookie(&self, name: &str) -> Option<&str> {
+        cookie::get(&self.headers, name)
+    }
+
+    /// # state
+    ///
+    /// **Purpose:**
+    /// Returns the shared application value of type `T` registered with `Server::with_state`.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - `Some(value)` if a `T` was registered, otherwise `None`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{request::Request, state::AppState};
+    /// # use std::sync::Arc;
+    /// # struct Config;
+    /// # let mut request = Request::read_from(&mut &b"GET / HTTP/1.1\r\n\r\n"[..])?;
+    /// # let mut state = AppState::default();
+    /// # state.insert(Config);
+    /// # request.state = Arc::new(state);
+    /// let config = request.state::<Config>().expect("config registered");
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn state<T: Send + Sync + 'static>(&self) -> Option<Arc<T>> {
+        self.state.get()
+    }
+
+    /// # is_upgrade
+    ///
+    /// **Purpose:**
+    /// Checks whether the client asked to switch protocols to WebSocket (`Connection: Upgrade` with `Upgrade: websocket`).
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - `true` if the request is a WebSocket upgrade.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::request::Request;
+    /// # let request = Request::read_from(&mut &b"GET /chat HTTP/1.1\r\nConnection: Upgrade\r\nUpgrade: websocket\r\n\r\n"[..])?;
+    /// if request.is_upgrade() { /* tunnel */ }
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn is_upgrade(&self) -> bool {
+        self.headers.has_token("Connection", "upgrade")
+            && self.headers.has_token("Upgrade", "websocket")
+    }
+
+    /// # is_http10
+    ///
+    /// **Purpose:**
+    /// Checks whether the client speaks HTTP/1.0, which may omit `Host`, cannot receive
+    /// `chunked` bodies or interim responses, and closes after each response by default.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - `true` for `HTTP/1.0` requests.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::request::Request;
+    /// # let request = Request::read_from(&mut &b"GET / HTTP/1.0\r\n\r\n"[..])?;
+    /// if request.is_http10() { /* frame with Content-Length only */ }
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn is_http10(&self) -> bool {
+        self.version == "HTTP/1.0"
+    }
+
+    /// # keep_alive
+    ///
+    /// **Purpose:**
+    /// Checks whether the client wants the connection kept open after the response: HTTP/1.1
+    /// unless it sent `Connection: close`, HTTP/1.0 only if it sent `Connection: keep-alive`.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - `true` if the client expects a persistent connection.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{request::Request, response::Response};
+    /// # let request = Request::read_from(&mut &b"GET / HTTP/1.1\r\nConnection: close\r\n\r\n"[..])?;
+    /// # let mut response = Response::new(200, "OK");
+    /// if !request.keep_alive() { response.headers.set("Connection", "close"); }
+    /// # assert_eq!(response.headers.get("Connection"), Some("close"));
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn keep_alive(&self) -> bool {
+        if self.is_http10() {
+            self.headers.has_token("Connection", "keep-alive")
+        } else {
+            !self.headers.has_token("Connection", "close")
+        }
+    }
+}
+
+
+impl RequestError {
+
+    /// # error
+    ///
+    /// **Purpose:**
+    /// Wraps the reason in an I/O error so it can travel through the readers.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - An `InvalidData` error carrying this value.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// return Err(RequestError::UnsupportedVersion.error());
+    /// ```
+    pub(crate) fn error(self) -> io::Error {
+        io::Error::new(io::ErrorKind::InvalidData, self)
+    }
+
+    /// # of
+    ///
+    /// **Purpose:**
+    /// Recovers the reason from an error built by `error`.
+    ///
+    /// **Parameters:**
+    /// - `error`: Error returned while reading a request.
+    ///
+    /// **Returns:**
+    /// - `Some(reason)` if the error carries a `RequestError`, otherwise `None`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// if let Some(reason) = RequestError::of(&error) { /* answer reason.response() */ }
+    /// ```
+    pub(crate) fn of(error: &io::Error) -> Option<RequestError> {
+        error.get_ref()?.downcast_ref::<RequestError>().copied()
+    }
+
+    /// # response
+    ///
+    /// **Purpose:**
+    /// Builds the response owed to a client whose request is refused for this reason.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - The matching `5xx` response.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let response = RequestError::UnsupportedVersion.response();
+    /// ```
+    pub(crate) fn response(self) -> Response {
+        let (status, reason) = match self {
+            RequestError::UnsupportedVersion => (505, "HTTP Version Not Supported"),
+        };
+        Response::new(status, reason)
+            .with_header("Content-Type", "text/plain; charset=utf-8")
+            .with_body(self.to_string())
+    }
+}
+
+
+impl fmt::Display for RequestError {
+
+    /// # fmt
+    ///
+    /// **Purpose:**
+    /// Describes the reason.
+    ///
+    /// **Parameters:**
+    /// - `f`: Formatter to write to.
+    ///
+    /// **Returns:**
+    /// - The formatter result.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let message = RequestError::UnsupportedVersion.to_string();
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RequestError::UnsupportedVersion => f.write_str("HTTP version not supported"),
+        }
+    }
+}
+
+
+impl Error for RequestError {}
+
+
+/// # read_headers_within
+///
+/// **Purpose:**
+/// Reads a header block up to and including the terminating empty line, refusing it once it has
+/// too many fields or bytes.
+///
+/// **Parameters:**
+/// - `reader`: Buffered reader positioned at the first header line.
+/// - `max_headers`: Most header fields accepted.
+/// - `budget`: Bytes the block may still use, including the terminating empty line; reduced by
+///   what is read.
+/// - `line`: Scratch buffer each line is read into.
+///
+/// **Returns:**
+/// - The parsed `Headers`.
+///
+/// **Errors / Failures:**
+/// - `LimitExceeded::Headers` if the block breaks either limit.
+/// - `UnexpectedEof` if the stream ends inside the header block.
+/// - `InvalidData` if a header line has no `:` separator or its name is not a token (which
+///   includes whitespace before the colon and obsolete line folding).
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let headers = read_headers_within(&mut reader, 100, &mut budget, &mut scratch)?;
+/// ```
+pub(crate) fn read_headers_within<R: BufRead>(
+    reader: &mut R,
+    max_headers: usize,
+    budget: &mut usize,
+    line: &mut Vec<u8>,
+) -> io::Result<Headers> {
+    let mut headers = Headers::new();
+    let mut count = 0;
+    loop {
+        let line = read_line_within(reader, budget, line)?
+            .ok_or_else(|| io::Error::new(io::ErrorKind::UnexpectedEof, "truncated header block"))?;
+        if line.is_empty() {
+            return Ok(headers);
+        }
+        count += 1;
+        if count > max_headers {
+            return Err(LimitExceeded::Headers.error());
+        }
+        let (name, value) = line
+            .split_once(':')
+            .filter(|(name, _)| is_token(name))
+            .ok_or_else(|| invalid("malformed header line"))?;
+        headers.append(name, value.trim());
+    }
+}
+
+
+/// # read_body_within
+///
+/// **Purpose:**
+/// Reads a message body framed by `Transfer-Encoding: chunked` or `Content-Length`, refusing it
+/// once it would exceed a size cap.
+///
+/// **Parameters:**
+/// - `reader`: Buffered reader positioned at the first body byte.
+/// - `headers`: Header block describing the body framing.
+/// - `limit`: Largest decoded body accepted.
+///
+/// **Returns:**
+/// - The decoded body bytes (empty when neither framing header is present).
+///
+/// **Errors / Failures:**
+/// - `LimitExceeded::Body` if a declared `Content-Length` is over `limit` (nothing is read) or
+///   a chunked body grows past it.
+/// - `InvalidData` if a chunk size is not a valid number, or `Content-Length` is not one or
+///   has conflicting values.
+/// - `UnexpectedEof` if the stream ends before the body is complete.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let body = read_body_within(&mut reader, &headers, 1024 * 1024)?;
+/// ```
+pub(crate) fn read_body_within<R: BufRead>(reader: &mut R, headers: &Headers, limit: usize) -> io::Result<Vec<u8>> {
+    if headers.has_token("Transfer-Encoding", "chunked") {
+        return read_chunked(reader, limit);
+    }
+
+    match content_length(headers)? {
+        Some(length) => {
+            if length > limit {
+                return Err(LimitExceeded::Body.error());
+            }
+            let mut body = vec![0; length];
+            reader.read_exact(&mut body)?;
+            Ok(body)
+        }
+        None => Ok(Vec::new()),
+    }
+}
+
+
+/// # check_framing
+///
+/// **Purpose:**
+/// Refuses request heads whose body length could be read differently by another HTTP hop,
+/// following RFC 9112 section 6: such requests are answered with `400` and the connection closed
+/// rather than guessed at, so the server can sit behind other proxies without request smuggling.
+///
+/// **Parameters:**
+/// - `version`: Request HTTP version.
+/// - `headers`: Request header block.
+///
+/// **Returns:**
+/// - `Ok(())` if the framing is unambiguous.
+///
+/// **Errors / Failures:**
+/// - `InvalidData` if both `Content-Length` and `Transfer-Encoding` are present, if
+///   `Transfer-Encoding` appears on an HTTP/1.0 request or does not end in a single `chunked`,
+///   or if `Content-Length` is malformed or given conflicting values.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// check_framing(&request.version, &request.headers)?;
+/// ```
+fn check_framing(version: &str, headers: &Headers) -> io::Result<()> {
+    let codings: Vec<&str> = headers
+        .get_all("Transfer-Encoding")
+        .into_iter()
+        .flat_map(|value| value.split(','))
+        .map(str::trim)
+        .filter(|coding| !coding.is_empty())
+        .collect();
+    if headers.contains("Transfer-Encoding") {
+        if headers.contains("Content-Length") {
+            return Err(invalid("both Content-Length and Transfer-Encoding present"));
+        }
+        if version == "HTTP/1.0" {
+            return Err(invalid("Transfer-Encoding on an HTTP/1.0 request"));
+        }
+        let chunked = codings.iter().filter(|coding| coding.eq_ignore_ascii_case("chunked")).count();
+        if chunked != 1 || !codings.last().is_some_and(|coding| coding.eq_ignore_ascii_case("chunked")) {
+            return Err(invalid("unsupported Transfer-Encoding"));
+        }
+    }
+    content_length(headers).map(|_| ())
+}
+
+
+/// # content_length
+///
+/// **Purpose:**
+/// Reads the declared body length. Repeated fields and comma-separated lists are accepted
+/// only when every value is the same.
+///
+/// **Parameters:**
+/// - `headers`: Header block of the message.
+///
+/// **Returns:**
+/// - `Some(length)`, or `None` when no `Content-Length` is present.
+///
+/// **Errors / Failures:**
+/// - `InvalidData` if a value is not a plain decimal number or the values disagree.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let length = content_length(&headers)?.unwrap_or(0);
+/// ```
+fn content_length(headers: &Headers) -> io::Result<Option<usize>> {
+    let mut length = None;
+    for value in headers.get_all("Content-Length").into_iter().flat_map(|value| value.split(',')) {
+        let value = value.trim();
+        if value.is_empty() || !value.bytes().all(|byte| byte.is_ascii_digit()) {
+            return Err(invalid("invalid Content-Length"));
+        }
+        let parsed: usize = value.parse().map_err(|_| invalid("invalid Content-Length"))?;
+        if length.is_some_and(|length| length != parsed) {
+            return Err(invalid("conflicting Content-Length values"));
+        }
+        length = Some(parsed);
+    }
+    Ok(length)
+}
+
+
+/// # read_chunked
+///
+/// **Purpose:**
+/// Decodes a `chunked` body, discarding chunk extensions and trailer fields.
+///
+/// **Parameters:**
+/// - `reader`: Buffered reader positioned at the first chunk-size line.
+/// - `limit`: Largest decoded body accepted.
+///
+/// **Returns:**
+/// - The concatenated chunk data.
+///
+/// **Errors / Failures:**
+/// - `InvalidData` if a chunk size is not valid hexadecimal or a chunk is not CRLF-terminated.
+/// - `LimitExceeded::Body` as soon as a chunk would take the body past `limit`.
+/// - `LimitExceeded::Headers` if the trailer fields exceed what is left of `limit`.
+/// - `UnexpectedEof` if the stream ends before the last chunk.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let body = read_chunked(&mut reader, usize::MAX)?;
+/// ```
+fn read_chunked<R: BufRead>(reader: &mut R, limit: usize) -> io::Result<Vec<u8>> {
+    let mut body = Vec::new();
+    let mut scratch = Vec::new();
+    loop {
+        let mut line_budget = MAX_CHUNK_LINE;
+        let line = read_line_within(reader, &mut line_budget, &mut scratch)
+            .map_err(|error| match LimitExceeded::of(&error) {
+                Some(_) => invalid("chunk size line too long"),
+                None => error,
+            })?
+            .ok_or_else(|| io::Error::new(io::ErrorKind::UnexpectedEof, "truncated chunked body"))?;
+        let size = line.split(';').next().unwrap_or("").trim();
+        if size.is_empty() || !size.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+            return Err(invalid("invalid chunk size"));
+        }
+        let size = usize::from_str_radix(size, 16).map_err(|_| invalid("invalid chunk size"))?;
+
+        if size == 0 {
+            let mut trailer_budget = limit - body.len();
+            read_headers_within(reader, usize::MAX, &mut trailer_budget, &mut scratch)?;
+            return Ok(body);
+        }
+        if size > limit - body.len() {
+            return Err(LimitExceeded::Body.error());
+        }
+
+        let start = body.len();
+        body.resize(start + size, 0);
+        reader.read_exact(&mut body[start..])?;
+
+        let mut crlf = [0; 2];
+        reader.read_exact(&mut crlf)?;
+        if &crlf != b"\r\n" {
+            return Err(invalid("chunk not terminated by CRLF"));
+        }
+    }
+}
+
+
+/// # read_line_within
+///
+/// **Purpose:**
+/// Reads a single CRLF- (or LF-) terminated line and strips the terminator, never buffering more
+/// than the remaining byte budget.
+///
+/// **Parameters:**
+/// - `reader`: Buffered reader to read from.
+/// - `budget`: Bytes still allowed, terminator included; reduced by what is read.
+/// - `line`: Buffer the line is read into, replacing its contents.
+///
+/// **Returns:**
+/// - `Some(line)`, borrowed from the buffer, on success, or `None` if the stream was already
+///   at EOF.
+///
+/// **Errors / Failures:**
+/// - `LimitExceeded::Headers` if the line does not end within the budget.
+/// - `InvalidData` if the line is not valid UTF-8.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let line = read_line_within(&mut reader, &mut budget, &mut scratch)?;
+/// ```
+pub(crate) fn read_line_within<'a, R: BufRead>(
+    reader: &mut R,
+    budget: &mut usize,
+    line: &'a mut Vec<u8>,
+) -> io::Result<Option<&'a str>> {
+    line.clear();
+    let allowed = u64::try_from(*budget).unwrap_or(u64::MAX).saturating_add(1);
+    let read = reader.take(allowed).read_until(b'\n', line)?;
+    if read > *budget {
+        return Err(LimitExceeded::Headers.error());
+    }
+    *budget -= read;
+    if read == 0 {
+        return Ok(None);
+    }
+    let text = std::str::from_utf8(line).map_err(|_| invalid("line is not valid UTF-8"))?;
+    Ok(Some(text.trim_end_matches(['\r', '\n'])))
+}
+
+
+/// # is_token
+///
+/// **Purpose:**
+/// Checks that text is an RFC 9110 token, as methods and header names must be.
+///
+/// **Parameters:**
+/// - `text`: Candidate token.
+///
+/// **Returns:**
+/// - `true` if `text` is non-empty and made only of token characters.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// assert!(is_token("X-Request-Id") && !is_token("Bad Name"));
+/// ```
+pub(crate) fn is_token(text: &str) -> bool {
+    !text.is_empty()
+        && text
+            .bytes()
+            .all(|byte| byte.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&byte))
+}
+
+
+/// # is_version
+///
+/// **Purpose:**
+/// Checks that text is a well-formed HTTP version (`HTTP/1.1`).
+///
+/// **Parameters:**
+/// - `text`: Candidate version.
+///
+/// **Returns:**
+/// - `true` for `HTTP/` followed by a digit, a dot, and a digit.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// assert!(is_version("HTTP/1.0") && !is_version("HTTP/one"));
+/// ```
+fn is_version(text: &str) -> bool {
+    match text.strip_prefix("HTTP/").map(str::as_bytes) {
+        Some([major, b'.', minor]) => major.is_ascii_digit() && minor.is_ascii_digit(),
+        _ => false,
+    }
+}
+
+
+/// # invalid
+///
+/// **Purpose:**
+/// Builds an `InvalidData` I/O error with the given message.
+///
+/// **Parameters:**
+/// - `message`: Description of what was malformed.
+///
+/// **Returns:**
+/// - An `io::Error` of kind `InvalidData`.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// return Err(invalid("malformed header line"));
+/// ```
+pub(crate) fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.to_string())
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn head(raw: &str) -> io::Result<Request> {
+        Request::read_head(&mut raw.as_bytes(), &Limits::default().head(), &mut Vec::new())
+    }
+
+    #[test]
+    fn http_1_requests_are_read() {
+        let request = head("GET / HTTP/1.1\r\nHost: a\r\n\r\n").unwrap();
+        assert_eq!((request.method.as_str(), request.target.as_str(), request.version.as_str()), ("GET", "/", "HTTP/1.1"));
+        assert!(head("GET / HTTP/1.0\r\n\r\n").is_ok());
+    }
+
+    #[test]
+    fn other_major_versions_are_unsupported() {
+        for version in ["HTTP/2.0", "HTTP/0.9", "HTTP/3.0"] {
+            let error = head(&format!("GET / {version}\r\n\r\n")).unwrap_err();
+            assert_eq!(RequestError::of(&error), Some(RequestError::UnsupportedVersion), "{version}");
+            assert_eq!(RequestError::UnsupportedVersion.response().status, 505);
+        }
+    }
+
+    fn read(raw: &str) -> io::Result<Request> {
+        Request::read_from(&mut raw.as_bytes())
+    }
+
+    fn limit(result: io::Result<Request>) -> Option<LimitExceeded> {
+        LimitExceeded::of(&result.err()?)
+    }
+
+    #[test]
+    fn content_length_bodies_are_read_exactly() {
+        let mut raw = "POST /upload HTTP/1.1\r\nContent-Length: 5\r\n\r\nhelloGET / HTTP/1.1\r\n\r\n".as_bytes();
+        assert_eq!(Request::read_from(&mut raw).unwrap().body, b"hello");
+        assert_eq!(Request::read_from(&mut raw).unwrap().method, "GET");
+    }
+
+    #[test]
+    fn ambiguous_framing_is_refused() {
+        for raw in [
+            "POST / HTTP/1.1\r\nContent-Length: 3\r\nTransfer-Encoding: chunked\r\n\r\n",
+            "POST / HTTP/1.0\r\nTransfer-Encoding: chunked\r\n\r\n",
+            "POST / HTTP/1.1\r\nTransfer-Encoding: gzip\r\n\r\n",
+            "POST / HTTP/1.1\r\nTransfer-Encoding: chunked, chunked\r\n\r\n",
+            "POST / HTTP/1.1\r\nTransfer-Encoding: chunked, gzip\r\n\r\n",
+            "POST / HTTP/1.1\r\nContent-Length: 3\r\nContent-Length: 4\r\n\r\nabcd",
+            "POST / HTTP/1.1\r\nContent-Length: +3\r\n\r\nabc",
+            "POST / HTTP/1.1\r\nContent-Length: \r\n\r\n",
+        ] {
+            assert_eq!(read(raw).unwrap_err().kind(), io::ErrorKind::InvalidData, "{raw:?}");
+        }
+    }
+
+    #[test]
+    fn repeated_equal_content_lengths_are_accepted() {
+        let request = read("POST / HTTP/1.1\r\nContent-Length: 3, 3\r\n\r\nabc").unwrap();
+        assert_eq!(request.body, b"abc");
+    }
+
+    #[test]
+    fn malformed_heads_are_bad_requests() {
+        for raw in [
+            "GET /\r\n\r\n",
+            "GET  / HTTP/1.1\r\n\r\n",
+            "G(T / HTTP/1.1\r\n\r\n",
+            "GET /\x01 HTTP/1.1\r\n\r\n",
+            "GET / HTTP/1.1\r\nNo colon\r\n\r\n",
+            "GET / HTTP/1.1\r\nBad name: x\r\n\r\n",
+        ] {
+            assert_eq!(read(raw).unwrap_err().kind(), io::ErrorKind::InvalidData, "{raw:?}");
+        }
+        assert_eq!(read("").unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+        assert_eq!(read("GET / HTTP/1.1\r\nHost: a\r\n").unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+    }
+
+    const SMALL: HeadLimits = HeadLimits { max_target: 8, max_headers: 2, max_bytes: 256 };
+
+    fn within(raw: &str) -> io::Result<Request> {
+        Request::read_within(&mut raw.as_bytes(), &SMALL, 16)
+    }
+
+    #[test]
+    fn long_targets_are_refused_with_414() {
+        assert!(within("GET /1234567 HTTP/1.1\r\n\r\n").is_ok());
+        assert_eq!(limit(within("GET /12345678 HTTP/1.1\r\n\r\n")), Some(LimitExceeded::Target));
+        assert_eq!(limit(within(&format!("GET /{} HTTP/1.1\r\n\r\n", "x".repeat(300)))), Some(LimitExceeded::Target));
+        assert_eq!(LimitExceeded::Target.response().status, 414);
+    }
+
+    #[test]
+    fn too_many_or_too_large_headers_are_refused_with_431() {
+        assert!(within("GET / HTTP/1.1\r\nA: 1\r\nB: 2\r\n\r\n").is_ok());
+        assert_eq!(limit(within("GET / HTTP/1.1\r\nA: 1\r\nB: 2\r\nC: 3\r\n\r\n")), Some(LimitExceeded::Headers));
+        let long = format!("GET / HTTP/1.1\r\nA: {}\r\n\r\n", "x".repeat(300));
+        assert_eq!(limit(within(&long)), Some(LimitExceeded::Headers));
+        assert_eq!(LimitExceeded::Headers.response().status, 431);
+    }
+
+    #[test]
+    fn bodies_over_the_cap_are_refused_with_413() {
+        assert_eq!(within("POST / HTTP/1.1\r\nContent-Length: 16\r\n\r\n0123456789abcdef").unwrap().body.len(), 16);
+        assert_eq!(limit(within("POST / HTTP/1.1\r\nContent-Length: 17\r\n\r\n")), Some(LimitExceeded::Body));
+        assert_eq!(LimitExceeded::Body.response().status, 413);
+    }
+
+    #[test]
+    fn chunked_bodies_are_decoded() {
+        let raw = "POST / HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n\
+                   5;name=value\r\nhello\r\n6\r\n world\r\n0\r\nTrailer: yes\r\n\r\nGET / HTTP/1.1\r\n\r\n";
+        let mut reader = raw.as_bytes();
+        assert_eq!(Request::read_from(&mut reader).unwrap().body, b"hello world");
+        assert_eq!(Request::read_from(&mut reader).unwrap().method, "GET");
+    }
+
+    #[test]
+    fn malformed_chunks_are_refused() {
+        for body in ["x\r\nhello\r\n0\r\n\r\n", "\r\n", "5\r\nhelloXX0\r\n\r\n", "-1\r\n\r\n"] {
+            let raw = format!("POST / HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n{body}");
+            assert_eq!(read(&raw).unwrap_err().kind(), io::ErrorKind::InvalidData, "{body:?}");
+        }
+        let long = format!("POST / HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n1;{}\r\n", "x".repeat(MAX_CHUNK_LINE));
+        assert_eq!(read(&long).unwrap_err().kind(), io::ErrorKind::InvalidData);
+        let truncated = "POST / HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n5\r\nhel";
+        assert_eq!(read(truncated).unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+    }
+
+    #[test]
+    fn chunked_bodies_respect_the_body_cap() {
+        let raw = "POST / HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n8\r\n12345678\r\n9\r\n123456789\r\n0\r\n\r\n";
+        assert_eq!(limit(within(raw)), Some(LimitExceeded::Body));
+    }
+
+    #[test]
+    fn malformed_versions_are_bad_requests() {
+        for version in ["HTTP/one", "HTTP/1", "HTTP/1.1.1", "http/1.1"] {
+            let error = head(&format!("GET / {version}\r\n\r\n")).unwrap_err();
+            assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+            assert_eq!(RequestError::of(&error), None, "{version}");
+        }
+    }
+}
@@ -0,0 +1,1583 @@
+
+// ============================================================
+//  DAEGONICA SOFTWARE — response.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: Response
+//!
+//! **Purpose:**
+//! Represents an HTTP response that can be built by handlers, read from an upstream, and written to a client.
+//!
+//! **Context:**
+//! - Used by the connection handler, the proxy, and the response cache.
+//!
+//! **Responsibilities:**
+//! - Holds the status, headers, and body of a response: buffered, memory-mapped from a file, or
+//!   streamed from a file in fixed-size chunks as it is written.
+//! - Parses upstream responses and serializes responses with correct `Content-Length` framing and a `Date` header.
+//! - Writes the head and an in-memory body with one vectored write, without joining them first.
+//! - Sends streamed file bodies to clients with `sendfile(2)` where the platform supports it.
+//! - Sends declared trailer fields after a `chunked` body, computed from the bytes as they are
+//!   written (e.g. a `Content-Digest` checksum of a streamed download).
+//! - Builds file responses for handlers (`send_file`, `download`) with type, modification date, and disposition headers.
+//! - Does NOT stream generated bodies or negotiate content encodings.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+use std::{
+    fmt,
+    fs,
+    io::{self, BufRead, IoSlice, Read, Seek, SeekFrom, Write},
+    net::{Shutdown, TcpStream},
+    path::{Component, Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+use crate::{
+    base64,
+    date,
+    extract::encode_component,
+    hash::Sha256,
+    headers::Headers,
+    json_value::JsonValue,
+    log::{self, LogLevel},
+    mime,
+    mmap::MappedFile,
+    limits::LimitExceeded,
+    request::{invalid, read_body_within, read_headers_within, read_line_within},
+    sandbox::Sandbox,
+    template::{Template, TemplateEngine},
+    timeouts::ClientStream,
+};
+
+
+/// # Response
+///
+/// **Summary:**
+/// A complete HTTP response with a buffered or file-backed body.
+///
+/// **Fields:**
+/// - `status`: Numeric status code (e.g. `200`).
+/// - `reason`: Reason phrase sent after the status code.
+/// - `headers`: Response header fields (framing headers are computed on write).
+/// - `body`: Response body bytes; empty while the body comes from a file.
+/// - `file`: File sent in place of `body`, if any; boxed to keep responses small.
+/// - `trailers`: Trailer fields sent after a `chunked` body, computed as it is written; `None`
+///   until one is declared.
+///
+/// **Usage Example:**
+/// ```rust
+/// # use server::response::Response;
+/// # let mut stream = Vec::new();
+/// let response = Response::new(200, "OK").with_body("hello");
+/// response.write_to(&mut stream)?;
+/// # assert!(stream.ends_with(b"\r\n\r\nhello"));
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct Response {
+    pub status: u16,
+    pub reason: String,
+    pub headers: Headers,
+    pub body: Vec<u8>,
+    file: Option<Box<FileBody>>,
+    trailers: Option<Arc<Vec<Trailer>>>,
+}
+
+
+/// # TrailerDigest
+///
+/// **Summary:**
+/// Computes a trailer field's value from the response body as it is written, such as a
+/// checksum of a download that is never held in memory whole.
+///
+/// **Usage Example:**
+/// ```rust
+/// # use server::response::{Response, TrailerDigest};
+/// struct ByteCount(u64);
+/// impl TrailerDigest for ByteCount {
+///     fn update(&mut self, chunk: &[u8]) { self.0 += chunk.len() as u64; }
+///     fn finish(self: Box<Self>) -> String { self.0.to_string() }
+/// }
+/// let response = Response::send_file("exports/7.csv").with_trailer("X-Byte-Count", || ByteCount(0));
+/// ```
+pub trait TrailerDigest {
+
+    /// # update
+    ///
+    /// **Purpose:**
+    /// Takes in the next piece of the body.
+    ///
+    /// **Parameters:**
+    /// - `chunk`: Body bytes following everything seen so far.
+    ///
+    /// **Returns:**
+    /// None.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::response::TrailerDigest;
+    /// # struct ByteCount(u64);
+    /// # impl TrailerDigest for ByteCount {
+    /// #     fn update(&mut self, chunk: &[u8]) { self.0 += chunk.len() as u64; }
+    /// #     fn finish(self: Box<Self>) -> String { self.0.to_string() }
+    /// # }
+    /// # let mut digest = ByteCount(0);
+    /// digest.update(b"hello");
+    /// # assert_eq!(digest.0, 5);
+    /// ```
+    fn update(&mut self, chunk: &[u8]);
+
+    /// # finish
+    ///
+    /// **Purpose:**
+    /// Produces the trailer value once the whole body has been seen.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - The field value; line breaks are dropped when it is written.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::response::TrailerDigest;
+    /// # struct ByteCount(u64);
+    /// # impl TrailerDigest for ByteCount {
+    /// #     fn update(&mut self, chunk: &[u8]) { self.0 += chunk.len() as u64; }
+    /// #     fn finish(self: Box<Self>) -> String { self.0.to_string() }
+    /// # }
+    /// # let digest: Box<dyn TrailerDigest> = Box::new(ByteCount(5));
+    /// let value = digest.finish();
+    /// # assert_eq!(value, "5");
+    /// ```
+    fn finish(self: Box<Self>) -> String;
+}
+
+
+/// # Trailer
+///
+/// **Summary:**
+/// Trailer field declared on a response.
+///
+/// **Fields:**
+/// - `name`: Field name, announced in the `Trailer` header.
+/// - `start`: Creates a fresh digest each time the response is written.
+#[derive(Clone)]
+struct Trailer {
+    name: String,
+    start: Arc<dyn Fn() -> Box<dyn TrailerDigest> + Send + Sync>,
+}
+
+
+/// # ContentDigest
+///
+/// **Summary:**
+/// SHA-256 `Content-Digest` (RFC 9530) of the body, for `Response::with_content_digest`.
+///
+/// **Fields:**
+/// - `hasher`: Digest of the body seen so far.
+#[derive(Debug)]
+struct ContentDigest {
+    hasher: Sha256,
+}
+
+
+/// # Chunked
+///
+/// **Summary:**
+/// Writer that frames everything written through it as `chunked` transfer coding and feeds
+/// the bytes to the response's trailer digests.
+///
+/// **Fields:**
+/// - `writer`: Destination of the framed body.
+/// - `digests`: Trailer names with their digests in progress.
+struct Chunked<'a, W: Write> {
+    writer: &'a mut W,
+    digests: Vec<(&'a str, Box<dyn TrailerDigest>)>,
+}
+
+
+/// # FileBody
+///
+/// **Summary:**
+/// Response body sent from a file instead of from `Response::body`.
+///
+/// **Variants:**
+/// - `Mapped`: Memory-mapped contents, shared by clones, and the part of them sent: all of
+///   it unless a byte range was selected.
+/// - `Streamed`: File read in `STREAM_CHUNK` pieces while the response is written, from
+///   `offset` for `len` bytes: the length it had when the response was built, unless a byte
+///   range was selected. With a `sandbox`, `path` is relative to it and opened through it.
+#[derive(Debug, Clone)]
+enum FileBody {
+    Mapped { map: Arc<MappedFile>, start: usize, len: usize },
+    Streamed { path: PathBuf, offset: u64, len: u64, sandbox: Option<Arc<Sandbox>> },
+}
+
+
+/// # STREAM_CHUNK
+///
+/// **Summary:**
+/// Size of each read when a file body is streamed.
+const STREAM_CHUNK: usize = 64 * 1024;
+
+
+impl Response {
+
+    /// # new
+    ///
+    /// **Purpose:**
+    /// Creates a response with the given status and an empty body.
+    ///
+    /// **Parameters:**
+    /// - `status`: Numeric status code.
+    /// - `reason`: Reason phrase.
+    ///
+    /// **Returns:**
+    /// - A new `Response` instance.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::response::Response;
+    /// let response = Response::new(404, "Not Found");
+    /// ```
+    pub fn new(status: u16, reason: impl Into<String>) -> Response {
+        Response {
+            status,
+            reason: reason.into(),
+            headers: Headers::new(),
+            body: Vec::new(),
+            file: None,
+            trailers: None,
+        }
+    }
+
+    /// # redirect
+    ///
+    /// **Purpose:**
+    /// Creates a redirect response pointing at `location`.
+    ///
+    /// **Parameters:**
+    /// - `status`: Redirect status (`301`, `302`, `303`, `307`, or `308`).
+    /// - `location`: Value of the `Location` header.
+    ///
+    /// **Returns:**
+    /// - A redirect `Response` with an empty body.
+    ///
+    /// **Errors / Failures:**
+    /// None. Other status codes get the generic reason `Redirect`.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::response::Response;
+    /// let response = Response::redirect(301, "https://example.com/");
+    /// # assert_eq!(response.headers.get("Location"), Some("https://example.com/"));
+    /// ```
+    pub fn redirect(status: u16, location: impl Into<String>) -> Response {
+        let reason = match status {
+            301 => "Moved Permanently",
+            302 => "Found",
+            303 => "See Other",
+            307 => "Temporary Redirect",
+            308 => "Permanent Redirect",
+            _ => "Redirect",
+        };
+        Response::new(status, reason).with_header("Location", location)
+    }
+
+    /// # render
+    ///
+    /// **Purpose:**
+    /// Creates a `200 OK` HTML page from a template.
+    ///
+    /// **Parameters:**
+    /// - `template`: Parsed template.
+    /// - `context`: Values the template refers to.
+    ///
+    /// **Returns:**
+    /// - A `Response` with the rendered body and an HTML `Content-Type`.
+    ///
+    /// **Errors / Failures:**
+    /// None. Missing values render as nothing.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{json_value::JsonValue, response::Response, template::Template};
+    /// # let page = Template::parse("<h1>{{ title }}</h1>").unwrap();
+    /// let response = Response::render(&page, &JsonValue::Object(vec![("title".into(), "Home".into())]));
+    /// # assert_eq!(response.body, b"<h1>Home</h1>");
+    /// ```
+    pub fn render(template: &Template, context: &JsonValue) -> Response {
+        Response::new(200, "OK")
+            .with_header("Content-Type", "text/html; charset=utf-8")
+            .with_body(template.render(context))
+    }
+
+    /// # render_template
+    ///
+    /// **Purpose:**
+    /// Creates a `200 OK` HTML page by rendering a named template with any `TemplateEngine`.
+    ///
+    /// **Parameters:**
+    /// - `engine`: Engine holding the template.
+    /// - `name`: Template name.
+    /// - `context`: Values the template refers to.
+    ///
+    /// **Returns:**
+    /// - A `Response` with the rendered body, or `500 Internal Server Error` if rendering fails.
+    ///
+    /// **Errors / Failures:**
+    /// None. Rendering errors are logged, not shown to the client.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{json_value::JsonValue, response::Response, template::{Template, Templates}};
+    /// # let templates = Templates::new().add("index.html", Template::parse("<p>{{ user }}</p>").unwrap());
+    /// # let context = JsonValue::Object(vec![("user".into(), "ana".into())]);
+    /// let response = Response::render_template(&templates, "index.html", &context);
+    /// # assert_eq!(response.body, b"<p>ana</p>");
+    /// ```
+    pub fn render_template(engine: &dyn TemplateEngine, name: &str, context: &JsonValue) -> Response {
+        match engine.render(name, context) {
+            Ok(body) => Response::new(200, "OK")
+                .with_header("Content-Type", "text/html; charset=utf-8")
+                .with_body(body),
+            Err(error) => {
+                log::emit(LogLevel::Error, format_args!("Rendering template {name} failed: {error}"));
+                Response::new(500, "Internal Server Error")
+                    .with_header("Content-Type", "text/plain; charset=utf-8")
+                    .with_body("template error\n")
+            }
+        }
+    }
+
+    /// # send_file
+    ///
+    /// **Purpose:**
+    /// Creates a `200 OK` response carrying a file, shown inline by the browser.
+    ///
+    /// **Parameters:**
+    /// - `path`: File to send. Paths with `..` components are refused.
+    ///
+    /// **Returns:**
+    /// - A `Response` with the file's contents, `Content-Type` from its extension,
+    ///   `Last-Modified`, and an `inline` `Content-Disposition`, or `404 Not Found` if the path is
+    ///   refused, missing, a directory, or unreadable.
+    ///
+    /// **Errors / Failures:**
+    /// None. Failures become the `404` response.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{request::Request, response::Response};
+    /// # fn report(request: &Request) -> Option<Response> {
+    /// let response = Response::send_file(format!("reports/{}.pdf", request.param("id")?));
+    /// # Some(response)
+    /// # }
+    /// ```
+    pub fn send_file(path: impl AsRef<Path>) -> Response {
+        let path = path.as_ref();
+        let name = path.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default();
+        file_response(path, "inline", &name)
+    }
+
+    /// # download
+    ///
+    /// **Purpose:**
+    /// Creates a `200 OK` response that makes the browser save a file under a chosen name.
+    ///
+    /// **Parameters:**
+    /// - `path`: File to send. Paths with `..` components are refused.
+    /// - `filename`: Name offered to the user; non-ASCII names are sent with an RFC 5987 fallback.
+    ///
+    /// **Returns:**
+    /// - A `Response` like `send_file`'s but with an `attachment` `Content-Disposition`, or
+    ///   `404 Not Found`.
+    ///
+    /// **Errors / Failures:**
+    /// None. Failures become the `404` response.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::response::Response;
+    /// let response = Response::download("exports/7.csv", "März export.csv");
+    /// ```
+    pub fn download(path: impl AsRef<Path>, filename: &str) -> Response {
+        file_response(path.as_ref(), "attachment", filename)
+    }
+
+    /// # with_header
+    ///
+    /// **Purpose:**
+    /// Sets a header on the response, replacing any existing value.
+    ///
+    /// **Parameters:**
+    /// - `name`: Header name.
+    /// - `value`: Header value.
+    ///
+    /// **Returns:**
+    /// - The updated `Response`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::response::Response;
+    /// let response = Response::new(200, "OK").with_header("Cache-Control", "max-age=60");
+    /// ```
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Response {
+        self.headers.set(name, value);
+        self
+    }
+
+    /// # with_body
+    ///
+    /// **Purpose:**
+    /// Replaces the response body.
+    ///
+    /// **Parameters:**
+    /// - `body`: New body bytes.
+    ///
+    /// **Returns:**
+    /// - The updated `Response`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::response::Response;
+    /// let response = Response::new(200, "OK").with_body("<h1>Hi</h1>");
+    /// ```
+    pub fn with_body(mut self, body: impl Into<Vec<u8>>) -> Response {
+        self.body = body.into();
+        self.file = None;
+        self
+    }
+
+    /// # with_mapped_body
+    ///
+    /// **Purpose:**
+    /// Replaces the response body with a memory-mapped file, which is written straight from
+    /// the mapping.
+    ///
+    /// **Parameters:**
+    /// - `map`: Mapped file contents.
+    ///
+    /// **Returns:**
+    /// - The updated `Response`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let response = Response::new(200, "OK").with_mapped_body(MappedFile::map(&fs::File::open(path)?)?);
+    /// ```
+    pub(crate) fn with_mapped_body(mut self, map: MappedFile) -> Response {
+        self.body = Vec::new();
+        let len = map.as_slice().len();
+        self.file = Some(Box::new(FileBody::Mapped { map: Arc::new(map), start: 0, len }));
+        self
+    }
+
+    /// # with_streamed_body
+    ///
+    /// **Purpose:**
+    /// Replaces the response body with a file that is opened and read in fixed-size chunks
+    /// only while the response is written, so it is never held in memory whole.
+    ///
+    /// **Parameters:**
+    /// - `path`: File to send.
+    /// - `len`: Its current length, sent as `Content-Length`.
+    ///
+    /// **Returns:**
+    /// - The updated `Response`.
+    ///
+    /// **Errors / Failures:**
+    /// None. A file that cannot be opened, or has shrunk, fails the write instead.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let response = Response::new(200, "OK").with_streamed_body(path, fs::metadata(path)?.len());
+    /// ```
+    pub(crate) fn with_streamed_body(mut self, path: &Path, len: u64) -> Response {
+        self.body = Vec::new();
+        self.file = Some(Box::new(FileBody::Streamed { path: path.to_path_buf(), offset: 0, len, sandbox: None }));
+        self
+    }
+
+    /// # with_sandboxed_body
+    ///
+    /// **Purpose:**
+    /// Like `with_streamed_body`, but opens the file through a sandbox each time the response
+    /// is written, so the file cannot be swapped for one outside the sandbox's root.
+    ///
+    /// **Parameters:**
+    /// - `sandbox`: Sandbox the file lives in.
+    /// - `relative`: File path relative to the sandbox root.
+    /// - `len`: Its current length, sent as `Content-Length`.
+    ///
+    /// **Returns:**
+    /// - The updated `Response`.
+    ///
+    /// **Errors / Failures:**
+    /// None. A file that cannot be opened beneath the root, or has shrunk, fails the write
+    /// instead.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let response = Response::new(200, "OK").with_sandboxed_body(Arc::clone(&sandbox), Path::new("video.mp4"), len);
+    /// ```
+    pub(crate) fn with_sandboxed_body(mut self, sandbox: Arc<Sandbox>, relative: &Path, len: u64) -> Response {
+        self.body = Vec::new();
+        self.file = Some(Box::new(FileBody::Streamed { path: relative.to_path_buf(), offset: 0, len, sandbox: Some(sandbox) }));
+        self
+    }
+
+    /// # with_trailer
+    ///
+    /// **Purpose:**
+    /// Declares a trailer field whose value is computed from the body as it is written. A
+    /// response with trailers is sent with `chunked` framing and a `Trailer` header instead of
+    /// `Content-Length`, and its file body is read through userspace rather than with
+    /// `sendfile(2)`. HTTP/1.0 clients get the body without the trailers.
+    ///
+    /// **Parameters:**
+    /// - `name`: Field name (e.g. `X-Checksum`).
+    /// - `start`: Creates the digest; called once each time the response is written.
+    ///
+    /// **Returns:**
+    /// - The updated `Response`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::response::{Response, TrailerDigest};
+    /// # #[derive(Default)]
+    /// # struct Crc32(u32);
+    /// # impl TrailerDigest for Crc32 {
+    /// #     fn update(&mut self, chunk: &[u8]) { self.0 = chunk.iter().fold(self.0, |crc, &byte| crc.rotate_left(5) ^ u32::from(byte)); }
+    /// #     fn finish(self: Box<Self>) -> String { format!("{:08x}", self.0) }
+    /// # }
+    /// let response = Response::download("exports/7.csv", "export.csv").with_trailer("X-Checksum", Crc32::default);
+    /// ```
+    pub fn with_trailer<D, F>(mut self, name: impl Into<String>, start: F) -> Response
+    where
+        D: TrailerDigest + 'static,
+        F: Fn() -> D + Send + Sync + 'static,
+    {
+        Arc::make_mut(self.trailers.get_or_insert_default()).push(Trailer {
+            name: name.into(),
+            start: Arc::new(move || Box::new(start())),
+        });
+        self
+    }
+
+    /// # with_content_digest
+    ///
+    /// **Purpose:**
+    /// Declares a `Content-Digest` trailer carrying the SHA-256 of the body, so clients can
+    /// verify a download that was checksummed on the fly.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - The updated `Response`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::response::Response;
+    /// let response = Response::send_file("releases/app.tar.gz").with_content_digest();
+    /// ```
+    pub fn with_content_digest(self) -> Response {
+        self.with_trailer("Content-Digest", || ContentDigest { hasher: Sha256::new() })
+    }
+
+    /// # remove_trailers
+    ///
+    /// **Purpose:**
+    /// Drops every declared trailer, so the response goes back to `Content-Length` framing
+    /// (for clients that cannot receive `chunked` bodies).
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// None.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// if request.is_http10() { response.remove_trailers(); }
+    /// ```
+    pub(crate) fn remove_trailers(&mut self) {
+        self.trailers = None;
+    }
+
+    /// # trailers
+    ///
+    /// **Purpose:**
+    /// Lists the declared trailer fields.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - The trailers in declaration order, empty if none was declared.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let names: Vec<&str> = self.trailers().iter().map(|trailer| trailer.name.as_str()).collect();
+    /// ```
+    fn trailers(&self) -> &[Trailer] {
+        self.trailers.as_deref().map_or(&[], Vec::as_slice)
+    }
+
+    /// # body_len
+    ///
+    /// **Purpose:**
+    /// Returns the length of the body as it will be written, whether buffered or from a file.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - The body length in bytes.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::response::Response;
+    /// # let response = Response::new(200, "OK").with_body("hello");
+    /// let bytes = response.body_len();
+    /// # assert_eq!(bytes, 5);
+    /// ```
+    pub fn body_len(&self) -> usize {
+        match self.file.as_deref() {
+            None => self.body.len(),
+            Some(FileBody::Mapped { len, .. }) => *len,
+            Some(FileBody::Streamed { len, .. }) => *len as usize,
+        }
+    }
+
+    /// # slice
+    ///
+    /// **Purpose:**
+    /// Narrows the body to a byte range, keeping file bodies on their file so a range of a
+    /// large file is still sent without loading it.
+    ///
+    /// **Parameters:**
+    /// - `start`: Offset of the first byte kept.
+    /// - `len`: Number of bytes kept; `start + len` must not exceed `body_len`.
+    ///
+    /// **Returns:**
+    /// - The response with only that range as its body.
+    ///
+    /// **Errors / Failures:**
+    /// - Panics if the range lies outside the body.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let partial = response.slice(100, 50);
+    /// ```
+    pub(crate) fn slice(mut self, start: u64, len: u64) -> Response {
+        assert!(start + len <= self.body_len() as u64, "range outside the body");
+        match self.file.as_deref_mut() {
+            None => self.body = self.body[start as usize..(start + len) as usize].to_vec(),
+            Some(FileBody::Mapped { start: first, len: kept, .. }) => {
+                *first += start as usize;
+                *kept = len as usize;
+            }
+            Some(FileBody::Streamed { offset, len: kept, .. }) => {
+                *offset += start;
+                *kept = len;
+            }
+        }
+        self
+    }
+
+    /// # body_range
+    ///
+    /// **Purpose:**
+    /// Copies a byte range of the body, reading it from the file for streamed bodies.
+    ///
+    /// **Parameters:**
+    /// - `start`: Offset of the first byte.
+    /// - `len`: Number of bytes; `start + len` must not exceed `body_len`.
+    ///
+    /// **Returns:**
+    /// - The bytes of the range.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns an I/O error if a streamed file cannot be read, or has shrunk.
+    /// - Panics if the range lies outside a buffered or mapped body.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let part = response.body_range(0, 499)?;
+    /// ```
+    pub(crate) fn body_range(&self, start: u64, len: u64) -> io::Result<Vec<u8>> {
+        let (start, len) = (start as usize, len as usize);
+        match self.file.as_deref() {
+            None => Ok(self.body[start..start + len].to_vec()),
+            Some(FileBody::Mapped { map, start: first, .. }) => Ok(map.as_slice()[first + start..first + start + len].to_vec()),
+            Some(FileBody::Streamed { path, offset, sandbox, .. }) => read_streamed(path, sandbox.as_deref(), offset + start as u64, len as u64),
+        }
+    }
+
+    /// # body_mut
+    ///
+    /// **Purpose:**
+    /// Borrows the body for editing, first loading a file body into the buffer.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - The buffered body.
+    ///
+    /// **Errors / Failures:**
+    /// None. A streamed file that can no longer be read is logged and leaves the body empty.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::response::Response;
+    /// # let mut response = Response::new(200, "OK").with_body("hello");
+    /// response.body_mut().extend_from_slice(b"\n");
+    /// # assert_eq!(response.body, b"hello\n");
+    /// ```
+    pub fn body_mut(&mut self) -> &mut Vec<u8> {
+        match self.file.take().map(|file| *file) {
+            None => {}
+            Some(FileBody::Mapped { map, start, len }) => self.body = map.as_slice()[start..start + len].to_vec(),
+            Some(FileBody::Streamed { path, offset, len, sandbox }) => match read_streamed(&path, sandbox.as_deref(), offset, len) {
+                Ok(contents) => self.body = contents,
+                Err(err) => log::emit(LogLevel::Warn, format_args!("cannot read {}: {err}", path.display())),
+            },
+        }
+        &mut self.body
+    }
+
+    /// # read_from
+    ///
+    /// **Purpose:**
+    /// Reads a complete response to a `GET` request, skipping interim `1xx` responses.
+    /// Bodies are decoded from `chunked` or `Content-Length` framing, or read until EOF otherwise.
+    /// The whole exchange, heads included, must fit in `limit` bytes.
+    ///
+    /// **Parameters:**
+    /// - `reader`: Buffered reader over the upstream connection.
+    /// - `limit`: Most bytes of status lines, headers, and decoded body accepted.
+    ///
+    /// **Returns:**
+    /// - The parsed `Response` with a decoded body.
+    ///
+    /// **Errors / Failures:**
+    /// - `UnexpectedEof` if the connection closes before a status line arrives.
+    /// - `InvalidData` if the status line, a header, or the body framing is malformed.
+    /// - `LimitExceeded::Headers` or `LimitExceeded::Body` as soon as the response outgrows `limit`;
+    ///   a declared `Content-Length` over what is left is refused before any of the body is read.
+    ///
+    /// **Examples:**
+    /// ```rust,no_run
+    /// # use server::response::Response;
+    /// # use std::{io::BufReader, net::TcpStream};
+    /// # let upstream = TcpStream::connect("127.0.0.1:3000")?;
+    /// let response = Response::read_from(&mut BufReader::new(&upstream), 10 * 1024 * 1024)?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn read_from<R: BufRead>(reader: &mut R, limit: usize) -> io::Result<Response> {
+        let mut budget = limit;
+        let mut scratch = Vec::new();
+        loop {
+            let line = read_line_within(reader, &mut budget, &mut scratch)?
+                .ok_or_else(|| io::Error::new(io::ErrorKind::UnexpectedEof, "connection closed"))?;
+
+            let mut parts = line.splitn(3, ' ');
+            let status = match (parts.next(), parts.next()) {
+                (Some(version), Some(status)) if version.starts_with("HTTP/") => status
+                    .parse::<u16>()
+                    .map_err(|_| invalid("invalid status code"))?,
+                _ => return Err(invalid("malformed status line")),
+            };
+            let reason = parts.next().unwrap_or("").to_string();
+            let headers = read_headers_within(reader, usize::MAX, &mut budget, &mut scratch)?;
+
+            if (100..200).contains(&status) {
+                continue;
+            }
+
+            let body = if status == 204 || status == 304 {
+                Vec::new()
+            } else if headers.has_token("Transfer-Encoding", "chunked") || headers.contains("Content-Length") {
+                read_body_within(reader, &headers, budget)?
+            } else {
+                let mut body = Vec::new();
+                reader.take(u64::try_from(budget).unwrap_or(u64::MAX).saturating_add(1)).read_to_end(&mut body)?;
+                if body.len() > budget {
+                    return Err(LimitExceeded::Body.error());
+                }
+                body
+            };
+
+            return Ok(Response {
+                status,
+                reason,
+                headers,
+                body,
+                file: None,
+                trailers: None,
+            });
+        }
+    }
+
+    /// # write_to
+    ///
+    /// **Purpose:**
+    /// Serializes the status line, headers, `Date` (if missing), a computed `Content-Length`, and the body.
+    /// `204 No Content` and `304 Not Modified` responses are written without `Content-Length` or body.
+    ///
+    /// **Parameters:**
+    /// - `writer`: Destination for the serialized response.
+    ///
+    /// **Returns:**
+    /// - `Ok(())` once the response is written.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns any I/O error raised by `writer`.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::response::Response;
+    /// # let response = Response::new(204, "No Content");
+    /// # let mut stream = Vec::new();
+    /// response.write_to(&mut stream)?;
+    /// # assert!(stream.starts_with(b"HTTP/1.1 204 No Content\r\n"));
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        self.write_with(writer, &mut Vec::new())
+    }
+
+    /// # write_with
+    ///
+    /// **Purpose:**
+    /// Shared body of `write_to` and `send_with`, serializing the head into a caller-owned
+    /// buffer.
+    ///
+    /// **Parameters:**
+    /// - `writer`: Destination for the serialized response.
+    /// - `head`: Scratch buffer for the head; its contents are replaced.
+    ///
+    /// **Returns:**
+    /// - `Ok(())` once the response is written.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns any I/O error raised by `writer` or while reading a streamed file.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// self.write_with(writer, &mut scratch)?;
+    /// ```
+    fn write_with<W: Write>(&self, writer: &mut W, head: &mut Vec<u8>) -> io::Result<()> {
+        self.head_into(head)?;
+        if matches!(self.status, 204 | 304) {
+            return writer.write_all(head);
+        }
+        if self.trailers.is_some() {
+            return self.write_chunked(writer, head);
+        }
+        let body = match self.file.as_deref() {
+            None => &self.body[..],
+            Some(FileBody::Mapped { map, start, len }) => &map.as_slice()[*start..start + len],
+            Some(FileBody::Streamed { path, offset, len, sandbox }) => {
+                let mut file = open_at(path, sandbox.as_deref(), *offset)?;
+                writer.write_all(head)?;
+                return copy_file(&mut file, writer, *len, path);
+            }
+        };
+        write_all_vectored(writer, &mut [IoSlice::new(head), IoSlice::new(body)])
+    }
+
+    /// # send
+    ///
+    /// **Purpose:**
+    /// Writes the response to a client exactly as `write_to` would, but sends a streamed file
+    /// body with `sendfile(2)` where supported, so its bytes never pass through userspace.
+    ///
+    /// **Parameters:**
+    /// - `client`: Client connection, with its time limits.
+    ///
+    /// **Returns:**
+    /// - `Ok(())` once the response is written.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns any I/O error raised while writing or reading the file.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{response::Response, timeouts::{ClientStream, Timeouts}};
+    /// # use std::net::{TcpListener, TcpStream};
+    /// # let listener = TcpListener::bind("127.0.0.1:0")?;
+    /// # let _peer = TcpStream::connect(listener.local_addr()?)?;
+    /// # let (stream, _) = listener.accept()?;
+    /// # let response = Response::new(200, "OK").with_body("hello");
+    /// response.send(&mut ClientStream::new(&stream, Timeouts::new()))?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn send(&self, client: &mut ClientStream<'_>) -> io::Result<()> {
+        self.send_with(client, &mut Vec::new())
+    }
+
+    /// # send_with
+    ///
+    /// **Purpose:**
+    /// Like `send`, but serializes the head into a caller-owned buffer, such as a connection's
+    /// pooled scratch buffer.
+    ///
+    /// **Parameters:**
+    /// - `client`: Client connection, with its time limits.
+    /// - `head`: Scratch buffer for the head; its contents are replaced.
+    ///
+    /// **Returns:**
+    /// - `Ok(())` once the response is written.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns any I/O error raised while writing or reading the file.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// response.send_with(stream, &mut scratch)?;
+    /// ```
+    pub(crate) fn send_with(&self, client: &mut ClientStream<'_>, head: &mut Vec<u8>) -> io::Result<()> {
+        let Some(FileBody::Streamed { path, offset, len, sandbox }) = self.file.as_deref().filter(|_| self.trailers.is_none()) else {
+            return self.write_with(client, head);
+        };
+        self.head_into(head)?;
+        if matches!(self.status, 204 | 304) {
+            return client.write_all(head);
+        }
+        let mut file = open_at(path, sandbox.as_deref(), 0)?;
+        client.write_all(head)?;
+        let sent = client.send_file(&file, *offset, *len)?;
+        if sent < *len {
+            file.seek(SeekFrom::Start(offset + sent))?;
+            copy_file(&mut file, client, len - sent, path)?;
+        }
+        Ok(())
+    }
+
+    /// # write_chunked
+    ///
+    /// **Purpose:**
+    /// Writes the head and then the body as `chunked` transfer coding, ending with the
+    /// trailer fields computed from it.
+    ///
+    /// **Parameters:**
+    /// - `writer`: Destination for the serialized response.
+    /// - `head`: Serialized head, announcing the chunked framing and the trailers.
+    ///
+    /// **Returns:**
+    /// - `Ok(())` once the last chunk and the trailers are written.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns any I/O error raised by `writer` or while reading a streamed file, including
+    ///   `UnexpectedEof` if the file shrank since the response was built.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// return self.write_chunked(writer, head);
+    /// ```
+    fn write_chunked<W: Write>(&self, writer: &mut W, head: &[u8]) -> io::Result<()> {
+        let file = match self.file.as_deref() {
+            Some(FileBody::Streamed { path, offset, sandbox, .. }) => Some(open_at(path, sandbox.as_deref(), *offset)?),
+            _ => None,
+        };
+        writer.write_all(head)?;
+        let mut chunked = Chunked {
+            writer,
+            digests: self.trailers().iter().map(|trailer| (trailer.name.as_str(), (trailer.start)())).collect(),
+        };
+        match (self.file.as_deref(), file) {
+            (Some(FileBody::Streamed { path, len, .. }), Some(mut file)) => copy_file(&mut file, &mut chunked, *len, path)?,
+            (Some(FileBody::Mapped { map, start, len }), _) => chunked.write_all(&map.as_slice()[*start..start + len])?,
+            _ => chunked.write_all(&self.body)?,
+        }
+        chunked.finish()
+    }
+
+    /// # write_head_to
+    ///
+    /// **Purpose:**
+    /// Serializes the status line and headers exactly as `write_to` would, including the
+    /// body's `Content-Length`, but not the body itself (for `HEAD` responses). A `Date`
+    /// header is added unless the response already has one.
+    ///
+    /// **Parameters:**
+    /// - `writer`: Destination for the serialized head.
+    ///
+    /// **Returns:**
+    /// - `Ok(())` once the head is written.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns any I/O error raised by `writer`.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::response::Response;
+    /// # let response = Response::new(200, "OK").with_body("hello");
+    /// # let mut stream = Vec::new();
+    /// response.write_head_to(&mut stream)?;
+    /// # assert!(stream.ends_with(b"\r\n\r\n"));
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn write_head_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        let mut head = Vec::new();
+        self.head_into(&mut head)?;
+        writer.write_all(&head)
+    }
+
+    /// # head_into
+    ///
+    /// **Purpose:**
+    /// Serializes the status line and headers, with `Date` and `Content-Length` added, for
+    /// `write_to`, `send_with`, and `write_head_to`. Responses with trailers announce `chunked`
+    /// framing and the trailer names instead of a length.
+    ///
+    /// **Parameters:**
+    /// - `head`: Buffer to fill; its previous contents are discarded.
+    ///
+    /// **Returns:**
+    /// - `Ok(())` once the head, ending with the blank line, is in `head`.
+    ///
+    /// **Errors / Failures:**
+    /// None in practice; formatting into memory cannot fail.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// self.head_into(&mut head)?;
+    /// ```
+    fn head_into(&self, head: &mut Vec<u8>) -> io::Result<()> {
+        head.clear();
+        write!(head, "HTTP/1.1 {} {}\r\n", self.status, self.reason)?;
+        for (name, value) in self.headers.iter() {
+            if !["Content-Length", "Transfer-Encoding", "Trailer"].iter().any(|framing| name.eq_ignore_ascii_case(framing)) {
+                write!(head, "{name}: {value}\r\n")?;
+            }
+        }
+        if !self.headers.contains("Date") {
+            write!(head, "Date: {}\r\n", date::http_date_now())?;
+        }
+        if !matches!(self.status, 204 | 304) {
+            if self.trailers.is_none() {
+                write!(head, "Content-Length: {}\r\n", self.body_len())?;
+            } else {
+                let names: Vec<&str> = self.trailers().iter().map(|trailer| trailer.name.as_str()).collect();
+                write!(head, "Transfer-Encoding: chunked\r\nTrailer: {}\r\n", names.join(", "))?;
+            }
+        }
+        head.extend_from_slice(b"\r\n");
+        Ok(())
+    }
+
+    /// # write_final
+    ///
+    /// **Purpose:**
+    /// Sends a refusal on a connection that will not be read further: request bytes that have
+    /// already arrived are drained (so closing does not reset the connection before the client
+    /// reads the reply), the response is written with `Connection: close` under a short write
+    /// timeout, and the sending side is shut down.
+    ///
+    /// **Parameters:**
+    /// - `stream`: Client connection.
+    /// - `timeout`: Longest time to spend writing.
+    ///
+    /// **Returns:**
+    /// - `Ok(())` once the response has been written.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns an I/O error if the response cannot be written in time.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// Response::new(408, "Request Timeout").write_final(&stream, Duration::from_secs(1))?;
+    /// ```
+    pub(crate) fn write_final(self, mut stream: &TcpStream, timeout: Duration) -> io::Result<()> {
+        stream.set_nonblocking(true)?;
+        let mut discard = [0u8; 4096];
+        while matches!(stream.read(&mut discard), Ok(read) if read > 0) {}
+        stream.set_nonblocking(false)?;
+        stream.set_write_timeout(Some(timeout))?;
+
+        self.with_header("Connection", "close").write_to(&mut stream)?;
+        stream.shutdown(Shutdown::Write)
+    }
+}
+
+
+impl fmt::Debug for Trailer {
+
+    /// # fmt
+    ///
+    /// **Purpose:**
+    /// Formats the trailer by name; its digest has nothing printable.
+    ///
+    /// **Parameters:**
+    /// - `formatter`: Destination formatter.
+    ///
+    /// **Returns:**
+    /// - The formatting result.
+    ///
+    /// **Errors / Failures:**
+    /// - Propagates formatter errors.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// println!("{trailer:?}");
+    /// ```
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.debug_struct("Trailer").field("name", &self.name).finish_non_exhaustive()
+    }
+}
+
+
+impl TrailerDigest for ContentDigest {
+
+    /// # update
+    ///
+    /// **Purpose:**
+    /// Hashes the next piece of the body.
+    ///
+    /// **Parameters:**
+    /// - `chunk`: Body bytes.
+    ///
+    /// **Returns:**
+    /// None.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// digest.update(b"hello");
+    /// ```
+    fn update(&mut self, chunk: &[u8]) {
+        self.hasher.update(chunk);
+    }
+
+    /// # finish
+    ///
+    /// **Purpose:**
+    /// Formats the digest as an RFC 9530 dictionary member.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - `sha-256=:<base64>:`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let value = Box::new(digest).finish();
+    /// ```
+    fn finish(self: Box<Self>) -> String {
+        format!("sha-256=:{}:", base64::encode(&self.hasher.finish()))
+    }
+}
+
+
+impl<W: Write> Chunked<'_, W> {
+
+    /// # finish
+    ///
+    /// **Purpose:**
+    /// Writes the terminating chunk with each trailer field's final value.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - `Ok(())` once the body is complete.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns any I/O error raised by the writer.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// chunked.finish()?;
+    /// ```
+    fn finish(self) -> io::Result<()> {
+        let mut tail = b"0\r\n".to_vec();
+        for (name, digest) in self.digests {
+            let value: String = digest.finish().chars().filter(|c| *c != '\r' && *c != '\n').collect();
+            write!(tail, "{name}: {value}\r\n")?;
+        }
+        tail.extend_from_slice(b"\r\n");
+        self.writer.write_all(&tail)
+    }
+}
+
+
+impl<W: Write> Write for Chunked<'_, W> {
+
+    /// # write
+    ///
+    /// **Purpose:**
+    /// Sends the bytes as one chunk and feeds them to the digests.
+    ///
+    /// **Parameters:**
+    /// - `bytes`: Body bytes; an empty slice writes nothing, as a zero-size chunk would end
+    ///   the body.
+    ///
+    /// **Returns:**
+    /// - The number of bytes taken, always all of them.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns any I/O error raised by the writer, after which the framing is broken.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// chunked.write_all(body)?;
+    /// ```
+    fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
+        if bytes.is_empty() {
+            return Ok(0);
+        }
+        let size = format!("{:x}\r\n", bytes.len());
+        write_all_vectored(self.writer, &mut [IoSlice::new(size.as_bytes()), IoSlice::new(bytes), IoSlice::new(b"\r\n")])?;
+        for (_, digest) in &mut self.digests {
+            digest.update(bytes);
+        }
+        Ok(bytes.len())
+    }
+
+    /// # flush
+    ///
+    /// **Purpose:**
+    /// Flushes the underlying writer.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - `Ok(())` once flushed.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns any I/O error raised by the writer.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// chunked.flush()?;
+    /// ```
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+
+/// # file_response
+///
+/// **Purpose:**
+/// Shared body of `send_file` and `download`.
+///
+/// **Parameters:**
+/// - `path`: File to send.
+/// - `disposition`: `inline` or `attachment`.
+/// - `filename`: Name for `Content-Disposition`; omitted when empty.
+///
+/// **Returns:**
+/// - The file response, or `404 Not Found`.
+///
+/// **Errors / Failures:**
+/// None. Refused, missing, and unreadable files become the `404` response.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let response = file_response(Path::new("a.pdf"), "inline", "a.pdf");
+/// ```
+fn file_response(path: &Path, disposition: &str, filename: &str) -> Response {
+    let not_found = || {
+        Response::new(404, "Not Found")
+            .with_header("Content-Type", "text/plain; charset=utf-8")
+            .with_body("not found\n")
+    };
+    if path.components().any(|component| component == Component::ParentDir) {
+        return not_found();
+    }
+    let Ok(metadata) = fs::metadata(path) else {
+        return not_found();
+    };
+    if !metadata.is_file() || fs::File::open(path).is_err() {
+        return not_found();
+    }
+
+    let mut response = Response::new(200, "OK")
+        .with_header("Content-Type", mime::content_type(path))
+        .with_streamed_body(path, metadata.len());
+    if let Ok(modified) = metadata.modified() {
+        response.headers.set("Last-Modified", date::format_http_date(modified));
+    }
+    response.headers.set("Content-Disposition", content_disposition(disposition, filename));
+    response
+}
+
+
+/// # write_all_vectored
+///
+/// **Purpose:**
+/// Writes every buffer in order, using vectored writes so the kernel gathers them without a
+/// joining copy, and resuming after partial writes.
+///
+/// **Parameters:**
+/// - `writer`: Destination.
+/// - `buffers`: Buffers to write; consumed as they are written.
+///
+/// **Returns:**
+/// - `Ok(())` once every byte is written.
+///
+/// **Errors / Failures:**
+/// - Returns any I/O error raised by `writer`, or `WriteZero` if it stops accepting bytes.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// write_all_vectored(writer, &mut [IoSlice::new(&head), IoSlice::new(&body)])?;
+/// ```
+fn write_all_vectored<W: Write>(writer: &mut W, mut buffers: &mut [IoSlice<'_>]) -> io::Result<()> {
+    IoSlice::advance_slices(&mut buffers, 0);
+    while !buffers.is_empty() {
+        match writer.write_vectored(buffers) {
+            Ok(0) => return Err(io::Error::from(io::ErrorKind::WriteZero)),
+            Ok(written) => IoSlice::advance_slices(&mut buffers, written),
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(())
+}
+
+
+/// # open_at
+///
+/// **Purpose:**
+/// Opens a streamed file body positioned at its first byte.
+///
+/// **Parameters:**
+/// - `path`: File to open, relative to `sandbox` if one is given.
+/// - `sandbox`: Sandbox to open the file through, if any.
+/// - `offset`: Offset of the body within the file.
+///
+/// **Returns:**
+/// - The open file, positioned at `offset`.
+///
+/// **Errors / Failures:**
+/// - Returns any I/O error raised while opening or seeking.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let mut file = open_at(path, None, offset)?;
+/// ```
+fn open_at(path: &Path, sandbox: Option<&Sandbox>, offset: u64) -> io::Result<fs::File> {
+    let mut file = match sandbox {
+        Some(sandbox) => sandbox.open_beneath(path)?,
+        None => fs::File::open(path)?,
+    };
+    if offset > 0 {
+        file.seek(SeekFrom::Start(offset))?;
+    }
+    Ok(file)
+}
+
+
+/// # read_streamed
+///
+/// **Purpose:**
+/// Reads part of a streamed file body into memory.
+///
+/// **Parameters:**
+/// - `path`: File to read, relative to `sandbox` if one is given.
+/// - `sandbox`: Sandbox to open the file through, if any.
+/// - `offset`: Offset of the first byte.
+/// - `len`: Number of bytes.
+///
+/// **Returns:**
+/// - The bytes read.
+///
+/// **Errors / Failures:**
+/// - Returns any I/O error raised while reading, including `UnexpectedEof` if the file is
+///   shorter than the range.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let contents = read_streamed(path, None, 0, len)?;
+/// ```
+fn read_streamed(path: &Path, sandbox: Option<&Sandbox>, offset: u64, len: u64) -> io::Result<Vec<u8>> {
+    let mut contents = Vec::new();
+    copy_file(&mut open_at(path, sandbox, offset)?, &mut contents, len, path)?;
+    Ok(contents)
+}
+
+
+/// # copy_file
+///
+/// **Purpose:**
+/// Copies a file body to a writer in `STREAM_CHUNK` pieces.
+///
+/// **Parameters:**
+/// - `file`: Source file, positioned at the first byte to copy.
+/// - `writer`: Destination.
+/// - `len`: Number of bytes to copy.
+/// - `path`: File name for the error message.
+///
+/// **Returns:**
+/// - `Ok(())` once `len` bytes are copied.
+///
+/// **Errors / Failures:**
+/// - Returns any I/O error raised while reading or writing.
+/// - `UnexpectedEof` if the file is shorter than `len`, as the `Content-Length` already sent
+///   promised more.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// copy_file(&mut fs::File::open(path)?, writer, len, path)?;
+/// ```
+fn copy_file<W: Write>(file: &mut fs::File, writer: &mut W, len: u64, path: &Path) -> io::Result<()> {
+    let mut file = file.take(len);
+    let mut chunk = vec![0; STREAM_CHUNK];
+    let mut remaining = len;
+    loop {
+        let read = file.read(&mut chunk)?;
+        if read == 0 {
+            break;
+        }
+        writer.write_all(&chunk[..read])?;
+        remaining -= read as u64;
+    }
+    if remaining > 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            format!("{} shrank while being sent", path.display()),
+        ));
+    }
+    Ok(())
+}
+
+
+/// # content_disposition
+///
+/// **Purpose:**
+/// Formats a `Content-Disposition` value, adding an RFC 5987 `filename*` when the name is not
+/// plain ASCII.
+///
+/// **Parameters:**
+/// - `disposition`: `inline` or `attachment`.
+/// - `filename`: File name to offer; omitted when empty.
+///
+/// **Returns:**
+/// - The header value.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// assert_eq!(content_disposition("attachment", "é.txt"), "attachment; filename=\"_.txt\"; filename*=UTF-8''%C3%A9.txt");
+/// ```
+fn content_disposition(disposition: &str, filename: &str) -> String {
+    if filename.is_empty() {
+        return disposition.to_string();
+    }
+    let fallback: String = filename
+        .chars()
+        .map(|c| if c.is_ascii() && !c.is_ascii_control() && c != '"' && c != '\\' { c } else { '_' })
+        .collect();
+    if fallback == filename {
+        format!("{disposition}; filename=\"{filename}\"")
+    } else {
+        format!("{disposition}; filename=\"{fallback}\"; filename*=UTF-8''{}", encode_component(filename))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::template::Templates;
+
+    fn read(raw: &str, limit: usize) -> io::Result<Response> {
+        Response::read_from(&mut raw.as_bytes(), limit)
+    }
+
+    #[test]
+    fn reads_framed_and_unframed_bodies_within_the_limit() {
+        let response = read("HTTP/1.1 100 Continue\r\n\r\nHTTP/1.1 200 OK\r\nContent-Length: 5\r\n\r\nhello", 1024).unwrap();
+        assert_eq!((response.status, response.body.as_slice()), (200, &b"hello"[..]));
+
+        let response = read("HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n5\r\nhello\r\n0\r\n\r\n", 1024).unwrap();
+        assert_eq!(response.body, b"hello");
+
+        let response = read("HTTP/1.0 200 OK\r\n\r\nuntil eof", 1024).unwrap();
+        assert_eq!(response.body, b"until eof");
+    }
+
+    #[test]
+    fn responses_larger_than_the_limit_are_refused() {
+        let declared = read("HTTP/1.1 200 OK\r\nContent-Length: 100\r\n\r\n", 64).unwrap_err();
+        assert_eq!(LimitExceeded::of(&declared), Some(LimitExceeded::Body));
+
+        let chunked = read("HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n40\r\n", 64).unwrap_err();
+        assert_eq!(LimitExceeded::of(&chunked), Some(LimitExceeded::Body));
+
+        let unframed = format!("HTTP/1.0 200 OK\r\n\r\n{}", "x".repeat(64));
+        assert_eq!(LimitExceeded::of(&read(&unframed, 64).unwrap_err()), Some(LimitExceeded::Body));
+
+        let head = format!("HTTP/1.1 200 OK\r\nX-Padding: {}\r\n\r\n", "x".repeat(64));
+        assert_eq!(LimitExceeded::of(&read(&head, 64).unwrap_err()), Some(LimitExceeded::Headers));
+    }
+
+    #[test]
+    fn rendered_pages_are_html() {
+        let page = Template::parse("<h1>{{title}}</h1>").unwrap();
+        let response = Response::render(&page, &JsonValue::Object(vec![("title".into(), "A & B".into())]));
+        assert_eq!(response.headers.get("Content-Type"), Some("text/html; charset=utf-8"));
+        assert_eq!(response.body, b"<h1>A &amp; B</h1>");
+
+        let response = Response::render_template(&Templates::new(), "missing.html", &JsonValue::Null);
+        assert_eq!(response.status, 500);
+    }
+}
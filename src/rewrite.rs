@@ -0,0 +1,244 @@
+
+// ============================================================
+//  DAEGONICA SOFTWARE — rewrite.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: Rewrite
+//!
+//! **Purpose:**
+//! Remaps request paths with regex rewrite rules before routing, in the spirit of nginx `rewrite`.
+//!
+//! **Context:**
+//! - Each virtual host evaluates its rules once per request, right after it is selected.
+//!
+//! **Responsibilities:**
+//! - Matches rule patterns against the request path and expands `$n` capture references.
+//! - Applies internal rewrites, stops on `Last`, and answers `Redirect` rules with a redirect response.
+//! - Keeps the original query string unless the replacement supplies its own.
+//! - Does NOT re-run host selection after a rewrite.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+use std::io;
+
+use crate::{regex::Regex, request::Request, response::Response};
+
+
+/// # RewriteFlag
+///
+/// **Summary:**
+/// What happens after a rule matches.
+///
+/// **Variants:**
+/// - `Internal`: Rewrite the path and keep evaluating later rules.
+/// - `Last`: Rewrite the path and stop evaluating rules.
+/// - `Redirect`: Stop and answer with the given redirect status, using the replacement as `Location`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RewriteFlag {
+    Internal,
+    Last,
+    Redirect(u16),
+}
+
+
+/// # RewriteRule
+///
+/// **Summary:**
+/// One pattern → replacement rule.
+///
+/// **Fields:**
+/// - `pattern`: Regex searched in the request path.
+/// - `replacement`: New target; `$0`–`$9` refer to capture groups.
+/// - `flag`: Behaviour after a match.
+///
+/// **Usage Example:**
+/// ```rust
+/// # use server::{rewrite::{RewriteFlag, RewriteRule}, vhost::VirtualHost};
+/// let rule = RewriteRule::new(r"^/blog/(\d+)$", "/posts?id=$1", RewriteFlag::Last)?;
+/// let site = VirtualHost::new().rewrite(rule);
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct RewriteRule {
+    pattern: Regex,
+    replacement: String,
+    flag: RewriteFlag,
+}
+
+
+impl RewriteRule {
+
+    /// # new
+    ///
+    /// **Purpose:**
+    /// Compiles a rewrite rule.
+    ///
+    /// **Parameters:**
+    /// - `pattern`: Regular expression matched against the request path.
+    /// - `replacement`: Replacement target with `$n` references.
+    /// - `flag`: Behaviour after a match.
+    ///
+    /// **Returns:**
+    /// - A compiled `RewriteRule`.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns `InvalidData` if `pattern` is not a valid regex.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::rewrite::{RewriteFlag, RewriteRule};
+    /// let rule = RewriteRule::new("^/old/(.*)$", "/new/$1", RewriteFlag::Redirect(301))?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn new(pattern: &str, replacement: impl Into<String>, flag: RewriteFlag) -> io::Result<RewriteRule> {
+        Ok(RewriteRule {
+            pattern: Regex::new(pattern)?,
+            replacement: replacement.into(),
+            flag,
+        })
+    }
+}
+
+
+/// # apply
+///
+/// **Purpose:**
+/// Runs `rules` in order against the request, rewriting its target in place.
+///
+/// **Parameters:**
+/// - `rules`: Rules of the selected virtual host.
+/// - `request`: Request to rewrite.
+///
+/// **Returns:**
+/// - `Some(response)` if a `Redirect` rule matched, otherwise `None` (the request may have been rewritten).
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust
+/// # use server::{request::Request, rewrite::{self, RewriteFlag, RewriteRule}};
+/// # fn serve(rules: &[RewriteRule], mut request: Request, mut stream: &mut Vec<u8>) -> std::io::Result<()> {
+/// if let Some(redirect) = rewrite::apply(&rules, &mut request) {
+///     return redirect.write_to(&mut stream);
+/// }
+/// # Ok(())
+/// # }
+/// # let rules = [RewriteRule::new("^/old/(.*)$", "/new/$1", RewriteFlag::Redirect(301))?];
+/// # let mut stream = Vec::new();
+/// # serve(&rules, Request::read_from(&mut &b"GET /old/page HTTP/1.1\r\n\r\n"[..])?, &mut stream)?;
+/// # assert!(stream.starts_with(b"HTTP/1.1 301"));
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub fn apply(rules: &[RewriteRule], request: &mut Request) -> Option<Response> {
+    for rule in rules {
+        let Some(captures) = rule.pattern.captures(request.path()) else {
+            continue;
+        };
+
+        let mut target = expand(&rule.replacement, &captures);
+        if !target.contains('?')
+            && let Some(query) = request.query()
+        {
+            target = format!("{target}?{query}");
+        }
+
+        match rule.flag {
+            RewriteFlag::Redirect(status) => return Some(Response::redirect(status, target)),
+            RewriteFlag::Last => {
+                request.target = target;
+                break;
+            }
+            RewriteFlag::Internal => request.target = target,
+        }
+    }
+    None
+}
+
+
+/// # expand
+///
+/// **Purpose:**
+/// Substitutes `$0`–`$9` in a replacement template with captured text.
+///
+/// **Parameters:**
+/// - `template`: Replacement text.
+/// - `captures`: Capture groups; missing or unmatched groups expand to nothing.
+///
+/// **Returns:**
+/// - The expanded string.
+///
+/// **Errors / Failures:**
+/// None. A `$` not followed by a digit is kept literally.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// assert_eq!(expand("/new/$1", &[None, Some("x".into())]), "/new/x");
+/// ```
+pub(crate) fn expand(template: &str, captures: &[Option<String>]) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut chars = template.chars().peekable();
+    while let Some(c) = chars.next() {
+        let group = chars.peek().and_then(|next| next.to_digit(10));
+        match (c, group) {
+            ('$', Some(index)) => {
+                chars.next();
+                if let Some(Some(text)) = captures.get(index as usize) {
+                    out.push_str(text);
+                }
+            }
+            (c, _) => out.push(c),
+        }
+    }
+    out
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(target: &str) -> Request {
+        Request::read_from(&mut format!("GET {target} HTTP/1.1\r\nHost: example.com\r\n\r\n").as_bytes()).unwrap()
+    }
+
+    fn rule(pattern: &str, replacement: &str, flag: RewriteFlag) -> RewriteRule {
+        RewriteRule::new(pattern, replacement, flag).unwrap()
+    }
+
+    #[test]
+    fn internal_rules_chain_and_last_rules_stop() {
+        let rules = [
+            rule("^/blog/(\\d+)$", "/posts/$1", RewriteFlag::Internal),
+            rule("^/posts/(\\d+)$", "/article?id=$1", RewriteFlag::Last),
+            rule("^/article$", "/never", RewriteFlag::Internal),
+        ];
+        let mut request = request("/blog/42");
+        assert!(apply(&rules, &mut request).is_none());
+        assert_eq!(request.target, "/article?id=42");
+    }
+
+    #[test]
+    fn redirect_rules_answer_and_keep_the_query() {
+        let rules = [rule("^/old/(.*)$", "/new/$1", RewriteFlag::Redirect(301))];
+        let mut request = request("/old/page?lang=en");
+        let response = apply(&rules, &mut request).unwrap();
+        assert_eq!((response.status, response.headers.get("Location")), (301, Some("/new/page?lang=en")));
+        assert_eq!(request.target, "/old/page?lang=en");
+        assert!(apply(&rules, &mut self::request("/other")).is_none());
+    }
+
+    #[test]
+    fn replacements_expand_groups_and_keep_stray_dollars() {
+        let captures = [Some("/a/b".to_string()), Some("a".to_string()), None];
+        assert_eq!(expand("/$1/$2/$9/$x/$", &captures), "/a///$x/$");
+        assert!(RewriteRule::new("(", "/", RewriteFlag::Internal).is_err());
+    }
+}
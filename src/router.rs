@@ -0,0 +1,1096 @@
+
+// ============================================================
+//  DAEGONICA SOFTWARE — router.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: Router
+//!
+//! **Purpose:**
+//! Maps request methods and paths to handler functions.
+//!
+//! **Context:**
+//! - Each virtual host owns a router that is consulted before its static files.
+//!
+//! **Responsibilities:**
+//! - Stores route registrations in order and indexes their paths in a segment trie.
+//! - Dispatches a request to the first route matching its method and path.
+//! - Applies a trailing-slash policy (strict, equivalent, or redirect), globally or per route.
+//! - Answers `405 Method Not Allowed` with an `Allow` header when only the method differs.
+//! - Answers `OPTIONS` for routed paths automatically unless an `OPTIONS` route is registered.
+//! - Runs `GET` routes for `HEAD` requests that have no `HEAD` route of their own.
+//! - Groups routes under a shared prefix with a scoped middleware stack.
+//! - Wraps the site's fallback (static files, the not-found page) in router-level middleware too,
+//!   so authentication, access rules, and headers cover every response of the site.
+//! - Captures `:name` segments and trailing `*name` catch-all segments as request parameters.
+//! - Applies optional regex constraints to parameters (`:id(\d+)`), falling through on a mismatch.
+//! - Keeps per-route OpenAPI documentation and serves the generated document.
+//! - Registers async handlers when built with the `async` feature, blocking on them on the
+//!   tokio runtime of `Server::serve_async`, and reports whether it holds any.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+use std::sync::Arc;
+
+#[cfg(feature = "async")]
+use crate::log::{self, LogLevel};
+use crate::{
+    openapi::{self, Operation},
+    request::Request,
+    response::Response,
+    trie::RouteTrie,
+};
+
+
+/// # Handler
+///
+/// **Summary:**
+/// Boxed request handler shared by worker threads.
+pub type Handler = Box<dyn Fn(&Request) -> Response + Send + Sync>;
+
+
+/// # Next
+///
+/// **Summary:**
+/// The rest of the middleware chain (ending in the route handler), passed to each middleware.
+pub type Next<'a> = &'a dyn Fn(&Request) -> Response;
+
+
+/// # Middleware
+///
+/// **Summary:**
+/// Shared function wrapping route handlers; it may answer directly or call `next`.
+pub type Middleware = Arc<dyn Fn(&Request, Next<'_>) -> Response + Send + Sync>;
+
+
+/// # TrailingSlash
+///
+/// **Summary:**
+/// How a path that differs from a route only by a trailing `/` is treated.
+///
+/// **Variants:**
+/// - `Strict`: `/foo` and `/foo/` are distinct; only exact matches run.
+/// - `Ignore`: `/foo` and `/foo/` both run the route.
+/// - `Redirect`: The other form is redirected to the route's registered path.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TrailingSlash {
+    #[default]
+    Strict,
+    Ignore,
+    Redirect,
+}
+
+
+/// # PathMatch
+///
+/// **Summary:**
+/// How a request path relates to a route's path.
+///
+/// **Variants:**
+/// - `Run`: The route handles the path, with the captured parameters.
+/// - `Redirect`: The path should be redirected to the given canonical form.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum PathMatch {
+    Run(Vec<(String, String)>),
+    Redirect(String),
+}
+
+
+/// # Router
+///
+/// **Summary:**
+/// Ordered list of routes.
+///
+/// **Fields:**
+/// - `routes`: Registered routes; when several match, the earliest registered wins.
+/// - `trie`: Path index over `routes`.
+/// - `trailing_slash`: Policy for routes without their own.
+/// - `middleware`: Middleware wrapping every route of this router, outermost first.
+///
+/// **Usage Example:**
+/// ```rust
+/// # use server::{request::Request, response::Response, router::{Next, Router, TrailingSlash}};
+/// # fn docs(_: &Request) -> Response { Response::new(200, "OK") }
+/// # fn list_users(_: &Request) -> Response { Response::new(200, "OK") }
+/// # fn require_token(request: &Request, next: Next<'_>) -> Response { next(request) }
+/// # let request = Request::read_from(&mut &b"GET /ping HTTP/1.1\r\n\r\n"[..])?;
+/// let router = Router::new()
+///     .trailing_slash(TrailingSlash::Ignore)
+///     .get("/ping", |_| Response::new(200, "OK").with_body("pong"))
+///     .get("/docs/", docs)
+///     .with_trailing_slash(TrailingSlash::Redirect)
+///     .scope("/api/v1", |api| api.middleware(require_token).get("/users", list_users));
+/// let response = router.handle(&request);
+/// # assert_eq!(response.unwrap().body, b"pong");
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Default)]
+pub struct Router {
+    routes: Vec<Route>,
+    trie: RouteTrie,
+    trailing_slash: TrailingSlash,
+    middleware: Vec<Middleware>,
+}
+
+
+/// # Route
+///
+/// **Summary:**
+/// One method + path registration.
+///
+/// **Fields:**
+/// - `method`: Request method to match.
+/// - `path`: Route path as registered.
+/// - `trailing_slash`: Route-specific policy, overriding the router's.
+/// - `doc`: OpenAPI documentation, if any.
+/// - `handler`: Handler producing the response.
+/// - `awaits`: Whether the handler blocks on a future (`route_async`) and so needs a tokio
+///   runtime around it.
+struct Route {
+    method: String,
+    path: String,
+    trailing_slash: Option<TrailingSlash>,
+    doc: Option<Operation>,
+    handler: Handler,
+    awaits: bool,
+}
+
+
+impl Router {
+
+    /// # new
+    ///
+    /// **Purpose:**
+    /// Creates a router with no routes.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - An empty `Router`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::router::Router;
+    /// let router = Router::new();
+    /// ```
+    pub fn new() -> Router {
+        Router::default()
+    }
+
+    /// # route
+    ///
+    /// **Purpose:**
+    /// Registers `handler` for requests with the given method and path. Path segments written
+    /// `:name` match any single segment and a final `*name` matches the rest of the path; both
+    /// are available to the handler through `Request::param`. Either may be followed by a regex
+    /// in parentheses that the captured text must match in full.
+    ///
+    /// **Parameters:**
+    /// - `method`: Request method (e.g. `POST`).
+    /// - `path`: Route path (e.g. `/upload`, `/users/:id(\d+)`, `/files/*path`).
+    /// - `handler`: Closure producing the response.
+    ///
+    /// **Returns:**
+    /// - The updated `Router`.
+    ///
+    /// **Errors / Failures:**
+    /// - Panics if a parameter constraint is not a valid regex.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{request::Request, response::Response, router::Router};
+    /// # fn upload(_: &Request) -> Response { Response::new(201, "Created") }
+    /// # fn download(_: &Request) -> Response { Response::new(200, "OK") }
+    /// let router = Router::new().route("POST", "/upload", upload).route("GET", "/files/*path", download);
+    /// ```
+    pub fn route<F>(mut self, method: &str, path: &str, handler: F) -> Router
+    where
+        F: Fn(&Request) -> Response + Send + Sync + 'static,
+    {
+        self.add(Route {
+            method: method.to_string(),
+            path: path.to_string(),
+            trailing_slash: None,
+            doc: None,
+            handler: Box::new(handler),
+            awaits: false,
+        });
+        self
+    }
+
+    /// # get
+    ///
+    /// **Purpose:**
+    /// Registers a `GET` route.
+    ///
+    /// **Parameters:**
+    /// - `path`: Route path, optionally with `:name` and `*name` segments.
+    /// - `handler`: Closure producing the response.
+    ///
+    /// **Returns:**
+    /// - The updated `Router`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{request::Request, response::Response, router::Router};
+    /// # fn sleep(_: &Request) -> Response { Response::new(200, "OK") }
+    /// let router = Router::new().get("/sleep", sleep);
+    /// ```
+    pub fn get<F>(self, path: &str, handler: F) -> Router
+    where
+        F: Fn(&Request) -> Response + Send + Sync + 'static,
+    {
+        self.route("GET", path, handler)
+    }
+
+    /// # route_async
+    ///
+    /// **Purpose:**
+    /// Registers an async handler for requests with the given method and path. Connections are
+    /// still served synchronously, so the worker serving the request blocks on the handler's
+    /// future, on the tokio runtime it runs in; the route therefore only works under
+    /// `Server::serve_async`, and `Server::run` and `Server::start` refuse routers holding one.
+    ///
+    /// **Parameters:**
+    /// - `method`: Request method (e.g. `POST`).
+    /// - `path`: Route path, as for `route`.
+    /// - `handler`: Closure taking an owned copy of the request and returning a future of the
+    ///   response.
+    ///
+    /// **Returns:**
+    /// - The updated `Router`.
+    ///
+    /// **Errors / Failures:**
+    /// - Panics if a parameter constraint is not a valid regex.
+    /// - The route answers `500 Internal Server Error` when handled outside a tokio runtime
+    ///   (e.g. by calling `Router::handle` directly).
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{request::Request, response::Response, router::Router};
+    /// # async fn enqueue(_: Request) -> Response { Response::new(202, "Accepted") }
+    /// let router = Router::new().route_async("POST", "/jobs", |request| async move { enqueue(request).await });
+    /// ```
+    #[cfg(feature = "async")]
+    pub fn route_async<F, Fut>(self, method: &str, path: &str, handler: F) -> Router
+    where
+        F: Fn(Request) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Response>,
+    {
+        let router = self.route(method, path, move |request: &Request| block_on(handler(request.clone())));
+        router.mark_last_async()
+    }
+
+    /// # get_async
+    ///
+    /// **Purpose:**
+    /// Registers an async `GET` route.
+    ///
+    /// **Parameters:**
+    /// - `path`: Route path, optionally with `:name` and `*name` segments.
+    /// - `handler`: Closure returning a future of the response.
+    ///
+    /// **Returns:**
+    /// - The updated `Router`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{request::Request, response::Response, router::Router};
+    /// # async fn fetch_user(_: Request) -> Response { Response::new(200, "OK") }
+    /// let router = Router::new().get_async("/user/:id", |request| async move { fetch_user(request).await });
+    /// ```
+    #[cfg(feature = "async")]
+    pub fn get_async<F, Fut>(self, path: &str, handler: F) -> Router
+    where
+        F: Fn(Request) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Response>,
+    {
+        self.route_async("GET", path, handler)
+    }
+
+    /// # mark_last_async
+    ///
+    /// **Purpose:**
+    /// Records that the route registered last blocks on a future.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - The updated `Router`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let router = self.route(method, path, handler).mark_last_async();
+    /// ```
+    #[cfg(feature = "async")]
+    fn mark_last_async(mut self) -> Router {
+        if let Some(route) = self.routes.last_mut() {
+            route.awaits = true;
+        }
+        self
+    }
+
+    /// # has_async_routes
+    ///
+    /// **Purpose:**
+    /// Checks whether any route, including those of scopes, was registered with `route_async`.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - `true` if a route needs a tokio runtime to be served.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// if router.has_async_routes() { return Err(needs_runtime()); }
+    /// ```
+    pub(crate) fn has_async_routes(&self) -> bool {
+        self.routes.iter().any(|route| route.awaits)
+    }
+
+    /// # middleware
+    ///
+    /// **Purpose:**
+    /// Adds middleware around every route of this router, including routes registered before
+    /// the call and routes of nested scopes, and around the router's automatic `OPTIONS`, `405`,
+    /// and trailing-slash redirect responses. Middleware added first runs first.
+    ///
+    /// **Parameters:**
+    /// - `middleware`: Closure receiving the request and the rest of the chain.
+    ///
+    /// **Returns:**
+    /// - The updated `Router`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{response::Response, router::Router};
+    /// let router = Router::new().middleware(|request, next| match request.headers.get("X-Token") {
+    ///     Some(_) => next(request),
+    ///     None => Response::new(401, "Unauthorized"),
+    /// });
+    /// ```
+    pub fn middleware<F>(mut self, middleware: F) -> Router
+    where
+        F: Fn(&Request, Next<'_>) -> Response + Send + Sync + 'static,
+    {
+        self.middleware.push(Arc::new(middleware));
+        self
+    }
+
+    /// # scope
+    ///
+    /// **Purpose:**
+    /// Registers a group of routes under a path prefix. The group is built on a fresh router,
+    /// so middleware added inside it applies only to the group's routes (inside any middleware
+    /// of this router). Routes without a trailing-slash override use this router's policy.
+    ///
+    /// **Parameters:**
+    /// - `prefix`: Path prefix prepended to every route in the group (e.g. `/api/v1`).
+    /// - `build`: Closure registering the group's routes and middleware.
+    ///
+    /// **Returns:**
+    /// - The updated `Router`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{request::Request, response::Response, router::{Next, Router}};
+    /// # fn auth(request: &Request, next: Next<'_>) -> Response { next(request) }
+    /// # fn stats(_: &Request) -> Response { Response::new(200, "OK") }
+    /// let router = Router::new().scope("/admin", |admin| admin.middleware(auth).get("/stats", stats));
+    /// ```
+    pub fn scope<F>(mut self, prefix: &str, build: F) -> Router
+    where
+        F: FnOnce(Router) -> Router,
+    {
+        let prefix = prefix.trim_end_matches('/');
+        let group = build(Router::new());
+        for route in group.routes {
+            let handler = route.handler;
+            let handler: Handler = if group.middleware.is_empty() {
+                handler
+            } else {
+                let middleware = group.middleware.clone();
+                Box::new(move |request| run_chain(&middleware, &handler, request))
+            };
+            self.add(Route {
+                path: format!("{prefix}{}", route.path),
+                handler,
+                ..route
+            });
+        }
+        self
+    }
+
+    /// # trailing_slash
+    ///
+    /// **Purpose:**
+    /// Sets the trailing-slash policy for every route without its own.
+    ///
+    /// **Parameters:**
+    /// - `policy`: Default policy (initially `Strict`).
+    ///
+    /// **Returns:**
+    /// - The updated `Router`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::router::{Router, TrailingSlash};
+    /// let router = Router::new().trailing_slash(TrailingSlash::Redirect);
+    /// ```
+    pub fn trailing_slash(mut self, policy: TrailingSlash) -> Router {
+        self.trailing_slash = policy;
+        self
+    }
+
+    /// # with_trailing_slash
+    ///
+    /// **Purpose:**
+    /// Overrides the trailing-slash policy of the most recently registered route.
+    ///
+    /// **Parameters:**
+    /// - `policy`: Policy for that route.
+    ///
+    /// **Returns:**
+    /// - The updated `Router`.
+    ///
+    /// **Errors / Failures:**
+    /// None. Does nothing if no route has been registered yet.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{request::Request, response::Response, router::{Router, TrailingSlash}};
+    /// # fn docs(_: &Request) -> Response { Response::new(200, "OK") }
+    /// let router = Router::new().get("/docs/", docs).with_trailing_slash(TrailingSlash::Redirect);
+    /// ```
+    pub fn with_trailing_slash(mut self, policy: TrailingSlash) -> Router {
+        if let Some(route) = self.routes.last_mut() {
+            route.trailing_slash = Some(policy);
+        }
+        self
+    }
+
+    /// # with_doc
+    ///
+    /// **Purpose:**
+    /// Attaches OpenAPI documentation to the most recently registered route.
+    ///
+    /// **Parameters:**
+    /// - `doc`: Summary, parameters, and request/response types of that route.
+    ///
+    /// **Returns:**
+    /// - The updated `Router`.
+    ///
+    /// **Errors / Failures:**
+    /// None. Does nothing if no route has been registered yet.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{openapi::Operation, request::Request, response::Response, router::Router};
+    /// # fn list_users(_: &Request) -> Response { Response::new(200, "OK") }
+    /// let router = Router::new().get("/users", list_users).with_doc(Operation::new().summary("List users"));
+    /// ```
+    pub fn with_doc(mut self, doc: Operation) -> Router {
+        if let Some(route) = self.routes.last_mut() {
+            route.doc = Some(doc);
+        }
+        self
+    }
+
+    /// # openapi
+    ///
+    /// **Purpose:**
+    /// Renders an OpenAPI 3 document describing every registered route; routes without
+    /// documentation are listed with their path parameters only.
+    ///
+    /// **Parameters:**
+    /// - `title`: API title.
+    /// - `version`: API version.
+    ///
+    /// **Returns:**
+    /// - The document as JSON text.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,no_run
+    /// # use server::{request::Request, response::Response, router::Router};
+    /// # use std::fs;
+    /// # fn list_users(_: &Request) -> Response { Response::new(200, "OK") }
+    /// # let router = Router::new().get("/users", list_users);
+    /// fs::write("openapi.json", router.openapi("Users API", "1.0.0"))?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn openapi(&self, title: &str, version: &str) -> String {
+        let routes: Vec<(&str, &str, Option<&Operation>)> = self
+            .routes
+            .iter()
+            .map(|route| (route.method.as_str(), route.path.as_str(), route.doc.as_ref()))
+            .collect();
+        openapi::document(title, version, &routes)
+    }
+
+    /// # serve_openapi
+    ///
+    /// **Purpose:**
+    /// Registers a `GET` route at `path` answering with the OpenAPI document of the routes
+    /// registered so far; call it after the routes it should describe.
+    ///
+    /// **Parameters:**
+    /// - `path`: Path serving the document (e.g. `/openapi.json`).
+    /// - `title`: API title.
+    /// - `version`: API version.
+    ///
+    /// **Returns:**
+    /// - The updated `Router`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{request::Request, response::Response, router::Router};
+    /// # fn list_users(_: &Request) -> Response { Response::new(200, "OK") }
+    /// let router = Router::new().get("/users", list_users).serve_openapi("/openapi.json", "Users API", "1.0.0");
+    /// ```
+    pub fn serve_openapi(self, path: &str, title: &str, version: &str) -> Router {
+        let document = self.openapi(title, version);
+        self.get(path, move |_| {
+            Response::new(200, "OK")
+                .with_header("Content-Type", "application/json")
+                .with_body(document.clone())
+        })
+    }
+
+    /// # handle
+    ///
+    /// **Purpose:**
+    /// Runs the first route matching the request's method and path, honouring each route's
+    /// trailing-slash policy, and exposes captured path parameters to the handler. If the path
+    /// is routed but not for this method, answers `OPTIONS`
+    /// with `204 No Content` and anything else with `405 Method Not Allowed`, both carrying an
+    /// `Allow` header. `HEAD` requests fall back to the `GET` route; the server drops the body.
+    /// Router-level middleware wraps all of these responses.
+    ///
+    /// **Parameters:**
+    /// - `request`: Parsed client request.
+    ///
+    /// **Returns:**
+    /// - `Some(response)` if a route matched (possibly a redirect, `204`, or `405`), otherwise `None`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{request::Request, response::Response, router::Router};
+    /// # let router = Router::new().get("/", |_| Response::new(200, "OK"));
+    /// # let request = Request::read_from(&mut &b"GET / HTTP/1.1\r\n\r\n"[..])?;
+    /// let response = router.handle(&request);
+    /// # assert_eq!(response.unwrap().status, 200);
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn handle(&self, request: &Request) -> Option<Response> {
+        let mut candidates = self.candidates(request.path());
+        let position = self.position(&candidates, &request.method);
+
+        match position.map(|position| candidates.swap_remove(position)) {
+            Some((route, PathMatch::Run(params))) if params.is_empty() => {
+                Some(run_chain(&self.middleware, &self.routes[route].handler, request))
+            }
+            Some((route, PathMatch::Run(params))) => {
+                let mut request = request.clone();
+                request.params.extend(params);
+                Some(run_chain(&self.middleware, &self.routes[route].handler, &request))
+            }
+            Some((_, PathMatch::Redirect(path))) => Some(run_chain(
+                &self.middleware,
+                &|request| canonical_redirect(request, &path),
+                request,
+            )),
+            None => {
+                let allowed = self.methods_of(&candidates);
+                if allowed.is_empty() {
+                    return None;
+                }
+                let allow = allowed.join(", ");
+                let automatic = |request: &Request| {
+                    let response = match request.method.as_str() {
+                        "OPTIONS" => Response::new(204, "No Content"),
+                        _ => Response::new(405, "Method Not Allowed"),
+                    };
+                    response.with_header("Allow", allow.as_str())
+                };
+                Some(run_chain(&self.middleware, &automatic, request))
+            }
+        }
+    }
+
+    /// # handle_or
+    ///
+    /// **Purpose:**
+    /// Like `handle`, but requests no route matches are answered by `fallback` inside the
+    /// router-level middleware, so that middleware sees every request of the site. Scoped
+    /// middleware still applies only to its own routes.
+    ///
+    /// **Parameters:**
+    /// - `request`: Parsed client request.
+    /// - `fallback`: Handler for unrouted requests, such as static files and the not-found page.
+    ///
+    /// **Returns:**
+    /// - The route's response, or the fallback's response after router-level middleware.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{request::Request, response::Response, router::Router, static_files::StaticFiles};
+    /// # fn not_found() -> Response { Response::new(404, "Not Found") }
+    /// # let router = Router::new();
+    /// # let files = StaticFiles::new("html");
+    /// # let request = Request::read_from(&mut &b"GET /missing.html HTTP/1.1\r\n\r\n"[..])?;
+    /// let response = router.handle_or(&request, &|request| files.serve(request.path()).unwrap_or_else(not_found));
+    /// # assert_eq!(response.status, 404);
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn handle_or(&self, request: &Request, fallback: &dyn Fn(&Request) -> Response) -> Response {
+        self.handle(request).unwrap_or_else(|| run_chain(&self.middleware, fallback, request))
+    }
+
+    /// # route_pattern
+    ///
+    /// **Purpose:**
+    /// Names the route `handle` would answer a request with, as registered, so metrics can be
+    /// labelled by route rather than by raw path.
+    ///
+    /// **Parameters:**
+    /// - `request`: Parsed client request.
+    ///
+    /// **Returns:**
+    /// - The path of the route matching the method, else of the first route matching the path
+    ///   (answered with `204` or `405`), or `None` if no route handles the path.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{request::Request, response::Response, router::Router};
+    /// # let router = Router::new().get("/users/:id", |_| Response::new(200, "OK"));
+    /// # let request = Request::read_from(&mut &b"GET /users/7 HTTP/1.1\r\n\r\n"[..])?;
+    /// let route = router.route_pattern(&request).unwrap_or("(unrouted)");
+    /// # assert_eq!(route, "/users/:id");
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn route_pattern(&self, request: &Request) -> Option<&str> {
+        let candidates = self.candidates(request.path());
+        let position = self.position(&candidates, &request.method).unwrap_or(0);
+        candidates.get(position).map(|(route, _)| self.routes[*route].path.as_str())
+    }
+
+    /// # allowed_methods
+    ///
+    /// **Purpose:**
+    /// Lists the methods registered for a path, plus the automatically answered `OPTIONS`
+    /// (and `HEAD` where `GET` is registered).
+    ///
+    /// **Parameters:**
+    /// - `path`: Request path, without the query.
+    ///
+    /// **Returns:**
+    /// - Sorted, de-duplicated method names; empty if no route handles the path.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{request::Request, response::Response, router::Router};
+    /// # fn handler(_: &Request) -> Response { Response::new(200, "OK") }
+    /// # let router = Router::new().route("POST", "/upload", handler).route("PUT", "/upload", handler);
+    /// let allow = router.allowed_methods("/upload").join(", ");
+    /// # println!("{allow}");
+    /// ```
+    pub fn allowed_methods(&self, path: &str) -> Vec<String> {
+        self.methods_of(&self.candidates(path))
+    }
+
+    /// # methods
+    ///
+    /// **Purpose:**
+    /// Lists every method registered on any route, plus `OPTIONS` (and `HEAD` if `GET` is registered).
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - Sorted, de-duplicated method names.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{request::Request, response::Response, router::Router};
+    /// # fn handler(_: &Request) -> Response { Response::new(200, "OK") }
+    /// # let router = Router::new().route("POST", "/upload", handler).get("/", handler);
+    /// let all = router.methods();
+    /// # println!("{all:?}");
+    /// ```
+    pub fn methods(&self) -> Vec<String> {
+        let mut methods: Vec<String> = self.routes.iter().map(|route| route.method.clone()).collect();
+        implied_methods(&mut methods);
+        methods
+    }
+
+    /// # methods_of
+    ///
+    /// **Purpose:**
+    /// Lists the methods of the given candidate routes, plus the implied `HEAD` and `OPTIONS`.
+    ///
+    /// **Parameters:**
+    /// - `candidates`: Routes matching a path.
+    ///
+    /// **Returns:**
+    /// - Sorted, de-duplicated method names; empty if there are no candidates.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let allowed = router.methods_of(&router.candidates(path));
+    /// ```
+    fn methods_of(&self, candidates: &[(usize, PathMatch)]) -> Vec<String> {
+        let mut methods: Vec<String> = candidates
+            .iter()
+            .map(|(route, _)| self.routes[*route].method.clone())
+            .collect();
+        if methods.is_empty() {
+            return methods;
+        }
+        implied_methods(&mut methods);
+        methods
+    }
+
+    /// # position
+    ///
+    /// **Purpose:**
+    /// Finds the candidate route registered for a method, letting `HEAD` fall back to `GET`.
+    ///
+    /// **Parameters:**
+    /// - `candidates`: Routes matching the request path.
+    /// - `method`: Request method.
+    ///
+    /// **Returns:**
+    /// - The index into `candidates` of the first route for the method, or `None`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let position = self.position(&candidates, &request.method);
+    /// ```
+    fn position(&self, candidates: &[(usize, PathMatch)], method: &str) -> Option<usize> {
+        let registered = |wanted: &str| candidates.iter().position(|(route, _)| self.routes[*route].method == wanted);
+        registered(method).or_else(|| if method == "HEAD" { registered("GET") } else { None })
+    }
+
+    /// # candidates
+    ///
+    /// **Purpose:**
+    /// Lists the routes that apply to a path, directly or through their trailing-slash policy,
+    /// in registration order.
+    ///
+    /// **Parameters:**
+    /// - `path`: Request path.
+    ///
+    /// **Returns:**
+    /// - Route indexes with how each matched; a route matching directly precedes its slash-toggled match.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let candidates = router.candidates("/docs");
+    /// ```
+    fn candidates(&self, path: &str) -> Vec<(usize, PathMatch)> {
+        let mut found: Vec<(usize, PathMatch)> = self
+            .trie
+            .lookup(path)
+            .into_iter()
+            .map(|(route, params)| (route, PathMatch::Run(params)))
+            .collect();
+
+        if let Some(toggled) = toggle_slash(path) {
+            for (route, params) in self.trie.lookup(&toggled) {
+                match self.routes[route].trailing_slash.unwrap_or(self.trailing_slash) {
+                    TrailingSlash::Strict => {}
+                    TrailingSlash::Ignore => found.push((route, PathMatch::Run(params))),
+                    TrailingSlash::Redirect => found.push((route, PathMatch::Redirect(toggled.clone()))),
+                }
+            }
+        }
+        found.sort_by_key(|(route, _)| *route);
+        found
+    }
+
+    /// # add
+    ///
+    /// **Purpose:**
+    /// Stores a route and indexes its path in the trie.
+    ///
+    /// **Parameters:**
+    /// - `route`: Route to register.
+    ///
+    /// **Returns:**
+    /// None.
+    ///
+    /// **Errors / Failures:**
+    /// - Panics if a parameter constraint is not a valid regex.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// self.add(route);
+    /// ```
+    fn add(&mut self, route: Route) {
+        self.trie.insert(&route.path, self.routes.len());
+        self.routes.push(route);
+    }
+}
+
+
+/// # run_chain
+///
+/// **Purpose:**
+/// Runs a request through a middleware stack and finally the handler.
+///
+/// **Parameters:**
+/// - `middleware`: Remaining middleware, outermost first.
+/// - `handler`: Route handler (or automatic response) at the end of the chain.
+/// - `request`: Request being handled.
+///
+/// **Returns:**
+/// - The `Response` produced by the first middleware or, if all call `next`, by the handler.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let response = run_chain(&router.middleware, &route.handler, request);
+/// ```
+fn run_chain(middleware: &[Middleware], handler: &dyn Fn(&Request) -> Response, request: &Request) -> Response {
+    match middleware.split_first() {
+        None => handler(request),
+        Some((first, rest)) => first(request, &|request| run_chain(rest, handler, request)),
+    }
+}
+
+
+/// # implied_methods
+///
+/// **Purpose:**
+/// Adds the methods the router answers implicitly (`OPTIONS`, and `HEAD` when `GET` is present),
+/// then sorts and de-duplicates the list.
+///
+/// **Parameters:**
+/// - `methods`: Registered method names.
+///
+/// **Returns:**
+/// None. `methods` is updated in place.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// implied_methods(&mut methods);
+/// ```
+fn implied_methods(methods: &mut Vec<String>) {
+    if methods.iter().any(|method| method == "GET") {
+        methods.push("HEAD".to_string());
+    }
+    methods.push("OPTIONS".to_string());
+    methods.sort();
+    methods.dedup();
+}
+
+
+/// # toggle_slash
+///
+/// **Purpose:**
+/// Returns the other trailing-slash form of a path (`/docs` ↔ `/docs/`).
+///
+/// **Parameters:**
+/// - `path`: Request path.
+///
+/// **Returns:**
+/// - The toggled path, or `None` for the root path `/`.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// assert_eq!(toggle_slash("/docs/").as_deref(), Some("/docs"));
+/// ```
+fn toggle_slash(path: &str) -> Option<String> {
+    match path.strip_suffix('/') {
+        Some("") => None,
+        Some(trimmed) => Some(trimmed.to_string()),
+        None => Some(format!("{path}/")),
+    }
+}
+
+
+/// # canonical_redirect
+///
+/// **Purpose:**
+/// Redirects a request to a route's canonical path, keeping the query string. Safe methods
+/// get `301`; others get `308` so the method and body are preserved.
+///
+/// **Parameters:**
+/// - `request`: Request whose path differs only by a trailing slash.
+/// - `path`: The request path in the route's trailing-slash form.
+///
+/// **Returns:**
+/// - The redirect `Response`.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let response = canonical_redirect(&request, "/docs/");
+/// ```
+fn canonical_redirect(request: &Request, path: &str) -> Response {
+    let status = if matches!(request.method.as_str(), "GET" | "HEAD") { 301 } else { 308 };
+    let location = match request.query() {
+        Some(query) => format!("{path}?{query}"),
+        None => path.to_string(),
+    };
+    Response::redirect(status, location)
+}
+
+
+/// # block_on
+///
+/// **Purpose:**
+/// Drives an async handler's future to completion on the current tokio runtime, from one of
+/// its blocking threads.
+///
+/// **Parameters:**
+/// - `future`: The handler's future.
+///
+/// **Returns:**
+/// - The handler's response, or `500 Internal Server Error` if no runtime is current.
+///
+/// **Errors / Failures:**
+/// None. A missing runtime is logged.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let response = block_on(handler(request.clone()));
+/// ```
+#[cfg(feature = "async")]
+fn block_on(future: impl Future<Output = Response>) -> Response {
+    match tokio::runtime::Handle::try_current() {
+        Ok(runtime) => runtime.block_on(future),
+        Err(_) => {
+            log::emit(LogLevel::Error, format_args!("Async route served outside a tokio runtime; use Server::serve_async"));
+            Response::new(500, "Internal Server Error")
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(raw: &str) -> Request {
+        Request::read_from(&mut raw.as_bytes()).unwrap()
+    }
+
+    fn get(router: &Router, path: &str) -> Option<Response> {
+        router.handle(&request(&format!("GET {path} HTTP/1.1\r\nHost: example.com\r\n\r\n")))
+    }
+
+    #[test]
+    fn captures_parameters_and_respects_constraints() {
+        let router = Router::new()
+            .get(r"/users/:id(\d+)", |request| Response::new(200, "OK").with_body(request.param("id").unwrap_or("").to_string()))
+            .get("/users/:name", |_| Response::new(200, "OK").with_body("by name"));
+        assert_eq!(get(&router, "/users/42").unwrap().body, b"42");
+        assert_eq!(get(&router, "/users/alice").unwrap().body, b"by name");
+        assert!(get(&router, "/missing").is_none());
+    }
+
+    #[test]
+    fn scopes_prefix_routes_and_run_their_middleware() {
+        let router = Router::new().scope("/api", |api| {
+            api.middleware(|request: &Request, next: Next<'_>| next(request).with_header("X-Scoped", "yes"))
+                .get("/ping", |_| Response::new(200, "OK"))
+        });
+        let response = get(&router, "/api/ping").unwrap();
+        assert_eq!(response.headers.get("X-Scoped"), Some("yes"));
+        assert!(get(&router, "/ping").is_none());
+        assert!(!router.has_async_routes());
+    }
+
+    #[test]
+    fn fallbacks_run_inside_router_middleware_only() {
+        let router = Router::new()
+            .middleware(|request: &Request, next: Next<'_>| next(request).with_header("X-Site", "yes"))
+            .scope("/api", |api| api.middleware(|request: &Request, next: Next<'_>| next(request).with_header("X-Scoped", "yes")));
+        let fallback = |_: &Request| Response::new(404, "Not Found");
+        let response = router.handle_or(&request("GET /api/missing HTTP/1.1\r\nHost: example.com\r\n\r\n"), &fallback);
+        assert_eq!((response.status, response.headers.get("X-Site")), (404, Some("yes")));
+        assert_eq!(response.headers.get("X-Scoped"), None);
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn async_routes_are_reported_through_scopes() {
+        let router = Router::new().scope("/jobs", |jobs| jobs.get_async("/:id", |_| async { Response::new(200, "OK") }));
+        assert!(router.has_async_routes());
+        assert_eq!(get(&router, "/jobs/1").unwrap().status, 500);
+    }
+}
@@ -0,0 +1,578 @@
+
+// ============================================================
+//  DAEGONICA SOFTWARE — rsa.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: RSA
+//!
+//! **Purpose:**
+//! Verifies RSASSA-PKCS1-v1_5 SHA-256 signatures (JWT `RS256`) with the standard library only.
+//!
+//! **Context:**
+//! - Internal helper for bearer-token verification.
+//!
+//! **Responsibilities:**
+//! - Loads public keys from modulus/exponent bytes or PEM (`PUBLIC KEY` and `RSA PUBLIC KEY`).
+//! - Performs the public-key operation with Montgomery multiplication.
+//! - Does NOT sign, decrypt, or handle private keys; public operations need no side-channel care.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+use std::{cmp::Ordering, io};
+
+use crate::{base64, hash, request::invalid};
+
+
+/// # MIN_MODULUS_BITS
+///
+/// **Summary:**
+/// Smallest accepted key size.
+pub(crate) const MIN_MODULUS_BITS: usize = 2048;
+
+
+/// # SHA256_DIGEST_INFO
+///
+/// **Summary:**
+/// DER prefix of a PKCS#1 `DigestInfo` for SHA-256 (RFC 8017 §9.2).
+const SHA256_DIGEST_INFO: [u8; 19] = [
+    0x30, 0x31, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x01, 0x05, 0x00, 0x04, 0x20,
+];
+
+
+/// # RSA_ENCRYPTION_OID
+///
+/// **Summary:**
+/// DER-encoded object identifier `1.2.840.113549.1.1.1` (rsaEncryption).
+const RSA_ENCRYPTION_OID: [u8; 9] = [0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x01, 0x01];
+
+
+/// # RsaPublicKey
+///
+/// **Summary:**
+/// RSA public key prepared for repeated verification.
+///
+/// **Fields:**
+/// - `modulus`: `n` as little-endian 32-bit limbs.
+/// - `exponent`: Public exponent `e`.
+/// - `size`: Modulus length in bytes.
+/// - `n_prime`: `-n⁻¹ mod 2³²`, the Montgomery reduction factor.
+/// - `r_squared`: `R² mod n`, used to enter Montgomery form.
+///
+/// **Usage Example:**
+/// ```rust,ignore
+/// let key = RsaPublicKey::from_pem(&fs::read_to_string("public.pem")?)?;
+/// let valid = key.verify_sha256(signing_input, &signature);
+/// ```
+#[derive(Debug, Clone)]
+pub(crate) struct RsaPublicKey {
+    modulus: Vec<u32>,
+    exponent: u64,
+    size: usize,
+    n_prime: u32,
+    r_squared: Vec<u32>,
+}
+
+
+impl RsaPublicKey {
+
+    /// # from_components
+    ///
+    /// **Purpose:**
+    /// Builds a key from big-endian modulus and exponent bytes (as found in a JWK).
+    ///
+    /// **Parameters:**
+    /// - `modulus`: `n`, big-endian.
+    /// - `exponent`: `e`, big-endian.
+    ///
+    /// **Returns:**
+    /// - The prepared key.
+    ///
+    /// **Errors / Failures:**
+    /// - `InvalidData` for moduli under [`MIN_MODULUS_BITS`], even moduli, or unusable exponents.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let key = RsaPublicKey::from_components(&n, &[1, 0, 1])?;
+    /// ```
+    pub(crate) fn from_components(modulus: &[u8], exponent: &[u8]) -> io::Result<RsaPublicKey> {
+        let modulus = strip_zeros(modulus);
+        let exponent = strip_zeros(exponent);
+        if modulus.len() * 8 < MIN_MODULUS_BITS || modulus[modulus.len() - 1] & 1 == 0 {
+            return Err(invalid("RSA modulus too small or malformed"));
+        }
+        if exponent.is_empty() || exponent.len() > 8 || exponent == [1] {
+            return Err(invalid("unsupported RSA exponent"));
+        }
+
+        let limbs = to_limbs(modulus, modulus.len().div_ceil(4));
+        let mut inverse = 1u32;
+        for _ in 0..5 {
+            inverse = inverse.wrapping_mul(2u32.wrapping_sub(limbs[0].wrapping_mul(inverse)));
+        }
+
+        // R² mod n by doubling 1 modulo n, 2 · 32 · k times.
+        let mut r_squared = vec![0u32; limbs.len()];
+        r_squared[0] = 1;
+        for _ in 0..limbs.len() * 64 {
+            let carry = shift_left(&mut r_squared);
+            if carry || compare(&r_squared, &limbs) != Ordering::Less {
+                subtract(&mut r_squared, &limbs);
+            }
+        }
+
+        Ok(RsaPublicKey {
+            size: modulus.len(),
+            exponent: exponent.iter().fold(0, |value, byte| (value << 8) | u64::from(*byte)),
+            n_prime: inverse.wrapping_neg(),
+            modulus: limbs,
+            r_squared,
+        })
+    }
+
+    /// # from_pem
+    ///
+    /// **Purpose:**
+    /// Builds a key from a PEM `PUBLIC KEY` (SubjectPublicKeyInfo) or `RSA PUBLIC KEY` (PKCS#1) block.
+    ///
+    /// **Parameters:**
+    /// - `pem`: PEM text.
+    ///
+    /// **Returns:**
+    /// - The prepared key.
+    ///
+    /// **Errors / Failures:**
+    /// - `InvalidData` if the block is missing, malformed, or not an RSA key.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let key = RsaPublicKey::from_pem(&pem)?;
+    /// ```
+    pub(crate) fn from_pem(pem: &str) -> io::Result<RsaPublicKey> {
+        let malformed = || invalid("malformed RSA public key PEM");
+        let (label, der) = pem_block(pem).ok_or_else(malformed)?;
+        let pkcs1 = match label {
+            "RSA PUBLIC KEY" => der,
+            "PUBLIC KEY" => {
+                let (spki, _) = der_element(&der, 0x30).ok_or_else(malformed)?;
+                let (algorithm, rest) = der_element(spki, 0x30).ok_or_else(malformed)?;
+                let (oid, _) = der_element(algorithm, 0x06).ok_or_else(malformed)?;
+                if oid != RSA_ENCRYPTION_OID {
+                    return Err(invalid("PEM public key is not an RSA key"));
+                }
+                let (bits, _) = der_element(rest, 0x03).ok_or_else(malformed)?;
+                bits.strip_prefix(&[0]).ok_or_else(malformed)?.to_vec()
+            }
+            _ => return Err(malformed()),
+        };
+
+        let (sequence, _) = der_element(&pkcs1, 0x30).ok_or_else(malformed)?;
+        let (modulus, rest) = der_element(sequence, 0x02).ok_or_else(malformed)?;
+        let (exponent, _) = der_element(rest, 0x02).ok_or_else(malformed)?;
+        RsaPublicKey::from_components(modulus, exponent)
+    }
+
+    /// # verify_sha256
+    ///
+    /// **Purpose:**
+    /// Checks an RSASSA-PKCS1-v1_5 signature over the SHA-256 digest of a message.
+    ///
+    /// **Parameters:**
+    /// - `message`: Signed bytes.
+    /// - `signature`: Signature bytes, exactly the modulus length.
+    ///
+    /// **Returns:**
+    /// - `true` if the signature is valid.
+    ///
+    /// **Errors / Failures:**
+    /// None. Malformed signatures are simply invalid.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// if !key.verify_sha256(input.as_bytes(), &signature) { return Err("bad signature"); }
+    /// ```
+    pub(crate) fn verify_sha256(&self, message: &[u8], signature: &[u8]) -> bool {
+        if signature.len() != self.size {
+            return false;
+        }
+        let signature = to_limbs(signature, self.modulus.len());
+        if compare(&signature, &self.modulus) != Ordering::Less {
+            return false;
+        }
+
+        let encoded = from_limbs(&self.power(&signature), self.size);
+        let mut expected = vec![0x00, 0x01];
+        expected.resize(self.size - SHA256_DIGEST_INFO.len() - 33, 0xff);
+        expected.push(0x00);
+        expected.extend_from_slice(&SHA256_DIGEST_INFO);
+        expected.extend_from_slice(&hash::sha256(message));
+        encoded == expected
+    }
+
+    /// # power
+    ///
+    /// **Purpose:**
+    /// Computes `base^e mod n` by square-and-multiply in Montgomery form.
+    ///
+    /// **Parameters:**
+    /// - `base`: Value below `n`, as limbs.
+    ///
+    /// **Returns:**
+    /// - The result, as limbs.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let encoded = self.power(&signature);
+    /// ```
+    fn power(&self, base: &[u32]) -> Vec<u32> {
+        let mut one = vec![0u32; self.modulus.len()];
+        one[0] = 1;
+        let base = self.multiply(base, &self.r_squared);
+        let mut result = self.multiply(&one, &self.r_squared);
+        for bit in (0..64 - self.exponent.leading_zeros()).rev() {
+            result = self.multiply(&result, &result);
+            if self.exponent >> bit & 1 == 1 {
+                result = self.multiply(&result, &base);
+            }
+        }
+        self.multiply(&result, &one)
+    }
+
+    /// # multiply
+    ///
+    /// **Purpose:**
+    /// Montgomery product `a · b · R⁻¹ mod n` (coarsely integrated operand scanning).
+    ///
+    /// **Parameters:**
+    /// - `a`: First factor below `n`.
+    /// - `b`: Second factor below `n`.
+    ///
+    /// **Returns:**
+    /// - The reduced product.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let square = self.multiply(&x, &x);
+    /// ```
+    fn multiply(&self, a: &[u32], b: &[u32]) -> Vec<u32> {
+        let n = &self.modulus;
+        let k = n.len();
+        let mut t = vec![0u32; k + 2];
+        for &word in b {
+            let mut carry = 0u64;
+            for j in 0..k {
+                let sum = u64::from(t[j]) + u64::from(a[j]) * u64::from(word) + carry;
+                t[j] = sum as u32;
+                carry = sum >> 32;
+            }
+            let sum = u64::from(t[k]) + carry;
+            t[k] = sum as u32;
+            t[k + 1] = (sum >> 32) as u32;
+
+            let m = t[0].wrapping_mul(self.n_prime);
+            let mut carry = (u64::from(t[0]) + u64::from(m) * u64::from(n[0])) >> 32;
+            for j in 1..k {
+                let sum = u64::from(t[j]) + u64::from(m) * u64::from(n[j]) + carry;
+                t[j - 1] = sum as u32;
+                carry = sum >> 32;
+            }
+            let sum = u64::from(t[k]) + carry;
+            t[k - 1] = sum as u32;
+            t[k] = t[k + 1] + (sum >> 32) as u32;
+            t[k + 1] = 0;
+        }
+
+        let overflow = t[k] != 0;
+        t.truncate(k);
+        if overflow || compare(&t, n) != Ordering::Less {
+            subtract(&mut t, n);
+        }
+        t
+    }
+}
+
+
+/// # strip_zeros
+///
+/// **Purpose:**
+/// Drops leading zero bytes from a big-endian integer.
+///
+/// **Parameters:**
+/// - `bytes`: Big-endian integer.
+///
+/// **Returns:**
+/// - The significant bytes.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// assert_eq!(strip_zeros(&[0, 1, 0]), &[1, 0]);
+/// ```
+fn strip_zeros(bytes: &[u8]) -> &[u8] {
+    let start = bytes.iter().position(|byte| *byte != 0).unwrap_or(bytes.len());
+    &bytes[start..]
+}
+
+
+/// # to_limbs
+///
+/// **Purpose:**
+/// Converts a big-endian integer into little-endian 32-bit limbs.
+///
+/// **Parameters:**
+/// - `bytes`: Big-endian integer.
+/// - `limbs`: Number of limbs to produce (the value must fit).
+///
+/// **Returns:**
+/// - The limbs, zero-extended.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let value = to_limbs(&signature, key_limbs);
+/// ```
+fn to_limbs(bytes: &[u8], limbs: usize) -> Vec<u32> {
+    let mut out = vec![0u32; limbs];
+    for (index, byte) in bytes.iter().rev().enumerate() {
+        out[index / 4] |= u32::from(*byte) << (8 * (index % 4));
+    }
+    out
+}
+
+
+/// # from_limbs
+///
+/// **Purpose:**
+/// Converts little-endian limbs into a fixed-length big-endian byte string.
+///
+/// **Parameters:**
+/// - `limbs`: Integer limbs.
+/// - `size`: Output length in bytes.
+///
+/// **Returns:**
+/// - The big-endian bytes, left-padded with zeros.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let encoded = from_limbs(&value, 256);
+/// ```
+fn from_limbs(limbs: &[u32], size: usize) -> Vec<u8> {
+    (0..size)
+        .rev()
+        .map(|index| (limbs[index / 4] >> (8 * (index % 4))) as u8)
+        .collect()
+}
+
+
+/// # compare
+///
+/// **Purpose:**
+/// Compares two integers of equal limb count.
+///
+/// **Parameters:**
+/// - `a`: First integer.
+/// - `b`: Second integer.
+///
+/// **Returns:**
+/// - Their ordering.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// if compare(&x, &n) != Ordering::Less { subtract(&mut x, &n); }
+/// ```
+fn compare(a: &[u32], b: &[u32]) -> Ordering {
+    a.iter().rev().cmp(b.iter().rev())
+}
+
+
+/// # subtract
+///
+/// **Purpose:**
+/// Subtracts `b` from `a` in place, wrapping on underflow.
+///
+/// **Parameters:**
+/// - `a`: Minuend, overwritten with the difference.
+/// - `b`: Subtrahend of the same limb count.
+///
+/// **Returns:**
+/// None.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// subtract(&mut value, &modulus);
+/// ```
+fn subtract(a: &mut [u32], b: &[u32]) {
+    let mut borrow = false;
+    for (x, y) in a.iter_mut().zip(b) {
+        let (difference, under1) = x.overflowing_sub(*y);
+        let (difference, under2) = difference.overflowing_sub(u32::from(borrow));
+        *x = difference;
+        borrow = under1 || under2;
+    }
+}
+
+
+/// # shift_left
+///
+/// **Purpose:**
+/// Doubles an integer in place.
+///
+/// **Parameters:**
+/// - `a`: Integer to double.
+///
+/// **Returns:**
+/// - `true` if a bit was shifted out of the top limb.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let carry = shift_left(&mut value);
+/// ```
+fn shift_left(a: &mut [u32]) -> bool {
+    let mut carry = 0;
+    for limb in a.iter_mut() {
+        let next = *limb >> 31;
+        *limb = (*limb << 1) | carry;
+        carry = next;
+    }
+    carry == 1
+}
+
+
+/// # pem_block
+///
+/// **Purpose:**
+/// Extracts the label and decoded contents of the first PEM block.
+///
+/// **Parameters:**
+/// - `pem`: PEM text.
+///
+/// **Returns:**
+/// - `Some((label, der))`, or `None` if no well-formed block is present.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let (label, der) = pem_block(&pem)?;
+/// ```
+fn pem_block(pem: &str) -> Option<(&str, Vec<u8>)> {
+    let (_, rest) = pem.split_once("-----BEGIN ")?;
+    let (label, rest) = rest.split_once("-----")?;
+    let (body, _) = rest.split_once(&format!("-----END {label}-----"))?;
+    let text: String = body.chars().filter(|c| !c.is_whitespace()).collect();
+    Some((label, base64::decode(&text)?))
+}
+
+
+/// # der_element
+///
+/// **Purpose:**
+/// Reads one DER element with the expected tag.
+///
+/// **Parameters:**
+/// - `data`: DER bytes starting at the element.
+/// - `tag`: Expected tag byte (e.g. `0x30` for SEQUENCE).
+///
+/// **Returns:**
+/// - `Some((contents, rest))`, or `None` on a tag mismatch or truncated input.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let (sequence, _) = der_element(&der, 0x30)?;
+/// ```
+fn der_element(data: &[u8], tag: u8) -> Option<(&[u8], &[u8])> {
+    let (&found, rest) = data.split_first()?;
+    let (&first, mut rest) = rest.split_first()?;
+    if found != tag {
+        return None;
+    }
+    let length = if first < 0x80 {
+        usize::from(first)
+    } else {
+        let count = usize::from(first & 0x7f);
+        if count == 0 || count > 4 || rest.len() < count {
+            return None;
+        }
+        let (bytes, tail) = rest.split_at(count);
+        rest = tail;
+        bytes.iter().fold(0, |length, byte| (length << 8) | usize::from(*byte))
+    };
+    (rest.len() >= length).then(|| rest.split_at(length))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::base64;
+
+    const SPKI_PEM: &str = include_str!("../tests/data/rs256_public.pem");
+    const PKCS1_PEM: &str = include_str!("../tests/data/rs256_public_pkcs1.pem");
+    const MESSAGE: &[u8] = b"The quick brown fox jumps over the lazy dog";
+
+    // `openssl dgst -sha256 -sign` over MESSAGE with the private half of the test key.
+    const SIGNATURE: &str = "Z6b7ZABMG9ddGnswMeZuyy4AE7VlVnmlPeUA6VwWqSjqmFIuF9Ep8Jxn/LQrxe1iFqePiQDhruh1xUQJA+w0OXY1VGvG4/P5zM85VuUoN1PIkMx/H787ErZnQSlG8OUtXxqIk6nGhOCQsUMduryeddzWc/nDjlmoFsvq1EU/PI10oMvvjc++YYZIQnW7gpph2jO1P2aNz+00AORf5DqHwUyOaXgn15/IvovViCd9mt1hDU43UbODKoE0fkQGDav0Hr8TwgJBidn+8N19r3pdJ34Pye2TAvshSoXgvZTkKr+02jiYR3eYlU8fgr7wMDRlvbjBAXMRi6cBx34+5ke0Mw==";
+
+    #[test]
+    fn verifies_an_openssl_signature() {
+        let signature = base64::decode(SIGNATURE).unwrap();
+        for pem in [SPKI_PEM, PKCS1_PEM] {
+            let key = RsaPublicKey::from_pem(pem).unwrap();
+            assert!(key.verify_sha256(MESSAGE, &signature));
+        }
+    }
+
+    #[test]
+    fn rejects_tampered_signatures_and_messages() {
+        let key = RsaPublicKey::from_pem(SPKI_PEM).unwrap();
+        let signature = base64::decode(SIGNATURE).unwrap();
+        for index in [0, 100, signature.len() - 1] {
+            let mut tampered = signature.clone();
+            tampered[index] ^= 0x01;
+            assert!(!key.verify_sha256(MESSAGE, &tampered));
+        }
+        assert!(!key.verify_sha256(b"The quick brown fox jumps over the lazy cog", &signature));
+        assert!(!key.verify_sha256(MESSAGE, &signature[1..]));
+        assert!(!key.verify_sha256(MESSAGE, &[0xff; 256]));
+    }
+
+    #[test]
+    fn rejects_weak_or_malformed_keys() {
+        assert!(RsaPublicKey::from_components(&[0xc5; 128], &[1, 0, 1]).is_err());
+        assert!(RsaPublicKey::from_components(&[0xc4; 256], &[1, 0, 1]).is_err());
+        assert!(RsaPublicKey::from_components(&[0xc5; 256], &[1]).is_err());
+        assert!(RsaPublicKey::from_pem("-----BEGIN PUBLIC KEY-----\nAAAA\n-----END PUBLIC KEY-----\n").is_err());
+    }
+}
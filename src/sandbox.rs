@@ -0,0 +1,576 @@
+
+// ============================================================
+//  DAEGONICA SOFTWARE — sandbox.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: Sandbox
+//!
+//! **Purpose:**
+//! Opens files strictly beneath a directory handle, so a path that slipped through request
+//! handling with a `..`, an absolute link, or a link out of the tree still cannot reach
+//! anything outside the document root.
+//!
+//! **Context:**
+//! - Enabled per mount with `StaticFiles::sandbox`, which then opens every file, directory,
+//!   and streamed body through it.
+//!
+//! **Responsibilities:**
+//! - Holds the document root open as a directory handle, so renaming or replacing the root
+//!   path later does not move the sandbox.
+//! - On Linux, resolves with `openat2(2)` and `RESOLVE_BENEATH`, letting the kernel refuse any
+//!   step out of the root.
+//! - Elsewhere, and on kernels without `openat2`, walks the path one component at a time with
+//!   `openat(2)` and `O_NOFOLLOW` from the root handle, expanding relative links itself and
+//!   refusing absolute links and `..` above the root.
+//! - On non-Unix platforms, falls back to canonicalizing the path and checking its prefix.
+//! - Does NOT restrict links that stay inside the root; `SymlinkPolicy` decides those.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+use std::{fs::File, io, path::Path};
+
+#[cfg(unix)]
+use std::{
+    ffi::{CString, OsStr, OsString},
+    os::{
+        fd::{AsRawFd, FromRawFd},
+        unix::ffi::{OsStrExt, OsStringExt},
+    },
+    path::Component,
+};
+
+#[cfg(not(unix))]
+use std::path::PathBuf;
+
+#[cfg(target_os = "linux")]
+use std::sync::atomic::{AtomicBool, Ordering};
+
+
+/// # MAX_LINKS
+///
+/// **Summary:**
+/// Most symbolic links the userspace walk expands for one path, matching the kernel's limit.
+#[cfg(unix)]
+const MAX_LINKS: usize = 40;
+
+
+/// # RESOLVE_BENEATH
+///
+/// **Summary:**
+/// `openat2` flag refusing any resolution step that leaves the starting directory.
+#[cfg(target_os = "linux")]
+const RESOLVE_BENEATH: u64 = 0x08;
+
+
+/// # RESOLVE_NO_MAGICLINKS
+///
+/// **Summary:**
+/// `openat2` flag refusing `/proc` magic links, which could name files anywhere.
+#[cfg(target_os = "linux")]
+const RESOLVE_NO_MAGICLINKS: u64 = 0x02;
+
+
+/// # OpenHow
+///
+/// **Summary:**
+/// The kernel's `struct open_how`, the argument block of `openat2(2)`.
+///
+/// **Fields:**
+/// - `flags`: `open(2)` flags.
+/// - `mode`: Creation mode; zero since nothing is created.
+/// - `resolve`: `RESOLVE_*` flags.
+#[cfg(target_os = "linux")]
+#[repr(C)]
+struct OpenHow {
+    flags: u64,
+    mode: u64,
+    resolve: u64,
+}
+
+
+/// # Sandbox
+///
+/// **Summary:**
+/// A document root held open, from which files are opened beneath it only.
+///
+/// **Fields:**
+/// - `root`: Canonical root path, checked against on platforms without `openat(2)`.
+/// - `directory`: Handle to the root directory, on Unix.
+/// - `openat2`: Whether `openat2(2)` is still worth trying; cleared once the kernel or a
+///   seccomp filter refuses it.
+///
+/// **Usage Example:**
+/// ```rust,ignore
+/// let sandbox = Sandbox::open(Path::new("/srv/www"))?;
+/// let file = sandbox.open_beneath(Path::new("css/site.css"))?;
+/// ```
+#[derive(Debug)]
+pub(crate) struct Sandbox {
+    #[cfg(not(unix))]
+    root: PathBuf,
+    #[cfg(unix)]
+    directory: File,
+    #[cfg(target_os = "linux")]
+    openat2: AtomicBool,
+}
+
+
+impl Sandbox {
+
+    /// # open
+    ///
+    /// **Purpose:**
+    /// Opens a directory as a sandbox root.
+    ///
+    /// **Parameters:**
+    /// - `root`: Directory to confine opens to.
+    ///
+    /// **Returns:**
+    /// - The sandbox.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns an I/O error if `root` cannot be opened as a directory.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let sandbox = Sandbox::open(Path::new("html"))?;
+    /// ```
+    #[cfg(unix)]
+    pub(crate) fn open(root: &Path) -> io::Result<Sandbox> {
+        use std::{fs::OpenOptions, os::unix::fs::OpenOptionsExt};
+
+        let directory = OpenOptions::new().read(true).custom_flags(libc::O_DIRECTORY).open(root)?;
+        Ok(Sandbox {
+            directory,
+            #[cfg(target_os = "linux")]
+            openat2: AtomicBool::new(true),
+        })
+    }
+
+    /// # open
+    ///
+    /// **Purpose:**
+    /// Records a directory as a sandbox root on platforms without `openat(2)`.
+    ///
+    /// **Parameters:**
+    /// - `root`: Directory to confine opens to.
+    ///
+    /// **Returns:**
+    /// - The sandbox, holding the canonical root path.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns an I/O error if `root` cannot be resolved, or `NotADirectory` if it is not one.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let sandbox = Sandbox::open(Path::new("html"))?;
+    /// ```
+    #[cfg(not(unix))]
+    pub(crate) fn open(root: &Path) -> io::Result<Sandbox> {
+        let root = std::fs::canonicalize(root)?;
+        if !root.is_dir() {
+            return Err(io::Error::from(io::ErrorKind::NotADirectory));
+        }
+        Ok(Sandbox { root })
+    }
+
+    /// # open_beneath
+    ///
+    /// **Purpose:**
+    /// Opens a file or directory read-only, resolving every step of the path beneath the root.
+    ///
+    /// **Parameters:**
+    /// - `relative`: Path relative to the root; empty for the root itself.
+    ///
+    /// **Returns:**
+    /// - The open file.
+    ///
+    /// **Errors / Failures:**
+    /// - `PermissionDenied` if resolution would leave the root.
+    /// - Passes through other OS errors, such as `NotFound`.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let file = sandbox.open_beneath(Path::new("index.html"))?;
+    /// ```
+    pub(crate) fn open_beneath(&self, relative: &Path) -> io::Result<File> {
+        #[cfg(target_os = "linux")]
+        if self.openat2.load(Ordering::Relaxed) {
+            match self.openat2(relative) {
+                Err(err) if matches!(err.raw_os_error(), Some(libc::ENOSYS | libc::EPERM | libc::E2BIG)) => {
+                    self.openat2.store(false, Ordering::Relaxed);
+                }
+                Err(err) if err.raw_os_error() == Some(libc::EXDEV) => return Err(escaped(relative)),
+                result => return result,
+            }
+        }
+        self.walk(relative)
+    }
+
+    /// # openat2
+    ///
+    /// **Purpose:**
+    /// Opens a path beneath the root with the kernel enforcing containment.
+    ///
+    /// **Parameters:**
+    /// - `relative`: Path relative to the root.
+    ///
+    /// **Returns:**
+    /// - The open file.
+    ///
+    /// **Errors / Failures:**
+    /// - `EXDEV` if the path leaves the root; `ENOSYS` or `EPERM` if the call is unavailable.
+    /// - Passes through other OS errors.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let file = self.openat2(Path::new("a/b.txt"))?;
+    /// ```
+    #[cfg(target_os = "linux")]
+    fn openat2(&self, relative: &Path) -> io::Result<File> {
+        let name = c_name(if relative.as_os_str().is_empty() { OsStr::new(".") } else { relative.as_os_str() })?;
+        let how = OpenHow {
+            flags: (libc::O_RDONLY | libc::O_CLOEXEC) as u64,
+            mode: 0,
+            resolve: RESOLVE_BENEATH | RESOLVE_NO_MAGICLINKS,
+        };
+        // SAFETY: `name` is NUL-terminated and `how` is a valid `open_how` of the size passed,
+        // both alive for the duration of the call.
+        let fd = unsafe {
+            libc::syscall(
+                libc::SYS_openat2,
+                self.directory.as_raw_fd(),
+                name.as_ptr(),
+                &raw const how,
+                std::mem::size_of::<OpenHow>(),
+            )
+        };
+        if fd < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        // SAFETY: the kernel returned a fresh descriptor that nothing else owns.
+        Ok(unsafe { File::from_raw_fd(fd as i32) })
+    }
+
+    /// # walk
+    ///
+    /// **Purpose:**
+    /// Resolves a path beneath the root in userspace, opening one component at a time from
+    /// the directory reached so far without following links, and expanding relative link
+    /// targets in place.
+    ///
+    /// **Parameters:**
+    /// - `relative`: Path relative to the root.
+    ///
+    /// **Returns:**
+    /// - The open file.
+    ///
+    /// **Errors / Failures:**
+    /// - `PermissionDenied` for an absolute path or link target, or a `..` above the root.
+    /// - `ELOOP` after `MAX_LINKS` link expansions.
+    /// - Passes through other OS errors.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let file = self.walk(Path::new("docs/../index.html"))?;
+    /// ```
+    #[cfg(unix)]
+    fn walk(&self, relative: &Path) -> io::Result<File> {
+        let mut pending: Vec<OsString> = Vec::new();
+        push_components(&mut pending, relative).ok_or_else(|| escaped(relative))?;
+        let mut opened: Vec<File> = Vec::new();
+        let mut links = 0;
+        while let Some(name) = pending.pop() {
+            if name == ".." {
+                opened.pop().ok_or_else(|| escaped(relative))?;
+                continue;
+            }
+            let directory = opened.last().unwrap_or(&self.directory);
+            let flags = if pending.is_empty() { 0 } else { libc::O_DIRECTORY };
+            match openat(directory, &name, flags) {
+                Ok(file) => opened.push(file),
+                Err(err) => {
+                    let Ok(target) = readlinkat(directory, &name) else {
+                        return Err(err);
+                    };
+                    links += 1;
+                    if links > MAX_LINKS {
+                        return Err(io::Error::from_raw_os_error(libc::ELOOP));
+                    }
+                    push_components(&mut pending, Path::new(&target)).ok_or_else(|| escaped(relative))?;
+                }
+            }
+        }
+        match opened.pop() {
+            Some(file) => Ok(file),
+            None => self.directory.try_clone(),
+        }
+    }
+
+    /// # walk
+    ///
+    /// **Purpose:**
+    /// Opens a path beneath the root after checking that its canonical form stays there.
+    ///
+    /// **Parameters:**
+    /// - `relative`: Path relative to the root.
+    ///
+    /// **Returns:**
+    /// - The open file.
+    ///
+    /// **Errors / Failures:**
+    /// - `PermissionDenied` if the canonical path lies outside the root.
+    /// - Passes through other OS errors.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let file = self.walk(Path::new("index.html"))?;
+    /// ```
+    #[cfg(not(unix))]
+    fn walk(&self, relative: &Path) -> io::Result<File> {
+        let target = std::fs::canonicalize(self.root.join(relative))?;
+        if !target.starts_with(&self.root) {
+            return Err(escaped(relative));
+        }
+        File::open(target)
+    }
+}
+
+
+/// # push_components
+///
+/// **Purpose:**
+/// Queues a relative path's components for the userspace walk, first component last.
+///
+/// **Parameters:**
+/// - `pending`: Stack of components still to resolve.
+/// - `path`: Relative path or link target.
+///
+/// **Returns:**
+/// - `Some(())`, or `None` if the path is absolute.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// push_components(&mut pending, Path::new("../shared/logo.svg"))?;
+/// ```
+#[cfg(unix)]
+fn push_components(pending: &mut Vec<OsString>, path: &Path) -> Option<()> {
+    let mut names = Vec::new();
+    for component in path.components() {
+        match component {
+            Component::Normal(name) => names.push(name.to_os_string()),
+            Component::ParentDir => names.push(OsString::from("..")),
+            Component::CurDir => {}
+            Component::RootDir | Component::Prefix(_) => return None,
+        }
+    }
+    pending.extend(names.into_iter().rev());
+    Some(())
+}
+
+
+/// # openat
+///
+/// **Purpose:**
+/// Opens one name inside a directory read-only, without following a link.
+///
+/// **Parameters:**
+/// - `directory`: Directory holding the name.
+/// - `name`: Single path component.
+/// - `flags`: Extra `open(2)` flags, such as `O_DIRECTORY`.
+///
+/// **Returns:**
+/// - The open file.
+///
+/// **Errors / Failures:**
+/// - Passes through OS errors; a link fails rather than being followed.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let file = openat(&directory, OsStr::new("index.html"), 0)?;
+/// ```
+#[cfg(unix)]
+fn openat(directory: &File, name: &OsStr, flags: libc::c_int) -> io::Result<File> {
+    let name = c_name(name)?;
+    // SAFETY: `name` is NUL-terminated and `directory` is an open descriptor.
+    let fd = unsafe {
+        libc::openat(directory.as_raw_fd(), name.as_ptr(), libc::O_RDONLY | libc::O_CLOEXEC | libc::O_NOFOLLOW | flags)
+    };
+    if fd < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    // SAFETY: the kernel returned a fresh descriptor that nothing else owns.
+    Ok(unsafe { File::from_raw_fd(fd) })
+}
+
+
+/// # readlinkat
+///
+/// **Purpose:**
+/// Reads the target of a symbolic link inside a directory.
+///
+/// **Parameters:**
+/// - `directory`: Directory holding the link.
+/// - `name`: Single path component.
+///
+/// **Returns:**
+/// - The link's target, as stored.
+///
+/// **Errors / Failures:**
+/// - Passes through OS errors, `EINVAL` when `name` is not a link.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let target = readlinkat(&directory, OsStr::new("current"))?;
+/// ```
+#[cfg(unix)]
+fn readlinkat(directory: &File, name: &OsStr) -> io::Result<OsString> {
+    let name = c_name(name)?;
+    let mut target = vec![0u8; 4096];
+    // SAFETY: `name` is NUL-terminated and `target` has room for the length passed.
+    let len = unsafe { libc::readlinkat(directory.as_raw_fd(), name.as_ptr(), target.as_mut_ptr().cast(), target.len()) };
+    if len < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    target.truncate(len as usize);
+    Ok(OsString::from_vec(target))
+}
+
+
+/// # c_name
+///
+/// **Purpose:**
+/// Converts a path for a system call.
+///
+/// **Parameters:**
+/// - `name`: Path or component.
+///
+/// **Returns:**
+/// - The NUL-terminated name.
+///
+/// **Errors / Failures:**
+/// - `InvalidInput` if `name` contains a NUL byte.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let name = c_name(OsStr::new("index.html"))?;
+/// ```
+#[cfg(unix)]
+fn c_name(name: &OsStr) -> io::Result<CString> {
+    CString::new(name.as_bytes()).map_err(|_| io::Error::from(io::ErrorKind::InvalidInput))
+}
+
+
+/// # escaped
+///
+/// **Purpose:**
+/// Builds the error for a path that would leave the sandbox.
+///
+/// **Parameters:**
+/// - `relative`: Offending path.
+///
+/// **Returns:**
+/// - A `PermissionDenied` error naming the path.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// return Err(escaped(relative));
+/// ```
+fn escaped(relative: &Path) -> io::Error {
+    io::Error::new(io::ErrorKind::PermissionDenied, format!("{} escapes the document root", relative.display()))
+}
+
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::{fs, io::Read, os::unix::fs::symlink, path::PathBuf};
+
+    // A document root beside a directory it must not reach, removed when dropped.
+    struct Tree(PathBuf);
+
+    impl Tree {
+        fn new(name: &str) -> Tree {
+            let base = std::env::temp_dir().join(format!("sandbox-test-{}-{name}", std::process::id()));
+            let _ = fs::remove_dir_all(&base);
+            fs::create_dir_all(base.join("root/sub")).unwrap();
+            fs::create_dir_all(base.join("outside")).unwrap();
+            fs::write(base.join("root/a.txt"), "a").unwrap();
+            fs::write(base.join("root/sub/b.txt"), "b").unwrap();
+            fs::write(base.join("outside/secret.txt"), "secret").unwrap();
+            symlink("sub/b.txt", base.join("root/inside")).unwrap();
+            symlink("../a.txt", base.join("root/sub/up")).unwrap();
+            symlink("../outside/secret.txt", base.join("root/out")).unwrap();
+            symlink(base.join("outside/secret.txt"), base.join("root/absolute")).unwrap();
+            symlink("loop", base.join("root/loop")).unwrap();
+            Tree(base)
+        }
+
+        fn sandbox(&self) -> Sandbox {
+            Sandbox::open(&self.0.join("root")).unwrap()
+        }
+    }
+
+    impl Drop for Tree {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn read(file: io::Result<File>) -> String {
+        let mut contents = String::new();
+        file.unwrap().read_to_string(&mut contents).unwrap();
+        contents
+    }
+
+    fn check(open: impl Fn(&Path) -> io::Result<File>) {
+        assert_eq!(read(open(Path::new("a.txt"))), "a");
+        assert_eq!(read(open(Path::new("./sub/../a.txt"))), "a");
+        assert_eq!(read(open(Path::new("inside"))), "b");
+        assert_eq!(read(open(Path::new("sub/up"))), "a");
+        assert!(open(Path::new("")).unwrap().metadata().unwrap().is_dir());
+        for escape in ["../outside/secret.txt", "sub/../../outside/secret.txt", "out", "absolute", "/etc/passwd"] {
+            assert!(open(Path::new(escape)).is_err(), "{escape}");
+        }
+        assert!(open(Path::new("missing")).is_err());
+        assert!(open(Path::new("loop")).is_err());
+    }
+
+    #[test]
+    fn opens_only_beneath_the_root() {
+        let tree = Tree::new("beneath");
+        let sandbox = tree.sandbox();
+        check(|path| sandbox.open_beneath(path));
+    }
+
+    #[test]
+    fn the_component_walk_refuses_the_same_escapes() {
+        let tree = Tree::new("walk");
+        let sandbox = tree.sandbox();
+        check(|path| sandbox.walk(path));
+        assert_eq!(sandbox.walk(Path::new("out")).unwrap_err().kind(), io::ErrorKind::PermissionDenied);
+        assert_eq!(sandbox.walk(Path::new("absolute")).unwrap_err().kind(), io::ErrorKind::PermissionDenied);
+    }
+
+    #[test]
+    fn the_root_stays_put_when_its_path_moves() {
+        let tree = Tree::new("moved");
+        let sandbox = tree.sandbox();
+        fs::rename(tree.0.join("root"), tree.0.join("renamed")).unwrap();
+        fs::create_dir(tree.0.join("root")).unwrap();
+        assert_eq!(read(sandbox.open_beneath(Path::new("a.txt"))), "a");
+    }
+}
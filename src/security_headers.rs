@@ -0,0 +1,347 @@
+
+// ============================================================
+//  DAEGONICA SOFTWARE — security_headers.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: SecurityHeaders
+//!
+//! **Purpose:**
+//! Adds browser hardening headers to every response of a site: routes, static files, and the
+//! not-found page.
+//!
+//! **Context:**
+//! - Installed as router middleware with `Router::middleware(headers.middleware())`; router-level
+//!   middleware also wraps the site's static files and not-found answer.
+//!
+//! **Responsibilities:**
+//! - Sets `X-Content-Type-Options`, `X-Frame-Options`, and `Referrer-Policy` by default.
+//! - Sets a configurable `Content-Security-Policy`.
+//! - Applies per-route overrides selected by path pattern; headers a handler sets itself are kept.
+//! - Does NOT emit `Strict-Transport-Security`, which only belongs on HTTPS responses.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+use crate::{glob::Glob, request::Request, response::Response, router::Next};
+
+
+/// # SecurityHeaders
+///
+/// **Summary:**
+/// Set of security headers, with optional per-route variants.
+///
+/// **Fields:**
+/// - `headers`: Header names and values to add.
+/// - `overrides`: Route patterns with the header set to use instead, checked in order.
+///
+/// **Usage Example:**
+/// ```rust
+/// # use server::{request::Request, response::Response, router::Router, security_headers::SecurityHeaders};
+/// # fn home(_: &Request) -> Response { Response::new(200, "OK") }
+/// let headers = SecurityHeaders::new()
+///     .content_security_policy("default-src 'self'")
+///     .route("/embed/**", SecurityHeaders::new().frame_options("SAMEORIGIN"));
+/// let router = Router::new().middleware(headers.middleware()).get("/", home);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SecurityHeaders {
+    headers: Vec<(String, String)>,
+    overrides: Vec<(Glob, SecurityHeaders)>,
+}
+
+
+impl Default for SecurityHeaders {
+
+    /// # default
+    ///
+    /// **Purpose:**
+    /// Creates the default header set.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - `X-Content-Type-Options: nosniff`, `X-Frame-Options: DENY`, and
+    ///   `Referrer-Policy: strict-origin-when-cross-origin`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::security_headers::SecurityHeaders;
+    /// let headers = SecurityHeaders::default();
+    /// ```
+    fn default() -> SecurityHeaders {
+        SecurityHeaders {
+            headers: vec![
+                ("X-Content-Type-Options".to_string(), "nosniff".to_string()),
+                ("X-Frame-Options".to_string(), "DENY".to_string()),
+                ("Referrer-Policy".to_string(), "strict-origin-when-cross-origin".to_string()),
+            ],
+            overrides: Vec::new(),
+        }
+    }
+}
+
+
+impl SecurityHeaders {
+
+    /// # new
+    ///
+    /// **Purpose:**
+    /// Creates the default header set (see `default`).
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - A new `SecurityHeaders`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::security_headers::SecurityHeaders;
+    /// let headers = SecurityHeaders::new();
+    /// ```
+    pub fn new() -> SecurityHeaders {
+        SecurityHeaders::default()
+    }
+
+    /// # content_security_policy
+    ///
+    /// **Purpose:**
+    /// Sets the `Content-Security-Policy` header.
+    ///
+    /// **Parameters:**
+    /// - `policy`: Policy directives.
+    ///
+    /// **Returns:**
+    /// - The updated `SecurityHeaders`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::security_headers::SecurityHeaders;
+    /// let headers = SecurityHeaders::new().content_security_policy("default-src 'self'; img-src *");
+    /// ```
+    pub fn content_security_policy(self, policy: &str) -> SecurityHeaders {
+        self.header("Content-Security-Policy", policy)
+    }
+
+    /// # frame_options
+    ///
+    /// **Purpose:**
+    /// Sets the `X-Frame-Options` header.
+    ///
+    /// **Parameters:**
+    /// - `value`: `DENY` (default) or `SAMEORIGIN`.
+    ///
+    /// **Returns:**
+    /// - The updated `SecurityHeaders`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::security_headers::SecurityHeaders;
+    /// let headers = SecurityHeaders::new().frame_options("SAMEORIGIN");
+    /// ```
+    pub fn frame_options(self, value: &str) -> SecurityHeaders {
+        self.header("X-Frame-Options", value)
+    }
+
+    /// # referrer_policy
+    ///
+    /// **Purpose:**
+    /// Sets the `Referrer-Policy` header.
+    ///
+    /// **Parameters:**
+    /// - `policy`: Policy name (e.g. `no-referrer`).
+    ///
+    /// **Returns:**
+    /// - The updated `SecurityHeaders`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::security_headers::SecurityHeaders;
+    /// let headers = SecurityHeaders::new().referrer_policy("no-referrer");
+    /// ```
+    pub fn referrer_policy(self, policy: &str) -> SecurityHeaders {
+        self.header("Referrer-Policy", policy)
+    }
+
+    /// # header
+    ///
+    /// **Purpose:**
+    /// Adds or replaces any header in the set (e.g. `Permissions-Policy`).
+    ///
+    /// **Parameters:**
+    /// - `name`: Header name.
+    /// - `value`: Header value.
+    ///
+    /// **Returns:**
+    /// - The updated `SecurityHeaders`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::security_headers::SecurityHeaders;
+    /// let headers = SecurityHeaders::new().header("Permissions-Policy", "camera=()");
+    /// ```
+    pub fn header(mut self, name: &str, value: &str) -> SecurityHeaders {
+        self.headers.retain(|(existing, _)| !existing.eq_ignore_ascii_case(name));
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// # without
+    ///
+    /// **Purpose:**
+    /// Removes a header from the set.
+    ///
+    /// **Parameters:**
+    /// - `name`: Header name.
+    ///
+    /// **Returns:**
+    /// - The updated `SecurityHeaders`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::security_headers::SecurityHeaders;
+    /// let embeddable = SecurityHeaders::new().without("X-Frame-Options");
+    /// ```
+    pub fn without(mut self, name: &str) -> SecurityHeaders {
+        self.headers.retain(|(existing, _)| !existing.eq_ignore_ascii_case(name));
+        self
+    }
+
+    /// # route
+    ///
+    /// **Purpose:**
+    /// Uses a different header set for request paths matching a pattern. The first matching
+    /// override wins; unmatched paths use this set.
+    ///
+    /// **Parameters:**
+    /// - `pattern`: Path glob (e.g. `/widgets/**`).
+    /// - `headers`: Header set for those paths.
+    ///
+    /// **Returns:**
+    /// - The updated `SecurityHeaders`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::security_headers::SecurityHeaders;
+    /// let headers = SecurityHeaders::new().route("/docs/**", SecurityHeaders::new().content_security_policy("default-src *"));
+    /// ```
+    pub fn route(mut self, pattern: &str, headers: SecurityHeaders) -> SecurityHeaders {
+        self.overrides.push((Glob::new(pattern), headers));
+        self
+    }
+
+    /// # middleware
+    ///
+    /// **Purpose:**
+    /// Turns the header set into router middleware.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - A closure accepted by `Router::middleware`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{router::Router, security_headers::SecurityHeaders};
+    /// let router = Router::new().middleware(SecurityHeaders::new().middleware());
+    /// ```
+    pub fn middleware(self) -> impl Fn(&Request, Next<'_>) -> Response + Send + Sync + 'static {
+        move |request, next| self.handle(request, next)
+    }
+
+    /// # handle
+    ///
+    /// **Purpose:**
+    /// Runs the rest of the chain and adds the headers selected for the request path,
+    /// leaving any header the handler already set.
+    ///
+    /// **Parameters:**
+    /// - `request`: Request being handled.
+    /// - `next`: The rest of the middleware chain.
+    ///
+    /// **Returns:**
+    /// - The downstream response with security headers.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{request::Request, response::Response, router::Next, security_headers::SecurityHeaders};
+    /// # let headers = SecurityHeaders::new();
+    /// # let request = &Request::read_from(&mut &b"GET / HTTP/1.1\r\n\r\n"[..]).unwrap();
+    /// # let next: Next<'_> = &|_| Response::new(200, "OK");
+    /// let response = headers.handle(request, next);
+    /// # assert!(response.headers.get("X-Content-Type-Options").is_some());
+    /// ```
+    pub fn handle(&self, request: &Request, next: Next<'_>) -> Response {
+        let mut response = next(request);
+        for (name, value) in &self.select(request.path()).headers {
+            if !response.headers.contains(name) {
+                response.headers.set(name.as_str(), value.as_str());
+            }
+        }
+        response
+    }
+
+    /// # select
+    ///
+    /// **Purpose:**
+    /// Picks the header set for a path.
+    ///
+    /// **Parameters:**
+    /// - `path`: Request path.
+    ///
+    /// **Returns:**
+    /// - The first matching override, or this set.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let headers = self.select("/embed/map");
+    /// ```
+    fn select(&self, path: &str) -> &SecurityHeaders {
+        self.overrides
+            .iter()
+            .find(|(pattern, _)| pattern.matches(path))
+            .map(|(_, headers)| headers.select(path))
+            .unwrap_or(self)
+    }
+}
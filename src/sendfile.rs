@@ -0,0 +1,202 @@
+
+// ============================================================
+//  DAEGONICA SOFTWARE — sendfile.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: Sendfile
+//!
+//! **Purpose:**
+//! Copies file bytes to a socket inside the kernel, without passing them through a userspace
+//! buffer.
+//!
+//! **Context:**
+//! - Used by `ClientStream::send_file` when a response body is streamed from a file.
+//!
+//! **Responsibilities:**
+//! - Wraps `sendfile(2)` on Linux and macOS, whose signatures differ.
+//! - Reports `Unsupported` where the call is missing or refuses the file, so callers can fall
+//!   back to buffered writes.
+//! - Does NOT loop, pace, or apply timeouts; each call sends at most one batch.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+use std::{fs::File, io, net::TcpStream};
+
+
+/// # send
+///
+/// **Purpose:**
+/// Sends up to `count` bytes of `file`, starting at `offset`, to `stream`. The file position
+/// is left unchanged.
+///
+/// **Parameters:**
+/// - `stream`: Destination socket; its write timeout applies.
+/// - `file`: Source file.
+/// - `offset`: Position in the file of the first byte to send.
+/// - `count`: Largest number of bytes to send.
+///
+/// **Returns:**
+/// - The number of bytes sent; `0` once `offset` reaches the end of the file.
+///
+/// **Errors / Failures:**
+/// - `Unsupported` if the platform cannot send this file to this socket.
+/// - Passes through other OS errors, including `WouldBlock` when the write timeout expires.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let sent = sendfile::send(stream, &file, 0, 1 << 20)?;
+/// ```
+#[cfg(target_os = "linux")]
+pub(crate) fn send(stream: &TcpStream, file: &File, offset: u64, count: usize) -> io::Result<usize> {
+    use std::os::fd::AsRawFd;
+
+    let mut offset = libc::off_t::try_from(offset).map_err(|_| io::Error::from(io::ErrorKind::Unsupported))?;
+    // SAFETY: both descriptors are open for the duration of the call and `offset` is a valid
+    // `off_t` the kernel updates in place.
+    let sent = unsafe { libc::sendfile(stream.as_raw_fd(), file.as_raw_fd(), &raw mut offset, count) };
+    if sent < 0 {
+        return Err(unsupported(io::Error::last_os_error()));
+    }
+    Ok(sent as usize)
+}
+
+
+/// # send
+///
+/// **Purpose:**
+/// Sends up to `count` bytes of `file`, starting at `offset`, to `stream`. The file position
+/// is left unchanged.
+///
+/// **Parameters:**
+/// - `stream`: Destination socket; its write timeout applies.
+/// - `file`: Source file.
+/// - `offset`: Position in the file of the first byte to send.
+/// - `count`: Largest number of bytes to send.
+///
+/// **Returns:**
+/// - The number of bytes sent; `0` once `offset` reaches the end of the file.
+///
+/// **Errors / Failures:**
+/// - `Unsupported` if the platform cannot send this file to this socket.
+/// - Passes through other OS errors, including `WouldBlock` when the write timeout expires
+///   before anything was sent.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let sent = sendfile::send(stream, &file, 0, 1 << 20)?;
+/// ```
+#[cfg(target_os = "macos")]
+pub(crate) fn send(stream: &TcpStream, file: &File, offset: u64, count: usize) -> io::Result<usize> {
+    use std::os::fd::AsRawFd;
+
+    let offset = libc::off_t::try_from(offset).map_err(|_| io::Error::from(io::ErrorKind::Unsupported))?;
+    let mut len = libc::off_t::try_from(count).unwrap_or(libc::off_t::MAX);
+    // SAFETY: both descriptors are open for the duration of the call, `len` is a valid
+    // `off_t` the kernel updates in place, and no header/trailer vectors are passed.
+    let result = unsafe {
+        libc::sendfile(file.as_raw_fd(), stream.as_raw_fd(), offset, &raw mut len, std::ptr::null_mut(), 0)
+    };
+    // macOS reports partial progress alongside EAGAIN and EINTR; keep what was sent.
+    if result < 0 && len == 0 {
+        return Err(unsupported(io::Error::last_os_error()));
+    }
+    Ok(len as usize)
+}
+
+
+/// # send
+///
+/// **Purpose:**
+/// Stands in for `sendfile(2)` on platforms without it.
+///
+/// **Parameters:**
+/// - `stream`: Destination socket.
+/// - `file`: Source file.
+/// - `offset`: Position of the first byte.
+/// - `count`: Largest number of bytes.
+///
+/// **Returns:**
+/// Never returns a count.
+///
+/// **Errors / Failures:**
+/// - Always `Unsupported`.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let sent = sendfile::send(stream, &file, 0, 1 << 20)?;
+/// ```
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+pub(crate) fn send(_stream: &TcpStream, _file: &File, _offset: u64, _count: usize) -> io::Result<usize> {
+    Err(io::Error::from(io::ErrorKind::Unsupported))
+}
+
+
+/// # unsupported
+///
+/// **Purpose:**
+/// Maps the errors `sendfile(2)` raises for unsuitable descriptors onto `Unsupported`.
+///
+/// **Parameters:**
+/// - `error`: Error from the call.
+///
+/// **Returns:**
+/// - `Unsupported` for `EINVAL`, `ENOSYS`, `ENOTSOCK`, and `EOPNOTSUPP`; otherwise `error`.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// return Err(unsupported(io::Error::last_os_error()));
+/// ```
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn unsupported(error: io::Error) -> io::Error {
+    match error.raw_os_error() {
+        Some(libc::EINVAL | libc::ENOSYS | libc::ENOTSOCK | libc::EOPNOTSUPP) => io::Error::from(io::ErrorKind::Unsupported),
+        _ => error,
+    }
+}
+
+
+#[cfg(all(test, any(target_os = "linux", target_os = "macos")))]
+mod tests {
+    use super::*;
+    use std::{
+        env,
+        fs,
+        io::{Read, Seek},
+        net::TcpListener,
+        process,
+    };
+
+    #[test]
+    fn sends_the_requested_range_without_moving_the_file_position() {
+        let path = env::temp_dir().join(format!("sendfile-test-{}.bin", process::id()));
+        let contents: Vec<u8> = (0..=255u8).cycle().take(4096).collect();
+        fs::write(&path, &contents).unwrap();
+        let mut file = File::open(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let mut client = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        let (server, _) = listener.accept().unwrap();
+        let mut sent = 0;
+        while sent < 1000 {
+            sent += send(&server, &file, 100 + sent as u64, 1000 - sent).unwrap();
+        }
+        assert_eq!(send(&server, &file, 4096, 10).unwrap(), 0, "nothing is left at the end");
+        drop(server);
+
+        let mut received = Vec::new();
+        client.read_to_end(&mut received).unwrap();
+        assert_eq!(received, &contents[100..1100]);
+        assert_eq!(file.stream_position().unwrap(), 0);
+    }
+}
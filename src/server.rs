@@ -0,0 +1,3057 @@
+
+// ============================================================
+//  DAEGONICA SOFTWARE — server.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: Server
+//!
+//! **Purpose:**
+//! Accepts TCP connections and serves HTTP requests using the thread pool, virtual hosts, proxy, and cache.
+//!
+//! **Context:**
+//! - Built by the server binary from command-line flags; usable directly by embedding applications.
+//!
+//! **Responsibilities:**
+//! - Owns the listener configuration, worker count, and shared request-handling state.
+//! - Selects a virtual host per request from its `Host` header and applies its rewrite rules.
+//! - Rejects requests whose `Host` header is missing, repeated, malformed, or not allowed.
+//! - Answers pipelined requests on a connection strictly in order.
+//! - Keeps connections open for further requests within the keep-alive limits, optionally
+//!   parking them in an event loop between requests so idle connections hold no worker.
+//! - Accepts connections and waits for their request heads in an `io_uring` when built with
+//!   the `io_uring` feature and enabled, submitting the workers' socket I/O to it in batches.
+//! - Takes the client address from a PROXY protocol preamble when behind a load balancer.
+//! - Answers liveness and readiness probes when enabled.
+//! - Serves the operator API on a separate listener when enabled, including drain and shutdown.
+//! - Drains on `SIGTERM` when enabled, closing whatever is still open once a deadline passes.
+//! - Serves on a thread of its own with `start`, returning a handle that stops it on demand.
+//! - Runs registered background tasks while serving and stops them when it stops.
+//! - Keeps traffic metrics and per-route request timings, and answers their endpoint when one
+//!   is configured.
+//! - Serves HTTP/1.0 clients: `Host` is optional, bodies are `Content-Length` framed, and
+//!   connections close after the response unless the client asks to keep them.
+//! - Routes each request through the forward proxy, response cache, reverse proxy, or local handlers.
+//! - Sends `103 Early Hints` with the preload links declared for a path before its response.
+//! - Runs inside an existing tokio runtime with `serve_async` when built with the `async`
+//!   feature, accepting on the runtime and serving each connection synchronously on one of
+//!   its blocking threads; `run` and `start` refuse async routes, which need that runtime.
+//! - Does NOT terminate TLS.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+use std::{
+    io::{self, BufReader, Read, Write},
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, Shutdown, SocketAddr, TcpListener, TcpStream},
+    sync::{
+        Arc,
+        Weak,
+        atomic::{AtomicBool, Ordering},
+    },
+    thread,
+    time::{Duration, Instant},
+};
+#[cfg(all(feature = "io_uring", target_os = "linux"))]
+use std::mem;
+#[cfg(any(target_os = "linux", target_os = "macos", target_os = "freebsd"))]
+use std::sync::OnceLock;
+
+#[cfg(feature = "otel")]
+use crate::trace::{self, Tracer};
+#[cfg(any(target_os = "linux", target_os = "macos", target_os = "freebsd"))]
+use crate::event_loop::EventLoop;
+#[cfg(all(feature = "io_uring", target_os = "linux"))]
+use crate::uring::Uring;
+#[cfg(windows)]
+use crate::service;
+use crate::{
+    Spawner,
+    ThreadPool,
+    admin::{Admin, AdminListener},
+    buffer_pool::BufferPool,
+    cache::ResponseCache,
+    cancel::Cancellation,
+    concurrency::{ConcurrencyLimit, Permit},
+    connection::{ConnectionInfo, ConnectionRegistry, Registration},
+    dev::DevReload,
+    duplicates::DuplicatePolicy,
+    early_hints::EarlyHints,
+    health::Health,
+    hsts::Hsts,
+    ip::{self, Cidr},
+    keep_alive::KeepAlive,
+    limits::{LimitExceeded, Limits},
+    log::{self, LogLevel},
+    metrics::{Gauge, Metrics},
+    peer_limit::{PeerLimit, PeerPermit},
+    privileges::Privileges,
+    proxy::{ForwardProxy, Proxy},
+    proxy_protocol::{self, ProxyProtocol},
+    request::{Request, RequestError, read_body_within},
+    response::Response,
+    signal,
+    socket::SocketOptions,
+    state::AppState,
+    tasks::Tasks,
+    throttle::Throttle,
+    timeouts::{ClientStream, Timeouts},
+    validate::json_string,
+    vhost::{HostPattern, VirtualHost},
+};
+
+
+/// # DEFAULT_CACHE_BYTES
+///
+/// **Summary:**
+/// Byte budget of the shared response cache unless configured otherwise.
+const DEFAULT_CACHE_BYTES: usize = 16 * 1024 * 1024;
+
+
+/// # DEFAULT_SERVER_HEADER
+///
+/// **Summary:**
+/// `Server` header value sent unless configured otherwise.
+const DEFAULT_SERVER_HEADER: &str = "Daegonica";
+
+
+/// # REJECTION_WRITE_TIMEOUT
+///
+/// **Summary:**
+/// Time allowed to write an error response to a client whose request could not be read.
+const REJECTION_WRITE_TIMEOUT: Duration = Duration::from_secs(1);
+
+
+/// # LINGER_TIME
+///
+/// **Summary:**
+/// Longest time spent discarding input from a refused client before closing.
+const LINGER_TIME: Duration = Duration::from_secs(2);
+
+
+/// # LINGER_BYTES
+///
+/// **Summary:**
+/// Most input discarded from a refused client before closing.
+const LINGER_BYTES: usize = 1024 * 1024;
+
+
+/// # DRAIN_POLL
+///
+/// **Summary:**
+/// How often the `SIGTERM` watcher checks for the signal, and for open connections while
+/// draining.
+const DRAIN_POLL: Duration = Duration::from_millis(100);
+
+
+/// # Server
+///
+/// **Summary:**
+/// HTTP server configuration and shared request-handling state.
+///
+/// **Fields:**
+/// - `addr`: Address to bind.
+/// - `workers`: Number of worker threads.
+/// - `proxy`: Upstream proxy; when set, requests are forwarded instead of served locally.
+/// - `forward_proxy`: Forward proxy for absolute-form and `CONNECT` requests, if enabled.
+/// - `cache`: Shared response cache for `GET` requests on opted-in routes.
+/// - `hosts`: Named virtual hosts, checked in order.
+/// - `default_host`: Site used when no named host matches.
+/// - `state`: Application state attached to every request.
+/// - `hsts`: `Strict-Transport-Security` policy for HTTPS requests, if enabled.
+/// - `concurrency`: Cap on connections queued or in progress (unlimited by default).
+/// - `peers`: Cap on open connections per client IP address (unlimited by default).
+/// - `timeouts`: Read, write, header, and overall limits for client connections.
+/// - `limits`: Request size limits.
+/// - `allowed_hosts`: Host names requests may name; any host is accepted when empty.
+/// - `duplicates`: Treatment of repeated request header fields.
+/// - `server_header`: `Server` header value for responses, or `None` to send none.
+/// - `keep_alive`: Persistent connection limits.
+/// - `event_loop`: Whether idle persistent connections wait in the event loop instead of a worker.
+/// - `io_uring`: Whether connections are accepted and served through `io_uring` (`io_uring` feature).
+/// - `socket`: Listener and accepted-connection socket options.
+/// - `throttle`: Response bandwidth caps per connection.
+/// - `early_hints`: Links announced in a `103 Early Hints` response per request path.
+/// - `proxy_protocol`: Which peers open connections with a PROXY protocol preamble, if enabled.
+/// - `trusted_proxies`: Proxy ranges whose forwarding headers set the request's client and scheme.
+/// - `metrics`: Traffic counters, exported if configured.
+/// - `health`: Liveness and readiness probes, if enabled.
+/// - `admin`: Operator API listener, if enabled.
+/// - `privileges`: Account to switch to once the listeners are bound, if set.
+/// - `drain_deadline`: How long to drain after `SIGTERM` before closing what is still open, if enabled.
+/// - `tasks`: Background tasks started with the server and stopped with it.
+/// - `dev`: Live-reload channel for development, if enabled.
+/// - `buffers`: Scratch buffers lent to connections for request lines and response heads.
+/// - `tracer`: OpenTelemetry span exporter, if enabled (`otel` feature).
+/// - `idle`: Event loop holding idle persistent connections, once started by `run`.
+/// - `uring`: Ring accepting and serving connections, once started by `run` (`io_uring` feature).
+/// - `connections`: Connections currently being served.
+/// - `draining`: Set once persistent connections should close after their current request.
+/// - `stopping`: Set once the accept loop should stop.
+///
+/// **Usage Example:**
+/// ```rust,no_run
+/// # use server::{server::Server, static_files::StaticFiles, vhost::VirtualHost};
+/// Server::new("127.0.0.1:7878")
+///     .vhost(VirtualHost::new().name("example.com").files(StaticFiles::new("/srv/example")))
+///     .default_host(VirtualHost::new().files(StaticFiles::new("html")))
+///     .run()?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub struct Server {
+    addr: String,
+    workers: usize,
+    proxy: Option<Proxy>,
+    forward_proxy: Option<ForwardProxy>,
+    cache: ResponseCache,
+    hosts: Vec<VirtualHost>,
+    default_host: VirtualHost,
+    state: Arc<AppState>,
+    hsts: Option<Hsts>,
+    concurrency: ConcurrencyLimit,
+    peers: PeerLimit,
+    timeouts: Timeouts,
+    limits: Limits,
+    allowed_hosts: Vec<HostPattern>,
+    duplicates: DuplicatePolicy,
+    server_header: Option<String>,
+    keep_alive: KeepAlive,
+    event_loop: bool,
+    #[cfg(feature = "io_uring")]
+    io_uring: bool,
+    socket: SocketOptions,
+    throttle: Throttle,
+    early_hints: EarlyHints,
+    proxy_protocol: Option<ProxyProtocol>,
+    trusted_proxies: Vec<Cidr>,
+    metrics: Metrics,
+    health: Option<Health>,
+    admin: Option<Admin>,
+    privileges: Option<Privileges>,
+    drain_deadline: Option<Duration>,
+    tasks: Tasks,
+    dev: Option<DevReload>,
+    buffers: Arc<BufferPool>,
+    #[cfg(feature = "otel")]
+    tracer: Option<Tracer>,
+    #[cfg(any(target_os = "linux", target_os = "macos", target_os = "freebsd"))]
+    idle: OnceLock<EventLoop<ConnectionState>>,
+    #[cfg(all(feature = "io_uring", target_os = "linux"))]
+    uring: OnceLock<Uring<ConnectionState>>,
+    connections: Arc<ConnectionRegistry>,
+    draining: AtomicBool,
+    stopping: AtomicBool,
+}
+
+
+/// # ConnectionState
+///
+/// **Summary:**
+/// What a client connection carries from one request to the next, including while it waits
+/// in the event loop or the ring.
+///
+/// **Fields:**
+/// - `info`: Metadata of the connection, attached to each request.
+/// - `peer`: Client address, from the PROXY protocol preamble when one was read.
+/// - `served`: Requests read from the connection so far.
+/// - `_registered`: Keeps the connection listed among open connections.
+/// - `_open`: Holds the connection in the open-connections gauge.
+/// - `_permits`: Peer and concurrency admissions, released when the connection closes.
+/// - `preload`: Bytes of the next request the ring received before a worker took over, and
+///   whether its head was overdue by then (`io_uring` feature).
+struct ConnectionState {
+    info: Arc<ConnectionInfo>,
+    peer: Option<SocketAddr>,
+    served: usize,
+    _registered: Registration,
+    _open: Gauge,
+    _permits: (PeerPermit, Permit),
+    #[cfg(all(feature = "io_uring", target_os = "linux"))]
+    preload: (Vec<u8>, bool),
+}
+
+
+/// # ServerHandle
+///
+/// **Summary:**
+/// Control over a server started with `Server::start`. Dropping it leaves the server running.
+///
+/// **Fields:**
+/// - `server`: The running server.
+/// - `local_addr`: Address its listener is bound to.
+/// - `thread`: Accept thread, which returns once the server has stopped and its workers have
+///   finished.
+///
+/// **Usage Example:**
+/// ```rust
+/// # use server::server::Server;
+/// # use std::{io::{Read, Write}, net::{SocketAddr, TcpStream}, time::Duration};
+/// # fn http_get(addr: SocketAddr, path: &str) -> std::io::Result<String> {
+/// #     let mut stream = TcpStream::connect(addr)?;
+/// #     write!(stream, "GET {path} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n")?;
+/// #     let mut response = String::new();
+/// #     stream.read_to_string(&mut response)?;
+/// #     Ok(response)
+/// # }
+/// let handle = Server::new("127.0.0.1:0").start()?;
+/// let response = http_get(handle.local_addr(), "/");
+/// handle.graceful_shutdown(Duration::from_secs(5));
+/// handle.await_stopped()?;
+/// # assert!(response?.starts_with("HTTP/1.1 "));
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub struct ServerHandle {
+    server: Arc<Server>,
+    local_addr: SocketAddr,
+    thread: thread::JoinHandle<io::Result<()>>,
+}
+
+
+impl Server {
+
+    /// # new
+    ///
+    /// **Purpose:**
+    /// Creates a server for `addr` with four workers, a 16 MiB response cache that no route
+    /// uses yet, and an empty default host.
+    ///
+    /// **Parameters:**
+    /// - `addr`: Address to bind (e.g. `127.0.0.1:7878`).
+    ///
+    /// **Returns:**
+    /// - A new `Server` instance.
+    ///
+    /// **Errors / Failures:**
+    /// None. Binding happens in `run`.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::server::Server;
+    /// let server = Server::new("127.0.0.1:7878");
+    /// ```
+    pub fn new(addr: impl Into<String>) -> Server {
+        Server {
+            addr: addr.into(),
+            workers: 4,
+            proxy: None,
+            forward_proxy: None,
+            cache: ResponseCache::new(DEFAULT_CACHE_BYTES),
+            hosts: Vec::new(),
+            default_host: VirtualHost::new(),
+            state: Arc::default(),
+            hsts: None,
+            concurrency: ConcurrencyLimit::default(),
+            peers: PeerLimit::default(),
+            timeouts: Timeouts::default(),
+            limits: Limits::default(),
+            allowed_hosts: Vec::new(),
+            duplicates: DuplicatePolicy::default(),
+            server_header: Some(DEFAULT_SERVER_HEADER.to_string()),
+            keep_alive: KeepAlive::default(),
+            event_loop: false,
+            #[cfg(feature = "io_uring")]
+            io_uring: false,
+            socket: SocketOptions::default(),
+            throttle: Throttle::default(),
+            early_hints: EarlyHints::default(),
+            proxy_protocol: None,
+            trusted_proxies: Vec::new(),
+            metrics: Metrics::default(),
+            health: None,
+            admin: None,
+            privileges: None,
+            drain_deadline: None,
+            tasks: Tasks::default(),
+            dev: None,
+            buffers: Arc::default(),
+            #[cfg(feature = "otel")]
+            tracer: None,
+            #[cfg(any(target_os = "linux", target_os = "macos", target_os = "freebsd"))]
+            idle: OnceLock::new(),
+            #[cfg(all(feature = "io_uring", target_os = "linux"))]
+            uring: OnceLock::new(),
+            connections: Arc::default(),
+            draining: AtomicBool::new(false),
+            stopping: AtomicBool::new(false),
+        }
+    }
+
+    /// # workers
+    ///
+    /// **Purpose:**
+    /// Sets the number of worker threads.
+    ///
+    /// **Parameters:**
+    /// - `workers`: Worker thread count; must be non-zero.
+    ///
+    /// **Returns:**
+    /// - The updated `Server`.
+    ///
+    /// **Errors / Failures:**
+    /// None. A zero count panics in `run`.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::server::Server;
+    /// let server = Server::new("127.0.0.1:7878").workers(8);
+    /// ```
+    pub fn workers(mut self, workers: usize) -> Server {
+        self.workers = workers;
+        self
+    }
+
+    /// # proxy
+    ///
+    /// **Purpose:**
+    /// Enables reverse proxy mode: every request is forwarded to `proxy`'s upstream.
+    ///
+    /// **Parameters:**
+    /// - `proxy`: Configured reverse proxy.
+    ///
+    /// **Returns:**
+    /// - The updated `Server`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{proxy::Proxy, server::Server};
+    /// let server = Server::new("127.0.0.1:7878").proxy(Proxy::new("127.0.0.1:3000"));
+    /// ```
+    pub fn proxy(mut self, proxy: Proxy) -> Server {
+        self.proxy = Some(proxy);
+        self
+    }
+
+    /// # forward_proxy
+    ///
+    /// **Purpose:**
+    /// Enables forward proxy handling for absolute-form and `CONNECT` requests.
+    ///
+    /// **Parameters:**
+    /// - `forward_proxy`: Configured forward proxy.
+    ///
+    /// **Returns:**
+    /// - The updated `Server`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{proxy::ForwardProxy, server::Server};
+    /// let server = Server::new("127.0.0.1:3128").forward_proxy(ForwardProxy::new());
+    /// ```
+    pub fn forward_proxy(mut self, forward_proxy: ForwardProxy) -> Server {
+        self.forward_proxy = Some(forward_proxy);
+        self
+    }
+
+    /// # cache_size
+    ///
+    /// **Purpose:**
+    /// Replaces the response cache with an empty one bounded to `bytes`, keeping its opted-in
+    /// routes.
+    ///
+    /// **Parameters:**
+    /// - `bytes`: Maximum total size of cached responses.
+    ///
+    /// **Returns:**
+    /// - The updated `Server`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::server::Server;
+    /// let server = Server::new("127.0.0.1:7878").cache_size(64 * 1024 * 1024);
+    /// ```
+    pub fn cache_size(mut self, bytes: usize) -> Server {
+        self.cache = self.cache.resized(bytes);
+        self
+    }
+
+    /// # cache_route
+    ///
+    /// **Purpose:**
+    /// Opts the paths matching `pattern` into the response cache, which no path uses by
+    /// default. A cache hit is answered before router middleware runs, so IP rules, rate limits,
+    /// and authentication are skipped for it: only opt in routes that answer every client alike.
+    ///
+    /// **Parameters:**
+    /// - `pattern`: Path glob (e.g. `/assets/**`).
+    ///
+    /// **Returns:**
+    /// - The updated `Server`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::server::Server;
+    /// let server = Server::new("127.0.0.1:7878").cache_route("/assets/**").cache_route("/feed.xml");
+    /// ```
+    pub fn cache_route(mut self, pattern: &str) -> Server {
+        self.cache = self.cache.route(pattern);
+        self
+    }
+
+    /// # vhost
+    ///
+    /// **Purpose:**
+    /// Adds a named virtual host. Hosts are matched in the order they were added.
+    ///
+    /// **Parameters:**
+    /// - `host`: Site with at least one name.
+    ///
+    /// **Returns:**
+    /// - The updated `Server`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{server::Server, vhost::VirtualHost};
+    /// let server = Server::new("0.0.0.0:80").vhost(VirtualHost::new().name("example.com"));
+    /// ```
+    pub fn vhost(mut self, host: VirtualHost) -> Server {
+        self.hosts.push(host);
+        self
+    }
+
+    /// # default_host
+    ///
+    /// **Purpose:**
+    /// Sets the catch-all site used when the `Host` header is missing or matches no named host.
+    ///
+    /// **Parameters:**
+    /// - `host`: Catch-all site.
+    ///
+    /// **Returns:**
+    /// - The updated `Server`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{server::Server, static_files::StaticFiles, vhost::VirtualHost};
+    /// let server = Server::new("0.0.0.0:80").default_host(VirtualHost::new().files(StaticFiles::new("html")));
+    /// ```
+    pub fn default_host(mut self, host: VirtualHost) -> Server {
+        self.default_host = host;
+        self
+    }
+
+    /// # with_state
+    ///
+    /// **Purpose:**
+    /// Shares a value (database pool, configuration, counters) with every handler; handlers read it
+    /// through the `State<T>` extractor or `Request::state`. One value is kept per type.
+    ///
+    /// **Parameters:**
+    /// - `state`: Value to share; use atomics or locks inside it for mutable data.
+    ///
+    /// **Returns:**
+    /// - The updated `Server`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::server::Server;
+    /// # struct Config;
+    /// # impl Config { fn load() -> std::io::Result<Config> { Ok(Config) } }
+    /// # #[derive(Default)]
+    /// # struct Hits(std::sync::atomic::AtomicU64);
+    /// let server = Server::new("0.0.0.0:80").with_state(Config::load()?).with_state(Hits::default());
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn with_state<T: Send + Sync + 'static>(mut self, state: T) -> Server {
+        Arc::make_mut(&mut self.state).insert(state);
+        self
+    }
+
+    /// # hsts
+    ///
+    /// **Purpose:**
+    /// Emits `Strict-Transport-Security` on responses to HTTPS requests. The server does not
+    /// terminate TLS, so a request counts as HTTPS when the proxy in front of it says so (see
+    /// `trust_proxy`); plain HTTP responses never carry the header.
+    ///
+    /// **Parameters:**
+    /// - `hsts`: Policy to send.
+    ///
+    /// **Returns:**
+    /// - The updated `Server`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{hsts::Hsts, server::Server};
+    /// let server = Server::new("127.0.0.1:8080").hsts(Hsts::new(31_536_000).include_subdomains());
+    /// ```
+    pub fn hsts(mut self, hsts: Hsts) -> Server {
+        self.hsts = Some(hsts);
+        self
+    }
+
+    /// # timeouts
+    ///
+    /// **Purpose:**
+    /// Replaces the time limits applied to client connections.
+    ///
+    /// **Parameters:**
+    /// - `timeouts`: Read, write, header, and overall limits.
+    ///
+    /// **Returns:**
+    /// - The updated `Server`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{server::Server, timeouts::Timeouts};
+    /// # use std::time::Duration;
+    /// let server = Server::new("127.0.0.1:7878").timeouts(Timeouts::new().header(Duration::from_secs(5)));
+    /// ```
+    pub fn timeouts(mut self, timeouts: Timeouts) -> Server {
+        self.timeouts = timeouts;
+        self
+    }
+
+    /// # limits
+    ///
+    /// **Purpose:**
+    /// Replaces the request size limits. Requests over a limit are answered with the matching
+    /// `4xx` status and the connection is closed.
+    ///
+    /// **Parameters:**
+    /// - `limits`: Size limits.
+    ///
+    /// **Returns:**
+    /// - The updated `Server`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{limits::Limits, server::Server};
+    /// let server = Server::new("127.0.0.1:7878").limits(Limits::new().max_body(1024 * 1024));
+    /// ```
+    pub fn limits(mut self, limits: Limits) -> Server {
+        self.limits = limits;
+        self
+    }
+
+    /// # allow_host
+    ///
+    /// **Purpose:**
+    /// Adds a host name or pattern (same syntax as `VirtualHost::name`) that requests may name.
+    /// Once any host is allowed, requests for other hosts are answered with `421 Misdirected
+    /// Request` before routing, which blocks `Host` header injection and DNS rebinding.
+    ///
+    /// **Parameters:**
+    /// - `pattern`: Host name or pattern without port (e.g. `example.com`, `*.example.com`).
+    ///
+    /// **Returns:**
+    /// - The updated `Server`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::server::Server;
+    /// let server = Server::new("0.0.0.0:80").allow_host("example.com").allow_host("*.example.com");
+    /// ```
+    pub fn allow_host(mut self, pattern: &str) -> Server {
+        self.allowed_hosts.push(HostPattern::new(pattern));
+        self
+    }
+
+    /// # duplicate_headers
+    ///
+    /// **Purpose:**
+    /// Replaces the policy for repeated request header fields, applied before routing or
+    /// proxying so every backend sees the same single value.
+    ///
+    /// **Parameters:**
+    /// - `policy`: Per-header duplicate rules.
+    ///
+    /// **Returns:**
+    /// - The updated `Server`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{duplicates::{Duplicate, DuplicatePolicy}, server::Server};
+    /// let server = Server::new("127.0.0.1:7878").duplicate_headers(DuplicatePolicy::new().fallback(Duplicate::Join));
+    /// ```
+    pub fn duplicate_headers(mut self, policy: DuplicatePolicy) -> Server {
+        self.duplicates = policy;
+        self
+    }
+
+    /// # server_header
+    ///
+    /// **Purpose:**
+    /// Sets the `Server` header sent with every handled response, replacing any set by a
+    /// handler or upstream, or removes it entirely so the software is not advertised.
+    ///
+    /// **Parameters:**
+    /// - `value`: Header value, or `None` to suppress the header.
+    ///
+    /// **Returns:**
+    /// - The updated `Server`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::server::Server;
+    /// let server = Server::new("0.0.0.0:80").server_header(Some("example"));
+    /// let quiet = Server::new("0.0.0.0:80").server_header(None);
+    /// ```
+    pub fn server_header(mut self, value: Option<&str>) -> Server {
+        self.server_header = value.map(str::to_string);
+        self
+    }
+
+    /// # keep_alive
+    ///
+    /// **Purpose:**
+    /// Replaces the persistent connection limits. Each kept-alive connection holds a worker
+    /// while idle unless the event loop is enabled, so a short idle timeout keeps the pool
+    /// available.
+    ///
+    /// **Parameters:**
+    /// - `keep_alive`: Idle timeout and request limit.
+    ///
+    /// **Returns:**
+    /// - The updated `Server`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{keep_alive::KeepAlive, server::Server};
+    /// # use std::time::Duration;
+    /// let server = Server::new("127.0.0.1:7878").keep_alive(KeepAlive::new().idle(Duration::from_secs(2)));
+    /// ```
+    pub fn keep_alive(mut self, keep_alive: KeepAlive) -> Server {
+        self.keep_alive = keep_alive;
+        self
+    }
+
+    /// # event_loop
+    ///
+    /// **Purpose:**
+    /// Parks persistent connections in a readiness-polling event loop (`epoll` or `kqueue`)
+    /// between requests, so workers only run requests and thousands of idle keep-alive
+    /// connections cost no threads. A connection is handed back to the pool as soon as its next
+    /// request begins to arrive, and idle ones still close after the keep-alive idle timeout.
+    /// Not available on other platforms, where the server logs a warning and idle connections
+    /// keep their workers.
+    ///
+    /// **Parameters:**
+    /// - `enabled`: Whether to use the event loop (off by default).
+    ///
+    /// **Returns:**
+    /// - The updated `Server`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{keep_alive::KeepAlive, server::Server};
+    /// # use std::time::Duration;
+    /// let server = Server::new("0.0.0.0:80").event_loop(true).keep_alive(KeepAlive::new().idle(Duration::from_secs(60)));
+    /// ```
+    pub fn event_loop(mut self, enabled: bool) -> Server {
+        self.event_loop = enabled;
+        self
+    }
+
+    /// # io_uring
+    ///
+    /// **Purpose:**
+    /// Serves connections from one `io_uring` on Linux. The accept thread drives the ring: it
+    /// accepts connections and receives their request heads, handing each to the pool only
+    /// once its head has arrived, so idle keep-alive connections and slow clients cost no
+    /// threads (this takes over from `event_loop`). Workers still run each request to the end
+    /// of its response, but their socket reads, writes, and file sends are queued to the ring
+    /// and submitted together, one system call for whatever all of them have ready. Where the
+    /// kernel refuses rings (older than 5.7, or disabled by `kernel.io_uring_disabled` or
+    /// seccomp) the server logs a warning at startup and uses blocking I/O. `serve_async`
+    /// ignores this setting.
+    ///
+    /// **Parameters:**
+    /// - `enabled`: Whether to use `io_uring` (off by default).
+    ///
+    /// **Returns:**
+    /// - The updated `Server`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::server::Server;
+    /// let server = Server::new("0.0.0.0:80").io_uring(true);
+    /// ```
+    #[cfg(feature = "io_uring")]
+    pub fn io_uring(mut self, enabled: bool) -> Server {
+        self.io_uring = enabled;
+        self
+    }
+
+    /// # socket_options
+    ///
+    /// **Purpose:**
+    /// Replaces the listener and connection socket options (`SO_REUSEADDR`, `SO_REUSEPORT`,
+    /// accept backlog, `TCP_NODELAY`).
+    ///
+    /// **Parameters:**
+    /// - `options`: Socket settings.
+    ///
+    /// **Returns:**
+    /// - The updated `Server`.
+    ///
+    /// **Errors / Failures:**
+    /// None. Binding errors surface from `run`.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{server::Server, socket::SocketOptions};
+    /// let server = Server::new("0.0.0.0:80").socket_options(SocketOptions::new().nodelay(true).backlog(1024));
+    /// ```
+    pub fn socket_options(mut self, options: SocketOptions) -> Server {
+        self.socket = options;
+        self
+    }
+
+    /// # throttle
+    ///
+    /// **Purpose:**
+    /// Caps the response bandwidth of each connection, optionally per route.
+    ///
+    /// **Parameters:**
+    /// - `throttle`: Default and per-route rates.
+    ///
+    /// **Returns:**
+    /// - The updated `Server`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{server::Server, throttle::Throttle};
+    /// let server = Server::new("127.0.0.1:7878").throttle(Throttle::default().route("/downloads/**", 64 * 1024));
+    /// ```
+    pub fn throttle(mut self, throttle: Throttle) -> Server {
+        self.throttle = throttle;
+        self
+    }
+
+    /// # early_hints
+    ///
+    /// **Purpose:**
+    /// Sends a `103 Early Hints` response announcing critical assets before the final response
+    /// to requests whose paths have hints declared, so browsers can start fetching them while
+    /// the page is produced.
+    ///
+    /// **Parameters:**
+    /// - `hints`: `Link` headers by path pattern.
+    ///
+    /// **Returns:**
+    /// - The updated `Server`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{early_hints::EarlyHints, server::Server};
+    /// let server = Server::new("127.0.0.1:7878").early_hints(EarlyHints::new().preload("/", "/app.css", "style"));
+    /// ```
+    pub fn early_hints(mut self, hints: EarlyHints) -> Server {
+        self.early_hints = hints;
+        self
+    }
+
+    /// # proxy_protocol
+    ///
+    /// **Purpose:**
+    /// Reads a PROXY protocol (v1 or v2) preamble from connections made by a load balancer, and
+    /// uses the client address it carries for logging, rate limiting, and handlers. Connections
+    /// that should carry a preamble but do not are dropped.
+    ///
+    /// **Parameters:**
+    /// - `proxy_protocol`: Which peers send the preamble.
+    ///
+    /// **Returns:**
+    /// - The updated `Server`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{proxy_protocol::ProxyProtocol, server::Server};
+    /// let server = Server::new("0.0.0.0:8080").proxy_protocol(ProxyProtocol::new());
+    /// ```
+    pub fn proxy_protocol(mut self, proxy_protocol: ProxyProtocol) -> Server {
+        self.proxy_protocol = Some(proxy_protocol);
+        self
+    }
+
+    /// # trust_proxy
+    ///
+    /// **Purpose:**
+    /// Trusts the forwarding headers of proxies in a range. For requests from a trusted peer,
+    /// the client address (`Forwarded: for=`, else `X-Forwarded-For`) and scheme (`proto=`, else
+    /// `X-Forwarded-Proto`) they report become `request.client` and `request.secure`, which the
+    /// access log, HSTS, and address middleware without ranges of their own then use. While no
+    /// range is trusted, no forwarding header is believed: every request counts as plain HTTP
+    /// from its peer address.
+    ///
+    /// **Parameters:**
+    /// - `range`: CIDR range of the load balancers or reverse proxies in front of the server.
+    ///
+    /// **Returns:**
+    /// - The updated `Server`.
+    ///
+    /// **Errors / Failures:**
+    /// - `InvalidInput` if the range is malformed.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::server::Server;
+    /// let server = Server::new("0.0.0.0:8080").trust_proxy("10.0.0.0/8")?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn trust_proxy(mut self, range: &str) -> io::Result<Server> {
+        self.trusted_proxies.push(Cidr::parse(range)?);
+        Ok(self)
+    }
+
+    /// # metrics
+    ///
+    /// **Purpose:**
+    /// Replaces the server's traffic counters, e.g. with a handle that exports them on an
+    /// endpoint or an admin listener.
+    ///
+    /// **Parameters:**
+    /// - `metrics`: Counters to maintain.
+    ///
+    /// **Returns:**
+    /// - The updated `Server`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{metrics::Metrics, server::Server};
+    /// let server = Server::new("0.0.0.0:80").metrics(Metrics::new().endpoint("/metrics"));
+    /// ```
+    pub fn metrics(mut self, metrics: Metrics) -> Server {
+        self.metrics = metrics;
+        self
+    }
+
+    /// # health
+    ///
+    /// **Purpose:**
+    /// Answers liveness and readiness probes on the main listener.
+    ///
+    /// **Parameters:**
+    /// - `health`: Probe paths, readiness checks, and draining state.
+    ///
+    /// **Returns:**
+    /// - The updated `Server`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{health::Health, server::Server};
+    /// let server = Server::new("0.0.0.0:80").health(Health::new());
+    /// ```
+    pub fn health(mut self, health: Health) -> Server {
+        self.health = Some(health);
+        self
+    }
+
+    /// # dev_reload
+    ///
+    /// **Purpose:**
+    /// Turns on development live reload: HTML responses get a script that reloads the page
+    /// whenever the channel is notified.
+    ///
+    /// **Parameters:**
+    /// - `reload`: Channel, usually fed by a `Watcher` through `DevReload::watch`.
+    ///
+    /// **Returns:**
+    /// - The updated `Server`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{dev::DevReload, server::Server};
+    /// # let reload = DevReload::new();
+    /// let server = Server::new("127.0.0.1:7878").dev_reload(reload);
+    /// ```
+    pub fn dev_reload(mut self, reload: DevReload) -> Server {
+        self.dev = Some(reload);
+        self
+    }
+
+    /// # buffer_pool
+    ///
+    /// **Purpose:**
+    /// Replaces the pool connections borrow their scratch buffer from (4 KiB buffers, 256 kept
+    /// idle, by default), e.g. to size it for many workers or to read its counters.
+    ///
+    /// **Parameters:**
+    /// - `pool`: Pool to use; may be shared with other servers.
+    ///
+    /// **Returns:**
+    /// - The updated `Server`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{buffer_pool::BufferPool, server::Server};
+    /// # use std::sync::Arc;
+    /// let server = Server::new("127.0.0.1:7878").buffer_pool(Arc::new(BufferPool::new(8 * 1024, 1024)));
+    /// ```
+    pub fn buffer_pool(mut self, pool: Arc<BufferPool>) -> Server {
+        self.buffers = pool;
+        self
+    }
+
+    /// # admin
+    ///
+    /// **Purpose:**
+    /// Serves the operator API on a separate listener, started by `run`.
+    ///
+    /// **Parameters:**
+    /// - `admin`: Admin listener address.
+    ///
+    /// **Returns:**
+    /// - The updated `Server`.
+    ///
+    /// **Errors / Failures:**
+    /// None. Binding happens in `run`.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{admin::Admin, server::Server};
+    /// let server = Server::new("0.0.0.0:80").admin(Admin::new("127.0.0.1:9000"));
+    /// ```
+    pub fn admin(mut self, admin: Admin) -> Server {
+        self.admin = Some(admin);
+        self
+    }
+
+    /// # privileges
+    ///
+    /// **Purpose:**
+    /// Switches the process to an unprivileged account once `run` has bound the public and
+    /// admin listeners, so the server can start as root to bind ports 80 and 443 without
+    /// serving requests as root.
+    ///
+    /// **Parameters:**
+    /// - `privileges`: User and group to run as.
+    ///
+    /// **Returns:**
+    /// - The updated `Server`.
+    ///
+    /// **Errors / Failures:**
+    /// None. `run` fails before accepting a connection if the switch does not succeed. It
+    /// applies to the whole process, not just this server.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{privileges::Privileges, server::Server};
+    /// let server = Server::new("0.0.0.0:80").privileges(Privileges::new().user("www-data"));
+    /// ```
+    pub fn privileges(mut self, privileges: Privileges) -> Server {
+        self.privileges = Some(privileges);
+        self
+    }
+
+    /// # drain_on_sigterm
+    ///
+    /// **Purpose:**
+    /// Drains instead of dying when the process receives `SIGTERM`: the readiness probe fails,
+    /// keep-alive connections get `Connection: close` on their next response, and `run` returns
+    /// once no connection is open or the deadline passes, closing whatever is left.
+    ///
+    /// **Parameters:**
+    /// - `deadline`: Longest time to wait for open connections to finish.
+    ///
+    /// **Returns:**
+    /// - The updated `Server`.
+    ///
+    /// **Errors / Failures:**
+    /// None. The handler is installed by `run`, and replaces the signal's default action for
+    /// the whole process. Has no effect on platforms without Unix signals.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::server::Server;
+    /// # use std::time::Duration;
+    /// let server = Server::new("0.0.0.0:80").drain_on_sigterm(Duration::from_secs(30));
+    /// ```
+    pub fn drain_on_sigterm(mut self, deadline: Duration) -> Server {
+        self.drain_deadline = Some(deadline);
+        self
+    }
+
+    /// # tasks
+    ///
+    /// **Purpose:**
+    /// Runs background tasks beside the server: they start once the listener is bound (and
+    /// privileges are dropped), and are told to stop when the server stops, which waits a
+    /// bounded time for them to return.
+    ///
+    /// **Parameters:**
+    /// - `tasks`: Registered tasks.
+    ///
+    /// **Returns:**
+    /// - The updated `Server`.
+    ///
+    /// **Errors / Failures:**
+    /// None. `run` fails if a task's thread cannot be started.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{server::Server, tasks::Tasks};
+    /// # use std::time::Duration;
+    /// # fn sweep() {}
+    /// let server = Server::new("0.0.0.0:80").tasks(Tasks::new().every("session-sweep", Duration::from_secs(60), sweep));
+    /// ```
+    pub fn tasks(mut self, tasks: Tasks) -> Server {
+        self.tasks = tasks;
+        self
+    }
+
+    /// # tracer
+    ///
+    /// **Purpose:**
+    /// Records a span for every request and proxied upstream call and exports them to an OTLP
+    /// collector, continuing traces named by incoming `traceparent` headers.
+    ///
+    /// **Parameters:**
+    /// - `tracer`: Collector endpoint, service name, and sampling ratio.
+    ///
+    /// **Returns:**
+    /// - The updated `Server`.
+    ///
+    /// **Errors / Failures:**
+    /// None. The export thread is started by `run`.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{server::Server, trace::Tracer};
+    /// let server = Server::new("0.0.0.0:80").tracer(Tracer::new("http://127.0.0.1:4318/v1/traces", "web"));
+    /// ```
+    #[cfg(feature = "otel")]
+    pub fn tracer(mut self, tracer: Tracer) -> Server {
+        self.tracer = Some(tracer);
+        self
+    }
+
+    /// # cache
+    ///
+    /// **Purpose:**
+    /// Returns the shared response cache (e.g. to read its statistics).
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - A reference to the `ResponseCache`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::server::Server;
+    /// # let server = Server::new("127.0.0.1:7878");
+    /// let hits = server.cache().stats().hits;
+    /// # assert_eq!(hits, 0);
+    /// ```
+    pub fn cache(&self) -> &ResponseCache {
+        &self.cache
+    }
+
+    /// # concurrency_limit
+    ///
+    /// **Purpose:**
+    /// Caps how many connections may be queued or in progress at once. Connections beyond the
+    /// cap are answered with `503 Service Unavailable` instead of waiting for a worker.
+    ///
+    /// **Parameters:**
+    /// - `limit`: Concurrency cap.
+    ///
+    /// **Returns:**
+    /// - The updated `Server`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{concurrency::ConcurrencyLimit, server::Server};
+    /// let server = Server::new("127.0.0.1:7878").concurrency_limit(ConcurrencyLimit::new(64));
+    /// ```
+    pub fn concurrency_limit(mut self, limit: ConcurrencyLimit) -> Server {
+        self.concurrency = limit;
+        self
+    }
+
+    /// # concurrency
+    ///
+    /// **Purpose:**
+    /// Returns the concurrency cap (e.g. to read its statistics, or to clone a live handle before `run`).
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - A reference to the `ConcurrencyLimit`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::server::Server;
+    /// # let server = Server::new("127.0.0.1:7878");
+    /// let busy = server.concurrency().stats().current;
+    /// # assert_eq!(busy, 0);
+    /// ```
+    pub fn concurrency(&self) -> &ConcurrencyLimit {
+        &self.concurrency
+    }
+
+    /// # peer_limit
+    ///
+    /// **Purpose:**
+    /// Caps how many connections one client IP address may hold open. Connections beyond the
+    /// cap are refused from the accept loop, with `429 Too Many Requests` unless configured to
+    /// close silently.
+    ///
+    /// **Parameters:**
+    /// - `limit`: Per-address cap.
+    ///
+    /// **Returns:**
+    /// - The updated `Server`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{peer_limit::PeerLimit, server::Server};
+    /// let server = Server::new("0.0.0.0:80").peer_limit(PeerLimit::new(8).respond(false));
+    /// ```
+    pub fn peer_limit(mut self, limit: PeerLimit) -> Server {
+        self.peers = limit;
+        self
+    }
+
+    /// # peers
+    ///
+    /// **Purpose:**
+    /// Returns the per-address cap (e.g. to read its counters, or to clone a live handle before `run`).
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - A reference to the `PeerLimit`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::server::Server;
+    /// # let server = Server::new("127.0.0.1:7878");
+    /// let refused = server.peers().rejected();
+    /// # assert_eq!(refused, 0);
+    /// ```
+    pub fn peers(&self) -> &PeerLimit {
+        &self.peers
+    }
+
+    /// # worker_count
+    ///
+    /// **Purpose:**
+    /// Returns the configured number of worker threads.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - The worker count.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let workers = server.worker_count();
+    /// ```
+    pub(crate) fn worker_count(&self) -> usize {
+        self.workers
+    }
+
+    /// # traffic
+    ///
+    /// **Purpose:**
+    /// Returns the server's traffic counters.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - A reference to the `Metrics`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let in_flight = server.traffic().stats().in_flight;
+    /// ```
+    pub(crate) fn traffic(&self) -> &Metrics {
+        &self.metrics
+    }
+
+    /// # reverse_proxy
+    ///
+    /// **Purpose:**
+    /// Returns the reverse proxy, if one is configured.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - The `Proxy`, or `None` when requests are served locally.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let weights = server.reverse_proxy().map(Proxy::weights);
+    /// ```
+    pub(crate) fn reverse_proxy(&self) -> Option<&Proxy> {
+        self.proxy.as_ref()
+    }
+
+    /// # open_connections
+    ///
+    /// **Purpose:**
+    /// Lists the client connections currently being served.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - Metadata of each open connection, oldest first.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let open = server.open_connections().len();
+    /// ```
+    pub(crate) fn open_connections(&self) -> Vec<Arc<ConnectionInfo>> {
+        self.connections.snapshot()
+    }
+
+    /// # describe
+    ///
+    /// **Purpose:**
+    /// Renders the server's configuration for the admin API.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - A JSON object; settings without a JSON shape of their own are given as their `Debug` text.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let body = server.describe();
+    /// ```
+    pub(crate) fn describe(&self) -> String {
+        let debug = |value: &dyn std::fmt::Debug| json_string(&format!("{value:?}"));
+        let limit = |limit: usize| if limit == usize::MAX { "null".to_string() } else { limit.to_string() };
+        let fields = [
+            ("addr", json_string(&self.addr)),
+            ("workers", self.workers.to_string()),
+            ("virtual_hosts", self.hosts.len().to_string()),
+            ("allowed_hosts", self.allowed_hosts.len().to_string()),
+            ("reverse_proxy", self.proxy.is_some().to_string()),
+            ("forward_proxy", self.forward_proxy.is_some().to_string()),
+            ("hsts", self.hsts.as_ref().map_or("null".to_string(), |hsts| json_string(&hsts.header_value()))),
+            ("max_concurrent", limit(self.concurrency.stats().limit)),
+            ("timeouts", debug(&self.timeouts)),
+            ("limits", debug(&self.limits)),
+            ("keep_alive", debug(&self.keep_alive)),
+            ("event_loop", self.event_loop.to_string()),
+            #[cfg(feature = "io_uring")]
+            ("io_uring", self.io_uring.to_string()),
+            ("socket", debug(&self.socket)),
+            ("throttle", debug(&self.throttle)),
+            ("early_hints", debug(&self.early_hints)),
+            ("duplicates", debug(&self.duplicates)),
+            ("server_header", self.server_header.as_deref().map_or("null".to_string(), json_string)),
+            ("proxy_protocol", self.proxy_protocol.as_ref().map_or("null".to_string(), |value| debug(value))),
+            ("trusted_proxies", self.trusted_proxies.len().to_string()),
+            ("health", self.health.is_some().to_string()),
+            ("privileges", self.privileges.as_ref().map_or("null".to_string(), |value| debug(value))),
+            ("drain_deadline", self.drain_deadline.map_or("null".to_string(), |value| debug(&value))),
+            ("tasks", self.tasks.len().to_string()),
+        ];
+        let fields: Vec<String> = fields.iter().map(|(name, value)| format!("{}:{value}", json_string(name))).collect();
+        format!("{{{}}}", fields.join(","))
+    }
+
+    /// # drain
+    ///
+    /// **Purpose:**
+    /// Stops keeping connections alive, so each closes after its current request, and fails the
+    /// readiness probe if one is configured.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// None.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// server.drain();
+    /// ```
+    pub(crate) fn drain(&self) {
+        self.draining.store(true, Ordering::Release);
+        if let Some(health) = &self.health {
+            health.drain();
+        }
+    }
+
+    /// # is_draining
+    ///
+    /// **Purpose:**
+    /// Checks whether `drain` has been called.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - `true` once the server is draining.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// if server.is_draining() { response.headers.set("Connection", "close"); }
+    /// ```
+    pub(crate) fn is_draining(&self) -> bool {
+        self.draining.load(Ordering::Acquire)
+    }
+
+    /// # stop
+    ///
+    /// **Purpose:**
+    /// Stops the accept loop, so `run` returns once queued connections have been served, and
+    /// tells the background tasks and the live-reload notifier to stop. Implies `drain`.
+    ///
+    /// **Parameters:**
+    /// - `listener`: Address the public listener is bound to, connected to once to wake the
+    ///   blocked accept.
+    ///
+    /// **Returns:**
+    /// None.
+    ///
+    /// **Errors / Failures:**
+    /// None. If the wake-up connection fails, the loop stops at the next accepted connection.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// server.stop(listener.local_addr()?);
+    /// ```
+    pub(crate) fn stop(&self, listener: SocketAddr) {
+        self.drain();
+        self.stopping.store(true, Ordering::Release);
+        self.tasks.stop();
+        if let Some(dev) = &self.dev {
+            dev.stop();
+        }
+        let ip = match listener.ip() {
+            IpAddr::V4(ip) if ip.is_unspecified() => IpAddr::V4(Ipv4Addr::LOCALHOST),
+            IpAddr::V6(ip) if ip.is_unspecified() => IpAddr::V6(Ipv6Addr::LOCALHOST),
+            ip => ip,
+        };
+        let _ = TcpStream::connect_timeout(&SocketAddr::new(ip, listener.port()), Duration::from_secs(1));
+    }
+
+    /// # drain_within
+    ///
+    /// **Purpose:**
+    /// Drains, waits until no connection is open or `deadline` passes, then stops the accept
+    /// loop and closes whatever is left.
+    ///
+    /// **Parameters:**
+    /// - `listener`: Address the public listener is bound to, for waking the accept loop.
+    /// - `deadline`: Longest time to wait for open connections.
+    ///
+    /// **Returns:**
+    /// None, once no connection is open.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// server.drain_within(listener, Duration::from_secs(30));
+    /// ```
+    pub(crate) fn drain_within(&self, listener: SocketAddr, deadline: Duration) {
+        self.drain();
+        let started = Instant::now();
+        while started.elapsed() < deadline && !self.connections.snapshot().is_empty() {
+            thread::sleep(DRAIN_POLL);
+        }
+        let open = self.connections.snapshot().len();
+        if open > 0 {
+            log::emit(LogLevel::Info, format_args!("Drain deadline passed; closing {open} connections"));
+        }
+        self.close(listener);
+    }
+
+    /// # close
+    ///
+    /// **Purpose:**
+    /// Stops the accept loop and shuts down every open connection at once, mid-request or not.
+    ///
+    /// **Parameters:**
+    /// - `listener`: Address the public listener is bound to, for waking the accept loop.
+    ///
+    /// **Returns:**
+    /// None, once no connection is open.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// server.close(listener);
+    /// ```
+    pub(crate) fn close(&self, listener: SocketAddr) {
+        self.stop(listener);
+        while self.connections.close_all() > 0 {
+            thread::sleep(DRAIN_POLL);
+        }
+    }
+
+    /// # run
+    ///
+    /// **Purpose:**
+    /// Binds the listener, starts the admin API if configured, and serves connections on the
+    /// thread pool until accepting fails or the admin API asks for a shutdown. Connections over
+    /// the per-address or concurrency cap are shed from the accept loop; connections that fail
+    /// or time out are logged and dropped.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - `Ok(())` after an admin shutdown, once the workers have finished every queued connection.
+    ///
+    /// **Errors / Failures:**
+    /// - `InvalidInput` if a site has routes registered with `Router::route_async`; serve those
+    ///   with `serve_async`.
+    /// - Returns an I/O error if the address or the admin listener cannot be bound, privileges
+    ///   cannot be dropped, the event loop cannot be started, or accepting a connection fails
+    ///   (which also drains the server, so the event loop releases its connections before the
+    ///   workers are joined).
+    /// - Panics if the worker count is zero.
+    ///
+    /// **Examples:**
+    /// ```rust,no_run
+    /// # use server::server::Server;
+    /// Server::new("127.0.0.1:7878").run()?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn run(self) -> io::Result<()> {
+        let (server, listener, pool) = self.bind()?;
+        let served = server.serve(&listener, &pool);
+        server.tasks.shutdown();
+        served
+    }
+
+    /// # start
+    ///
+    /// **Purpose:**
+    /// Starts the server like `run`, but serves on a thread of its own and hands back a handle
+    /// for stopping it, so embedding applications and tests need no signals or admin API.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - A `ServerHandle` once the listener is bound and the configured services are running.
+    ///
+    /// **Errors / Failures:**
+    /// - `InvalidInput` if a site has routes registered with `Router::route_async`.
+    /// - Returns an I/O error if the address or the admin listener cannot be bound, privileges
+    ///   cannot be dropped, the event loop cannot be started, or the accept thread cannot be
+    ///   created. Later accept failures are returned by `ServerHandle::await_stopped`.
+    /// - Panics if the worker count is zero.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::server::Server;
+    /// let handle = Server::new("127.0.0.1:0").start()?;
+    /// let url = format!("http://{}/", handle.local_addr());
+    /// handle.shutdown();
+    /// handle.await_stopped()?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn start(self) -> io::Result<ServerHandle> {
+        let (server, listener, pool) = self.bind()?;
+        let local_addr = listener.local_addr()?;
+        let serving = Arc::clone(&server);
+        let thread = thread::Builder::new()
+            .name("accept".to_string())
+            .spawn(move || {
+                let served = serving.serve(&listener, &pool);
+                serving.tasks.shutdown();
+                served
+            })?;
+        Ok(ServerHandle { server, local_addr, thread })
+    }
+
+    /// # bind
+    ///
+    /// **Purpose:**
+    /// Binds the public and admin listeners, drops privileges, and only then creates the
+    /// worker pool and starts the services beside the accept loop, for `run` and `start`.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - The shared server, its bound listener, and its worker pool.
+    ///
+    /// **Errors / Failures:**
+    /// - `InvalidInput`, before anything is bound, if a site has routes registered with
+    ///   `Router::route_async`: the pool's workers run no tokio runtime to block on them in.
+    /// - Returns an I/O error if an address cannot be bound, privileges cannot be dropped, or
+    ///   a service cannot be started (see `bind_privileged` and `start_services`).
+    /// - Panics if the worker count is zero.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let (server, listener, pool) = self.bind()?;
+    /// ```
+    fn bind(self) -> io::Result<(Arc<Server>, TcpListener, ThreadPool)> {
+        if self.hosts.iter().chain([&self.default_host]).any(VirtualHost::has_async_routes) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "async routes need a tokio runtime; serve this router with Server::serve_async",
+            ));
+        }
+        let listener = self.socket.bind(&self.addr)?;
+        let admin = self.bind_privileged()?;
+        let pool = ThreadPool::with_queue_gauge(self.workers, self.metrics.queue_gauge());
+        let server = Arc::new(self);
+        server.start_services(admin, listener.local_addr()?, pool.spawner())?;
+        Ok((server, listener, pool))
+    }
+
+    /// # serve
+    ///
+    /// **Purpose:**
+    /// Runs the accept loop, queueing each admitted connection on the pool, until the server
+    /// is stopped or accepting fails. With a ring running, the ring's loop runs instead.
+    ///
+    /// **Parameters:**
+    /// - `listener`: Bound public listener.
+    /// - `pool`: Worker pool serving the connections.
+    ///
+    /// **Returns:**
+    /// - `Ok(())` once the server has been stopped.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns the accept error, after draining the server.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// server.serve(&listener, &pool)?;
+    /// ```
+    fn serve(self: &Arc<Server>, listener: &TcpListener, pool: &ThreadPool) -> io::Result<()> {
+        #[cfg(all(feature = "io_uring", target_os = "linux"))]
+        if let Some(ring) = self.uring.get() {
+            return self.serve_ring(ring, listener, pool).inspect_err(|_| self.drain());
+        }
+        for stream in listener.incoming() {
+            let stream = stream.inspect_err(|_| self.drain())?;
+            if self.stopping.load(Ordering::Acquire) {
+                break;
+            }
+            let Some(permits) = self.admit(&stream) else {
+                continue;
+            };
+            let server = Arc::clone(self);
+            let spawner = pool.spawner();
+            pool.execute(move || {
+                if let Err(error) = server.handle_connection(stream, spawner, permits) {
+                    log::emit(LogLevel::Warn, format_args!("Connection dropped: {error}"));
+                }
+            });
+        }
+        Ok(())
+    }
+
+    /// # serve_ring
+    ///
+    /// **Purpose:**
+    /// Runs the ring's loop on the accept thread until the server is stopped or accepting
+    /// fails: each admitted connection waits in the ring for its request head, then is queued
+    /// on the pool with the bytes received; one whose head is overdue is queued too, and its
+    /// worker answers `408`.
+    ///
+    /// **Parameters:**
+    /// - `ring`: The started ring.
+    /// - `listener`: Bound public listener.
+    /// - `pool`: Worker pool serving the connections.
+    ///
+    /// **Returns:**
+    /// - `Ok(())` once the server has been stopped.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns the accept error (see `Uring::run`).
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// server.serve_ring(ring, &listener, &pool)?;
+    /// ```
+    #[cfg(all(feature = "io_uring", target_os = "linux"))]
+    fn serve_ring(self: &Arc<Server>, ring: &Uring<ConnectionState>, listener: &TcpListener, pool: &ThreadPool) -> io::Result<()> {
+        let spawner = pool.spawner();
+        let admit = |stream: &TcpStream| {
+            let permits = self.admit(stream)?;
+            Some((self.connection_state(stream, permits), self.timeouts.head_wait()))
+        };
+        let ready = |stream, mut state: ConnectionState, preload, overdue| {
+            state.preload = (preload, overdue);
+            let (server, next) = (Arc::clone(self), spawner.clone());
+            spawner.execute(move || {
+                if let Err(error) = server.serve_connection(stream, state, next) {
+                    log::emit(LogLevel::Warn, format_args!("Connection dropped: {error}"));
+                }
+            });
+        };
+        ring.run(listener, admit, ready, || self.is_draining(), || self.stopping.load(Ordering::Acquire))
+    }
+
+    /// # serve_async
+    ///
+    /// **Purpose:**
+    /// Serves connections from inside an existing tokio runtime instead of a thread pool of
+    /// the server's own. Only accepting is asynchronous: each connection is still served
+    /// synchronously, one blocking thread of the runtime per connection, and a route
+    /// registered with `Router::route_async` blocks that thread on its future. This lets an
+    /// application share one runtime and reuse async clients in handlers; it does not let the
+    /// server hold more connections than the runtime has blocking threads. Stops like `run`,
+    /// on an accept error or an admin shutdown.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - `Ok(())` after an admin shutdown, once every connection in flight has been served.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns an I/O error if the address or the admin listener cannot be bound, privileges
+    ///   cannot be dropped, the event loop cannot be started, or accepting a connection fails.
+    /// - Panics if called outside a tokio runtime.
+    ///
+    /// **Examples:**
+    /// ```rust,no_run
+    /// # use server::{request::Request, response::Response, router::Router, server::Server, vhost::VirtualHost};
+    /// # async fn fetch_user(_: Request) -> Response { Response::new(200, "OK") }
+    /// let router = Router::new().get_async("/user/:id", |request| async move { fetch_user(request).await });
+    /// tokio::spawn(Server::new("127.0.0.1:7878").default_host(VirtualHost::new().router(router)).serve_async());
+    /// ```
+    #[cfg(feature = "async")]
+    pub async fn serve_async(self) -> io::Result<()> {
+        let listener = self.socket.bind(&self.addr)?;
+        let admin = self.bind_privileged()?;
+        listener.set_nonblocking(true)?;
+        let listener = tokio::net::TcpListener::from_std(listener)?;
+        let spawner = Spawner::blocking(tokio::runtime::Handle::current());
+        let server = Arc::new(self);
+        server.start_services(admin, listener.local_addr()?, spawner.clone())?;
+
+        let mut connections = tokio::task::JoinSet::new();
+        let mut served = Ok(());
+        loop {
+            while connections.try_join_next().is_some() {}
+            let stream = listener.accept().await.and_then(|(stream, _)| stream.into_std());
+            let stream = match stream.and_then(|stream| stream.set_nonblocking(false).map(|()| stream)) {
+                Ok(stream) => stream,
+                Err(err) => {
+                    server.drain();
+                    served = Err(err);
+                    break;
+                }
+            };
+            if server.stopping.load(Ordering::Acquire) {
+                break;
+            }
+            let Some(permits) = server.admit(&stream) else {
+                continue;
+            };
+            let (server, spawner) = (Arc::clone(&server), spawner.clone());
+            connections.spawn_blocking(move || {
+                if let Err(error) = server.handle_connection(stream, spawner, permits) {
+                    log::emit(LogLevel::Warn, format_args!("Connection dropped: {error}"));
+                }
+            });
+        }
+        while connections.join_next().await.is_some() {}
+        let tasks = Arc::clone(&server);
+        let _ = tokio::task::spawn_blocking(move || tasks.tasks.shutdown()).await;
+        served
+    }
+
+    /// # bind_privileged
+    ///
+    /// **Purpose:**
+    /// Binds the admin listener, if configured, and then drops privileges, so every socket
+    /// that may need a low port or a root-owned path is open before the server gives up root,
+    /// and no thread serving requests ever runs with it.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - The bound admin listener, if the admin API is configured.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns an I/O error if the admin listener cannot be bound or privileges cannot be
+    ///   dropped.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let admin = self.bind_privileged()?;
+    /// ```
+    fn bind_privileged(&self) -> io::Result<Option<AdminListener>> {
+        let admin = self.admin.as_ref().map(Admin::bind).transpose()?;
+        if let Some(privileges) = &self.privileges {
+            privileges.apply()?;
+            log::emit(LogLevel::Info, format_args!("dropped privileges; no longer running as root"));
+        }
+        Ok(admin)
+    }
+
+    /// # start_services
+    ///
+    /// **Purpose:**
+    /// Starts what runs beside the accept loop: the admin API, the trace exporter, the event
+    /// loop, and the live-reload notifier, each when configured. Then starts the background
+    /// tasks, watches for
+    /// `SIGTERM` if asked to, and reports a Windows service as running once everything is up.
+    /// Runs after `bind_privileged`, so none of these threads start with root's rights.
+    ///
+    /// **Parameters:**
+    /// - `admin`: Bound admin listener, if the admin API is configured.
+    /// - `listener`: Address the public listener is bound to, for admin shutdowns.
+    /// - `spawner`: Handle the event loop queues woken connections on.
+    ///
+    /// **Returns:**
+    /// - `Ok(())` once everything configured is running.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns an I/O error if the admin thread, the exporter, the event loop, the notifier,
+    ///   a background task, or the `SIGTERM` watcher cannot be started.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// server.start_services(admin, listener.local_addr()?, pool.spawner())?;
+    /// ```
+    fn start_services(self: &Arc<Server>, admin: Option<AdminListener>, listener: SocketAddr, spawner: Spawner) -> io::Result<()> {
+        if let Some(admin) = admin {
+            admin.serve(Arc::clone(self), listener)?;
+        }
+        #[cfg(feature = "otel")]
+        if let Some(tracer) = &self.tracer {
+            tracer.start()?;
+        }
+        #[cfg(feature = "io_uring")]
+        if self.io_uring {
+            self.start_uring();
+        }
+        if self.event_loop {
+            self.start_event_loop(spawner)?;
+        }
+        if let Some(dev) = &self.dev {
+            dev.start()?;
+        }
+        self.tasks.start()?;
+        if let Some(deadline) = self.drain_deadline {
+            signal::handle_sigterm()?;
+            self.watch_sigterm(listener, deadline)?;
+        }
+        #[cfg(windows)]
+        service::started(self, listener);
+        Ok(())
+    }
+
+    /// # watch_sigterm
+    ///
+    /// **Purpose:**
+    /// Starts the thread that waits for `SIGTERM`, then drains: it waits until no connection is
+    /// open or `deadline` passes, stops the accept loop, and shuts down every connection still
+    /// open so the workers can finish.
+    ///
+    /// **Parameters:**
+    /// - `listener`: Address the public listener is bound to, for waking the accept loop.
+    /// - `deadline`: Longest time to wait for open connections after the signal.
+    ///
+    /// **Returns:**
+    /// - `Ok(())` once the thread is running.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns an I/O error if the thread cannot be created. The thread ends without acting
+    ///   if the server stops for another reason first.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// server.watch_sigterm(listener, Duration::from_secs(30))?;
+    /// ```
+    fn watch_sigterm(self: &Arc<Server>, listener: SocketAddr, deadline: Duration) -> io::Result<()> {
+        let server: Weak<Server> = Arc::downgrade(self);
+        thread::Builder::new().name("sigterm".to_string()).spawn(move || {
+            while !signal::terminated() {
+                thread::sleep(DRAIN_POLL);
+                if server.upgrade().is_none_or(|server| server.stopping.load(Ordering::Acquire)) {
+                    return;
+                }
+            }
+            let Some(server) = server.upgrade() else {
+                return;
+            };
+            log::emit(LogLevel::Info, format_args!("SIGTERM received; draining for up to {deadline:?}"));
+            server.drain_within(listener, deadline);
+        })?;
+        Ok(())
+    }
+
+    /// # admit
+    ///
+    /// **Purpose:**
+    /// Applies the socket options to a new connection and takes its per-address and
+    /// concurrency permits, shedding it if either cap is reached.
+    ///
+    /// **Parameters:**
+    /// - `stream`: Newly accepted connection.
+    ///
+    /// **Returns:**
+    /// - The permits to hold while the connection is open, or `None` once it has been shed.
+    ///
+    /// **Errors / Failures:**
+    /// None. Socket option and rejection failures are ignored.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let Some(permits) = server.admit(&stream) else { continue; };
+    /// ```
+    fn admit(&self, stream: &TcpStream) -> Option<(PeerPermit, Permit)> {
+        let _ = self.socket.configure(stream);
+        let Some(peer_permit) = stream.peer_addr().ok().and_then(|peer| self.peers.try_acquire(peer.ip())) else {
+            let _ = self.peers.reject(stream);
+            return None;
+        };
+        let Some(permit) = self.concurrency.try_acquire() else {
+            let _ = self.concurrency.reject(stream);
+            return None;
+        };
+        Some((peer_permit, permit))
+    }
+
+    /// # start_uring
+    ///
+    /// **Purpose:**
+    /// Sets up the ring the accept loop will drive.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// None.
+    ///
+    /// **Errors / Failures:**
+    /// None. If the kernel refuses the ring, a warning is logged and the server keeps
+    /// blocking I/O.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// server.start_uring();
+    /// ```
+    #[cfg(all(feature = "io_uring", target_os = "linux"))]
+    fn start_uring(&self) {
+        match Uring::new() {
+            Ok(ring) => {
+                let _ = self.uring.set(ring);
+            }
+            Err(error) => log::emit(LogLevel::Warn, format_args!("io_uring is unavailable ({error}); using blocking I/O")),
+        }
+    }
+
+    /// # start_uring
+    ///
+    /// **Purpose:**
+    /// Stands in for the ring on platforms other than Linux.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// None, after logging that blocking I/O is used.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// server.start_uring();
+    /// ```
+    #[cfg(all(feature = "io_uring", not(target_os = "linux")))]
+    fn start_uring(&self) {
+        log::emit(LogLevel::Warn, format_args!("io_uring needs Linux; using blocking I/O"));
+    }
+
+    /// # start_event_loop
+    ///
+    /// **Purpose:**
+    /// Creates the event loop and starts its thread, which queues each woken connection on the
+    /// pool and stops once the server drains.
+    ///
+    /// **Parameters:**
+    /// - `spawner`: Handle for queueing woken connections.
+    ///
+    /// **Returns:**
+    /// - `Ok(())` once the thread is running.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns an I/O error if the readiness queue or the thread cannot be created.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// server.start_event_loop(pool.spawner())?;
+    /// ```
+    #[cfg(any(target_os = "linux", target_os = "macos", target_os = "freebsd"))]
+    fn start_event_loop(self: &Arc<Server>, spawner: Spawner) -> io::Result<()> {
+        let _ = self.idle.set(EventLoop::new()?);
+        let server = Arc::clone(self);
+        thread::Builder::new().name("event-loop".to_string()).spawn(move || {
+            let Some(idle) = server.idle.get() else {
+                return;
+            };
+            let ready = |stream, state| {
+                let (server, next) = (Arc::clone(&server), spawner.clone());
+                spawner.execute(move || {
+                    if let Err(error) = server.serve_connection(stream, state, next) {
+                        log::emit(LogLevel::Warn, format_args!("Connection dropped: {error}"));
+                    }
+                });
+            };
+            idle.run(ready, || server.is_draining());
+        })?;
+        Ok(())
+    }
+
+    /// # start_event_loop
+    ///
+    /// **Purpose:**
+    /// Stands in for the event loop on platforms without `epoll` or `kqueue`.
+    ///
+    /// **Parameters:**
+    /// - `spawner`: Handle that would queue woken connections.
+    ///
+    /// **Returns:**
+    /// - `Ok(())`, after logging that idle connections keep their workers.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// server.start_event_loop(pool.spawner())?;
+    /// ```
+    #[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "freebsd")))]
+    fn start_event_loop(self: &Arc<Server>, _spawner: Spawner) -> io::Result<()> {
+        log::emit(LogLevel::Warn, format_args!("The event loop needs epoll or kqueue; idle connections keep their workers"));
+        Ok(())
+    }
+
+    /// # select_host
+    ///
+    /// **Purpose:**
+    /// Selects the virtual host named by the request's `Host` header (port and case ignored)
+    /// and copies any subdomain captures into the request's parameters.
+    ///
+    /// **Parameters:**
+    /// - `request`: Parsed client request.
+    ///
+    /// **Returns:**
+    /// - The matching named host, or the default host.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let site = server.select_host(&mut request);
+    /// ```
+    fn select_host(&self, request: &mut Request) -> &VirtualHost {
+        let host = request.headers.get("Host").map(host_name).unwrap_or_default();
+        for site in &self.hosts {
+            if let Some(params) = site.matches(&host) {
+                request.params.extend(params);
+                return site;
+            }
+        }
+        &self.default_host
+    }
+
+    /// # screen
+    ///
+    /// **Purpose:**
+    /// Vets a request head before its body is read, so refused uploads are answered without
+    /// being received (and without a `100 Continue`).
+    ///
+    /// **Parameters:**
+    /// - `request`: Request with its head read; repeated headers are normalized in place.
+    ///
+    /// **Returns:**
+    /// - `None` if the request may proceed, otherwise the refusal to send.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// if let Some(refusal) = self.screen(&mut request) { return refusal.write_final(&stream, timeout); }
+    /// ```
+    fn screen(&self, request: &mut Request) -> Option<Response> {
+        if let Err(name) = self.duplicates.apply(&mut request.headers) {
+            return Some(bad_request(&format!("repeated {name} header")));
+        }
+        self.check_host(request).or_else(|| unmet_expectation(request))
+    }
+
+    /// # check_host
+    ///
+    /// **Purpose:**
+    /// Validates a request's `Host` header: HTTP/1.1 requests must carry exactly one well-formed
+    /// value, and the host must be in the allowlist if one is set. HTTP/1.0 requests without
+    /// `Host` (old clients, health probes) are let through, since browsers always send one.
+    ///
+    /// **Parameters:**
+    /// - `request`: Parsed client request.
+    ///
+    /// **Returns:**
+    /// - `None` if the request may proceed, otherwise `Some(400)` for a missing, repeated, or
+    ///   malformed `Host` header, or `Some(421)` for a host outside the allowlist.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// if let Some(refusal) = self.check_host(&request) { return refusal.write_final(&stream, timeout); }
+    /// ```
+    fn check_host(&self, request: &Request) -> Option<Response> {
+        let values = request.headers.get_all("Host");
+        let host = match values.as_slice() {
+            [] if request.is_http10() => return None,
+            [value] if is_authority(value) => host_name(value),
+            [] => return Some(bad_request("missing Host header")),
+            [_] => return Some(bad_request("malformed Host header")),
+            _ => return Some(bad_request("multiple Host headers")),
+        };
+        if self.allowed_hosts.is_empty() {
+            return None;
+        }
+        if self.allowed_hosts.iter().any(|pattern| pattern.captures(&host).is_some()) {
+            return None;
+        }
+        Some(
+            Response::new(421, "Misdirected Request")
+                .with_header("Content-Type", "text/plain; charset=utf-8")
+                .with_body("unknown host"),
+        )
+    }
+
+    /// # handle_connection
+    ///
+    /// **Purpose:**
+    /// Registers a newly accepted connection and starts serving it.
+    ///
+    /// **Parameters:**
+    /// - `stream`: TCP stream representing the client connection.
+    /// - `spawner`: Handle for queueing background refresh jobs.
+    /// - `permits`: Peer and concurrency admissions, held until the connection closes.
+    ///
+    /// **Returns:**
+    /// - `Ok(())` once the connection is closed or parked in the event loop.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns the I/O error that ended the connection early (see `serve_connection`).
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// server.handle_connection(stream, pool.spawner(), (peer_permit, permit))?;
+    /// ```
+    fn handle_connection(self: &Arc<Server>, stream: TcpStream, spawner: Spawner, permits: (PeerPermit, Permit)) -> io::Result<()> {
+        let state = self.connection_state(&stream, permits);
+        self.serve_connection(stream, state, spawner)
+    }
+
+    /// # connection_state
+    ///
+    /// **Purpose:**
+    /// Registers a newly accepted connection and counts it as open.
+    ///
+    /// **Parameters:**
+    /// - `stream`: TCP stream representing the client connection.
+    /// - `permits`: Peer and concurrency admissions, held until the connection closes.
+    ///
+    /// **Returns:**
+    /// - The state the connection carries until it closes, with nothing served yet.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let state = server.connection_state(&stream, permits);
+    /// ```
+    fn connection_state(&self, stream: &TcpStream, permits: (PeerPermit, Permit)) -> ConnectionState {
+        let open = self.metrics.connection();
+        let info = Arc::new(ConnectionInfo::new(stream));
+        ConnectionState {
+            peer: info.remote,
+            served: 0,
+            _registered: ConnectionRegistry::register(&self.connections, &info, stream),
+            _open: open,
+            _permits: permits,
+            info,
+            #[cfg(all(feature = "io_uring", target_os = "linux"))]
+            preload: (Vec::new(), false),
+        }
+    }
+
+    /// # serve_connection
+    ///
+    /// **Purpose:**
+    /// Serves the requests arriving on one TCP stream, one after another, until the client
+    /// closes it or goes idle, a response ends it, or the keep-alive request limit is reached.
+    /// Pipelined requests are taken from the buffer the earlier ones arrived in and answered
+    /// strictly in order; if the connection ends with some still unanswered, its sending side
+    /// is shut down and the rest discarded before closing, so the last response is not lost
+    /// to a reset. A PROXY protocol preamble, when expected from the peer, is read before the
+    /// first request.
+    /// With the event loop or the ring running, a connection with nothing more buffered is
+    /// parked there after each response and resumed here, on any worker, when its next request
+    /// arrives; through the ring, its I/O goes through the ring as well.
+    ///
+    /// **Parameters:**
+    /// - `stream`: TCP stream representing the client connection.
+    /// - `state`: What the connection carries between requests.
+    /// - `spawner`: Handle for queueing background refresh jobs.
+    ///
+    /// **Returns:**
+    /// - `Ok(())` once the connection is closed or parked.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns the I/O error that ended the connection early (see `serve_request`), including
+    ///   `InvalidData` for a missing or malformed PROXY protocol preamble.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// server.serve_connection(stream, state, spawner)?;
+    /// ```
+    fn serve_connection(self: &Arc<Server>, stream: TcpStream, mut state: ConnectionState, spawner: Spawner) -> io::Result<()> {
+        let mut buf_reader = BufReader::new(ClientStream::new(&stream, self.timeouts));
+        buf_reader.get_mut().meter(self.metrics.clone());
+        #[cfg(all(feature = "io_uring", target_os = "linux"))]
+        if let Some(ring) = self.uring.get() {
+            buf_reader.get_mut().io_uring(ring.queue());
+            let (preload, overdue) = mem::take(&mut state.preload);
+            buf_reader.get_mut().preload(preload, overdue);
+        }
+        if state.served == 0
+            && let Some(socket_peer) = state.info.remote
+            && let Some(proxy_protocol) = &self.proxy_protocol
+            && proxy_protocol.expects(socket_peer)
+        {
+            state.peer = Some(proxy_protocol::read_preamble(&mut buf_reader, socket_peer)?);
+        }
+        let mut scratch = self.buffers.checkout();
+        let limit = self.keep_alive.request_limit();
+        while state.served < limit {
+            if state.served > 0 {
+                buf_reader.get_mut().next_request(self.keep_alive.idle_timeout());
+            }
+            state.served += 1;
+            if !self.serve_request(&state.info, state.peer, &mut buf_reader, &mut scratch, &spawner, limit - state.served)? {
+                break;
+            }
+            #[cfg(all(feature = "io_uring", target_os = "linux"))]
+            if let Some(ring) = self.uring.get()
+                && !buffered(&buf_reader)
+            {
+                drop(buf_reader);
+                return ring.park(stream, self.keep_alive.idle_timeout(), self.timeouts.head_wait(), state);
+            }
+            #[cfg(any(target_os = "linux", target_os = "macos", target_os = "freebsd"))]
+            if let Some(idle) = self.idle.get()
+                && buf_reader.buffer().is_empty()
+            {
+                drop(buf_reader);
+                return idle.park(stream, self.keep_alive.idle_timeout(), state);
+            }
+        }
+        if buffered(&buf_reader) {
+            let _ = stream.shutdown(Shutdown::Write);
+            linger(&stream);
+        }
+        Ok(())
+    }
+
+    /// # serve_request
+    ///
+    /// **Purpose:**
+    /// Reads one request from the connection and sends an appropriate response, answering
+    /// from the response cache when possible. Stale cache hits are served immediately and
+    /// refreshed by a background pool job; concurrent misses for one key share a single origin
+    /// fetch, after any early hints declared for the path. Request heads are screened before the
+    /// body is read, and a client sending `Expect: 100-continue` gets its interim response only
+    /// once the body is wanted.
+    ///
+    /// **Parameters:**
+    /// - `connection`: Metadata of the client connection, attached to the request.
+    /// - `peer`: Client address, from the PROXY protocol preamble when one was read.
+    /// - `buf_reader`: Buffered client connection, kept across requests; also gives the socket.
+    /// - `scratch`: Connection's pooled buffer, for the request line, headers, and response head.
+    /// - `spawner`: Handle for queueing background refresh jobs.
+    /// - `remaining`: Further requests the connection may serve after this one.
+    ///
+    /// **Returns:**
+    /// - `Ok(true)` if the connection stays open for another request, `Ok(false)` if it is done
+    ///   (including when an idle persistent connection closes or times out).
+    ///
+    /// **Errors / Failures:**
+    /// - Returns an I/O error if reading the request, reaching the upstream, or writing the response
+    ///   fails, including `TimedOut` when a connection time limit runs out (after answering `408`
+    ///   if the request itself was too slow to arrive).
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let open = server.serve_request(&connection, peer, &mut buf_reader, &mut scratch, &spawner, 99)?;
+    /// ```
+    fn serve_request(
+        self: &Arc<Server>,
+        connection: &Arc<ConnectionInfo>,
+        peer: Option<SocketAddr>,
+        buf_reader: &mut BufReader<ClientStream<'_>>,
+        scratch: &mut Vec<u8>,
+        spawner: &Spawner,
+        remaining: usize,
+    ) -> io::Result<bool> {
+        let stream = buf_reader.get_ref().socket();
+        let mut request = match Request::read_head(buf_reader, &self.limits.head(), scratch) {
+            Ok(request) => request,
+            Err(_) if buf_reader.buffer().is_empty() && buf_reader.get_ref().awaiting() => return Ok(false),
+            Err(error) => return Err(refuse(stream, &self.metrics, error)),
+        };
+        buf_reader.get_mut().head_read();
+        let started = Instant::now();
+        let _in_flight = self.metrics.request();
+        if let Some(refusal) = self.screen(&mut request) {
+            self.metrics.record_response(refusal.status);
+            let _ = refusal.write_final(stream, REJECTION_WRITE_TIMEOUT);
+            linger(stream);
+            return Ok(false);
+        }
+        let continues = !request.is_http10() && request.headers.has_token("Expect", "100-continue");
+        buf_reader.get_mut().expect_continue(continues);
+        let limit = self.limits.body_limit(request.path());
+        request.body = match read_body_within(buf_reader, &request.headers, limit) {
+            Ok(body) => body,
+            Err(error) => return Err(refuse(stream, &self.metrics, error)),
+        };
+        buf_reader.get_mut().expect_continue(false);
+        buf_reader.get_mut().throttle(self.throttle.rate_for(request.path()));
+        request.peer = peer;
+        request.connection = Arc::clone(connection);
+        request.cancellation = Cancellation::watch(stream);
+        let _finish = request.cancellation.finish_on_drop();
+        request.client = ip::client_ip(&request, &self.trusted_proxies);
+        request.secure = ip::client_proto(&request, &self.trusted_proxies).as_deref() == Some("https");
+        request.state = Arc::clone(&self.state);
+        if self.metrics.exports(&request) {
+            return self.write_response(&request, self.metrics.response(), buf_reader.get_mut(), scratch, remaining);
+        }
+        if let Some(probe) = self.health.as_ref().and_then(|health| health.respond(&request)) {
+            return self.write_response(&request, probe, buf_reader.get_mut(), scratch, remaining);
+        }
+        if let Some(dev) = self.dev.as_ref().filter(|dev| dev.handles(&request)) {
+            return dev.subscribe(stream).map(|()| false);
+        }
+        #[cfg(feature = "otel")]
+        let _span = self.tracer.as_ref().map(|tracer| tracer.server_span(&request));
+        let site = self.select_host(&mut request);
+
+        if let Some(forward_proxy) = &self.forward_proxy
+            && forward_proxy.handles(&request)
+        {
+            site.log(request.client, &request, None);
+            return forward_proxy.serve(&request, buf_reader).map(|()| false);
+        }
+
+        if let Some(redirect) = site.rewrite_request(&mut request) {
+            site.log(request.client, &request, Some(&redirect));
+            return self.write_response(&request, redirect, buf_reader.get_mut(), scratch, remaining);
+        }
+
+        let route = self.route_label(site, &request);
+        #[cfg(feature = "otel")]
+        trace::set_route(route);
+        let cacheable = request.method == "GET"
+            && self.cache.covers(request.path())
+            && !request.is_upgrade()
+            && !request.headers.contains("Range");
+        if cacheable && let Some(hit) = self.cache.lookup(&request) {
+            if hit.revalidate {
+                let (server, mut request) = (Arc::clone(self), request.clone());
+                request.cancellation = Cancellation::default();
+                spawner.execute(move || {
+                    let site = server.select_host(&mut request);
+                    if let Ok(response) = server.fetch_origin(site, &request) {
+                        server.cache.store(&request, &response);
+                    }
+                });
+            }
+            site.log(request.client, &request, Some(&hit.response));
+            let open = self.write_response(&request, hit.response, buf_reader.get_mut(), scratch, remaining);
+            self.metrics.observe(route, &request.method, started.elapsed());
+            return open;
+        }
+
+        if !request.is_http10()
+            && let Some(interim) = self.early_hints.interim(request.path())
+        {
+            let client = buf_reader.get_mut();
+            client.write_all(&interim)?;
+            client.flush()?;
+        }
+
+        if let Some(proxy) = &self.proxy
+            && !cacheable
+        {
+            site.log(request.client, &request, None);
+            return proxy.forward(&request, buf_reader).map(|()| false);
+        }
+
+        let response = if cacheable {
+            self.cache.fetch_coalesced(&request, || self.fetch_origin(site, &request))?
+        } else {
+            self.fetch_origin(site, &request)?
+        };
+        site.log(request.client, &request, Some(&response));
+        let open = self.write_response(&request, response, buf_reader.get_mut(), scratch, remaining);
+        self.metrics.observe(route, &request.method, started.elapsed());
+        open
+    }
+
+    /// # write_response
+    ///
+    /// **Purpose:**
+    /// Sends a response to the client, adding server-wide headers (`Server`, `Strict-Transport-Security`
+    /// on HTTPS requests, and either `Keep-Alive` or `Connection: close`), injecting the
+    /// live-reload script into HTML in dev mode, and omitting the body
+    /// for `HEAD` requests while keeping every header, including `Content-Length`. The
+    /// connection is kept only if the client wants it (HTTP/1.0 clients must ask), the response
+    /// does not close it, and requests remain. Bodies are framed by `Content-Length`, or
+    /// `chunked` when the response declares trailers; HTTP/1.0 clients, which cannot read
+    /// `chunked` bodies, get those responses without their trailers.
+    ///
+    /// **Parameters:**
+    /// - `request`: Request being answered.
+    /// - `response`: Response to send.
+    /// - `stream`: Client connection, with its time limits.
+    /// - `scratch`: Buffer the response head is serialized into.
+    /// - `remaining`: Further requests the connection may serve after this one.
+    ///
+    /// **Returns:**
+    /// - `Ok(true)` if the connection stays open, `Ok(false)` if it was marked for closing.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns any I/O error raised while writing.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let open = self.write_response(&request, response, buf_reader.get_mut(), scratch, remaining)?;
+    /// ```
+    fn write_response(
+        &self,
+        request: &Request,
+        mut response: Response,
+        stream: &mut ClientStream<'_>,
+        scratch: &mut Vec<u8>,
+        remaining: usize,
+    ) -> io::Result<bool> {
+        if let Some(hsts) = &self.hsts
+            && request.secure
+        {
+            response.headers.set("Strict-Transport-Security", hsts.header_value());
+        }
+        if let Some(dev) = &self.dev {
+            dev.inject(&mut response);
+        }
+        self.metrics.record_response(response.status);
+        #[cfg(feature = "otel")]
+        trace::set_status(response.status);
+        match &self.server_header {
+            Some(value) => response.headers.set("Server", value.as_str()),
+            None => response.headers.remove("Server"),
+        }
+        let open = remaining > 0 && !self.is_draining() && request.keep_alive() && !response.headers.has_token("Connection", "close");
+        if open {
+            response.headers.set("Keep-Alive", self.keep_alive.header_value(remaining));
+            if request.is_http10() {
+                response.headers.set("Connection", "keep-alive");
+            }
+        } else {
+            response.headers.set("Connection", "close");
+        }
+        if request.is_http10() {
+            response.remove_trailers();
+        }
+        if request.method == "HEAD" {
+            response.write_head_to(stream)?;
+        } else {
+            response.send_with(stream, scratch)?;
+        }
+        Ok(open)
+    }
+
+    /// # fetch_origin
+    ///
+    /// **Purpose:**
+    /// Produces a fresh response for a request, bypassing the cache: from the upstream in proxy
+    /// mode, otherwise from the selected virtual host.
+    ///
+    /// **Parameters:**
+    /// - `site`: Virtual host selected for the request.
+    /// - `request`: Parsed client request, with host parameters applied.
+    ///
+    /// **Returns:**
+    /// - The origin `Response`, `502 Bad Gateway` if the upstream response is larger than the
+    ///   proxy buffers, or `504 Gateway Timeout` if the upstream does not answer in time.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns an I/O error if the upstream cannot be reached or replies with a malformed response.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let response = server.fetch_origin(site, &request)?;
+    /// ```
+    fn fetch_origin(&self, site: &VirtualHost, request: &Request) -> io::Result<Response> {
+        match &self.proxy {
+            Some(proxy) => match proxy.fetch(request) {
+                Err(error) if matches!(error.kind(), io::ErrorKind::TimedOut | io::ErrorKind::WouldBlock) => {
+                    Ok(Response::new(504, "Gateway Timeout"))
+                }
+                Err(error) if LimitExceeded::of(&error).is_some() => Ok(Response::new(502, "Bad Gateway")),
+                fetched => fetched,
+            },
+            None => Ok(site.respond(request)),
+        }
+    }
+
+    /// # route_label
+    ///
+    /// **Purpose:**
+    /// Names the route a request is timed under in the duration histograms.
+    ///
+    /// **Parameters:**
+    /// - `site`: Virtual host selected for the request.
+    /// - `request`: Request after rewrites.
+    ///
+    /// **Returns:**
+    /// - `(proxy)` when a reverse proxy answers everything, else the matching route's pattern,
+    ///   else `(unrouted)` (static files and `404`s share it, keeping raw paths out of labels).
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let route = self.route_label(site, &request);
+    /// ```
+    fn route_label<'a>(&self, site: &'a VirtualHost, request: &Request) -> &'a str {
+        if self.proxy.is_some() {
+            return "(proxy)";
+        }
+        site.route_pattern(request).unwrap_or("(unrouted)")
+    }
+}
+
+
+impl ServerHandle {
+
+    /// # local_addr
+    ///
+    /// **Purpose:**
+    /// Returns the address the server listens on, with the port the OS picked when started on
+    /// port 0.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - The bound listener address.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::server::Server;
+    /// # let handle = Server::new("127.0.0.1:0").start()?;
+    /// let port = handle.local_addr().port();
+    /// # assert_ne!(port, 0);
+    /// # handle.shutdown();
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    /// # shutdown
+    ///
+    /// **Purpose:**
+    /// Stops the server at once: the accept loop ends and every open connection is closed,
+    /// mid-response or not.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// None, once no connection is open; `await_stopped` then returns as soon as the workers
+    /// have exited.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::server::Server;
+    /// # let handle = Server::new("127.0.0.1:0").start()?;
+    /// handle.shutdown();
+    /// # handle.await_stopped()?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn shutdown(&self) {
+        self.server.close(self.local_addr);
+    }
+
+    /// # graceful_shutdown
+    ///
+    /// **Purpose:**
+    /// Drains the server as `SIGTERM` would: the readiness probe fails, keep-alive connections
+    /// close after their next response, and once no connection is open or `deadline` passes,
+    /// the accept loop ends and whatever is left is closed.
+    ///
+    /// **Parameters:**
+    /// - `deadline`: Longest time to wait for open connections to finish.
+    ///
+    /// **Returns:**
+    /// None, once no connection is open. Blocks for up to `deadline`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::server::Server;
+    /// # use std::time::Duration;
+    /// # let handle = Server::new("127.0.0.1:0").start()?;
+    /// handle.graceful_shutdown(Duration::from_secs(10));
+    /// # handle.await_stopped()?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn graceful_shutdown(&self, deadline: Duration) {
+        self.server.drain_within(self.local_addr, deadline);
+    }
+
+    /// # await_stopped
+    ///
+    /// **Purpose:**
+    /// Waits for the server to stop, whether through this handle, the admin API, `SIGTERM`, or
+    /// an accept failure, and for its workers to finish.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - `Ok(())` once a stopped server's workers have all exited.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns the accept error that ended the server, or `Other` if the accept thread
+    ///   panicked.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::server::Server;
+    /// # let handle = Server::new("127.0.0.1:0").start()?;
+    /// handle.shutdown();
+    /// handle.await_stopped()?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn await_stopped(self) -> io::Result<()> {
+        self.thread
+            .join()
+            .unwrap_or_else(|_| Err(io::Error::other("the accept thread panicked")))
+    }
+}
+
+
+/// # refuse
+///
+/// **Purpose:**
+/// Ends a connection whose request could not be read, answering first when the client is owed
+/// a response (see `rejection`).
+///
+/// **Parameters:**
+/// - `stream`: Client connection.
+/// - `metrics`: Counters to record the refusal's status in.
+/// - `error`: Error that ended the read.
+///
+/// **Returns:**
+/// - `error`, so the worker reports why the connection was dropped.
+///
+/// **Errors / Failures:**
+/// - Failures writing the refusal are ignored.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let request = match Request::read_head(&mut reader, &limits) { Ok(r) => r, Err(e) => return Err(refuse(&stream, &self.metrics, e)) };
+/// ```
+fn refuse(stream: &TcpStream, metrics: &Metrics, error: io::Error) -> io::Error {
+    if let Some(response) = rejection(&error) {
+        metrics.record_response(response.status);
+        let _ = response.write_final(stream, REJECTION_WRITE_TIMEOUT);
+        linger(stream);
+    }
+    error
+}
+
+
+/// # unmet_expectation
+///
+/// **Purpose:**
+/// Refuses `Expect` headers asking for anything other than `100-continue`.
+///
+/// **Parameters:**
+/// - `request`: Request with its head read.
+///
+/// **Returns:**
+/// - `Some(417 Expectation Failed)` for an unsupported expectation, otherwise `None`.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let refusal = unmet_expectation(&request);
+/// ```
+fn unmet_expectation(request: &Request) -> Option<Response> {
+    let unmet = request
+        .headers
+        .get_all("Expect")
+        .iter()
+        .flat_map(|value| value.split(','))
+        .map(str::trim)
+        .any(|expectation| !expectation.is_empty() && !expectation.eq_ignore_ascii_case("100-continue"));
+    unmet.then(|| {
+        Response::new(417, "Expectation Failed")
+            .with_header("Content-Type", "text/plain; charset=utf-8")
+            .with_body("unsupported expectation")
+    })
+}
+
+
+/// # rejection
+///
+/// **Purpose:**
+/// Picks the response owed to a client whose request could not be read.
+///
+/// **Parameters:**
+/// - `error`: Error raised while reading the request head or body.
+///
+/// **Returns:**
+/// - `Some(408)` when the client was too slow, the matching `4xx` when it broke a size limit,
+///   `Some(505)` when it asked for an HTTP major version other than 1, `Some(400)` when the
+///   request was malformed, otherwise `None` (the client went away or the connection failed,
+///   so it is just closed).
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// if let Some(response) = rejection(&error) { response.write_final(&stream, REJECTION_WRITE_TIMEOUT)?; }
+/// ```
+fn rejection(error: &io::Error) -> Option<Response> {
+    if let Some(limit) = LimitExceeded::of(error) {
+        return Some(limit.response());
+    }
+    if let Some(reason) = RequestError::of(error) {
+        return Some(reason.response());
+    }
+    match error.kind() {
+        io::ErrorKind::TimedOut => Some(
+            Response::new(408, "Request Timeout")
+                .with_header("Content-Type", "text/plain; charset=utf-8")
+                .with_body("request not received in time"),
+        ),
+        io::ErrorKind::InvalidData => Some(
+            Response::new(400, "Bad Request")
+                .with_header("Content-Type", "text/plain; charset=utf-8")
+                .with_body("malformed request"),
+        ),
+        _ => None,
+    }
+}
+
+
+/// # buffered
+///
+/// **Purpose:**
+/// Tells whether bytes of a further request are already in hand, in the buffer or preloaded,
+/// so the connection must be served on rather than parked.
+///
+/// **Parameters:**
+/// - `buf_reader`: Buffered client connection.
+///
+/// **Returns:**
+/// - `true` if unread request bytes remain.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// if buffered(&buf_reader) { linger(&stream); }
+/// ```
+fn buffered(buf_reader: &BufReader<ClientStream<'_>>) -> bool {
+    !buf_reader.buffer().is_empty() || buf_reader.get_ref().preloaded()
+}
+
+
+/// # linger
+///
+/// **Purpose:**
+/// Discards what a refused client, or one with pipelined requests left unanswered, is still
+/// sending, for a bounded time and amount, so that closing the socket does not reset the
+/// connection before the client reads the last response.
+///
+/// **Parameters:**
+/// - `stream`: Client connection whose sending side has been shut down.
+///
+/// **Returns:**
+/// None.
+///
+/// **Errors / Failures:**
+/// None. Read errors end the wait.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// linger(&stream);
+/// ```
+fn linger(mut stream: &TcpStream) {
+    let started = Instant::now();
+    let mut discard = [0u8; 8192];
+    let mut drained = 0;
+    while started.elapsed() < LINGER_TIME && drained < LINGER_BYTES {
+        let remaining = LINGER_TIME.saturating_sub(started.elapsed()).max(Duration::from_millis(1));
+        if stream.set_read_timeout(Some(remaining)).is_err() {
+            return;
+        }
+        match stream.read(&mut discard) {
+            Ok(read) if read > 0 => drained += read,
+            _ => return,
+        }
+    }
+}
+
+
+/// # bad_request
+///
+/// **Purpose:**
+/// Builds the `400 Bad Request` sent when a request head is refused after parsing: a missing,
+/// malformed, or repeated `Host` header, or a header repeated against the duplicate header
+/// policy.
+///
+/// **Parameters:**
+/// - `reason`: Short explanation sent as the body.
+///
+/// **Returns:**
+/// - The `400` response.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let refusal = bad_request("missing Host header");
+/// ```
+fn bad_request(reason: &str) -> Response {
+    Response::new(400, "Bad Request")
+        .with_header("Content-Type", "text/plain; charset=utf-8")
+        .with_body(reason)
+}
+
+
+/// # is_authority
+///
+/// **Purpose:**
+/// Checks that a `Host` header value is a plausible `host[:port]`: a bracketed IPv6 literal or
+/// a name of letters, digits, `-`, `.` and `_`, optionally followed by a `:` and a numeric port.
+///
+/// **Parameters:**
+/// - `value`: Raw `Host` header value.
+///
+/// **Returns:**
+/// - `true` if the value is well formed.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// assert!(is_authority("example.com:8080"));
+/// assert!(!is_authority("evil.com/x"));
+/// ```
+fn is_authority(value: &str) -> bool {
+    let value = value.trim();
+    let (host_ok, port) = match value.strip_prefix('[') {
+        Some(rest) => match rest.split_once(']') {
+            Some((literal, port)) => (literal.parse::<Ipv6Addr>().is_ok(), port),
+            None => return false,
+        },
+        None => {
+            let (host, port) = value.split_at(value.find(':').unwrap_or(value.len()));
+            let name_ok = host.bytes().all(|byte| byte.is_ascii_alphanumeric() || b"-._".contains(&byte));
+            (!host.is_empty() && name_ok, port)
+        }
+    };
+    let port_ok = match port.strip_prefix(':') {
+        Some(digits) => digits.len() <= 5 && digits.bytes().all(|byte| byte.is_ascii_digit()),
+        None => port.is_empty(),
+    };
+    host_ok && port_ok
+}
+
+
+/// # host_name
+///
+/// **Purpose:**
+/// Reduces a `Host` header value to a lower-cased host name without its port.
+///
+/// **Parameters:**
+/// - `value`: Raw `Host` header value (e.g. `Example.com:8080` or `[::1]:80`).
+///
+/// **Returns:**
+/// - The bare host name.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// assert_eq!(host_name("Example.com:8080"), "example.com");
+/// ```
+pub(crate) fn host_name(value: &str) -> String {
+    let value = value.trim();
+    let host = match value.strip_prefix('[') {
+        Some(rest) => rest.split(']').next().unwrap_or(""),
+        None => value.split(':').next().unwrap_or(""),
+    };
+    host.to_ascii_lowercase()
+}
+
+
+#[cfg(all(test, feature = "async"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn blocking_entry_points_refuse_async_routes() {
+        let router = crate::router::Router::new().get_async("/slow", |_| async { Response::new(200, "OK") });
+        let server = Server::new("127.0.0.1:0").default_host(VirtualHost::new().router(router));
+        let error = server.start().err().unwrap();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+    }
+}
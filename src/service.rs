@@ -0,0 +1,565 @@
+
+// ============================================================
+//  DAEGONICA SOFTWARE — service.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: Service
+//!
+//! **Purpose:**
+//! Runs the server as a Windows service, so the Service Control Manager can start and stop it
+//! without a wrapper such as NSSM.
+//!
+//! **Context:**
+//! - `Service::run` takes the place of `Server::run` in the executable; `run` reports the
+//!   service as running from `start_services`, once the listeners are bound.
+//!
+//! **Responsibilities:**
+//! - Connects the process to the Service Control Manager and registers the control handler.
+//! - Reports the start-pending, running, stop-pending, and stopped states, with the exit code
+//!   of a server that failed.
+//! - Maps the stop and system shutdown controls to `Server::stop`, so queued connections are
+//!   served before the service reports stopped.
+//! - Runs the server in the foreground instead when the process was not started by the
+//!   Service Control Manager, so the same executable still works from a console.
+//! - Does NOT install or remove the service (use `sc.exe create`), handle pause and continue,
+//!   or run more than one service per process.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+use std::io;
+
+#[cfg(windows)]
+use std::{
+    ffi::c_void,
+    iter,
+    net::SocketAddr,
+    ptr,
+    sync::{Arc, Mutex, PoisonError, Weak},
+};
+
+use crate::server::Server;
+
+
+/// # STOP_WAIT_HINT
+///
+/// **Summary:**
+/// Milliseconds the Service Control Manager is told to wait for queued connections to finish
+/// after a stop, before it considers the service hung.
+#[cfg(windows)]
+const STOP_WAIT_HINT: u32 = 30_000;
+
+
+/// # SERVICE_WIN32_OWN_PROCESS
+///
+/// **Summary:**
+/// Service type of a service that has its process to itself.
+#[cfg(windows)]
+const SERVICE_WIN32_OWN_PROCESS: u32 = 0x10;
+
+
+/// # SERVICE_STOPPED
+///
+/// **Summary:**
+/// State of a service that is not running.
+#[cfg(windows)]
+const SERVICE_STOPPED: u32 = 1;
+
+
+/// # SERVICE_START_PENDING
+///
+/// **Summary:**
+/// State of a service that is binding its listeners.
+#[cfg(windows)]
+const SERVICE_START_PENDING: u32 = 2;
+
+
+/// # SERVICE_STOP_PENDING
+///
+/// **Summary:**
+/// State of a service finishing its queued connections.
+#[cfg(windows)]
+const SERVICE_STOP_PENDING: u32 = 3;
+
+
+/// # SERVICE_RUNNING
+///
+/// **Summary:**
+/// State of a service that is serving.
+#[cfg(windows)]
+const SERVICE_RUNNING: u32 = 4;
+
+
+/// # SERVICE_CONTROL_STOP
+///
+/// **Summary:**
+/// Control asking the service to stop.
+#[cfg(windows)]
+const SERVICE_CONTROL_STOP: u32 = 1;
+
+
+/// # SERVICE_CONTROL_INTERROGATE
+///
+/// **Summary:**
+/// Control asking the service to report its state again.
+#[cfg(windows)]
+const SERVICE_CONTROL_INTERROGATE: u32 = 4;
+
+
+/// # SERVICE_CONTROL_SHUTDOWN
+///
+/// **Summary:**
+/// Control sent when the system shuts down.
+#[cfg(windows)]
+const SERVICE_CONTROL_SHUTDOWN: u32 = 5;
+
+
+/// # SERVICE_ACCEPT_STOP
+///
+/// **Summary:**
+/// Accept flag for `SERVICE_CONTROL_STOP`.
+#[cfg(windows)]
+const SERVICE_ACCEPT_STOP: u32 = 1;
+
+
+/// # SERVICE_ACCEPT_SHUTDOWN
+///
+/// **Summary:**
+/// Accept flag for `SERVICE_CONTROL_SHUTDOWN`.
+#[cfg(windows)]
+const SERVICE_ACCEPT_SHUTDOWN: u32 = 4;
+
+
+/// # NO_ERROR
+///
+/// **Summary:**
+/// Win32 code for success.
+#[cfg(windows)]
+const NO_ERROR: u32 = 0;
+
+
+/// # ERROR_CALL_NOT_IMPLEMENTED
+///
+/// **Summary:**
+/// Win32 code the handler returns for controls it does not handle.
+#[cfg(windows)]
+const ERROR_CALL_NOT_IMPLEMENTED: u32 = 120;
+
+
+/// # ERROR_FAILED_SERVICE_CONTROLLER_CONNECT
+///
+/// **Summary:**
+/// Win32 code the dispatcher fails with when the process was not started as a service.
+#[cfg(windows)]
+const ERROR_FAILED_SERVICE_CONTROLLER_CONNECT: i32 = 1063;
+
+
+/// # ERROR_SERVICE_SPECIFIC_ERROR
+///
+/// **Summary:**
+/// Win32 code telling the Service Control Manager to read the service-specific exit code instead.
+#[cfg(windows)]
+const ERROR_SERVICE_SPECIFIC_ERROR: u32 = 1066;
+
+
+/// # STATE
+///
+/// **Summary:**
+/// The one service this process runs, shared between the dispatcher, the service thread, and
+/// the control handler, which the Service Control Manager calls without a way to pass a
+/// closure.
+#[cfg(windows)]
+static STATE: Mutex<Option<State>> = Mutex::new(None);
+
+
+/// # Service
+///
+/// **Summary:**
+/// Windows service the server runs as.
+///
+/// **Fields:**
+/// - `name`: Service name, as registered with `sc.exe create`.
+///
+/// **Usage Example:**
+/// ```rust,no_run
+/// # use server::{server::Server, service::Service};
+/// // sc.exe create daegonica binPath= "C:\\srv\\server.exe --service daegonica"
+/// Service::new("daegonica").run(Server::new("0.0.0.0:80"))?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct Service {
+    name: String,
+}
+
+
+/// # State
+///
+/// **Summary:**
+/// Progress of the running service.
+///
+/// **Fields:**
+/// - `name`: Service name as a NUL-terminated UTF-16 string, kept alive for the dispatcher.
+/// - `server`: Server waiting for the service thread to run it.
+/// - `status`: Status handle from the control handler registration, once made.
+/// - `target`: The running server and its listener address, for the stop control.
+/// - `result`: Outcome of `Server::run`, handed back to `Service::run`.
+#[cfg(windows)]
+struct State {
+    name: Vec<u16>,
+    server: Option<Server>,
+    status: Option<isize>,
+    target: Option<(Weak<Server>, SocketAddr)>,
+    result: Option<io::Result<()>>,
+}
+
+
+/// # ServiceTableEntry
+///
+/// **Summary:**
+/// `SERVICE_TABLE_ENTRYW`: a service name and its entry point.
+///
+/// **Fields:**
+/// - `name`: NUL-terminated UTF-16 service name, or null to end the table.
+/// - `main`: Entry point the dispatcher runs on a new thread.
+#[cfg(windows)]
+#[repr(C)]
+struct ServiceTableEntry {
+    name: *const u16,
+    main: Option<unsafe extern "system" fn(u32, *mut *mut u16)>,
+}
+
+
+/// # ServiceStatus
+///
+/// **Summary:**
+/// `SERVICE_STATUS`, as passed to `SetServiceStatus`.
+///
+/// **Fields:**
+/// - `service_type`: `SERVICE_WIN32_OWN_PROCESS`.
+/// - `current_state`: One of the state codes.
+/// - `controls_accepted`: Accept flags; none while starting or stopping.
+/// - `win32_exit_code`: Win32 error the service stopped with.
+/// - `service_specific_exit_code`: Exit code when `win32_exit_code` is `ERROR_SERVICE_SPECIFIC_ERROR`.
+/// - `check_point`: Progress counter while pending.
+/// - `wait_hint`: Milliseconds until the next state change while pending.
+#[cfg(windows)]
+#[repr(C)]
+struct ServiceStatus {
+    service_type: u32,
+    current_state: u32,
+    controls_accepted: u32,
+    win32_exit_code: u32,
+    service_specific_exit_code: u32,
+    check_point: u32,
+    wait_hint: u32,
+}
+
+
+// Service Control Manager entry points from advapi32, declared here rather than through a
+// bindings crate since these three are all the server needs.
+#[cfg(windows)]
+#[link(name = "advapi32")]
+unsafe extern "system" {
+    fn StartServiceCtrlDispatcherW(table: *const ServiceTableEntry) -> i32;
+    fn RegisterServiceCtrlHandlerExW(
+        name: *const u16,
+        handler: unsafe extern "system" fn(u32, u32, *mut c_void, *mut c_void) -> u32,
+        context: *mut c_void,
+    ) -> isize;
+    fn SetServiceStatus(status: isize, service_status: *const ServiceStatus) -> i32;
+}
+
+
+impl Service {
+
+    /// # new
+    ///
+    /// **Purpose:**
+    /// Names the service to run as.
+    ///
+    /// **Parameters:**
+    /// - `name`: Service name, as registered with the Service Control Manager.
+    ///
+    /// **Returns:**
+    /// - A new `Service`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::service::Service;
+    /// let service = Service::new("daegonica");
+    /// ```
+    pub fn new(name: impl Into<String>) -> Service {
+        Service { name: name.into() }
+    }
+
+    /// # run
+    ///
+    /// **Purpose:**
+    /// Hands the process to the Service Control Manager and serves until the service is stopped.
+    /// Blocks the calling thread, which must be the main thread, while the server runs on the
+    /// thread the dispatcher starts.
+    ///
+    /// **Parameters:**
+    /// - `server`: Server to run as the service.
+    ///
+    /// **Returns:**
+    /// - `Ok(())` once the service has stopped cleanly.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns the server's own error if `Server::run` fails; the service then reports that
+    ///   error as its exit code.
+    /// - Returns the OS error if the dispatcher or the control handler cannot be registered.
+    /// - Runs the server in the foreground if the process was not started as a service.
+    ///
+    /// **Examples:**
+    /// ```rust,no_run
+    /// # use server::{server::Server, service::Service};
+    /// # let server = Server::new("0.0.0.0:80");
+    /// Service::new("daegonica").run(server)?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    #[cfg(windows)]
+    pub fn run(self, server: Server) -> io::Result<()> {
+        let name: Vec<u16> = self.name.encode_utf16().chain(iter::once(0)).collect();
+        let table = [
+            ServiceTableEntry { name: name.as_ptr(), main: Some(service_main) },
+            ServiceTableEntry { name: ptr::null(), main: None },
+        ];
+        *lock() = Some(State { name, server: Some(server), status: None, target: None, result: None });
+
+        // SAFETY: the table ends with a null entry, and the name it points to lives in `STATE`
+        // until the dispatcher returns.
+        let dispatched = unsafe { StartServiceCtrlDispatcherW(table.as_ptr()) } != 0;
+        let state = lock().take();
+        if !dispatched {
+            let err = io::Error::last_os_error();
+            let server = state.and_then(|state| state.server);
+            return match server {
+                Some(server) if err.raw_os_error() == Some(ERROR_FAILED_SERVICE_CONTROLLER_CONNECT) => server.run(),
+                _ => Err(err),
+            };
+        }
+        state.and_then(|state| state.result).unwrap_or(Ok(()))
+    }
+
+    /// # run
+    ///
+    /// **Purpose:**
+    /// Stands in for running as a service on platforms other than Windows.
+    ///
+    /// **Parameters:**
+    /// - `_server`: Server that would have run.
+    ///
+    /// **Returns:**
+    /// Never returns `Ok`.
+    ///
+    /// **Errors / Failures:**
+    /// - Always `Unsupported`; use `Daemon` or a process supervisor instead.
+    ///
+    /// **Examples:**
+    /// ```rust,no_run
+    /// # use server::{server::Server, service::Service};
+    /// # let server = Server::new("0.0.0.0:80");
+    /// Service::new("daegonica").run(server)?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    #[cfg(not(windows))]
+    pub fn run(self, _server: Server) -> io::Result<()> {
+        Err(io::Error::new(io::ErrorKind::Unsupported, format!("cannot run {} as a service: Windows services need Windows", self.name)))
+    }
+}
+
+
+/// # started
+///
+/// **Purpose:**
+/// Reports the service as running once a server has bound its listeners, and remembers the
+/// server for the stop control.
+///
+/// **Parameters:**
+/// - `server`: Server that just started.
+/// - `listener`: Address its public listener is bound to.
+///
+/// **Returns:**
+/// None.
+///
+/// **Errors / Failures:**
+/// None. Does nothing unless the process is running as a service and no server was reported
+/// before.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// service::started(self, listener);
+/// ```
+#[cfg(windows)]
+pub(crate) fn started(server: &Arc<Server>, listener: SocketAddr) {
+    let mut state = lock();
+    let Some(state) = state.as_mut().filter(|state| state.status.is_some() && state.target.is_none()) else {
+        return;
+    };
+    state.target = Some((Arc::downgrade(server), listener));
+    report(state, SERVICE_RUNNING, None);
+}
+
+
+/// # service_main
+///
+/// **Purpose:**
+/// Entry point the dispatcher runs: registers the control handler and runs the server until it
+/// stops.
+///
+/// **Parameters:**
+/// - `_argc`, `_argv`: Start parameters from the Service Control Manager (unused).
+///
+/// **Returns:**
+/// None. The server's outcome is left in `STATE` for `Service::run`.
+///
+/// **Errors / Failures:**
+/// None. A failed registration or server is recorded as the result.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let table = [ServiceTableEntry { name: name.as_ptr(), main: Some(service_main) }];
+/// ```
+#[cfg(windows)]
+unsafe extern "system" fn service_main(_argc: u32, _argv: *mut *mut u16) {
+    let server = {
+        let mut guard = lock();
+        let Some(state) = guard.as_mut() else {
+            return;
+        };
+        // SAFETY: the name is NUL-terminated, and the handler has the required signature.
+        let status = unsafe { RegisterServiceCtrlHandlerExW(state.name.as_ptr(), control_handler, ptr::null_mut()) };
+        if status == 0 {
+            state.result = Some(Err(io::Error::last_os_error()));
+            return;
+        }
+        state.status = Some(status);
+        report(state, SERVICE_START_PENDING, None);
+        state.server.take()
+    };
+    let result = server.map_or(Ok(()), Server::run);
+    if let Some(state) = lock().as_mut() {
+        report(state, SERVICE_STOPPED, result.as_ref().err());
+        state.result = Some(result);
+    }
+}
+
+
+/// # control_handler
+///
+/// **Purpose:**
+/// Answers controls from the Service Control Manager, stopping the server on a stop or system
+/// shutdown.
+///
+/// **Parameters:**
+/// - `control`: Control code.
+/// - `_event_type`, `_event_data`, `_context`: Extra control data (unused).
+///
+/// **Returns:**
+/// - `NO_ERROR` for the controls handled, `ERROR_CALL_NOT_IMPLEMENTED` for the rest.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// RegisterServiceCtrlHandlerExW(name.as_ptr(), control_handler, ptr::null_mut());
+/// ```
+#[cfg(windows)]
+unsafe extern "system" fn control_handler(control: u32, _event_type: u32, _event_data: *mut c_void, _context: *mut c_void) -> u32 {
+    match control {
+        SERVICE_CONTROL_STOP | SERVICE_CONTROL_SHUTDOWN => {
+            let target = lock().as_mut().and_then(|state| {
+                report(state, SERVICE_STOP_PENDING, None);
+                state.target.take()
+            });
+            if let Some((server, listener)) = target
+                && let Some(server) = server.upgrade()
+            {
+                server.stop(listener);
+            }
+            NO_ERROR
+        }
+        SERVICE_CONTROL_INTERROGATE => NO_ERROR,
+        _ => ERROR_CALL_NOT_IMPLEMENTED,
+    }
+}
+
+
+/// # report
+///
+/// **Purpose:**
+/// Tells the Service Control Manager the service's state.
+///
+/// **Parameters:**
+/// - `state`: Running service, with its status handle.
+/// - `current_state`: State code to report.
+/// - `failure`: For `SERVICE_STOPPED`, the error the server failed with, if it did.
+///
+/// **Returns:**
+/// None.
+///
+/// **Errors / Failures:**
+/// None. Does nothing before the handler is registered, and a failed report is ignored.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// report(state, SERVICE_RUNNING, None);
+/// ```
+#[cfg(windows)]
+fn report(state: &State, current_state: u32, failure: Option<&io::Error>) {
+    let Some(status) = state.status else {
+        return;
+    };
+    let (win32_exit_code, service_specific_exit_code) = match failure.map(io::Error::raw_os_error) {
+        None => (NO_ERROR, 0),
+        Some(Some(code)) => (code as u32, 0),
+        Some(None) => (ERROR_SERVICE_SPECIFIC_ERROR, 1),
+    };
+    let service_status = ServiceStatus {
+        service_type: SERVICE_WIN32_OWN_PROCESS,
+        current_state,
+        controls_accepted: if current_state == SERVICE_RUNNING { SERVICE_ACCEPT_STOP | SERVICE_ACCEPT_SHUTDOWN } else { 0 },
+        win32_exit_code,
+        service_specific_exit_code,
+        check_point: u32::from(current_state == SERVICE_START_PENDING || current_state == SERVICE_STOP_PENDING),
+        wait_hint: if current_state == SERVICE_STOP_PENDING { STOP_WAIT_HINT } else { 0 },
+    };
+    // SAFETY: `status` came from `RegisterServiceCtrlHandlerExW`, and the status is a valid value.
+    unsafe { SetServiceStatus(status, &raw const service_status) };
+}
+
+
+/// # lock
+///
+/// **Purpose:**
+/// Locks `STATE`, recovering it if a thread panicked while holding it.
+///
+/// **Parameters:**
+/// None.
+///
+/// **Returns:**
+/// - The guard.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let state = lock().take();
+/// ```
+#[cfg(windows)]
+fn lock() -> std::sync::MutexGuard<'static, Option<State>> {
+    STATE.lock().unwrap_or_else(PoisonError::into_inner)
+}
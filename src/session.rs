@@ -0,0 +1,944 @@
+
+// ============================================================
+//  DAEGONICA SOFTWARE — session.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: Session
+//!
+//! **Purpose:**
+//! Keeps per-visitor key/value data across requests, identified by a session cookie.
+//!
+//! **Context:**
+//! - Installed as router middleware with `Router::middleware(sessions.middleware())`; handlers
+//!   and later middleware (CSRF, login flows) read the `Session` from the request extensions.
+//!
+//! **Responsibilities:**
+//! - Loads the session named by the cookie, or starts an empty one.
+//! - Stores sessions in memory up to a size cap, expiring them after an idle timeout and
+//!   evicting the least recently used when the cap is reached.
+//! - Sets the cookie once a new session holds data, deletes it when a session is cleared, and
+//!   moves the data to a fresh ID when a login or privilege change asks for it.
+//! - Does NOT persist sessions across restarts or share them between processes.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+use std::{
+    collections::{BTreeSet, HashMap},
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+use crate::{
+    cookie::{self, Cookie, SameSite},
+    extract::FromRequest,
+    random,
+    request::Request,
+    response::Response,
+    router::Next,
+};
+
+
+/// # DEFAULT_IDLE_TIMEOUT
+///
+/// **Summary:**
+/// Idle time after which a session expires unless configured otherwise (30 minutes).
+const DEFAULT_IDLE_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+
+
+/// # DEFAULT_MAX_SESSIONS
+///
+/// **Summary:**
+/// Live sessions kept unless configured otherwise.
+const DEFAULT_MAX_SESSIONS: usize = 100_000;
+
+
+/// # Session
+///
+/// **Summary:**
+/// Handle to one visitor's session data; clones share the same data.
+///
+/// **Fields:**
+/// - `id`: Session identifier sent in the cookie.
+/// - `data`: Key/value pairs.
+/// - `renew`: Set by `renew` to move the data to a fresh ID when the response is sent.
+///
+/// **Usage Example:**
+/// ```rust
+/// # use server::{extract::FromRequest, request::Request, response::Response, session::{Session, Sessions}};
+/// fn visit(session: Session) -> Response {
+///     let visits: u32 = session.get("visits").and_then(|v| v.parse().ok()).unwrap_or(0) + 1;
+///     session.insert("visits", visits.to_string());
+///     Response::new(200, "OK").with_body(visits.to_string())
+/// }
+/// # let request = &Request::read_from(&mut &b"GET / HTTP/1.1\r\n\r\n"[..]).unwrap();
+/// # let response = Sessions::new().handle(request, &|request| visit(Session::from_request(request).unwrap()));
+/// # assert_eq!(response.body, b"1");
+/// ```
+#[derive(Debug, Clone)]
+pub struct Session {
+    id: String,
+    data: Arc<Mutex<HashMap<String, String>>>,
+    renew: Arc<AtomicBool>,
+}
+
+
+/// # Entry
+///
+/// **Summary:**
+/// Stored session and its last use.
+///
+/// **Fields:**
+/// - `session`: Session handle.
+/// - `last_seen`: When the session was last loaded.
+struct Entry {
+    session: Session,
+    last_seen: Instant,
+}
+
+
+/// # Store
+///
+/// **Summary:**
+/// Live sessions with an index ordered by last use, so expiry and eviction take the oldest
+/// first without scanning.
+///
+/// **Fields:**
+/// - `entries`: Sessions by ID.
+/// - `by_use`: `(last_seen, id)` of every entry, least recently used first.
+#[derive(Default)]
+struct Store {
+    entries: HashMap<String, Entry>,
+    by_use: BTreeSet<(Instant, String)>,
+}
+
+
+/// # Sessions
+///
+/// **Summary:**
+/// Session middleware configuration and in-memory store.
+///
+/// **Fields:**
+/// - `cookie_name`: Name of the session cookie.
+/// - `idle_timeout`: Inactivity after which a session expires.
+/// - `secure`: Mark the cookie `Secure`.
+/// - `same_site`: `SameSite` attribute of the cookie.
+/// - `max_sessions`: Live sessions kept before the least recently used is evicted.
+/// - `store`: Live sessions.
+///
+/// **Usage Example:**
+/// ```rust
+/// # use server::{extract::extract, response::Response, router::Router, session::{Session, Sessions}};
+/// # use std::time::Duration;
+/// # fn visit(_: Session) -> Response { Response::new(200, "OK") }
+/// let sessions = Sessions::new().idle_timeout(Duration::from_secs(3600)).secure(true);
+/// let router = Router::new().middleware(sessions.middleware()).get("/visit", extract(visit));
+/// ```
+#[derive(Clone)]
+pub struct Sessions {
+    cookie_name: String,
+    idle_timeout: Duration,
+    secure: bool,
+    same_site: SameSite,
+    max_sessions: usize,
+    store: Arc<Mutex<Store>>,
+}
+
+
+impl Session {
+
+    /// # new
+    ///
+    /// **Purpose:**
+    /// Starts an empty session with a fresh random ID.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - A new `Session`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let session = Session::new();
+    /// ```
+    fn new() -> Session {
+        Session {
+            id: random::token(),
+            data: Arc::default(),
+            renew: Arc::default(),
+        }
+    }
+
+    /// # id
+    ///
+    /// **Purpose:**
+    /// Returns the session identifier.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - The ID sent in the session cookie.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::log::{self, LogLevel};
+    /// # use server::{extract::FromRequest, request::Request, response::Response, session::{Session, Sessions}};
+    /// # let request = &Request::read_from(&mut &b"GET / HTTP/1.1\r\n\r\n"[..]).unwrap();
+    /// # Sessions::new().handle(request, &|request| {
+    /// # let session = Session::from_request(request).unwrap();
+    /// log::emit(LogLevel::Info, format_args!("session {}", session.id()));
+    /// # Response::new(200, "OK")
+    /// # });
+    /// ```
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// # get
+    ///
+    /// **Purpose:**
+    /// Reads a value.
+    ///
+    /// **Parameters:**
+    /// - `key`: Key to read.
+    ///
+    /// **Returns:**
+    /// - `Some(value)` if set, otherwise `None`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{extract::FromRequest, request::Request, response::Response, session::{Session, Sessions}};
+    /// # let request = &Request::read_from(&mut &b"GET / HTTP/1.1\r\n\r\n"[..]).unwrap();
+    /// # Sessions::new().handle(request, &|request| {
+    /// # let session = Session::from_request(request).unwrap();
+    /// let user = session.get("user_id");
+    /// # assert_eq!(user, None);
+    /// # Response::new(200, "OK")
+    /// # });
+    /// ```
+    pub fn get(&self, key: &str) -> Option<String> {
+        self.data.lock().unwrap().get(key).cloned()
+    }
+
+    /// # insert
+    ///
+    /// **Purpose:**
+    /// Sets a value, replacing any earlier one.
+    ///
+    /// **Parameters:**
+    /// - `key`: Key to set.
+    /// - `value`: Value to store.
+    ///
+    /// **Returns:**
+    /// None.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # struct User { id: u64 }
+    /// # let user = User { id: 7 };
+    /// # use server::{extract::FromRequest, request::Request, response::Response, session::{Session, Sessions}};
+    /// # let request = &Request::read_from(&mut &b"GET / HTTP/1.1\r\n\r\n"[..]).unwrap();
+    /// # Sessions::new().handle(request, &|request| {
+    /// # let session = Session::from_request(request).unwrap();
+    /// session.insert("user_id", user.id.to_string());
+    /// # assert_eq!(session.get("user_id").as_deref(), Some("7"));
+    /// # Response::new(200, "OK")
+    /// # });
+    /// ```
+    pub fn insert(&self, key: impl Into<String>, value: impl Into<String>) {
+        self.data.lock().unwrap().insert(key.into(), value.into());
+    }
+
+    /// # remove
+    ///
+    /// **Purpose:**
+    /// Deletes a value.
+    ///
+    /// **Parameters:**
+    /// - `key`: Key to delete.
+    ///
+    /// **Returns:**
+    /// - The removed value, if any.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{extract::FromRequest, request::Request, response::Response, session::{Session, Sessions}};
+    /// # let request = &Request::read_from(&mut &b"GET / HTTP/1.1\r\n\r\n"[..]).unwrap();
+    /// # Sessions::new().handle(request, &|request| {
+    /// # let session = Session::from_request(request).unwrap();
+    /// # session.insert("flash", "Saved");
+    /// session.remove("flash");
+    /// # assert!(session.is_empty());
+    /// # Response::new(200, "OK")
+    /// # });
+    /// ```
+    pub fn remove(&self, key: &str) -> Option<String> {
+        self.data.lock().unwrap().remove(key)
+    }
+
+    /// # clear
+    ///
+    /// **Purpose:**
+    /// Deletes every value; an emptied session is discarded and its cookie removed (e.g. on logout).
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// None.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{extract::FromRequest, request::Request, response::Response, session::{Session, Sessions}};
+    /// # let request = &Request::read_from(&mut &b"GET / HTTP/1.1\r\n\r\n"[..]).unwrap();
+    /// # Sessions::new().handle(request, &|request| {
+    /// # let session = Session::from_request(request).unwrap();
+    /// # session.insert("user_id", "7");
+    /// session.clear();
+    /// # assert!(session.is_empty());
+    /// # Response::new(200, "OK")
+    /// # });
+    /// ```
+    pub fn clear(&self) {
+        self.data.lock().unwrap().clear();
+    }
+
+    /// # is_empty
+    ///
+    /// **Purpose:**
+    /// Checks whether the session holds no values.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - `true` if empty.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # fn login_page() -> Response { Response::redirect(303, "/login") }
+    /// # use server::{extract::FromRequest, request::Request, response::Response, session::{Session, Sessions}};
+    /// # let request = &Request::read_from(&mut &b"GET / HTTP/1.1\r\n\r\n"[..]).unwrap();
+    /// # Sessions::new().handle(request, &|request| {
+    /// # let session = Session::from_request(request).unwrap();
+    /// if session.is_empty() { return login_page(); }
+    /// # Response::new(200, "OK")
+    /// # });
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.data.lock().unwrap().is_empty()
+    }
+
+    /// # renew
+    ///
+    /// **Purpose:**
+    /// Moves the session to a fresh ID when the response is sent, invalidating the old one.
+    /// Call it on login and any other privilege change, so an ID an attacker planted or read
+    /// beforehand (session fixation) does not carry the new rights.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// None.
+    ///
+    /// **Errors / Failures:**
+    /// None. `id` keeps returning the old ID until the next request.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # struct User { id: u64 }
+    /// # let user = User { id: 7 };
+    /// # use server::{extract::FromRequest, request::Request, response::Response, session::{Session, Sessions}};
+    /// # let request = &Request::read_from(&mut &b"GET / HTTP/1.1\r\n\r\n"[..]).unwrap();
+    /// # Sessions::new().handle(request, &|request| {
+    /// # let session = Session::from_request(request).unwrap();
+    /// session.insert("user_id", user.id.to_string());
+    /// session.renew();
+    /// # Response::new(200, "OK")
+    /// # });
+    /// ```
+    pub fn renew(&self) {
+        self.renew.store(true, Ordering::Relaxed);
+    }
+}
+
+
+impl FromRequest for Session {
+
+    /// # from_request
+    ///
+    /// **Purpose:**
+    /// Fetches the session attached by the `Sessions` middleware.
+    ///
+    /// **Parameters:**
+    /// - `request`: Request being handled.
+    ///
+    /// **Returns:**
+    /// - The request's session.
+    ///
+    /// **Errors / Failures:**
+    /// - Rejects with `500 Internal Server Error` if the middleware is not installed.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{extract::FromRequest, request::Request, response::Response, session::{Session, Sessions}};
+    /// # fn handler(request: &Request) -> Result<Response, Response> {
+    /// let session = Session::from_request(&request)?;
+    /// # Ok(Response::new(200, "OK"))
+    /// # }
+    /// # let request = &Request::read_from(&mut &b"GET / HTTP/1.1\r\n\r\n"[..]).unwrap();
+    /// # let response = Sessions::new().handle(request, &|request| handler(request).unwrap_or_else(|rejection| rejection));
+    /// # assert_eq!(response.status, 200);
+    /// ```
+    fn from_request(request: &Request) -> Result<Session, Response> {
+        request
+            .extensions
+            .get::<Session>()
+            .cloned()
+            .ok_or_else(|| Response::new(500, "Internal Server Error"))
+    }
+}
+
+
+impl Default for Sessions {
+
+    /// # default
+    ///
+    /// **Purpose:**
+    /// Creates the default configuration: cookie `sid`, 30-minute idle timeout, `SameSite=Lax`,
+    /// and at most 100,000 live sessions.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - A new `Sessions` with an empty store.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::session::Sessions;
+    /// let sessions = Sessions::default();
+    /// ```
+    fn default() -> Sessions {
+        Sessions {
+            cookie_name: "sid".to_string(),
+            idle_timeout: DEFAULT_IDLE_TIMEOUT,
+            secure: false,
+            same_site: SameSite::Lax,
+            max_sessions: DEFAULT_MAX_SESSIONS,
+            store: Arc::default(),
+        }
+    }
+}
+
+
+impl Sessions {
+
+    /// # new
+    ///
+    /// **Purpose:**
+    /// Creates the default configuration (see `default`).
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - A new `Sessions`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::session::Sessions;
+    /// let sessions = Sessions::new();
+    /// ```
+    pub fn new() -> Sessions {
+        Sessions::default()
+    }
+
+    /// # cookie_name
+    ///
+    /// **Purpose:**
+    /// Sets the session cookie name.
+    ///
+    /// **Parameters:**
+    /// - `name`: Cookie name.
+    ///
+    /// **Returns:**
+    /// - The updated `Sessions`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::session::Sessions;
+    /// let sessions = Sessions::new().cookie_name("app_session");
+    /// ```
+    pub fn cookie_name(mut self, name: impl Into<String>) -> Sessions {
+        self.cookie_name = name.into();
+        self
+    }
+
+    /// # idle_timeout
+    ///
+    /// **Purpose:**
+    /// Sets how long an unused session is kept.
+    ///
+    /// **Parameters:**
+    /// - `timeout`: Idle lifetime.
+    ///
+    /// **Returns:**
+    /// - The updated `Sessions`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::session::Sessions;
+    /// # use std::time::Duration;
+    /// let sessions = Sessions::new().idle_timeout(Duration::from_secs(8 * 3600));
+    /// ```
+    pub fn idle_timeout(mut self, timeout: Duration) -> Sessions {
+        self.idle_timeout = timeout;
+        self
+    }
+
+    /// # secure
+    ///
+    /// **Purpose:**
+    /// Marks the session cookie `Secure` (HTTPS only).
+    ///
+    /// **Parameters:**
+    /// - `secure`: Whether to set the attribute.
+    ///
+    /// **Returns:**
+    /// - The updated `Sessions`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::session::Sessions;
+    /// let sessions = Sessions::new().secure(true);
+    /// ```
+    pub fn secure(mut self, secure: bool) -> Sessions {
+        self.secure = secure;
+        self
+    }
+
+    /// # same_site
+    ///
+    /// **Purpose:**
+    /// Sets the `SameSite` attribute of the session cookie.
+    ///
+    /// **Parameters:**
+    /// - `same_site`: Cross-site policy.
+    ///
+    /// **Returns:**
+    /// - The updated `Sessions`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{cookie::SameSite, session::Sessions};
+    /// let sessions = Sessions::new().same_site(SameSite::Strict);
+    /// ```
+    pub fn same_site(mut self, same_site: SameSite) -> Sessions {
+        self.same_site = same_site;
+        self
+    }
+
+    /// # max_sessions
+    ///
+    /// **Purpose:**
+    /// Caps the number of live sessions; starting one more evicts the least recently used.
+    ///
+    /// **Parameters:**
+    /// - `limit`: Most sessions to keep (at least one).
+    ///
+    /// **Returns:**
+    /// - The updated `Sessions`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::session::Sessions;
+    /// let sessions = Sessions::new().max_sessions(10_000);
+    /// ```
+    pub fn max_sessions(mut self, limit: usize) -> Sessions {
+        self.max_sessions = limit.max(1);
+        self
+    }
+
+    /// # sweep
+    ///
+    /// **Purpose:**
+    /// Drops every expired session from the store.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - The number of sessions removed.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::session::Sessions;
+    /// # let sessions = Sessions::new();
+    /// let expired = sessions.sweep();
+    /// # assert_eq!(expired, 0);
+    /// ```
+    pub fn sweep(&self) -> usize {
+        let mut store = self.store.lock().unwrap();
+        let mut removed = 0;
+        while let Some((last_seen, _)) = store.by_use.first()
+            && last_seen.elapsed() >= self.idle_timeout
+        {
+            store.evict_oldest();
+            removed += 1;
+        }
+        removed
+    }
+
+    /// # middleware
+    ///
+    /// **Purpose:**
+    /// Turns the configuration into router middleware.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - A closure accepted by `Router::middleware`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{router::Router, session::Sessions};
+    /// let router = Router::new().middleware(Sessions::new().middleware());
+    /// ```
+    pub fn middleware(self) -> impl Fn(&Request, Next<'_>) -> Response + Send + Sync + 'static {
+        move |request, next| self.handle(request, next)
+    }
+
+    /// # handle
+    ///
+    /// **Purpose:**
+    /// Attaches the visitor's session to the request, runs the rest of the chain, then saves a
+    /// new non-empty session (setting the cookie), discards an emptied one (removing it), or
+    /// moves a renewed one to a fresh ID (replacing it).
+    ///
+    /// **Parameters:**
+    /// - `request`: Request being handled.
+    /// - `next`: The rest of the middleware chain.
+    ///
+    /// **Returns:**
+    /// - The downstream response, with `Set-Cookie` when the cookie changes.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{request::Request, response::Response, router::Next, session::Sessions};
+    /// # let sessions = Sessions::new();
+    /// # let request = &Request::read_from(&mut &b"GET / HTTP/1.1\r\n\r\n"[..]).unwrap();
+    /// # let next: Next<'_> = &|_| Response::new(200, "OK");
+    /// let response = sessions.handle(request, next);
+    /// # assert_eq!(response.status, 200);
+    /// ```
+    pub fn handle(&self, request: &Request, next: Next<'_>) -> Response {
+        let existing = cookie::get(&request.headers, &self.cookie_name).and_then(|id| self.load(id));
+        let stored = existing.is_some();
+        let session = existing.unwrap_or_else(Session::new);
+
+        let mut request = request.clone();
+        request.extensions.insert(session.clone());
+        let mut response = next(&request);
+
+        let renewed = session.renew.swap(false, Ordering::Relaxed);
+        let saved = match (stored, session.is_empty()) {
+            (false, false) => Some(session),
+            (true, true) => {
+                self.store.lock().unwrap().remove(session.id());
+                let cookie = Cookie::removal(self.cookie_name.as_str()).secure(self.secure);
+                response.headers.append("Set-Cookie", cookie.to_string());
+                None
+            }
+            (true, false) if renewed => {
+                self.store.lock().unwrap().remove(session.id());
+                Some(Session {
+                    id: random::token(),
+                    ..session
+                })
+            }
+            _ => None,
+        };
+        if let Some(session) = saved {
+            self.save(&session);
+            let cookie = Cookie::new(self.cookie_name.as_str(), session.id())
+                .secure(self.secure)
+                .same_site(self.same_site);
+            response.headers.append("Set-Cookie", cookie.to_string());
+        }
+        response
+    }
+
+    /// # load
+    ///
+    /// **Purpose:**
+    /// Looks up a live session and refreshes its idle timer.
+    ///
+    /// **Parameters:**
+    /// - `id`: Session ID from the cookie.
+    ///
+    /// **Returns:**
+    /// - `Some(session)` if it exists and has not expired, otherwise `None`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let session = self.load(id);
+    /// ```
+    fn load(&self, id: &str) -> Option<Session> {
+        let mut store = self.store.lock().unwrap();
+        let entry = store.remove(id)?;
+        if entry.last_seen.elapsed() >= self.idle_timeout {
+            return None;
+        }
+        let session = entry.session.clone();
+        store.insert(entry.session);
+        Some(session)
+    }
+
+    /// # save
+    ///
+    /// **Purpose:**
+    /// Adds a new session to the store, dropping expired ones first and then, while the store
+    /// is full, the least recently used.
+    ///
+    /// **Parameters:**
+    /// - `session`: Session to store.
+    ///
+    /// **Returns:**
+    /// None.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// self.save(&session);
+    /// ```
+    fn save(&self, session: &Session) {
+        self.sweep();
+        let mut store = self.store.lock().unwrap();
+        while store.entries.len() >= self.max_sessions {
+            store.evict_oldest();
+        }
+        store.insert(session.clone());
+    }
+}
+
+
+impl Store {
+
+    /// # insert
+    ///
+    /// **Purpose:**
+    /// Stores a session as used just now.
+    ///
+    /// **Parameters:**
+    /// - `session`: Session to store.
+    ///
+    /// **Returns:**
+    /// None.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// store.insert(session.clone());
+    /// ```
+    fn insert(&mut self, session: Session) {
+        let last_seen = Instant::now();
+        self.by_use.insert((last_seen, session.id.clone()));
+        self.entries.insert(session.id.clone(), Entry { session, last_seen });
+    }
+
+    /// # remove
+    ///
+    /// **Purpose:**
+    /// Takes a session out of the store.
+    ///
+    /// **Parameters:**
+    /// - `id`: Session ID.
+    ///
+    /// **Returns:**
+    /// - The removed entry, if it was stored.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// store.remove(session.id());
+    /// ```
+    fn remove(&mut self, id: &str) -> Option<Entry> {
+        let entry = self.entries.remove(id)?;
+        self.by_use.remove(&(entry.last_seen, entry.session.id.clone()));
+        Some(entry)
+    }
+
+    /// # evict_oldest
+    ///
+    /// **Purpose:**
+    /// Drops the least recently used session.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// None.
+    ///
+    /// **Errors / Failures:**
+    /// None. An empty store is left as is.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// store.evict_oldest();
+    /// ```
+    fn evict_oldest(&mut self) {
+        if let Some((_, id)) = self.by_use.pop_first() {
+            self.entries.remove(&id);
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(cookie: Option<&str>) -> Request {
+        let cookie = cookie.map(|id| format!("Cookie: sid={id}\r\n")).unwrap_or_default();
+        let raw = format!("GET / HTTP/1.1\r\nHost: example.com\r\n{cookie}\r\n");
+        Request::read_from(&mut raw.as_bytes()).unwrap()
+    }
+
+    fn issued_id(response: &Response) -> Option<String> {
+        let cookie = response.headers.get("Set-Cookie")?;
+        let id = cookie.strip_prefix("sid=")?.split(';').next()?;
+        (!id.is_empty()).then(|| id.to_string())
+    }
+
+    fn login(request: &Request) -> Response {
+        let session = request.extensions.get::<Session>().unwrap();
+        session.insert("user", "alice");
+        Response::new(200, "OK")
+    }
+
+    #[test]
+    fn empty_sessions_are_not_stored() {
+        let sessions = Sessions::new();
+        let response = sessions.handle(&request(None), &|_| Response::new(200, "OK"));
+        assert!(!response.headers.contains("Set-Cookie"));
+        assert!(sessions.store.lock().unwrap().entries.is_empty());
+    }
+
+    #[test]
+    fn store_evicts_the_least_recently_used_when_full() {
+        let sessions = Sessions::new().max_sessions(2);
+        let first = issued_id(&sessions.handle(&request(None), &login)).unwrap();
+        let second = issued_id(&sessions.handle(&request(None), &login)).unwrap();
+        sessions.handle(&request(Some(&first)), &|_| Response::new(200, "OK"));
+        let third = issued_id(&sessions.handle(&request(None), &login)).unwrap();
+
+        let store = sessions.store.lock().unwrap();
+        assert_eq!(store.entries.len(), 2);
+        assert_eq!(store.by_use.len(), 2);
+        assert!(store.entries.contains_key(&first));
+        assert!(!store.entries.contains_key(&second));
+        assert!(store.entries.contains_key(&third));
+    }
+
+    #[test]
+    fn expired_sessions_are_swept() {
+        let sessions = Sessions::new().idle_timeout(Duration::ZERO);
+        let id = issued_id(&sessions.handle(&request(None), &login)).unwrap();
+        assert_eq!(sessions.sweep(), 1);
+        let reloaded = sessions.handle(&request(Some(&id)), &|request| {
+            let session = request.extensions.get::<Session>().unwrap();
+            Response::new(200, "OK").with_body(session.get("user").unwrap_or_default())
+        });
+        assert!(reloaded.body.is_empty());
+    }
+
+    #[test]
+    fn renew_moves_the_data_to_a_fresh_id() {
+        let sessions = Sessions::new();
+        let old = issued_id(&sessions.handle(&request(None), &login)).unwrap();
+        let response = sessions.handle(&request(Some(&old)), &|request| {
+            request.extensions.get::<Session>().unwrap().renew();
+            Response::new(200, "OK")
+        });
+        let new = issued_id(&response).unwrap();
+        assert_ne!(new, old);
+
+        let store = sessions.store.lock().unwrap();
+        assert!(!store.entries.contains_key(&old));
+        assert_eq!(store.entries[&new].session.get("user").as_deref(), Some("alice"));
+    }
+}
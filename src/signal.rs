@@ -0,0 +1,140 @@
+
+// ============================================================
+//  DAEGONICA SOFTWARE — signal.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: Signal
+//!
+//! **Purpose:**
+//! Notices `SIGTERM`, the signal init systems, container runtimes, and `kill` send to ask a
+//! process to stop, so the server can drain instead of dying mid-response.
+//!
+//! **Context:**
+//! - Installed by the server when `Server::drain_on_sigterm` is set; a watcher thread polls
+//!   `terminated` and starts the drain.
+//!
+//! **Responsibilities:**
+//! - Installs a handler that only sets a flag, the one thing a signal handler can safely do.
+//! - Reports whether the signal has arrived.
+//! - Does NOT handle `SIGINT` (Ctrl-C still stops the process at once) or any other signal,
+//!   and does NOT catch console close events on Windows, where `terminated` stays `false`.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+use std::{
+    io,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+
+/// # TERMINATED
+///
+/// **Summary:**
+/// Set by the signal handler once `SIGTERM` has arrived.
+static TERMINATED: AtomicBool = AtomicBool::new(false);
+
+
+/// # handle_sigterm
+///
+/// **Purpose:**
+/// Replaces the default action of `SIGTERM`, which ends the process, with recording it.
+///
+/// **Parameters:**
+/// None.
+///
+/// **Returns:**
+/// - `Ok(())` once the handler is installed; installing it again is harmless.
+///
+/// **Errors / Failures:**
+/// - Returns the OS error if the handler cannot be installed.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// signal::handle_sigterm()?;
+/// ```
+#[cfg(unix)]
+pub(crate) fn handle_sigterm() -> io::Result<()> {
+    // SAFETY: the handler only stores to an atomic, which is async-signal-safe.
+    if unsafe { libc::signal(libc::SIGTERM, on_sigterm as extern "C" fn(libc::c_int) as libc::sighandler_t) } == libc::SIG_ERR {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+
+/// # handle_sigterm
+///
+/// **Purpose:**
+/// Stands in for the `SIGTERM` handler on platforms without Unix signals.
+///
+/// **Parameters:**
+/// None.
+///
+/// **Returns:**
+/// - Always `Ok(())`; nothing is installed.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// signal::handle_sigterm()?;
+/// ```
+#[cfg(not(unix))]
+pub(crate) fn handle_sigterm() -> io::Result<()> {
+    Ok(())
+}
+
+
+/// # terminated
+///
+/// **Purpose:**
+/// Checks whether `SIGTERM` has arrived since the handler was installed.
+///
+/// **Parameters:**
+/// None.
+///
+/// **Returns:**
+/// - `true` once the signal has been received.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// if signal::terminated() { server.drain(); }
+/// ```
+pub(crate) fn terminated() -> bool {
+    TERMINATED.load(Ordering::Acquire)
+}
+
+
+/// # on_sigterm
+///
+/// **Purpose:**
+/// Signal handler: records that `SIGTERM` arrived.
+///
+/// **Parameters:**
+/// - `_signal`: Signal number (always `SIGTERM`).
+///
+/// **Returns:**
+/// None.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// libc::signal(libc::SIGTERM, on_sigterm as extern "C" fn(libc::c_int) as libc::sighandler_t);
+/// ```
+#[cfg(unix)]
+extern "C" fn on_sigterm(_signal: libc::c_int) {
+    TERMINATED.store(true, Ordering::Release);
+}
@@ -0,0 +1,465 @@
+
+// ============================================================
+//  DAEGONICA SOFTWARE — socket.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: SocketOptions
+//!
+//! **Purpose:**
+//! Creates the listening socket and tunes accepted connections.
+//!
+//! **Context:**
+//! - Set with `Server::socket_options`; used by `Server::run` to bind and by the accept loop.
+//!
+//! **Responsibilities:**
+//! - Binds the listener with `SO_REUSEADDR` (so restarts do not fail while old connections
+//!   sit in `TIME_WAIT`), optional `SO_REUSEPORT`, and a configurable accept backlog.
+//! - Sets `TCP_NODELAY` on accepted connections when enabled.
+//! - Does NOT apply listener options on non-Unix platforms, which bind with the standard defaults.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+use std::{
+    io,
+    net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs},
+};
+
+
+/// # DEFAULT_BACKLOG
+///
+/// **Summary:**
+/// Pending-connection queue length requested from the OS unless configured otherwise.
+pub const DEFAULT_BACKLOG: i32 = 128;
+
+
+/// # SocketOptions
+///
+/// **Summary:**
+/// Listener and connection socket settings.
+///
+/// **Fields:**
+/// - `nodelay`: Whether accepted connections disable Nagle's algorithm.
+/// - `reuse_address`: Whether the listener sets `SO_REUSEADDR`.
+/// - `reuse_port`: Whether the listener sets `SO_REUSEPORT`, letting several processes share the port.
+/// - `backlog`: Accept backlog passed to `listen`.
+///
+/// **Usage Example:**
+/// ```rust,no_run
+/// # use server::{server::Server, socket::SocketOptions};
+/// let options = SocketOptions::new().nodelay(true).backlog(1024);
+/// Server::new("0.0.0.0:80").socket_options(options).run()?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SocketOptions {
+    nodelay: bool,
+    reuse_address: bool,
+    reuse_port: bool,
+    backlog: i32,
+}
+
+
+impl Default for SocketOptions {
+
+    /// # default
+    ///
+    /// **Purpose:**
+    /// Creates the standard settings.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - `SocketOptions` with `SO_REUSEADDR` on, `SO_REUSEPORT` and `TCP_NODELAY` off, and
+    ///   `DEFAULT_BACKLOG`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::socket::SocketOptions;
+    /// let options = SocketOptions::default();
+    /// ```
+    fn default() -> SocketOptions {
+        SocketOptions {
+            nodelay: false,
+            reuse_address: true,
+            reuse_port: false,
+            backlog: DEFAULT_BACKLOG,
+        }
+    }
+}
+
+
+impl SocketOptions {
+
+    /// # new
+    ///
+    /// **Purpose:**
+    /// Creates the standard settings (see `default`).
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - New `SocketOptions`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::socket::SocketOptions;
+    /// let options = SocketOptions::new();
+    /// ```
+    pub fn new() -> SocketOptions {
+        SocketOptions::default()
+    }
+
+    /// # nodelay
+    ///
+    /// **Purpose:**
+    /// Sets `TCP_NODELAY` on accepted connections, sending small responses without waiting
+    /// to coalesce them.
+    ///
+    /// **Parameters:**
+    /// - `enabled`: Whether to disable Nagle's algorithm.
+    ///
+    /// **Returns:**
+    /// - The updated `SocketOptions`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::socket::SocketOptions;
+    /// let options = SocketOptions::new().nodelay(true);
+    /// ```
+    pub fn nodelay(mut self, enabled: bool) -> SocketOptions {
+        self.nodelay = enabled;
+        self
+    }
+
+    /// # reuse_address
+    ///
+    /// **Purpose:**
+    /// Sets whether the listener may bind while old connections on the port are in `TIME_WAIT`.
+    ///
+    /// **Parameters:**
+    /// - `enabled`: Whether to set `SO_REUSEADDR`.
+    ///
+    /// **Returns:**
+    /// - The updated `SocketOptions`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::socket::SocketOptions;
+    /// let options = SocketOptions::new().reuse_address(false);
+    /// ```
+    pub fn reuse_address(mut self, enabled: bool) -> SocketOptions {
+        self.reuse_address = enabled;
+        self
+    }
+
+    /// # reuse_port
+    ///
+    /// **Purpose:**
+    /// Sets whether several listeners may bind the same port, e.g. for zero-downtime restarts
+    /// where the new process starts before the old one exits.
+    ///
+    /// **Parameters:**
+    /// - `enabled`: Whether to set `SO_REUSEPORT`.
+    ///
+    /// **Returns:**
+    /// - The updated `SocketOptions`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::socket::SocketOptions;
+    /// let options = SocketOptions::new().reuse_port(true);
+    /// ```
+    pub fn reuse_port(mut self, enabled: bool) -> SocketOptions {
+        self.reuse_port = enabled;
+        self
+    }
+
+    /// # backlog
+    ///
+    /// **Purpose:**
+    /// Sets how many connections the OS queues before they are accepted. The OS may cap it
+    /// (e.g. at `net.core.somaxconn` on Linux).
+    ///
+    /// **Parameters:**
+    /// - `backlog`: Queue length (at least 1).
+    ///
+    /// **Returns:**
+    /// - The updated `SocketOptions`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::socket::SocketOptions;
+    /// let options = SocketOptions::new().backlog(4096);
+    /// ```
+    pub fn backlog(mut self, backlog: i32) -> SocketOptions {
+        self.backlog = backlog.max(1);
+        self
+    }
+
+    /// # bind
+    ///
+    /// **Purpose:**
+    /// Opens a listener on the first address `addr` resolves to that can be bound.
+    ///
+    /// **Parameters:**
+    /// - `addr`: Address to bind (e.g. `127.0.0.1:7878`).
+    ///
+    /// **Returns:**
+    /// - The listening `TcpListener`.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns the resolution error, or the error from the last address tried.
+    ///
+    /// **Examples:**
+    /// ```rust,no_run
+    /// # use server::socket::SocketOptions;
+    /// # let options = SocketOptions::new();
+    /// let listener = options.bind("0.0.0.0:80")?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn bind(&self, addr: &str) -> io::Result<TcpListener> {
+        let mut last_error = None;
+        for address in addr.to_socket_addrs()? {
+            match self.bind_address(address) {
+                Ok(listener) => return Ok(listener),
+                Err(error) => last_error = Some(error),
+            }
+        }
+        Err(last_error.unwrap_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "address resolved to nothing")))
+    }
+
+    /// # configure
+    ///
+    /// **Purpose:**
+    /// Applies the per-connection options to an accepted stream.
+    ///
+    /// **Parameters:**
+    /// - `stream`: Accepted client connection.
+    ///
+    /// **Returns:**
+    /// - `Ok(())` once the options are set.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns the OS error if an option cannot be set.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::socket::SocketOptions;
+    /// # use std::net::{TcpListener, TcpStream};
+    /// # let options = SocketOptions::new().nodelay(true);
+    /// # let listener = TcpListener::bind("127.0.0.1:0")?;
+    /// # let stream = TcpStream::connect(listener.local_addr()?)?;
+    /// options.configure(&stream)?;
+    /// # assert!(stream.nodelay()?);
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn configure(&self, stream: &TcpStream) -> io::Result<()> {
+        if self.nodelay {
+            stream.set_nodelay(true)?;
+        }
+        Ok(())
+    }
+
+    /// # bind_address
+    ///
+    /// **Purpose:**
+    /// Creates, configures, binds, and starts listening on a socket for one address.
+    ///
+    /// **Parameters:**
+    /// - `address`: Resolved address to bind.
+    ///
+    /// **Returns:**
+    /// - The listening `TcpListener`.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns the OS error from `socket`, `setsockopt`, `bind`, or `listen`.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let listener = self.bind_address("127.0.0.1:7878".parse()?)?;
+    /// ```
+    #[cfg(unix)]
+    fn bind_address(&self, address: SocketAddr) -> io::Result<TcpListener> {
+        use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+
+        let family = if address.is_ipv4() { libc::AF_INET } else { libc::AF_INET6 };
+        // SAFETY: socket(2) takes no pointers; a non-negative result is a fresh descriptor
+        // that is immediately given to an `OwnedFd`, which closes it on every error path.
+        let fd = unsafe { libc::socket(family, libc::SOCK_STREAM, 0) };
+        if fd < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        let socket = unsafe { OwnedFd::from_raw_fd(fd) };
+        // SAFETY: `fd` is open for the lifetime of `socket`.
+        check(unsafe { libc::fcntl(socket.as_raw_fd(), libc::F_SETFD, libc::FD_CLOEXEC) })?;
+
+        if self.reuse_address {
+            set_flag(&socket, libc::SO_REUSEADDR)?;
+        }
+        if self.reuse_port {
+            set_flag(&socket, libc::SO_REUSEPORT)?;
+        }
+
+        let (storage, length) = raw_address(address);
+        // SAFETY: `storage` holds a sockaddr of the given length for the socket's family.
+        check(unsafe { libc::bind(socket.as_raw_fd(), (&raw const storage).cast(), length) })?;
+        // SAFETY: listen(2) takes no pointers.
+        check(unsafe { libc::listen(socket.as_raw_fd(), self.backlog) })?;
+        Ok(TcpListener::from(socket))
+    }
+
+    /// # bind_address
+    ///
+    /// **Purpose:**
+    /// Binds one address with the platform defaults; listener options are not applied.
+    ///
+    /// **Parameters:**
+    /// - `address`: Resolved address to bind.
+    ///
+    /// **Returns:**
+    /// - The listening `TcpListener`.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns the OS error from binding.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let listener = self.bind_address("127.0.0.1:7878".parse()?)?;
+    /// ```
+    #[cfg(not(unix))]
+    fn bind_address(&self, address: SocketAddr) -> io::Result<TcpListener> {
+        TcpListener::bind(address)
+    }
+}
+
+
+/// # set_flag
+///
+/// **Purpose:**
+/// Turns on a boolean `SOL_SOCKET` option.
+///
+/// **Parameters:**
+/// - `socket`: Socket to configure.
+/// - `option`: Option name, e.g. `SO_REUSEADDR`.
+///
+/// **Returns:**
+/// - `Ok(())` once the option is set.
+///
+/// **Errors / Failures:**
+/// - Returns the OS error from `setsockopt`.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// set_flag(&socket, libc::SO_REUSEADDR)?;
+/// ```
+#[cfg(unix)]
+fn set_flag(socket: &std::os::fd::OwnedFd, option: libc::c_int) -> io::Result<()> {
+    use std::os::fd::AsRawFd;
+
+    let enabled: libc::c_int = 1;
+    let length = size_of::<libc::c_int>() as libc::socklen_t;
+    // SAFETY: `enabled` outlives the call and `length` is its size.
+    check(unsafe { libc::setsockopt(socket.as_raw_fd(), libc::SOL_SOCKET, option, (&raw const enabled).cast(), length) })
+}
+
+
+/// # raw_address
+///
+/// **Purpose:**
+/// Converts a socket address into the C representation expected by `bind`.
+///
+/// **Parameters:**
+/// - `address`: IPv4 or IPv6 socket address.
+///
+/// **Returns:**
+/// - The filled `sockaddr_storage` and the length of the family-specific address in it.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let (storage, length) = raw_address("127.0.0.1:80".parse()?);
+/// ```
+#[cfg(unix)]
+fn raw_address(address: SocketAddr) -> (libc::sockaddr_storage, libc::socklen_t) {
+    // SAFETY: the sockaddr types are plain data for which all zero bytes is a valid value.
+    let mut storage: libc::sockaddr_storage = unsafe { std::mem::zeroed() };
+    let length = match address {
+        SocketAddr::V4(v4) => {
+            let mut raw: libc::sockaddr_in = unsafe { std::mem::zeroed() };
+            raw.sin_family = libc::AF_INET as libc::sa_family_t;
+            raw.sin_port = v4.port().to_be();
+            raw.sin_addr.s_addr = u32::from_ne_bytes(v4.ip().octets());
+            // SAFETY: `sockaddr_storage` is large enough and aligned for every sockaddr type.
+            unsafe { (&raw mut storage).cast::<libc::sockaddr_in>().write(raw) };
+            size_of::<libc::sockaddr_in>()
+        }
+        SocketAddr::V6(v6) => {
+            let mut raw: libc::sockaddr_in6 = unsafe { std::mem::zeroed() };
+            raw.sin6_family = libc::AF_INET6 as libc::sa_family_t;
+            raw.sin6_port = v6.port().to_be();
+            raw.sin6_flowinfo = v6.flowinfo();
+            raw.sin6_addr.s6_addr = v6.ip().octets();
+            raw.sin6_scope_id = v6.scope_id();
+            // SAFETY: as above.
+            unsafe { (&raw mut storage).cast::<libc::sockaddr_in6>().write(raw) };
+            size_of::<libc::sockaddr_in6>()
+        }
+    };
+    (storage, length as libc::socklen_t)
+}
+
+
+/// # check
+///
+/// **Purpose:**
+/// Turns a C return code into an `io::Result`.
+///
+/// **Parameters:**
+/// - `result`: Value returned by a libc call.
+///
+/// **Returns:**
+/// - `Ok(())` for non-negative results.
+///
+/// **Errors / Failures:**
+/// - The thread's last OS error for negative results.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// check(unsafe { libc::listen(fd, 128) })?;
+/// ```
+#[cfg(unix)]
+fn check(result: libc::c_int) -> io::Result<()> {
+    if result < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
@@ -0,0 +1,239 @@
+
+// ============================================================
+//  DAEGONICA SOFTWARE — state.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: State
+//!
+//! **Purpose:**
+//! Shares application values (database pools, configuration, counters) with every handler.
+//!
+//! **Context:**
+//! - `Server::with_state` registers values; the server attaches them to each request, where
+//!   handlers read them through the `State<T>` extractor or `Request::state`.
+//!
+//! **Responsibilities:**
+//! - Stores one shared value per type.
+//! - Hands out `Arc` references to registered values.
+//! - Does NOT provide interior mutability; use atomics or locks inside `T` for mutable state.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+use std::{
+    any::{Any, TypeId},
+    collections::HashMap,
+    fmt,
+    ops::Deref,
+    sync::Arc,
+};
+
+use crate::{extract::FromRequest, request::Request, response::Response};
+
+
+/// # AppState
+///
+/// **Summary:**
+/// Type-keyed map of shared application values.
+///
+/// **Fields:**
+/// - `values`: Registered values keyed by their type.
+///
+/// **Usage Example:**
+/// ```rust
+/// # use server::state::AppState;
+/// # use std::sync::Arc;
+/// # struct Config;
+/// # impl Config { fn load() -> std::io::Result<Config> { Ok(Config) } }
+/// let mut state = AppState::default();
+/// state.insert(Config::load()?);
+/// let config: Arc<Config> = state.get().unwrap();
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Clone, Default)]
+pub struct AppState {
+    values: HashMap<TypeId, Arc<dyn Any + Send + Sync>>,
+}
+
+
+/// # State
+///
+/// **Summary:**
+/// Extractor for a value registered with `Server::with_state`.
+///
+/// **Usage Example:**
+/// ```rust
+/// # use server::{extract::extract, response::Response, router::Router, server::Server, state::State, vhost::VirtualHost};
+/// # use std::sync::atomic::{AtomicU64, Ordering};
+/// struct Hits(AtomicU64);
+///
+/// fn count(State(hits): State<Hits>) -> Response {
+///     let n = hits.0.fetch_add(1, Ordering::Relaxed) + 1;
+///     Response::new(200, "OK").with_body(n.to_string())
+/// }
+/// let server = Server::new("127.0.0.1:7878")
+///     .with_state(Hits(AtomicU64::new(0)))
+///     .default_host(VirtualHost::new().router(Router::new().get("/hits", extract(count))));
+/// ```
+pub struct State<T>(pub Arc<T>);
+
+
+impl AppState {
+
+    /// # insert
+    ///
+    /// **Purpose:**
+    /// Registers a value, replacing any earlier value of the same type.
+    ///
+    /// **Parameters:**
+    /// - `value`: Value to share.
+    ///
+    /// **Returns:**
+    /// None.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::state::AppState;
+    /// # #[derive(Default)]
+    /// # struct Config;
+    /// # let mut state = AppState::default();
+    /// state.insert(Config::default());
+    /// ```
+    pub fn insert<T: Send + Sync + 'static>(&mut self, value: T) {
+        self.values.insert(TypeId::of::<T>(), Arc::new(value));
+    }
+
+    /// # get
+    ///
+    /// **Purpose:**
+    /// Returns the registered value of type `T`.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - `Some(value)` if a `T` was registered, otherwise `None`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::state::AppState;
+    /// # struct Config;
+    /// # let mut state = AppState::default();
+    /// # state.insert(Config);
+    /// let config = state.get::<Config>();
+    /// # assert!(config.is_some());
+    /// ```
+    pub fn get<T: Send + Sync + 'static>(&self) -> Option<Arc<T>> {
+        let value = Arc::clone(self.values.get(&TypeId::of::<T>())?);
+        value.downcast().ok()
+    }
+}
+
+
+impl fmt::Debug for AppState {
+
+    /// # fmt
+    ///
+    /// **Purpose:**
+    /// Formats the map by size only, since values need not implement `Debug`.
+    ///
+    /// **Parameters:**
+    /// - `f`: Formatter.
+    ///
+    /// **Returns:**
+    /// - The formatter result.
+    ///
+    /// **Errors / Failures:**
+    /// - Propagates formatter errors.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::state::AppState;
+    /// # let state = AppState::default();
+    /// println!("{:?}", state);
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AppState").field("values", &self.values.len()).finish()
+    }
+}
+
+
+impl<T> Deref for State<T> {
+    type Target = T;
+
+    /// # deref
+    ///
+    /// **Purpose:**
+    /// Gives direct access to the shared value.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - A reference to the value.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::state::State;
+    /// # use std::sync::Arc;
+    /// # struct Db { pool: Vec<u8> }
+    /// # let state = State(Arc::new(Db { pool: Vec::new() }));
+    /// let pool = &state.pool;
+    /// ```
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+
+impl<T: Send + Sync + 'static> FromRequest for State<T> {
+
+    /// # from_request
+    ///
+    /// **Purpose:**
+    /// Fetches the shared `T` attached to the request by the server.
+    ///
+    /// **Parameters:**
+    /// - `request`: Request being handled.
+    ///
+    /// **Returns:**
+    /// - The shared value.
+    ///
+    /// **Errors / Failures:**
+    /// - Rejects with `500 Internal Server Error` if no `T` was registered, since that is a
+    ///   server configuration mistake rather than a client error.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{extract::FromRequest, request::Request, response::Response, state::{AppState, State}};
+    /// # use std::sync::Arc;
+    /// # struct Config;
+    /// # let mut request = Request::read_from(&mut &b"GET / HTTP/1.1\r\n\r\n"[..]).unwrap();
+    /// # let mut state = AppState::default();
+    /// # state.insert(Config);
+    /// # request.state = Arc::new(state);
+    /// let State(config) = State::<Config>::from_request(&request)?;
+    /// # Ok::<(), Response>(())
+    /// ```
+    fn from_request(request: &Request) -> Result<State<T>, Response> {
+        request
+            .state::<T>()
+            .map(State)
+            .ok_or_else(|| Response::new(500, "Internal Server Error"))
+    }
+}
@@ -0,0 +1,1612 @@
+
+// ============================================================
+//  DAEGONICA SOFTWARE — static_files.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: StaticFiles
+//!
+//! **Purpose:**
+//! Serves files from a document root or from assets embedded in the binary, applying per-path
+//! `Cache-Control` policies.
+//!
+//! **Context:**
+//! - Used by the connection handler for every locally served `GET` request.
+//!
+//! **Responsibilities:**
+//! - Maps request paths onto files beneath the document root, refusing `..` traversal.
+//! - Serves a directory's index file.
+//! - Tags responses with an `ETag` chosen per mount: weak tags from a file's modification time
+//!   and size (the default), strong tags from a hash of its contents, or none at all. Embedded
+//!   assets always use their precomputed content hash unless tags are disabled.
+//! - Dates files from disk with `Last-Modified`, for `If-Range` validation.
+//! - Reads small files through an optional in-memory `FileCache`. Large files are sent with
+//!   `sendfile(2)` on Linux and macOS and memory-mapped elsewhere, falling back to fixed-size
+//!   chunked reads where neither works.
+//! - Picks each file's `Content-Type` from its extension through a replaceable `MimeTypes`
+//!   registry, taking the charset of text files from their bytes rather than assuming UTF-8.
+//! - Attaches the first matching `Cache-Control` policy to each served file.
+//! - Optionally renders `.md` files to HTML inside a wrapper template, falling back to
+//!   `index.md` or `README.md` for directories without an index file.
+//! - Optionally lists directories without an index file through a themeable template, with
+//!   selectable columns and `?sort=` links.
+//! - Optionally opens everything through a sandbox rooted at a handle to the document root, so
+//!   even a path-handling bug cannot reach files outside it.
+//! - Follows symbolic links freely, only while they stay inside the document root, or not at
+//!   all, answering `403 Forbidden` for refused links and leaving them out of listings.
+//! - Does NOT list hidden (dot) files or cut byte ranges, which the virtual host does for it.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+use std::{
+    cmp::Ordering,
+    collections::HashMap,
+    fs,
+    io::{self, Read},
+    path::{Component, Path, PathBuf},
+    sync::{Arc, Mutex, PoisonError},
+    time::SystemTime,
+};
+
+use crate::{
+    date::{self, DateTime},
+    embed::EmbeddedAssets,
+    extract::encode_component,
+    file_cache::FileCache,
+    glob::Glob,
+    hash::{self, Sha256},
+    json_value::JsonValue,
+    log::{self, LogLevel},
+    markdown,
+    mime::MimeTypes,
+    mmap::MappedFile,
+    response::Response,
+    sandbox::Sandbox,
+    template::Template,
+};
+
+
+/// # MARKDOWN_WRAPPER
+///
+/// **Summary:**
+/// Page rendered Markdown is placed in unless `markdown_template` supplies another. Receives
+/// `title`, `content` (the rendered HTML), and `path`.
+const MARKDOWN_WRAPPER: &str = "<!DOCTYPE html>
+<html>
+<head>
+<meta charset=\"utf-8\">
+<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">
+<title>{{title}}</title>
+<style>body{max-width:46rem;margin:2rem auto;padding:0 1rem;font:16px/1.6 system-ui,sans-serif}pre{background:#f4f4f4;padding:.75rem;overflow:auto}code{font-family:ui-monospace,monospace}blockquote{margin:0;padding-left:1rem;border-left:3px solid #ccc;color:#555}</style>
+</head>
+<body>
+<main>
+{{{content}}}
+</main>
+</body>
+</html>
+";
+
+
+/// # LISTING_TEMPLATE
+///
+/// **Summary:**
+/// Page directory listings are rendered with unless `listing_template` supplies another.
+/// Receives `path`, `parent`, `entries` (each with `name`, `href`, `dir`, `size`, `size_text`, and
+/// `modified`), `columns`, `links`, `sorted`, `sort`, `order`, and `descending`.
+const LISTING_TEMPLATE: &str = "<!DOCTYPE html>
+<html>
+<head>
+<meta charset=\"utf-8\">
+<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">
+<title>Index of {{path}}</title>
+<style>body{margin:2rem;font:15px/1.5 system-ui,sans-serif}table{border-collapse:collapse}th,td{padding:.2rem 1.5rem .2rem 0;text-align:left}td.size{text-align:right}th a{color:inherit}</style>
+</head>
+<body>
+<h1>Index of {{path}}</h1>
+<table>
+<thead>
+<tr>{{#if columns.name}}<th><a href=\"{{links.name}}\">Name</a>{{#if sorted.name}}{{#if descending}} &darr;{{else}} &uarr;{{/if}}{{/if}}</th>{{/if}}{{#if columns.size}}<th><a href=\"{{links.size}}\">Size</a>{{#if sorted.size}}{{#if descending}} &darr;{{else}} &uarr;{{/if}}{{/if}}</th>{{/if}}{{#if columns.modified}}<th><a href=\"{{links.modified}}\">Modified</a>{{#if sorted.modified}}{{#if descending}} &darr;{{else}} &uarr;{{/if}}{{/if}}</th>{{/if}}</tr>
+</thead>
+<tbody>
+{{#if parent}}
+<tr><td><a href=\"{{parent}}\">../</a></td></tr>
+{{/if}}
+{{#each entries}}
+<tr>{{#if columns.name}}<td><a href=\"{{href}}\">{{name}}{{#if dir}}/{{/if}}</a></td>{{/if}}{{#if columns.size}}<td class=\"size\">{{size_text}}</td>{{/if}}{{#if columns.modified}}<td>{{modified}}</td>{{/if}}</tr>
+{{/each}}
+</tbody>
+</table>
+</body>
+</html>
+";
+
+
+/// # LISTING_COLUMNS
+///
+/// **Summary:**
+/// Columns a directory listing can show, and the keys it can be sorted by.
+const LISTING_COLUMNS: [&str; 3] = ["name", "size", "modified"];
+
+
+/// # DEFAULT_LARGE_FILE_THRESHOLD
+///
+/// **Summary:**
+/// Size from which files are sent without being read into memory, unless
+/// `large_file_threshold` says otherwise.
+const DEFAULT_LARGE_FILE_THRESHOLD: u64 = 1024 * 1024;
+
+
+/// # EtagStrategy
+///
+/// **Summary:**
+/// How a `StaticFiles` mount derives the `ETag` of the files it serves.
+///
+/// **Variants:**
+/// - `Weak`: `W/"mtime-size"`, from metadata alone; cheap, but differs between replicas whose
+///   copies carry different timestamps.
+/// - `Strong`: A quoted hash of the served bytes, identical on every replica serving the same
+///   content. Large files are hashed once per modification and remembered.
+/// - `Disabled`: No `ETag`, for content that changes too often to revalidate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EtagStrategy {
+    Weak,
+    Strong,
+    Disabled,
+}
+
+
+/// # CHARSET_SAMPLE
+///
+/// **Summary:**
+/// Bytes read from the start of a large text file to check its charset before it is sent.
+const CHARSET_SAMPLE: usize = 4 * 1024;
+
+
+/// # SymlinkPolicy
+///
+/// **Summary:**
+/// Whether a `StaticFiles` mount serves paths that pass through symbolic links.
+///
+/// **Variants:**
+/// - `Follow`: Follows every link, wherever it points.
+/// - `WithinRoot`: Follows links whose final target is still beneath the document root.
+/// - `Refuse`: Refuses any path with a link beneath the document root.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SymlinkPolicy {
+    Follow,
+    WithinRoot,
+    Refuse,
+}
+
+
+/// # StaticFiles
+///
+/// **Summary:**
+/// Static file handler rooted at one directory.
+///
+/// **Fields:**
+/// - `root`: Document root directory.
+/// - `embedded`: Assets compiled into the binary, served instead of `root` when set.
+/// - `index`: File served for directory requests.
+/// - `cache_rules`: `Cache-Control` policies by path pattern, first match wins.
+/// - `markdown`: Wrapper template for rendered `.md` files, or `None` to serve them as text.
+/// - `listing`: Template for directory listings, or `None` to list nothing.
+/// - `listing_columns`: Columns the listing shows.
+/// - `file_cache`: Cache consulted instead of reading files, or `None` to always read from disk.
+/// - `large_file_threshold`: Size from which files are not read into memory, or `None` to
+///   always read them.
+/// - `sendfile`: Whether large files are sent with `sendfile(2)` rather than memory-mapped.
+/// - `etag`: How `ETag`s are derived.
+/// - `mime_types`: Extension-to-`Content-Type` registry.
+/// - `symlinks`: Which symbolic links are followed.
+/// - `sandbox`: Root directory handle every file is opened through, or `None` to open paths
+///   directly.
+/// - `content_tags`: Strong tags of large files, with the modification time and size they were
+///   computed for.
+///
+/// **Usage Example:**
+/// ```rust
+/// # use server::static_files::StaticFiles;
+/// let files = StaticFiles::new("html")
+///     .cache_control("/assets/**", "public, max-age=31536000, immutable")
+///     .cache_control("/", "no-cache");
+/// let response = files.serve("/assets/site.css");
+/// ```
+pub struct StaticFiles {
+    root: PathBuf,
+    embedded: Option<EmbeddedAssets>,
+    index: String,
+    cache_rules: Vec<(Glob, String)>,
+    markdown: Option<Template>,
+    listing: Option<Template>,
+    listing_columns: Vec<String>,
+    file_cache: Option<Arc<FileCache>>,
+    large_file_threshold: Option<u64>,
+    sendfile: bool,
+    etag: EtagStrategy,
+    mime_types: MimeTypes,
+    symlinks: SymlinkPolicy,
+    sandbox: Option<Arc<Sandbox>>,
+    content_tags: Mutex<HashMap<PathBuf, (SystemTime, u64, String)>>,
+}
+
+
+/// # ListingEntry
+///
+/// **Summary:**
+/// One file or subdirectory in a directory listing.
+///
+/// **Fields:**
+/// - `name`: File name.
+/// - `dir`: Whether the entry is a directory.
+/// - `size`: Size in bytes, `0` for directories.
+/// - `modified`: Last modification time, if known.
+struct ListingEntry {
+    name: String,
+    dir: bool,
+    size: u64,
+    modified: Option<SystemTime>,
+}
+
+
+impl StaticFiles {
+
+    /// # new
+    ///
+    /// **Purpose:**
+    /// Creates a handler serving files beneath `root`, with `index.html` as the directory index.
+    ///
+    /// **Parameters:**
+    /// - `root`: Document root directory.
+    ///
+    /// **Returns:**
+    /// - A new `StaticFiles` instance.
+    ///
+    /// **Errors / Failures:**
+    /// None. A missing root simply serves nothing.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::static_files::StaticFiles;
+    /// let files = StaticFiles::new("html");
+    /// ```
+    pub fn new(root: impl Into<PathBuf>) -> StaticFiles {
+        StaticFiles {
+            root: root.into(),
+            embedded: None,
+            index: "index.html".to_string(),
+            cache_rules: Vec::new(),
+            markdown: None,
+            listing: None,
+            listing_columns: LISTING_COLUMNS.iter().map(|column| column.to_string()).collect(),
+            file_cache: None,
+            large_file_threshold: Some(DEFAULT_LARGE_FILE_THRESHOLD),
+            sendfile: cfg!(any(target_os = "linux", target_os = "macos")),
+            etag: EtagStrategy::Weak,
+            mime_types: MimeTypes::new(),
+            symlinks: SymlinkPolicy::Follow,
+            sandbox: None,
+            content_tags: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// # embedded
+    ///
+    /// **Purpose:**
+    /// Creates a handler serving assets compiled into the binary, with `index.html` as the
+    /// directory index. Nothing is read from disk.
+    ///
+    /// **Parameters:**
+    /// - `assets`: Embedded files, from `write_manifest` or `embed_assets!`.
+    ///
+    /// **Returns:**
+    /// - A new `StaticFiles` instance.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// static ASSETS: &[(&str, &[u8])] = include!(concat!(env!("OUT_DIR"), "/assets.rs"));
+    /// let files = StaticFiles::embedded(EmbeddedAssets::new(ASSETS));
+    /// ```
+    pub fn embedded(assets: EmbeddedAssets) -> StaticFiles {
+        let mut files = StaticFiles::new(PathBuf::new());
+        files.embedded = Some(assets);
+        files
+    }
+
+    /// # index
+    ///
+    /// **Purpose:**
+    /// Sets the file served when a directory is requested.
+    ///
+    /// **Parameters:**
+    /// - `index`: Index file name (e.g. `hello.html`).
+    ///
+    /// **Returns:**
+    /// - The updated `StaticFiles`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::static_files::StaticFiles;
+    /// let files = StaticFiles::new("html").index("hello.html");
+    /// ```
+    pub fn index(mut self, index: impl Into<String>) -> StaticFiles {
+        self.index = index.into();
+        self
+    }
+
+    /// # cache_control
+    ///
+    /// **Purpose:**
+    /// Adds a `Cache-Control` policy for request paths matching `pattern`.
+    /// Rules are checked in the order they were added; the first match wins.
+    ///
+    /// **Parameters:**
+    /// - `pattern`: Path glob (`*` within a segment, `**` across segments).
+    /// - `value`: `Cache-Control` header value to send.
+    ///
+    /// **Returns:**
+    /// - The updated `StaticFiles`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::static_files::StaticFiles;
+    /// let files = StaticFiles::new("html").cache_control("/", "no-cache");
+    /// ```
+    pub fn cache_control(mut self, pattern: &str, value: impl Into<String>) -> StaticFiles {
+        self.cache_rules.push((Glob::new(pattern), value.into()));
+        self
+    }
+
+    /// # markdown
+    ///
+    /// **Purpose:**
+    /// Renders requested `.md` files to HTML in the built-in page wrapper instead of serving
+    /// their source.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - The updated `StaticFiles`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::static_files::StaticFiles;
+    /// let docs = StaticFiles::new("docs").markdown();
+    /// ```
+    pub fn markdown(self) -> StaticFiles {
+        let wrapper = Template::parse(MARKDOWN_WRAPPER).expect("the built-in Markdown wrapper parses");
+        self.markdown_template(wrapper)
+    }
+
+    /// # markdown_template
+    ///
+    /// **Purpose:**
+    /// Renders requested `.md` files to HTML inside a custom wrapper template. The template
+    /// receives `title` (the first `# ` heading, or the file name), `content` (the rendered
+    /// HTML, to be inserted with `{{{content}}}`), and `path`.
+    ///
+    /// **Parameters:**
+    /// - `wrapper`: Page template.
+    ///
+    /// **Returns:**
+    /// - The updated `StaticFiles`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,no_run
+    /// # use server::{static_files::StaticFiles, template::Template};
+    /// let docs = StaticFiles::new("docs").markdown_template(Template::open("docs.html")?);
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn markdown_template(mut self, wrapper: Template) -> StaticFiles {
+        self.markdown = Some(wrapper);
+        self
+    }
+
+    /// # directory_listing
+    ///
+    /// **Purpose:**
+    /// Lists directories that have no index file, using the built-in page layout.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - The updated `StaticFiles`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::static_files::StaticFiles;
+    /// let files = StaticFiles::new("/srv/downloads").directory_listing();
+    /// ```
+    pub fn directory_listing(self) -> StaticFiles {
+        let template = Template::parse(LISTING_TEMPLATE).expect("the built-in listing template parses");
+        self.listing_template(template)
+    }
+
+    /// # listing_template
+    ///
+    /// **Purpose:**
+    /// Lists directories that have no index file, rendering them with a custom template so the
+    /// listing can carry the site's own header, footer, and styles. See `LISTING_TEMPLATE` for
+    /// the values it receives.
+    ///
+    /// **Parameters:**
+    /// - `template`: Listing page template.
+    ///
+    /// **Returns:**
+    /// - The updated `StaticFiles`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,no_run
+    /// # use server::{static_files::StaticFiles, template::Template};
+    /// let files = StaticFiles::new("/srv/downloads").listing_template(Template::open("listing.html")?);
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn listing_template(mut self, template: Template) -> StaticFiles {
+        self.listing = Some(template);
+        self
+    }
+
+    /// # listing_columns
+    ///
+    /// **Purpose:**
+    /// Chooses the columns directory listings show; the template sees them as `columns.<name>`.
+    ///
+    /// **Parameters:**
+    /// - `columns`: Any of `name`, `size`, and `modified`. Others are ignored.
+    ///
+    /// **Returns:**
+    /// - The updated `StaticFiles`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::static_files::StaticFiles;
+    /// let files = StaticFiles::new("/srv/downloads").directory_listing().listing_columns(&["name", "size"]);
+    /// ```
+    pub fn listing_columns(mut self, columns: &[&str]) -> StaticFiles {
+        self.listing_columns = columns
+            .iter()
+            .filter(|column| LISTING_COLUMNS.contains(column))
+            .map(|column| column.to_string())
+            .collect();
+        self
+    }
+
+    /// # file_cache
+    ///
+    /// **Purpose:**
+    /// Reads files through an in-memory cache, which may be shared with other handlers.
+    ///
+    /// **Parameters:**
+    /// - `cache`: Cache to use.
+    ///
+    /// **Returns:**
+    /// - The updated `StaticFiles`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{file_cache::FileCache, static_files::StaticFiles};
+    /// # use std::sync::Arc;
+    /// let files = StaticFiles::new("html").file_cache(Arc::new(FileCache::new(8 * 1024 * 1024)));
+    /// ```
+    pub fn file_cache(mut self, cache: Arc<FileCache>) -> StaticFiles {
+        self.file_cache = Some(cache);
+        self
+    }
+
+    /// # large_file_threshold
+    ///
+    /// **Purpose:**
+    /// Sets the size from which files are sent without being read into memory (1 MiB by
+    /// default); such files bypass the file cache. See `sendfile` for how they are sent.
+    ///
+    /// **Parameters:**
+    /// - `bytes`: Smallest file size treated as large, or `None` to always read files.
+    ///
+    /// **Returns:**
+    /// - The updated `StaticFiles`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::static_files::StaticFiles;
+    /// let files = StaticFiles::new("html").large_file_threshold(Some(256 * 1024));
+    /// ```
+    pub fn large_file_threshold(mut self, bytes: Option<u64>) -> StaticFiles {
+        self.large_file_threshold = bytes;
+        self
+    }
+
+    /// # sendfile
+    ///
+    /// **Purpose:**
+    /// Chooses how large files are sent: copied to the socket inside the kernel with
+    /// `sendfile(2)` (the default on Linux and macOS), or memory-mapped and written from the
+    /// mapping. Either way a file that cannot be sent so is read in fixed-size chunks. Keep
+    /// mapping off for files rewritten in place while being served: a truncated mapping
+    /// crashes the process.
+    ///
+    /// **Parameters:**
+    /// - `enabled`: `true` for `sendfile(2)`, `false` to memory-map.
+    ///
+    /// **Returns:**
+    /// - The updated `StaticFiles`.
+    ///
+    /// **Errors / Failures:**
+    /// None. On platforms without `sendfile(2)`, enabled large files are read in chunks.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::static_files::StaticFiles;
+    /// let files = StaticFiles::new("html").sendfile(false);
+    /// ```
+    pub fn sendfile(mut self, enabled: bool) -> StaticFiles {
+        self.sendfile = enabled;
+        self
+    }
+
+    /// # etag
+    ///
+    /// **Purpose:**
+    /// Chooses how `ETag`s are derived for this mount (weak tags from metadata by default).
+    ///
+    /// **Parameters:**
+    /// - `strategy`: Weak, strong, or disabled; see `EtagStrategy`.
+    ///
+    /// **Returns:**
+    /// - The updated `StaticFiles`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::static_files::{EtagStrategy, StaticFiles};
+    /// let files = StaticFiles::new("/srv/releases").etag(EtagStrategy::Strong);
+    /// ```
+    pub fn etag(mut self, strategy: EtagStrategy) -> StaticFiles {
+        self.etag = strategy;
+        self
+    }
+
+    /// # mime_types
+    ///
+    /// **Purpose:**
+    /// Replaces the registry that picks each file's `Content-Type`, to add types the built-in
+    /// table lacks, override its choices, or change the type sent for unknown extensions.
+    ///
+    /// **Parameters:**
+    /// - `types`: Registry to use.
+    ///
+    /// **Returns:**
+    /// - The updated `StaticFiles`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{mime::MimeTypes, static_files::StaticFiles};
+    /// let files = StaticFiles::new("html").mime_types(MimeTypes::new().insert("glb", "model/gltf-binary"));
+    /// ```
+    pub fn mime_types(mut self, types: MimeTypes) -> StaticFiles {
+        self.mime_types = types;
+        self
+    }
+
+    /// # symlinks
+    ///
+    /// **Purpose:**
+    /// Chooses which symbolic links beneath the document root are followed (all of them by
+    /// default). Requests through a refused link get `403 Forbidden`.
+    ///
+    /// **Parameters:**
+    /// - `policy`: Follow, follow within the root, or refuse; see `SymlinkPolicy`.
+    ///
+    /// **Returns:**
+    /// - The updated `StaticFiles`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::static_files::{StaticFiles, SymlinkPolicy};
+    /// let files = StaticFiles::new("/srv/www").symlinks(SymlinkPolicy::WithinRoot);
+    /// ```
+    pub fn symlinks(mut self, policy: SymlinkPolicy) -> StaticFiles {
+        self.symlinks = policy;
+        self
+    }
+
+    /// # sandbox
+    ///
+    /// **Purpose:**
+    /// Opens the document root as a directory handle now and resolves every later file,
+    /// directory, and streamed body beneath it (with `openat2(2)` and `RESOLVE_BENEATH` on
+    /// Linux, a component-by-component walk elsewhere), so no path, however it was built, can
+    /// leave the root. A file that would is not found. Sandboxed mounts read small files
+    /// directly rather than through a file cache.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - The updated `StaticFiles`.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns an I/O error if the root cannot be opened as a directory.
+    ///
+    /// **Examples:**
+    /// ```rust,no_run
+    /// # use server::static_files::StaticFiles;
+    /// let files = StaticFiles::new("/srv/www").sandbox()?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn sandbox(mut self) -> io::Result<StaticFiles> {
+        self.sandbox = Some(Arc::new(Sandbox::open(&self.root)?));
+        Ok(self)
+    }
+
+    /// # serve
+    ///
+    /// **Purpose:**
+    /// Builds a `200 OK` response for the file that `path` maps to.
+    ///
+    /// **Parameters:**
+    /// - `path`: Request path without the query string.
+    ///
+    /// **Returns:**
+    /// - `Some(response)` if a readable file exists, otherwise `None`.
+    ///
+    /// **Errors / Failures:**
+    /// None. Unreadable files and traversal attempts yield `None`.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{response::Response, static_files::StaticFiles};
+    /// # fn not_found() -> Response { Response::new(404, "Not Found") }
+    /// # let files = StaticFiles::new("html");
+    /// let response = files.serve("/").unwrap_or_else(not_found);
+    /// ```
+    pub fn serve(&self, path: &str) -> Option<Response> {
+        self.serve_with_query(path, None)
+    }
+
+    /// # serve_with_query
+    ///
+    /// **Purpose:**
+    /// Like `serve`, but passes the query string on to directory listings, which read their
+    /// `sort` and `order` parameters from it.
+    ///
+    /// **Parameters:**
+    /// - `path`: Request path without the query string.
+    /// - `query`: Raw query string, if any.
+    ///
+    /// **Returns:**
+    /// - `Some(response)` for a readable file or a listed directory, otherwise `None`. A listed
+    ///   directory requested without its trailing slash is redirected to it, and a path through
+    ///   a symbolic link the policy refuses gets `403 Forbidden`.
+    ///
+    /// **Errors / Failures:**
+    /// None. Unreadable files and traversal attempts yield `None`.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{request::Request, static_files::StaticFiles};
+    /// # let files = StaticFiles::new("html");
+    /// # let request = Request::read_from(&mut &b"GET /report.pdf?v=2 HTTP/1.1\r\n\r\n"[..])?;
+    /// let response = files.serve_with_query(request.path(), request.query());
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn serve_with_query(&self, path: &str, query: Option<&str>) -> Option<Response> {
+        let resolved = match &self.embedded {
+            Some(assets) => return self.serve_embedded(assets, path),
+            None => self.resolve(path),
+        };
+        if resolved.as_deref().is_some_and(|file| !self.links_allowed(file)) {
+            return Some(forbidden());
+        }
+        let mut response = match resolved {
+            Some(file) => {
+                let mut response = match (&self.markdown, is_markdown(&file)) {
+                    (Some(wrapper), true) => render_markdown(wrapper, &file, path, self.read(&file).ok()?),
+                    _ => self.file_response(&file).ok()?,
+                };
+                if let Some(etag) = self.entity_tag(&file, &response) {
+                    response.headers.set("ETag", etag);
+                }
+                response
+            }
+            None => {
+                let template = self.listing.as_ref()?;
+                let directory = self.locate(path).filter(|directory| self.metadata(directory).is_ok_and(|metadata| metadata.is_dir()))?;
+                if !self.links_allowed(&directory) {
+                    return Some(forbidden());
+                }
+                if !path.ends_with('/') {
+                    let location = match query {
+                        Some(query) => format!("{path}/?{query}"),
+                        None => format!("{path}/"),
+                    };
+                    return Some(Response::new(301, "Moved Permanently").with_header("Location", location));
+                }
+                self.render_listing(template, &directory, path, query)?
+            }
+        };
+        if let Some(policy) = self.cache_policy(path) {
+            response.headers.set("Cache-Control", policy);
+        }
+        Some(response)
+    }
+
+    /// # cache_policy
+    ///
+    /// **Purpose:**
+    /// Finds the `Cache-Control` value configured for `path`.
+    ///
+    /// **Parameters:**
+    /// - `path`: Request path without the query string.
+    ///
+    /// **Returns:**
+    /// - The first matching policy, or `None`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::static_files::StaticFiles;
+    /// # let files = StaticFiles::new("html").cache_control("/assets/**", "public, max-age=31536000, immutable");
+    /// let policy = files.cache_policy("/assets/app.js");
+    /// # assert_eq!(policy, Some("public, max-age=31536000, immutable"));
+    /// ```
+    pub fn cache_policy(&self, path: &str) -> Option<&str> {
+        self.cache_rules
+            .iter()
+            .find(|(glob, _)| glob.matches(path))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// # serve_embedded
+    ///
+    /// **Purpose:**
+    /// Builds the response for an embedded asset, resolving directories to their index file.
+    ///
+    /// **Parameters:**
+    /// - `assets`: Embedded files.
+    /// - `path`: Request path without the query string.
+    ///
+    /// **Returns:**
+    /// - `Some(response)` carrying the asset and, unless tags are disabled, its `ETag`;
+    ///   otherwise `None`.
+    ///
+    /// **Errors / Failures:**
+    /// None. Paths containing `..` or invalid percent-encoding yield `None`.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let response = files.serve_embedded(assets, "/css/site.css");
+    /// ```
+    fn serve_embedded(&self, assets: &EmbeddedAssets, path: &str) -> Option<Response> {
+        let decoded = percent_decode(path)?;
+        let mut segments = Vec::new();
+        for segment in decoded.split('/') {
+            match segment {
+                "" | "." => continue,
+                ".." => return None,
+                segment => segments.push(segment),
+            }
+        }
+        let name = segments.join("/");
+        let index = match name.as_str() {
+            "" => self.index.clone(),
+            name => format!("{name}/{}", self.index),
+        };
+        let (name, asset) = match assets.get(&name) {
+            Some(asset) => (name, asset),
+            None => {
+                let asset = assets.get(&index)?;
+                (index, asset)
+            }
+        };
+
+        let file = Path::new(&name);
+        let mut response = match (&self.markdown, is_markdown(file)) {
+            (Some(wrapper), true) => render_markdown(wrapper, file, path, asset.contents.to_vec()),
+            _ => Response::new(200, "OK")
+                .with_header("Content-Type", self.mime_types.content_type_for(file, asset.contents))
+                .with_body(asset.contents),
+        };
+        if self.etag != EtagStrategy::Disabled {
+            response.headers.set("ETag", asset.etag.as_str());
+        }
+        if let Some(policy) = self.cache_policy(path) {
+            response.headers.set("Cache-Control", policy);
+        }
+        Some(response)
+    }
+
+    /// # locate
+    ///
+    /// **Purpose:**
+    /// Maps a request path onto a path beneath the document root, without checking what is there.
+    ///
+    /// **Parameters:**
+    /// - `path`: Request path without the query string.
+    ///
+    /// **Returns:**
+    /// - The mapped path, or `None`.
+    ///
+    /// **Errors / Failures:**
+    /// None. Paths containing `..`, backslashes, colons, NUL bytes, or invalid percent-encoding
+    /// yield `None`, as does any segment the platform would read as a drive, prefix, or root
+    /// rather than a plain name.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let directory = files.locate("/downloads/");
+    /// ```
+    fn locate(&self, path: &str) -> Option<PathBuf> {
+        let decoded = percent_decode(path)?;
+        let mut relative = PathBuf::new();
+        for segment in decoded.split('/') {
+            match segment {
+                "" | "." => continue,
+                segment if segment.contains(['\\', '\0', ':']) => return None,
+                segment => relative.push(segment),
+            }
+        }
+        if !relative.components().all(|component| matches!(component, Component::Normal(_))) {
+            return None;
+        }
+        Some(self.root.join(relative))
+    }
+
+    /// # render_listing
+    ///
+    /// **Purpose:**
+    /// Builds the listing page for a directory, sorted as the query asks.
+    ///
+    /// **Parameters:**
+    /// - `template`: Listing page template.
+    /// - `directory`: Directory to list.
+    /// - `path`: Request path, ending in `/`.
+    /// - `query`: Raw query string, holding optional `sort` (`name`, `size`, `modified`) and
+    ///   `order` (`asc`, `desc`) parameters.
+    ///
+    /// **Returns:**
+    /// - A `200 OK` HTML response, or `None` if the directory cannot be read.
+    ///
+    /// **Errors / Failures:**
+    /// None. Entries whose metadata cannot be read, or that sit behind a refused symbolic link,
+    /// are skipped.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let response = files.render_listing(template, &directory, "/downloads/", Some("sort=size"));
+    /// ```
+    fn render_listing(&self, template: &Template, directory: &Path, path: &str, query: Option<&str>) -> Option<Response> {
+        let mut sort = "name";
+        let mut descending = false;
+        for pair in query.unwrap_or("").split('&') {
+            match pair.split_once('=') {
+                Some(("sort", key)) => sort = LISTING_COLUMNS.into_iter().find(|column| *column == key).unwrap_or(sort),
+                Some(("order", order)) => descending = order == "desc",
+                _ => {}
+            }
+        }
+
+        let mut entries = Vec::new();
+        for entry in fs::read_dir(directory).ok()?.flatten() {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if name.starts_with('.') || !self.links_allowed(&entry.path()) {
+                continue;
+            }
+            let Ok(metadata) = self.metadata(&entry.path()) else {
+                continue;
+            };
+            entries.push(ListingEntry {
+                name,
+                dir: metadata.is_dir(),
+                size: if metadata.is_dir() { 0 } else { metadata.len() },
+                modified: metadata.modified().ok(),
+            });
+        }
+        entries.sort_by(|a, b| {
+            let order = match sort {
+                "size" => a.size.cmp(&b.size),
+                "modified" => a.modified.cmp(&b.modified),
+                _ => Ordering::Equal,
+            }
+            .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+            b.dir.cmp(&a.dir).then(if descending { order.reverse() } else { order })
+        });
+
+        let flags = |enabled: &dyn Fn(&str) -> bool| {
+            JsonValue::Object(LISTING_COLUMNS.iter().map(|column| (column.to_string(), JsonValue::from(enabled(column)))).collect())
+        };
+        let links = LISTING_COLUMNS
+            .iter()
+            .map(|column| {
+                let order = if *column == sort && !descending { "desc" } else { "asc" };
+                (column.to_string(), JsonValue::from(format!("?sort={column}&order={order}")))
+            })
+            .collect();
+        let entries = entries
+            .into_iter()
+            .map(|entry| {
+                let href = if entry.dir { format!("{}/", encode_component(&entry.name)) } else { encode_component(&entry.name) };
+                JsonValue::Object(vec![
+                    ("name".to_string(), JsonValue::from(entry.name)),
+                    ("href".to_string(), JsonValue::from(href)),
+                    ("dir".to_string(), JsonValue::from(entry.dir)),
+                    ("size".to_string(), JsonValue::from(entry.size as f64)),
+                    ("size_text".to_string(), JsonValue::from(if entry.dir { "-".to_string() } else { human_size(entry.size) })),
+                    ("modified".to_string(), JsonValue::from(entry.modified.map(format_modified).unwrap_or_default())),
+                ])
+            })
+            .collect();
+
+        let decoded = percent_decode(path).unwrap_or_else(|| path.to_string());
+        let parent = if decoded == "/" { JsonValue::Null } else { JsonValue::from("../") };
+        let context = JsonValue::Object(vec![
+            ("path".to_string(), JsonValue::from(decoded)),
+            ("parent".to_string(), parent),
+            ("entries".to_string(), JsonValue::Array(entries)),
+            ("columns".to_string(), flags(&|column| self.listing_columns.iter().any(|shown| shown == column))),
+            ("links".to_string(), JsonValue::Object(links)),
+            ("sorted".to_string(), flags(&|column| column == sort)),
+            ("sort".to_string(), JsonValue::from(sort)),
+            ("order".to_string(), JsonValue::from(if descending { "desc" } else { "asc" })),
+            ("descending".to_string(), JsonValue::from(descending)),
+        ]);
+        Some(Response::render(template, &context))
+    }
+
+    /// # read
+    ///
+    /// **Purpose:**
+    /// Reads a resolved file, through the file cache when one is attached, or through the
+    /// sandbox (bypassing the cache) when the mount is sandboxed.
+    ///
+    /// **Parameters:**
+    /// - `file`: File beneath the document root.
+    ///
+    /// **Returns:**
+    /// - The file's contents.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns an I/O error if the file cannot be read.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let contents = self.read(&file)?;
+    /// ```
+    fn read(&self, file: &Path) -> io::Result<Vec<u8>> {
+        if self.sandbox.is_some() {
+            let mut contents = Vec::new();
+            self.open(file)?.read_to_end(&mut contents)?;
+            return Ok(contents);
+        }
+        match &self.file_cache {
+            Some(cache) => cache.read(file),
+            None => fs::read(file),
+        }
+    }
+
+    /// # open
+    ///
+    /// **Purpose:**
+    /// Opens a file or directory beneath the document root, through the sandbox if the mount
+    /// has one.
+    ///
+    /// **Parameters:**
+    /// - `file`: Path beneath the document root.
+    ///
+    /// **Returns:**
+    /// - The open file.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns an I/O error if it cannot be opened, including `PermissionDenied` if a
+    ///   sandboxed path resolves outside the root.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let source = self.open(&file)?;
+    /// ```
+    fn open(&self, file: &Path) -> io::Result<fs::File> {
+        match &self.sandbox {
+            Some(sandbox) => sandbox.open_beneath(self.relative(file)),
+            None => fs::File::open(file),
+        }
+    }
+
+    /// # metadata
+    ///
+    /// **Purpose:**
+    /// Reads the metadata of a path beneath the document root, through the sandbox if the
+    /// mount has one.
+    ///
+    /// **Parameters:**
+    /// - `file`: Path beneath the document root.
+    ///
+    /// **Returns:**
+    /// - Its metadata, following links.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns an I/O error if the path cannot be inspected or, when sandboxed, opened
+    ///   beneath the root.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let len = self.metadata(&file)?.len();
+    /// ```
+    fn metadata(&self, file: &Path) -> io::Result<fs::Metadata> {
+        match &self.sandbox {
+            Some(_) => self.open(file)?.metadata(),
+            None => fs::metadata(file),
+        }
+    }
+
+    /// # relative
+    ///
+    /// **Purpose:**
+    /// Strips the document root from a path, for opening it through the sandbox.
+    ///
+    /// **Parameters:**
+    /// - `file`: Path beneath the document root.
+    ///
+    /// **Returns:**
+    /// - The path relative to the root; `file` unchanged if it is not beneath the root, which
+    ///   the sandbox then refuses as absolute.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let relative = self.relative(Path::new("html/css/site.css"));
+    /// ```
+    fn relative<'a>(&self, file: &'a Path) -> &'a Path {
+        file.strip_prefix(&self.root).unwrap_or(file)
+    }
+
+    /// # streamed
+    ///
+    /// **Purpose:**
+    /// Attaches a large file as a streamed body, opened through the sandbox if the mount has
+    /// one.
+    ///
+    /// **Parameters:**
+    /// - `response`: Response to carry the body.
+    /// - `file`: File beneath the document root.
+    /// - `len`: Its current length.
+    ///
+    /// **Returns:**
+    /// - The updated response.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// return Ok(self.streamed(response, file, len));
+    /// ```
+    fn streamed(&self, response: Response, file: &Path, len: u64) -> Response {
+        match &self.sandbox {
+            Some(sandbox) => response.with_sandboxed_body(Arc::clone(sandbox), self.relative(file), len),
+            None => response.with_streamed_body(file, len),
+        }
+    }
+
+    /// # links_allowed
+    ///
+    /// **Purpose:**
+    /// Checks a path beneath the document root against the symlink policy.
+    ///
+    /// **Parameters:**
+    /// - `path`: File or directory about to be served or listed.
+    ///
+    /// **Returns:**
+    /// - `true` if the policy follows every link on the way to `path`. Under `WithinRoot`, a
+    ///   path that cannot be resolved is refused.
+    ///
+    /// **Errors / Failures:**
+    /// None. The check is made before the file is opened, so a link swapped in between is not
+    /// noticed.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// if !self.links_allowed(&file) { return Some(forbidden()); }
+    /// ```
+    fn links_allowed(&self, path: &Path) -> bool {
+        match self.symlinks {
+            SymlinkPolicy::Follow => true,
+            SymlinkPolicy::WithinRoot => match (fs::canonicalize(&self.root), fs::canonicalize(path)) {
+                (Ok(root), Ok(target)) => target.starts_with(root),
+                _ => false,
+            },
+            SymlinkPolicy::Refuse => {
+                let Ok(relative) = path.strip_prefix(&self.root) else {
+                    return false;
+                };
+                let mut current = self.root.clone();
+                relative.components().all(|component| {
+                    current.push(component);
+                    fs::symlink_metadata(&current).is_ok_and(|metadata| !metadata.file_type().is_symlink())
+                })
+            }
+        }
+    }
+
+    /// # file_response
+    ///
+    /// **Purpose:**
+    /// Builds the `200 OK` response for a resolved file. Files below the large-file threshold
+    /// are read; larger ones are streamed (and sent with `sendfile(2)`) or memory-mapped,
+    /// falling back to streaming when mapping fails.
+    ///
+    /// **Parameters:**
+    /// - `file`: File beneath the document root.
+    ///
+    /// **Returns:**
+    /// - The response, with the file's `Content-Type` (its charset checked against the contents,
+    ///   or the first `CHARSET_SAMPLE` bytes of a large file) and, where the platform records it,
+    ///   `Last-Modified`.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns an I/O error if the file cannot be inspected or read. A failed mapping is
+    ///   logged at debug level.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let response = self.file_response(&file)?;
+    /// ```
+    fn file_response(&self, file: &Path) -> io::Result<Response> {
+        let mut response = Response::new(200, "OK");
+        let metadata = self.metadata(file)?;
+        if let Ok(modified) = metadata.modified() {
+            response.headers.set("Last-Modified", date::format_http_date(modified));
+        }
+        let len = metadata.len();
+        if self.large_file_threshold.is_some_and(|threshold| len >= threshold) {
+            let text = self.mime_types.lookup(file).starts_with("text/");
+            let sample = if text { self.open(file).map(read_sample).unwrap_or_default() } else { Vec::new() };
+            response.headers.set("Content-Type", self.mime_types.content_type_for(file, &sample));
+            if self.sendfile {
+                return Ok(self.streamed(response, file, len));
+            }
+            return Ok(match self.open(file).and_then(|source| MappedFile::map(&source)) {
+                Ok(map) => response.with_mapped_body(map),
+                Err(err) => {
+                    log::emit(LogLevel::Debug, format_args!("cannot map {}, streaming it instead: {err}", file.display()));
+                    self.streamed(response, file, len)
+                }
+            });
+        }
+        let contents = self.read(file)?;
+        response.headers.set("Content-Type", self.mime_types.content_type_for(file, &contents));
+        Ok(response.with_body(contents))
+    }
+
+    /// # entity_tag
+    ///
+    /// **Purpose:**
+    /// Derives the `ETag` for a file response under the mount's strategy.
+    ///
+    /// **Parameters:**
+    /// - `file`: File the response was built from.
+    /// - `response`: The response, whose bytes a strong tag hashes.
+    ///
+    /// **Returns:**
+    /// - The quoted tag, or `None` if tags are disabled or the file's metadata or contents
+    ///   cannot be read.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let etag = files.entity_tag(&file, &response);
+    /// ```
+    fn entity_tag(&self, file: &Path, response: &Response) -> Option<String> {
+        match self.etag {
+            EtagStrategy::Disabled => None,
+            EtagStrategy::Weak => {
+                let metadata = self.metadata(file).ok()?;
+                let modified = metadata.modified().ok()?.duration_since(SystemTime::UNIX_EPOCH).ok()?;
+                Some(format!("W/\"{:x}-{:x}\"", modified.as_millis(), metadata.len()))
+            }
+            EtagStrategy::Strong if response.body.len() == response.body_len() => Some(content_tag(&hash::sha256(&response.body))),
+            EtagStrategy::Strong => self.file_tag(file).ok(),
+        }
+    }
+
+    /// # file_tag
+    ///
+    /// **Purpose:**
+    /// Returns the strong tag of a file sent from disk, hashing it in chunks only if it changed
+    /// since it was last hashed.
+    ///
+    /// **Parameters:**
+    /// - `file`: File to tag.
+    ///
+    /// **Returns:**
+    /// - The quoted content hash.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns an I/O error if the file cannot be read.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let etag = files.file_tag(Path::new("html/video.mp4"))?;
+    /// ```
+    fn file_tag(&self, file: &Path) -> io::Result<String> {
+        let metadata = self.metadata(file)?;
+        let (modified, len) = (metadata.modified()?, metadata.len());
+        if let Some((seen, size, tag)) = self.content_tags.lock().unwrap_or_else(PoisonError::into_inner).get(file)
+            && (*seen, *size) == (modified, len)
+        {
+            return Ok(tag.clone());
+        }
+
+        let mut hasher = Sha256::new();
+        let mut source = self.open(file)?;
+        let mut chunk = vec![0; 64 * 1024];
+        loop {
+            match source.read(&mut chunk)? {
+                0 => break,
+                read => hasher.update(&chunk[..read]),
+            }
+        }
+        let tag = content_tag(&hasher.finish());
+        self.content_tags
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(file.to_path_buf(), (modified, len, tag.clone()));
+        Ok(tag)
+    }
+
+    /// # resolve
+    ///
+    /// **Purpose:**
+    /// Maps a request path onto a file beneath the document root.
+    ///
+    /// **Parameters:**
+    /// - `path`: Request path without the query string.
+    ///
+    /// **Returns:**
+    /// - `Some(path)` of an existing file, or `None`.
+    ///
+    /// **Errors / Failures:**
+    /// None. Paths containing `..`, backslashes, NUL bytes, or invalid percent-encoding yield `None`.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let file = files.resolve("/hello.html");
+    /// ```
+    fn resolve(&self, path: &str) -> Option<PathBuf> {
+        let mut file = self.locate(path)?;
+        let is_file = |candidate: &Path| self.metadata(candidate).is_ok_and(|metadata| metadata.is_file());
+        if self.metadata(&file).is_ok_and(|metadata| metadata.is_dir()) {
+            let directory = file.clone();
+            file.push(&self.index);
+            if !is_file(&file) && self.markdown.is_some() {
+                file = ["index.md", "README.md"]
+                    .iter()
+                    .map(|name| directory.join(name))
+                    .find(|candidate| is_file(candidate))?;
+            }
+        }
+        is_file(&file).then_some(file)
+    }
+}
+
+
+/// # content_tag
+///
+/// **Purpose:**
+/// Formats a strong `ETag` from a SHA-256 digest, the same way embedded assets are tagged.
+///
+/// **Parameters:**
+/// - `digest`: Digest of the served bytes.
+///
+/// **Returns:**
+/// - The quoted first half of the digest in hex.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let etag = content_tag(&hash::sha256(b"hello"));
+/// ```
+fn content_tag(digest: &[u8; 32]) -> String {
+    format!("\"{}\"", hash::hex(&digest[..16]))
+}
+
+
+/// # forbidden
+///
+/// **Purpose:**
+/// Builds the answer for a path the symlink policy refuses.
+///
+/// **Parameters:**
+/// None.
+///
+/// **Returns:**
+/// - A `403 Forbidden` plain-text response.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// return Some(forbidden());
+/// ```
+fn forbidden() -> Response {
+    Response::new(403, "Forbidden")
+        .with_header("Content-Type", "text/plain; charset=utf-8")
+        .with_body("forbidden")
+}
+
+
+/// # read_sample
+///
+/// **Purpose:**
+/// Reads the start of a file, for charset detection.
+///
+/// **Parameters:**
+/// - `source`: Open file to sample.
+///
+/// **Returns:**
+/// - Up to `CHARSET_SAMPLE` leading bytes; fewer, possibly none, if reading fails.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let content_type = types.content_type_for(&file, &read_sample(fs::File::open(&file)?));
+/// ```
+fn read_sample(source: fs::File) -> Vec<u8> {
+    let mut sample = Vec::with_capacity(CHARSET_SAMPLE);
+    let _ = source.take(CHARSET_SAMPLE as u64).read_to_end(&mut sample);
+    sample
+}
+
+
+/// # human_size
+///
+/// **Purpose:**
+/// Formats a byte count for a listing, in binary units.
+///
+/// **Parameters:**
+/// - `bytes`: File size.
+///
+/// **Returns:**
+/// - The size, e.g. `512 B` or `1.5 KiB`.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// assert_eq!(human_size(1536), "1.5 KiB");
+/// ```
+fn human_size(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["KiB", "MiB", "GiB", "TiB", "PiB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit + 1 < UNITS.len() {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{size:.1} {}", UNITS[unit])
+}
+
+
+/// # format_modified
+///
+/// **Purpose:**
+/// Formats a modification time for a listing (`2026-10-14 09:30`, UTC).
+///
+/// **Parameters:**
+/// - `time`: Modification time.
+///
+/// **Returns:**
+/// - The formatted timestamp.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let shown = format_modified(metadata.modified()?);
+/// ```
+fn format_modified(time: SystemTime) -> String {
+    let date = DateTime::from_system_time(time);
+    format!("{:04}-{:02}-{:02} {:02}:{:02}", date.year, date.month, date.day, date.hour, date.minute)
+}
+
+
+/// # is_markdown
+///
+/// **Purpose:**
+/// Checks whether a file is Markdown by its extension.
+///
+/// **Parameters:**
+/// - `file`: Path of the file being served.
+///
+/// **Returns:**
+/// - `true` for `.md` and `.markdown` files, in any case.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// assert!(is_markdown(Path::new("README.md")));
+/// ```
+fn is_markdown(file: &Path) -> bool {
+    file.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("md") || ext.eq_ignore_ascii_case("markdown"))
+}
+
+
+/// # render_markdown
+///
+/// **Purpose:**
+/// Builds the HTML page for a Markdown file.
+///
+/// **Parameters:**
+/// - `wrapper`: Page template.
+/// - `file`: Path of the Markdown file, whose name is the fallback title.
+/// - `path`: Request path, passed to the template.
+/// - `contents`: File contents.
+///
+/// **Returns:**
+/// - A `200 OK` HTML response.
+///
+/// **Errors / Failures:**
+/// None. Invalid UTF-8 is replaced rather than rejected.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let response = render_markdown(wrapper, &file, "/guide.md", contents);
+/// ```
+fn render_markdown(wrapper: &Template, file: &Path, path: &str, contents: Vec<u8>) -> Response {
+    let source = String::from_utf8_lossy(&contents);
+    let title = markdown::title(&source).unwrap_or_else(|| {
+        file.file_stem().map(|stem| stem.to_string_lossy().into_owned()).unwrap_or_default()
+    });
+    let context = JsonValue::Object(vec![
+        ("title".to_string(), JsonValue::from(title)),
+        ("content".to_string(), JsonValue::from(markdown::to_html(&source))),
+        ("path".to_string(), JsonValue::from(path)),
+    ]);
+    Response::render(wrapper, &context)
+}
+
+
+/// # percent_decode
+///
+/// **Purpose:**
+/// Decodes `%XX` escapes in a URL path.
+///
+/// **Parameters:**
+/// - `input`: Percent-encoded path.
+///
+/// **Returns:**
+/// - `Some(decoded)` if every `%` is followed by exactly two hex digits and the result is
+///   UTF-8, otherwise `None`.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// assert_eq!(percent_decode("/a%20b").as_deref(), Some("/a b"));
+/// ```
+pub(crate) fn percent_decode(input: &str) -> Option<String> {
+    let bytes = input.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = bytes.get(i + 1..i + 3).filter(|hex| hex.iter().all(u8::is_ascii_hexdigit))?;
+            decoded.push(u8::from_str_radix(std::str::from_utf8(hex).ok()?, 16).ok()?);
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8(decoded).ok()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn percent_decoding_takes_exactly_two_hex_digits() {
+        assert_eq!(percent_decode("a%20b%2Fc%e2%82%ac").as_deref(), Some("a b/c€"));
+        for input in ["%+1", "%-1", "%1", "%zz", "% 1", "%", "%ff"] {
+            assert_eq!(percent_decode(input), None, "{input}");
+        }
+    }
+
+    #[test]
+    fn locates_plain_paths_beneath_the_root() {
+        let files = StaticFiles::new("html");
+        assert_eq!(files.locate("/css/site.css"), Some(Path::new("html").join("css").join("site.css")));
+        assert_eq!(files.locate("/a/./b//c"), Some(Path::new("html").join("a").join("b").join("c")));
+        assert_eq!(files.locate("/my%20file.txt"), Some(Path::new("html").join("my file.txt")));
+        assert_eq!(files.locate("/"), Some(PathBuf::from("html")));
+    }
+
+    #[test]
+    fn refuses_paths_that_escape_the_root() {
+        let files = StaticFiles::new("html");
+        for path in [
+            "/../etc/passwd",
+            "/a/../../b",
+            "/a%2f..%2f..%2fb",
+            "/%2e%2e/secret",
+            "/a%5c..%5cb",
+            "/a\\b",
+            "/a%00.txt",
+            "/C:/Windows/win.ini",
+            "/c:",
+            "/x/D:secret",
+            "/%5c%5cserver%5cshare",
+            "/%zz",
+        ] {
+            assert_eq!(files.locate(path), None, "{path}");
+        }
+    }
+}
@@ -0,0 +1,546 @@
+
+// ============================================================
+//  DAEGONICA SOFTWARE — syslog.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: Syslog
+//!
+//! **Purpose:**
+//! Sends log lines to a syslog daemon or collector as RFC 5424 messages.
+//!
+//! **Context:**
+//! - Used by `AccessLog::syslog` for access logs and `log::set_syslog` for diagnostics, on hosts
+//!   where the server may not write log files.
+//!
+//! **Responsibilities:**
+//! - Reaches the daemon over a local Unix datagram socket (`/dev/log`), UDP, or TCP with
+//!   octet-counted framing (RFC 6587).
+//! - Connects lazily and reconnects after a failed send, so a restarted daemon is picked up.
+//! - Does NOT buffer or retry messages a send failed on, or speak TLS (RFC 5425).
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+use std::{
+    fs,
+    io::{self, Write},
+    net::{TcpStream, UdpSocket},
+    process,
+    sync::{Mutex, PoisonError},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::date::DateTime;
+
+
+/// # DEFAULT_APP_NAME
+///
+/// **Summary:**
+/// APP-NAME field used unless `Syslog::app_name` sets another.
+const DEFAULT_APP_NAME: &str = "daegonica-server";
+
+
+/// # Facility
+///
+/// **Summary:**
+/// Syslog facility the messages are filed under.
+///
+/// **Variants:**
+/// - `User`: Generic user-level messages.
+/// - `Daemon`: System daemons (the default).
+/// - `Local0` … `Local7`: Reserved for local use, commonly routed by site configuration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Facility {
+    User = 1,
+    Daemon = 3,
+    Local0 = 16,
+    Local1 = 17,
+    Local2 = 18,
+    Local3 = 19,
+    Local4 = 20,
+    Local5 = 21,
+    Local6 = 22,
+    Local7 = 23,
+}
+
+
+/// # Severity
+///
+/// **Summary:**
+/// Syslog severity of one message.
+///
+/// **Variants:**
+/// - `Error`: Error conditions.
+/// - `Warning`: Warning conditions.
+/// - `Informational`: Normal operation, including access log lines.
+/// - `Debug`: Debug-level messages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Severity {
+    Error = 3,
+    Warning = 4,
+    Informational = 6,
+    Debug = 7,
+}
+
+
+/// # Transport
+///
+/// **Summary:**
+/// How messages reach the daemon.
+///
+/// **Variants:**
+/// - `Unix`: Datagram socket at a path.
+/// - `Udp`: UDP datagrams to an address.
+/// - `Tcp`: Octet-counted messages over a TCP stream to an address.
+#[derive(Debug, Clone)]
+enum Transport {
+    Unix(String),
+    Udp(String),
+    Tcp(String),
+}
+
+
+/// # Connection
+///
+/// **Summary:**
+/// An open connection to the daemon.
+///
+/// **Variants:**
+/// - `Unix`: Connected Unix datagram socket.
+/// - `Udp`: Connected UDP socket.
+/// - `Tcp`: TCP stream.
+#[derive(Debug)]
+enum Connection {
+    #[cfg(unix)]
+    Unix(std::os::unix::net::UnixDatagram),
+    Udp(UdpSocket),
+    Tcp(TcpStream),
+}
+
+
+/// # Syslog
+///
+/// **Summary:**
+/// Destination for syslog messages, safe to share between threads.
+///
+/// **Fields:**
+/// - `transport`: How messages reach the daemon.
+/// - `facility`: Facility every message is filed under.
+/// - `app_name`: APP-NAME field.
+/// - `msg_id`: MSGID field, identifying the kind of log.
+/// - `hostname`: HOSTNAME field, read once at construction.
+/// - `connection`: Open connection, or `None` until the next send.
+///
+/// **Usage Example:**
+/// ```rust,no_run
+/// # use server::{access_log::AccessLog, log, syslog::{Facility, Syslog}, vhost::VirtualHost};
+/// let site = VirtualHost::new().access_log(AccessLog::syslog(Syslog::unix("/dev/log").facility(Facility::Local0)));
+/// log::set_syslog(Syslog::udp("logs.internal:514"));
+/// ```
+#[derive(Debug)]
+pub struct Syslog {
+    transport: Transport,
+    facility: Facility,
+    app_name: String,
+    msg_id: String,
+    hostname: String,
+    connection: Mutex<Option<Connection>>,
+}
+
+
+impl Syslog {
+
+    /// # unix
+    ///
+    /// **Purpose:**
+    /// Sends messages to the local daemon's datagram socket.
+    ///
+    /// **Parameters:**
+    /// - `path`: Socket path, usually `/dev/log`.
+    ///
+    /// **Returns:**
+    /// - A new `Syslog`; nothing is connected until the first message.
+    ///
+    /// **Errors / Failures:**
+    /// None. On targets without Unix sockets every send fails.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::syslog::Syslog;
+    /// let syslog = Syslog::unix("/dev/log");
+    /// ```
+    pub fn unix(path: &str) -> Syslog {
+        Syslog::with_transport(Transport::Unix(path.to_string()))
+    }
+
+    /// # udp
+    ///
+    /// **Purpose:**
+    /// Sends messages as UDP datagrams (RFC 5426).
+    ///
+    /// **Parameters:**
+    /// - `addr`: Collector address, usually port 514.
+    ///
+    /// **Returns:**
+    /// - A new `Syslog`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::syslog::Syslog;
+    /// let syslog = Syslog::udp("10.0.0.9:514");
+    /// ```
+    pub fn udp(addr: &str) -> Syslog {
+        Syslog::with_transport(Transport::Udp(addr.to_string()))
+    }
+
+    /// # tcp
+    ///
+    /// **Purpose:**
+    /// Sends messages over TCP with octet-counted framing (RFC 6587).
+    ///
+    /// **Parameters:**
+    /// - `addr`: Collector address, usually port 514 or 601.
+    ///
+    /// **Returns:**
+    /// - A new `Syslog`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::syslog::Syslog;
+    /// let syslog = Syslog::tcp("logs.internal:601");
+    /// ```
+    pub fn tcp(addr: &str) -> Syslog {
+        Syslog::with_transport(Transport::Tcp(addr.to_string()))
+    }
+
+    /// # facility
+    ///
+    /// **Purpose:**
+    /// Files messages under another facility.
+    ///
+    /// **Parameters:**
+    /// - `facility`: Facility to use.
+    ///
+    /// **Returns:**
+    /// - The updated `Syslog`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::syslog::{Facility, Syslog};
+    /// let syslog = Syslog::unix("/dev/log").facility(Facility::Local3);
+    /// ```
+    pub fn facility(mut self, facility: Facility) -> Syslog {
+        self.facility = facility;
+        self
+    }
+
+    /// # app_name
+    ///
+    /// **Purpose:**
+    /// Sets the APP-NAME field, e.g. to tell several servers on one host apart.
+    ///
+    /// **Parameters:**
+    /// - `name`: Application name; spaces are replaced, as the field may not contain them.
+    ///
+    /// **Returns:**
+    /// - The updated `Syslog`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::syslog::Syslog;
+    /// let syslog = Syslog::unix("/dev/log").app_name("storefront");
+    /// ```
+    pub fn app_name(mut self, name: &str) -> Syslog {
+        self.app_name = header_field(name);
+        self
+    }
+
+    /// # msg_id
+    ///
+    /// **Purpose:**
+    /// Sets the MSGID field, e.g. `access` on an access log, so collectors can route by it.
+    ///
+    /// **Parameters:**
+    /// - `id`: Message id; spaces are replaced.
+    ///
+    /// **Returns:**
+    /// - The updated `Syslog`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::syslog::Syslog;
+    /// let syslog = Syslog::unix("/dev/log").msg_id("access");
+    /// ```
+    pub fn msg_id(mut self, id: &str) -> Syslog {
+        self.msg_id = header_field(id);
+        self
+    }
+
+    /// # send
+    ///
+    /// **Purpose:**
+    /// Formats one RFC 5424 message and sends it, reconnecting once if the send fails.
+    ///
+    /// **Parameters:**
+    /// - `severity`: Message severity.
+    /// - `message`: Message text; a trailing newline is dropped.
+    ///
+    /// **Returns:**
+    /// - `Ok(())` once sent.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns an I/O error if the daemon cannot be reached; the message is lost.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// syslog.send(Severity::Informational, "127.0.0.1 - - ...")?;
+    /// ```
+    pub(crate) fn send(&self, severity: Severity, message: &str) -> io::Result<()> {
+        let line = format!(
+            "<{}>1 {} {} {} {} {} - {}",
+            (self.facility as u8) * 8 + severity as u8,
+            timestamp(SystemTime::now()),
+            self.hostname,
+            self.app_name,
+            process::id(),
+            self.msg_id,
+            message.trim_end_matches('\n'),
+        );
+        let mut connection = self.connection.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(open) = connection.as_mut()
+            && open.send(line.as_bytes()).is_ok()
+        {
+            return Ok(());
+        }
+        *connection = None;
+        let mut open = self.connect()?;
+        open.send(line.as_bytes())?;
+        *connection = Some(open);
+        Ok(())
+    }
+
+    /// # with_transport
+    ///
+    /// **Purpose:**
+    /// Creates a `Syslog` with default fields for a transport.
+    ///
+    /// **Parameters:**
+    /// - `transport`: How messages reach the daemon.
+    ///
+    /// **Returns:**
+    /// - A `Syslog` filing under `Daemon` with this host's name.
+    ///
+    /// **Errors / Failures:**
+    /// None. The hostname is `-` if it cannot be read.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let syslog = Syslog::with_transport(Transport::Udp("127.0.0.1:514".to_string()));
+    /// ```
+    fn with_transport(transport: Transport) -> Syslog {
+        let hostname = fs::read_to_string("/etc/hostname")
+            .ok()
+            .or_else(|| std::env::var("HOSTNAME").ok())
+            .or_else(|| std::env::var("COMPUTERNAME").ok())
+            .map(|name| header_field(name.trim()))
+            .unwrap_or_else(|| "-".to_string());
+        Syslog {
+            transport,
+            facility: Facility::Daemon,
+            app_name: DEFAULT_APP_NAME.to_string(),
+            msg_id: "-".to_string(),
+            hostname,
+            connection: Mutex::new(None),
+        }
+    }
+
+    /// # connect
+    ///
+    /// **Purpose:**
+    /// Opens a connection over the configured transport.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - The open connection.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns an I/O error if the socket cannot be created or connected.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let connection = self.connect()?;
+    /// ```
+    fn connect(&self) -> io::Result<Connection> {
+        match &self.transport {
+            Transport::Unix(path) => connect_unix(path),
+            Transport::Udp(addr) => {
+                let socket = UdpSocket::bind(if addr.starts_with('[') { "[::]:0" } else { "0.0.0.0:0" })?;
+                socket.connect(addr)?;
+                Ok(Connection::Udp(socket))
+            }
+            Transport::Tcp(addr) => Ok(Connection::Tcp(TcpStream::connect(addr)?)),
+        }
+    }
+}
+
+
+impl Connection {
+
+    /// # send
+    ///
+    /// **Purpose:**
+    /// Sends one formatted message: as a whole datagram, or prefixed with its length on TCP.
+    ///
+    /// **Parameters:**
+    /// - `message`: Formatted message.
+    ///
+    /// **Returns:**
+    /// - `Ok(())` once sent.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns any I/O error raised by the socket.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// connection.send(line.as_bytes())?;
+    /// ```
+    fn send(&mut self, message: &[u8]) -> io::Result<()> {
+        match self {
+            #[cfg(unix)]
+            Connection::Unix(socket) => socket.send(message).map(drop),
+            Connection::Udp(socket) => socket.send(message).map(drop),
+            Connection::Tcp(stream) => {
+                let mut framed = format!("{} ", message.len()).into_bytes();
+                framed.extend_from_slice(message);
+                stream.write_all(&framed)
+            }
+        }
+    }
+}
+
+
+/// # connect_unix
+///
+/// **Purpose:**
+/// Connects a datagram socket to the local daemon.
+///
+/// **Parameters:**
+/// - `path`: Socket path.
+///
+/// **Returns:**
+/// - The connection.
+///
+/// **Errors / Failures:**
+/// - Returns an I/O error if no daemon listens at `path`.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let connection = connect_unix("/dev/log")?;
+/// ```
+#[cfg(unix)]
+fn connect_unix(path: &str) -> io::Result<Connection> {
+    let socket = std::os::unix::net::UnixDatagram::unbound()?;
+    socket.connect(path)?;
+    Ok(Connection::Unix(socket))
+}
+
+
+/// # connect_unix
+///
+/// **Purpose:**
+/// Stands in on targets without Unix domain sockets.
+///
+/// **Parameters:**
+/// - `_path`: Socket path.
+///
+/// **Returns:**
+/// None.
+///
+/// **Errors / Failures:**
+/// - Always returns `Unsupported`.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let connection = connect_unix("/dev/log")?;
+/// ```
+#[cfg(not(unix))]
+fn connect_unix(_path: &str) -> io::Result<Connection> {
+    Err(io::Error::new(io::ErrorKind::Unsupported, "unix syslog sockets need a Unix target"))
+}
+
+
+/// # timestamp
+///
+/// **Purpose:**
+/// Formats a time as an RFC 5424 timestamp in UTC with milliseconds.
+///
+/// **Parameters:**
+/// - `time`: Time to format.
+///
+/// **Returns:**
+/// - E.g. `2026-10-14T06:32:07.123Z`.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let stamp = timestamp(SystemTime::now());
+/// ```
+fn timestamp(time: SystemTime) -> String {
+    let date = DateTime::from_system_time(time);
+    let millis = time.duration_since(UNIX_EPOCH).map_or(0, |elapsed| elapsed.subsec_millis());
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{millis:03}Z",
+        date.year, date.month, date.day, date.hour, date.minute, date.second
+    )
+}
+
+
+/// # header_field
+///
+/// **Purpose:**
+/// Makes text usable as a header field, which must be printable ASCII without spaces.
+///
+/// **Parameters:**
+/// - `text`: Field value.
+///
+/// **Returns:**
+/// - The value with other characters replaced by `_`, or `-` if empty.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// assert_eq!(header_field("my app"), "my_app");
+/// ```
+fn header_field(text: &str) -> String {
+    if text.is_empty() {
+        return "-".to_string();
+    }
+    text.chars().map(|c| if c.is_ascii_graphic() { c } else { '_' }).collect()
+}
@@ -0,0 +1,643 @@
+
+// ============================================================
+//  DAEGONICA SOFTWARE — tasks.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: Tasks
+//!
+//! **Purpose:**
+//! Supervises the long-running background jobs an application runs beside its server, such
+//! as sweeping expired sessions, renewing certificates, or probing dependencies.
+//!
+//! **Context:**
+//! - Set with `Server::tasks`; the server starts every task once its listener is bound, and
+//!   stops them when it stops, through the admin API, `SIGTERM`, a `ServerHandle`, or an
+//!   accept failure.
+//!
+//! **Responsibilities:**
+//! - Runs each registered task on a thread named after it.
+//! - Restarts a task that panics when its policy says so, waiting longer after each
+//!   consecutive panic, and logs every panic under the task's name.
+//! - Tells tasks to stop through their `TaskContext`, whose `sleep` returns early once the
+//!   server stops, and waits a bounded time for them to return.
+//! - Does NOT interrupt a task that ignores its context; after `SHUTDOWN_GRACE` it is logged
+//!   and left running.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+use std::{
+    fmt,
+    io,
+    panic::{self, AssertUnwindSafe},
+    sync::{Arc, Condvar, Mutex, MutexGuard, PoisonError},
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+use crate::log::{self, LogLevel};
+
+
+/// # RESTART_DELAY
+///
+/// **Summary:**
+/// Wait before restarting a task after its first panic; doubled after each further panic in
+/// a row.
+const RESTART_DELAY: Duration = Duration::from_secs(1);
+
+
+/// # MAX_RESTART_DELAY
+///
+/// **Summary:**
+/// Longest wait between restarts. A task that runs this long before panicking starts over
+/// from `RESTART_DELAY`.
+const MAX_RESTART_DELAY: Duration = Duration::from_secs(60);
+
+
+/// # SHUTDOWN_GRACE
+///
+/// **Summary:**
+/// How long stopping waits for tasks to return before leaving the rest running.
+const SHUTDOWN_GRACE: Duration = Duration::from_secs(10);
+
+
+/// # SHUTDOWN_POLL
+///
+/// **Summary:**
+/// How often stopping checks whether the tasks have returned.
+const SHUTDOWN_POLL: Duration = Duration::from_millis(50);
+
+
+/// # Job
+///
+/// **Summary:**
+/// Body of a task, rerun from the start on restart.
+type Job = Arc<dyn Fn(&TaskContext) + Send + Sync>;
+
+
+/// # Restart
+///
+/// **Summary:**
+/// What happens when a task panics. A task that returns normally is never restarted.
+///
+/// **Variants:**
+/// - `Never`: The task stays stopped.
+/// - `OnPanic`: The task is started again after a delay, unless the server is stopping.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Restart {
+    Never,
+    OnPanic,
+}
+
+
+/// # Tasks
+///
+/// **Summary:**
+/// Registry of background tasks and the threads running them.
+///
+/// **Fields:**
+/// - `tasks`: Registered tasks in order, with their restart policies and bodies.
+/// - `stop`: Stop signal shared with every running task.
+/// - `running`: Threads started by `start`, joined when the tasks stop.
+///
+/// **Usage Example:**
+/// ```rust
+/// # use server::{server::Server, session::Sessions, tasks::{Restart, Tasks}};
+/// # use std::time::Duration;
+/// # fn renew_if_due() {}
+/// # let sessions = Sessions::new();
+/// let sweeper = sessions.clone();
+/// let tasks = Tasks::new()
+///     .every("session-sweep", Duration::from_secs(60), move || { sweeper.sweep(); })
+///     .spawn("cert-renewal", Restart::OnPanic, |ctx| while ctx.sleep(Duration::from_secs(3600)) { renew_if_due(); });
+/// let server = Server::new("0.0.0.0:80").tasks(tasks);
+/// ```
+#[derive(Default)]
+pub struct Tasks {
+    tasks: Vec<(String, Restart, Job)>,
+    stop: Arc<StopSignal>,
+    running: Mutex<Vec<JoinHandle<()>>>,
+}
+
+
+/// # TaskContext
+///
+/// **Summary:**
+/// What a running task is given: its name and a way to learn the server is stopping.
+///
+/// **Fields:**
+/// - `name`: Name the task was registered under.
+/// - `stop`: Stop signal shared by the registry's tasks.
+///
+/// **Usage Example:**
+/// ```rust
+/// # use server::tasks::{Restart, Tasks};
+/// # use std::time::Duration;
+/// # fn probe_database(_name: &str) {}
+/// let tasks = Tasks::new().spawn("probe", Restart::OnPanic, |ctx| {
+///     while ctx.sleep(Duration::from_secs(5)) { probe_database(ctx.name()); }
+/// });
+/// ```
+pub struct TaskContext {
+    name: String,
+    stop: Arc<StopSignal>,
+}
+
+
+/// # StopSignal
+///
+/// **Summary:**
+/// Flag telling tasks to stop, with a condition variable to cut their sleeps short.
+///
+/// **Fields:**
+/// - `stopped`: Set once the tasks are told to stop.
+/// - `wake`: Notified when `stopped` is set.
+#[derive(Debug, Default)]
+struct StopSignal {
+    stopped: Mutex<bool>,
+    wake: Condvar,
+}
+
+
+impl Tasks {
+
+    /// # new
+    ///
+    /// **Purpose:**
+    /// Creates an empty registry.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - A `Tasks` with nothing registered.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::tasks::Tasks;
+    /// let tasks = Tasks::new();
+    /// ```
+    pub fn new() -> Tasks {
+        Tasks::default()
+    }
+
+    /// # spawn
+    ///
+    /// **Purpose:**
+    /// Registers a long-running task. Its body should return once `TaskContext::stopping` is
+    /// true, and wait with `TaskContext::sleep` so stopping wakes it.
+    ///
+    /// **Parameters:**
+    /// - `name`: Name for logs and the task's thread.
+    /// - `restart`: What to do if the body panics.
+    /// - `job`: Body of the task.
+    ///
+    /// **Returns:**
+    /// - The updated `Tasks`.
+    ///
+    /// **Errors / Failures:**
+    /// None. The task starts with the server.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::tasks::{Restart, Tasks};
+    /// # fn warm_cache() {}
+    /// let tasks = Tasks::new().spawn("warm-cache", Restart::Never, |_| warm_cache());
+    /// ```
+    pub fn spawn(mut self, name: impl Into<String>, restart: Restart, job: impl Fn(&TaskContext) + Send + Sync + 'static) -> Tasks {
+        self.tasks.push((name.into(), restart, Arc::new(job)));
+        self
+    }
+
+    /// # every
+    ///
+    /// **Purpose:**
+    /// Registers a job to run periodically, first when the server starts and then once per
+    /// interval, restarted if it panics.
+    ///
+    /// **Parameters:**
+    /// - `name`: Name for logs and the task's thread.
+    /// - `interval`: Wait between the end of one run and the start of the next.
+    /// - `job`: Work done on each run.
+    ///
+    /// **Returns:**
+    /// - The updated `Tasks`.
+    ///
+    /// **Errors / Failures:**
+    /// None. A panicking run is logged and the schedule resumes after the restart delay.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{session::Sessions, tasks::Tasks};
+    /// # use std::time::Duration;
+    /// # let sessions = Sessions::new();
+    /// let tasks = Tasks::new().every("session-sweep", Duration::from_secs(60), move || { sessions.sweep(); });
+    /// ```
+    pub fn every(self, name: impl Into<String>, interval: Duration, job: impl Fn() + Send + Sync + 'static) -> Tasks {
+        self.spawn(name, Restart::OnPanic, move |ctx| {
+            job();
+            while ctx.sleep(interval) {
+                job();
+            }
+        })
+    }
+
+    /// # len
+    ///
+    /// **Purpose:**
+    /// Counts the registered tasks.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - How many tasks are registered.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::tasks::Tasks;
+    /// # let tasks = Tasks::new().every("tick", std::time::Duration::from_secs(1), || {});
+    /// let registered = tasks.len();
+    /// # assert_eq!(registered, 1);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.tasks.len()
+    }
+
+    /// # is_empty
+    ///
+    /// **Purpose:**
+    /// Checks whether any task is registered.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - `true` if there are no tasks.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::tasks::Tasks;
+    /// # let tasks = Tasks::new();
+    /// if tasks.is_empty() { return; }
+    /// # unreachable!();
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.tasks.is_empty()
+    }
+
+    /// # start
+    ///
+    /// **Purpose:**
+    /// Starts a thread for every registered task.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - `Ok(())` once every thread is running.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns an I/O error if a thread cannot be created; the tasks already started keep
+    ///   running until the tasks are stopped.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// self.tasks.start()?;
+    /// ```
+    pub(crate) fn start(&self) -> io::Result<()> {
+        for (name, restart, job) in &self.tasks {
+            let context = TaskContext { name: name.clone(), stop: Arc::clone(&self.stop) };
+            let (restart, job) = (*restart, Arc::clone(job));
+            let thread = thread::Builder::new().name(name.clone()).spawn(move || supervise(&context, restart, &job))?;
+            lock(&self.running).push(thread);
+        }
+        Ok(())
+    }
+
+    /// # stop
+    ///
+    /// **Purpose:**
+    /// Tells every task to stop, without waiting for them.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// None.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// self.tasks.stop();
+    /// ```
+    pub(crate) fn stop(&self) {
+        *lock(&self.stop.stopped) = true;
+        self.stop.wake.notify_all();
+    }
+
+    /// # shutdown
+    ///
+    /// **Purpose:**
+    /// Tells every task to stop and waits up to `SHUTDOWN_GRACE` for them to return.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// None, once every task has returned or the grace period has passed.
+    ///
+    /// **Errors / Failures:**
+    /// None. Tasks still running afterwards are logged by name and left to finish on their own.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// server.tasks.shutdown();
+    /// ```
+    pub(crate) fn shutdown(&self) {
+        self.stop();
+        let mut running = std::mem::take(&mut *lock(&self.running));
+        let started = Instant::now();
+        while started.elapsed() < SHUTDOWN_GRACE && running.iter().any(|thread| !thread.is_finished()) {
+            thread::sleep(SHUTDOWN_POLL);
+        }
+        for thread in running.drain(..) {
+            if thread.is_finished() {
+                let _ = thread.join();
+            } else {
+                let name = thread.thread().name().unwrap_or("unnamed").to_string();
+                log::emit(LogLevel::Warn, format_args!("Task {name} did not stop within {SHUTDOWN_GRACE:?}; leaving it running"));
+            }
+        }
+    }
+}
+
+
+impl fmt::Debug for Tasks {
+
+    /// # fmt
+    ///
+    /// **Purpose:**
+    /// Lists the registered tasks by name and restart policy, since their bodies cannot be
+    /// printed.
+    ///
+    /// **Parameters:**
+    /// - `formatter`: Destination formatter.
+    ///
+    /// **Returns:**
+    /// - The formatting result.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns `fmt::Error` if the formatter fails.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::tasks::Tasks;
+    /// # let tasks = Tasks::new().every("tick", std::time::Duration::from_secs(1), || {});
+    /// println!("{tasks:?}");
+    /// ```
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let tasks: Vec<(&str, Restart)> = self.tasks.iter().map(|(name, restart, _)| (name.as_str(), *restart)).collect();
+        formatter.debug_struct("Tasks").field("tasks", &tasks).finish()
+    }
+}
+
+
+impl TaskContext {
+
+    /// # name
+    ///
+    /// **Purpose:**
+    /// Returns the name the task was registered under.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - The task name.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{log::{self, LogLevel}, tasks::{Restart, Tasks}};
+    /// # let tasks = Tasks::new().spawn("cert-renewal", Restart::Never, |ctx| {
+    /// log::emit(LogLevel::Info, format_args!("{} renewed", ctx.name()));
+    /// # });
+    /// ```
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// # stopping
+    ///
+    /// **Purpose:**
+    /// Checks whether the server has asked its tasks to stop.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - `true` once the task should return.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::tasks::{Restart, Tasks};
+    /// # use std::time::Duration;
+    /// # fn process_next_batch() {}
+    /// # let tasks = Tasks::new().spawn("batches", Restart::Never, |ctx| {
+    /// while !ctx.stopping() { process_next_batch(); }
+    /// # });
+    /// ```
+    pub fn stopping(&self) -> bool {
+        *lock(&self.stop.stopped)
+    }
+
+    /// # sleep
+    ///
+    /// **Purpose:**
+    /// Waits for a duration, waking early if the server asks its tasks to stop.
+    ///
+    /// **Parameters:**
+    /// - `duration`: How long to wait.
+    ///
+    /// **Returns:**
+    /// - `true` if the full duration passed, `false` as soon as the task should stop.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::tasks::{Restart, Tasks};
+    /// # use std::time::Duration;
+    /// # fn check_upstream() {}
+    /// # let tasks = Tasks::new().spawn("upstream", Restart::Never, |ctx| {
+    /// while ctx.sleep(Duration::from_secs(30)) { check_upstream(); }
+    /// # });
+    /// ```
+    pub fn sleep(&self, duration: Duration) -> bool {
+        let stopped = lock(&self.stop.stopped);
+        let (stopped, _) = self
+            .stop
+            .wake
+            .wait_timeout_while(stopped, duration, |stopped| !*stopped)
+            .unwrap_or_else(PoisonError::into_inner);
+        !*stopped
+    }
+}
+
+
+/// # supervise
+///
+/// **Purpose:**
+/// Runs a task on its thread, restarting it after panics as its policy allows.
+///
+/// **Parameters:**
+/// - `context`: The task's context.
+/// - `restart`: Its restart policy.
+/// - `job`: Its body.
+///
+/// **Returns:**
+/// None, once the task returns, panics without a restart, or is told to stop.
+///
+/// **Errors / Failures:**
+/// None. Panics are caught and logged.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// thread::spawn(move || supervise(&context, Restart::OnPanic, &job));
+/// ```
+fn supervise(context: &TaskContext, restart: Restart, job: &Job) {
+    let mut delay = RESTART_DELAY;
+    loop {
+        let started = Instant::now();
+        if panic::catch_unwind(AssertUnwindSafe(|| job(context))).is_ok() {
+            return;
+        }
+        log::emit(LogLevel::Error, format_args!("Task {} panicked", context.name));
+        if restart == Restart::Never || context.stopping() {
+            return;
+        }
+        if started.elapsed() >= MAX_RESTART_DELAY {
+            delay = RESTART_DELAY;
+        }
+        log::emit(LogLevel::Info, format_args!("Restarting task {} in {delay:?}", context.name));
+        if !context.sleep(delay) {
+            return;
+        }
+        delay = (delay * 2).min(MAX_RESTART_DELAY);
+    }
+}
+
+
+/// # lock
+///
+/// **Purpose:**
+/// Locks a mutex, recovering it if a thread panicked while holding it.
+///
+/// **Parameters:**
+/// - `mutex`: Mutex to lock.
+///
+/// **Returns:**
+/// - The guard.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// lock(&self.running).push(thread);
+/// ```
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn counter() -> (Arc<AtomicUsize>, Arc<AtomicUsize>) {
+        let runs = Arc::new(AtomicUsize::new(0));
+        (Arc::clone(&runs), runs)
+    }
+
+    fn wait_for(runs: &AtomicUsize, at_least: usize) {
+        let started = Instant::now();
+        while runs.load(Ordering::SeqCst) < at_least {
+            assert!(started.elapsed() < Duration::from_secs(10), "only {} runs", runs.load(Ordering::SeqCst));
+            thread::sleep(Duration::from_millis(10));
+        }
+    }
+
+    #[test]
+    fn periodic_jobs_run_at_once_and_then_on_schedule() {
+        let (runs, seen) = counter();
+        let tasks = Tasks::new().every("tick", Duration::from_millis(20), move || {
+            runs.fetch_add(1, Ordering::SeqCst);
+        });
+        tasks.start().unwrap();
+        wait_for(&seen, 3);
+        tasks.shutdown();
+        let after = seen.load(Ordering::SeqCst);
+        thread::sleep(Duration::from_millis(60));
+        assert_eq!(seen.load(Ordering::SeqCst), after, "no runs after shutdown");
+    }
+
+    #[test]
+    fn stopping_cuts_sleeps_short() {
+        let tasks = Tasks::new().spawn("sleeper", Restart::Never, |ctx| {
+            assert!(!ctx.sleep(Duration::from_secs(3600)));
+            assert!(ctx.stopping());
+        });
+        tasks.start().unwrap();
+        let started = Instant::now();
+        tasks.shutdown();
+        assert!(started.elapsed() < Duration::from_secs(5));
+        assert!(lock(&tasks.running).is_empty());
+    }
+
+    #[test]
+    fn panicking_tasks_restart_only_when_asked() {
+        let (always, restarted) = counter();
+        let (once, never) = counter();
+        let tasks = Tasks::new()
+            .spawn("flaky", Restart::OnPanic, move |_| {
+                if always.fetch_add(1, Ordering::SeqCst) == 0 {
+                    panic!("first run fails");
+                }
+            })
+            .spawn("fragile", Restart::Never, move |_| {
+                once.fetch_add(1, Ordering::SeqCst);
+                panic!("fails for good");
+            });
+        tasks.start().unwrap();
+        wait_for(&restarted, 2);
+        tasks.shutdown();
+        assert_eq!(never.load(Ordering::SeqCst), 1);
+        assert_eq!(restarted.load(Ordering::SeqCst), 2, "a task that returns is not restarted");
+    }
+}
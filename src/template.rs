@@ -0,0 +1,1131 @@
+
+// ============================================================
+//  DAEGONICA SOFTWARE — template.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: Template
+//!
+//! **Purpose:**
+//! Renders HTML pages from small logic-light templates and a `JsonValue` context.
+//!
+//! **Context:**
+//! - Used by handlers through `Response::render`; templates are parsed once and rendered per
+//!   request.
+//! - `TemplateEngine` lets other engines (Tera, Handlebars, Askama) be plugged in behind the
+//!   application's own feature flags; `Templates` is the built-in implementation, and
+//!   `TemplateDir` a directory-backed one the file watcher can reload.
+//!
+//! **Responsibilities:**
+//! - Substitutes `{{name}}` and dotted paths (`{{user.name}}`, `{{items.0}}`), HTML-escaping
+//!   the value; `{{{name}}}` or `{{&name}}` insert it unescaped.
+//! - Supports `{{#if x}}`, `{{#unless x}}`, and `{{#each xs}}` sections, each with an optional
+//!   `{{else}}`. Inside `each`, `{{this}}`, `{{@index}}`, and `{{@key}}` refer to the current item,
+//!   and names not found on it are looked up in the enclosing scopes.
+//! - Drops comments (`{{! ... }}`) and the lines holding nothing but a section tag, so block
+//!   markup does not leave blank lines behind.
+//! - Does NOT support partials, helpers, or expressions; missing values render as nothing.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    error::Error,
+    fmt,
+    fs,
+    io,
+    path::{Path, PathBuf},
+    sync::{PoisonError, RwLock},
+};
+
+use crate::{
+    json_value::JsonValue,
+    log::{self, LogLevel},
+};
+
+
+/// # Template
+///
+/// **Summary:**
+/// A parsed template, ready to render any number of times.
+///
+/// **Fields:**
+/// - `nodes`: Top-level parts of the template.
+///
+/// **Usage Example:**
+/// ```rust
+/// # use server::{json_value::JsonValue, template::Template};
+/// # let context = JsonValue::Object(vec![
+/// #     ("title".into(), "Menu".into()),
+/// #     ("items".into(), JsonValue::Array(vec![JsonValue::Object(vec![("name".into(), "Tea".into())])])),
+/// # ]);
+/// let page = Template::parse("<h1>{{title}}</h1>{{#each items}}<li>{{name}}</li>{{/each}}")?;
+/// let html = page.render(&context);
+/// # assert_eq!(html, "<h1>Menu</h1><li>Tea</li>");
+/// # Ok::<(), server::template::TemplateError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct Template {
+    nodes: Vec<Node>,
+}
+
+
+/// # TemplateEngine
+///
+/// **Summary:**
+/// Renders templates by name. Implement it to adapt another engine; the server itself depends
+/// on none.
+///
+/// **Usage Example:**
+/// ```rust
+/// # use server::{json_value::JsonValue, response::Response, template::{Template, TemplateEngine, Templates}};
+/// # let context = JsonValue::Object(vec![("title".into(), "Home".into())]);
+/// # let engine = Templates::new().add("index.html", Template::parse("<h1>{{title}}</h1>").unwrap());
+/// #[cfg(feature = "tera")]
+/// impl TemplateEngine for TeraEngine {
+///     fn render(&self, name: &str, context: &JsonValue) -> Result<String, Box<dyn Error + Send + Sync>> {
+///         Ok(self.tera.render(name, &tera::Context::from_value(to_serde(context))?)?)
+///     }
+/// }
+/// let response = Response::render_template(&engine, "index.html", &context);
+/// # assert_eq!(response.body, b"<h1>Home</h1>");
+/// ```
+pub trait TemplateEngine: Send + Sync {
+
+    /// # render
+    ///
+    /// **Purpose:**
+    /// Renders the template registered under a name.
+    ///
+    /// **Parameters:**
+    /// - `name`: Template name, e.g. `index.html`.
+    /// - `context`: Values the template refers to.
+    ///
+    /// **Returns:**
+    /// - The rendered text.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns the engine's error if the template is unknown or fails to render.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{json_value::JsonValue, template::{Template, TemplateEngine, Templates}};
+    /// # let context = JsonValue::Object(vec![("title".into(), "Home".into())]);
+    /// # let engine = Templates::new().add("index.html", Template::parse("<h1>{{title}}</h1>").unwrap());
+    /// let html = engine.render("index.html", &context)?;
+    /// # assert_eq!(html, "<h1>Home</h1>");
+    /// # Ok::<(), Box<dyn std::error::Error + Send + Sync>>(())
+    /// ```
+    fn render(&self, name: &str, context: &JsonValue) -> Result<String, Box<dyn Error + Send + Sync>>;
+}
+
+
+/// # Templates
+///
+/// **Summary:**
+/// Built-in `TemplateEngine`: named templates parsed up front.
+///
+/// **Fields:**
+/// - `templates`: Parsed templates by name.
+///
+/// **Usage Example:**
+/// ```rust,no_run
+/// # use server::{json_value::JsonValue, response::Response, template::{Template, Templates}};
+/// # let context = JsonValue::Object(vec![("title".into(), "Home".into())]);
+/// let templates = Templates::dir("templates")?.add("inline", Template::parse("{{x}}")?);
+/// let response = Response::render_template(&templates, "index.html", &context);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Templates {
+    templates: HashMap<String, Template>,
+}
+
+
+/// # TemplateDir
+///
+/// **Summary:**
+/// `TemplateEngine` over a directory of templates that can be re-read while the server runs.
+///
+/// **Fields:**
+/// - `root`: Template directory.
+/// - `templates`: Templates from the last successful load.
+///
+/// **Usage Example:**
+/// ```rust,no_run
+/// # use server::{template::TemplateDir, watch::Watcher};
+/// # use std::sync::Arc;
+/// let templates = Arc::new(TemplateDir::open("templates")?);
+/// Watcher::new("templates").templates(Arc::clone(&templates)).spawn()?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct TemplateDir {
+    root: PathBuf,
+    templates: RwLock<Templates>,
+}
+
+
+/// # TemplateError
+///
+/// **Summary:**
+/// Why a template could not be parsed.
+///
+/// **Fields:**
+/// - `line`: Line of the offending tag, counting from `1`.
+/// - `message`: What is wrong.
+///
+/// **Usage Example:**
+/// ```rust
+/// # use server::template::Template;
+/// if let Err(error) = Template::parse("{{#if x}}") {
+///     eprintln!("line {}: {}", error.line, error.message);
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TemplateError {
+    pub line: usize,
+    pub message: String,
+}
+
+
+/// # Node
+///
+/// **Summary:**
+/// One part of a parsed template.
+///
+/// **Variants:**
+/// - `Text`: Literal text.
+/// - `Value`: A value looked up by path, escaped unless `escape` is false.
+/// - `Section`: A conditional or loop with its body and `{{else}}` branch.
+#[derive(Debug, Clone)]
+enum Node {
+    Text(String),
+    Value { path: String, escape: bool },
+    Section { kind: Section, path: String, body: Vec<Node>, otherwise: Vec<Node> },
+}
+
+
+/// # Section
+///
+/// **Summary:**
+/// Kind of block section.
+///
+/// **Variants:**
+/// - `If`: Renders the body when the value is truthy.
+/// - `Unless`: Renders the body when the value is falsy.
+/// - `Each`: Renders the body once per array item or object member.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Section {
+    If,
+    Unless,
+    Each,
+}
+
+
+/// # Token
+///
+/// **Summary:**
+/// A lexical piece of template source.
+///
+/// **Variants:**
+/// - `Text`: Literal text between tags.
+/// - `Tag`: Trimmed tag contents, whether it used triple braces, and its line.
+#[derive(Debug)]
+enum Token<'a> {
+    Text(&'a str),
+    Tag { content: &'a str, raw: bool, line: usize },
+}
+
+
+/// # Scope
+///
+/// **Summary:**
+/// One level of the lookup stack: the root context or the current item of an `each`.
+///
+/// **Fields:**
+/// - `value`: Value names are looked up in.
+/// - `index`: Position of the item, inside `each`.
+/// - `key`: Member name of the item, inside `each` over an object.
+struct Scope<'a> {
+    value: &'a JsonValue,
+    index: Option<usize>,
+    key: Option<&'a str>,
+}
+
+
+impl Template {
+
+    /// # parse
+    ///
+    /// **Purpose:**
+    /// Parses template source.
+    ///
+    /// **Parameters:**
+    /// - `source`: Template text.
+    ///
+    /// **Returns:**
+    /// - The parsed `Template`.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns a `TemplateError` for unclosed tags, unknown or unbalanced sections, a stray
+    ///   `{{else}}`, or an empty tag.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::template::Template;
+    /// let template = Template::parse("Hello, {{name}}!")?;
+    /// # Ok::<(), server::template::TemplateError>(())
+    /// ```
+    pub fn parse(source: &str) -> Result<Template, TemplateError> {
+        let tokens = tokenize(source)?;
+        let mut tokens = tokens.into_iter();
+        let (nodes, _) = parse_nodes(&mut tokens, None)?;
+        Ok(Template { nodes })
+    }
+
+    /// # open
+    ///
+    /// **Purpose:**
+    /// Reads and parses a template file.
+    ///
+    /// **Parameters:**
+    /// - `path`: Template file.
+    ///
+    /// **Returns:**
+    /// - The parsed `Template`.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns an I/O error if the file cannot be read, or an `InvalidData` error carrying the
+    ///   `TemplateError` if it does not parse.
+    ///
+    /// **Examples:**
+    /// ```rust,no_run
+    /// # use server::template::Template;
+    /// let page = Template::open("templates/page.html")?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Template> {
+        let source = fs::read_to_string(path)?;
+        Template::parse(&source).map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+    }
+
+    /// # render
+    ///
+    /// **Purpose:**
+    /// Renders the template against a context.
+    ///
+    /// **Parameters:**
+    /// - `context`: Root value names are looked up in, usually an object.
+    ///
+    /// **Returns:**
+    /// - The rendered text.
+    ///
+    /// **Errors / Failures:**
+    /// None. Missing values render as nothing and are falsy.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{json_value::JsonValue, template::Template};
+    /// # let template = Template::parse("Hello, {{name}}!")?;
+    /// let html = template.render(&JsonValue::Object(vec![("name".into(), "Ana".into())]));
+    /// # assert_eq!(html, "Hello, Ana!");
+    /// # Ok::<(), server::template::TemplateError>(())
+    /// ```
+    pub fn render(&self, context: &JsonValue) -> String {
+        let mut out = String::new();
+        let mut scopes = vec![Scope {
+            value: context,
+            index: None,
+            key: None,
+        }];
+        render_nodes(&self.nodes, &mut scopes, &mut out);
+        out
+    }
+}
+
+
+impl Templates {
+
+    /// # new
+    ///
+    /// **Purpose:**
+    /// Creates an empty registry.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - A new `Templates`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::template::Templates;
+    /// let templates = Templates::new();
+    /// ```
+    pub fn new() -> Templates {
+        Templates::default()
+    }
+
+    /// # dir
+    ///
+    /// **Purpose:**
+    /// Loads every file beneath a directory, named by its path relative to it with `/`
+    /// separators (`layouts/base.html`).
+    ///
+    /// **Parameters:**
+    /// - `root`: Template directory.
+    ///
+    /// **Returns:**
+    /// - A registry holding every template found.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns an I/O error if the directory cannot be read, or an `InvalidData` error naming
+    ///   the first file that is not UTF-8 or does not parse.
+    ///
+    /// **Examples:**
+    /// ```rust,no_run
+    /// # use server::template::Templates;
+    /// let templates = Templates::dir("templates")?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn dir(root: impl AsRef<Path>) -> io::Result<Templates> {
+        let mut templates = Templates::new();
+        let mut pending = vec![root.as_ref().to_path_buf()];
+        while let Some(directory) = pending.pop() {
+            for entry in fs::read_dir(&directory)? {
+                let path = entry?.path();
+                if path.is_dir() {
+                    pending.push(path);
+                    continue;
+                }
+                let Ok(relative) = path.strip_prefix(root.as_ref()) else {
+                    continue;
+                };
+                let name = relative.components().map(|part| part.as_os_str().to_string_lossy()).collect::<Vec<_>>().join("/");
+                let template = Template::open(&path)
+                    .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, format!("{name}: {error}")))?;
+                templates.templates.insert(name, template);
+            }
+        }
+        Ok(templates)
+    }
+
+    /// # add
+    ///
+    /// **Purpose:**
+    /// Registers a template under a name, replacing any with the same name.
+    ///
+    /// **Parameters:**
+    /// - `name`: Template name.
+    /// - `template`: Parsed template.
+    ///
+    /// **Returns:**
+    /// - The updated `Templates`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::template::{Template, Templates};
+    /// let templates = Templates::new().add("greeting", Template::parse("Hi {{name}}")?);
+    /// # Ok::<(), server::template::TemplateError>(())
+    /// ```
+    pub fn add(mut self, name: &str, template: Template) -> Templates {
+        self.templates.insert(name.to_string(), template);
+        self
+    }
+
+    /// # get
+    ///
+    /// **Purpose:**
+    /// Looks up a registered template.
+    ///
+    /// **Parameters:**
+    /// - `name`: Template name.
+    ///
+    /// **Returns:**
+    /// - The template, or `None` if none has that name.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::template::{Template, Templates};
+    /// # let templates = Templates::new().add("index.html", Template::parse("<h1>{{title}}</h1>")?);
+    /// let page = templates.get("index.html");
+    /// # assert!(page.is_some());
+    /// # Ok::<(), server::template::TemplateError>(())
+    /// ```
+    pub fn get(&self, name: &str) -> Option<&Template> {
+        self.templates.get(name)
+    }
+}
+
+
+impl TemplateEngine for Templates {
+
+    /// # render
+    ///
+    /// **Purpose:**
+    /// Renders a registered template.
+    ///
+    /// **Parameters:**
+    /// - `name`: Template name.
+    /// - `context`: Values the template refers to.
+    ///
+    /// **Returns:**
+    /// - The rendered text.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns an error if no template has that name.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{json_value::JsonValue, template::{Template, TemplateEngine, Templates}};
+    /// # let context = JsonValue::Object(vec![("title".into(), "Home".into())]);
+    /// # let templates = Templates::new().add("index.html", Template::parse("<h1>{{title}}</h1>").unwrap());
+    /// let html = templates.render("index.html", &context)?;
+    /// # assert_eq!(html, "<h1>Home</h1>");
+    /// # Ok::<(), Box<dyn std::error::Error + Send + Sync>>(())
+    /// ```
+    fn render(&self, name: &str, context: &JsonValue) -> Result<String, Box<dyn Error + Send + Sync>> {
+        let template = self.get(name).ok_or_else(|| format!("no template named {name}"))?;
+        Ok(template.render(context))
+    }
+}
+
+
+impl TemplateDir {
+
+    /// # open
+    ///
+    /// **Purpose:**
+    /// Loads every template beneath a directory, as `Templates::dir` does.
+    ///
+    /// **Parameters:**
+    /// - `root`: Template directory.
+    ///
+    /// **Returns:**
+    /// - The loaded `TemplateDir`.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns the error from `Templates::dir`.
+    ///
+    /// **Examples:**
+    /// ```rust,no_run
+    /// # use server::template::TemplateDir;
+    /// let templates = TemplateDir::open("templates")?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn open(root: impl Into<PathBuf>) -> io::Result<TemplateDir> {
+        let root = root.into();
+        let templates = Templates::dir(&root)?;
+        Ok(TemplateDir { root, templates: RwLock::new(templates) })
+    }
+
+    /// # root
+    ///
+    /// **Purpose:**
+    /// Returns the template directory.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - The directory given to `open`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,no_run
+    /// # use server::template::TemplateDir;
+    /// # let templates = TemplateDir::open("templates")?;
+    /// println!("templates in {}", templates.root().display());
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// # reload
+    ///
+    /// **Purpose:**
+    /// Re-reads the directory, swapping the new templates in only if all of them load, so a
+    /// half-finished edit keeps the previous version serving.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - `Ok(())` once the new templates are in use.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns the load error, which is also logged; the previous templates stay in use.
+    ///
+    /// **Examples:**
+    /// ```rust,no_run
+    /// # use server::template::TemplateDir;
+    /// # let templates = TemplateDir::open("templates")?;
+    /// templates.reload()?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn reload(&self) -> io::Result<()> {
+        match Templates::dir(&self.root) {
+            Ok(templates) => {
+                *self.templates.write().unwrap_or_else(PoisonError::into_inner) = templates;
+                Ok(())
+            }
+            Err(error) => {
+                log::emit(LogLevel::Warn, format_args!("Reloading templates from {} failed: {error}", self.root.display()));
+                Err(error)
+            }
+        }
+    }
+}
+
+
+impl TemplateEngine for TemplateDir {
+
+    /// # render
+    ///
+    /// **Purpose:**
+    /// Renders a template from the last successful load.
+    ///
+    /// **Parameters:**
+    /// - `name`: Template name, relative to the directory.
+    /// - `context`: Values the template refers to.
+    ///
+    /// **Returns:**
+    /// - The rendered text.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns an error if no template has that name.
+    ///
+    /// **Examples:**
+    /// ```rust,no_run
+    /// # use server::{json_value::JsonValue, template::{TemplateDir, TemplateEngine}};
+    /// # let templates = TemplateDir::open("templates")?;
+    /// # let context = JsonValue::Object(vec![("title".into(), "Home".into())]);
+    /// let html = templates.render("index.html", &context)?;
+    /// # Ok::<(), Box<dyn std::error::Error + Send + Sync>>(())
+    /// ```
+    fn render(&self, name: &str, context: &JsonValue) -> Result<String, Box<dyn Error + Send + Sync>> {
+        self.templates.read().unwrap_or_else(PoisonError::into_inner).render(name, context)
+    }
+}
+
+
+impl fmt::Display for TemplateError {
+
+    /// # fmt
+    ///
+    /// **Purpose:**
+    /// Describes the parse error with its line.
+    ///
+    /// **Parameters:**
+    /// - `f`: Formatter to write to.
+    ///
+    /// **Returns:**
+    /// - The formatter result.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::template::Template;
+    /// # let error = Template::parse("{{#if x}}").unwrap_err();
+    /// let message = error.to_string();
+    /// # assert!(message.contains(&error.message));
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "template line {}: {}", self.line, self.message)
+    }
+}
+
+
+impl Error for TemplateError {}
+
+
+/// # tokenize
+///
+/// **Purpose:**
+/// Splits source into text and tags, removing comments and the whitespace of lines that hold
+/// only a section tag or comment.
+///
+/// **Parameters:**
+/// - `source`: Template text.
+///
+/// **Returns:**
+/// - The tokens in source order.
+///
+/// **Errors / Failures:**
+/// - Returns a `TemplateError` for a tag without closing braces.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let tokens = tokenize("a {{b}} c")?;
+/// ```
+fn tokenize(source: &str) -> Result<Vec<Token<'_>>, TemplateError> {
+    let mut tokens = Vec::new();
+    let mut position = 0;
+    let mut line = 1;
+    while let Some(found) = source[position..].find("{{") {
+        let open = position + found;
+        let raw = source[open..].starts_with("{{{");
+        let (start, close) = if raw { (open + 3, "}}}") } else { (open + 2, "}}") };
+        let tag_line = line + source[position..open].matches('\n').count();
+        let end = source[start..].find(close).map(|offset| start + offset).ok_or_else(|| TemplateError {
+            line: tag_line,
+            message: "tag is not closed".to_string(),
+        })?;
+        let content = source[start..end].trim();
+        let mut text_end = open;
+        let mut next = end + close.len();
+
+        let standalone = !raw && (content.starts_with(['#', '/', '!']) || content == "else");
+        if standalone {
+            let line_start = source[..open].rfind('\n').map_or(0, |newline| newline + 1);
+            let line_end = source[next..].find('\n').map_or(source.len(), |newline| next + newline);
+            if line_start >= position
+                && source[line_start..open].trim().is_empty()
+                && source[next..line_end].trim().is_empty()
+            {
+                text_end = line_start;
+                next = (line_end + 1).min(source.len());
+            }
+        }
+
+        if text_end > position {
+            tokens.push(Token::Text(&source[position..text_end]));
+        }
+        if !content.starts_with('!') {
+            tokens.push(Token::Tag { content, raw, line: tag_line });
+        }
+        line += source[position..next].matches('\n').count();
+        position = next;
+    }
+    if position < source.len() {
+        tokens.push(Token::Text(&source[position..]));
+    }
+    Ok(tokens)
+}
+
+
+/// # parse_nodes
+///
+/// **Purpose:**
+/// Builds nodes from tokens until the end of the input or the closing tag of an open section.
+///
+/// **Parameters:**
+/// - `tokens`: Remaining tokens.
+/// - `open`: Name and line of the section being parsed, or `None` at the top level.
+///
+/// **Returns:**
+/// - The section body and its `{{else}}` branch (empty without one).
+///
+/// **Errors / Failures:**
+/// - Returns a `TemplateError` for unbalanced, unknown, or empty tags.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let (body, otherwise) = parse_nodes(tokens, Some(("each", 3)))?;
+/// ```
+fn parse_nodes<'a>(
+    tokens: &mut impl Iterator<Item = Token<'a>>,
+    open: Option<(&str, usize)>,
+) -> Result<(Vec<Node>, Vec<Node>), TemplateError> {
+    let mut body = Vec::new();
+    let mut otherwise = Vec::new();
+    let mut in_else = false;
+    while let Some(token) = tokens.next() {
+        let nodes = if in_else { &mut otherwise } else { &mut body };
+        let (content, raw, line) = match token {
+            Token::Text(text) => {
+                nodes.push(Node::Text(text.to_string()));
+                continue;
+            }
+            Token::Tag { content, raw, line } => (content, raw, line),
+        };
+        let error = |message: String| TemplateError { line, message };
+
+        if let Some(name) = content.strip_prefix('/') {
+            return match open {
+                Some((expected, _)) if expected == name.trim() => Ok((body, otherwise)),
+                Some((expected, _)) => Err(error(format!("expected {{{{/{expected}}}}}, found {{{{/{}}}}}", name.trim()))),
+                None => Err(error(format!("{{{{/{}}}}} has no opening section", name.trim()))),
+            };
+        }
+        if content == "else" && !raw {
+            if open.is_none() || in_else {
+                return Err(error("{{else}} outside a section".to_string()));
+            }
+            in_else = true;
+            continue;
+        }
+        if let Some(section) = content.strip_prefix('#') {
+            let (name, path) = section.split_once(char::is_whitespace).unwrap_or((section, ""));
+            let kind = match name {
+                "if" => Section::If,
+                "unless" => Section::Unless,
+                "each" => Section::Each,
+                _ => return Err(error(format!("unknown section {{{{#{name}}}}}"))),
+            };
+            if path.trim().is_empty() {
+                return Err(error(format!("{{{{#{name}}}}} needs a value")));
+            }
+            let (section_body, section_otherwise) = parse_nodes(tokens, Some((name, line)))?;
+            nodes.push(Node::Section {
+                kind,
+                path: path.trim().to_string(),
+                body: section_body,
+                otherwise: section_otherwise,
+            });
+            continue;
+        }
+
+        let (path, escape) = match content.strip_prefix('&') {
+            Some(path) => (path.trim(), false),
+            None => (content, !raw),
+        };
+        if path.is_empty() {
+            return Err(error("empty tag".to_string()));
+        }
+        nodes.push(Node::Value {
+            path: path.to_string(),
+            escape,
+        });
+    }
+    match open {
+        Some((name, line)) => Err(TemplateError {
+            line,
+            message: format!("{{{{#{name}}}}} is not closed"),
+        }),
+        None => Ok((body, otherwise)),
+    }
+}
+
+
+/// # render_nodes
+///
+/// **Purpose:**
+/// Appends the rendering of nodes to the output.
+///
+/// **Parameters:**
+/// - `nodes`: Nodes to render.
+/// - `scopes`: Lookup stack, innermost last.
+/// - `out`: Output text.
+///
+/// **Returns:**
+/// None.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// render_nodes(&self.nodes, &mut scopes, &mut out);
+/// ```
+fn render_nodes<'a>(nodes: &[Node], scopes: &mut Vec<Scope<'a>>, out: &mut String) {
+    for node in nodes {
+        match node {
+            Node::Text(text) => out.push_str(text),
+            Node::Value { path, escape } => {
+                let text = lookup(scopes, path).map(|value| display(&value)).unwrap_or_default();
+                if *escape {
+                    out.push_str(&escape_html(&text));
+                } else {
+                    out.push_str(&text);
+                }
+            }
+            Node::Section { kind, path, body, otherwise } => {
+                let value = lookup(scopes, path);
+                match kind {
+                    Section::Each => {
+                        let items: Vec<(Option<&'a str>, &'a JsonValue)> = match value {
+                            Some(Cow::Borrowed(JsonValue::Array(items))) => items.iter().map(|item| (None, item)).collect(),
+                            Some(Cow::Borrowed(JsonValue::Object(members))) => {
+                                members.iter().map(|(key, item)| (Some(key.as_str()), item)).collect()
+                            }
+                            _ => Vec::new(),
+                        };
+                        if items.is_empty() {
+                            render_nodes(otherwise, scopes, out);
+                        }
+                        for (index, (key, value)) in items.into_iter().enumerate() {
+                            scopes.push(Scope {
+                                value,
+                                index: Some(index),
+                                key,
+                            });
+                            render_nodes(body, scopes, out);
+                            scopes.pop();
+                        }
+                    }
+                    Section::If | Section::Unless => {
+                        let truthy = value.is_some_and(|value| is_truthy(&value));
+                        if truthy == (*kind == Section::If) {
+                            render_nodes(body, scopes, out);
+                        } else {
+                            render_nodes(otherwise, scopes, out);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+
+/// # lookup
+///
+/// **Purpose:**
+/// Resolves a path against the scopes, innermost first.
+///
+/// **Parameters:**
+/// - `scopes`: Lookup stack, innermost last.
+/// - `path`: `this`, `@index`, `@key`, or dot-separated member names and array indexes,
+///   optionally starting with `this.` to skip outer scopes.
+///
+/// **Returns:**
+/// - The value, or `None` if no scope has it.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let name = lookup(scopes, "user.name");
+/// ```
+fn lookup<'a>(scopes: &[Scope<'a>], path: &str) -> Option<Cow<'a, JsonValue>> {
+    let innermost = scopes.last()?;
+    match path {
+        "this" | "." => return Some(Cow::Borrowed(innermost.value)),
+        "@index" => return innermost.index.map(|index| Cow::Owned(JsonValue::Number(index as f64))),
+        "@key" => return innermost.key.map(|key| Cow::Owned(JsonValue::String(key.to_string()))),
+        _ => {}
+    }
+    if let Some(rest) = path.strip_prefix("this.") {
+        return follow(innermost.value, rest).map(Cow::Borrowed);
+    }
+    let (first, _) = path.split_once('.').unwrap_or((path, ""));
+    scopes
+        .iter()
+        .rev()
+        .find(|scope| member(scope.value, first).is_some())
+        .and_then(|scope| follow(scope.value, path))
+        .map(Cow::Borrowed)
+}
+
+
+/// # follow
+///
+/// **Purpose:**
+/// Walks a dotted path down from a value.
+///
+/// **Parameters:**
+/// - `value`: Starting value.
+/// - `path`: Dot-separated member names and array indexes.
+///
+/// **Returns:**
+/// - The value at the end of the path, or `None` if any step is missing.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let city = follow(&context, "address.city");
+/// ```
+fn follow<'a>(value: &'a JsonValue, path: &str) -> Option<&'a JsonValue> {
+    path.split('.').try_fold(value, member)
+}
+
+
+/// # member
+///
+/// **Purpose:**
+/// Takes one step of a path.
+///
+/// **Parameters:**
+/// - `value`: Object or array.
+/// - `name`: Member name, or index into an array.
+///
+/// **Returns:**
+/// - The member, or `None`.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let first = member(&items, "0");
+/// ```
+fn member<'a>(value: &'a JsonValue, name: &str) -> Option<&'a JsonValue> {
+    match value {
+        JsonValue::Array(items) => items.get(name.parse::<usize>().ok()?),
+        _ => value.get(name),
+    }
+}
+
+
+/// # is_truthy
+///
+/// **Purpose:**
+/// Decides whether a value counts as true in `if` and `unless`.
+///
+/// **Parameters:**
+/// - `value`: Value to test.
+///
+/// **Returns:**
+/// - `false` for `null`, `false`, `0`, empty strings, and empty arrays; `true` otherwise.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// assert!(!is_truthy(&JsonValue::String(String::new())));
+/// ```
+fn is_truthy(value: &JsonValue) -> bool {
+    match value {
+        JsonValue::Null => false,
+        JsonValue::Bool(flag) => *flag,
+        JsonValue::Number(number) => *number != 0.0,
+        JsonValue::String(text) => !text.is_empty(),
+        JsonValue::Array(items) => !items.is_empty(),
+        JsonValue::Object(_) => true,
+    }
+}
+
+
+/// # display
+///
+/// **Purpose:**
+/// Converts a value to the text substituted for it.
+///
+/// **Parameters:**
+/// - `value`: Value to show.
+///
+/// **Returns:**
+/// - Strings as they are, whole numbers without a fraction, booleans as `true`/`false`, and
+///   nothing for `null`, arrays, and objects.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// assert_eq!(display(&JsonValue::Number(3.0)), "3");
+/// ```
+fn display(value: &JsonValue) -> String {
+    match value {
+        JsonValue::String(text) => text.clone(),
+        JsonValue::Number(number) if number.fract() == 0.0 && number.abs() < 1e15 => format!("{}", *number as i64),
+        JsonValue::Number(number) => number.to_string(),
+        JsonValue::Bool(flag) => flag.to_string(),
+        JsonValue::Null | JsonValue::Array(_) | JsonValue::Object(_) => String::new(),
+    }
+}
+
+
+/// # escape_html
+///
+/// **Purpose:**
+/// Escapes text for use in HTML element content and quoted attribute values.
+///
+/// **Parameters:**
+/// - `text`: Text to escape.
+///
+/// **Returns:**
+/// - The text with `&`, `<`, `>`, `"`, and `'` replaced by character references.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// assert_eq!(escape_html("<b>"), "&lt;b&gt;");
+/// ```
+pub(crate) fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(source: &str, context: &str) -> String {
+        Template::parse(source).unwrap().render(&JsonValue::parse(context).unwrap())
+    }
+
+    #[test]
+    fn values_are_escaped_unless_raw() {
+        let context = r#"{"name": "<b>Ana</b> & co", "user": {"tags": ["x", "y"]}}"#;
+        assert_eq!(render("{{name}}", context), "&lt;b&gt;Ana&lt;/b&gt; &amp; co");
+        assert_eq!(render("{{{name}}}|{{&name}}", context), "<b>Ana</b> & co|<b>Ana</b> & co");
+        assert_eq!(render("{{user.tags.1}}[{{missing.path}}]", context), "y[]");
+    }
+
+    #[test]
+    fn conditionals_pick_a_branch() {
+        let source = "{{#if admin}}admin{{else}}user{{/if}}/{{#unless items}}empty{{/unless}}";
+        assert_eq!(render(source, r#"{"admin": true, "items": []}"#), "admin/empty");
+        assert_eq!(render(source, r#"{"admin": false, "items": [1]}"#), "user/");
+    }
+
+    #[test]
+    fn loops_expose_the_item_index_key_and_outer_names() {
+        let context = r#"{"sep": ";", "list": ["a", "b"], "map": {"k": 1}}"#;
+        assert_eq!(render("{{#each list}}{{@index}}={{this}}{{sep}}{{/each}}", context), "0=a;1=b;");
+        assert_eq!(render("{{#each map}}{{@key}}:{{this}}{{/each}}", context), "k:1");
+        assert_eq!(render("{{#each none}}x{{else}}nothing{{/each}}", context), "nothing");
+    }
+
+    #[test]
+    fn comments_and_standalone_section_lines_leave_nothing_behind() {
+        let source = "<ul>\n{{! entries }}\n{{#each list}}\n<li>{{this}}</li>\n{{/each}}\n</ul>\n";
+        assert_eq!(render(source, r#"{"list": ["a"]}"#), "<ul>\n<li>a</li>\n</ul>\n");
+    }
+
+    #[test]
+    fn unbalanced_sections_report_their_line() {
+        let error = Template::parse("line one\n{{#if x}}\nnever closed").unwrap_err();
+        assert_eq!(error.line, 2);
+        assert!(Template::parse("{{/each}}").is_err());
+    }
+
+    #[test]
+    fn unknown_template_names_are_errors() {
+        let templates = Templates::new().add("page", Template::parse("hi {{name}}").unwrap());
+        let context = JsonValue::parse(r#"{"name": "ana"}"#).unwrap();
+        assert_eq!(TemplateEngine::render(&templates, "page", &context).unwrap(), "hi ana");
+        assert!(TemplateEngine::render(&templates, "other", &context).is_err());
+    }
+}
@@ -0,0 +1,266 @@
+
+// ============================================================
+//  DAEGONICA SOFTWARE — throttle.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: Throttle
+//!
+//! **Purpose:**
+//! Caps how fast response bytes are sent on each connection.
+//!
+//! **Context:**
+//! - Set with `Server::throttle`; the connection handler picks the rate for each request's
+//!   path and the client stream paces its writes to it.
+//!
+//! **Responsibilities:**
+//! - Holds a default per-connection rate and per-route overrides.
+//! - Paces writes by sending small slices and sleeping whenever the connection gets ahead of
+//!   its rate, for simulating slow links or keeping one download from saturating the uplink.
+//! - Does NOT pace proxied responses, which are streamed straight to the socket, or share one
+//!   budget between connections.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+use std::{
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::glob::Glob;
+
+
+/// # SLICES_PER_SECOND
+///
+/// **Summary:**
+/// How many slices a second's worth of bytes is split into, so pacing stays smooth.
+const SLICES_PER_SECOND: u64 = 10;
+
+
+/// # Throttle
+///
+/// **Summary:**
+/// Response bandwidth caps, in bytes per second per connection. Throttled transfers still
+/// count against the request timeout, which may need raising for large downloads.
+///
+/// **Fields:**
+/// - `rate`: Default cap, or `None` for unthrottled.
+/// - `overrides`: Route patterns with the cap to use instead, checked in order.
+///
+/// **Usage Example:**
+/// ```rust
+/// # use server::{server::Server, throttle::Throttle};
+/// let throttle = Throttle::new(1024 * 1024).route("/downloads/**", 64 * 1024);
+/// let server = Server::new("127.0.0.1:7878").throttle(throttle);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Throttle {
+    rate: Option<u64>,
+    overrides: Vec<(Glob, Option<u64>)>,
+}
+
+
+/// # Pacer
+///
+/// **Summary:**
+/// Write pacing state for one response.
+///
+/// **Fields:**
+/// - `rate`: Bytes per second allowed.
+/// - `started`: When the response began to be written.
+/// - `sent`: Bytes written since `started`.
+#[derive(Debug)]
+pub(crate) struct Pacer {
+    rate: u64,
+    started: Instant,
+    sent: u64,
+}
+
+
+impl Throttle {
+
+    /// # new
+    ///
+    /// **Purpose:**
+    /// Caps every connection at a rate.
+    ///
+    /// **Parameters:**
+    /// - `bytes_per_second`: Response bytes each connection may send per second; `0` means unthrottled.
+    ///
+    /// **Returns:**
+    /// - A new `Throttle`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::throttle::Throttle;
+    /// let throttle = Throttle::new(56_000 / 8);
+    /// ```
+    pub fn new(bytes_per_second: u64) -> Throttle {
+        Throttle {
+            rate: (bytes_per_second > 0).then_some(bytes_per_second),
+            overrides: Vec::new(),
+        }
+    }
+
+    /// # route
+    ///
+    /// **Purpose:**
+    /// Uses a different cap for request paths matching a pattern. The first matching override wins.
+    ///
+    /// **Parameters:**
+    /// - `pattern`: Path glob (e.g. `/downloads/**`).
+    /// - `bytes_per_second`: Cap on those paths; `0` leaves them unthrottled.
+    ///
+    /// **Returns:**
+    /// - The updated `Throttle`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::throttle::Throttle;
+    /// let throttle = Throttle::default().route("/videos/**", 256 * 1024);
+    /// ```
+    pub fn route(mut self, pattern: &str, bytes_per_second: u64) -> Throttle {
+        self.overrides.push((Glob::new(pattern), (bytes_per_second > 0).then_some(bytes_per_second)));
+        self
+    }
+
+    /// # rate_for
+    ///
+    /// **Purpose:**
+    /// Picks the cap for a request path.
+    ///
+    /// **Parameters:**
+    /// - `path`: Request path.
+    ///
+    /// **Returns:**
+    /// - The first matching override's cap, or the default, in bytes per second (`None` when unthrottled).
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// client.throttle(throttle.rate_for(request.path()));
+    /// ```
+    pub(crate) fn rate_for(&self, path: &str) -> Option<u64> {
+        self.overrides
+            .iter()
+            .find(|(pattern, _)| pattern.matches(path))
+            .map_or(self.rate, |(_, rate)| *rate)
+    }
+}
+
+
+impl Pacer {
+
+    /// # new
+    ///
+    /// **Purpose:**
+    /// Starts pacing a response.
+    ///
+    /// **Parameters:**
+    /// - `rate`: Bytes per second allowed (non-zero).
+    ///
+    /// **Returns:**
+    /// - A `Pacer` whose schedule starts now.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let pacer = Pacer::new(64 * 1024);
+    /// ```
+    pub(crate) fn new(rate: u64) -> Pacer {
+        Pacer {
+            rate: rate.max(1),
+            started: Instant::now(),
+            sent: 0,
+        }
+    }
+
+    /// # slice
+    ///
+    /// **Purpose:**
+    /// Returns the most bytes one write may send.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - A tenth of a second's worth of bytes (at least 1).
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let buf = &buf[..buf.len().min(pacer.slice())];
+    /// ```
+    pub(crate) fn slice(&self) -> usize {
+        (self.rate / SLICES_PER_SECOND).max(1) as usize
+    }
+
+    /// # record
+    ///
+    /// **Purpose:**
+    /// Counts written bytes and sleeps until the schedule catches up with them.
+    ///
+    /// **Parameters:**
+    /// - `written`: Bytes just written.
+    ///
+    /// **Returns:**
+    /// None.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// pacer.record(written);
+    /// ```
+    pub(crate) fn record(&mut self, written: usize) {
+        self.sent += written as u64;
+        let due = Duration::from_secs_f64(self.sent as f64 / self.rate as f64);
+        let elapsed = self.started.elapsed();
+        if due > elapsed {
+            thread::sleep(due - elapsed);
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn route_overrides_pick_the_rate() {
+        let throttle = Throttle::new(1000).route("/downloads/**", 10);
+        assert_eq!(throttle.rate_for("/downloads/big.iso"), Some(10));
+        assert_eq!(throttle.rate_for("/"), Some(1000));
+        assert_eq!(Throttle::default().rate_for("/"), None);
+    }
+
+    #[test]
+    fn pacers_hold_writes_to_the_rate() {
+        let mut pacer = Pacer::new(1000);
+        assert_eq!(pacer.slice(), 100);
+        let started = Instant::now();
+        pacer.record(200);
+        assert!(started.elapsed() >= Duration::from_millis(190));
+        assert_eq!(Pacer::new(5).slice(), 1);
+    }
+}
@@ -0,0 +1,1363 @@
+
+// ============================================================
+//  DAEGONICA SOFTWARE — timeouts.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: Timeouts
+//!
+//! **Purpose:**
+//! Bounds how long a client connection may hold a worker.
+//!
+//! **Context:**
+//! - Configured with `Server::timeouts` and applied to every client connection.
+//!
+//! **Responsibilities:**
+//! - Sets socket read and write timeouts so a silent peer cannot block a worker indefinitely.
+//! - Enforces a deadline for receiving the request head and an overall deadline for the exchange.
+//! - Requires a minimum data rate while the head arrives, so slowloris clients that trickle
+//!   bytes are dropped long before the header deadline.
+//! - Re-arms the socket timeouts before every read and write, so trickling bytes does not
+//!   extend either deadline.
+//! - Bounds the wait for the next request on a persistent connection, restarting the
+//!   per-request deadlines once it begins to arrive.
+//! - Paces response writes when the server throttles the connection, including file bodies
+//!   sent with `sendfile(2)`.
+//! - Counts the bytes read and written when metered.
+//! - Hands out bytes already received for the connection before reading the socket again.
+//! - Routes reads, writes, and file sends through the server's `io_uring` when built with the
+//!   `io_uring` feature and enabled, falling back to blocking calls once the ring stops.
+//! - Sends a deferred `100 Continue` just before the first body read, when the client asked for one.
+//! - Does NOT bound proxied tunnels (`CONNECT`, WebSocket), which replace these limits with
+//!   their own idle timeout.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+use std::{
+    fs::File,
+    io::{self, IoSlice, Read, Write},
+    mem,
+    net::TcpStream,
+    time::{Duration, Instant},
+};
+
+#[cfg(all(feature = "io_uring", target_os = "linux"))]
+use crate::uring::RingQueue;
+use crate::{metrics::Metrics, sendfile, throttle::Pacer};
+
+
+/// # HEADER_RATE_GRACE
+///
+/// **Summary:**
+/// Time a connection gets before the minimum header rate is enforced.
+pub const HEADER_RATE_GRACE: Duration = Duration::from_secs(5);
+
+
+/// # SENDFILE_BATCH
+///
+/// **Summary:**
+/// Most bytes handed to one `sendfile(2)` call, so the write timeout is re-armed regularly.
+const SENDFILE_BATCH: usize = 1024 * 1024;
+
+
+/// # Timeouts
+///
+/// **Summary:**
+/// Connection time limits; `None` fields are not enforced.
+///
+/// **Fields:**
+/// - `read`: Longest wait for any single read.
+/// - `write`: Longest wait for any single write.
+/// - `header`: Time allowed, from the start of the connection, to receive the request head.
+/// - `header_rate`: Minimum average bytes per second while receiving the head.
+/// - `request`: Time allowed for the whole exchange, from the start of the connection until
+///   the response has been written (handler time included).
+///
+/// **Usage Example:**
+/// ```rust,no_run
+/// # use server::{server::Server, timeouts::Timeouts};
+/// # use std::time::Duration;
+/// let timeouts = Timeouts::new().header(Duration::from_secs(5)).request(Duration::from_secs(30));
+/// Server::new("127.0.0.1:7878").timeouts(timeouts).run()?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timeouts {
+    read: Option<Duration>,
+    write: Option<Duration>,
+    header: Option<Duration>,
+    header_rate: Option<u32>,
+    request: Option<Duration>,
+}
+
+
+/// # ClientStream
+///
+/// **Summary:**
+/// Client connection that applies `Timeouts` to every read and write. The server wraps each
+/// accepted socket in one; the proxies receive it to relay the rest of the exchange.
+///
+/// **Fields:**
+/// - `stream`: Underlying socket.
+/// - `timeouts`: Limits to enforce.
+/// - `started`: When the connection began being served.
+/// - `head_read`: Whether the request head has been received, ending the header limits.
+/// - `head_bytes`: Bytes received while reading the head.
+/// - `continue_pending`: Whether a `100 Continue` is owed before the next read.
+/// - `idle`: Keep-alive wait for the next request, while none of it has arrived.
+/// - `pacer`: Bandwidth pacing for the current response, if throttled.
+/// - `metrics`: Counters for the bytes read and written, if metered.
+/// - `preloaded`: Bytes of the request received before the worker took over, read first.
+/// - `overdue`: Whether the head's deadline passed before the worker took over, so reading
+///   past the preloaded bytes times out.
+/// - `ring`: Queue of the `io_uring` serving the connection, if any (`io_uring` feature).
+#[derive(Debug)]
+pub struct ClientStream<'a> {
+    stream: &'a TcpStream,
+    timeouts: Timeouts,
+    started: Instant,
+    head_read: bool,
+    head_bytes: usize,
+    continue_pending: bool,
+    idle: Option<Duration>,
+    pacer: Option<Pacer>,
+    metrics: Option<Metrics>,
+    preloaded: Vec<u8>,
+    overdue: bool,
+    #[cfg(all(feature = "io_uring", target_os = "linux"))]
+    ring: Option<&'a RingQueue>,
+}
+
+
+/// # DeadlineReader
+///
+/// **Summary:**
+/// Reader over a side-channel socket (admin API, metrics scrapes) that must finish within a
+/// fixed time, however slowly the peer trickles bytes.
+///
+/// **Fields:**
+/// - `socket`: Underlying socket.
+/// - `deadline`: When reading gives up.
+#[derive(Debug)]
+pub(crate) struct DeadlineReader<'a, S> {
+    socket: &'a S,
+    deadline: Instant,
+}
+
+
+/// # TimedSocket
+///
+/// **Summary:**
+/// Socket whose read timeout can be re-armed, so `DeadlineReader` works over both TCP and
+/// Unix domain sockets.
+pub(crate) trait TimedSocket {
+
+    /// # set_read_timeout
+    ///
+    /// **Purpose:**
+    /// Bounds how long the next read may wait.
+    ///
+    /// **Parameters:**
+    /// - `timeout`: Longest wait for the next read, or `None` for no limit.
+    ///
+    /// **Returns:**
+    /// - `Ok(())` once the timeout is set.
+    ///
+    /// **Errors / Failures:**
+    /// - Passes through the socket error, e.g. `InvalidInput` for a zero timeout.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// self.socket.set_read_timeout(Some(remaining))?;
+    /// ```
+    fn set_read_timeout(&self, timeout: Option<Duration>) -> io::Result<()>;
+}
+
+
+impl TimedSocket for TcpStream {
+
+    /// # set_read_timeout
+    ///
+    /// **Purpose:**
+    /// Sets the TCP socket read timeout.
+    ///
+    /// **Parameters:**
+    /// - `timeout`: Longest wait for the next read, or `None` for no limit.
+    ///
+    /// **Returns:**
+    /// - `Ok(())` once the timeout is set.
+    ///
+    /// **Errors / Failures:**
+    /// - Passes through the socket error, e.g. `InvalidInput` for a zero timeout.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// self.socket.set_read_timeout(Some(remaining))?;
+    /// ```
+    fn set_read_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        TcpStream::set_read_timeout(self, timeout)
+    }
+}
+
+
+#[cfg(unix)]
+impl TimedSocket for std::os::unix::net::UnixStream {
+
+    /// # set_read_timeout
+    ///
+    /// **Purpose:**
+    /// Sets the Unix socket read timeout.
+    ///
+    /// **Parameters:**
+    /// - `timeout`: Longest wait for the next read, or `None` for no limit.
+    ///
+    /// **Returns:**
+    /// - `Ok(())` once the timeout is set.
+    ///
+    /// **Errors / Failures:**
+    /// - Passes through the socket error, e.g. `InvalidInput` for a zero timeout.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// self.socket.set_read_timeout(Some(remaining))?;
+    /// ```
+    fn set_read_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        std::os::unix::net::UnixStream::set_read_timeout(self, timeout)
+    }
+}
+
+
+impl Default for Timeouts {
+
+    /// # default
+    ///
+    /// **Purpose:**
+    /// Creates the default limits: 30 second reads and writes, 20 seconds and at least 100 bytes
+    /// per second for the request head, and two minutes for the exchange.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - The default `Timeouts`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::timeouts::Timeouts;
+    /// let timeouts = Timeouts::default();
+    /// ```
+    fn default() -> Timeouts {
+        Timeouts {
+            read: Some(Duration::from_secs(30)),
+            write: Some(Duration::from_secs(30)),
+            header: Some(Duration::from_secs(20)),
+            header_rate: Some(100),
+            request: Some(Duration::from_secs(120)),
+        }
+    }
+}
+
+
+impl Timeouts {
+
+    /// # new
+    ///
+    /// **Purpose:**
+    /// Creates the default limits.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - A new `Timeouts`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::timeouts::Timeouts;
+    /// let timeouts = Timeouts::new();
+    /// ```
+    pub fn new() -> Timeouts {
+        Timeouts::default()
+    }
+
+    /// # none
+    ///
+    /// **Purpose:**
+    /// Creates limits that enforce nothing, for callers that set their own.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - A `Timeouts` with every limit off.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::timeouts::Timeouts;
+    /// # use std::time::Duration;
+    /// let timeouts = Timeouts::none().header(Duration::from_secs(10));
+    /// ```
+    pub fn none() -> Timeouts {
+        Timeouts {
+            read: None,
+            write: None,
+            header: None,
+            header_rate: None,
+            request: None,
+        }
+    }
+
+    /// # read
+    ///
+    /// **Purpose:**
+    /// Sets the longest wait for any single read from the client.
+    ///
+    /// **Parameters:**
+    /// - `timeout`: Read timeout; must be non-zero.
+    ///
+    /// **Returns:**
+    /// - The updated `Timeouts`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::timeouts::Timeouts;
+    /// # use std::time::Duration;
+    /// let timeouts = Timeouts::new().read(Duration::from_secs(10));
+    /// ```
+    pub fn read(mut self, timeout: Duration) -> Timeouts {
+        self.read = Some(timeout);
+        self
+    }
+
+    /// # write
+    ///
+    /// **Purpose:**
+    /// Sets the longest wait for any single write to the client.
+    ///
+    /// **Parameters:**
+    /// - `timeout`: Write timeout; must be non-zero.
+    ///
+    /// **Returns:**
+    /// - The updated `Timeouts`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::timeouts::Timeouts;
+    /// # use std::time::Duration;
+    /// let timeouts = Timeouts::new().write(Duration::from_secs(10));
+    /// ```
+    pub fn write(mut self, timeout: Duration) -> Timeouts {
+        self.write = Some(timeout);
+        self
+    }
+
+    /// # header
+    ///
+    /// **Purpose:**
+    /// Sets the time allowed to receive the request line and headers.
+    ///
+    /// **Parameters:**
+    /// - `timeout`: Header deadline, measured from the start of the connection.
+    ///
+    /// **Returns:**
+    /// - The updated `Timeouts`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::timeouts::Timeouts;
+    /// # use std::time::Duration;
+    /// let timeouts = Timeouts::new().header(Duration::from_secs(5));
+    /// ```
+    pub fn header(mut self, timeout: Duration) -> Timeouts {
+        self.header = Some(timeout);
+        self
+    }
+
+    /// # min_header_rate
+    ///
+    /// **Purpose:**
+    /// Sets the minimum average rate at which the request head must arrive. Each byte received
+    /// buys the client `1 / rate` seconds, and at least `HEADER_RATE_GRACE` is always allowed;
+    /// the header deadline still caps the total.
+    ///
+    /// **Parameters:**
+    /// - `bytes_per_second`: Minimum rate; must be non-zero.
+    ///
+    /// **Returns:**
+    /// - The updated `Timeouts`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::timeouts::Timeouts;
+    /// let timeouts = Timeouts::new().min_header_rate(500);
+    /// ```
+    pub fn min_header_rate(mut self, bytes_per_second: u32) -> Timeouts {
+        self.header_rate = Some(bytes_per_second.max(1));
+        self
+    }
+
+    /// # request
+    ///
+    /// **Purpose:**
+    /// Sets the time allowed for the whole exchange, including the handler.
+    ///
+    /// **Parameters:**
+    /// - `timeout`: Overall deadline, measured from the start of the connection.
+    ///
+    /// **Returns:**
+    /// - The updated `Timeouts`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::timeouts::Timeouts;
+    /// # use std::time::Duration;
+    /// let timeouts = Timeouts::new().request(Duration::from_secs(30));
+    /// ```
+    pub fn request(mut self, timeout: Duration) -> Timeouts {
+        self.request = Some(timeout);
+        self
+    }
+
+    /// # apply
+    ///
+    /// **Purpose:**
+    /// Sets the read and write limits on a socket the server opened itself, such as an upstream
+    /// connection, where the header and exchange deadlines do not apply.
+    ///
+    /// **Parameters:**
+    /// - `socket`: Socket to bound.
+    ///
+    /// **Returns:**
+    /// - `Ok(())` once both timeouts are set.
+    ///
+    /// **Errors / Failures:**
+    /// - Passes through the socket error.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// self.timeouts.apply(&upstream)?;
+    /// ```
+    pub(crate) fn apply(&self, socket: &TcpStream) -> io::Result<()> {
+        socket.set_read_timeout(self.read)?;
+        socket.set_write_timeout(self.write)
+    }
+
+    /// # head_wait
+    ///
+    /// **Purpose:**
+    /// Works out how long a request head may take to arrive while it waits in the `io_uring`,
+    /// before any worker applies the finer limits: the header limit, or else the read timeout.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - The wait limit, or `None` for no limit.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// ring.park(stream, keep_alive.idle_timeout(), timeouts.head_wait(), state)?;
+    /// ```
+    #[cfg(all(feature = "io_uring", target_os = "linux"))]
+    pub(crate) fn head_wait(&self) -> Option<Duration> {
+        self.header.or(self.read)
+    }
+}
+
+
+impl<'a> ClientStream<'a> {
+
+    /// # new
+    ///
+    /// **Purpose:**
+    /// Starts timing a client connection.
+    ///
+    /// **Parameters:**
+    /// - `stream`: Client socket.
+    /// - `timeouts`: Limits to enforce.
+    ///
+    /// **Returns:**
+    /// - A new `ClientStream` whose deadlines run from now.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::timeouts::{ClientStream, Timeouts};
+    /// # use std::net::{TcpListener, TcpStream};
+    /// # let listener = TcpListener::bind("127.0.0.1:0")?;
+    /// # let peer = TcpStream::connect(listener.local_addr()?)?;
+    /// # let (stream, _) = listener.accept()?;
+    /// let client = ClientStream::new(&stream, Timeouts::new());
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn new(stream: &'a TcpStream, timeouts: Timeouts) -> ClientStream<'a> {
+        ClientStream {
+            stream,
+            timeouts,
+            started: Instant::now(),
+            head_read: false,
+            head_bytes: 0,
+            continue_pending: false,
+            idle: None,
+            pacer: None,
+            metrics: None,
+            preloaded: Vec::new(),
+            overdue: false,
+            #[cfg(all(feature = "io_uring", target_os = "linux"))]
+            ring: None,
+        }
+    }
+
+    /// # socket
+    ///
+    /// **Purpose:**
+    /// Returns the underlying socket, e.g. to clone it for a tunnel.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - The client `TcpStream`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::timeouts::{ClientStream, Timeouts};
+    /// # use std::net::{TcpListener, TcpStream};
+    /// # let listener = TcpListener::bind("127.0.0.1:0")?;
+    /// # let peer = TcpStream::connect(listener.local_addr()?)?;
+    /// # let (stream, _) = listener.accept()?;
+    /// # let mut client = ClientStream::new(&stream, Timeouts::new());
+    /// let socket = client.socket().try_clone()?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn socket(&self) -> &'a TcpStream {
+        self.stream
+    }
+
+    /// # head_read
+    ///
+    /// **Purpose:**
+    /// Marks the request head as received, ending the header deadline, rate limit, and any
+    /// keep-alive idle wait.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// None.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::timeouts::{ClientStream, Timeouts};
+    /// # use std::net::{TcpListener, TcpStream};
+    /// # let listener = TcpListener::bind("127.0.0.1:0")?;
+    /// # let peer = TcpStream::connect(listener.local_addr()?)?;
+    /// # let (stream, _) = listener.accept()?;
+    /// # let mut reader = std::io::BufReader::new(ClientStream::new(&stream, Timeouts::new()));
+    /// reader.get_mut().head_read();
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn head_read(&mut self) {
+        self.head_read = true;
+        self.idle = None;
+    }
+
+    /// # next_request
+    ///
+    /// **Purpose:**
+    /// Prepares a persistent connection for its next request: the wait for the first byte is
+    /// bounded by `idle`, and the header and request deadlines start over once it arrives.
+    ///
+    /// **Parameters:**
+    /// - `idle`: Keep-alive idle timeout.
+    ///
+    /// **Returns:**
+    /// None.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use std::time::Duration;
+    /// # use server::timeouts::{ClientStream, Timeouts};
+    /// # use std::net::{TcpListener, TcpStream};
+    /// # let listener = TcpListener::bind("127.0.0.1:0")?;
+    /// # let peer = TcpStream::connect(listener.local_addr()?)?;
+    /// # let (stream, _) = listener.accept()?;
+    /// # let mut reader = std::io::BufReader::new(ClientStream::new(&stream, Timeouts::new()));
+    /// reader.get_mut().next_request(Duration::from_secs(5));
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn next_request(&mut self, idle: Duration) {
+        self.started = Instant::now();
+        self.head_read = false;
+        self.head_bytes = 0;
+        self.continue_pending = false;
+        self.idle = Some(idle);
+    }
+
+    /// # awaiting
+    ///
+    /// **Purpose:**
+    /// Tells whether a persistent connection is still idle, with no byte of its next request
+    /// read from the socket.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - `true` while waiting for the next request.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::timeouts::{ClientStream, Timeouts};
+    /// # use std::net::{TcpListener, TcpStream};
+    /// # let listener = TcpListener::bind("127.0.0.1:0")?;
+    /// # let peer = TcpStream::connect(listener.local_addr()?)?;
+    /// # let (stream, _) = listener.accept()?;
+    /// # let mut reader = std::io::BufReader::new(ClientStream::new(&stream, Timeouts::new()));
+    /// if reader.get_ref().awaiting() { /* close quietly */ }
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn awaiting(&self) -> bool {
+        self.idle.is_some()
+    }
+
+    /// # throttle
+    ///
+    /// **Purpose:**
+    /// Paces the writes of the next response to a bandwidth cap, or lifts the cap.
+    ///
+    /// **Parameters:**
+    /// - `rate`: Bytes per second, or `None` for unthrottled.
+    ///
+    /// **Returns:**
+    /// None.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::timeouts::{ClientStream, Timeouts};
+    /// # use std::net::{TcpListener, TcpStream};
+    /// # let listener = TcpListener::bind("127.0.0.1:0")?;
+    /// # let peer = TcpStream::connect(listener.local_addr()?)?;
+    /// # let (stream, _) = listener.accept()?;
+    /// # let mut reader = std::io::BufReader::new(ClientStream::new(&stream, Timeouts::new()));
+    /// reader.get_mut().throttle(Some(64 * 1024));
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn throttle(&mut self, rate: Option<u64>) {
+        self.pacer = rate.map(Pacer::new);
+    }
+
+    /// # meter
+    ///
+    /// **Purpose:**
+    /// Counts the bytes read from and written to the client in server metrics.
+    ///
+    /// **Parameters:**
+    /// - `metrics`: Counters to add to.
+    ///
+    /// **Returns:**
+    /// None.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::metrics::Metrics;
+    /// # let metrics = Metrics::new();
+    /// # use server::timeouts::{ClientStream, Timeouts};
+    /// # use std::net::{TcpListener, TcpStream};
+    /// # let listener = TcpListener::bind("127.0.0.1:0")?;
+    /// # let peer = TcpStream::connect(listener.local_addr()?)?;
+    /// # let (stream, _) = listener.accept()?;
+    /// # let mut client = ClientStream::new(&stream, Timeouts::new());
+    /// client.meter(metrics.clone());
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn meter(&mut self, metrics: Metrics) {
+        self.metrics = Some(metrics);
+    }
+
+    /// # expect_continue
+    ///
+    /// **Purpose:**
+    /// Arms or disarms the interim `100 Continue` response. While armed, the next read first
+    /// tells the client to send its body, so a refusal written before any body read goes out
+    /// without one.
+    ///
+    /// **Parameters:**
+    /// - `expected`: Whether the client is waiting for `100 Continue`.
+    ///
+    /// **Returns:**
+    /// None.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::timeouts::{ClientStream, Timeouts};
+    /// # use std::net::{TcpListener, TcpStream};
+    /// # let listener = TcpListener::bind("127.0.0.1:0")?;
+    /// # let peer = TcpStream::connect(listener.local_addr()?)?;
+    /// # let (stream, _) = listener.accept()?;
+    /// # let mut reader = std::io::BufReader::new(ClientStream::new(&stream, Timeouts::new()));
+    /// reader.get_mut().expect_continue(true);
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn expect_continue(&mut self, expected: bool) {
+        self.continue_pending = expected;
+    }
+
+    /// # preload
+    ///
+    /// **Purpose:**
+    /// Hands over bytes already received from the client, which reads return before the
+    /// socket is read again.
+    ///
+    /// **Parameters:**
+    /// - `bytes`: Bytes received, in order.
+    /// - `overdue`: Whether the head's deadline has already passed; the first read after the
+    ///   bytes then fails with `TimedOut`.
+    ///
+    /// **Returns:**
+    /// None.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// client.preload(b"GET / HTTP/1.1\r\nHost: a\r\n\r\n".to_vec(), false);
+    /// ```
+    #[cfg(all(feature = "io_uring", target_os = "linux"))]
+    pub(crate) fn preload(&mut self, bytes: Vec<u8>, overdue: bool) {
+        self.preloaded = bytes;
+        self.overdue = overdue;
+    }
+
+    /// # preloaded
+    ///
+    /// **Purpose:**
+    /// Tells whether bytes handed over with `preload` are still unread.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - `true` while preloaded bytes remain.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let buffered = !reader.buffer().is_empty() || reader.get_ref().preloaded();
+    /// ```
+    pub(crate) fn preloaded(&self) -> bool {
+        !self.preloaded.is_empty()
+    }
+
+    /// # io_uring
+    ///
+    /// **Purpose:**
+    /// Sends this connection's reads, writes, and file sends through an `io_uring`.
+    ///
+    /// **Parameters:**
+    /// - `ring`: Queue of the ring; calls fall back to blocking I/O once it has stopped.
+    ///
+    /// **Returns:**
+    /// None.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// client.io_uring(ring.queue());
+    /// ```
+    #[cfg(all(feature = "io_uring", target_os = "linux"))]
+    pub(crate) fn io_uring(&mut self, ring: &'a RingQueue) {
+        self.ring = Some(ring);
+    }
+
+    /// # send_file
+    ///
+    /// **Purpose:**
+    /// Sends part of a file to the client with `sendfile(2)`, or spliced through the ring when
+    /// the connection has one, under the same timeouts, pacing, and metering as `write`.
+    ///
+    /// **Parameters:**
+    /// - `file`: Source file.
+    /// - `offset`: Position in the file of the first byte to send.
+    /// - `len`: Number of bytes to send.
+    ///
+    /// **Returns:**
+    /// - The number of bytes sent. Fewer than `len` only when zero-copy is unavailable for this
+    ///   file or platform; the caller writes the rest itself.
+    ///
+    /// **Errors / Failures:**
+    /// - `TimedOut` if a wait runs out; other socket and file errors are passed through.
+    /// - `UnexpectedEof` if the file ends before `len` bytes.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let sent = client.send_file(&file, 0, len)?;
+    /// ```
+    pub(crate) fn send_file(&mut self, file: &File, offset: u64, len: u64) -> io::Result<u64> {
+        let mut sent = 0;
+        while sent < len {
+            let mut batch = usize::try_from(len - sent).unwrap_or(usize::MAX).min(SENDFILE_BATCH);
+            if let Some(pacer) = &self.pacer {
+                batch = batch.min(pacer.slice());
+            }
+            let wait = self.budget(self.timeouts.write, false)?;
+            let written = match self.send_file_batch(file, offset + sent, batch, wait) {
+                Ok(0) => return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "file ended early")),
+                Ok(written) => written,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(err) if err.kind() == io::ErrorKind::Unsupported => break,
+                Err(err) => return Err(timed_out(err)),
+            };
+            if let Some(pacer) = &mut self.pacer {
+                pacer.record(written);
+            }
+            if let Some(metrics) = &self.metrics {
+                metrics.record_sent(written);
+            }
+            sent += written as u64;
+        }
+        Ok(sent)
+    }
+
+    /// # recv
+    ///
+    /// **Purpose:**
+    /// Reads from the socket once, through the ring when the connection has one.
+    ///
+    /// **Parameters:**
+    /// - `buf`: Destination buffer.
+    /// - `wait`: Time limit, or `None` for no limit.
+    ///
+    /// **Returns:**
+    /// - The number of bytes read (`0` at end of stream).
+    ///
+    /// **Errors / Failures:**
+    /// - `WouldBlock` if the wait runs out; other socket errors are passed through.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let read = self.recv(buf, wait).map_err(timed_out)?;
+    /// ```
+    fn recv(&self, buf: &mut [u8], wait: Option<Duration>) -> io::Result<usize> {
+        #[cfg(all(feature = "io_uring", target_os = "linux"))]
+        if let Some(ring) = self.ring {
+            match ring.recv(self.stream, buf, wait) {
+                Err(error) if error.kind() == io::ErrorKind::Unsupported => {}
+                result => return result,
+            }
+        }
+        self.stream.set_read_timeout(wait)?;
+        let mut stream = self.stream;
+        stream.read(buf)
+    }
+
+    /// # send
+    ///
+    /// **Purpose:**
+    /// Writes to the socket once, through the ring when the connection has one.
+    ///
+    /// **Parameters:**
+    /// - `buf`: Bytes to send.
+    /// - `wait`: Time limit, or `None` for no limit.
+    ///
+    /// **Returns:**
+    /// - The number of bytes written.
+    ///
+    /// **Errors / Failures:**
+    /// - `WouldBlock` if the wait runs out; other socket errors are passed through.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let written = self.send(buf, wait).map_err(timed_out)?;
+    /// ```
+    fn send(&self, buf: &[u8], wait: Option<Duration>) -> io::Result<usize> {
+        #[cfg(all(feature = "io_uring", target_os = "linux"))]
+        if let Some(ring) = self.ring {
+            match ring.send(self.stream, buf, wait) {
+                Err(error) if error.kind() == io::ErrorKind::Unsupported => {}
+                result => return result,
+            }
+        }
+        self.stream.set_write_timeout(wait)?;
+        let mut stream = self.stream;
+        stream.write(buf)
+    }
+
+    /// # send_vectored
+    ///
+    /// **Purpose:**
+    /// Writes several buffers to the socket once, through the ring when the connection has one.
+    ///
+    /// **Parameters:**
+    /// - `bufs`: Buffers to send, in order.
+    /// - `wait`: Time limit, or `None` for no limit.
+    ///
+    /// **Returns:**
+    /// - The number of bytes written, across the buffers.
+    ///
+    /// **Errors / Failures:**
+    /// - `WouldBlock` if the wait runs out; other socket errors are passed through.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let written = self.send_vectored(bufs, wait).map_err(timed_out)?;
+    /// ```
+    fn send_vectored(&self, bufs: &[IoSlice<'_>], wait: Option<Duration>) -> io::Result<usize> {
+        #[cfg(all(feature = "io_uring", target_os = "linux"))]
+        if let Some(ring) = self.ring {
+            match ring.send_vectored(self.stream, bufs, wait) {
+                Err(error) if error.kind() == io::ErrorKind::Unsupported => {}
+                result => return result,
+            }
+        }
+        self.stream.set_write_timeout(wait)?;
+        let mut stream = self.stream;
+        stream.write_vectored(bufs)
+    }
+
+    /// # send_file_batch
+    ///
+    /// **Purpose:**
+    /// Sends one batch of a file without copying it through userspace, spliced through the
+    /// ring when the connection has one and with `sendfile(2)` otherwise.
+    ///
+    /// **Parameters:**
+    /// - `file`: Source file.
+    /// - `offset`: Position in the file of the first byte to send.
+    /// - `batch`: Largest number of bytes to send.
+    /// - `wait`: Time limit, or `None` for no limit.
+    ///
+    /// **Returns:**
+    /// - The number of bytes sent; `0` once `offset` reaches the end of the file.
+    ///
+    /// **Errors / Failures:**
+    /// - `Unsupported` if neither way works for this file or platform.
+    /// - `WouldBlock` if the wait runs out; other socket and file errors are passed through.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let written = self.send_file_batch(file, offset, batch, wait)?;
+    /// ```
+    fn send_file_batch(&self, file: &File, offset: u64, batch: usize, wait: Option<Duration>) -> io::Result<usize> {
+        #[cfg(all(feature = "io_uring", target_os = "linux"))]
+        if let Some(ring) = self.ring {
+            match ring.send_file(self.stream, file, offset, batch, wait) {
+                Err(error) if error.kind() == io::ErrorKind::Unsupported => {}
+                result => return result,
+            }
+        }
+        self.stream.set_write_timeout(wait)?;
+        sendfile::send(self.stream, file, offset, batch)
+    }
+
+    /// # budget
+    ///
+    /// **Purpose:**
+    /// Works out how long the next operation may wait. While the connection is idle the
+    /// keep-alive timeout applies as well.
+    ///
+    /// **Parameters:**
+    /// - `per_call`: The read or write timeout.
+    /// - `head`: Whether the header deadline and rate limit apply.
+    ///
+    /// **Returns:**
+    /// - The wait limit, or `None` for no limit.
+    ///
+    /// **Errors / Failures:**
+    /// - `TimedOut` if a deadline has already passed.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let wait = self.budget(self.timeouts.read, !self.head_read)?;
+    /// ```
+    fn budget(&self, per_call: Option<Duration>, head: bool) -> io::Result<Option<Duration>> {
+        let earned = self
+            .timeouts
+            .header_rate
+            .map(|rate| Duration::from_secs_f64(self.head_bytes as f64 / f64::from(rate)).max(HEADER_RATE_GRACE));
+        let head_limits = [self.timeouts.header, earned].into_iter().filter(|_| head);
+        let mut wait = per_call;
+        for limit in head_limits.chain([self.timeouts.request, self.idle]).flatten() {
+            let remaining = limit.saturating_sub(self.started.elapsed());
+            if remaining.is_zero() {
+                return Err(io::Error::new(io::ErrorKind::TimedOut, "client deadline exceeded"));
+            }
+            wait = Some(wait.map_or(remaining, |wait| wait.min(remaining)));
+        }
+        Ok(wait)
+    }
+}
+
+
+impl<'a, S: TimedSocket> DeadlineReader<'a, S> {
+
+    /// # new
+    ///
+    /// **Purpose:**
+    /// Starts the clock on a side-channel exchange.
+    ///
+    /// **Parameters:**
+    /// - `socket`: Connected socket.
+    /// - `timeout`: Time allowed for all reads together, from now.
+    ///
+    /// **Returns:**
+    /// - A new `DeadlineReader`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let reader = BufReader::new(DeadlineReader::new(&stream, ADMIN_TIMEOUT));
+    /// ```
+    pub(crate) fn new(socket: &'a S, timeout: Duration) -> DeadlineReader<'a, S> {
+        DeadlineReader { socket, deadline: Instant::now() + timeout }
+    }
+}
+
+
+impl<'a, S: TimedSocket> Read for DeadlineReader<'a, S>
+where
+    &'a S: Read,
+{
+
+    /// # read
+    ///
+    /// **Purpose:**
+    /// Reads from the socket, waiting no longer than the time left before the deadline.
+    ///
+    /// **Parameters:**
+    /// - `buf`: Destination buffer.
+    ///
+    /// **Returns:**
+    /// - The number of bytes read (`0` at end of stream).
+    ///
+    /// **Errors / Failures:**
+    /// - `TimedOut` once the deadline has passed; other socket errors are passed through.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let request = Request::read_within(&mut BufReader::new(reader), &HeadLimits::OPERATOR, 1024)?;
+    /// ```
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let remaining = self.deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return Err(io::Error::new(io::ErrorKind::TimedOut, "client deadline exceeded"));
+        }
+        self.socket.set_read_timeout(Some(remaining))?;
+        let mut socket = self.socket;
+        socket.read(buf).map_err(timed_out)
+    }
+}
+
+
+impl Read for ClientStream<'_> {
+
+    /// # read
+    ///
+    /// **Purpose:**
+    /// Reads from the client, waiting no longer than the read timeout or the nearest deadline,
+    /// after sending any pending `100 Continue`. Preloaded bytes are returned first, without
+    /// waiting.
+    ///
+    /// **Parameters:**
+    /// - `buf`: Destination buffer.
+    ///
+    /// **Returns:**
+    /// - The number of bytes read (`0` at end of stream).
+    ///
+    /// **Errors / Failures:**
+    /// - `TimedOut` if the wait runs out; other socket errors are passed through.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use std::io::{Read, Write};
+    /// # use server::timeouts::{ClientStream, Timeouts};
+    /// # use std::net::{TcpListener, TcpStream};
+    /// # let listener = TcpListener::bind("127.0.0.1:0")?;
+    /// # let peer = TcpStream::connect(listener.local_addr()?)?;
+    /// # let (stream, _) = listener.accept()?;
+    /// # let mut client = ClientStream::new(&stream, Timeouts::new());
+    /// # (&peer).write_all(b"GET / HTTP/1.1\r\n\r\n")?;
+    /// # let mut buf = [0; 1024];
+    /// let read = client.read(&mut buf)?;
+    /// # assert!(read > 0);
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.continue_pending {
+            self.continue_pending = false;
+            self.write_all(b"HTTP/1.1 100 Continue\r\n\r\n")?;
+        }
+        let read = if self.preloaded.is_empty() {
+            if mem::take(&mut self.overdue) {
+                return Err(io::Error::new(io::ErrorKind::TimedOut, "client deadline exceeded"));
+            }
+            let wait = self.budget(self.timeouts.read, !self.head_read)?;
+            self.recv(buf, wait).map_err(timed_out)?
+        } else {
+            let read = buf.len().min(self.preloaded.len());
+            buf[..read].copy_from_slice(&self.preloaded[..read]);
+            self.preloaded.drain(..read);
+            read
+        };
+        if read > 0 && self.idle.take().is_some() {
+            self.started = Instant::now();
+        }
+        if !self.head_read {
+            self.head_bytes += read;
+        }
+        if let Some(metrics) = &self.metrics {
+            metrics.record_received(read);
+        }
+        Ok(read)
+    }
+}
+
+
+impl Write for ClientStream<'_> {
+
+    /// # write
+    ///
+    /// **Purpose:**
+    /// Writes to the client, waiting no longer than the write timeout or the overall deadline.
+    /// When throttled, writes at most one pacing slice and sleeps until the rate allows more.
+    ///
+    /// **Parameters:**
+    /// - `buf`: Bytes to send.
+    ///
+    /// **Returns:**
+    /// - The number of bytes written.
+    ///
+    /// **Errors / Failures:**
+    /// - `TimedOut` if the wait runs out; other socket errors are passed through.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use std::io::Write;
+    /// # use server::timeouts::{ClientStream, Timeouts};
+    /// # use std::net::{TcpListener, TcpStream};
+    /// # let listener = TcpListener::bind("127.0.0.1:0")?;
+    /// # let peer = TcpStream::connect(listener.local_addr()?)?;
+    /// # let (stream, _) = listener.accept()?;
+    /// # let mut client = ClientStream::new(&stream, Timeouts::new());
+    /// client.write_all(b"HTTP/1.1 200 OK\r\n")?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let buf = match &self.pacer {
+            Some(pacer) => &buf[..buf.len().min(pacer.slice())],
+            None => buf,
+        };
+        let wait = self.budget(self.timeouts.write, false)?;
+        let written = self.send(buf, wait).map_err(timed_out)?;
+        if let Some(pacer) = &mut self.pacer {
+            pacer.record(written);
+        }
+        if let Some(metrics) = &self.metrics {
+            metrics.record_sent(written);
+        }
+        Ok(written)
+    }
+
+    /// # write_vectored
+    ///
+    /// **Purpose:**
+    /// Writes several buffers to the client in one call, under the same limits as `write`.
+    /// When throttled, writes at most one pacing slice of the first non-empty buffer.
+    ///
+    /// **Parameters:**
+    /// - `bufs`: Buffers to send, in order.
+    ///
+    /// **Returns:**
+    /// - The number of bytes written, across the buffers.
+    ///
+    /// **Errors / Failures:**
+    /// - `TimedOut` if the wait runs out; other socket errors are passed through.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use std::io::{IoSlice, Write};
+    /// # use server::timeouts::{ClientStream, Timeouts};
+    /// # use std::net::{TcpListener, TcpStream};
+    /// # let listener = TcpListener::bind("127.0.0.1:0")?;
+    /// # let peer = TcpStream::connect(listener.local_addr()?)?;
+    /// # let (stream, _) = listener.accept()?;
+    /// # let mut client = ClientStream::new(&stream, Timeouts::new());
+    /// # let (head, body) = (b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\n\r\n".to_vec(), b"hi".to_vec());
+    /// let written = client.write_vectored(&[IoSlice::new(&head), IoSlice::new(&body)])?;
+    /// # assert!(written > 0);
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+        if self.pacer.is_some() {
+            let first = bufs.iter().find(|buf| !buf.is_empty()).map_or(&[][..], |buf| &buf[..]);
+            return self.write(first);
+        }
+        let wait = self.budget(self.timeouts.write, false)?;
+        let written = self.send_vectored(bufs, wait).map_err(timed_out)?;
+        if let Some(metrics) = &self.metrics {
+            metrics.record_sent(written);
+        }
+        Ok(written)
+    }
+
+    /// # flush
+    ///
+    /// **Purpose:**
+    /// Flushes the socket (a no-op for TCP).
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - `Ok(())`.
+    ///
+    /// **Errors / Failures:**
+    /// - Passes through socket errors.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use std::io::Write;
+    /// # use server::timeouts::{ClientStream, Timeouts};
+    /// # use std::net::{TcpListener, TcpStream};
+    /// # let listener = TcpListener::bind("127.0.0.1:0")?;
+    /// # let peer = TcpStream::connect(listener.local_addr()?)?;
+    /// # let (stream, _) = listener.accept()?;
+    /// # let mut client = ClientStream::new(&stream, Timeouts::new());
+    /// client.flush()?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    fn flush(&mut self) -> io::Result<()> {
+        self.stream.flush()
+    }
+}
+
+
+/// # timed_out
+///
+/// **Purpose:**
+/// Reports socket timeouts uniformly; Unix signals them as `WouldBlock`.
+///
+/// **Parameters:**
+/// - `error`: Error from a socket read or write.
+///
+/// **Returns:**
+/// - A `TimedOut` error for timeouts, otherwise `error` unchanged.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let result = stream.read(buf).map_err(timed_out);
+/// ```
+fn timed_out(error: io::Error) -> io::Error {
+    match error.kind() {
+        io::ErrorKind::WouldBlock => io::Error::new(io::ErrorKind::TimedOut, "client timed out"),
+        _ => error,
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::TcpListener;
+
+    fn pair() -> (TcpStream, TcpStream) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let client = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        (client, listener.accept().unwrap().0)
+    }
+
+    #[test]
+    fn head_reads_stop_at_the_header_deadline() {
+        let (mut client, server) = pair();
+        let mut stream = ClientStream::new(&server, Timeouts::none().header(Duration::from_millis(100)));
+        client.write_all(b"GET / HTTP/1.1\r\n").unwrap();
+        let mut buf = [0; 64];
+        assert_eq!(stream.read(&mut buf).unwrap(), 16);
+        assert_eq!(stream.read(&mut buf).unwrap_err().kind(), io::ErrorKind::TimedOut);
+    }
+
+    #[test]
+    fn the_header_deadline_ends_once_the_head_is_read() {
+        let (mut client, server) = pair();
+        let mut stream = ClientStream::new(&server, Timeouts::none().header(Duration::from_millis(50)));
+        stream.head_read();
+        std::thread::sleep(Duration::from_millis(80));
+        client.write_all(b"body").unwrap();
+        assert_eq!(stream.read(&mut [0; 8]).unwrap(), 4);
+    }
+
+    #[test]
+    fn deadline_readers_give_up_when_the_time_is_spent() {
+        let (_client, server) = pair();
+        let mut reader = DeadlineReader::new(&server, Duration::from_millis(50));
+        let started = Instant::now();
+        assert_eq!(reader.read(&mut [0; 8]).unwrap_err().kind(), io::ErrorKind::TimedOut);
+        assert!(started.elapsed() < Duration::from_secs(2));
+    }
+}
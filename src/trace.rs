@@ -0,0 +1,931 @@
+
+// ============================================================
+//  DAEGONICA SOFTWARE — trace.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: Trace
+//!
+//! **Purpose:**
+//! Records OpenTelemetry spans for requests and exports them to an OTLP collector.
+//!
+//! **Context:**
+//! - Compiled with the `otel` feature and enabled with `Server::tracer`. The server opens a
+//!   span per request; the reverse proxy opens a child span per upstream call and passes its
+//!   context upstream in `traceparent`.
+//!
+//! **Responsibilities:**
+//! - Parses and emits W3C Trace Context `traceparent` headers, continuing incoming traces and
+//!   honouring their sampled flag.
+//! - Keeps the spans of the request a worker is serving on that worker's thread, so handlers can
+//!   read `current_traceparent` and forward it.
+//! - Records only the request headers asked for, with sensitive values redacted.
+//! - Batches finished spans and posts them as OTLP/HTTP JSON from a background thread, dropping
+//!   spans rather than blocking requests when the collector falls behind.
+//! - Does NOT speak OTLP over gRPC or protobuf, reach collectors over TLS, or export metrics
+//!   and logs.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+use std::{
+    cell::RefCell,
+    io,
+    sync::{
+        Arc,
+        Condvar,
+        Mutex,
+        PoisonError,
+        atomic::{AtomicU64, Ordering},
+    },
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::{
+    client,
+    hash,
+    log::{self, LogLevel},
+    random,
+    redact,
+    request::Request,
+    validate::json_string,
+};
+
+
+/// # EXPORT_INTERVAL
+///
+/// **Summary:**
+/// Longest a finished span waits before being exported.
+const EXPORT_INTERVAL: Duration = Duration::from_secs(5);
+
+
+/// # BATCH_SIZE
+///
+/// **Summary:**
+/// Spans sent per export request; a full batch is sent without waiting for the interval.
+const BATCH_SIZE: usize = 512;
+
+
+/// # MAX_QUEUE
+///
+/// **Summary:**
+/// Finished spans held while the collector is slow or unreachable; later spans are dropped.
+const MAX_QUEUE: usize = 4096;
+
+
+/// # SPAN_KIND_SERVER
+///
+/// **Summary:**
+/// OTLP span kind of a request served by this server.
+const SPAN_KIND_SERVER: u8 = 2;
+
+
+/// # SPAN_KIND_CLIENT
+///
+/// **Summary:**
+/// OTLP span kind of a call this server makes upstream.
+const SPAN_KIND_CLIENT: u8 = 3;
+
+
+thread_local! {
+    /// # ACTIVE
+    ///
+    /// **Summary:**
+    /// Open spans of the request this thread is serving, outermost first.
+    static ACTIVE: RefCell<Vec<Span>> = const { RefCell::new(Vec::new()) };
+}
+
+
+/// # TraceContext
+///
+/// **Summary:**
+/// Identity of one span within a trace, as carried by `traceparent`.
+///
+/// **Fields:**
+/// - `trace_id`: Trace the span belongs to.
+/// - `span_id`: The span itself.
+/// - `sampled`: Whether the trace is being recorded.
+///
+/// **Usage Example:**
+/// ```rust
+/// # use server::trace::TraceContext;
+/// let parent = TraceContext::parse("00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01");
+/// # assert!(parent.is_some());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TraceContext {
+    pub trace_id: [u8; 16],
+    pub span_id: [u8; 8],
+    pub sampled: bool,
+}
+
+
+/// # Tracer
+///
+/// **Summary:**
+/// Span exporter for one collector. Clones share the export queue.
+///
+/// **Fields:**
+/// - `endpoint`: OTLP/HTTP traces URL, e.g. `http://127.0.0.1:4318/v1/traces`.
+/// - `service`: `service.name` reported for every span.
+/// - `ratio`: Share of new traces recorded; continued traces follow their parent's flag.
+/// - `headers`: Request headers recorded on server spans, redacted per `redact`.
+/// - `queue`: Finished spans waiting for export.
+///
+/// **Usage Example:**
+/// ```rust,no_run
+/// # use server::{server::Server, trace::Tracer};
+/// let tracer = Tracer::new("http://127.0.0.1:4318/v1/traces", "storefront").sample_ratio(0.1);
+/// Server::new("0.0.0.0:80").tracer(tracer).run()?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct Tracer {
+    endpoint: String,
+    service: String,
+    ratio: f64,
+    headers: Vec<String>,
+    queue: Arc<Queue>,
+}
+
+
+/// # Queue
+///
+/// **Summary:**
+/// Export queue shared between request threads and the export thread.
+///
+/// **Fields:**
+/// - `spans`: Finished spans rendered as OTLP JSON objects.
+/// - `ready`: Wakes the export thread when a batch is full.
+/// - `dropped`: Spans discarded because the queue was full.
+#[derive(Debug, Default)]
+struct Queue {
+    spans: Mutex<Vec<String>>,
+    ready: Condvar,
+    dropped: AtomicU64,
+}
+
+
+/// # Span
+///
+/// **Summary:**
+/// An open span.
+///
+/// **Fields:**
+/// - `tracer`: Where the span is exported.
+/// - `context`: The span's own identity.
+/// - `parent`: Parent span id, if the span is not a trace root.
+/// - `name`: Span name.
+/// - `kind`: OTLP span kind.
+/// - `start`: Start time in nanoseconds since the Unix epoch.
+/// - `attributes`: String attributes.
+/// - `status`: HTTP status the span ended with, if one was seen.
+#[derive(Debug)]
+struct Span {
+    tracer: Tracer,
+    context: TraceContext,
+    parent: Option<[u8; 8]>,
+    name: String,
+    kind: u8,
+    start: u64,
+    attributes: Vec<(String, String)>,
+    status: Option<u16>,
+}
+
+
+/// # SpanGuard
+///
+/// **Summary:**
+/// Keeps a span open on the current thread; dropping it ends the span and queues it for export.
+///
+/// **Fields:**
+/// None.
+#[derive(Debug)]
+pub(crate) struct SpanGuard(());
+
+
+impl TraceContext {
+
+    /// # parse
+    ///
+    /// **Purpose:**
+    /// Reads a `traceparent` header value (`version-traceid-parentid-flags`).
+    ///
+    /// **Parameters:**
+    /// - `value`: Header value.
+    ///
+    /// **Returns:**
+    /// - The context, or `None` if the value is malformed, uses the invalid version `ff`, or has
+    ///   an all-zero id. Fields after the flags, allowed by future versions, are ignored.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{request::Request, trace::TraceContext};
+    /// # let request = Request::read_from(&mut &b"GET / HTTP/1.1\r\ntraceparent: 00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01\r\n\r\n"[..])?;
+    /// let parent = request.headers.get("traceparent").and_then(TraceContext::parse);
+    /// # assert!(parent.is_some());
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn parse(value: &str) -> Option<TraceContext> {
+        let mut fields = value.trim().split('-');
+        let version = fields.next().filter(|version| version.len() == 2 && *version != "ff")?;
+        let trace_id: [u8; 16] = unhex(fields.next()?)?.try_into().ok()?;
+        let span_id: [u8; 8] = unhex(fields.next()?)?.try_into().ok()?;
+        let flags: [u8; 1] = unhex(fields.next()?)?.try_into().ok()?;
+        if (version == "00" && fields.next().is_some()) || trace_id == [0; 16] || span_id == [0; 8] {
+            return None;
+        }
+        Some(TraceContext {
+            trace_id,
+            span_id,
+            sampled: flags[0] & 1 == 1,
+        })
+    }
+
+    /// # header_value
+    ///
+    /// **Purpose:**
+    /// Formats the context as a version `00` `traceparent` value.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - The header value.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{headers::Headers, trace::TraceContext};
+    /// # let context = TraceContext::parse("00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01").unwrap();
+    /// # let mut headers = Headers::new();
+    /// headers.set("traceparent", context.header_value());
+    /// # assert_eq!(headers.get("traceparent"), Some("00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01"));
+    /// ```
+    pub fn header_value(&self) -> String {
+        format!("00-{}-{}-{:02x}", hash::hex(&self.trace_id), hash::hex(&self.span_id), u8::from(self.sampled))
+    }
+
+    /// # child
+    ///
+    /// **Purpose:**
+    /// Creates the context of a new span in the same trace.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - A context with the same trace id and sampled flag and a fresh span id.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let context = parent.child();
+    /// ```
+    fn child(&self) -> TraceContext {
+        TraceContext {
+            span_id: random_id(),
+            ..*self
+        }
+    }
+}
+
+
+impl Tracer {
+
+    /// # new
+    ///
+    /// **Purpose:**
+    /// Creates a tracer exporting to an OTLP/HTTP collector and recording every new trace.
+    ///
+    /// **Parameters:**
+    /// - `endpoint`: `http://` URL of the collector's traces endpoint (usually port 4318, path `/v1/traces`).
+    /// - `service`: Service name reported in the exported resource.
+    ///
+    /// **Returns:**
+    /// - A new `Tracer`; nothing is exported until `Server::run` starts it.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::trace::Tracer;
+    /// let tracer = Tracer::new("http://otel-collector:4318/v1/traces", "api");
+    /// ```
+    pub fn new(endpoint: &str, service: &str) -> Tracer {
+        Tracer {
+            endpoint: endpoint.to_string(),
+            service: service.to_string(),
+            ratio: 1.0,
+            headers: Vec::new(),
+            queue: Arc::default(),
+        }
+    }
+
+    /// # sample_ratio
+    ///
+    /// **Purpose:**
+    /// Records only a share of the traces this server starts. Requests continuing a trace from
+    /// `traceparent` follow the caller's sampling decision instead.
+    ///
+    /// **Parameters:**
+    /// - `ratio`: Share between `0.0` (none) and `1.0` (all).
+    ///
+    /// **Returns:**
+    /// - The updated `Tracer`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::trace::Tracer;
+    /// let tracer = Tracer::new("http://127.0.0.1:4318/v1/traces", "api").sample_ratio(0.25);
+    /// ```
+    pub fn sample_ratio(mut self, ratio: f64) -> Tracer {
+        self.ratio = ratio.clamp(0.0, 1.0);
+        self
+    }
+
+    /// # capture_header
+    ///
+    /// **Purpose:**
+    /// Records a request header on every server span as `http.request.header.<name>`. Values of
+    /// sensitive headers (see `redact`) are recorded as `[redacted]`.
+    ///
+    /// **Parameters:**
+    /// - `name`: Header name.
+    ///
+    /// **Returns:**
+    /// - The updated `Tracer`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::trace::Tracer;
+    /// let tracer = Tracer::new("http://127.0.0.1:4318/v1/traces", "api").capture_header("User-Agent");
+    /// ```
+    pub fn capture_header(mut self, name: &str) -> Tracer {
+        self.headers.push(name.to_string());
+        self
+    }
+
+    /// # dropped
+    ///
+    /// **Purpose:**
+    /// Returns how many finished spans were discarded because the export queue was full.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - Spans dropped since start-up.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::trace::Tracer;
+    /// # let tracer = Tracer::new("http://127.0.0.1:4318/v1/traces", "api");
+    /// println!("dropped {} spans", tracer.dropped());
+    /// ```
+    pub fn dropped(&self) -> u64 {
+        self.queue.dropped.load(Ordering::Relaxed)
+    }
+
+    /// # start
+    ///
+    /// **Purpose:**
+    /// Starts the background thread that exports finished spans in batches.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - `Ok(())` once the thread is running.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns an I/O error if the thread cannot be spawned. Failed exports are logged and
+    ///   their spans discarded.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// tracer.start()?;
+    /// ```
+    pub(crate) fn start(&self) -> io::Result<()> {
+        let tracer = self.clone();
+        thread::Builder::new().name("otel-export".to_string()).spawn(move || {
+            loop {
+                let batch = tracer.next_batch();
+                if let Err(error) = tracer.export(&batch) {
+                    log::emit(LogLevel::Warn, format_args!("Trace export failed: {error}"));
+                }
+            }
+        })?;
+        Ok(())
+    }
+
+    /// # server_span
+    ///
+    /// **Purpose:**
+    /// Opens the span for a request on the current thread, continuing the caller's trace when
+    /// the request carries a valid `traceparent`.
+    ///
+    /// **Parameters:**
+    /// - `request`: Request being served, with its client address resolved.
+    ///
+    /// **Returns:**
+    /// - A guard ending the span when dropped.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let _span = tracer.server_span(&request);
+    /// ```
+    pub(crate) fn server_span(&self, request: &Request) -> SpanGuard {
+        let parent = request.headers.get("traceparent").and_then(TraceContext::parse);
+        let context = match parent {
+            Some(parent) => parent.child(),
+            None => TraceContext {
+                trace_id: random_id(),
+                span_id: random_id(),
+                sampled: self.samples(),
+            },
+        };
+        let mut attributes = vec![
+            ("http.request.method".to_string(), request.method.clone()),
+            ("url.path".to_string(), request.path().to_string()),
+        ];
+        if let Some(client) = request.client {
+            attributes.push(("client.address".to_string(), client.to_string()));
+        }
+        for name in &self.headers {
+            let values = request.headers.get_all(name);
+            if !values.is_empty() {
+                let key = format!("http.request.header.{}", name.to_ascii_lowercase());
+                attributes.push((key, redact::value(name, &values.join(", ")).to_string()));
+            }
+        }
+        open(Span {
+            tracer: self.clone(),
+            context,
+            parent: parent.map(|parent| parent.span_id),
+            name: request.method.clone(),
+            kind: SPAN_KIND_SERVER,
+            start: now(),
+            attributes,
+            status: None,
+        })
+    }
+
+    /// # samples
+    ///
+    /// **Purpose:**
+    /// Decides whether to record a trace this server starts.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - `true` with probability `ratio`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let sampled = self.samples();
+    /// ```
+    fn samples(&self) -> bool {
+        let mut bytes = [0u8; 8];
+        random::fill(&mut bytes);
+        (u64::from_le_bytes(bytes) as f64) < self.ratio * u64::MAX as f64
+    }
+
+    /// # enqueue
+    ///
+    /// **Purpose:**
+    /// Queues a finished span for export, waking the export thread once a batch is full.
+    ///
+    /// **Parameters:**
+    /// - `span`: Span rendered as an OTLP JSON object.
+    ///
+    /// **Returns:**
+    /// None.
+    ///
+    /// **Errors / Failures:**
+    /// None. The span is counted as dropped if the queue is full.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// span.tracer.enqueue(span.to_json());
+    /// ```
+    fn enqueue(&self, span: String) {
+        let mut spans = self.queue.spans.lock().unwrap_or_else(PoisonError::into_inner);
+        if spans.len() >= MAX_QUEUE {
+            self.queue.dropped.fetch_add(1, Ordering::Relaxed);
+            return;
+        }
+        spans.push(span);
+        if spans.len() >= BATCH_SIZE {
+            self.queue.ready.notify_one();
+        }
+    }
+
+    /// # next_batch
+    ///
+    /// **Purpose:**
+    /// Waits until a batch is full or the export interval has passed with spans queued.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - Up to `BATCH_SIZE` spans, oldest first.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let batch = tracer.next_batch();
+    /// ```
+    fn next_batch(&self) -> Vec<String> {
+        let mut spans = self.queue.spans.lock().unwrap_or_else(PoisonError::into_inner);
+        loop {
+            let (guard, _) = self
+                .queue
+                .ready
+                .wait_timeout_while(spans, EXPORT_INTERVAL, |spans| spans.len() < BATCH_SIZE)
+                .unwrap_or_else(PoisonError::into_inner);
+            spans = guard;
+            if !spans.is_empty() {
+                let take = spans.len().min(BATCH_SIZE);
+                return spans.drain(..take).collect();
+            }
+        }
+    }
+
+    /// # export
+    ///
+    /// **Purpose:**
+    /// Posts a batch of spans to the collector.
+    ///
+    /// **Parameters:**
+    /// - `batch`: Spans rendered as OTLP JSON objects.
+    ///
+    /// **Returns:**
+    /// - `Ok(())` once the collector accepts the batch.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns an I/O error if the collector cannot be reached or answers with a non-`2xx` status.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// tracer.export(&batch)?;
+    /// ```
+    fn export(&self, batch: &[String]) -> io::Result<()> {
+        let body = format!(
+            concat!(
+                r#"{{"resourceSpans":[{{"resource":{{"attributes":[{{"key":"service.name","value":{{"stringValue":{}}}}}]}},"#,
+                r#""scopeSpans":[{{"scope":{{"name":"daegonica-server","version":"{}"}},"spans":[{}]}}]}}]}}"#,
+            ),
+            json_string(&self.service),
+            env!("CARGO_PKG_VERSION"),
+            batch.join(","),
+        );
+        let response = client::send("POST", &self.endpoint, &[("Content-Type", "application/json")], body.as_bytes())?;
+        if !(200..300).contains(&response.status) {
+            return Err(io::Error::other(format!("collector answered {}", response.status)));
+        }
+        Ok(())
+    }
+}
+
+
+impl Span {
+
+    /// # to_json
+    ///
+    /// **Purpose:**
+    /// Renders the span, ended now, as an OTLP JSON span object.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - The JSON object. Server spans with a `5xx` status and client spans with a `4xx` or `5xx`
+    ///   status are marked as errors; streamed exchanges, whose status is not read, are left unset.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let json = span.to_json();
+    /// ```
+    fn to_json(&self) -> String {
+        let mut attributes: Vec<String> = self
+            .attributes
+            .iter()
+            .map(|(key, value)| format!(r#"{{"key":"{key}","value":{{"stringValue":{}}}}}"#, json_string(value)))
+            .collect();
+        if let Some(status) = self.status {
+            attributes.push(format!(r#"{{"key":"http.response.status_code","value":{{"intValue":"{status}"}}}}"#));
+        }
+        let failed_from = if self.kind == SPAN_KIND_SERVER { 500 } else { 400 };
+        let code = match self.status {
+            Some(status) if status >= failed_from => 2,
+            _ => 0,
+        };
+        let parent = self.parent.map_or(String::new(), |parent| format!(r#""parentSpanId":"{}","#, hash::hex(&parent)));
+        format!(
+            r#"{{"traceId":"{}","spanId":"{}",{parent}"name":{},"kind":{},"startTimeUnixNano":"{}","endTimeUnixNano":"{}","attributes":[{}],"status":{{"code":{code}}}}}"#,
+            hash::hex(&self.context.trace_id),
+            hash::hex(&self.context.span_id),
+            json_string(&self.name),
+            self.kind,
+            self.start,
+            now(),
+            attributes.join(","),
+        )
+    }
+}
+
+
+impl Drop for SpanGuard {
+
+    /// # drop
+    ///
+    /// **Purpose:**
+    /// Ends the innermost open span on this thread and queues it if its trace is sampled.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// None.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// drop(span);
+    /// ```
+    fn drop(&mut self) {
+        let span = ACTIVE.with(|active| active.borrow_mut().pop());
+        if let Some(span) = span
+            && span.context.sampled
+        {
+            span.tracer.enqueue(span.to_json());
+        }
+    }
+}
+
+
+/// # client_span
+///
+/// **Purpose:**
+/// Opens a child span for a call upstream, when the current thread is serving a traced request.
+///
+/// **Parameters:**
+/// - `method`: Method of the upstream request.
+/// - `upstream`: Upstream address in `host:port` form.
+///
+/// **Returns:**
+/// - A guard ending the span when dropped, or `None` outside a traced request.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let _span = trace::client_span(&request.method, &address);
+/// ```
+pub(crate) fn client_span(method: &str, upstream: &str) -> Option<SpanGuard> {
+    let (tracer, parent) = ACTIVE.with(|active| active.borrow().last().map(|span| (span.tracer.clone(), span.context)))?;
+    Some(open(Span {
+        tracer,
+        context: parent.child(),
+        parent: Some(parent.span_id),
+        name: method.to_string(),
+        kind: SPAN_KIND_CLIENT,
+        start: now(),
+        attributes: vec![
+            ("http.request.method".to_string(), method.to_string()),
+            ("server.address".to_string(), upstream.to_string()),
+        ],
+        status: None,
+    }))
+}
+
+
+/// # set_route
+///
+/// **Purpose:**
+/// Names the current request's span after its route pattern (`GET /users/:id`) and records it
+/// as `http.route`.
+///
+/// **Parameters:**
+/// - `route`: Route pattern or placeholder label.
+///
+/// **Returns:**
+/// None.
+///
+/// **Errors / Failures:**
+/// None. Does nothing outside a traced request.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// trace::set_route(route);
+/// ```
+pub(crate) fn set_route(route: &str) {
+    ACTIVE.with(|active| {
+        if let Some(span) = active.borrow_mut().first_mut() {
+            span.name = format!("{} {route}", span.name);
+            span.attributes.push(("http.route".to_string(), route.to_string()));
+        }
+    });
+}
+
+
+/// # set_status
+///
+/// **Purpose:**
+/// Records the HTTP status on the innermost open span.
+///
+/// **Parameters:**
+/// - `status`: Status code sent or received.
+///
+/// **Returns:**
+/// None.
+///
+/// **Errors / Failures:**
+/// None. Does nothing outside a traced request.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// trace::set_status(response.status);
+/// ```
+pub(crate) fn set_status(status: u16) {
+    ACTIVE.with(|active| {
+        if let Some(span) = active.borrow_mut().last_mut() {
+            span.status = Some(status);
+        }
+    });
+}
+
+
+/// # current_traceparent
+///
+/// **Purpose:**
+/// Returns the `traceparent` value identifying the innermost open span, for handlers that call
+/// other services and want the calls joined to the request's trace.
+///
+/// **Parameters:**
+/// None.
+///
+/// **Returns:**
+/// - The header value, or `None` outside a traced request.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust
+/// # use server::{headers::Headers, trace};
+/// # let mut outbound = Headers::new();
+/// if let Some(value) = trace::current_traceparent() { outbound.set("traceparent", value); }
+/// # assert!(outbound.get("traceparent").is_none());
+/// ```
+pub fn current_traceparent() -> Option<String> {
+    ACTIVE.with(|active| active.borrow().last().map(|span| span.context.header_value()))
+}
+
+
+/// # open
+///
+/// **Purpose:**
+/// Pushes a span onto this thread's open spans.
+///
+/// **Parameters:**
+/// - `span`: Span to open.
+///
+/// **Returns:**
+/// - The guard that ends it.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let guard = open(span);
+/// ```
+fn open(span: Span) -> SpanGuard {
+    ACTIVE.with(|active| active.borrow_mut().push(span));
+    SpanGuard(())
+}
+
+
+/// # random_id
+///
+/// **Purpose:**
+/// Draws a random non-zero trace or span id.
+///
+/// **Parameters:**
+/// None.
+///
+/// **Returns:**
+/// - `N` random bytes, not all zero.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let span_id: [u8; 8] = random_id();
+/// ```
+fn random_id<const N: usize>() -> [u8; N] {
+    let mut id = [0u8; N];
+    while id.iter().all(|byte| *byte == 0) {
+        random::fill(&mut id);
+    }
+    id
+}
+
+
+/// # now
+///
+/// **Purpose:**
+/// Reads the wall clock for span timestamps.
+///
+/// **Parameters:**
+/// None.
+///
+/// **Returns:**
+/// - Nanoseconds since the Unix epoch (`0` if the clock is before it).
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let start = now();
+/// ```
+fn now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |elapsed| elapsed.as_nanos() as u64)
+}
+
+
+/// # unhex
+///
+/// **Purpose:**
+/// Decodes lowercase hexadecimal, as `traceparent` requires.
+///
+/// **Parameters:**
+/// - `text`: Hex digits.
+///
+/// **Returns:**
+/// - The bytes, or `None` for odd lengths and characters outside `0-9a-f`.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// assert_eq!(unhex("0aff"), Some(vec![0x0a, 0xff]));
+/// ```
+fn unhex(text: &str) -> Option<Vec<u8>> {
+    let digit = |c: u8| match c {
+        b'0'..=b'9' => Some(c - b'0'),
+        b'a'..=b'f' => Some(c - b'a' + 10),
+        _ => None,
+    };
+    if !text.len().is_multiple_of(2) {
+        return None;
+    }
+    text.as_bytes()
+        .chunks(2)
+        .map(|pair| Some(digit(pair[0])? << 4 | digit(pair[1])?))
+        .collect()
+}
@@ -0,0 +1,341 @@
+
+// ============================================================
+//  DAEGONICA SOFTWARE — trie.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: Trie
+//!
+//! **Purpose:**
+//! Indexes route paths segment by segment so lookups cost O(path length) instead of O(route count).
+//!
+//! **Context:**
+//! - Backs `Router`, which decides between the candidates by method, registration order, and
+//!   trailing-slash policy.
+//!
+//! **Responsibilities:**
+//! - Compiles route paths into literal, `:param`, and `*catch-all` segments with optional regex constraints.
+//! - Stores route indexes in a tree keyed by literal segments, with parameter and catch-all edges.
+//! - Returns every route matching a path, with its captured parameters.
+//! - Does NOT know about methods or handlers.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+use std::collections::HashMap;
+
+use crate::regex::Regex;
+
+
+/// # Params
+///
+/// **Summary:**
+/// Parameters captured by one match, in path order.
+pub(crate) type Params = Vec<(String, String)>;
+
+
+/// # Segment
+///
+/// **Summary:**
+/// One `/`-separated piece of a route path.
+///
+/// **Variants:**
+/// - `Literal`: Text that must match the path segment exactly.
+/// - `Param`: Any non-empty segment, captured under the given name (`:id`), optionally
+///   required to fully match a regex (`:id(\d+)`).
+/// - `CatchAll`: The rest of the path, possibly empty, captured under the given name (`*path`),
+///   with the same optional constraint.
+#[derive(Debug, Clone)]
+enum Segment {
+    Literal(String),
+    Param(String, Option<Regex>),
+    CatchAll(String, Option<Regex>),
+}
+
+
+/// # RouteTrie
+///
+/// **Summary:**
+/// Segment tree mapping route paths to route indexes.
+///
+/// **Fields:**
+/// - `root`: Node for the first path segment.
+///
+/// **Usage Example:**
+/// ```rust,ignore
+/// let mut trie = RouteTrie::default();
+/// trie.insert("/users/:id", 0);
+/// let matches = trie.lookup("/users/42");
+/// ```
+#[derive(Debug, Default)]
+pub(crate) struct RouteTrie {
+    root: Node,
+}
+
+
+/// # Node
+///
+/// **Summary:**
+/// One position in the segment tree.
+///
+/// **Fields:**
+/// - `routes`: Routes whose path ends here.
+/// - `literals`: Children keyed by exact segment text.
+/// - `params`: Children for `:name` segments, one per distinct name and constraint.
+/// - `catch_alls`: Routes ending in a `*name` segment at this position.
+#[derive(Debug, Default)]
+struct Node {
+    routes: Vec<usize>,
+    literals: HashMap<String, Node>,
+    params: Vec<ParamEdge>,
+    catch_alls: Vec<CatchAll>,
+}
+
+
+/// # ParamEdge
+///
+/// **Summary:**
+/// A `:name` segment leading to a child node.
+///
+/// **Fields:**
+/// - `name`: Parameter name.
+/// - `constraint`: Regex the segment must fully match, if any.
+/// - `node`: Child node.
+#[derive(Debug)]
+struct ParamEdge {
+    name: String,
+    constraint: Option<Regex>,
+    node: Node,
+}
+
+
+/// # CatchAll
+///
+/// **Summary:**
+/// A route ending in a `*name` segment.
+///
+/// **Fields:**
+/// - `route`: Route index.
+/// - `name`: Parameter name.
+/// - `constraint`: Regex the remainder must fully match, if any.
+#[derive(Debug)]
+struct CatchAll {
+    route: usize,
+    name: String,
+    constraint: Option<Regex>,
+}
+
+
+impl RouteTrie {
+
+    /// # insert
+    ///
+    /// **Purpose:**
+    /// Adds a route path under the given route index.
+    ///
+    /// **Parameters:**
+    /// - `path`: Route path as registered.
+    /// - `route`: Index of the route in the router.
+    ///
+    /// **Returns:**
+    /// None.
+    ///
+    /// **Errors / Failures:**
+    /// - Panics if a parameter constraint is not a valid regex.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// trie.insert("/files/*path", 3);
+    /// ```
+    pub fn insert(&mut self, path: &str, route: usize) {
+        let mut node = &mut self.root;
+        for segment in compile(path) {
+            node = match segment {
+                Segment::Literal(text) => node.literals.entry(text).or_default(),
+                Segment::Param(name, constraint) => {
+                    let pattern = constraint.as_ref().map(Regex::as_str);
+                    let existing = node.params.iter().position(|edge| {
+                        edge.name == name && edge.constraint.as_ref().map(Regex::as_str) == pattern
+                    });
+                    let index = match existing {
+                        Some(index) => index,
+                        None => {
+                            node.params.push(ParamEdge {
+                                name,
+                                constraint,
+                                node: Node::default(),
+                            });
+                            node.params.len() - 1
+                        }
+                    };
+                    &mut node.params[index].node
+                }
+                Segment::CatchAll(name, constraint) => {
+                    node.catch_alls.push(CatchAll { route, name, constraint });
+                    return;
+                }
+            };
+        }
+        node.routes.push(route);
+    }
+
+    /// # lookup
+    ///
+    /// **Purpose:**
+    /// Finds every route matching a request path. Literal segments are hash lookups, so the
+    /// cost grows with the path length and the number of parameter edges on the way, not with
+    /// the size of the route table.
+    ///
+    /// **Parameters:**
+    /// - `path`: Request path, without the query.
+    ///
+    /// **Returns:**
+    /// - Matching route indexes with their captured parameters, in ascending route order.
+    ///
+    /// **Errors / Failures:**
+    /// None. Paths not starting with `/` match nothing.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let matches = trie.lookup("/users/42");
+    /// ```
+    pub fn lookup(&self, path: &str) -> Vec<(usize, Params)> {
+        let Some(rest) = path.strip_prefix('/') else {
+            return Vec::new();
+        };
+        let parts: Vec<&str> = rest.split('/').collect();
+        let mut found = Vec::new();
+        self.root.collect(&parts, &mut Vec::new(), &mut found);
+        found.sort_by_key(|(route, _)| *route);
+        found
+    }
+}
+
+
+impl Node {
+
+    /// # collect
+    ///
+    /// **Purpose:**
+    /// Depth-first search below this node for routes matching the remaining path segments.
+    ///
+    /// **Parameters:**
+    /// - `parts`: Remaining path segments.
+    /// - `params`: Parameters captured on the way here.
+    /// - `found`: Output list of matches.
+    ///
+    /// **Returns:**
+    /// None. Matches are appended to `found`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// trie.root.collect(&parts, &mut Vec::new(), &mut found);
+    /// ```
+    fn collect(&self, parts: &[&str], params: &mut Params, found: &mut Vec<(usize, Params)>) {
+        for catch_all in &self.catch_alls {
+            let rest = parts.join("/");
+            if catch_all.constraint.as_ref().is_none_or(|constraint| constraint.is_match(&rest)) {
+                let mut captured = params.clone();
+                captured.push((catch_all.name.clone(), rest));
+                found.push((catch_all.route, captured));
+            }
+        }
+
+        let Some((part, rest)) = parts.split_first() else {
+            found.extend(self.routes.iter().map(|&route| (route, params.clone())));
+            return;
+        };
+
+        if let Some(child) = self.literals.get(*part) {
+            child.collect(rest, params, found);
+        }
+        if part.is_empty() {
+            return;
+        }
+        for edge in &self.params {
+            if edge.constraint.as_ref().is_none_or(|constraint| constraint.is_match(part)) {
+                params.push((edge.name.clone(), part.to_string()));
+                edge.node.collect(rest, params, found);
+                params.pop();
+            }
+        }
+    }
+}
+
+
+/// # compile
+///
+/// **Purpose:**
+/// Splits a route path into literal, parameter, and catch-all segments.
+///
+/// **Parameters:**
+/// - `path`: Route path as registered.
+///
+/// **Returns:**
+/// - The compiled segments, one per `/`-separated piece after the leading slash.
+///
+/// **Errors / Failures:**
+/// - Panics if a parameter constraint is not a valid regex.
+/// - A `*name` segment that is not last is kept as a catch-all and swallows the rest.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let segments = compile("/users/:id(\\d+)/files/*path");
+/// ```
+fn compile(path: &str) -> Vec<Segment> {
+    path.strip_prefix('/')
+        .unwrap_or(path)
+        .split('/')
+        .map(|segment| match segment.chars().next() {
+            Some(':') if segment.len() > 1 => {
+                let (name, constraint) = parameter(&segment[1..]);
+                Segment::Param(name, constraint)
+            }
+            Some('*') if segment.len() > 1 => {
+                let (name, constraint) = parameter(&segment[1..]);
+                Segment::CatchAll(name, constraint)
+            }
+            _ => Segment::Literal(segment.to_string()),
+        })
+        .collect()
+}
+
+
+/// # parameter
+///
+/// **Purpose:**
+/// Splits a parameter declaration such as `id(\d+)` into its name and compiled constraint.
+///
+/// **Parameters:**
+/// - `declaration`: Parameter text after the `:` or `*` marker.
+///
+/// **Returns:**
+/// - The parameter name and, if present, a regex anchored to match the whole value.
+///
+/// **Errors / Failures:**
+/// - Panics if the constraint is not a valid regex.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let (name, constraint) = parameter("id(\\d+)");
+/// ```
+fn parameter(declaration: &str) -> (String, Option<Regex>) {
+    let Some((name, pattern)) = declaration
+        .strip_suffix(')')
+        .and_then(|declaration| declaration.split_once('('))
+    else {
+        return (declaration.to_string(), None);
+    };
+
+    let constraint = Regex::new(&format!("^(?:{pattern})$"))
+        .unwrap_or_else(|err| panic!("invalid constraint on route parameter '{name}': {err}"));
+    (name.to_string(), Some(constraint))
+}
@@ -0,0 +1,2072 @@
+
+// ============================================================
+//  DAEGONICA SOFTWARE — uring.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: Uring
+//!
+//! **Purpose:**
+//! Drives the server's connections from one `io_uring` instead of a blocking call per thread:
+//! new and idle connections wait in the ring until their request head has arrived, and the
+//! socket reads, writes, and file sends of every worker are submitted to it together.
+//!
+//! **Context:**
+//! - Built with the `io_uring` feature on Linux and enabled with `Server::io_uring`; the ring
+//!   runs on the accept thread in place of the blocking accept loop, and takes over parking
+//!   from the event loop.
+//! - Workers reach the ring through its `RingQueue`, which every `ClientStream` of the server
+//!   holds.
+//!
+//! **Responsibilities:**
+//! - Sets up and maps a ring with `io_uring_setup(2)`, and reports the OS error when the
+//!   kernel refuses one (too old, or disabled by `kernel.io_uring_disabled` or seccomp), so
+//!   the server can fall back to blocking I/O.
+//! - Keeps an accept armed and receives request heads into per-connection buffers, handing a
+//!   connection to the pool only once its head is complete; a connection that is idle, or
+//!   sends its head slowly, holds no thread.
+//! - Hands a connection whose head is overdue to the pool marked as such, so its worker
+//!   answers `408`; closes idle connections whose deadline passes or once the server drains,
+//!   and all of them once it stops.
+//! - Gathers the operations workers queue while the ring thread waits and submits them with
+//!   one `io_uring_enter(2)`, which also reaps every completion ready by then; each worker
+//!   then wakes with its own result.
+//! - Bounds worker operations with linked timeouts, reported as `WouldBlock` like an expired
+//!   socket timeout, and sends files by splicing them through a per-thread pipe.
+//! - Does NOT parse or answer requests: a worker still serves a connection from its head to
+//!   the end of its response, blocking on its own completions, so a client that reads a
+//!   large response slowly keeps its worker until a timeout.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    fs::File,
+    io::{self, IoSlice},
+    mem,
+    net::{Shutdown, TcpListener, TcpStream},
+    os::fd::{AsRawFd, FromRawFd, OwnedFd, RawFd},
+    ptr,
+    sync::{
+        Arc,
+        Condvar,
+        Mutex,
+        PoisonError,
+        atomic::{AtomicU32, Ordering},
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::log::{self, LogLevel};
+
+
+/// # ENTRIES
+///
+/// **Summary:**
+/// Submission queue size; the most operations submitted by one `io_uring_enter(2)`.
+const ENTRIES: u32 = 256;
+
+
+/// # TICK
+///
+/// **Summary:**
+/// Period of the ring's own timer, which is also how late a waiting connection may be closed
+/// or handed over after its deadline.
+const TICK: Duration = Duration::from_millis(250);
+
+
+/// # HEAD_CHUNK
+///
+/// **Summary:**
+/// Bytes by which a waiting connection's buffer grows for each receive.
+const HEAD_CHUNK: usize = 4 * 1024;
+
+
+/// # PRELOAD_LIMIT
+///
+/// **Summary:**
+/// Most bytes received for a connection before it goes to a worker, head complete or not; the
+/// worker's header limits then decide what to do with it.
+const PRELOAD_LIMIT: usize = 64 * 1024;
+
+
+/// # SPLICE_CHUNK
+///
+/// **Summary:**
+/// Most file bytes spliced into the pipe at once, the default pipe capacity.
+const SPLICE_CHUNK: usize = 64 * 1024;
+
+
+/// # NO_OFFSET
+///
+/// **Summary:**
+/// Offset telling the kernel to use a descriptor's own position, required for pipes.
+const NO_OFFSET: u64 = u64::MAX;
+
+
+/// # WAKE_TOKEN
+///
+/// **Summary:**
+/// User data of the read on the wake-up `eventfd`.
+const WAKE_TOKEN: u64 = 0;
+
+
+/// # TICK_TOKEN
+///
+/// **Summary:**
+/// User data of the ring's timer.
+const TICK_TOKEN: u64 = 1;
+
+
+/// # ACCEPT_TOKEN
+///
+/// **Summary:**
+/// User data of the armed accept.
+const ACCEPT_TOKEN: u64 = 2;
+
+
+/// # TIMEOUT_TOKEN
+///
+/// **Summary:**
+/// User data of linked timeouts, whose completions are ignored.
+const TIMEOUT_TOKEN: u64 = 3;
+
+
+/// # CANCEL_TOKEN
+///
+/// **Summary:**
+/// User data of cancellations, whose completions are ignored.
+const CANCEL_TOKEN: u64 = 4;
+
+
+/// # FIRST_TOKEN
+///
+/// **Summary:**
+/// First user data handed to a connection or worker operation.
+const FIRST_TOKEN: u64 = 5;
+
+
+/// # OP_SENDMSG
+///
+/// **Summary:**
+/// `IORING_OP_SENDMSG` opcode.
+const OP_SENDMSG: u8 = 9;
+
+
+/// # OP_TIMEOUT
+///
+/// **Summary:**
+/// `IORING_OP_TIMEOUT` opcode.
+const OP_TIMEOUT: u8 = 11;
+
+
+/// # OP_ACCEPT
+///
+/// **Summary:**
+/// `IORING_OP_ACCEPT` opcode.
+const OP_ACCEPT: u8 = 13;
+
+
+/// # OP_ASYNC_CANCEL
+///
+/// **Summary:**
+/// `IORING_OP_ASYNC_CANCEL` opcode.
+const OP_ASYNC_CANCEL: u8 = 14;
+
+
+/// # OP_LINK_TIMEOUT
+///
+/// **Summary:**
+/// `IORING_OP_LINK_TIMEOUT` opcode.
+const OP_LINK_TIMEOUT: u8 = 15;
+
+
+/// # OP_READ
+///
+/// **Summary:**
+/// `IORING_OP_READ` opcode.
+const OP_READ: u8 = 22;
+
+
+/// # OP_SEND
+///
+/// **Summary:**
+/// `IORING_OP_SEND` opcode.
+const OP_SEND: u8 = 26;
+
+
+/// # OP_RECV
+///
+/// **Summary:**
+/// `IORING_OP_RECV` opcode.
+const OP_RECV: u8 = 27;
+
+
+/// # OP_SPLICE
+///
+/// **Summary:**
+/// `IORING_OP_SPLICE` opcode.
+const OP_SPLICE: u8 = 30;
+
+
+/// # IOSQE_IO_LINK
+///
+/// **Summary:**
+/// Entry flag chaining the next entry (a timeout) to this one.
+const IOSQE_IO_LINK: u8 = 1 << 2;
+
+
+/// # ENTER_GETEVENTS
+///
+/// **Summary:**
+/// `IORING_ENTER_GETEVENTS`: wait for completions as well as submitting.
+const ENTER_GETEVENTS: u32 = 1;
+
+
+/// # FEAT_SINGLE_MMAP
+///
+/// **Summary:**
+/// `IORING_FEAT_SINGLE_MMAP`: both queues share one mapping.
+const FEAT_SINGLE_MMAP: u32 = 1;
+
+
+/// # SPLICE_F_MOVE
+///
+/// **Summary:**
+/// Splice flag asking the kernel to move pages rather than copy them where it can.
+const SPLICE_F_MOVE: u32 = 1;
+
+
+/// # OFF_SQ_RING
+///
+/// **Summary:**
+/// Mapping offset of the submission queue ring.
+const OFF_SQ_RING: libc::off_t = 0;
+
+
+/// # OFF_CQ_RING
+///
+/// **Summary:**
+/// Mapping offset of the completion queue ring.
+const OFF_CQ_RING: libc::off_t = 0x800_0000;
+
+
+/// # OFF_SQES
+///
+/// **Summary:**
+/// Mapping offset of the submission queue entries.
+const OFF_SQES: libc::off_t = 0x1000_0000;
+
+
+thread_local! {
+    /// # PIPE
+    ///
+    /// **Summary:**
+    /// Read and write ends of this thread's splice pipe, opened on its first file send.
+    static PIPE: RefCell<Option<(OwnedFd, OwnedFd)>> = const { RefCell::new(None) };
+}
+
+
+/// # Uring
+///
+/// **Summary:**
+/// The ring shared by a server's connections, each waiting one carrying caller-defined state.
+///
+/// **Fields:**
+/// - `ring`: The mapped ring, driven by whichever thread calls `run`.
+/// - `queue`: Operations queued by workers.
+/// - `parking`: Connections handed back by workers to wait for their next request.
+///
+/// **Usage Example:**
+/// ```rust,ignore
+/// let ring = Arc::new(Uring::new()?);
+/// let driver = Arc::clone(&ring);
+/// thread::spawn(move || driver.run(&listener, |_| Some((state, head)), |stream, state, head, overdue| serve(stream, state, head, overdue), || false, || false));
+/// let read = ring.queue().recv(&stream, &mut buf, Some(Duration::from_secs(5)))?;
+/// ```
+#[derive(Debug)]
+pub(crate) struct Uring<T> {
+    ring: Mutex<Ring>,
+    queue: RingQueue,
+    parking: Mutex<Parking<T>>,
+}
+
+
+/// # RingQueue
+///
+/// **Summary:**
+/// Where workers queue socket operations for the ring thread.
+///
+/// **Fields:**
+/// - `wake`: `eventfd` the ring thread has a read pending on, written to wake it.
+/// - `jobs`: Operations not yet submitted.
+#[derive(Debug)]
+pub(crate) struct RingQueue {
+    wake: OwnedFd,
+    jobs: Mutex<Jobs>,
+}
+
+
+/// # Jobs
+///
+/// **Summary:**
+/// Worker operations waiting to be submitted.
+///
+/// **Fields:**
+/// - `pending`: Operations in the order they were queued.
+/// - `closed`: Set once the ring thread has stopped; workers then use blocking calls.
+#[derive(Debug, Default)]
+struct Jobs {
+    pending: Vec<Job>,
+    closed: bool,
+}
+
+
+/// # Job
+///
+/// **Summary:**
+/// One worker operation.
+///
+/// **Fields:**
+/// - `entry`: Submission entry, pointing at the worker's buffers.
+/// - `timeout`: Time limit, boxed so the linked timeout can point at it.
+/// - `done`: Where the result is delivered.
+#[derive(Debug)]
+struct Job {
+    entry: Sqe,
+    timeout: Option<Box<KernelTimespec>>,
+    done: Arc<Done>,
+}
+
+
+/// # Done
+///
+/// **Summary:**
+/// Completion slot a worker sleeps on.
+///
+/// **Fields:**
+/// - `result`: The operation's result, once complete.
+/// - `finished`: Signalled when `result` is set.
+#[derive(Debug, Default)]
+struct Done {
+    result: Mutex<Option<i32>>,
+    finished: Condvar,
+}
+
+
+/// # Parking
+///
+/// **Summary:**
+/// Connections handed to the ring by workers, not yet picked up by the ring thread.
+///
+/// **Fields:**
+/// - `arrivals`: Connections to start receiving from.
+/// - `closed`: Set once the server drains; connections parked afterwards are closed.
+#[derive(Debug)]
+struct Parking<T> {
+    arrivals: Vec<Waiting<T>>,
+    closed: bool,
+}
+
+
+/// # Waiting
+///
+/// **Summary:**
+/// One connection waiting in the ring for its request head.
+///
+/// **Fields:**
+/// - `stream`: Client socket.
+/// - `state`: Caller state handed back with the socket.
+/// - `buf`: Bytes received so far; the kernel writes into its spare capacity.
+/// - `deadline`: When the connection is closed if idle, or handed over as overdue, or `None`
+///   for never.
+/// - `head`: Time allowed for the head once its first byte arrives, for idle connections.
+/// - `idle`: Whether no byte of the next request has arrived on a persistent connection.
+/// - `expired`: Set once the socket has been shut down to end the receive.
+/// - `overdue`: Set once the receive has been cancelled because the deadline passed.
+#[derive(Debug)]
+struct Waiting<T> {
+    stream: TcpStream,
+    state: T,
+    buf: Vec<u8>,
+    deadline: Option<Instant>,
+    head: Option<Duration>,
+    idle: bool,
+    expired: bool,
+    overdue: bool,
+}
+
+
+/// # Flight
+///
+/// **Summary:**
+/// What a submitted entry's user data stands for.
+///
+/// **Variants:**
+/// - `Job`: A worker operation.
+/// - `Head`: A receive for a waiting connection.
+#[derive(Debug)]
+enum Flight<T> {
+    Job(Job),
+    Head(Waiting<T>),
+}
+
+
+/// # Armed
+///
+/// **Summary:**
+/// Which of the ring thread's own entries are in flight.
+///
+/// **Fields:**
+/// - `wake`: The read on the wake-up `eventfd`.
+/// - `tick`: The timer.
+/// - `accept`: The accept.
+#[derive(Debug)]
+struct Armed {
+    wake: bool,
+    tick: bool,
+    accept: bool,
+}
+
+
+/// # SqringOffsets
+///
+/// **Summary:**
+/// `struct io_sqring_offsets`: where the submission queue fields sit in its mapping.
+///
+/// **Fields:**
+/// - `head`, `tail`, `ring_mask`, `ring_entries`, `flags`, `dropped`, `array`: Field offsets.
+/// - `resv1`, `user_addr`: Reserved.
+#[repr(C)]
+#[derive(Debug, Default)]
+struct SqringOffsets {
+    head: u32,
+    tail: u32,
+    ring_mask: u32,
+    ring_entries: u32,
+    flags: u32,
+    dropped: u32,
+    array: u32,
+    resv1: u32,
+    user_addr: u64,
+}
+
+
+/// # CqringOffsets
+///
+/// **Summary:**
+/// `struct io_cqring_offsets`: where the completion queue fields sit in its mapping.
+///
+/// **Fields:**
+/// - `head`, `tail`, `ring_mask`, `ring_entries`, `overflow`, `cqes`, `flags`: Field offsets.
+/// - `resv1`, `user_addr`: Reserved.
+#[repr(C)]
+#[derive(Debug, Default)]
+struct CqringOffsets {
+    head: u32,
+    tail: u32,
+    ring_mask: u32,
+    ring_entries: u32,
+    overflow: u32,
+    cqes: u32,
+    flags: u32,
+    resv1: u32,
+    user_addr: u64,
+}
+
+
+/// # Params
+///
+/// **Summary:**
+/// `struct io_uring_params`: setup options in, queue sizes and layout out.
+///
+/// **Fields:**
+/// - `sq_entries`, `cq_entries`: Queue sizes chosen by the kernel.
+/// - `flags`, `sq_thread_cpu`, `sq_thread_idle`, `wq_fd`: Setup options, all left zero.
+/// - `features`: Features the kernel supports.
+/// - `resv`: Reserved.
+/// - `sq_off`, `cq_off`: Queue layouts.
+#[repr(C)]
+#[derive(Debug, Default)]
+struct Params {
+    sq_entries: u32,
+    cq_entries: u32,
+    flags: u32,
+    sq_thread_cpu: u32,
+    sq_thread_idle: u32,
+    features: u32,
+    wq_fd: u32,
+    resv: [u32; 3],
+    sq_off: SqringOffsets,
+    cq_off: CqringOffsets,
+}
+
+
+/// # Sqe
+///
+/// **Summary:**
+/// `struct io_uring_sqe`: one submission queue entry, with the unions flattened to the members
+/// this module uses.
+///
+/// **Fields:**
+/// - `opcode`: Operation to perform.
+/// - `flags`: Entry flags, e.g. `IOSQE_IO_LINK`.
+/// - `ioprio`: I/O priority; unused.
+/// - `fd`: Descriptor operated on (the output for splices).
+/// - `off`: File offset; output offset for splices; expiry count for timeouts.
+/// - `addr`: Buffer, message, timeout, or cancelled user data; input offset for splices.
+/// - `len`: Buffer length, or entry count for messages and timeouts.
+/// - `op_flags`: Message, accept, or splice flags.
+/// - `user_data`: Tag copied to the completion.
+/// - `buf_index`, `personality`: Unused.
+/// - `splice_fd_in`: Input descriptor for splices.
+/// - `addr3`, `pad`: Unused.
+#[repr(C)]
+#[derive(Debug, Default, Clone, Copy)]
+struct Sqe {
+    opcode: u8,
+    flags: u8,
+    ioprio: u16,
+    fd: i32,
+    off: u64,
+    addr: u64,
+    len: u32,
+    op_flags: u32,
+    user_data: u64,
+    buf_index: u16,
+    personality: u16,
+    splice_fd_in: i32,
+    addr3: u64,
+    pad: u64,
+}
+
+
+/// # Cqe
+///
+/// **Summary:**
+/// `struct io_uring_cqe`: one completion queue entry.
+///
+/// **Fields:**
+/// - `user_data`: Tag of the completed entry.
+/// - `res`: Result, or a negated `errno`.
+/// - `flags`: Completion flags; unused.
+#[repr(C)]
+#[derive(Debug)]
+struct Cqe {
+    user_data: u64,
+    res: i32,
+    flags: u32,
+}
+
+
+/// # KernelTimespec
+///
+/// **Summary:**
+/// `struct __kernel_timespec`, the duration of a timeout.
+///
+/// **Fields:**
+/// - `tv_sec`: Whole seconds.
+/// - `tv_nsec`: Remaining nanoseconds.
+#[repr(C)]
+#[derive(Debug)]
+struct KernelTimespec {
+    tv_sec: i64,
+    tv_nsec: i64,
+}
+
+
+/// # Region
+///
+/// **Summary:**
+/// One shared mapping of ring memory, unmapped on drop.
+///
+/// **Fields:**
+/// - `address`: Start of the mapping.
+/// - `len`: Length of the mapping in bytes.
+#[derive(Debug)]
+struct Region {
+    address: *mut u8,
+    len: usize,
+}
+
+
+/// # Ring
+///
+/// **Summary:**
+/// An `io_uring`: its descriptor, its mapped queues, and the memory its own entries use.
+///
+/// **Fields:**
+/// - `fd`: Ring descriptor.
+/// - `params`: Queue sizes and layout reported by the kernel.
+/// - `sq`: Submission queue ring, which also holds the completion queue when the kernel shares
+///   one mapping.
+/// - `cq`: Separate completion queue ring, on kernels that need one.
+/// - `sqes`: Submission queue entries.
+/// - `queued`: Entries written but not yet submitted.
+/// - `wake_count`: Buffer the wake-up `eventfd` is read into.
+/// - `tick`: Period of the timer.
+#[derive(Debug)]
+struct Ring {
+    fd: OwnedFd,
+    params: Params,
+    sq: Region,
+    cq: Option<Region>,
+    sqes: Region,
+    queued: u32,
+    wake_count: Box<u64>,
+    tick: Box<KernelTimespec>,
+}
+
+
+// SAFETY: a region is only reached through the `Ring` that owns it, and a `Ring` only through
+// `&mut` under the `Uring` mutex, so moving it to another thread shares nothing.
+unsafe impl Send for Region {}
+
+
+impl<T> Uring<T> {
+
+    /// # new
+    ///
+    /// **Purpose:**
+    /// Sets up a ring with nothing waiting in it.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - A new `Uring`.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns the OS error if the kernel refuses the ring or its memory cannot be mapped,
+    ///   or the wake-up `eventfd` cannot be created.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let ring = Uring::new()?;
+    /// ```
+    pub(crate) fn new() -> io::Result<Uring<T>> {
+        Ok(Uring {
+            ring: Mutex::new(Ring::new()?),
+            queue: RingQueue::new()?,
+            parking: Mutex::new(Parking { arrivals: Vec::new(), closed: false }),
+        })
+    }
+
+    /// # queue
+    ///
+    /// **Purpose:**
+    /// Returns the queue workers submit their socket operations through.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - The ring's `RingQueue`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// client.io_uring(ring.queue());
+    /// ```
+    pub(crate) fn queue(&self) -> &RingQueue {
+        &self.queue
+    }
+
+    /// # park
+    ///
+    /// **Purpose:**
+    /// Hands a persistent connection back to the ring until its next request head has arrived.
+    /// Once the server drains the connection is closed at once instead.
+    ///
+    /// **Parameters:**
+    /// - `stream`: Client socket with nothing buffered for it.
+    /// - `idle`: Keep-alive idle timeout, until the first byte of the next request.
+    /// - `head`: Time then allowed for the rest of the head, or `None` for no limit.
+    /// - `state`: State handed back with the socket.
+    ///
+    /// **Returns:**
+    /// - `Ok(())` once the connection is queued or closed.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns the OS error if the ring thread cannot be woken; the connection is still
+    ///   picked up on its next timer tick.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// ring.park(stream, keep_alive.idle_timeout(), timeouts.head_wait(), state)?;
+    /// ```
+    pub(crate) fn park(&self, stream: TcpStream, idle: Duration, head: Option<Duration>, state: T) -> io::Result<()> {
+        let mut parking = self.parking.lock().unwrap_or_else(PoisonError::into_inner);
+        if parking.closed {
+            return Ok(());
+        }
+        let mut waiting = Waiting::new(stream, state, Some(idle));
+        waiting.head = head;
+        waiting.idle = true;
+        parking.arrivals.push(waiting);
+        drop(parking);
+        self.queue.wake()
+    }
+
+    /// # run
+    ///
+    /// **Purpose:**
+    /// Drives the ring on the calling thread: accepts connections from `listener`, receives
+    /// their request heads, and submits the operations workers queue, until `stopping` returns
+    /// `true`. It then closes every waiting connection, lets the worker operations in flight
+    /// finish, and switches workers to blocking calls before returning.
+    ///
+    /// **Parameters:**
+    /// - `listener`: Bound public listener.
+    /// - `admit`: Called on the ring thread with each accepted connection; returns the state to
+    ///   keep with it and how long its head may take to arrive, or `None` once it has been shed.
+    /// - `ready`: Called on the ring thread with each connection whose head has arrived (or
+    ///   whose buffer is full, or whose deadline passed), its state, the bytes received, and
+    ///   whether the deadline passed; should queue it for a worker and return promptly.
+    /// - `draining`: Checked on every wake-up; once `true`, idle persistent connections close.
+    /// - `stopping`: Checked on every wake-up; `true` ends the loop.
+    ///
+    /// **Returns:**
+    /// - `Ok(())` once stopped.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns the accept error, after shutting down as for a stop.
+    /// - Returns the OS error if the ring's own entries cannot be submitted at the start. A
+    ///   failed wait later on is logged and retried after a tick.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// ring.run(&listener, |stream| server.admit(stream), |stream, state, head, overdue| serve(stream, state, head, overdue), || server.is_draining(), || server.is_stopping())?;
+    /// ```
+    pub(crate) fn run(
+        &self,
+        listener: &TcpListener,
+        mut admit: impl FnMut(&TcpStream) -> Option<(T, Option<Duration>)>,
+        mut ready: impl FnMut(TcpStream, T, Vec<u8>, bool),
+        draining: impl Fn() -> bool,
+        stopping: impl Fn() -> bool,
+    ) -> io::Result<()> {
+        let mut ring = self.ring.lock().unwrap_or_else(PoisonError::into_inner);
+        let accept = Sqe { op_flags: libc::SOCK_CLOEXEC as u32, user_data: ACCEPT_TOKEN, ..Sqe::new(OP_ACCEPT, listener.as_raw_fd()) };
+        let (wake, tick) = (ring.wake_entry(self.queue.wake.as_raw_fd()), ring.tick_entry());
+        ring.push(&[wake, tick, accept])?;
+        let mut armed = Armed { wake: true, tick: true, accept: true };
+        let mut flight: HashMap<u64, Flight<T>> = HashMap::new();
+        let mut next_token = FIRST_TOKEN;
+        let (mut failure, mut stopped, mut drained, mut poked) = (None, false, false, false);
+        loop {
+            if let Err(error) = ring.enter(1) {
+                log::emit(LogLevel::Warn, format_args!("io_uring wait failed: {error}"));
+                thread::sleep(TICK);
+            }
+            while let Some(completion) = ring.reap() {
+                match completion.user_data {
+                    WAKE_TOKEN => armed.wake = false,
+                    TICK_TOKEN => {
+                        armed.tick = false;
+                        let now = Instant::now();
+                        for (&token, entry) in &mut flight {
+                            if let Flight::Head(waiting) = entry
+                                && !waiting.overdue
+                                && waiting.deadline.is_some_and(|deadline| deadline <= now)
+                            {
+                                if waiting.idle {
+                                    waiting.expire();
+                                } else if ring.push(&[Sqe { addr: token, user_data: CANCEL_TOKEN, ..Sqe::new(OP_ASYNC_CANCEL, -1) }]).is_ok() {
+                                    waiting.overdue = true;
+                                }
+                            }
+                        }
+                    }
+                    ACCEPT_TOKEN => {
+                        armed.accept = false;
+                        if completion.res < 0 {
+                            if completion.res != -libc::ECANCELED {
+                                failure.get_or_insert(io::Error::from_raw_os_error(-completion.res));
+                            }
+                            continue;
+                        }
+                        // SAFETY: the completed accept returned a fresh connected socket nothing
+                        // else owns.
+                        let stream = unsafe { TcpStream::from_raw_fd(completion.res) };
+                        if stopped || stopping() {
+                            continue;
+                        }
+                        if let Some((state, wait)) = admit(&stream) {
+                            let waiting = Waiting::new(stream, state, wait);
+                            receive(&mut ring, &mut flight, next(&mut next_token), waiting);
+                        }
+                    }
+                    TIMEOUT_TOKEN | CANCEL_TOKEN => {}
+                    token => match flight.remove(&token) {
+                        Some(Flight::Job(job)) => job.done.finish(completion.res),
+                        Some(Flight::Head(waiting)) => {
+                            if let Some(waiting) = waiting.received(completion.res, &mut ready) {
+                                receive(&mut ring, &mut flight, token, waiting);
+                            }
+                        }
+                        None => {}
+                    },
+                }
+            }
+
+            for job in self.queue.take() {
+                submit(&mut ring, &mut flight, next(&mut next_token), job);
+            }
+            let arrivals = mem::take(&mut self.parking.lock().unwrap_or_else(PoisonError::into_inner).arrivals);
+            for waiting in arrivals {
+                receive(&mut ring, &mut flight, next(&mut next_token), waiting);
+            }
+
+            if !stopped && (failure.is_some() || stopping()) {
+                stopped = true;
+                self.parking.lock().unwrap_or_else(PoisonError::into_inner).closed = true;
+                expire(&mut flight, |_| true);
+                if armed.accept {
+                    let _ = ring.push(&[Sqe { addr: ACCEPT_TOKEN, user_data: CANCEL_TOKEN, ..Sqe::new(OP_ASYNC_CANCEL, -1) }]);
+                }
+            } else if !drained && draining() {
+                drained = true;
+                self.parking.lock().unwrap_or_else(PoisonError::into_inner).closed = true;
+                expire(&mut flight, |waiting| waiting.idle);
+            }
+
+            let finished = stopped && flight.is_empty() && !armed.accept && self.queue.close();
+            if !armed.accept && !stopped {
+                armed.accept = ring.push(&[accept]).is_ok();
+            }
+            if !armed.tick && !stopped {
+                let tick = ring.tick_entry();
+                armed.tick = ring.push(&[tick]).is_ok();
+            }
+            if !armed.wake && !finished {
+                let wake = ring.wake_entry(self.queue.wake.as_raw_fd());
+                armed.wake = ring.push(&[wake]).is_ok();
+            }
+            if finished {
+                if armed.wake && !poked {
+                    poked = self.queue.wake().is_ok();
+                }
+                if !armed.wake && !armed.tick {
+                    break;
+                }
+            }
+        }
+        failure.map_or(Ok(()), Err)
+    }
+}
+
+
+impl RingQueue {
+
+    /// # new
+    ///
+    /// **Purpose:**
+    /// Creates an empty queue and its wake-up `eventfd`.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - A new `RingQueue`.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns the OS error if the `eventfd` cannot be created.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let queue = RingQueue::new()?;
+    /// ```
+    fn new() -> io::Result<RingQueue> {
+        // SAFETY: `eventfd` takes no pointers.
+        let fd = unsafe { libc::eventfd(0, libc::EFD_CLOEXEC) };
+        if fd < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        // SAFETY: the kernel returned a fresh descriptor nothing else owns.
+        let wake = unsafe { OwnedFd::from_raw_fd(fd) };
+        Ok(RingQueue { wake, jobs: Mutex::default() })
+    }
+
+    /// # recv
+    ///
+    /// **Purpose:**
+    /// Reads from a socket through the ring.
+    ///
+    /// **Parameters:**
+    /// - `stream`: Source socket.
+    /// - `buf`: Destination buffer.
+    /// - `wait`: Time limit, or `None` for no limit.
+    ///
+    /// **Returns:**
+    /// - The number of bytes read (`0` at end of stream).
+    ///
+    /// **Errors / Failures:**
+    /// - `WouldBlock` if the time limit runs out; `Unsupported` once the ring has stopped;
+    ///   other socket errors are passed through.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let read = queue.recv(stream, &mut buf, Some(Duration::from_secs(5)))?;
+    /// ```
+    pub(crate) fn recv(&self, stream: &TcpStream, buf: &mut [u8], wait: Option<Duration>) -> io::Result<usize> {
+        let entry = Sqe { addr: buf.as_mut_ptr() as u64, len: length(buf.len()), ..Sqe::new(OP_RECV, stream.as_raw_fd()) };
+        self.run(entry, wait).map(|read| read as usize)
+    }
+
+    /// # send
+    ///
+    /// **Purpose:**
+    /// Writes to a socket through the ring.
+    ///
+    /// **Parameters:**
+    /// - `stream`: Destination socket.
+    /// - `buf`: Bytes to send.
+    /// - `wait`: Time limit, or `None` for no limit.
+    ///
+    /// **Returns:**
+    /// - The number of bytes written.
+    ///
+    /// **Errors / Failures:**
+    /// - `WouldBlock` if the time limit runs out; `Unsupported` once the ring has stopped;
+    ///   other socket errors are passed through.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let written = queue.send(stream, b"HTTP/1.1 200 OK\r\n", None)?;
+    /// ```
+    pub(crate) fn send(&self, stream: &TcpStream, buf: &[u8], wait: Option<Duration>) -> io::Result<usize> {
+        let entry = Sqe {
+            addr: buf.as_ptr() as u64,
+            len: length(buf.len()),
+            op_flags: libc::MSG_NOSIGNAL as u32,
+            ..Sqe::new(OP_SEND, stream.as_raw_fd())
+        };
+        self.run(entry, wait).map(|written| written as usize)
+    }
+
+    /// # send_vectored
+    ///
+    /// **Purpose:**
+    /// Writes several buffers to a socket in one operation through the ring.
+    ///
+    /// **Parameters:**
+    /// - `stream`: Destination socket.
+    /// - `bufs`: Buffers to send, in order.
+    /// - `wait`: Time limit, or `None` for no limit.
+    ///
+    /// **Returns:**
+    /// - The number of bytes written, across the buffers.
+    ///
+    /// **Errors / Failures:**
+    /// - `WouldBlock` if the time limit runs out; `Unsupported` once the ring has stopped;
+    ///   other socket errors are passed through.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let written = queue.send_vectored(stream, &[IoSlice::new(&head), IoSlice::new(&body)], None)?;
+    /// ```
+    pub(crate) fn send_vectored(&self, stream: &TcpStream, bufs: &[IoSlice<'_>], wait: Option<Duration>) -> io::Result<usize> {
+        // SAFETY: `msghdr` is plain data for which all zeroes is a valid, empty message.
+        let mut message: libc::msghdr = unsafe { mem::zeroed() };
+        // `IoSlice` is guaranteed to be ABI compatible with `iovec` on Unix.
+        message.msg_iov = bufs.as_ptr().cast_mut().cast();
+        message.msg_iovlen = bufs.len() as _;
+        let entry = Sqe {
+            addr: (&raw const message) as u64,
+            len: 1,
+            op_flags: libc::MSG_NOSIGNAL as u32,
+            ..Sqe::new(OP_SENDMSG, stream.as_raw_fd())
+        };
+        self.run(entry, wait).map(|written| written as usize)
+    }
+
+    /// # send_file
+    ///
+    /// **Purpose:**
+    /// Sends up to one pipe's worth of a file to a socket without copying it through
+    /// userspace: splices it into this thread's pipe, then from the pipe to the socket. The
+    /// file position is left unchanged.
+    ///
+    /// **Parameters:**
+    /// - `stream`: Destination socket.
+    /// - `file`: Source file.
+    /// - `offset`: Position in the file of the first byte to send.
+    /// - `count`: Largest number of bytes to send; at most 64 KiB go per call.
+    /// - `wait`: Time limit for each splice into the socket.
+    ///
+    /// **Returns:**
+    /// - The number of bytes sent; `0` once `offset` reaches the end of the file.
+    ///
+    /// **Errors / Failures:**
+    /// - `WouldBlock` if the time limit runs out; `Unsupported` once the ring has stopped or
+    ///   if the file cannot be spliced; other socket and file errors are passed through.
+    /// - Returns the OS error if the pipe cannot be opened. A pipe left holding bytes by a
+    ///   failed send is replaced, so no stale bytes are sent later.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let sent = queue.send_file(stream, &file, 0, 1 << 20, None)?;
+    /// ```
+    pub(crate) fn send_file(&self, stream: &TcpStream, file: &File, offset: u64, count: usize, wait: Option<Duration>) -> io::Result<usize> {
+        PIPE.with(|slot| {
+            let mut slot = slot.borrow_mut();
+            let (read_end, write_end) = match &*slot {
+                Some((read_end, write_end)) => (read_end.as_raw_fd(), write_end.as_raw_fd()),
+                None => {
+                    let (read_end, write_end) = pipe()?;
+                    let ends = (read_end.as_raw_fd(), write_end.as_raw_fd());
+                    *slot = Some((read_end, write_end));
+                    ends
+                }
+            };
+            let filled = self.run(splice(file.as_raw_fd(), offset, write_end, count.min(SPLICE_CHUNK)), None)? as usize;
+            let mut drained = 0;
+            while drained < filled {
+                match self.run(splice(read_end, NO_OFFSET, stream.as_raw_fd(), filled - drained), wait) {
+                    Ok(moved) if moved > 0 => drained += moved as usize,
+                    moved => {
+                        *slot = None;
+                        let error = moved.err().unwrap_or_else(|| io::Error::from(io::ErrorKind::WriteZero));
+                        return if drained > 0 { Ok(drained) } else { Err(error) };
+                    }
+                }
+            }
+            Ok(filled)
+        })
+    }
+
+    /// # run
+    ///
+    /// **Purpose:**
+    /// Queues one operation for the ring thread and sleeps until it completes. The kernel may
+    /// use the buffers the entry points at until then, so the wait is never cut short.
+    ///
+    /// **Parameters:**
+    /// - `entry`: Entry to submit.
+    /// - `wait`: Time limit, or `None` for no limit.
+    ///
+    /// **Returns:**
+    /// - The operation's result.
+    ///
+    /// **Errors / Failures:**
+    /// - `Unsupported` once the ring has stopped, or for `EINVAL`, which is how kernels report
+    ///   operations they cannot do; callers then fall back to blocking calls.
+    /// - `WouldBlock` if the time limit cancelled the operation.
+    /// - Otherwise the operation's own error, or the OS error if the ring thread cannot be
+    ///   woken.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let read = self.run(entry, wait)?;
+    /// ```
+    fn run(&self, entry: Sqe, wait: Option<Duration>) -> io::Result<i32> {
+        let timeout = wait.map(|wait| {
+            Box::new(KernelTimespec {
+                tv_sec: i64::try_from(wait.as_secs()).unwrap_or(i64::MAX),
+                tv_nsec: i64::from(wait.subsec_nanos()),
+            })
+        });
+        let done = Arc::new(Done::default());
+        {
+            let mut jobs = self.jobs.lock().unwrap_or_else(PoisonError::into_inner);
+            if jobs.closed {
+                return Err(io::Error::new(io::ErrorKind::Unsupported, "io_uring stopped"));
+            }
+            let first = jobs.pending.is_empty();
+            jobs.pending.push(Job { entry, timeout, done: Arc::clone(&done) });
+            if first {
+                self.wake()?;
+            }
+        }
+        match done.wait() {
+            res if res == -libc::EINVAL => Err(io::Error::new(io::ErrorKind::Unsupported, "io_uring operation not supported")),
+            res if res == -libc::ECANCELED => Err(io::Error::from(io::ErrorKind::WouldBlock)),
+            res if res < 0 => Err(io::Error::from_raw_os_error(-res)),
+            res => Ok(res),
+        }
+    }
+
+    /// # wake
+    ///
+    /// **Purpose:**
+    /// Completes the ring thread's pending read on the `eventfd`, waking it.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - `Ok(())` once written.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns the OS error if the write fails.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// self.wake()?;
+    /// ```
+    fn wake(&self) -> io::Result<()> {
+        let one = 1u64;
+        // SAFETY: writes the eight bytes of `one` to the open `eventfd`.
+        let written = unsafe { libc::write(self.wake.as_raw_fd(), (&raw const one).cast(), mem::size_of::<u64>()) };
+        if written < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(())
+    }
+
+    /// # take
+    ///
+    /// **Purpose:**
+    /// Takes every queued operation, for the ring thread to submit.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - The operations, oldest first.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// for job in self.queue.take() { /* submit */ }
+    /// ```
+    fn take(&self) -> Vec<Job> {
+        mem::take(&mut self.jobs.lock().unwrap_or_else(PoisonError::into_inner).pending)
+    }
+
+    /// # close
+    ///
+    /// **Purpose:**
+    /// Turns later operations away, so workers use blocking calls, provided none is queued.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - `true` once the queue is closed; `false` if operations are still waiting.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let finished = flight.is_empty() && self.queue.close();
+    /// ```
+    fn close(&self) -> bool {
+        let mut jobs = self.jobs.lock().unwrap_or_else(PoisonError::into_inner);
+        jobs.closed |= jobs.pending.is_empty();
+        jobs.closed
+    }
+}
+
+
+impl Done {
+
+    /// # finish
+    ///
+    /// **Purpose:**
+    /// Delivers the result and wakes the waiting worker.
+    ///
+    /// **Parameters:**
+    /// - `res`: The completion's result.
+    ///
+    /// **Returns:**
+    /// None.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// job.done.finish(completion.res);
+    /// ```
+    fn finish(&self, res: i32) {
+        *self.result.lock().unwrap_or_else(PoisonError::into_inner) = Some(res);
+        self.finished.notify_one();
+    }
+
+    /// # wait
+    ///
+    /// **Purpose:**
+    /// Sleeps until the result is delivered.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - The completion's result.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let res = done.wait();
+    /// ```
+    fn wait(&self) -> i32 {
+        let mut result = self.result.lock().unwrap_or_else(PoisonError::into_inner);
+        loop {
+            if let Some(res) = *result {
+                return res;
+            }
+            result = self.finished.wait(result).unwrap_or_else(PoisonError::into_inner);
+        }
+    }
+}
+
+
+impl<T> Waiting<T> {
+
+    /// # new
+    ///
+    /// **Purpose:**
+    /// Starts waiting for a connection's request head.
+    ///
+    /// **Parameters:**
+    /// - `stream`: Client socket.
+    /// - `state`: State handed back with the socket.
+    /// - `wait`: Time allowed from now, or `None` for no limit.
+    ///
+    /// **Returns:**
+    /// - A `Waiting` with an empty buffer.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let waiting = Waiting::new(stream, state, Some(Duration::from_secs(20)));
+    /// ```
+    fn new(stream: TcpStream, state: T, wait: Option<Duration>) -> Waiting<T> {
+        Waiting {
+            stream,
+            state,
+            buf: Vec::new(),
+            deadline: wait.map(|wait| Instant::now() + wait),
+            head: None,
+            idle: false,
+            expired: false,
+            overdue: false,
+        }
+    }
+
+    /// # received
+    ///
+    /// **Purpose:**
+    /// Takes in the result of a receive: hands the connection to `ready` once its head is
+    /// complete or its buffer full, or as overdue once its receive was cancelled at the
+    /// deadline, and closes it if the peer closed, the receive failed, or the connection
+    /// expired.
+    ///
+    /// **Parameters:**
+    /// - `res`: The receive's result.
+    /// - `ready`: Called with the connection, its state, its bytes, and whether it is overdue
+    ///   when it is ready.
+    ///
+    /// **Returns:**
+    /// - The connection, to receive more for; `None` once handed over or closed.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// if let Some(waiting) = waiting.received(completion.res, &mut ready) { /* receive again */ }
+    /// ```
+    fn received(mut self, res: i32, ready: &mut impl FnMut(TcpStream, T, Vec<u8>, bool)) -> Option<Waiting<T>> {
+        if self.overdue && res == -libc::ECANCELED && !self.expired {
+            ready(self.stream, self.state, self.buf, true);
+            return None;
+        }
+        if res <= 0 || self.expired {
+            return None;
+        }
+        // SAFETY: the kernel initialised `res` bytes of the spare capacity the receive was
+        // given, which `receive` sized within the buffer's capacity.
+        unsafe { self.buf.set_len(self.buf.len() + res as usize) };
+        if mem::take(&mut self.idle) {
+            self.deadline = self.head.map(|head| Instant::now() + head);
+        }
+        if self.buf.len() >= PRELOAD_LIMIT || head_complete(&self.buf) {
+            ready(self.stream, self.state, self.buf, false);
+            return None;
+        }
+        if self.overdue {
+            ready(self.stream, self.state, self.buf, true);
+            return None;
+        }
+        Some(self)
+    }
+
+    /// # expire
+    ///
+    /// **Purpose:**
+    /// Shuts the socket down, which ends the pending receive with end of stream; the
+    /// connection is then closed instead of handed over.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// None.
+    ///
+    /// **Errors / Failures:**
+    /// None. A failed shutdown leaves the connection to its peer.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// waiting.expire();
+    /// ```
+    fn expire(&mut self) {
+        if !self.expired {
+            self.expired = true;
+            let _ = self.stream.shutdown(Shutdown::Both);
+        }
+    }
+}
+
+
+impl Sqe {
+
+    /// # new
+    ///
+    /// **Purpose:**
+    /// Starts an entry for an operation.
+    ///
+    /// **Parameters:**
+    /// - `opcode`: Operation to perform.
+    /// - `fd`: Descriptor operated on.
+    ///
+    /// **Returns:**
+    /// - An entry with every other field zero.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let entry = Sqe { addr: buf.as_mut_ptr() as u64, len: length(buf.len()), ..Sqe::new(OP_RECV, fd) };
+    /// ```
+    fn new(opcode: u8, fd: RawFd) -> Sqe {
+        Sqe { opcode, fd, ..Sqe::default() }
+    }
+}
+
+
+impl Region {
+
+    /// # map
+    ///
+    /// **Purpose:**
+    /// Maps part of a ring into this process.
+    ///
+    /// **Parameters:**
+    /// - `ring`: Ring descriptor.
+    /// - `len`: Bytes to map.
+    /// - `offset`: Which part of the ring to map (`OFF_SQ_RING`, `OFF_CQ_RING`, or `OFF_SQES`).
+    ///
+    /// **Returns:**
+    /// - The mapping.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns the OS error if mapping fails.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let sqes = Region::map(&fd, entries * mem::size_of::<Sqe>(), OFF_SQES)?;
+    /// ```
+    fn map(ring: &OwnedFd, len: usize, offset: libc::off_t) -> io::Result<Region> {
+        // SAFETY: a fresh shared mapping of the ring's memory at an offset the kernel defines;
+        // it stays valid until `munmap` in `drop`.
+        let address = unsafe {
+            libc::mmap(
+                ptr::null_mut(),
+                len,
+                libc::PROT_READ | libc::PROT_WRITE,
+                libc::MAP_SHARED | libc::MAP_POPULATE,
+                ring.as_raw_fd(),
+                offset,
+            )
+        };
+        if address == libc::MAP_FAILED {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(Region { address: address.cast(), len })
+    }
+
+    /// # word
+    ///
+    /// **Purpose:**
+    /// Borrows a queue field shared with the kernel.
+    ///
+    /// **Parameters:**
+    /// - `offset`: Field offset reported in `Params`.
+    ///
+    /// **Returns:**
+    /// - The field, as an atomic so kernel updates are observed.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let tail = self.sq.word(self.params.sq_off.tail).load(Ordering::Acquire);
+    /// ```
+    fn word(&self, offset: u32) -> &AtomicU32 {
+        // SAFETY: the kernel places its aligned 32-bit queue fields at these offsets inside the
+        // mapping, which outlives the borrow.
+        unsafe { &*self.address.add(offset as usize).cast::<AtomicU32>() }
+    }
+}
+
+
+impl Drop for Region {
+
+    /// # drop
+    ///
+    /// **Purpose:**
+    /// Unmaps the ring memory.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// None.
+    ///
+    /// **Errors / Failures:**
+    /// None. A failed `munmap` leaks the mapping.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// drop(region);
+    /// ```
+    fn drop(&mut self) {
+        // SAFETY: the mapping was created by `map` with this address and length and is not
+        // used again.
+        unsafe {
+            libc::munmap(self.address.cast(), self.len);
+        }
+    }
+}
+
+
+impl Ring {
+
+    /// # new
+    ///
+    /// **Purpose:**
+    /// Creates a ring and maps its queues.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - The ring.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns the OS error if the kernel refuses the ring or its memory cannot be mapped.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let ring = Ring::new()?;
+    /// ```
+    fn new() -> io::Result<Ring> {
+        let mut params = Params::default();
+        // SAFETY: `params` is a zeroed `io_uring_params` the kernel fills in.
+        let fd = unsafe { libc::syscall(libc::SYS_io_uring_setup, ENTRIES, &raw mut params) };
+        if fd < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        // SAFETY: the kernel returned a fresh descriptor nothing else owns.
+        let fd = unsafe { OwnedFd::from_raw_fd(fd as RawFd) };
+        let sq_len = params.sq_off.array as usize + params.sq_entries as usize * mem::size_of::<u32>();
+        let cq_len = params.cq_off.cqes as usize + params.cq_entries as usize * mem::size_of::<Cqe>();
+        let (sq, cq) = if params.features & FEAT_SINGLE_MMAP != 0 {
+            (Region::map(&fd, sq_len.max(cq_len), OFF_SQ_RING)?, None)
+        } else {
+            (Region::map(&fd, sq_len, OFF_SQ_RING)?, Some(Region::map(&fd, cq_len, OFF_CQ_RING)?))
+        };
+        let sqes = Region::map(&fd, params.sq_entries as usize * mem::size_of::<Sqe>(), OFF_SQES)?;
+        let tick = Box::new(KernelTimespec { tv_sec: TICK.as_secs() as i64, tv_nsec: i64::from(TICK.subsec_nanos()) });
+        Ok(Ring { fd, params, sq, cq, sqes, queued: 0, wake_count: Box::new(0), tick })
+    }
+
+    /// # wake_entry
+    ///
+    /// **Purpose:**
+    /// Builds the read on the wake-up `eventfd`.
+    ///
+    /// **Parameters:**
+    /// - `wake`: The `eventfd`.
+    ///
+    /// **Returns:**
+    /// - The entry, reading into the ring's own buffer.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// ring.push(&[ring.wake_entry(self.queue.wake.as_raw_fd())])?;
+    /// ```
+    fn wake_entry(&mut self, wake: RawFd) -> Sqe {
+        Sqe {
+            off: NO_OFFSET,
+            addr: (&raw mut *self.wake_count) as u64,
+            len: mem::size_of::<u64>() as u32,
+            user_data: WAKE_TOKEN,
+            ..Sqe::new(OP_READ, wake)
+        }
+    }
+
+    /// # tick_entry
+    ///
+    /// **Purpose:**
+    /// Builds the timer, which completes one tick after submission.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - The entry.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// ring.push(&[ring.tick_entry()])?;
+    /// ```
+    fn tick_entry(&self) -> Sqe {
+        Sqe { addr: (&raw const *self.tick) as u64, len: 1, user_data: TICK_TOKEN, ..Sqe::new(OP_TIMEOUT, -1) }
+    }
+
+    /// # push
+    ///
+    /// **Purpose:**
+    /// Writes entries into the submission queue, to go with the next `enter`. If they do not
+    /// all fit, what is already queued is submitted first, so linked entries stay together.
+    ///
+    /// **Parameters:**
+    /// - `entries`: Entries to queue, in order.
+    ///
+    /// **Returns:**
+    /// - `Ok(())` once queued.
+    ///
+    /// **Errors / Failures:**
+    /// - `WouldBlock` if the queue is still too full; returns the OS error if submitting fails.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// ring.push(&[entry, timeout])?;
+    /// ```
+    fn push(&mut self, entries: &[Sqe]) -> io::Result<()> {
+        let count = entries.len() as u32;
+        if self.params.sq_entries - self.queued < count {
+            self.enter(0)?;
+        }
+        if self.params.sq_entries - self.queued < count {
+            return Err(io::Error::new(io::ErrorKind::WouldBlock, "io_uring submission queue full"));
+        }
+        let sq_off = &self.params.sq_off;
+        let tail = self.sq.word(sq_off.tail).load(Ordering::Relaxed);
+        let mask = self.sq.word(sq_off.ring_mask).load(Ordering::Relaxed);
+        let sqes = self.sqes.address.cast::<Sqe>();
+        for (n, entry) in (0..).zip(entries) {
+            let index = tail.wrapping_add(n) & mask;
+            // SAFETY: `index` is masked into both arrays, and the slot is free: no more than
+            // `sq_entries` entries are ever queued, and the kernel consumes what it submits.
+            unsafe {
+                sqes.add(index as usize).write(*entry);
+                self.sq.address.add(sq_off.array as usize).cast::<u32>().add(index as usize).write(index);
+            }
+        }
+        self.sq.word(sq_off.tail).store(tail.wrapping_add(count), Ordering::Release);
+        self.queued += count;
+        Ok(())
+    }
+
+    /// # enter
+    ///
+    /// **Purpose:**
+    /// Submits every queued entry with `io_uring_enter(2)` and, if asked, waits for
+    /// completions.
+    ///
+    /// **Parameters:**
+    /// - `wait`: Completions to wait for; `0` only submits.
+    ///
+    /// **Returns:**
+    /// - `Ok(())`, including when a signal or a full completion queue cut the call short; the
+    ///   caller reaps and calls again.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns the OS error for any other failure.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// ring.enter(1)?;
+    /// ```
+    fn enter(&mut self, wait: u32) -> io::Result<()> {
+        let flags = if wait > 0 { ENTER_GETEVENTS } else { 0 };
+        // SAFETY: the ring descriptor is open and no signal mask is passed.
+        let entered = unsafe {
+            libc::syscall(libc::SYS_io_uring_enter, self.fd.as_raw_fd(), self.queued, wait, flags, ptr::null::<libc::c_void>(), 0usize)
+        };
+        if entered < 0 {
+            let error = io::Error::last_os_error();
+            return match error.raw_os_error() {
+                Some(libc::EINTR | libc::EAGAIN | libc::EBUSY) => Ok(()),
+                _ => Err(error),
+            };
+        }
+        self.queued -= (entered as u32).min(self.queued);
+        Ok(())
+    }
+
+    /// # reap
+    ///
+    /// **Purpose:**
+    /// Takes the oldest completion off the completion queue.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - The completion, or `None` if the queue is empty.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// while let Some(completion) = ring.reap() { /* ... */ }
+    /// ```
+    fn reap(&self) -> Option<Cqe> {
+        let cq = self.cq.as_ref().unwrap_or(&self.sq);
+        let cq_off = &self.params.cq_off;
+        let head = cq.word(cq_off.head).load(Ordering::Relaxed);
+        if head == cq.word(cq_off.tail).load(Ordering::Acquire) {
+            return None;
+        }
+        let index = head & cq.word(cq_off.ring_mask).load(Ordering::Relaxed);
+        // SAFETY: `index` is masked into the completion array, and the kernel finished writing
+        // this entry before publishing the tail read above.
+        let completion = unsafe { cq.address.add(cq_off.cqes as usize).cast::<Cqe>().add(index as usize).read() };
+        cq.word(cq_off.head).store(head.wrapping_add(1), Ordering::Release);
+        Some(completion)
+    }
+}
+
+
+/// # submit
+///
+/// **Purpose:**
+/// Queues a worker operation on the ring, linked to its timeout if it has one. If it cannot
+/// be queued the worker is woken with the error.
+///
+/// **Parameters:**
+/// - `ring`: The ring.
+/// - `flight`: Entries in flight, which the operation joins.
+/// - `token`: User data for the operation.
+/// - `job`: The operation.
+///
+/// **Returns:**
+/// None.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// submit(&mut ring, &mut flight, next(&mut next_token), job);
+/// ```
+fn submit<T>(ring: &mut Ring, flight: &mut HashMap<u64, Flight<T>>, token: u64, mut job: Job) {
+    job.entry.user_data = token;
+    let pushed = match &job.timeout {
+        Some(timespec) => {
+            job.entry.flags |= IOSQE_IO_LINK;
+            let timeout = Sqe { addr: (&raw const **timespec) as u64, len: 1, user_data: TIMEOUT_TOKEN, ..Sqe::new(OP_LINK_TIMEOUT, -1) };
+            ring.push(&[job.entry, timeout])
+        }
+        None => ring.push(&[job.entry]),
+    };
+    match pushed {
+        Ok(()) => {
+            flight.insert(token, Flight::Job(job));
+        }
+        Err(error) => job.done.finish(-error.raw_os_error().unwrap_or(libc::EAGAIN)),
+    }
+}
+
+
+/// # receive
+///
+/// **Purpose:**
+/// Queues a receive into a waiting connection's buffer, growing it by up to one chunk. If it
+/// cannot be queued the connection is closed.
+///
+/// **Parameters:**
+/// - `ring`: The ring.
+/// - `flight`: Entries in flight, which the connection joins.
+/// - `token`: User data for the receive.
+/// - `waiting`: The connection.
+///
+/// **Returns:**
+/// None.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// receive(&mut ring, &mut flight, token, waiting);
+/// ```
+fn receive<T>(ring: &mut Ring, flight: &mut HashMap<u64, Flight<T>>, token: u64, mut waiting: Waiting<T>) {
+    let room = HEAD_CHUNK.min(PRELOAD_LIMIT - waiting.buf.len());
+    waiting.buf.reserve(room);
+    let spare = waiting.buf.spare_capacity_mut();
+    let entry = Sqe {
+        addr: spare.as_mut_ptr() as u64,
+        len: length(spare.len().min(room)),
+        user_data: token,
+        ..Sqe::new(OP_RECV, waiting.stream.as_raw_fd())
+    };
+    if ring.push(&[entry]).is_ok() {
+        flight.insert(token, Flight::Head(waiting));
+    }
+}
+
+
+/// # expire
+///
+/// **Purpose:**
+/// Shuts down the waiting connections that match, so each is closed when its receive ends.
+///
+/// **Parameters:**
+/// - `flight`: Entries in flight.
+/// - `matches`: Which waiting connections to close.
+///
+/// **Returns:**
+/// None.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// expire(&mut flight, |waiting| waiting.idle);
+/// ```
+fn expire<T>(flight: &mut HashMap<u64, Flight<T>>, matches: impl Fn(&Waiting<T>) -> bool) {
+    for entry in flight.values_mut() {
+        if let Flight::Head(waiting) = entry
+            && matches(waiting)
+        {
+            waiting.expire();
+        }
+    }
+}
+
+
+/// # next
+///
+/// **Purpose:**
+/// Hands out the next user data.
+///
+/// **Parameters:**
+/// - `next_token`: Counter of user data handed out.
+///
+/// **Returns:**
+/// - A token not yet in use.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let token = next(&mut next_token);
+/// ```
+fn next(next_token: &mut u64) -> u64 {
+    let token = *next_token;
+    *next_token += 1;
+    token
+}
+
+
+/// # head_complete
+///
+/// **Purpose:**
+/// Checks whether received bytes hold the blank line ending a request head.
+///
+/// **Parameters:**
+/// - `buf`: Bytes received.
+///
+/// **Returns:**
+/// - `true` if a head is complete.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// assert!(head_complete(b"GET / HTTP/1.1\r\nHost: a\r\n\r\n"));
+/// ```
+fn head_complete(buf: &[u8]) -> bool {
+    buf.windows(2).any(|window| window == b"\n\n") || buf.windows(3).any(|window| window == b"\n\r\n")
+}
+
+
+/// # splice
+///
+/// **Purpose:**
+/// Builds a splice entry moving bytes from one descriptor to another.
+///
+/// **Parameters:**
+/// - `from`: Input descriptor.
+/// - `from_offset`: Input offset, or `NO_OFFSET` for a pipe.
+/// - `to`: Output descriptor; its own position is used.
+/// - `len`: Largest number of bytes to move.
+///
+/// **Returns:**
+/// - The entry.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let entry = splice(file, 0, write_end, 64 * 1024);
+/// ```
+fn splice(from: RawFd, from_offset: u64, to: RawFd, len: usize) -> Sqe {
+    Sqe {
+        off: NO_OFFSET,
+        addr: from_offset,
+        len: length(len),
+        op_flags: SPLICE_F_MOVE,
+        splice_fd_in: from,
+        ..Sqe::new(OP_SPLICE, to)
+    }
+}
+
+
+/// # pipe
+///
+/// **Purpose:**
+/// Opens the pipe file bytes are spliced through.
+///
+/// **Parameters:**
+/// None.
+///
+/// **Returns:**
+/// - The read and write ends.
+///
+/// **Errors / Failures:**
+/// - Returns the OS error if the pipe cannot be created.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let (read_end, write_end) = pipe()?;
+/// ```
+fn pipe() -> io::Result<(OwnedFd, OwnedFd)> {
+    let mut fds: [RawFd; 2] = [-1; 2];
+    // SAFETY: `fds` has room for the two descriptors `pipe2` writes.
+    if unsafe { libc::pipe2(fds.as_mut_ptr(), libc::O_CLOEXEC) } < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    // SAFETY: both descriptors are fresh and owned by nothing else.
+    Ok(unsafe { (OwnedFd::from_raw_fd(fds[0]), OwnedFd::from_raw_fd(fds[1])) })
+}
+
+
+/// # length
+///
+/// **Purpose:**
+/// Clamps a buffer length to the 32 bits an entry holds; the kernel may return short counts.
+///
+/// **Parameters:**
+/// - `len`: Buffer length.
+///
+/// **Returns:**
+/// - `len`, or `u32::MAX` if larger.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let entry = Sqe { len: length(buf.len()), ..Sqe::new(OP_RECV, fd) };
+/// ```
+fn length(len: usize) -> u32 {
+    u32::try_from(len).unwrap_or(u32::MAX)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        io::{Read, Write},
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            mpsc,
+        },
+    };
+
+    type Ready = mpsc::Receiver<(TcpStream, &'static str, Vec<u8>, bool)>;
+
+    struct Running {
+        ring: Arc<Uring<&'static str>>,
+        addr: std::net::SocketAddr,
+        ready: Ready,
+        draining: Arc<AtomicBool>,
+        stopping: Arc<AtomicBool>,
+        thread: thread::JoinHandle<io::Result<()>>,
+    }
+
+    fn start(wait: Option<Duration>) -> Running {
+        let ring = Arc::new(Uring::new().unwrap());
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (sender, ready) = mpsc::channel();
+        let (draining, stopping) = (Arc::new(AtomicBool::new(false)), Arc::new(AtomicBool::new(false)));
+        let (driver, drain, stop) = (Arc::clone(&ring), Arc::clone(&draining), Arc::clone(&stopping));
+        let thread = thread::spawn(move || {
+            driver.run(
+                &listener,
+                |_| Some(("fresh", wait)),
+                |stream, state, head, overdue| sender.send((stream, state, head, overdue)).unwrap(),
+                || drain.load(Ordering::Acquire),
+                || stop.load(Ordering::Acquire),
+            )
+        });
+        Running { ring, addr, ready, draining, stopping, thread }
+    }
+
+    fn connect(addr: std::net::SocketAddr) -> TcpStream {
+        let client = TcpStream::connect(addr).unwrap();
+        client.set_read_timeout(Some(Duration::from_secs(10))).unwrap();
+        client
+    }
+
+    fn stop(running: Running) {
+        running.stopping.store(true, Ordering::Release);
+        drop(TcpStream::connect(running.addr));
+        running.thread.join().unwrap().unwrap();
+    }
+
+    #[test]
+    fn connections_are_handed_over_once_their_head_is_complete() {
+        let running = start(Some(Duration::from_secs(10)));
+        let mut client = connect(running.addr);
+        client.write_all(b"GET / HTTP/1.1\r\n").unwrap();
+        assert!(running.ready.recv_timeout(Duration::from_millis(300)).is_err());
+
+        client.write_all(b"Host: a\r\n\r\n").unwrap();
+        let (_, state, head, _) = running.ready.recv_timeout(Duration::from_secs(10)).unwrap();
+        assert_eq!(state, "fresh");
+        assert_eq!(head, b"GET / HTTP/1.1\r\nHost: a\r\n\r\n");
+        stop(running);
+    }
+
+    #[test]
+    fn worker_operations_complete_through_the_ring() {
+        let running = start(Some(Duration::from_secs(10)));
+        let mut client = connect(running.addr);
+        client.write_all(b"GET / HTTP/1.1\r\n\r\n").unwrap();
+        let (stream, _, _, _) = running.ready.recv_timeout(Duration::from_secs(10)).unwrap();
+        let queue = running.ring.queue();
+
+        client.write_all(b"body").unwrap();
+        let mut buf = [0; 16];
+        let read = queue.recv(&stream, &mut buf, Some(Duration::from_secs(10))).unwrap();
+        assert_eq!(&buf[..read], b"body");
+        queue.send(&stream, b"one ", None).unwrap();
+        queue.send_vectored(&stream, &[IoSlice::new(b"two "), IoSlice::new(b"three ")], None).unwrap();
+        let path = std::env::temp_dir().join(format!("uring-send-file-{}", std::process::id()));
+        std::fs::write(&path, b"xxfour").unwrap();
+        let sent = queue.send_file(&stream, &File::open(&path).unwrap(), 2, 16, None).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(sent, 4);
+        drop(stream);
+
+        let mut reply = String::new();
+        client.read_to_string(&mut reply).unwrap();
+        assert_eq!(reply, "one two three four");
+        stop(running);
+    }
+
+    #[test]
+    fn worker_operations_time_out_and_fall_back_once_stopped() {
+        let running = start(Some(Duration::from_secs(10)));
+        let mut client = connect(running.addr);
+        client.write_all(b"GET / HTTP/1.1\r\n\r\n").unwrap();
+        let (stream, _, _, _) = running.ready.recv_timeout(Duration::from_secs(10)).unwrap();
+        let ring = Arc::clone(&running.ring);
+
+        let mut buf = [0; 16];
+        let error = ring.queue().recv(&stream, &mut buf, Some(Duration::from_millis(100))).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::WouldBlock);
+        stop(running);
+        let error = ring.queue().recv(&stream, &mut buf, None).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::Unsupported);
+    }
+
+    #[test]
+    fn overdue_heads_are_handed_over_and_idle_connections_closed_at_their_deadline() {
+        let running = start(Some(Duration::from_millis(300)));
+        let mut client = connect(running.addr);
+        client.write_all(b"GET / HTTP/1.1\r\n").unwrap();
+        let (stream, state, head, overdue) = running.ready.recv_timeout(Duration::from_secs(10)).unwrap();
+        assert_eq!((state, &head[..], overdue), ("fresh", &b"GET / HTTP/1.1\r\n"[..], true));
+
+        running.ring.park(stream, Duration::from_millis(300), None, "kept").unwrap();
+        let mut buf = [0; 16];
+        assert_eq!(client.read(&mut buf).unwrap(), 0);
+        assert!(running.ready.try_recv().is_err());
+        stop(running);
+    }
+
+    #[test]
+    fn draining_closes_parked_connections_and_refuses_new_ones() {
+        let running = start(None);
+        let (mut parked, mut late) = (connect(running.addr), connect(running.addr));
+        for client in [&mut parked, &mut late] {
+            client.write_all(b"GET / HTTP/1.1\r\n\r\n").unwrap();
+        }
+        let (first, _, _, _) = running.ready.recv_timeout(Duration::from_secs(10)).unwrap();
+        let (second, _, _, _) = running.ready.recv_timeout(Duration::from_secs(10)).unwrap();
+        let (first, second) = if first.peer_addr().unwrap() == parked.local_addr().unwrap() { (first, second) } else { (second, first) };
+
+        running.ring.park(first, Duration::from_secs(60), None, "kept").unwrap();
+        parked.write_all(b"GET /next HTTP/1.1\r\n\r\n").unwrap();
+        let (first, state, head, overdue) = running.ready.recv_timeout(Duration::from_secs(10)).unwrap();
+        assert_eq!((state, &head[..], overdue), ("kept", &b"GET /next HTTP/1.1\r\n\r\n"[..], false));
+        running.ring.park(first, Duration::from_secs(60), None, "kept").unwrap();
+
+        running.draining.store(true, Ordering::Release);
+        let mut buf = [0; 16];
+        assert_eq!(parked.read(&mut buf).unwrap(), 0);
+        running.ring.park(second, Duration::from_secs(60), None, "kept").unwrap();
+        assert_eq!(late.read(&mut buf).unwrap(), 0);
+        stop(running);
+    }
+
+    #[test]
+    fn heads_are_recognised_by_their_blank_line() {
+        assert!(head_complete(b"GET / HTTP/1.1\r\nHost: a\r\n\r\n"));
+        assert!(head_complete(b"GET / HTTP/1.0\n\n"));
+        assert!(!head_complete(b"GET / HTTP/1.1\r\nHost: a\r\n"));
+    }
+}
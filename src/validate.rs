@@ -0,0 +1,651 @@
+
+// ============================================================
+//  DAEGONICA SOFTWARE — validate.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: Validate
+//!
+//! **Purpose:**
+//! Checks parsed request data against declared constraints and reports every failing field
+//! in one consistent `422 Unprocessable Entity` body.
+//!
+//! **Context:**
+//! - Types implement `Validate`; handlers take `Valid<Query<T>>`, `Valid<Path<T>>`, or (with the
+//!   `json` feature) `Valid<Json<T>>` to have the value validated before they run.
+//!
+//! **Responsibilities:**
+//! - Collects field errors with common checks (presence, length, range).
+//! - Renders the collected errors as a machine-readable JSON response.
+//! - Does NOT parse values; extraction failures stay `400 Bad Request`.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+use std::fmt::{Display, Write};
+
+use crate::{
+    extract::{FromRequest, Path, Query},
+    request::Request,
+    response::Response,
+};
+
+
+/// # Validate
+///
+/// **Summary:**
+/// A value whose contents can be checked after parsing.
+///
+/// **Usage Example:**
+/// ```rust
+/// # use server::validate::{Validate, ValidationErrors};
+/// # struct NewUser { name: String, age: u32, email: String }
+/// impl Validate for NewUser {
+///     fn validate(&self) -> Result<(), ValidationErrors> {
+///         let mut errors = ValidationErrors::new();
+///         errors.length("name", &self.name, 1, 64);
+///         errors.range("age", self.age, 13, 130);
+///         errors.check("email", self.email.contains('@'), "must be an email address");
+///         errors.into_result()
+///     }
+/// }
+/// # let user = NewUser { name: "Ana".into(), age: 12, email: "ana".into() };
+/// # assert_eq!(user.validate().unwrap_err().iter().count(), 2);
+/// ```
+pub trait Validate {
+    /// # validate
+    ///
+    /// **Purpose:**
+    /// Checks the value's constraints.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - `Ok(())` if every constraint holds.
+    ///
+    /// **Errors / Failures:**
+    /// - `ValidationErrors` listing each failing field.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::validate::{Validate, ValidationErrors};
+    /// # struct NewUser { name: String }
+    /// # impl Validate for NewUser {
+    /// #     fn validate(&self) -> Result<(), ValidationErrors> {
+    /// #         let mut errors = ValidationErrors::new();
+    /// #         errors.length("name", &self.name, 1, 64);
+    /// #         errors.into_result()
+    /// #     }
+    /// # }
+    /// # let user = NewUser { name: "Ana".into() };
+    /// user.validate()?;
+    /// # Ok::<(), ValidationErrors>(())
+    /// ```
+    fn validate(&self) -> Result<(), ValidationErrors>;
+}
+
+
+/// # FieldError
+///
+/// **Summary:**
+/// One failed constraint.
+///
+/// **Fields:**
+/// - `field`: Name of the offending field.
+/// - `message`: Human-readable explanation.
+///
+/// **Usage Example:**
+/// ```rust
+/// # use server::validate::ValidationErrors;
+/// # let mut errors = ValidationErrors::new();
+/// # errors.add("name", "must not be empty");
+/// for error in errors.iter() {
+///     println!("{}: {}", error.field, error.message);
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldError {
+    pub field: String,
+    pub message: String,
+}
+
+
+/// # ValidationErrors
+///
+/// **Summary:**
+/// Collector for field errors, rendered as a `422` response.
+///
+/// **Fields:**
+/// - `errors`: Failed constraints in the order they were checked.
+///
+/// **Usage Example:**
+/// ```rust
+/// # use server::{response::Response, validate::ValidationErrors};
+/// # struct Order { quantity: u32 }
+/// # fn create(order: &Order) -> Response {
+/// let mut errors = ValidationErrors::new();
+/// errors.check("quantity", order.quantity > 0, "must be positive");
+/// if let Err(errors) = errors.into_result() {
+///     return errors.to_response();
+/// }
+/// # Response::new(201, "Created")
+/// # }
+/// # assert_eq!(create(&Order { quantity: 0 }).status, 422);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ValidationErrors {
+    errors: Vec<FieldError>,
+}
+
+
+/// # Valid
+///
+/// **Summary:**
+/// Extractor wrapper that validates the inner extractor's value, rejecting with `422` on failure.
+///
+/// **Usage Example:**
+/// ```rust
+/// # use server::{response::Response, validate::Valid};
+/// # use server::extract::{FromQuery, Query, QueryParams};
+/// # use server::validate::{Validate, ValidationErrors};
+/// # struct SearchParams { q: String }
+/// # impl FromQuery for SearchParams {
+/// #     fn from_query(query: &QueryParams) -> Result<SearchParams, String> { Ok(SearchParams { q: query.require("q")? }) }
+/// # }
+/// # impl Validate for SearchParams {
+/// #     fn validate(&self) -> Result<(), ValidationErrors> {
+/// #         let mut errors = ValidationErrors::new();
+/// #         errors.length("q", &self.q, 1, 100);
+/// #         errors.into_result()
+/// #     }
+/// # }
+/// # fn run_search(params: &SearchParams) -> String { params.q.clone() }
+/// fn search(Valid(Query(params)): Valid<Query<SearchParams>>) -> Response {
+///     Response::new(200, "OK").with_body(run_search(&params))
+/// }
+/// ```
+pub struct Valid<E>(pub E);
+
+
+impl ValidationErrors {
+
+    /// # new
+    ///
+    /// **Purpose:**
+    /// Creates an empty collector.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - A new `ValidationErrors`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::validate::ValidationErrors;
+    /// let mut errors = ValidationErrors::new();
+    /// ```
+    pub fn new() -> ValidationErrors {
+        ValidationErrors::default()
+    }
+
+    /// # add
+    ///
+    /// **Purpose:**
+    /// Records a failed constraint.
+    ///
+    /// **Parameters:**
+    /// - `field`: Name of the offending field.
+    /// - `message`: Explanation.
+    ///
+    /// **Returns:**
+    /// None.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::validate::ValidationErrors;
+    /// # let mut errors = ValidationErrors::new();
+    /// errors.add("password", "must differ from the username");
+    /// # assert!(!errors.is_empty());
+    /// ```
+    pub fn add(&mut self, field: impl Into<String>, message: impl Into<String>) {
+        self.errors.push(FieldError {
+            field: field.into(),
+            message: message.into(),
+        });
+    }
+
+    /// # check
+    ///
+    /// **Purpose:**
+    /// Records `message` for `field` unless `condition` holds.
+    ///
+    /// **Parameters:**
+    /// - `field`: Field being checked.
+    /// - `condition`: Constraint result.
+    /// - `message`: Explanation used when the constraint fails.
+    ///
+    /// **Returns:**
+    /// None.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::validate::ValidationErrors;
+    /// # let mut errors = ValidationErrors::new();
+    /// # let email = "ana@example.com";
+    /// errors.check("email", email.contains('@'), "must be an email address");
+    /// # assert!(errors.is_empty());
+    /// ```
+    pub fn check(&mut self, field: &str, condition: bool, message: &str) {
+        if !condition {
+            self.add(field, message);
+        }
+    }
+
+    /// # length
+    ///
+    /// **Purpose:**
+    /// Checks that a string has between `min` and `max` characters (inclusive).
+    ///
+    /// **Parameters:**
+    /// - `field`: Field being checked.
+    /// - `value`: Field value.
+    /// - `min`: Fewest allowed characters.
+    /// - `max`: Most allowed characters.
+    ///
+    /// **Returns:**
+    /// None.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::validate::ValidationErrors;
+    /// # struct User { name: String }
+    /// # let user = User { name: String::new() };
+    /// # let mut errors = ValidationErrors::new();
+    /// errors.length("name", &user.name, 1, 64);
+    /// # assert_eq!(errors.iter().next().unwrap().field, "name");
+    /// ```
+    pub fn length(&mut self, field: &str, value: &str, min: usize, max: usize) {
+        let length = value.chars().count();
+        if length < min || length > max {
+            self.add(field, format!("must be between {min} and {max} characters long"));
+        }
+    }
+
+    /// # range
+    ///
+    /// **Purpose:**
+    /// Checks that a value lies between `min` and `max` (inclusive).
+    ///
+    /// **Parameters:**
+    /// - `field`: Field being checked.
+    /// - `value`: Field value.
+    /// - `min`: Smallest allowed value.
+    /// - `max`: Largest allowed value.
+    ///
+    /// **Returns:**
+    /// None.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::validate::ValidationErrors;
+    /// # struct Params { page_size: u32 }
+    /// # let params = Params { page_size: 500 };
+    /// # let mut errors = ValidationErrors::new();
+    /// errors.range("page_size", params.page_size, 1, 100);
+    /// # assert!(!errors.is_empty());
+    /// ```
+    pub fn range<T: PartialOrd + Display>(&mut self, field: &str, value: T, min: T, max: T) {
+        if value < min || value > max {
+            self.add(field, format!("must be between {min} and {max}"));
+        }
+    }
+
+    /// # is_empty
+    ///
+    /// **Purpose:**
+    /// Checks whether every constraint so far has held.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - `true` if no errors were recorded.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::validate::ValidationErrors;
+    /// # let errors = ValidationErrors::new();
+    /// assert!(errors.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// # iter
+    ///
+    /// **Purpose:**
+    /// Iterates over the recorded errors in check order.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - An iterator of `FieldError`s.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::validate::ValidationErrors;
+    /// # let mut errors = ValidationErrors::new();
+    /// # errors.add("name", "must not be empty");
+    /// let fields: Vec<&str> = errors.iter().map(|e| e.field.as_str()).collect();
+    /// # assert_eq!(fields, ["name"]);
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = &FieldError> {
+        self.errors.iter()
+    }
+
+    /// # into_result
+    ///
+    /// **Purpose:**
+    /// Finishes validation.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - `Ok(())` if no errors were recorded.
+    ///
+    /// **Errors / Failures:**
+    /// - `Err(self)` if any constraint failed.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::validate::ValidationErrors;
+    /// # fn validate(quantity: u32) -> Result<(), ValidationErrors> {
+    /// # let mut errors = ValidationErrors::new();
+    /// # errors.check("quantity", quantity > 0, "must be positive");
+    /// errors.into_result()
+    /// # }
+    /// # assert!(validate(0).is_err());
+    /// ```
+    pub fn into_result(self) -> Result<(), ValidationErrors> {
+        if self.is_empty() { Ok(()) } else { Err(self) }
+    }
+
+    /// # to_response
+    ///
+    /// **Purpose:**
+    /// Renders the errors as a `422 Unprocessable Entity` JSON response of the form
+    /// `{"status":422,"error":"Unprocessable Entity","fields":[{"field":"age","message":"..."}]}`.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - The rejection `Response`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{response::Response, validate::ValidationErrors};
+    /// # fn reject() -> Response {
+    /// # let mut errors = ValidationErrors::new();
+    /// # errors.add("quantity", "must be positive");
+    /// return errors.to_response();
+    /// # }
+    /// # assert_eq!(reject().status, 422);
+    /// ```
+    pub fn to_response(&self) -> Response {
+        let fields: Vec<String> = self
+            .errors
+            .iter()
+            .map(|error| {
+                format!(
+                    r#"{{"field":{},"message":{}}}"#,
+                    json_string(&error.field),
+                    json_string(&error.message)
+                )
+            })
+            .collect();
+        let body = format!(
+            r#"{{"status":422,"error":"Unprocessable Entity","fields":[{}]}}"#,
+            fields.join(",")
+        );
+        Response::new(422, "Unprocessable Entity")
+            .with_header("Content-Type", "application/json")
+            .with_body(body)
+    }
+}
+
+
+impl<T: Validate> Validate for Query<T> {
+
+    /// # validate
+    ///
+    /// **Purpose:**
+    /// Validates the parsed query value.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - `Ok(())` if the inner value is valid.
+    ///
+    /// **Errors / Failures:**
+    /// - The inner value's `ValidationErrors`.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::extract::{FromQuery, Query, QueryParams};
+    /// # use server::validate::{Validate, ValidationErrors};
+    /// # struct SearchParams { q: String }
+    /// # impl FromQuery for SearchParams {
+    /// #     fn from_query(query: &QueryParams) -> Result<SearchParams, String> { Ok(SearchParams { q: query.require("q")? }) }
+    /// # }
+    /// # impl Validate for SearchParams {
+    /// #     fn validate(&self) -> Result<(), ValidationErrors> {
+    /// #         let mut errors = ValidationErrors::new();
+    /// #         errors.length("q", &self.q, 1, 100);
+    /// #         errors.into_result()
+    /// #     }
+    /// # }
+    /// # let query = Query(SearchParams { q: "rust".into() });
+    /// query.validate()?;
+    /// # Ok::<(), ValidationErrors>(())
+    /// ```
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        self.0.validate()
+    }
+}
+
+
+impl<T: Validate> Validate for Path<T> {
+
+    /// # validate
+    ///
+    /// **Purpose:**
+    /// Validates the parsed path value.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - `Ok(())` if the inner value is valid.
+    ///
+    /// **Errors / Failures:**
+    /// - The inner value's `ValidationErrors`.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::extract::Path;
+    /// # use server::validate::{Validate, ValidationErrors};
+    /// # struct OrderId(u32);
+    /// # impl Validate for OrderId {
+    /// #     fn validate(&self) -> Result<(), ValidationErrors> {
+    /// #         let mut errors = ValidationErrors::new();
+    /// #         errors.range("id", self.0, 1, u32::MAX);
+    /// #         errors.into_result()
+    /// #     }
+    /// # }
+    /// # let path = Path(OrderId(7));
+    /// path.validate()?;
+    /// # Ok::<(), ValidationErrors>(())
+    /// ```
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        self.0.validate()
+    }
+}
+
+
+#[cfg(feature = "json")]
+impl<T: Validate> Validate for crate::json::Json<T> {
+
+    /// # validate
+    ///
+    /// **Purpose:**
+    /// Validates the deserialized body.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - `Ok(())` if the inner value is valid.
+    ///
+    /// **Errors / Failures:**
+    /// - The inner value's `ValidationErrors`.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::json::Json;
+    /// # use server::validate::{Validate, ValidationErrors};
+    /// # struct NewUser { name: String }
+    /// # impl Validate for NewUser {
+    /// #     fn validate(&self) -> Result<(), ValidationErrors> {
+    /// #         let mut errors = ValidationErrors::new();
+    /// #         errors.length("name", &self.name, 1, 64);
+    /// #         errors.into_result()
+    /// #     }
+    /// # }
+    /// # let body = Json(NewUser { name: "Ana".into() });
+    /// body.validate()?;
+    /// # Ok::<(), ValidationErrors>(())
+    /// ```
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        self.0.validate()
+    }
+}
+
+
+impl<E: FromRequest + Validate> FromRequest for Valid<E> {
+
+    /// # from_request
+    ///
+    /// **Purpose:**
+    /// Runs the inner extractor, then validates its value.
+    ///
+    /// **Parameters:**
+    /// - `request`: Request being handled.
+    ///
+    /// **Returns:**
+    /// - The validated extractor.
+    ///
+    /// **Errors / Failures:**
+    /// - The inner extractor's rejection if parsing fails.
+    /// - `422 Unprocessable Entity` listing the failing fields if validation fails.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{extract::FromRequest, request::Request, response::Response, validate::Valid};
+    /// # use server::extract::{FromQuery, Query, QueryParams};
+    /// # use server::validate::{Validate, ValidationErrors};
+    /// # struct SearchParams { q: String }
+    /// # impl FromQuery for SearchParams {
+    /// #     fn from_query(query: &QueryParams) -> Result<SearchParams, String> { Ok(SearchParams { q: query.require("q")? }) }
+    /// # }
+    /// # impl Validate for SearchParams {
+    /// #     fn validate(&self) -> Result<(), ValidationErrors> {
+    /// #         let mut errors = ValidationErrors::new();
+    /// #         errors.length("q", &self.q, 1, 100);
+    /// #         errors.into_result()
+    /// #     }
+    /// # }
+    /// # let request = Request::read_from(&mut &b"GET /search?q=rust HTTP/1.1\r\n\r\n"[..]).unwrap();
+    /// let Valid(Query(params)) = Valid::<Query<SearchParams>>::from_request(&request)?;
+    /// # assert_eq!(params.q, "rust");
+    /// # Ok::<(), Response>(())
+    /// ```
+    fn from_request(request: &Request) -> Result<Valid<E>, Response> {
+        let value = E::from_request(request)?;
+        value.validate().map_err(|errors| errors.to_response())?;
+        Ok(Valid(value))
+    }
+}
+
+
+/// # json_string
+///
+/// **Purpose:**
+/// Encodes text as a quoted JSON string literal.
+///
+/// **Parameters:**
+/// - `text`: Text to encode.
+///
+/// **Returns:**
+/// - The quoted, escaped literal.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// assert_eq!(json_string("a\"b"), r#""a\"b""#);
+/// ```
+pub(crate) fn json_string(text: &str) -> String {
+    let mut out = String::with_capacity(text.len() + 2);
+    out.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
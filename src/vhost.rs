@@ -0,0 +1,716 @@
+
+// ============================================================
+//  DAEGONICA SOFTWARE — vhost.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: VirtualHost
+//!
+//! **Purpose:**
+//! Bundles the routes, document root, and access log of one site served by the server.
+//!
+//! **Context:**
+//! - The server picks a virtual host for every request from its `Host` header, falling back to the default host.
+//!
+//! **Responsibilities:**
+//! - Matches `Host` header values against the host's names and subdomain patterns.
+//! - Answers configured redirects, then applies the host's rewrite rules, before routing.
+//! - Produces local responses from routes, then static files, then a not-found handler, running the
+//!   router-level middleware around all three.
+//! - Answers `If-None-Match` with `304 Not Modified` when a static file's `ETag` matches.
+//! - Answers `Range` requests for static files with the requested bytes.
+//! - Answers server-wide `OPTIONS *` requests.
+//! - Records handled requests in the host's access log.
+//! - Does NOT terminate TLS or select certificates.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+use std::net::{IpAddr, Ipv6Addr};
+
+use crate::{
+    access_log::AccessLog,
+    range,
+    redirect::Redirect,
+    request::Request,
+    response::Response,
+    rewrite::{self, RewriteRule},
+    router::{Handler, Router},
+    static_files::StaticFiles,
+};
+
+
+/// # VirtualHost
+///
+/// **Summary:**
+/// One named site.
+///
+/// **Fields:**
+/// - `names`: Host names and patterns this site answers to.
+/// - `redirects`: Declarative redirects, checked in order before rewrites.
+/// - `rewrites`: Rewrite rules evaluated before routing, in order.
+/// - `router`: Routes checked before static files.
+/// - `files`: Document root, if the site serves files.
+/// - `not_found`: Handler for requests nothing else answered.
+/// - `log`: Access log, if enabled.
+///
+/// **Usage Example:**
+/// ```rust,no_run
+/// # use server::{access_log::AccessLog, response::Response, router::Router, static_files::StaticFiles, vhost::VirtualHost};
+/// let tenants = VirtualHost::new()
+///     .name(":tenant.example.com")
+///     .router(Router::new().get("/", |r| Response::new(200, "OK").with_body(r.param("tenant").unwrap_or(""))));
+/// let blog = VirtualHost::new()
+///     .name("blog.example.com")
+///     .files(StaticFiles::new("/srv/blog"))
+///     .access_log(AccessLog::open("blog.log")?);
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Default)]
+pub struct VirtualHost {
+    names: Vec<HostPattern>,
+    redirects: Vec<Redirect>,
+    rewrites: Vec<RewriteRule>,
+    router: Router,
+    files: Option<StaticFiles>,
+    not_found: Option<Handler>,
+    log: Option<AccessLog>,
+}
+
+
+impl VirtualHost {
+
+    /// # new
+    ///
+    /// **Purpose:**
+    /// Creates a site with no names, routes, files, or log.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - An empty `VirtualHost`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::vhost::VirtualHost;
+    /// let site = VirtualHost::new();
+    /// ```
+    pub fn new() -> VirtualHost {
+        VirtualHost::default()
+    }
+
+    /// # name
+    ///
+    /// **Purpose:**
+    /// Adds a host name or subdomain pattern (without port) this site answers to. Pattern labels
+    /// may be `:name` (one label, captured as a request parameter) or `*` (one or more labels).
+    ///
+    /// **Parameters:**
+    /// - `name`: Host name or pattern, matched case-insensitively (e.g. `api.*`, `:tenant.example.com`).
+    ///
+    /// **Returns:**
+    /// - The updated `VirtualHost`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::vhost::VirtualHost;
+    /// let site = VirtualHost::new().name("example.com").name("*.example.com");
+    /// ```
+    pub fn name(mut self, name: &str) -> VirtualHost {
+        self.names.push(HostPattern::new(name));
+        self
+    }
+
+    /// # redirect
+    ///
+    /// **Purpose:**
+    /// Appends a declarative redirect. The first matching redirect wins.
+    ///
+    /// **Parameters:**
+    /// - `redirect`: Redirect rule.
+    ///
+    /// **Returns:**
+    /// - The updated `VirtualHost`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{redirect::Redirect, vhost::VirtualHost};
+    /// let site = VirtualHost::new().redirect(Redirect::permanent("/old-blog/**", "https://blog.example.com/$1"));
+    /// ```
+    pub fn redirect(mut self, redirect: Redirect) -> VirtualHost {
+        self.redirects.push(redirect);
+        self
+    }
+
+    /// # rewrite
+    ///
+    /// **Purpose:**
+    /// Appends a rewrite rule. Rules run in the order they were added.
+    ///
+    /// **Parameters:**
+    /// - `rule`: Compiled rewrite rule.
+    ///
+    /// **Returns:**
+    /// - The updated `VirtualHost`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{rewrite::{RewriteFlag, RewriteRule}, vhost::VirtualHost};
+    /// let site = VirtualHost::new().rewrite(RewriteRule::new("^/old/(.*)$", "/new/$1", RewriteFlag::Last)?);
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn rewrite(mut self, rule: RewriteRule) -> VirtualHost {
+        self.rewrites.push(rule);
+        self
+    }
+
+    /// # router
+    ///
+    /// **Purpose:**
+    /// Sets the site's routes.
+    ///
+    /// **Parameters:**
+    /// - `router`: Routes checked before static files.
+    ///
+    /// **Returns:**
+    /// - The updated `VirtualHost`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{request::Request, response::Response, router::Router, vhost::VirtualHost};
+    /// # fn ping(_: &Request) -> Response { Response::new(200, "OK").with_body("pong") }
+    /// let site = VirtualHost::new().router(Router::new().get("/ping", ping));
+    /// ```
+    pub fn router(mut self, router: Router) -> VirtualHost {
+        self.router = router;
+        self
+    }
+
+    /// # files
+    ///
+    /// **Purpose:**
+    /// Sets the site's document root.
+    ///
+    /// **Parameters:**
+    /// - `files`: Static file handler for the site.
+    ///
+    /// **Returns:**
+    /// - The updated `VirtualHost`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{static_files::StaticFiles, vhost::VirtualHost};
+    /// let site = VirtualHost::new().files(StaticFiles::new("html"));
+    /// ```
+    pub fn files(mut self, files: StaticFiles) -> VirtualHost {
+        self.files = Some(files);
+        self
+    }
+
+    /// # not_found
+    ///
+    /// **Purpose:**
+    /// Sets the handler used when neither a route nor a file matches.
+    ///
+    /// **Parameters:**
+    /// - `handler`: Closure producing the not-found response.
+    ///
+    /// **Returns:**
+    /// - The updated `VirtualHost`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{response::Response, vhost::VirtualHost};
+    /// let site = VirtualHost::new().not_found(|_| Response::new(404, "Not Found"));
+    /// ```
+    pub fn not_found<F>(mut self, handler: F) -> VirtualHost
+    where
+        F: Fn(&Request) -> Response + Send + Sync + 'static,
+    {
+        self.not_found = Some(Box::new(handler));
+        self
+    }
+
+    /// # access_log
+    ///
+    /// **Purpose:**
+    /// Sets the site's access log.
+    ///
+    /// **Parameters:**
+    /// - `log`: Opened access log.
+    ///
+    /// **Returns:**
+    /// - The updated `VirtualHost`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,no_run
+    /// # use server::{access_log::AccessLog, vhost::VirtualHost};
+    /// let site = VirtualHost::new().access_log(AccessLog::open("site.log")?);
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn access_log(mut self, log: AccessLog) -> VirtualHost {
+        self.log = Some(log);
+        self
+    }
+
+    /// # matches
+    ///
+    /// **Purpose:**
+    /// Checks whether a host name (already stripped of its port) belongs to this site.
+    ///
+    /// **Parameters:**
+    /// - `host`: Lower-cased host name from the request.
+    ///
+    /// **Returns:**
+    /// - `Some(params)` with the labels captured by the first matching pattern, otherwise `None`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::vhost::VirtualHost;
+    /// # let site = VirtualHost::new().name(":tenant.example.com");
+    /// let params = site.matches("acme.example.com");
+    /// # assert_eq!(params, Some(vec![("tenant".to_string(), "acme".to_string())]));
+    /// ```
+    pub fn matches(&self, host: &str) -> Option<Vec<(String, String)>> {
+        self.names.iter().find_map(|pattern| pattern.captures(host))
+    }
+
+    /// # rewrite_request
+    ///
+    /// **Purpose:**
+    /// Checks the site's declarative redirects, then applies its rewrite rules to a request
+    /// before it is routed.
+    ///
+    /// **Parameters:**
+    /// - `request`: Request to rewrite in place.
+    ///
+    /// **Returns:**
+    /// - `Some(response)` if a redirect or a redirecting rewrite rule matched, otherwise `None`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{redirect::Redirect, request::Request, vhost::VirtualHost};
+    /// # fn serve(site: &VirtualHost, mut request: Request, mut stream: &mut Vec<u8>) -> std::io::Result<()> {
+    /// if let Some(redirect) = site.rewrite_request(&mut request) {
+    ///     return redirect.write_to(&mut stream);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// # let site = VirtualHost::new().redirect(Redirect::permanent("/old/*", "/new/$1"));
+    /// # let mut stream = Vec::new();
+    /// # serve(&site, Request::read_from(&mut &b"GET /old/page HTTP/1.1\r\n\r\n"[..])?, &mut stream)?;
+    /// # assert!(stream.starts_with(b"HTTP/1.1 301"));
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn rewrite_request(&self, request: &mut Request) -> Option<Response> {
+        if let Some(response) = self.redirects.iter().find_map(|redirect| redirect.respond(request)) {
+            return Some(response);
+        }
+        rewrite::apply(&self.rewrites, request)
+    }
+
+    /// # respond
+    ///
+    /// **Purpose:**
+    /// Builds the local response for a request: a matching route, else a `GET` or `HEAD` static file,
+    /// else the not-found handler (or a plain `404 Not Found`). Static files and the not-found
+    /// answer pass through the router-level middleware just as routes do, so authentication,
+    /// access rules, rate limits, and added headers cover them. `OPTIONS *` is answered with
+    /// every method the site supports.
+    ///
+    /// **Parameters:**
+    /// - `request`: Parsed client request.
+    ///
+    /// **Returns:**
+    /// - The `Response` to send.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{request::Request, response::Response, router::Router, vhost::VirtualHost};
+    /// # let site = VirtualHost::new().router(Router::new().get("/", |_| Response::new(200, "OK")));
+    /// # let mut request = Request::read_from(&mut &b"GET / HTTP/1.1\r\nHost: acme.example.com\r\n\r\n"[..])?;
+    /// let response = site.respond(&request);
+    /// # assert_eq!(response.status, 200);
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn respond(&self, request: &Request) -> Response {
+        if request.method == "OPTIONS" && request.target == "*" {
+            let mut methods = self.router.methods();
+            if self.files.is_some() {
+                methods.extend(["GET".to_string(), "HEAD".to_string()]);
+                methods.sort();
+                methods.dedup();
+            }
+            return Response::new(204, "No Content").with_header("Allow", methods.join(", "));
+        }
+        self.router.handle_or(request, &|request| self.fallback(request))
+    }
+
+    /// # fallback
+    ///
+    /// **Purpose:**
+    /// Answers a request no route matches: a `GET` or `HEAD` static file, else the not-found
+    /// handler (or a plain `404 Not Found`).
+    ///
+    /// **Parameters:**
+    /// - `request`: Request, as passed on by the router-level middleware.
+    ///
+    /// **Returns:**
+    /// - The `Response` to send.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let response = self.router.handle_or(request, &|request| self.fallback(request));
+    /// ```
+    fn fallback(&self, request: &Request) -> Response {
+        if matches!(request.method.as_str(), "GET" | "HEAD")
+            && let Some(response) = self.files.as_ref().and_then(|files| files.serve_with_query(request.path(), request.query()))
+        {
+            return range::apply(request, revalidate(request, response));
+        }
+        match &self.not_found {
+            Some(handler) => handler(request),
+            None => Response::new(404, "Not Found"),
+        }
+    }
+
+    /// # route_pattern
+    ///
+    /// **Purpose:**
+    /// Names the router route that answers a request, for labelling metrics.
+    ///
+    /// **Parameters:**
+    /// - `request`: Request after rewrites.
+    ///
+    /// **Returns:**
+    /// - The matching route's registered path, or `None` for static files, `404`s, and other
+    ///   requests no route handles.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{request::Request, response::Response, router::Router, vhost::VirtualHost};
+    /// # let site = VirtualHost::new().router(Router::new().get("/", |_| Response::new(200, "OK")));
+    /// # let mut request = Request::read_from(&mut &b"GET / HTTP/1.1\r\nHost: acme.example.com\r\n\r\n"[..])?;
+    /// let route = site.route_pattern(&request);
+    /// # assert_eq!(route, Some("/"));
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn route_pattern(&self, request: &Request) -> Option<&str> {
+        self.router.route_pattern(request)
+    }
+
+    /// # has_async_routes
+    ///
+    /// **Purpose:**
+    /// Checks whether the site's router holds routes registered with `Router::route_async`.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - `true` if the site needs a tokio runtime to be served.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let needs_runtime = site.has_async_routes();
+    /// ```
+    pub(crate) fn has_async_routes(&self) -> bool {
+        self.router.has_async_routes()
+    }
+
+    /// # log
+    ///
+    /// **Purpose:**
+    /// Records a handled request in the site's access log, if it has one.
+    ///
+    /// **Parameters:**
+    /// - `client`: Client address, if known.
+    /// - `request`: Handled request.
+    /// - `response`: Response sent, or `None` if it was streamed through unparsed.
+    ///
+    /// **Returns:**
+    /// None.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{request::Request, response::Response, vhost::VirtualHost};
+    /// # let site = VirtualHost::new();
+    /// # let mut request = Request::read_from(&mut &b"GET / HTTP/1.1\r\nHost: acme.example.com\r\n\r\n"[..])?;
+    /// # let response = Response::new(200, "OK");
+    /// site.log(request.client, &request, Some(&response));
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn log(&self, client: Option<IpAddr>, request: &Request, response: Option<&Response>) {
+        if let Some(log) = &self.log {
+            log.record(
+                client,
+                request,
+                response.map(|r| r.status),
+                response.map(Response::body_len),
+            );
+        }
+    }
+}
+
+
+/// # HostPattern
+///
+/// **Summary:**
+/// A host name pattern compiled into per-label tokens.
+///
+/// **Fields:**
+/// - `labels`: Tokens for each dot-separated label, left to right.
+#[derive(Debug, Clone)]
+pub(crate) struct HostPattern {
+    labels: Vec<Label>,
+}
+
+
+/// # Label
+///
+/// **Summary:**
+/// One label of a host pattern.
+///
+/// **Variants:**
+/// - `Literal`: Label that must match exactly.
+/// - `Param`: Any single label, captured under the given name.
+/// - `Wildcard`: One or more labels.
+#[derive(Debug, Clone)]
+enum Label {
+    Literal(String),
+    Param(String),
+    Wildcard,
+}
+
+
+impl HostPattern {
+
+    /// # new
+    ///
+    /// **Purpose:**
+    /// Compiles a host pattern such as `api.*` or `:tenant.example.com`. IPv6 literals such
+    /// as `::1` are taken verbatim rather than as `:name` labels.
+    ///
+    /// **Parameters:**
+    /// - `pattern`: Host name or pattern.
+    ///
+    /// **Returns:**
+    /// - A compiled `HostPattern`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let pattern = HostPattern::new(":tenant.example.com");
+    /// ```
+    pub(crate) fn new(pattern: &str) -> HostPattern {
+        let pattern = pattern.to_ascii_lowercase();
+        let literal = pattern.parse::<Ipv6Addr>().is_ok();
+        let labels = pattern
+            .split('.')
+            .map(|label| match label {
+                _ if literal => Label::Literal(label.to_string()),
+                "*" => Label::Wildcard,
+                label if label.starts_with(':') => Label::Param(label[1..].to_string()),
+                label => Label::Literal(label.to_string()),
+            })
+            .collect();
+        HostPattern { labels }
+    }
+
+    /// # captures
+    ///
+    /// **Purpose:**
+    /// Matches a host name and collects named label captures.
+    ///
+    /// **Parameters:**
+    /// - `host`: Lower-cased host name.
+    ///
+    /// **Returns:**
+    /// - `Some(params)` on a match, otherwise `None`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let params = pattern.captures("acme.example.com");
+    /// ```
+    pub(crate) fn captures(&self, host: &str) -> Option<Vec<(String, String)>> {
+        let labels: Vec<&str> = host.split('.').collect();
+        let mut params = Vec::new();
+        match_labels(&self.labels, &labels, &mut params).then_some(params)
+    }
+}
+
+
+/// # match_labels
+///
+/// **Purpose:**
+/// Recursive backtracking matcher behind `HostPattern::captures`.
+///
+/// **Parameters:**
+/// - `pattern`: Remaining pattern labels.
+/// - `host`: Remaining host labels.
+/// - `params`: Captures collected so far.
+///
+/// **Returns:**
+/// - `true` if the remaining pattern consumes the remaining host labels exactly.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let matched = match_labels(&pattern.labels, &["api", "example", "com"], &mut Vec::new());
+/// ```
+fn match_labels(pattern: &[Label], host: &[&str], params: &mut Vec<(String, String)>) -> bool {
+    let Some((first, rest)) = pattern.split_first() else {
+        return host.is_empty();
+    };
+
+    match first {
+        Label::Literal(literal) => {
+            host.first() == Some(&literal.as_str()) && match_labels(rest, &host[1..], params)
+        }
+        Label::Param(name) => {
+            let Some(label) = host.first().filter(|label| !label.is_empty()) else {
+                return false;
+            };
+            params.push((name.clone(), label.to_string()));
+            if match_labels(rest, &host[1..], params) {
+                return true;
+            }
+            params.pop();
+            false
+        }
+        Label::Wildcard => (1..=host.len()).rev().any(|taken| match_labels(rest, &host[taken..], params)),
+    }
+}
+
+
+/// # revalidate
+///
+/// **Purpose:**
+/// Turns a static file response into `304 Not Modified` when the client already holds it.
+///
+/// **Parameters:**
+/// - `request`: Client request, possibly carrying `If-None-Match`.
+/// - `response`: File response, possibly carrying an `ETag`.
+///
+/// **Returns:**
+/// - A bodiless `304` keeping the `ETag` and `Cache-Control` if any listed tag (or `*`) matches,
+///   comparing weakly; otherwise `response` unchanged.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let response = revalidate(&request, files.serve(request.path())?);
+/// ```
+fn revalidate(request: &Request, response: Response) -> Response {
+    let (Some(etag), Some(condition)) = (response.headers.get("ETag"), request.headers.get("If-None-Match")) else {
+        return response;
+    };
+    let opaque = |tag: &str| tag.trim().trim_start_matches("W/").to_string();
+    if !condition.split(',').any(|tag| tag.trim() == "*" || opaque(tag) == opaque(etag)) {
+        return response;
+    }
+    let mut not_modified = Response::new(304, "Not Modified").with_header("ETag", etag);
+    if let Some(policy) = response.headers.get("Cache-Control") {
+        not_modified.headers.set("Cache-Control", policy);
+    }
+    not_modified
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn captures(pattern: &str, host: &str) -> Option<Vec<(String, String)>> {
+        HostPattern::new(pattern).captures(host)
+    }
+
+    #[test]
+    fn host_patterns_match_literals_params_and_wildcards() {
+        assert_eq!(captures("Example.com", "example.com"), Some(Vec::new()));
+        assert_eq!(captures("example.com", "www.example.com"), None);
+        assert_eq!(captures(":tenant.example.com", "acme.example.com"), Some(vec![("tenant".into(), "acme".into())]));
+        assert_eq!(captures(":tenant.example.com", "example.com"), None);
+        assert_eq!(captures("*.example.com", "a.b.example.com"), Some(Vec::new()));
+        assert_eq!(captures("*.example.com", "example.com"), None);
+        assert_eq!(captures("api.*", "api.example.org"), Some(Vec::new()));
+        assert_eq!(captures("::1", "::1"), Some(Vec::new()));
+    }
+
+    #[test]
+    fn sites_match_any_of_their_names() {
+        let site = VirtualHost::new().name("example.com").name(":sub.example.org");
+        assert!(site.matches("example.com").is_some());
+        assert_eq!(site.matches("docs.example.org"), Some(vec![("sub".into(), "docs".into())]));
+        assert!(site.matches("example.net").is_none());
+        assert!(VirtualHost::new().matches("example.com").is_none());
+    }
+
+    #[test]
+    fn options_asterisk_lists_every_method_the_site_supports() {
+        let site = VirtualHost::new().router(Router::new().route("POST", "/upload", |_| Response::new(201, "Created"))).files(StaticFiles::new("html"));
+        let response = site.respond(&Request::read_from(&mut "OPTIONS * HTTP/1.1\r\nHost: example.com\r\n\r\n".as_bytes()).unwrap());
+        assert_eq!(response.status, 204);
+        assert_eq!(response.headers.get("Allow"), Some("GET, HEAD, OPTIONS, POST"));
+    }
+}
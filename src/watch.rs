@@ -0,0 +1,452 @@
+
+// ============================================================
+//  DAEGONICA SOFTWARE — watch.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: Watch
+//!
+//! **Purpose:**
+//! Notices file changes under watched directories so edits show up without a restart.
+//!
+//! **Context:**
+//! - Optional; started by the application, typically in development, next to the server,
+//!   either on its own thread or as a server task (so it starts after privileges are dropped).
+//! - Drives `FileCache` invalidation, `TemplateDir` reloads, and any listener the application
+//!   registers.
+//!
+//! **Responsibilities:**
+//! - Polls the watched trees on a background thread, comparing modification times and sizes.
+//! - Reports created, modified, and deleted files to every listener in one batch per poll.
+//! - Does NOT use platform notification APIs; changes are seen within one polling interval.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+use std::{
+    collections::HashMap,
+    fs,
+    io,
+    path::{Path, PathBuf},
+    sync::Arc,
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use crate::{
+    file_cache::FileCache,
+    log::{self, LogLevel},
+    tasks::TaskContext,
+    template::TemplateDir,
+};
+
+
+/// # DEFAULT_INTERVAL
+///
+/// **Summary:**
+/// Time between polls unless `interval` says otherwise.
+const DEFAULT_INTERVAL: Duration = Duration::from_millis(500);
+
+
+/// # Listener
+///
+/// **Summary:**
+/// Callback receiving the files that changed in one poll.
+type Listener = Box<dyn Fn(&[PathBuf]) + Send + Sync>;
+
+
+/// # Snapshot
+///
+/// **Summary:**
+/// Modification time and size of every file in the watched trees.
+type Snapshot = HashMap<PathBuf, (Option<SystemTime>, u64)>;
+
+
+/// # Watcher
+///
+/// **Summary:**
+/// Polling file watcher, configured with builders and then spawned.
+///
+/// **Fields:**
+/// - `roots`: Files and directories to watch.
+/// - `interval`: Time between polls.
+/// - `listeners`: Callbacks run, in order, after each poll that found changes.
+///
+/// **Usage Example:**
+/// ```rust,no_run
+/// # use server::{file_cache::FileCache, watch::Watcher};
+/// # use std::sync::Arc;
+/// let cache = Arc::new(FileCache::new(8 * 1024 * 1024));
+/// Watcher::new("html")
+///     .file_cache(Arc::clone(&cache))
+///     .on_change(|paths| println!("{} files changed", paths.len()))
+///     .spawn()?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub struct Watcher {
+    roots: Vec<PathBuf>,
+    interval: Duration,
+    listeners: Vec<Listener>,
+}
+
+
+impl Watcher {
+
+    /// # new
+    ///
+    /// **Purpose:**
+    /// Creates a watcher for one tree, polling every 500 ms.
+    ///
+    /// **Parameters:**
+    /// - `root`: File or directory to watch, usually the document root.
+    ///
+    /// **Returns:**
+    /// - A new `Watcher` with no listeners.
+    ///
+    /// **Errors / Failures:**
+    /// None. A missing root is watched until it appears.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::watch::Watcher;
+    /// let watcher = Watcher::new("html");
+    /// ```
+    pub fn new(root: impl Into<PathBuf>) -> Watcher {
+        Watcher {
+            roots: vec![root.into()],
+            interval: DEFAULT_INTERVAL,
+            listeners: Vec::new(),
+        }
+    }
+
+    /// # watch
+    ///
+    /// **Purpose:**
+    /// Adds another file or directory to watch.
+    ///
+    /// **Parameters:**
+    /// - `root`: Path to watch.
+    ///
+    /// **Returns:**
+    /// - The updated `Watcher`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::watch::Watcher;
+    /// let watcher = Watcher::new("html").watch("content");
+    /// ```
+    pub fn watch(mut self, root: impl Into<PathBuf>) -> Watcher {
+        let root = root.into();
+        if !self.roots.contains(&root) {
+            self.roots.push(root);
+        }
+        self
+    }
+
+    /// # interval
+    ///
+    /// **Purpose:**
+    /// Sets the time between polls.
+    ///
+    /// **Parameters:**
+    /// - `interval`: Polling interval.
+    ///
+    /// **Returns:**
+    /// - The updated `Watcher`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::watch::Watcher;
+    /// # use std::time::Duration;
+    /// let watcher = Watcher::new("html").interval(Duration::from_millis(200));
+    /// ```
+    pub fn interval(mut self, interval: Duration) -> Watcher {
+        self.interval = interval;
+        self
+    }
+
+    /// # on_change
+    ///
+    /// **Purpose:**
+    /// Registers a callback run on the watcher thread with the files that changed.
+    ///
+    /// **Parameters:**
+    /// - `listener`: Callback receiving the created, modified, and deleted paths.
+    ///
+    /// **Returns:**
+    /// - The updated `Watcher`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::watch::Watcher;
+    /// let watcher = Watcher::new("html").on_change(|paths| println!("{paths:?}"));
+    /// ```
+    pub fn on_change<F>(mut self, listener: F) -> Watcher
+    where
+        F: Fn(&[PathBuf]) + Send + Sync + 'static,
+    {
+        self.listeners.push(Box::new(listener));
+        self
+    }
+
+    /// # file_cache
+    ///
+    /// **Purpose:**
+    /// Drops changed files from a file cache as soon as they are seen.
+    ///
+    /// **Parameters:**
+    /// - `cache`: Cache shared with `StaticFiles`. Watch the same root spelling the handler uses
+    ///   so the paths match.
+    ///
+    /// **Returns:**
+    /// - The updated `Watcher`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{file_cache::FileCache, watch::Watcher};
+    /// # use std::sync::Arc;
+    /// # let cache = Arc::new(FileCache::new(8 * 1024 * 1024));
+    /// let watcher = Watcher::new("html").file_cache(Arc::clone(&cache));
+    /// ```
+    pub fn file_cache(self, cache: Arc<FileCache>) -> Watcher {
+        self.on_change(move |paths| {
+            for path in paths {
+                cache.invalidate(path);
+            }
+        })
+    }
+
+    /// # templates
+    ///
+    /// **Purpose:**
+    /// Watches a template directory and reloads it whenever a file beneath it changes.
+    ///
+    /// **Parameters:**
+    /// - `templates`: Templates shared with the handlers that render them.
+    ///
+    /// **Returns:**
+    /// - The updated `Watcher`.
+    ///
+    /// **Errors / Failures:**
+    /// None. Failed reloads are logged and the previous templates stay in use.
+    ///
+    /// **Examples:**
+    /// ```rust,no_run
+    /// # use server::{template::TemplateDir, watch::Watcher};
+    /// # use std::sync::Arc;
+    /// # let templates = Arc::new(TemplateDir::open("templates")?);
+    /// let watcher = Watcher::new("html").templates(Arc::clone(&templates));
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn templates(self, templates: Arc<TemplateDir>) -> Watcher {
+        let root = templates.root().to_path_buf();
+        self.watch(root.clone()).on_change(move |paths| {
+            if paths.iter().any(|path| path.starts_with(&root)) {
+                let _ = templates.reload();
+            }
+        })
+    }
+
+    /// # spawn
+    ///
+    /// **Purpose:**
+    /// Takes a first snapshot and starts the `fs-watch` thread, which runs for the life of the
+    /// process.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - `Ok(())` once the thread is running.
+    ///
+    /// **Errors / Failures:**
+    /// - Returns an I/O error if the thread cannot be spawned.
+    ///
+    /// **Examples:**
+    /// ```rust,no_run
+    /// # use server::{file_cache::FileCache, watch::Watcher};
+    /// # use std::sync::Arc;
+    /// # let cache = Arc::new(FileCache::new(8 * 1024 * 1024));
+    /// Watcher::new("html").file_cache(cache).spawn()?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn spawn(self) -> io::Result<()> {
+        let mut previous = self.snapshot();
+        thread::Builder::new().name("fs-watch".to_string()).spawn(move || {
+            loop {
+                thread::sleep(self.interval);
+                self.poll(&mut previous);
+            }
+        })?;
+        Ok(())
+    }
+
+    /// # run
+    ///
+    /// **Purpose:**
+    /// Watches from a server task until the server stops, taking the first snapshot when the
+    /// task starts, after the listeners are bound and privileges are dropped.
+    ///
+    /// **Parameters:**
+    /// - `ctx`: Context of the task running the watcher.
+    ///
+    /// **Returns:**
+    /// None.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// # use server::{tasks::{Restart, Tasks}, watch::Watcher};
+    /// # let watcher = Watcher::new("html");
+    /// let tasks = Tasks::new().spawn("fs-watch", Restart::OnPanic, move |ctx| watcher.run(ctx));
+    /// ```
+    pub fn run(&self, ctx: &TaskContext) {
+        let mut previous = self.snapshot();
+        while ctx.sleep(self.interval) {
+            self.poll(&mut previous);
+        }
+    }
+
+    /// # poll
+    ///
+    /// **Purpose:**
+    /// Takes a new snapshot and reports what changed since the previous one to every listener.
+    ///
+    /// **Parameters:**
+    /// - `previous`: Last snapshot, replaced by the new one.
+    ///
+    /// **Returns:**
+    /// None.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// self.poll(&mut previous);
+    /// ```
+    fn poll(&self, previous: &mut Snapshot) {
+        let current = self.snapshot();
+        let changed = changes(previous, &current);
+        if !changed.is_empty() {
+            log::emit(LogLevel::Debug, format_args!("{} watched files changed", changed.len()));
+            for listener in &self.listeners {
+                listener(&changed);
+            }
+        }
+        *previous = current;
+    }
+
+    /// # snapshot
+    ///
+    /// **Purpose:**
+    /// Records the current state of every watched file.
+    ///
+    /// **Parameters:**
+    /// None.
+    ///
+    /// **Returns:**
+    /// - The snapshot.
+    ///
+    /// **Errors / Failures:**
+    /// None. Unreadable directories and files are left out.
+    ///
+    /// **Examples:**
+    /// ```rust,ignore
+    /// let current = self.snapshot();
+    /// ```
+    fn snapshot(&self) -> Snapshot {
+        let mut snapshot = Snapshot::new();
+        for root in &self.roots {
+            scan(root, &mut snapshot);
+        }
+        snapshot
+    }
+}
+
+
+/// # scan
+///
+/// **Purpose:**
+/// Adds a file, or every file beneath a directory, to a snapshot.
+///
+/// **Parameters:**
+/// - `path`: File or directory.
+/// - `snapshot`: Snapshot being built.
+///
+/// **Returns:**
+/// None.
+///
+/// **Errors / Failures:**
+/// None. Paths that cannot be read are skipped.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// scan(Path::new("html"), &mut snapshot);
+/// ```
+fn scan(path: &Path, snapshot: &mut Snapshot) {
+    let mut pending = vec![path.to_path_buf()];
+    while let Some(path) = pending.pop() {
+        let Ok(metadata) = fs::metadata(&path) else {
+            continue;
+        };
+        if !metadata.is_dir() {
+            snapshot.insert(path, (metadata.modified().ok(), metadata.len()));
+            continue;
+        }
+        let Ok(entries) = fs::read_dir(&path) else {
+            continue;
+        };
+        pending.extend(entries.flatten().map(|entry| entry.path()));
+    }
+}
+
+
+/// # changes
+///
+/// **Purpose:**
+/// Lists the files that differ between two snapshots.
+///
+/// **Parameters:**
+/// - `previous`: Earlier snapshot.
+/// - `current`: Later snapshot.
+///
+/// **Returns:**
+/// - Created, modified, and deleted paths, sorted.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust,ignore
+/// let changed = changes(&previous, &current);
+/// ```
+fn changes(previous: &Snapshot, current: &Snapshot) -> Vec<PathBuf> {
+    let mut changed: Vec<PathBuf> = current
+        .iter()
+        .filter(|(path, state)| previous.get(*path) != Some(state))
+        .map(|(path, _)| path.clone())
+        .chain(previous.keys().filter(|path| !current.contains_key(*path)).cloned())
+        .collect();
+    changed.sort();
+    changed
+}
@@ -0,0 +1,133 @@
+// ============================================================
+//  DAEGONICA SOFTWARE — tests/acl.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: Address ACL Integration Tests
+//!
+//! **Purpose:**
+//! Checks IP allow and deny rules through a running server, alone and in front of authentication.
+//!
+//! **Context:**
+//! - Run with `cargo test --test acl`; every request comes from `127.0.0.1`.
+//!
+//! **Responsibilities:**
+//! - Confirms that allowed peers are served and denied peers get `403` without reaching the handler.
+//! - Confirms that per-path overrides apply only to their paths, static files included.
+//! - Confirms that forwarding headers move the client address only for trusted proxies.
+//! - Confirms that an ACL placed before authentication refuses a denied peer before any challenge.
+//! - Does NOT cover IPv6 peers, whose range matching has unit tests in `ip`.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+mod common;
+
+use common::{calls, counted, get, serve, serve_files};
+use server::{
+    acl::{IpAcl, Precedence},
+    auth::BasicAuth,
+    router::Router,
+};
+
+
+#[test]
+fn allowed_peers_are_served_and_others_refused() {
+    let (counter, handler) = counted("hello", "no-store");
+    let router = Router::new().middleware(IpAcl::new().allow("127.0.0.0/8").unwrap().middleware()).get("/", handler);
+    let handle = serve(router, |server| server);
+    assert_eq!(get(handle.local_addr(), "/", "").status, 200);
+    handle.shutdown();
+
+    let (counter_denied, handler) = counted("hello", "no-store");
+    let router = Router::new().middleware(IpAcl::new().allow("10.0.0.0/8").unwrap().middleware()).get("/", handler);
+    let handle = serve(router, |server| server);
+    let reply = get(handle.local_addr(), "/", "");
+    assert_eq!((reply.status, reply.body.as_str()), (403, "access denied"));
+    handle.shutdown();
+
+    assert_eq!((calls(&counter), calls(&counter_denied)), (1, 0));
+}
+
+#[test]
+fn precedence_decides_between_overlapping_rules() {
+    let acl = || IpAcl::new().allow("127.0.0.1").unwrap().deny("127.0.0.0/8").unwrap();
+    let (_, handler) = counted("hello", "no-store");
+    let router = Router::new().middleware(acl().precedence(Precedence::DenyFirst).middleware()).get("/", handler);
+    let handle = serve(router, |server| server);
+    assert_eq!(get(handle.local_addr(), "/", "").status, 403);
+    handle.shutdown();
+
+    let (_, handler) = counted("hello", "no-store");
+    let router = Router::new().middleware(acl().precedence(Precedence::AllowFirst).middleware()).get("/", handler);
+    let handle = serve(router, |server| server);
+    assert_eq!(get(handle.local_addr(), "/", "").status, 200);
+    handle.shutdown();
+}
+
+#[test]
+fn path_overrides_apply_only_to_their_paths() {
+    let (_, public) = counted("public", "no-store");
+    let (_, metrics) = counted("metrics", "no-store");
+    let acl = IpAcl::new().route("/metrics", IpAcl::new().allow("10.0.0.0/8").unwrap());
+    let router = Router::new().middleware(acl.middleware()).get("/", public).get("/metrics", metrics);
+    let handle = serve(router, |server| server);
+
+    assert_eq!(get(handle.local_addr(), "/", "").status, 200);
+    assert_eq!(get(handle.local_addr(), "/metrics", "").status, 403);
+    handle.shutdown();
+}
+
+#[test]
+fn forwarding_headers_count_only_from_trusted_proxies() {
+    let (_, handler) = counted("hello", "no-store");
+    let acl = IpAcl::new().deny("203.0.113.0/24").unwrap().trust_proxy("127.0.0.1").unwrap();
+    let router = Router::new().middleware(acl.middleware()).get("/", handler);
+    let handle = serve(router, |server| server);
+    assert_eq!(get(handle.local_addr(), "/", "X-Forwarded-For: 203.0.113.9\r\n").status, 403);
+    assert_eq!(get(handle.local_addr(), "/", "X-Forwarded-For: 198.51.100.7\r\n").status, 200);
+    handle.shutdown();
+
+    let (_, handler) = counted("hello", "no-store");
+    let acl = IpAcl::new().deny("203.0.113.0/24").unwrap();
+    let router = Router::new().middleware(acl.middleware()).get("/", handler);
+    let handle = serve(router, |server| server);
+    assert_eq!(get(handle.local_addr(), "/", "X-Forwarded-For: 203.0.113.9\r\n").status, 200);
+    handle.shutdown();
+}
+
+#[test]
+fn an_acl_ahead_of_authentication_refuses_before_challenging() {
+    let (counter, handler) = counted("secret", "no-store");
+    let router = Router::new()
+        .middleware(IpAcl::new().allow("10.0.0.0/8").unwrap().middleware())
+        .middleware(BasicAuth::new("Admin area").user("ana", "s3cret").middleware())
+        .get("/", handler);
+    let handle = serve(router, |server| server);
+
+    let reply = get(handle.local_addr(), "/", "Authorization: Basic YW5hOnMzY3JldA==\r\n");
+    assert_eq!(reply.status, 403);
+    assert_eq!(reply.header("WWW-Authenticate"), None);
+    assert_eq!(calls(&counter), 0);
+    handle.shutdown();
+}
+
+#[test]
+fn static_files_are_covered_by_router_rules() {
+    let handle = serve_files(Router::new().middleware(IpAcl::new().allow("10.0.0.0/8").unwrap().middleware()), |server| server);
+    for path in ["/page.txt", "/missing"] {
+        assert_eq!(get(handle.local_addr(), path, "").status, 403, "{path}");
+    }
+    handle.shutdown();
+
+    let acl = IpAcl::new().route("/page.txt", IpAcl::new().allow("10.0.0.0/8").unwrap());
+    let handle = serve_files(Router::new().middleware(acl.middleware()), |server| server);
+    assert_eq!(get(handle.local_addr(), "/page.txt", "").status, 403);
+    assert_eq!(get(handle.local_addr(), "/missing", "").status, 404);
+    handle.shutdown();
+}
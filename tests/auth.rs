@@ -0,0 +1,107 @@
+// ============================================================
+//  DAEGONICA SOFTWARE — tests/auth.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: Authentication Integration Tests
+//!
+//! **Purpose:**
+//! Checks Basic authentication through a running server, from the challenge to the handler.
+//!
+//! **Context:**
+//! - Run with `cargo test --test auth`; each test serves its own router on a loopback port.
+//!
+//! **Responsibilities:**
+//! - Confirms that missing and wrong credentials get `401` with a challenge and never reach the handler.
+//! - Confirms that valid credentials reach the handler with the `AuthenticatedUser` attached.
+//! - Confirms that scoped middleware protects only its prefix.
+//! - Confirms that router-level authentication also guards static files and the not-found answer.
+//! - Does NOT cover Digest or token authentication, which have unit tests of their own.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+mod common;
+
+use common::{calls, counted, get, serve, serve_files};
+use server::{
+    auth::{AuthenticatedUser, BasicAuth},
+    request::Request,
+    response::Response,
+    router::Router,
+};
+
+
+// `ana:s3cret`, `ana:wrong`, and `bob:s3cret` in Basic encoding.
+const ANA: &str = "Authorization: Basic YW5hOnMzY3JldA==\r\n";
+const ANA_WRONG: &str = "Authorization: Basic YW5hOndyb25n\r\n";
+const BOB: &str = "Authorization: Basic Ym9iOnMzY3JldA==\r\n";
+
+
+fn whoami(request: &Request) -> Response {
+    let name = request.extensions.get::<AuthenticatedUser>().map_or("nobody", |AuthenticatedUser(name)| name);
+    Response::new(200, "OK").with_body(name.to_string())
+}
+
+
+#[test]
+fn requests_without_valid_credentials_are_challenged() {
+    let (counter, handler) = counted("secret", "no-store");
+    let router = Router::new().middleware(BasicAuth::new("Admin area").user("ana", "s3cret").middleware()).get("/", handler);
+    let handle = serve(router, |server| server);
+
+    for headers in ["", ANA_WRONG, BOB, "Authorization: Basic !!!\r\n", "Authorization: Bearer YW5hOnMzY3JldA==\r\n"] {
+        let reply = get(handle.local_addr(), "/", headers);
+        assert_eq!(reply.status, 401, "{headers:?}");
+        assert_eq!(reply.header("WWW-Authenticate"), Some(r#"Basic realm="Admin area", charset="UTF-8""#));
+        assert!(!reply.body.contains("secret"));
+    }
+    assert_eq!(calls(&counter), 0);
+    handle.shutdown();
+}
+
+#[test]
+fn valid_credentials_reach_the_handler_as_the_user() {
+    let router = Router::new().middleware(BasicAuth::new("Admin area").user("ana", "s3cret").middleware()).get("/me", whoami);
+    let handle = serve(router, |server| server);
+
+    let reply = get(handle.local_addr(), "/me", ANA);
+    assert_eq!((reply.status, reply.body.as_str()), (200, "ana"));
+    handle.shutdown();
+}
+
+#[test]
+fn scoped_authentication_leaves_other_routes_open() {
+    let router = Router::new()
+        .get("/", |_: &Request| Response::new(200, "OK").with_body("home"))
+        .scope("/admin", |admin| {
+            admin.middleware(BasicAuth::new("Admin area").user("ana", "s3cret").middleware()).get("/", whoami)
+        });
+    let handle = serve(router, |server| server);
+
+    assert_eq!(get(handle.local_addr(), "/", "").status, 200);
+    assert_eq!(get(handle.local_addr(), "/admin/", "").status, 401);
+    assert_eq!(get(handle.local_addr(), "/admin/", ANA).body, "ana");
+    handle.shutdown();
+}
+
+#[test]
+fn static_files_are_protected_by_router_authentication() {
+    let router = Router::new().middleware(BasicAuth::new("Files").user("ana", "s3cret").middleware());
+    let handle = serve_files(router, |server| server);
+
+    for path in ["/page.txt", "/missing"] {
+        let reply = get(handle.local_addr(), path, "");
+        assert_eq!(reply.status, 401, "{path}");
+        assert!(!reply.body.contains("static page"));
+    }
+    let reply = get(handle.local_addr(), "/page.txt", ANA);
+    assert_eq!((reply.status, reply.body.as_str()), (200, "static page\n"));
+    assert_eq!(get(handle.local_addr(), "/missing", ANA).status, 404);
+    handle.shutdown();
+}
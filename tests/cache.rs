@@ -0,0 +1,123 @@
+// ============================================================
+//  DAEGONICA SOFTWARE — tests/cache.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: Response Cache Integration Tests
+//!
+//! **Purpose:**
+//! Checks the response cache through a running server, alone and beside authentication.
+//!
+//! **Context:**
+//! - Run with `cargo test --test cache`; handlers count their calls, so a hit is one that did
+//!   not reach the handler.
+//!
+//! **Responsibilities:**
+//! - Confirms that opted-in routes are served from the cache and other routes never are.
+//! - Confirms that `private` and `no-store` responses, and requests carrying `Authorization`,
+//!   are never stored, so one user's answer is not replayed to another.
+//! - Confirms that a protected route that is not opted in keeps challenging anonymous clients.
+//! - Confirms that `Vary` keeps variants apart.
+//! - Does NOT cover eviction or coalescing, which have unit tests in `cache`.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+mod common;
+
+use common::{calls, counted, get, serve};
+use server::{auth::BasicAuth, request::Request, response::Response, router::Router};
+
+
+const ANA: &str = "Authorization: Basic YW5hOnMzY3JldA==\r\n";
+
+
+#[test]
+fn opted_in_routes_are_answered_from_the_cache() {
+    let (counter, handler) = counted("feed", "public, max-age=60");
+    let handle = serve(Router::new().get("/feed.xml", handler), |server| server.cache_route("/feed.xml"));
+
+    let first = get(handle.local_addr(), "/feed.xml", "");
+    let second = get(handle.local_addr(), "/feed.xml", "");
+    assert_eq!((first.status, second.status), (200, 200));
+    assert_eq!(second.body, "feed");
+    assert!(second.header("Age").is_some());
+    assert_eq!(calls(&counter), 1);
+    handle.shutdown();
+}
+
+#[test]
+fn routes_that_did_not_opt_in_are_never_cached() {
+    let (counter, handler) = counted("page", "public, max-age=60");
+    let handle = serve(Router::new().get("/page", handler), |server| server.cache_route("/feed.xml"));
+
+    get(handle.local_addr(), "/page", "");
+    get(handle.local_addr(), "/page", "");
+    assert_eq!(calls(&counter), 2);
+    handle.shutdown();
+}
+
+#[test]
+fn private_and_no_store_responses_are_not_kept() {
+    let (private, private_handler) = counted("mine", "private, max-age=60");
+    let (no_store, no_store_handler) = counted("mine", "no-store");
+    let router = Router::new().get("/private", private_handler).get("/no-store", no_store_handler);
+    let handle = serve(router, |server| server.cache_route("/**"));
+
+    for path in ["/private", "/private", "/no-store", "/no-store"] {
+        assert_eq!(get(handle.local_addr(), path, "").status, 200);
+    }
+    assert_eq!((calls(&private), calls(&no_store)), (2, 2));
+    handle.shutdown();
+}
+
+#[test]
+fn authorized_requests_are_not_stored_for_anonymous_clients() {
+    let (counter, handler) = counted("hello", "public, max-age=60");
+    let handle = serve(Router::new().get("/", handler), |server| server.cache_route("/"));
+
+    get(handle.local_addr(), "/", ANA);
+    get(handle.local_addr(), "/", "");
+    assert_eq!(calls(&counter), 2);
+    handle.shutdown();
+}
+
+#[test]
+fn protected_routes_keep_challenging_after_an_authenticated_hit() {
+    let (counter, handler) = counted("secret", "public, max-age=60");
+    let router = Router::new()
+        .get("/feed.xml", |_: &Request| Response::new(200, "OK").with_header("Cache-Control", "max-age=60").with_body("feed"))
+        .scope("/admin", |admin| {
+            admin.middleware(BasicAuth::new("Admin area").user("ana", "s3cret").middleware()).get("/", handler)
+        });
+    let handle = serve(router, |server| server.cache_route("/feed.xml"));
+
+    assert_eq!(get(handle.local_addr(), "/admin/", ANA).status, 200);
+    let anonymous = get(handle.local_addr(), "/admin/", "");
+    assert_eq!(anonymous.status, 401);
+    assert!(!anonymous.body.contains("secret"));
+    assert_eq!(calls(&counter), 1);
+    handle.shutdown();
+}
+
+#[test]
+fn vary_keeps_variants_apart() {
+    let router = Router::new().get("/greeting", |request: &Request| {
+        let french = request.headers.get("Accept-Language").is_some_and(|language| language.starts_with("fr"));
+        Response::new(200, "OK")
+            .with_header("Cache-Control", "public, max-age=60")
+            .with_header("Vary", "Accept-Language")
+            .with_body(if french { "bonjour" } else { "hello" })
+    });
+    let handle = serve(router, |server| server.cache_route("/greeting"));
+
+    assert_eq!(get(handle.local_addr(), "/greeting", "Accept-Language: fr\r\n").body, "bonjour");
+    assert_eq!(get(handle.local_addr(), "/greeting", "Accept-Language: en\r\n").body, "hello");
+    assert_eq!(get(handle.local_addr(), "/greeting", "Accept-Language: fr\r\n").body, "bonjour");
+    handle.shutdown();
+}
@@ -0,0 +1,309 @@
+// ============================================================
+//  DAEGONICA SOFTWARE — tests/common/mod.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: Integration Test Support
+//!
+//! **Purpose:**
+//! Starts a real server on a loopback port and speaks plain HTTP/1.1 to it, for the
+//! integration tests that check how middleware and the cache behave end to end.
+//!
+//! **Context:**
+//! - Included with `mod common;` by each file under `tests/`.
+//!
+//! **Responsibilities:**
+//! - Serves a router as the default site on an ephemeral port, optionally beside the static
+//!   files under `tests/data/site`.
+//! - Sends one request per connection and splits the answer into status, headers, and body.
+//! - Counts handler calls, so tests can tell a cache hit from a fresh response.
+//! - Does NOT decode chunked bodies; the handlers under test answer with fixed lengths.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+#![allow(dead_code)]
+
+use std::{
+    io::{Read, Write},
+    net::{SocketAddr, TcpStream},
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::Duration,
+};
+
+use server::{
+    request::Request,
+    response::Response,
+    router::Router,
+    server::{Server, ServerHandle},
+    static_files::StaticFiles,
+    vhost::VirtualHost,
+};
+
+
+/// # Reply
+///
+/// **Summary:**
+/// A response as read off the wire.
+///
+/// **Fields:**
+/// - `status`: Status code.
+/// - `headers`: Header fields in the order received, names as sent.
+/// - `body`: Body text.
+#[derive(Debug)]
+pub struct Reply {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+
+impl Reply {
+
+    /// # header
+    ///
+    /// **Purpose:**
+    /// Finds a header field by case-insensitive name.
+    ///
+    /// **Parameters:**
+    /// - `name`: Field name.
+    ///
+    /// **Returns:**
+    /// - The first value, or `None`.
+    ///
+    /// **Errors / Failures:**
+    /// None.
+    ///
+    /// **Examples:**
+    /// ```rust
+    /// assert!(reply.header("WWW-Authenticate").is_some());
+    /// ```
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(field, _)| field.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+
+/// # serve
+///
+/// **Purpose:**
+/// Starts a server on a loopback port with `router` as its default site.
+///
+/// **Parameters:**
+/// - `router`: Routes of the default site.
+/// - `configure`: Further server settings, such as cached routes.
+///
+/// **Returns:**
+/// - The running server's handle.
+///
+/// **Errors / Failures:**
+/// - Panics if the server cannot start.
+///
+/// **Examples:**
+/// ```rust
+/// let handle = serve(Router::new().get("/", hello), |server| server.cache_route("/"));
+/// ```
+pub fn serve(router: Router, configure: impl FnOnce(Server) -> Server) -> ServerHandle {
+    configure(Server::new("127.0.0.1:0"))
+        .default_host(VirtualHost::new().router(router))
+        .start()
+        .unwrap()
+}
+
+
+/// # serve_files
+///
+/// **Purpose:**
+/// Starts a server on a loopback port whose default site has `router` in front of the static
+/// files under `tests/data/site`.
+///
+/// **Parameters:**
+/// - `router`: Routes and middleware of the default site.
+/// - `configure`: Further server settings.
+///
+/// **Returns:**
+/// - The running server's handle.
+///
+/// **Errors / Failures:**
+/// - Panics if the server cannot start.
+///
+/// **Examples:**
+/// ```rust
+/// let handle = serve_files(Router::new().middleware(auth.middleware()), |server| server);
+/// ```
+pub fn serve_files(router: Router, configure: impl FnOnce(Server) -> Server) -> ServerHandle {
+    let files = StaticFiles::new(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data/site"));
+    configure(Server::new("127.0.0.1:0"))
+        .default_host(VirtualHost::new().router(router).files(files))
+        .start()
+        .unwrap()
+}
+
+
+/// # get
+///
+/// **Purpose:**
+/// Sends one `GET` request on a fresh connection and reads the whole answer.
+///
+/// **Parameters:**
+/// - `addr`: Server address.
+/// - `path`: Request target.
+/// - `headers`: Extra header lines, each ending in `\r\n`.
+///
+/// **Returns:**
+/// - The parsed `Reply`.
+///
+/// **Errors / Failures:**
+/// - Panics if the connection fails or the answer is not an HTTP/1.1 response.
+///
+/// **Examples:**
+/// ```rust
+/// let reply = get(handle.local_addr(), "/", "Authorization: Basic YW5hOnMzY3JldA==\r\n");
+/// ```
+pub fn get(addr: SocketAddr, path: &str, headers: &str) -> Reply {
+    get_host(addr, "localhost", path, headers)
+}
+
+
+/// # get_host
+///
+/// **Purpose:**
+/// Sends one `GET` request like `get`, naming `host` in the `Host` header.
+///
+/// **Parameters:**
+/// - `addr`: Server address.
+/// - `host`: `Host` header value.
+/// - `path`: Request target.
+/// - `headers`: Extra header lines, each ending in `\r\n`.
+///
+/// **Returns:**
+/// - The parsed `Reply`.
+///
+/// **Errors / Failures:**
+/// - Panics if the connection fails or the answer is not an HTTP/1.1 response.
+///
+/// **Examples:**
+/// ```rust
+/// let reply = get_host(handle.local_addr(), "example.com", "/", "");
+/// ```
+pub fn get_host(addr: SocketAddr, host: &str, path: &str, headers: &str) -> Reply {
+    let mut stream = TcpStream::connect(addr).unwrap();
+    stream.set_read_timeout(Some(Duration::from_secs(10))).unwrap();
+    write!(stream, "GET {path} HTTP/1.1\r\nHost: {host}\r\nConnection: close\r\n{headers}\r\n").unwrap();
+    let mut raw = String::new();
+    stream.read_to_string(&mut raw).unwrap();
+
+    let (head, body) = raw.split_once("\r\n\r\n").expect("response head");
+    let mut lines = head.split("\r\n");
+    let status = lines
+        .next()
+        .and_then(|line| line.strip_prefix("HTTP/1.1 "))
+        .and_then(|line| line.get(..3))
+        .and_then(|code| code.parse().ok())
+        .expect("status line");
+    let headers = lines
+        .filter_map(|line| line.split_once(':'))
+        .map(|(name, value)| (name.to_string(), value.trim().to_string()))
+        .collect();
+    Reply { status, headers, body: body.to_string() }
+}
+
+
+/// # try_status
+///
+/// **Purpose:**
+/// Sends one `GET` request like `get`, tolerating a server that refuses or resets the
+/// connection.
+///
+/// **Parameters:**
+/// - `addr`: Server address.
+/// - `path`: Request target.
+///
+/// **Returns:**
+/// - The response status, or `None` if no status line could be read.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust
+/// while try_status(handle.local_addr(), "/") != Some(200) { thread::sleep(Duration::from_millis(20)); }
+/// ```
+pub fn try_status(addr: SocketAddr, path: &str) -> Option<u16> {
+    let mut stream = TcpStream::connect(addr).ok()?;
+    stream.set_read_timeout(Some(Duration::from_secs(10))).ok()?;
+    let _ = write!(stream, "GET {path} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n");
+    let mut raw = Vec::new();
+    let _ = stream.read_to_end(&mut raw);
+    String::from_utf8_lossy(&raw).strip_prefix("HTTP/1.1 ")?.get(..3)?.parse().ok()
+}
+
+
+/// # counted
+///
+/// **Purpose:**
+/// Builds a handler that answers `200` with a fixed body and cache policy, counting its calls.
+///
+/// **Parameters:**
+/// - `body`: Response body.
+/// - `cache_control`: `Cache-Control` value sent with it.
+///
+/// **Returns:**
+/// - The call counter and the handler.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust
+/// let (calls, handler) = counted("hello", "public, max-age=60");
+/// ```
+pub fn counted(
+    body: &'static str,
+    cache_control: &'static str,
+) -> (Arc<AtomicUsize>, impl Fn(&Request) -> Response + Send + Sync + 'static) {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let counter = Arc::clone(&calls);
+    let handler = move |_: &Request| {
+        counter.fetch_add(1, Ordering::SeqCst);
+        Response::new(200, "OK")
+            .with_header("Content-Type", "text/plain; charset=utf-8")
+            .with_header("Cache-Control", cache_control)
+            .with_body(body)
+    };
+    (calls, handler)
+}
+
+
+/// # calls
+///
+/// **Purpose:**
+/// Reads a handler's call counter.
+///
+/// **Parameters:**
+/// - `counter`: Counter returned by `counted`.
+///
+/// **Returns:**
+/// - The number of calls so far.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust
+/// assert_eq!(calls(&counter), 1);
+/// ```
+pub fn calls(counter: &AtomicUsize) -> usize {
+    counter.load(Ordering::SeqCst)
+}
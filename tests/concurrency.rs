@@ -0,0 +1,68 @@
+// ============================================================
+//  DAEGONICA SOFTWARE — tests/concurrency.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: Concurrency Limit Integration Tests
+//!
+//! **Purpose:**
+//! Checks connection shedding through a running server.
+//!
+//! **Context:**
+//! - Run with `cargo test --test concurrency`.
+//!
+//! **Responsibilities:**
+//! - Confirms that a connection past the limit is answered `503` with `Retry-After`.
+//! - Confirms that the slot is given back once the open connection closes.
+//! - Does NOT cover permit counting, which has unit tests in `concurrency`.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+mod common;
+
+use std::{
+    io::{Read, Write},
+    net::TcpStream,
+    thread,
+    time::Duration,
+};
+
+use common::{counted, serve, try_status};
+use server::{concurrency::ConcurrencyLimit, router::Router};
+
+
+#[test]
+fn connections_past_the_limit_are_shed_with_503() {
+    let (_, handler) = counted("hello", "no-store");
+    let limit = ConcurrencyLimit::new(1).retry_after(Duration::from_secs(3));
+    let handle = serve(Router::new().get("/", handler), |server| server.concurrency_limit(limit));
+
+    let mut held = TcpStream::connect(handle.local_addr()).unwrap();
+    write!(held, "GET / HTTP/1.1\r\nHost: localhost\r\n\r\n").unwrap();
+    held.read_exact(&mut [0; 12]).unwrap();
+
+    let mut shed = TcpStream::connect(handle.local_addr()).unwrap();
+    shed.set_read_timeout(Some(Duration::from_secs(10))).unwrap();
+    let mut reply = String::new();
+    shed.read_to_string(&mut reply).unwrap();
+    assert!(reply.starts_with("HTTP/1.1 503"), "{reply}");
+    assert!(reply.contains("Retry-After: 3\r\n"), "{reply}");
+
+    drop(held);
+    let mut status = None;
+    for _ in 0..50 {
+        status = try_status(handle.local_addr(), "/");
+        if status == Some(200) {
+            break;
+        }
+        thread::sleep(Duration::from_millis(20));
+    }
+    assert_eq!(status, Some(200), "the slot is freed once the held connection closes");
+    handle.shutdown();
+}
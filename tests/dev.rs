@@ -0,0 +1,90 @@
+// ============================================================
+//  DAEGONICA SOFTWARE — tests/dev.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: Dev Reload Integration Tests
+//!
+//! **Purpose:**
+//! Checks development live reload through a running server.
+//!
+//! **Context:**
+//! - Run with `cargo test --test dev`.
+//!
+//! **Responsibilities:**
+//! - Confirms that HTML pages carry the reload script and that a notification reaches an open
+//!   event stream.
+//! - Confirms that open event streams do not hold worker threads.
+//! - Does NOT cover the file watcher, which only feeds `DevReload::notify`.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+mod common;
+
+use std::{
+    io::{BufRead, BufReader, Write},
+    net::TcpStream,
+    time::Duration,
+};
+
+use common::{get, serve};
+use server::{dev::DevReload, request::Request, response::Response, router::Router};
+
+
+/// # page
+///
+/// **Purpose:**
+/// Answers with a small HTML page.
+///
+/// **Parameters:**
+/// - `_request`: Incoming request (unused).
+///
+/// **Returns:**
+/// - A `200` HTML response.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust
+/// let router = Router::new().get("/", page);
+/// ```
+fn page(_request: &Request) -> Response {
+    Response::new(200, "OK")
+        .with_header("Content-Type", "text/html; charset=utf-8")
+        .with_body("<html><body>hi</body></html>")
+}
+
+
+#[test]
+fn streams_are_notified_without_holding_the_only_worker() {
+    let reload = DevReload::new();
+    let handle = serve(Router::new().get("/", page), |server| server.workers(1).dev_reload(reload.clone()));
+
+    let mut events = TcpStream::connect(handle.local_addr()).unwrap();
+    events.set_read_timeout(Some(Duration::from_secs(10))).unwrap();
+    write!(events, "GET /__dev/reload HTTP/1.1\r\nHost: localhost\r\n\r\n").unwrap();
+    let mut reader = BufReader::new(events);
+    let mut line = String::new();
+    while line != "event: hello\n" {
+        line.clear();
+        assert!(reader.read_line(&mut line).unwrap() > 0, "stream closed");
+    }
+
+    let html = get(handle.local_addr(), "/", "");
+    assert!(html.body.contains("new EventSource(\"/__dev/reload\")"), "{}", html.body);
+    assert!(html.body.ends_with("</script>\n</body></html>"), "{}", html.body);
+
+    reload.notify();
+    while line != "event: reload\n" {
+        line.clear();
+        assert!(reader.read_line(&mut line).unwrap() > 0, "stream closed");
+    }
+    handle.shutdown();
+}
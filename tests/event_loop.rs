@@ -0,0 +1,64 @@
+// ============================================================
+//  DAEGONICA SOFTWARE — tests/event_loop.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: Event Loop Integration Tests
+//!
+//! **Purpose:**
+//! Checks that idle persistent connections are parked off the worker pool through a running
+//! server.
+//!
+//! **Context:**
+//! - Run with `cargo test --test event_loop`; only built where the event loop exists.
+//!
+//! **Responsibilities:**
+//! - Confirms that many idle keep-alive connections do not hold the only worker, and that a
+//!   parked connection is served again when its next request arrives.
+//! - Does NOT cover idle timeouts or draining, which have unit tests in `event_loop`.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+#![cfg(any(target_os = "linux", target_os = "macos", target_os = "freebsd"))]
+
+mod common;
+
+use std::{
+    io::{BufReader, Write},
+    net::TcpStream,
+    time::Duration,
+};
+
+use common::{counted, get, serve};
+use server::{response::Response, router::Router};
+
+
+#[test]
+fn idle_connections_do_not_hold_workers() {
+    let (_, handler) = counted("hello", "no-store");
+    let handle = serve(Router::new().get("/", handler), |server| server.workers(1).event_loop(true));
+
+    let idle: Vec<TcpStream> = (0..16)
+        .map(|_| {
+            let stream = TcpStream::connect(handle.local_addr()).unwrap();
+            stream.set_read_timeout(Some(Duration::from_secs(10))).unwrap();
+            (&stream).write_all(b"GET / HTTP/1.1\r\nHost: a\r\n\r\n").unwrap();
+            let response = Response::read_from(&mut BufReader::new(&stream), 1024).unwrap();
+            assert!(response.headers.get("Keep-Alive").is_some());
+            stream
+        })
+        .collect();
+
+    assert_eq!(get(handle.local_addr(), "/", "").status, 200);
+    for stream in &idle {
+        (&*stream).write_all(b"GET / HTTP/1.1\r\nHost: a\r\n\r\n").unwrap();
+        assert_eq!(Response::read_from(&mut BufReader::new(stream), 1024).unwrap().status, 200);
+    }
+    handle.shutdown();
+}
@@ -0,0 +1,52 @@
+// ============================================================
+//  DAEGONICA SOFTWARE — tests/health.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: Health Probe Integration Tests
+//!
+//! **Purpose:**
+//! Checks the liveness and readiness probes through a running server.
+//!
+//! **Context:**
+//! - Run with `cargo test --test health`.
+//!
+//! **Responsibilities:**
+//! - Confirms that the probes are answered ahead of the router, uncached.
+//! - Confirms that draining a clone of the handle turns readiness to `503` while liveness
+//!   and ordinary routes keep answering.
+//! - Does NOT cover readiness checks, which have unit tests in `health`.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+mod common;
+
+use common::{calls, counted, get, serve};
+use server::{health::Health, router::Router};
+
+
+#[test]
+fn draining_fails_readiness_but_not_liveness() {
+    let (counter, handler) = counted("hello", "no-store");
+    let health = Health::new();
+    let handle = serve(Router::new().get("/", handler).get("/readyz", |_| unreachable!()), |server| {
+        server.health(health.clone())
+    });
+
+    let ready = get(handle.local_addr(), "/readyz", "");
+    assert_eq!((ready.status, ready.body.as_str()), (200, "ready\n"));
+    assert_eq!(ready.header("Cache-Control"), Some("no-store"));
+
+    health.drain();
+    assert_eq!(get(handle.local_addr(), "/readyz", "").status, 503);
+    assert_eq!(get(handle.local_addr(), "/healthz", "").status, 200);
+    assert_eq!(get(handle.local_addr(), "/", "").status, 200);
+    assert_eq!(calls(&counter), 1);
+    handle.shutdown();
+}
@@ -0,0 +1,156 @@
+
+// ============================================================
+//  DAEGONICA SOFTWARE — tests/io_uring.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: io_uring Integration Tests
+//!
+//! **Purpose:**
+//! Checks that a running server serves requests, keep-alive connections, and files through
+//! its `io_uring`.
+//!
+//! **Context:**
+//! - Run with `cargo test --features io_uring --test io_uring`; only built on Linux with the
+//!   `io_uring` feature.
+//!
+//! **Responsibilities:**
+//! - Confirms that idle and slow connections do not hold the only worker, that a parked
+//!   connection is served again, that a head overdue in the ring is answered `408`, and that
+//!   spliced files and their ranges arrive intact.
+//! - Does NOT cover draining, which has unit tests in `uring`.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+#![cfg(all(feature = "io_uring", target_os = "linux"))]
+
+mod common;
+
+use std::{
+    env,
+    fs,
+    io::{BufReader, Read, Write},
+    net::TcpStream,
+    process,
+    time::Duration,
+};
+
+use common::{counted, get, serve};
+use server::{
+    request::Request,
+    response::Response,
+    router::Router,
+    server::Server,
+    static_files::StaticFiles,
+    timeouts::Timeouts,
+    vhost::VirtualHost,
+};
+
+
+#[test]
+fn idle_and_slow_connections_do_not_hold_workers() {
+    let (_, handler) = counted("hello", "no-store");
+    let handle = serve(Router::new().get("/", handler), |server| server.workers(1).io_uring(true));
+
+    let idle: Vec<TcpStream> = (0..16)
+        .map(|_| {
+            let stream = TcpStream::connect(handle.local_addr()).unwrap();
+            stream.set_read_timeout(Some(Duration::from_secs(10))).unwrap();
+            (&stream).write_all(b"GET / HTTP/1.1\r\nHost: a\r\n\r\n").unwrap();
+            let response = Response::read_from(&mut BufReader::new(&stream), 1024).unwrap();
+            assert!(response.headers.get("Keep-Alive").is_some());
+            stream
+        })
+        .collect();
+    let slow = TcpStream::connect(handle.local_addr()).unwrap();
+    slow.set_read_timeout(Some(Duration::from_secs(10))).unwrap();
+    (&slow).write_all(b"GET / HTTP/1.1\r\nHo").unwrap();
+
+    assert_eq!(get(handle.local_addr(), "/", "").status, 200);
+    (&slow).write_all(b"st: a\r\n\r\n").unwrap();
+    assert_eq!(Response::read_from(&mut BufReader::new(&slow), 1024).unwrap().status, 200);
+    for stream in &idle {
+        (&*stream).write_all(b"GET / HTTP/1.1\r\nHost: a\r\n\r\n").unwrap();
+        assert_eq!(Response::read_from(&mut BufReader::new(stream), 1024).unwrap().status, 200);
+    }
+    handle.shutdown();
+}
+
+
+#[test]
+fn heads_overdue_in_the_ring_are_answered_408() {
+    let (_, handler) = counted("hello", "no-store");
+    let timeouts = Timeouts::new().header(Duration::from_millis(200));
+    let handle = serve(Router::new().get("/", handler), |server| server.timeouts(timeouts).io_uring(true));
+
+    let mut slow = TcpStream::connect(handle.local_addr()).unwrap();
+    slow.set_read_timeout(Some(Duration::from_secs(10))).unwrap();
+    slow.write_all(b"GET / HTTP/1.1\r\nHost: a\r\n").unwrap();
+    let mut reply = String::new();
+    slow.read_to_string(&mut reply).unwrap();
+    assert!(reply.starts_with("HTTP/1.1 408"), "{reply}");
+
+    assert_eq!(get(handle.local_addr(), "/", "").status, 200);
+    handle.shutdown();
+}
+
+
+#[test]
+fn request_bodies_and_pipelined_requests_are_served_in_order() {
+    let router = Router::new().route("POST", "/echo", |request: &Request| Response::new(200, "OK").with_body(request.body.clone()));
+    let handle = serve(router, |server| server.io_uring(true));
+    let mut stream = TcpStream::connect(handle.local_addr()).unwrap();
+    stream.set_read_timeout(Some(Duration::from_secs(10))).unwrap();
+    stream
+        .write_all(b"POST /echo HTTP/1.1\r\nHost: a\r\nContent-Length: 5\r\n\r\nfirstPOST /echo HTTP/1.1\r\nHost: a\r\nContent-Length: 6\r\n\r\n")
+        .unwrap();
+    stream.write_all(b"second").unwrap();
+
+    let mut reader = BufReader::new(&stream);
+    let first = Response::read_from(&mut reader, 1024).unwrap();
+    let second = Response::read_from(&mut reader, 1024).unwrap();
+    assert_eq!(first.body, b"first");
+    assert_eq!(second.body, b"second");
+    handle.shutdown();
+}
+
+
+#[test]
+fn spliced_files_and_their_ranges_arrive_intact() {
+    let root = env::temp_dir().join(format!("io-uring-files-{}", process::id()));
+    fs::create_dir_all(&root).unwrap();
+    let contents: Vec<u8> = (0..=255u8).cycle().take(300 * 1024).collect();
+    fs::write(root.join("blob.bin"), &contents).unwrap();
+    let files = StaticFiles::new(&root).large_file_threshold(Some(64 * 1024)).sendfile(true);
+    let handle = Server::new("127.0.0.1:0")
+        .default_host(VirtualHost::new().router(Router::new()).files(files))
+        .io_uring(true)
+        .start()
+        .unwrap();
+
+    let fetch = |headers: &str| {
+        let mut stream = TcpStream::connect(handle.local_addr()).unwrap();
+        stream.set_read_timeout(Some(Duration::from_secs(10))).unwrap();
+        write!(stream, "GET /blob.bin HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n{headers}\r\n").unwrap();
+        let mut raw = Vec::new();
+        stream.read_to_end(&mut raw).unwrap();
+        let split = raw.windows(4).position(|window| window == b"\r\n\r\n").expect("response head");
+        let body = raw.split_off(split + 4);
+        (String::from_utf8(raw).unwrap(), body)
+    };
+    let (head, body) = fetch("");
+    let (range_head, range) = fetch("Range: bytes=100000-100999\r\n");
+    handle.shutdown();
+    fs::remove_dir_all(&root).unwrap();
+
+    assert!(head.contains(&format!("Content-Length: {}\r\n", contents.len())), "{head}");
+    assert!(body == contents, "the body differs from the file");
+    assert!(range_head.starts_with("HTTP/1.1 206"), "{range_head}");
+    assert_eq!(range, &contents[100_000..101_000]);
+}
@@ -0,0 +1,78 @@
+// ============================================================
+//  DAEGONICA SOFTWARE — tests/keep_alive.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: Keep-Alive Integration Tests
+//!
+//! **Purpose:**
+//! Checks persistent connection limits through a running server.
+//!
+//! **Context:**
+//! - Run with `cargo test --test keep_alive`.
+//!
+//! **Responsibilities:**
+//! - Confirms that responses advertise the limits in `Keep-Alive` and that the last allowed
+//!   request is answered with `Connection: close`.
+//! - Confirms that idle connections are closed after the idle timeout.
+//! - Does NOT cover header formatting, which has unit tests in `keep_alive`.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+mod common;
+
+use std::{
+    io::{BufReader, Read, Write},
+    net::TcpStream,
+    thread,
+    time::Duration,
+};
+
+use common::{counted, serve};
+use server::{keep_alive::KeepAlive, response::Response, router::Router};
+
+
+#[test]
+fn connections_close_after_the_request_limit() {
+    let (_, handler) = counted("hello", "no-store");
+    let keep_alive = KeepAlive::new().idle(Duration::from_secs(7)).max_requests(2);
+    let handle = serve(Router::new().get("/", handler), |server| server.keep_alive(keep_alive));
+
+    let stream = TcpStream::connect(handle.local_addr()).unwrap();
+    stream.set_read_timeout(Some(Duration::from_secs(10))).unwrap();
+    let mut reader = BufReader::new(&stream);
+    (&stream).write_all(b"GET / HTTP/1.1\r\nHost: a\r\n\r\n").unwrap();
+    let first = Response::read_from(&mut reader, 1024).unwrap();
+    assert_eq!(first.headers.get("Keep-Alive"), Some("timeout=7, max=1"));
+
+    (&stream).write_all(b"GET / HTTP/1.1\r\nHost: a\r\n\r\n").unwrap();
+    let last = Response::read_from(&mut reader, 1024).unwrap();
+    assert_eq!(last.status, 200);
+    assert_eq!(last.headers.get("Connection"), Some("close"));
+    assert_eq!(reader.read(&mut [0; 1]).unwrap(), 0, "the server closes the connection");
+    handle.shutdown();
+}
+
+
+#[test]
+fn idle_connections_are_closed() {
+    let (_, handler) = counted("hello", "no-store");
+    let keep_alive = KeepAlive::new().idle(Duration::from_millis(200));
+    let handle = serve(Router::new().get("/", handler), |server| server.keep_alive(keep_alive));
+
+    let stream = TcpStream::connect(handle.local_addr()).unwrap();
+    stream.set_read_timeout(Some(Duration::from_secs(10))).unwrap();
+    let mut reader = BufReader::new(&stream);
+    (&stream).write_all(b"GET / HTTP/1.1\r\nHost: a\r\n\r\n").unwrap();
+    assert_eq!(Response::read_from(&mut reader, 1024).unwrap().status, 200);
+
+    thread::sleep(Duration::from_millis(400));
+    assert_eq!(reader.read(&mut [0; 1]).unwrap(), 0, "the idle connection was closed");
+    handle.shutdown();
+}
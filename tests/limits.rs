@@ -0,0 +1,127 @@
+// ============================================================
+//  DAEGONICA SOFTWARE — tests/limits.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: Request Limit Integration Tests
+//!
+//! **Purpose:**
+//! Checks request size limits through a running server.
+//!
+//! **Context:**
+//! - Run with `cargo test --test limits`.
+//!
+//! **Responsibilities:**
+//! - Confirms that bodies over the cap are refused with `413` before the handler runs, and
+//!   that per-route overrides raise the cap.
+//! - Confirms that heads with too many or too large header fields are refused with `431`.
+//! - Confirms that request targets over the cap are refused with `414` before routing.
+//! - Does NOT cover the parsing itself, which has unit tests in `request`.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+mod common;
+
+use std::{
+    io::{Read, Write},
+    net::{SocketAddr, TcpStream},
+    time::Duration,
+};
+
+use common::{calls, counted, serve};
+use server::{limits::Limits, router::Router};
+
+
+/// # send
+///
+/// **Purpose:**
+/// Writes a raw request on a fresh connection and reads the status of the answer.
+///
+/// **Parameters:**
+/// - `addr`: Server address.
+/// - `raw`: Whole request, head and body.
+///
+/// **Returns:**
+/// - The response status code.
+///
+/// **Errors / Failures:**
+/// - Panics if the connection fails or the answer has no HTTP/1.1 status line.
+///
+/// **Examples:**
+/// ```rust
+/// assert_eq!(send(addr, "GET / HTTP/1.1\r\nHost: a\r\n\r\n"), 200);
+/// ```
+fn send(addr: SocketAddr, raw: &str) -> u16 {
+    let mut stream = TcpStream::connect(addr).unwrap();
+    stream.set_read_timeout(Some(Duration::from_secs(10))).unwrap();
+    stream.write_all(raw.as_bytes()).unwrap();
+    let mut reply = Vec::new();
+    let _ = stream.read_to_end(&mut reply);
+    String::from_utf8_lossy(&reply)
+        .strip_prefix("HTTP/1.1 ")
+        .and_then(|line| line.get(..3))
+        .and_then(|code| code.parse().ok())
+        .expect("status line")
+}
+
+
+#[test]
+fn bodies_over_the_cap_are_refused_before_the_handler_runs() {
+    let (small, small_handler) = counted("stored", "no-store");
+    let (large, large_handler) = counted("stored", "no-store");
+    let router = Router::new()
+        .route("POST", "/notes", small_handler)
+        .route("POST", "/upload/photo", large_handler);
+    let limits = Limits::new().max_body(8).route_body("/upload/**", 64);
+    let handle = serve(router, |server| server.limits(limits));
+
+    let post = |path: &str, body: &str| {
+        send(
+            handle.local_addr(),
+            &format!("POST {path} HTTP/1.1\r\nHost: a\r\nConnection: close\r\nContent-Length: {}\r\n\r\n{body}", body.len()),
+        )
+    };
+    assert_eq!(post("/notes", "tiny"), 200);
+    assert_eq!(post("/notes", &"x".repeat(32)), 413);
+    assert_eq!(post("/upload/photo", &"x".repeat(32)), 200);
+    assert_eq!(post("/upload/photo", &"x".repeat(128)), 413);
+    assert_eq!(calls(&small), 1);
+    assert_eq!(calls(&large), 1);
+    handle.shutdown();
+}
+
+
+#[test]
+fn oversized_header_sections_are_refused_with_431() {
+    let (counter, handler) = counted("hello", "no-store");
+    let limits = Limits::new().max_headers(4).max_header_bytes(256);
+    let handle = serve(Router::new().get("/", handler), |server| server.limits(limits));
+
+    let request = |headers: &str| {
+        send(handle.local_addr(), &format!("GET / HTTP/1.1\r\nHost: a\r\nConnection: close\r\n{headers}\r\n"))
+    };
+    assert_eq!(request("X-One: 1\r\n"), 200);
+    assert_eq!(request(&(0..8).map(|n| format!("X-{n}: {n}\r\n")).collect::<String>()), 431);
+    assert_eq!(request(&format!("X-Big: {}\r\n", "x".repeat(512))), 431);
+    assert_eq!(calls(&counter), 1);
+    handle.shutdown();
+}
+
+
+#[test]
+fn long_request_targets_are_refused_with_414() {
+    let (counter, handler) = counted("hello", "no-store");
+    let handle = serve(Router::new().get("/", handler), |server| server.limits(Limits::new().max_target(64)));
+
+    let request = |target: &str| send(handle.local_addr(), &format!("GET {target} HTTP/1.1\r\nHost: a\r\nConnection: close\r\n\r\n"));
+    assert_eq!(request("/"), 200);
+    assert_eq!(request(&format!("/?q={}", "x".repeat(4096))), 414);
+    assert_eq!(calls(&counter), 1);
+    handle.shutdown();
+}
@@ -0,0 +1,103 @@
+// ============================================================
+//  DAEGONICA SOFTWARE — tests/markdown.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: Markdown Integration Tests
+//!
+//! **Purpose:**
+//! Checks Markdown rendering of static files through a running server.
+//!
+//! **Context:**
+//! - Run with `cargo test --test markdown`; documents come from `tests/data/docs`.
+//!
+//! **Responsibilities:**
+//! - Confirms that `.md` files are served as HTML pages in the default or a custom wrapper,
+//!   titled by their first heading, and that `index.md` answers for its directory.
+//! - Confirms that without the mode the same files are served as they are.
+//! - Does NOT cover the Markdown syntax, which has unit tests in `markdown`.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+mod common;
+
+use common::get;
+use server::{
+    router::Router,
+    server::{Server, ServerHandle},
+    static_files::StaticFiles,
+    template::Template,
+    vhost::VirtualHost,
+};
+
+
+/// # serve_docs
+///
+/// **Purpose:**
+/// Starts a server whose default site serves `tests/data/docs`.
+///
+/// **Parameters:**
+/// - `configure`: Further static file settings.
+///
+/// **Returns:**
+/// - The running server's handle.
+///
+/// **Errors / Failures:**
+/// - Panics if the server cannot start.
+///
+/// **Examples:**
+/// ```rust
+/// let handle = serve_docs(StaticFiles::markdown);
+/// ```
+fn serve_docs(configure: impl FnOnce(StaticFiles) -> StaticFiles) -> ServerHandle {
+    let files = configure(StaticFiles::new(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data/docs")));
+    Server::new("127.0.0.1:0")
+        .default_host(VirtualHost::new().router(Router::new()).files(files))
+        .start()
+        .unwrap()
+}
+
+
+#[test]
+fn markdown_files_are_rendered_into_the_wrapper() {
+    let handle = serve_docs(StaticFiles::markdown);
+
+    let page = get(handle.local_addr(), "/setup.md", "");
+    assert_eq!(page.status, 200);
+    assert!(page.header("Content-Type").is_some_and(|kind| kind.starts_with("text/html")));
+    assert!(page.body.contains("<title>Setup</title>"), "{}", page.body);
+    assert!(page.body.contains("<h1 id=\"setup\">Setup</h1>"), "{}", page.body);
+    assert!(page.body.contains("<a href=\"#\">Next</a>"), "script links are neutralized");
+
+    let index = get(handle.local_addr(), "/", "");
+    assert!(index.body.contains("<p>Read <em>this</em> first.</p>"), "{}", index.body);
+    handle.shutdown();
+}
+
+
+#[test]
+fn custom_wrappers_replace_the_default_page() {
+    let wrapper = Template::parse("<article data-path=\"{{path}}\">{{{content}}}</article>").unwrap();
+    let handle = serve_docs(|files| files.markdown_template(wrapper));
+
+    let page = get(handle.local_addr(), "/setup.md", "");
+    assert!(page.body.starts_with("<article data-path=\"/setup.md\"><h1"), "{}", page.body);
+    handle.shutdown();
+}
+
+
+#[test]
+fn markdown_is_served_as_is_unless_enabled() {
+    let handle = serve_docs(|files| files);
+
+    let page = get(handle.local_addr(), "/setup.md", "");
+    assert_eq!(page.status, 200);
+    assert_eq!(page.body, "# Setup\n\n[Next](javascript:alert(1))\n");
+    handle.shutdown();
+}
@@ -0,0 +1,52 @@
+// ============================================================
+//  DAEGONICA SOFTWARE — tests/metrics.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: Metrics Integration Tests
+//!
+//! **Purpose:**
+//! Checks the Prometheus endpoint through a running server.
+//!
+//! **Context:**
+//! - Run with `cargo test --test metrics`.
+//!
+//! **Responsibilities:**
+//! - Confirms that the endpoint answers in the Prometheus text format with the traffic served
+//!   so far, and that a clone of the handle sees the same counters.
+//! - Does NOT cover rendering details, which have unit tests in `metrics`.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+mod common;
+
+use common::{counted, get, serve};
+use server::{metrics::{CONTENT_TYPE, Metrics}, router::Router};
+
+
+#[test]
+fn the_endpoint_exports_the_traffic_served() {
+    let (_, handler) = counted("hello", "no-store");
+    let metrics = Metrics::new().endpoint("/metrics");
+    let handle = serve(Router::new().get("/", handler), |server| server.metrics(metrics.clone()));
+
+    assert_eq!(get(handle.local_addr(), "/", "").status, 200);
+    assert_eq!(get(handle.local_addr(), "/missing", "").status, 404);
+    let scrape = get(handle.local_addr(), "/metrics", "");
+    assert_eq!(scrape.status, 200);
+    assert_eq!(scrape.header("Content-Type"), Some(CONTENT_TYPE));
+    assert!(scrape.body.contains("http_responses_total{class=\"2xx\"} 1\n"), "{}", scrape.body);
+    assert!(scrape.body.contains("http_responses_total{class=\"4xx\"} 1\n"), "{}", scrape.body);
+    assert!(scrape.body.contains("http_requests_in_flight 1\n"), "the scrape itself is in flight");
+
+    let stats = metrics.stats();
+    assert!(stats.connections_total >= 3);
+    assert!(stats.received_bytes > 0 && stats.sent_bytes > 0);
+    handle.shutdown();
+}
@@ -0,0 +1,68 @@
+// ============================================================
+//  DAEGONICA SOFTWARE — tests/peer_limit.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: Peer Limit Integration Tests
+//!
+//! **Purpose:**
+//! Checks the per-address connection cap through a running server.
+//!
+//! **Context:**
+//! - Run with `cargo test --test peer_limit`.
+//!
+//! **Responsibilities:**
+//! - Confirms that a second connection from the same address is answered `429` with `Retry-After`.
+//! - Confirms that the slot is given back once the open connection closes.
+//! - Does NOT cover per-address counting, which has unit tests in `peer_limit`.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+mod common;
+
+use std::{
+    io::{Read, Write},
+    net::TcpStream,
+    thread,
+    time::Duration,
+};
+
+use common::{counted, serve, try_status};
+use server::{peer_limit::PeerLimit, router::Router};
+
+
+#[test]
+fn a_second_connection_from_one_address_gets_429() {
+    let (_, handler) = counted("hello", "no-store");
+    let limit = PeerLimit::new(1).respond(true).retry_after(Duration::from_secs(3));
+    let handle = serve(Router::new().get("/", handler), |server| server.peer_limit(limit));
+
+    let mut held = TcpStream::connect(handle.local_addr()).unwrap();
+    write!(held, "GET / HTTP/1.1\r\nHost: localhost\r\n\r\n").unwrap();
+    held.read_exact(&mut [0; 12]).unwrap();
+
+    let mut shed = TcpStream::connect(handle.local_addr()).unwrap();
+    shed.set_read_timeout(Some(Duration::from_secs(10))).unwrap();
+    let mut reply = String::new();
+    shed.read_to_string(&mut reply).unwrap();
+    assert!(reply.starts_with("HTTP/1.1 429"), "{reply}");
+    assert!(reply.contains("Retry-After: 3\r\n"), "{reply}");
+
+    drop(held);
+    let mut status = None;
+    for _ in 0..50 {
+        status = try_status(handle.local_addr(), "/");
+        if status == Some(200) {
+            break;
+        }
+        thread::sleep(Duration::from_millis(20));
+    }
+    assert_eq!(status, Some(200), "the slot is freed once the held connection closes");
+    handle.shutdown();
+}
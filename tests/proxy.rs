@@ -0,0 +1,93 @@
+// ============================================================
+//  DAEGONICA SOFTWARE — tests/proxy.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: Reverse Proxy Integration Tests
+//!
+//! **Purpose:**
+//! Checks how the reverse proxy answers when an upstream response it buffers for the cache
+//! misbehaves.
+//!
+//! **Context:**
+//! - Run with `cargo test --test proxy`; the upstream is a loopback listener that replays a
+//!   canned response to every connection.
+//!
+//! **Responsibilities:**
+//! - Confirms that upstream responses within the size cap are relayed.
+//! - Confirms that oversized upstream responses are answered with `502` instead of buffered.
+//! - Does NOT cover streamed (uncached) exchanges or WebSocket and `CONNECT` tunnels.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+mod common;
+
+use std::{
+    io::{BufRead, BufReader, Write},
+    net::TcpListener,
+    thread,
+};
+
+use common::{get, serve};
+use server::{proxy::Proxy, router::Router};
+
+
+/// # upstream
+///
+/// **Purpose:**
+/// Starts a loopback upstream that reads each request head and answers with `reply`.
+///
+/// **Parameters:**
+/// - `reply`: Raw response bytes sent on every connection.
+///
+/// **Returns:**
+/// - The upstream address in `host:port` form.
+///
+/// **Errors / Failures:**
+/// - Panics if the listener cannot be bound.
+///
+/// **Examples:**
+/// ```rust
+/// let address = upstream("HTTP/1.1 204 No Content\r\n\r\n".into());
+/// ```
+fn upstream(reply: String) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap().to_string();
+    thread::spawn(move || {
+        for mut stream in listener.incoming().flatten() {
+            let mut reader = BufReader::new(&stream);
+            let mut line = String::new();
+            while reader.read_line(&mut line).is_ok_and(|read| read > 2) {
+                line.clear();
+            }
+            let _ = stream.write_all(reply.as_bytes());
+        }
+    });
+    address
+}
+
+
+#[test]
+fn relays_upstream_responses_within_the_cap() {
+    let address = upstream("HTTP/1.1 200 OK\r\nContent-Length: 5\r\n\r\nhello".into());
+    let handle = serve(Router::new(), |server| server.proxy(Proxy::new(address).max_response(1024)).cache_route("/"));
+
+    let reply = get(handle.local_addr(), "/", "");
+    assert_eq!((reply.status, reply.body.as_str()), (200, "hello"));
+}
+
+
+#[test]
+fn oversized_upstream_responses_are_bad_gateways() {
+    let body = "x".repeat(4096);
+    let address = upstream(format!("HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n{body}", body.len()));
+    let handle = serve(Router::new(), |server| server.proxy(Proxy::new(address).max_response(1024)).cache_route("/"));
+
+    assert_eq!(get(handle.local_addr(), "/", "").status, 502);
+}
@@ -0,0 +1,49 @@
+// ============================================================
+//  DAEGONICA SOFTWARE — tests/rate_limit.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: Rate Limit Integration Tests
+//!
+//! **Purpose:**
+//! Checks per-client rate limiting through a running server.
+//!
+//! **Context:**
+//! - Run with `cargo test --test rate_limit`; static files come from `tests/data/site`.
+//!
+//! **Responsibilities:**
+//! - Confirms that static files and routes draw on the same bucket, so files cannot be fetched
+//!   past the limit.
+//! - Confirms that refusals are `429` with a `Retry-After` delay.
+//! - Does NOT cover refill timing or eviction, which have unit tests in `rate_limit`.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+mod common;
+
+use common::{calls, counted, get, serve_files};
+use server::{rate_limit::RateLimit, router::Router};
+
+
+#[test]
+fn static_files_count_against_the_client_limit() {
+    let (counter, handler) = counted("routed", "no-store");
+    let router = Router::new().middleware(RateLimit::new(0.01, 2).middleware()).get("/routed", handler);
+    let handle = serve_files(router, |server| server);
+
+    assert_eq!(get(handle.local_addr(), "/page.txt", "").status, 200);
+    assert_eq!(get(handle.local_addr(), "/page.txt", "").status, 200);
+    for path in ["/page.txt", "/routed", "/missing"] {
+        let reply = get(handle.local_addr(), path, "");
+        assert_eq!(reply.status, 429, "{path}");
+        assert_eq!(reply.header("Retry-After"), Some("100"), "{path}");
+    }
+    assert_eq!(calls(&counter), 0);
+    handle.shutdown();
+}
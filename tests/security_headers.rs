@@ -0,0 +1,56 @@
+// ============================================================
+//  DAEGONICA SOFTWARE — tests/security_headers.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: Security Headers Integration Tests
+//!
+//! **Purpose:**
+//! Checks that router-level middleware covers every response of a site, using the security
+//! headers as the visible marker.
+//!
+//! **Context:**
+//! - Run with `cargo test --test security_headers`; static files come from `tests/data/site`.
+//!
+//! **Responsibilities:**
+//! - Confirms that routed responses, static files, and the not-found answer all carry the headers.
+//! - Confirms that per-route overrides apply to static files under their pattern.
+//! - Does NOT cover scoped middleware, which wraps only the routes of its scope.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+mod common;
+
+use common::{counted, get, serve_files};
+use server::{router::Router, security_headers::SecurityHeaders};
+
+
+#[test]
+fn routes_static_files_and_not_found_answers_are_hardened() {
+    let (_, handler) = counted("routed", "no-store");
+    let router = Router::new().middleware(SecurityHeaders::new().middleware()).get("/routed", handler);
+    let handle = serve_files(router, |server| server);
+
+    for (path, status) in [("/routed", 200), ("/page.txt", 200), ("/missing", 404)] {
+        let reply = get(handle.local_addr(), path, "");
+        assert_eq!(reply.status, status, "{path}");
+        assert_eq!(reply.header("X-Content-Type-Options"), Some("nosniff"), "{path}");
+        assert!(reply.header("X-Frame-Options").is_some(), "{path}");
+    }
+}
+
+
+#[test]
+fn route_overrides_apply_to_static_files() {
+    let headers = SecurityHeaders::new().route("/page.txt", SecurityHeaders::new().frame_options("SAMEORIGIN"));
+    let handle = serve_files(Router::new().middleware(headers.middleware()), |server| server);
+
+    assert_eq!(get(handle.local_addr(), "/page.txt", "").header("X-Frame-Options"), Some("SAMEORIGIN"));
+    assert_ne!(get(handle.local_addr(), "/missing", "").header("X-Frame-Options"), Some("SAMEORIGIN"));
+}
@@ -0,0 +1,63 @@
+// ============================================================
+//  DAEGONICA SOFTWARE — tests/tasks.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: Background Task Integration Tests
+//!
+//! **Purpose:**
+//! Checks that background tasks follow a running server's lifetime.
+//!
+//! **Context:**
+//! - Run with `cargo test --test tasks`.
+//!
+//! **Responsibilities:**
+//! - Confirms that registered tasks start with the server and have returned once it has
+//!   stopped.
+//! - Does NOT cover restarts or scheduling, which have unit tests in `tasks`.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+mod common;
+
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+        mpsc,
+    },
+    time::Duration,
+};
+
+use common::{counted, get, serve};
+use server::{
+    router::Router,
+    tasks::{Restart, Tasks},
+};
+
+
+#[test]
+fn tasks_start_and_stop_with_the_server() {
+    let (started, running) = mpsc::channel();
+    let stopped = Arc::new(AtomicBool::new(false));
+    let done = Arc::clone(&stopped);
+    let tasks = Tasks::new().spawn("watch", Restart::Never, move |ctx| {
+        started.send(ctx.name().to_string()).unwrap();
+        while ctx.sleep(Duration::from_secs(3600)) {}
+        done.store(true, Ordering::SeqCst);
+    });
+    let (_, handler) = counted("hello", "no-store");
+    let handle = serve(Router::new().get("/", handler), |server| server.tasks(tasks));
+
+    assert_eq!(running.recv_timeout(Duration::from_secs(10)).unwrap(), "watch");
+    assert_eq!(get(handle.local_addr(), "/", "").status, 200);
+    handle.shutdown();
+    handle.await_stopped().unwrap();
+    assert!(stopped.load(Ordering::SeqCst), "the task returned before the server finished stopping");
+}
@@ -0,0 +1,75 @@
+// ============================================================
+//  DAEGONICA SOFTWARE — tests/throttle.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: Throttle Integration Tests
+//!
+//! **Purpose:**
+//! Checks response bandwidth caps through a running server.
+//!
+//! **Context:**
+//! - Run with `cargo test --test throttle`.
+//!
+//! **Responsibilities:**
+//! - Confirms that a throttled route is delivered no faster than its rate, intact, while other
+//!   routes are not slowed.
+//! - Does NOT cover rate selection or pacing, which have unit tests in `throttle`.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+mod common;
+
+use std::time::{Duration, Instant};
+
+use common::{get, serve};
+use server::{request::Request, response::Response, router::Router, throttle::Throttle};
+
+
+/// # download
+///
+/// **Purpose:**
+/// Answers with a 1000-byte body.
+///
+/// **Parameters:**
+/// - `_request`: Incoming request (unused).
+///
+/// **Returns:**
+/// - A `200` response.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust
+/// let router = Router::new().get("/slow", download);
+/// ```
+fn download(_request: &Request) -> Response {
+    Response::new(200, "OK").with_body("x".repeat(1000))
+}
+
+
+#[test]
+fn throttled_routes_are_paced_to_their_rate() {
+    let router = Router::new().get("/slow", download).get("/fast", download);
+    let handle = serve(router, |server| server.throttle(Throttle::default().route("/slow", 2000)));
+
+    let started = Instant::now();
+    let fast = get(handle.local_addr(), "/fast", "");
+    let fast_time = started.elapsed();
+    assert_eq!(fast.body.len(), 1000);
+
+    let started = Instant::now();
+    let slow = get(handle.local_addr(), "/slow", "");
+    let slow_time = started.elapsed();
+    assert_eq!(slow.body, "x".repeat(1000));
+    assert!(slow_time >= Duration::from_millis(450), "{slow_time:?}");
+    assert!(fast_time < slow_time, "{fast_time:?} vs {slow_time:?}");
+    handle.shutdown();
+}
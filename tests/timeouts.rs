@@ -0,0 +1,54 @@
+// ============================================================
+//  DAEGONICA SOFTWARE — tests/timeouts.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: Timeout Integration Tests
+//!
+//! **Purpose:**
+//! Checks connection time limits through a running server.
+//!
+//! **Context:**
+//! - Run with `cargo test --test timeouts`.
+//!
+//! **Responsibilities:**
+//! - Confirms that a client too slow to send its request head is answered `408` and closed.
+//! - Confirms that a prompt client on the same server is still served.
+//! - Does NOT cover the socket-level deadlines, which have unit tests in `timeouts`.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+mod common;
+
+use std::{
+    io::{Read, Write},
+    net::TcpStream,
+    time::Duration,
+};
+
+use common::{counted, get, serve};
+use server::{router::Router, timeouts::Timeouts};
+
+
+#[test]
+fn slow_request_heads_are_answered_408() {
+    let (_, handler) = counted("hello", "no-store");
+    let timeouts = Timeouts::new().header(Duration::from_millis(200));
+    let handle = serve(Router::new().get("/", handler), |server| server.timeouts(timeouts));
+
+    let mut slow = TcpStream::connect(handle.local_addr()).unwrap();
+    slow.set_read_timeout(Some(Duration::from_secs(10))).unwrap();
+    write!(slow, "GET / HTTP/1.1\r\nHost: localhost\r\n").unwrap();
+    let mut reply = String::new();
+    slow.read_to_string(&mut reply).unwrap();
+    assert!(reply.starts_with("HTTP/1.1 408"), "{reply}");
+
+    assert_eq!(get(handle.local_addr(), "/", "").status, 200);
+    handle.shutdown();
+}
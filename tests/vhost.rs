@@ -0,0 +1,73 @@
+// ============================================================
+//  DAEGONICA SOFTWARE — tests/vhost.rs
+//  Part of the Daegonica Software Rust Ecosystem
+// ============================================================
+
+//! # Daegonica Module: Virtual Host Integration Tests
+//!
+//! **Purpose:**
+//! Checks that the server picks the site named by each request's `Host` header.
+//!
+//! **Context:**
+//! - Run with `cargo test --test vhost`; every site answers `/` with its own name.
+//!
+//! **Responsibilities:**
+//! - Confirms that names match without regard to case or port.
+//! - Confirms that subdomain patterns capture their labels as request parameters.
+//! - Confirms that unknown and missing names fall back to the default site.
+//! - Does NOT cover pattern edge cases, which have unit tests in `vhost`.
+//!
+//! **Author:** Daegonica Software
+//! **Version:** 0.1.0
+//! **Last Updated:** 2026-10-14
+//!
+//! ---------------------------------------------------------------
+//! This file is part of the Daegonica Software codebase.
+//! ---------------------------------------------------------------
+
+mod common;
+
+use common::{get_host, serve};
+use server::{request::Request, response::Response, router::Router, vhost::VirtualHost};
+
+
+/// # named
+///
+/// **Purpose:**
+/// Builds a router answering `/` with `name` and any captured `tenant` parameter.
+///
+/// **Parameters:**
+/// - `name`: Site name to echo.
+///
+/// **Returns:**
+/// - The router.
+///
+/// **Errors / Failures:**
+/// None.
+///
+/// **Examples:**
+/// ```rust
+/// let site = VirtualHost::new().name("example.com").router(named("example"));
+/// ```
+fn named(name: &'static str) -> Router {
+    Router::new().get("/", move |request: &Request| {
+        Response::new(200, "OK").with_body(format!("{name}{}", request.param("tenant").map(|tenant| format!(":{tenant}")).unwrap_or_default()))
+    })
+}
+
+
+#[test]
+fn requests_reach_the_site_their_host_names() {
+    let handle = serve(named("default"), |server| {
+        server
+            .vhost(VirtualHost::new().name("example.com").router(named("example")))
+            .vhost(VirtualHost::new().name(":tenant.tenants.test").router(named("tenant")))
+    });
+    let addr = handle.local_addr();
+
+    assert_eq!(get_host(addr, "example.com", "/", "").body, "example");
+    assert_eq!(get_host(addr, "EXAMPLE.com:8080", "/", "").body, "example");
+    assert_eq!(get_host(addr, "acme.tenants.test", "/", "").body, "tenant:acme");
+    assert_eq!(get_host(addr, "deep.acme.tenants.test", "/", "").body, "default");
+    assert_eq!(get_host(addr, "other.test", "/", "").body, "default");
+}